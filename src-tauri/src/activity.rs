@@ -0,0 +1,330 @@
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use git2::{Repository, Sort, StatusOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::background_runs::{background_runs_path, read_background_runs};
+use crate::claude::workspace_thread_activity;
+use crate::claude_tasks::session_tasks_updated_since;
+use crate::git::status_for_workdir;
+use crate::git_utils::resolve_git_root;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+use crate::utils::now_millis;
+
+const DEFAULT_WINDOW_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 500;
+const MAX_COMMITS: usize = 200;
+const TITLE_CHARS: usize = 80;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum ActivityItem {
+    #[serde(rename_all = "camelCase")]
+    Commit {
+        sha: String,
+        summary: String,
+        author: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Thread {
+        thread_id: String,
+        title: Option<String>,
+        message_count: Option<i64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Task {
+        thread_id: String,
+        task_id: String,
+        subject: String,
+        status: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Run {
+        run_id: String,
+        thread_id: String,
+        status: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    File { path: String, status: String },
+}
+
+/// One line of a workspace's activity feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ActivityEntry {
+    /// Milliseconds since the epoch.
+    pub(crate) timestamp: i64,
+    #[serde(flatten)]
+    pub(crate) item: ActivityItem,
+}
+
+fn truncate_title(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= TITLE_CHARS {
+        return line.to_string();
+    }
+    let mut title: String = line.chars().take(TITLE_CHARS - 1).collect();
+    title.push('…');
+    title
+}
+
+fn commit_activity(repo: &Repository, since: i64) -> Vec<ActivityEntry> {
+    let Ok(mut revwalk) = repo.revwalk() else {
+        return Vec::new();
+    };
+    if revwalk.push_head().is_err() || revwalk.set_sorting(Sort::TIME).is_err() {
+        return Vec::new();
+    }
+    let mut entries = Vec::new();
+    for oid in revwalk.flatten().take(MAX_COMMITS) {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        let timestamp = commit.time().seconds() * 1000;
+        if timestamp < since {
+            break;
+        }
+        entries.push(ActivityEntry {
+            timestamp,
+            item: ActivityItem::Commit {
+                sha: oid.to_string(),
+                summary: commit.summary().unwrap_or("").to_string(),
+                author: commit.author().name().unwrap_or("").to_string(),
+            },
+        });
+    }
+    entries
+}
+
+/// Uncommitted files, dated by their modification time.
+fn file_activity(repo: &Repository, root: &Path, since: i64) -> Vec<ActivityEntry> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let Ok(statuses) = repo.statuses(Some(&mut options)) else {
+        return Vec::new();
+    };
+    statuses
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path()?.to_string();
+            let status = status_for_workdir(entry.status())?;
+            let timestamp = std::fs::metadata(root.join(&path))
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                // Deleted files have no mtime; show them as current.
+                .unwrap_or_else(now_millis);
+            (timestamp >= since).then(|| ActivityEntry {
+                timestamp,
+                item: ActivityItem::File {
+                    path,
+                    status: status.to_string(),
+                },
+            })
+        })
+        .collect()
+}
+
+fn git_activity(entry: &WorkspaceEntry, since: i64) -> Vec<ActivityEntry> {
+    let Ok(root) = resolve_git_root(entry) else {
+        return Vec::new();
+    };
+    let Ok(repo) = Repository::open(&root) else {
+        return Vec::new();
+    };
+    let mut entries = commit_activity(&repo, since);
+    entries.extend(file_activity(&repo, &root, since));
+    entries
+}
+
+/// Threads updated since `since`, plus task changes across all of the
+/// workspace's threads.
+fn thread_activity(entry: &WorkspaceEntry, since: i64) -> Vec<ActivityEntry> {
+    let mut entries = Vec::new();
+    for thread in workspace_thread_activity(entry) {
+        for (task, timestamp) in session_tasks_updated_since(&thread.thread_id, since) {
+            entries.push(ActivityEntry {
+                timestamp,
+                item: ActivityItem::Task {
+                    thread_id: thread.thread_id.clone(),
+                    task_id: task.id,
+                    subject: task.subject,
+                    status: task.status,
+                },
+            });
+        }
+        if thread.updated_at >= since {
+            entries.push(ActivityEntry {
+                timestamp: thread.updated_at,
+                item: ActivityItem::Thread {
+                    title: thread.first_prompt.as_deref().map(truncate_title),
+                    thread_id: thread.thread_id,
+                    message_count: thread.message_count,
+                },
+            });
+        }
+    }
+    entries
+}
+
+fn run_activity(settings_path: &Path, workspace_id: &str, since: i64) -> Vec<ActivityEntry> {
+    let Ok(runs) = background_runs_path(settings_path).and_then(|path| read_background_runs(&path))
+    else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    for run in runs
+        .into_values()
+        .filter(|run| run.workspace_id == workspace_id)
+    {
+        let mut push = |timestamp: i64, status: &str| {
+            if timestamp >= since {
+                entries.push(ActivityEntry {
+                    timestamp,
+                    item: ActivityItem::Run {
+                        run_id: run.id.clone(),
+                        thread_id: run.thread_id.clone(),
+                        status: status.to_string(),
+                    },
+                });
+            }
+        };
+        push(run.started_at, "started");
+        if let Some(finished_at) = run.finished_at {
+            push(finished_at, &run.status);
+        }
+    }
+    entries
+}
+
+/// Newest first, capped at `limit`.
+fn merge_activity(mut entries: Vec<ActivityEntry>, limit: usize) -> Vec<ActivityEntry> {
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    entries.truncate(limit);
+    entries
+}
+
+#[tauri::command]
+pub(crate) async fn get_workspace_activity(
+    workspace_id: String,
+    since: Option<i64>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ActivityEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_workspace_activity",
+            json!({ "workspaceId": workspace_id, "since": since, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let settings_path = state.settings_path.clone();
+    let since = since.unwrap_or_else(|| now_millis() - DEFAULT_WINDOW_MS);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    tokio::task::spawn_blocking(move || {
+        let mut entries = git_activity(&entry, since);
+        entries.extend(thread_activity(&entry, since));
+        entries.extend(run_activity(&settings_path, &entry.id, since));
+        merge_activity(entries, limit)
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    #[test]
+    fn merge_activity_orders_newest_first() {
+        let file = |timestamp, path: &str| ActivityEntry {
+            timestamp,
+            item: ActivityItem::File {
+                path: path.to_string(),
+                status: "M".to_string(),
+            },
+        };
+        let merged = merge_activity(vec![file(1, "a"), file(3, "b"), file(2, "c")], 2);
+        assert_eq!(
+            merged
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+        let value = serde_json::to_value(&merged[0]).unwrap();
+        assert_eq!(value["kind"], "file");
+        assert_eq!(value["path"], "b");
+        assert_eq!(value["timestamp"], 3);
+    }
+
+    #[test]
+    fn git_activity_lists_commits_and_changed_files() {
+        let root = std::env::temp_dir().join(format!("activity-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let repo = Repository::init(&root).unwrap();
+        std::fs::write(root.join("README.md"), "hello").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Ana", "ana@example.com").unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Initial commit",
+            &tree,
+            &[],
+        )
+        .unwrap();
+        std::fs::write(root.join("notes.md"), "draft").unwrap();
+
+        let commits = commit_activity(&repo, 0);
+        assert_eq!(commits.len(), 1);
+        assert!(matches!(
+            &commits[0].item,
+            ActivityItem::Commit { summary, author, .. }
+                if summary == "Initial commit" && author == "Ana"
+        ));
+        assert!(commit_activity(&repo, i64::MAX).is_empty());
+
+        let files = file_activity(&repo, &root, 0);
+        assert!(matches!(
+            &files[..],
+            [ActivityEntry { item: ActivityItem::File { path, .. }, .. }] if path == "notes.md"
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn thread_titles_are_single_line_and_short() {
+        assert_eq!(
+            truncate_title("Fix the build\nmore detail"),
+            "Fix the build"
+        );
+        let long = "x".repeat(200);
+        assert_eq!(truncate_title(&long).chars().count(), TITLE_CHARS);
+    }
+}
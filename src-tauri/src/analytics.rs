@@ -0,0 +1,640 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, Timelike};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::list_session_files;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+/// Tool results the CLI writes when a tool call was blocked or rejected.
+const DENIAL_MARKERS: &[&str] = &[
+    "doesn't want to proceed",
+    "haven't granted",
+    "permission to use",
+    "was denied",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AnalyticsRange {
+    Day,
+    #[default]
+    Week,
+    Month,
+    All,
+}
+
+impl AnalyticsRange {
+    fn days(self) -> Option<i64> {
+        match self {
+            AnalyticsRange::Day => Some(1),
+            AnalyticsRange::Week => Some(7),
+            AnalyticsRange::Month => Some(30),
+            AnalyticsRange::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ToolUsage {
+    pub(crate) tool: String,
+    pub(crate) calls: u32,
+    pub(crate) errors: u32,
+    pub(crate) denials: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageAnalytics {
+    pub(crate) range: AnalyticsRange,
+    /// Start of the range in ms; `None` for all time
+    pub(crate) since: Option<i64>,
+    pub(crate) sessions: u32,
+    pub(crate) turns: u32,
+    pub(crate) average_turn_ms: Option<i64>,
+    pub(crate) tool_calls: u32,
+    /// Sorted by call count, most used first
+    pub(crate) tools: Vec<ToolUsage>,
+    pub(crate) permission_denials: u32,
+    /// Denied tool calls as a fraction of all tool calls
+    pub(crate) permission_denial_rate: f64,
+    pub(crate) edits: u32,
+    pub(crate) edits_accepted: u32,
+    pub(crate) edits_rewound: u32,
+}
+
+/// Turns and messages bucketed by local day of week and hour of day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageHeatmap {
+    pub(crate) range: AnalyticsRange,
+    /// Start of the range in ms; `None` for all time
+    pub(crate) since: Option<i64>,
+    /// `turns[day][hour]`, with day 0 = Monday
+    pub(crate) turns: Vec<Vec<u32>>,
+    /// Prompts plus assistant replies, laid out like `turns`
+    pub(crate) messages: Vec<Vec<u32>>,
+    pub(crate) max_turns: u32,
+    pub(crate) max_messages: u32,
+}
+
+impl UsageHeatmap {
+    fn new(range: AnalyticsRange, since: Option<i64>) -> Self {
+        Self {
+            range,
+            since,
+            turns: vec![vec![0; 24]; 7],
+            messages: vec![vec![0; 24]; 7],
+            ..Self::default()
+        }
+    }
+}
+
+/// A `rewind_thread_files` call, kept so rewound edits can be told apart from
+/// accepted ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RewindRecord {
+    pub(crate) thread_id: String,
+    pub(crate) message_id: String,
+    pub(crate) at: i64,
+}
+
+/// workspace_id -> rewinds, oldest first
+pub(crate) type RewindLog = HashMap<String, Vec<RewindRecord>>;
+
+pub(crate) fn rewinds_path(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|path| path.join("rewinds.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+pub(crate) fn read_rewinds(path: &Path) -> Result<RewindLog, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+pub(crate) fn write_rewinds(path: &Path, data: &RewindLog) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+/// Remember a successful file rewind for the analytics panel.
+pub(crate) fn record_rewind(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    message_id: &str,
+) {
+    let result = rewinds_path(state).and_then(|path| {
+        let mut log = read_rewinds(&path)?;
+        log.entry(workspace_id.to_string())
+            .or_default()
+            .push(RewindRecord {
+                thread_id: thread_id.to_string(),
+                message_id: message_id.to_string(),
+                at: now_millis(),
+            });
+        write_rewinds(&path, &log)
+    });
+    if let Err(error) = result {
+        tracing::warn!("[analytics] Failed to record rewind: {error}");
+    }
+}
+
+#[derive(Default)]
+struct SessionStats {
+    turns: u32,
+    turn_ms_total: i64,
+    timed_turns: u32,
+    tools: HashMap<String, ToolUsage>,
+    denials: u32,
+    edits: u32,
+    edits_rewound: u32,
+    counted: bool,
+}
+
+fn line_timestamp(value: &Value) -> Option<i64> {
+    let raw = value.get("timestamp").and_then(Value::as_str)?;
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.timestamp_millis())
+        .ok()
+}
+
+fn content_blocks(value: &Value) -> &[Value] {
+    value
+        .get("message")
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+fn tool_result_text(block: &Value) -> String {
+    match block.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn is_denial(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    DENIAL_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+/// A user line that starts a turn, as opposed to one carrying tool results.
+fn is_prompt(value: &Value) -> bool {
+    if value.get("isMeta").and_then(Value::as_bool) == Some(true) {
+        return false;
+    }
+    match value
+        .get("message")
+        .and_then(|message| message.get("content"))
+    {
+        Some(Value::String(text)) => !text.trim().is_empty(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .any(|block| block.get("type").and_then(Value::as_str) == Some("text")),
+        _ => false,
+    }
+}
+
+fn close_turn(start: Option<i64>, end: Option<i64>, stats: &mut SessionStats) {
+    if let (Some(start), Some(end)) = (start, end) {
+        if end > start {
+            stats.turn_ms_total += end - start;
+            stats.timed_turns += 1;
+        }
+    }
+}
+
+/// Fold one session transcript into `stats`. Lines before `since` are skipped;
+/// edits made at or after a rewound message count as rewound.
+fn scan_session<R: BufRead>(
+    reader: R,
+    since: Option<i64>,
+    rewound_messages: &[&str],
+    stats: &mut SessionStats,
+) {
+    // tool_use id -> tool name
+    let mut pending_tools: HashMap<String, String> = HashMap::new();
+    let mut turn_start: Option<i64> = None;
+    let mut last_reply: Option<i64> = None;
+    let mut rewound = false;
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let uuid = value.get("uuid").and_then(Value::as_str).unwrap_or("");
+        if !uuid.is_empty() && rewound_messages.contains(&uuid) {
+            rewound = true;
+        }
+        let timestamp = line_timestamp(&value);
+        if let (Some(since), Some(timestamp)) = (since, timestamp) {
+            if timestamp < since {
+                continue;
+            }
+        }
+        match value.get("type").and_then(Value::as_str) {
+            Some("assistant") => {
+                for block in content_blocks(&value) {
+                    if block.get("type").and_then(Value::as_str) != Some("tool_use") {
+                        continue;
+                    }
+                    let name = block
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                        .to_string();
+                    let id = block.get("id").and_then(Value::as_str).unwrap_or("");
+                    let usage = stats
+                        .tools
+                        .entry(name.clone())
+                        .or_insert_with(|| ToolUsage {
+                            tool: name.clone(),
+                            ..ToolUsage::default()
+                        });
+                    usage.calls += 1;
+                    pending_tools.insert(id.to_string(), name);
+                }
+                last_reply = timestamp.or(last_reply);
+                stats.counted = true;
+            }
+            Some("user") => {
+                if is_prompt(&value) {
+                    close_turn(turn_start, last_reply, stats);
+                    stats.turns += 1;
+                    turn_start = timestamp;
+                    last_reply = None;
+                    stats.counted = true;
+                    continue;
+                }
+                for block in content_blocks(&value) {
+                    if block.get("type").and_then(Value::as_str) != Some("tool_result") {
+                        continue;
+                    }
+                    let id = block
+                        .get("tool_use_id")
+                        .and_then(Value::as_str)
+                        .unwrap_or("");
+                    let Some(name) = pending_tools.remove(id) else {
+                        continue;
+                    };
+                    let is_error = block.get("is_error").and_then(Value::as_bool) == Some(true);
+                    let usage = stats.tools.entry(name.clone()).or_default();
+                    if is_error {
+                        usage.errors += 1;
+                        if is_denial(&tool_result_text(block)) {
+                            usage.denials += 1;
+                            stats.denials += 1;
+                        }
+                    } else if EDIT_TOOLS.contains(&name.as_str()) {
+                        stats.edits += 1;
+                        if rewound {
+                            stats.edits_rewound += 1;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    close_turn(turn_start, last_reply, stats);
+}
+
+/// (day of week from Monday, hour) of a transcript line in `offset`'s local time.
+fn heatmap_slot(value: &Value, offset: &FixedOffset) -> Option<(usize, usize)> {
+    let raw = value.get("timestamp").and_then(Value::as_str)?;
+    let local = DateTime::parse_from_rfc3339(raw).ok()?.with_timezone(offset);
+    Some((
+        local.weekday().num_days_from_monday() as usize,
+        local.hour() as usize,
+    ))
+}
+
+/// Add one session's prompts and replies to `heatmap`. Replies are counted per
+/// message id, since the CLI writes each content block of a reply on its own line.
+fn fold_heatmap<R: BufRead>(reader: R, offset: &FixedOffset, heatmap: &mut UsageHeatmap) {
+    let mut replies: HashSet<String> = HashSet::new();
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if let (Some(since), Some(timestamp)) = (heatmap.since, line_timestamp(&value)) {
+            if timestamp < since {
+                continue;
+            }
+        }
+        let Some((day, hour)) = heatmap_slot(&value, offset) else {
+            continue;
+        };
+        match value.get("type").and_then(Value::as_str) {
+            Some("user") if is_prompt(&value) => {
+                heatmap.turns[day][hour] += 1;
+                heatmap.messages[day][hour] += 1;
+            }
+            Some("assistant") => {
+                let id = value
+                    .get("message")
+                    .and_then(|message| message.get("id"))
+                    .and_then(Value::as_str);
+                if id.is_none_or(|id| replies.insert(id.to_string())) {
+                    heatmap.messages[day][hour] += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn finish_heatmap(mut heatmap: UsageHeatmap) -> UsageHeatmap {
+    heatmap.max_turns = heatmap.turns.iter().flatten().copied().max().unwrap_or(0);
+    heatmap.max_messages = heatmap.messages.iter().flatten().copied().max().unwrap_or(0);
+    heatmap
+}
+
+fn build_analytics(
+    range: AnalyticsRange,
+    since: Option<i64>,
+    sessions: Vec<SessionStats>,
+) -> UsageAnalytics {
+    let mut analytics = UsageAnalytics {
+        range,
+        since,
+        ..UsageAnalytics::default()
+    };
+    let mut tools: HashMap<String, ToolUsage> = HashMap::new();
+    let mut turn_ms_total = 0;
+    let mut timed_turns = 0;
+    for session in sessions.into_iter().filter(|session| session.counted) {
+        analytics.sessions += 1;
+        analytics.turns += session.turns;
+        analytics.permission_denials += session.denials;
+        analytics.edits += session.edits;
+        analytics.edits_rewound += session.edits_rewound;
+        turn_ms_total += session.turn_ms_total;
+        timed_turns += session.timed_turns;
+        for (name, usage) in session.tools {
+            let total = tools.entry(name.clone()).or_insert_with(|| ToolUsage {
+                tool: name,
+                ..ToolUsage::default()
+            });
+            total.calls += usage.calls;
+            total.errors += usage.errors;
+            total.denials += usage.denials;
+        }
+    }
+    analytics.edits_accepted = analytics.edits - analytics.edits_rewound;
+    analytics.average_turn_ms = (timed_turns > 0).then(|| turn_ms_total / i64::from(timed_turns));
+    analytics.tools = tools.into_values().collect();
+    analytics
+        .tools
+        .sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.tool.cmp(&b.tool)));
+    analytics.tool_calls = analytics.tools.iter().map(|usage| usage.calls).sum();
+    analytics.permission_denial_rate = if analytics.tool_calls > 0 {
+        f64::from(analytics.permission_denials) / f64::from(analytics.tool_calls)
+    } else {
+        0.0
+    };
+    analytics
+}
+
+/// Aggregate tool usage, turn timing, permission denials and edit outcomes
+/// from a workspace's session files.
+#[tauri::command]
+pub(crate) async fn usage_analytics(
+    workspace_id: String,
+    range: Option<AnalyticsRange>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<UsageAnalytics, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "usage_analytics",
+            json!({ "workspaceId": workspace_id, "range": range }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let rewinds = rewinds_path(&state)
+        .ok()
+        .and_then(|path| read_rewinds(&path).ok())
+        .and_then(|mut log| log.remove(&workspace_id))
+        .unwrap_or_default();
+    let range = range.unwrap_or_default();
+    let since = range.days().map(|days| now_millis() - days * DAY_MS);
+
+    tokio::task::spawn_blocking(move || {
+        let mut sessions = Vec::new();
+        for (session_id, path, mtime) in list_session_files(&entry) {
+            if since.is_some_and(|since| mtime < since) {
+                continue;
+            }
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            let rewound_messages = rewinds
+                .iter()
+                .filter(|record| record.thread_id == session_id)
+                .map(|record| record.message_id.as_str())
+                .collect::<Vec<_>>();
+            let mut stats = SessionStats::default();
+            scan_session(BufReader::new(file), since, &rewound_messages, &mut stats);
+            sessions.push(stats);
+        }
+        build_analytics(range, since, sessions)
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+/// Turns and messages per hour of day and day of week across all workspaces,
+/// for an activity heatmap.
+#[tauri::command]
+pub(crate) async fn usage_heatmap(
+    range: Option<AnalyticsRange>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<UsageHeatmap, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "usage_heatmap", json!({ "range": range }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entries: Vec<_> = state.workspaces.lock().await.values().cloned().collect();
+    let range = range.unwrap_or_default();
+    let since = range.days().map(|days| now_millis() - days * DAY_MS);
+    let offset = *Local::now().offset();
+
+    tokio::task::spawn_blocking(move || {
+        let mut heatmap = UsageHeatmap::new(range, since);
+        // Workspaces sharing a path share a project dir; count its sessions once.
+        let mut seen = HashSet::new();
+        for entry in &entries {
+            for (_, path, mtime) in list_session_files(entry) {
+                if since.is_some_and(|since| mtime < since) || !seen.insert(path.clone()) {
+                    continue;
+                }
+                let Ok(file) = File::open(&path) else {
+                    continue;
+                };
+                fold_heatmap(BufReader::new(file), &offset, &mut heatmap);
+            }
+        }
+        finish_heatmap(heatmap)
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn transcript(lines: &[Value]) -> Cursor<String> {
+        Cursor::new(
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn tool_use(id: &str, name: &str, ts: &str) -> Value {
+        json!({
+            "type": "assistant",
+            "timestamp": ts,
+            "message": { "content": [{ "type": "tool_use", "id": id, "name": name, "input": {} }] }
+        })
+    }
+
+    fn tool_result(id: &str, is_error: bool, text: &str, uuid: &str) -> Value {
+        json!({
+            "type": "user",
+            "uuid": uuid,
+            "timestamp": "2026-01-01T00:00:05Z",
+            "message": { "content": [{ "type": "tool_result", "tool_use_id": id, "is_error": is_error, "content": text }] }
+        })
+    }
+
+    #[test]
+    fn scan_session_counts_tools_turns_denials_and_rewinds() {
+        let lines = [
+            json!({ "type": "user", "uuid": "u1", "timestamp": "2026-01-01T00:00:00Z", "message": { "content": "Fix the bug" } }),
+            tool_use("t1", "Read", "2026-01-01T00:00:02Z"),
+            tool_result("t1", false, "file contents", "r1"),
+            tool_use("t2", "Bash", "2026-01-01T00:00:03Z"),
+            tool_result(
+                "t2",
+                true,
+                "The user doesn't want to proceed with this tool use.",
+                "r2",
+            ),
+            tool_use("t3", "Edit", "2026-01-01T00:00:04Z"),
+            tool_result("t3", false, "ok", "r3"),
+            json!({ "type": "assistant", "timestamp": "2026-01-01T00:00:10Z", "message": { "content": [{ "type": "text", "text": "Done" }] } }),
+            json!({ "type": "user", "uuid": "u2", "timestamp": "2026-01-01T00:01:00Z", "message": { "content": [{ "type": "text", "text": "Now the tests" }] } }),
+            tool_use("t4", "Write", "2026-01-01T00:01:04Z"),
+            tool_result("t4", false, "ok", "r4"),
+        ];
+        let mut stats = SessionStats::default();
+        scan_session(transcript(&lines), None, &["u2"], &mut stats);
+
+        assert_eq!(stats.turns, 2);
+        assert_eq!(stats.timed_turns, 2);
+        assert_eq!(stats.turn_ms_total, 10_000 + 4_000);
+        assert_eq!(stats.tools["Bash"].denials, 1);
+        assert_eq!(stats.tools["Read"].calls, 1);
+        assert_eq!(stats.denials, 1);
+        assert_eq!(stats.edits, 2);
+        assert_eq!(stats.edits_rewound, 1);
+
+        let analytics = build_analytics(AnalyticsRange::All, None, vec![stats]);
+        assert_eq!(analytics.sessions, 1);
+        assert_eq!(analytics.tool_calls, 4);
+        assert_eq!(analytics.edits_accepted, 1);
+        assert_eq!(analytics.average_turn_ms, Some(7_000));
+        assert!((analytics.permission_denial_rate - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn heatmap_buckets_prompts_and_replies_in_local_time() {
+        let reply = |id: &str, ts: &str| {
+            json!({ "type": "assistant", "timestamp": ts, "message": { "id": id, "content": [] } })
+        };
+        let lines = [
+            // Monday 2026-01-05 23:30 UTC is Tuesday 01:30 at UTC+2.
+            json!({ "type": "user", "timestamp": "2026-01-05T23:30:00Z", "message": { "content": "Fix it" } }),
+            reply("m1", "2026-01-05T23:30:05Z"),
+            reply("m1", "2026-01-05T23:30:06Z"),
+            tool_result("t1", false, "ok", "r1"),
+            reply("m2", "2026-01-05T23:31:00Z"),
+        ];
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let mut heatmap = UsageHeatmap::new(AnalyticsRange::All, None);
+        fold_heatmap(transcript(&lines), &offset, &mut heatmap);
+        let heatmap = finish_heatmap(heatmap);
+
+        assert_eq!(heatmap.turns[1][1], 1);
+        assert_eq!(heatmap.messages[1][1], 3);
+        // The tool result is stamped Thursday 2026-01-01 02:00 local, but isn't a prompt.
+        assert_eq!(heatmap.turns[3][2], 0);
+        assert_eq!((heatmap.max_turns, heatmap.max_messages), (1, 3));
+    }
+
+    #[test]
+    fn scan_session_skips_lines_before_range() {
+        let lines = [
+            json!({ "type": "user", "timestamp": "2026-01-01T00:00:00Z", "message": { "content": "old" } }),
+            tool_use("t1", "Read", "2026-01-01T00:00:01Z"),
+        ];
+        let since = DateTime::parse_from_rfc3339("2026-02-01T00:00:00Z")
+            .unwrap()
+            .timestamp_millis();
+        let mut stats = SessionStats::default();
+        scan_session(transcript(&lines), Some(since), &[], &mut stats);
+        assert!(!stats.counted);
+        assert_eq!(
+            build_analytics(AnalyticsRange::Week, Some(since), vec![stats]).sessions,
+            0
+        );
+    }
+}
@@ -0,0 +1,516 @@
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+use tokio::process::Command;
+
+use crate::file_io::{content_hash, read_file_range, FileContentKind, LineRange};
+use crate::remote_backend;
+use crate::state::AppState;
+
+pub(crate) const ATTACHMENTS_DIR_NAME: &str = "attachments";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// The API rejects larger images, so bigger ones are referenced by path instead.
+const MAX_INLINE_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+const MAX_STAGED_FILE_BYTES: u64 = 20 * 1024 * 1024;
+/// Text files up to this size get a preview for the composer chip.
+const MAX_PREVIEW_FILE_BYTES: u64 = 64 * 1024;
+const PREVIEW_LINES: usize = 20;
+const PREVIEW_BYTES: usize = 2 * 1024;
+/// Binary files the CLI can read; anything else is rejected at staging.
+const STAGEABLE_BINARY_MIMES: &[&str] =
+    &["image/png", "image/jpeg", "image/gif", "image/webp", "application/pdf"];
+
+/// An image saved into a thread's attachments dir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AttachedImage {
+    pub(crate) path: String,
+    pub(crate) media_type: String,
+    pub(crate) size: u64,
+    /// The same image was already attached to this thread.
+    pub(crate) deduplicated: bool,
+}
+
+/// A dropped file copied into the thread's attachments dir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StagedAttachment {
+    pub(crate) name: String,
+    pub(crate) source_path: String,
+    /// The staged copy; reference this in the prompt.
+    pub(crate) path: String,
+    pub(crate) mime: String,
+    pub(crate) size: u64,
+    pub(crate) kind: FileContentKind,
+    /// First lines of small text files.
+    pub(crate) preview: Option<String>,
+    pub(crate) deduplicated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RejectedAttachment {
+    pub(crate) path: String,
+    pub(crate) reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AttachmentStageResult {
+    pub(crate) staged: Vec<StagedAttachment>,
+    pub(crate) rejected: Vec<RejectedAttachment>,
+}
+
+fn app_data_dir(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|path| path.to_path_buf())
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+/// Ids become directory names, so they must not be able to climb out.
+fn path_segment(id: &str) -> Result<&str, String> {
+    let id = id.trim();
+    if id.is_empty() || id == "." || id == ".." || id.contains(['/', '\\']) {
+        return Err(format!("Invalid id for attachments: {id:?}"));
+    }
+    Ok(id)
+}
+
+/// `<app data>/attachments/<workspace>/<thread>`, where composer uploads for a
+/// thread are kept until the thread is deleted.
+pub(crate) fn thread_attachments_dir(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<PathBuf, String> {
+    Ok(app_data_dir(state)?
+        .join(ATTACHMENTS_DIR_NAME)
+        .join(path_segment(workspace_id)?)
+        .join(path_segment(thread_id)?))
+}
+
+/// Write `bytes` into `dir` under a content-derived name. Returns the path and
+/// whether an identical file was already there.
+pub(crate) fn store_attachment(
+    dir: &Path,
+    bytes: &[u8],
+    extension: &str,
+) -> Result<(PathBuf, bool), String> {
+    let path = dir.join(format!("{}.{extension}", content_hash(bytes)));
+    if path.is_file() {
+        return Ok((path, true));
+    }
+    std::fs::create_dir_all(dir).map_err(|err| format!("Failed to create attachments dir: {err}"))?;
+    std::fs::write(&path, bytes).map_err(|err| format!("Failed to save attachment: {err}"))?;
+    Ok((path, false))
+}
+
+/// Decode AppleScript's `«data PNGf89504E47…»` rendering of clipboard data.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn decode_applescript_data(output: &str) -> Option<Vec<u8>> {
+    let hex = output
+        .trim()
+        .strip_prefix("«data PNGf")?
+        .strip_suffix('»')?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+async fn read_clipboard_png() -> Result<Option<Vec<u8>>, String> {
+    let output = Command::new("osascript")
+        .args(["-e", "the clipboard as «class PNGf»"])
+        .output()
+        .await
+        .map_err(|err| format!("Failed to read clipboard: {err}"))?;
+    // osascript fails when the clipboard holds no image.
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(decode_applescript_data(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "windows")]
+async fn read_clipboard_png() -> Result<Option<Vec<u8>>, String> {
+    const SCRIPT: &str = "Add-Type -AssemblyName System.Windows.Forms; \
+Add-Type -AssemblyName System.Drawing; \
+$image = [System.Windows.Forms.Clipboard]::GetImage(); \
+if ($image) { $stream = New-Object System.IO.MemoryStream; \
+$image.Save($stream, [System.Drawing.Imaging.ImageFormat]::Png); \
+[Convert]::ToBase64String($stream.ToArray()) }";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-STA", "-Command", SCRIPT])
+        .output()
+        .await
+        .map_err(|err| format!("Failed to read clipboard: {err}"))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let encoded = String::from_utf8_lossy(&output.stdout);
+    Ok(STANDARD.decode(encoded.trim()).ok())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+async fn read_clipboard_png() -> Result<Option<Vec<u8>>, String> {
+    // Wayland first, then X11; either tool may be missing.
+    let attempts: [(&str, &[&str]); 2] = [
+        ("wl-paste", &["--no-newline", "--type", "image/png"]),
+        ("xclip", &["-selection", "clipboard", "-target", "image/png", "-out"]),
+    ];
+    let mut launched = false;
+    for (program, args) in attempts {
+        let Ok(output) = Command::new(program).args(args).output().await else {
+            continue;
+        };
+        launched = true;
+        if output.status.success() && !output.stdout.is_empty() {
+            return Ok(Some(output.stdout));
+        }
+    }
+    if !launched {
+        return Err("Reading clipboard images needs wl-paste or xclip.".to_string());
+    }
+    Ok(None)
+}
+
+/// Save the image on the system clipboard into the thread's attachments dir and
+/// return its path for `send_user_message`'s `images`. Pasting the same image
+/// twice reuses the first file.
+#[tauri::command]
+pub(crate) async fn attach_clipboard_image(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AttachedImage, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "attach_clipboard_image",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let dir = thread_attachments_dir(&state, &workspace_id, &thread_id)?;
+    let bytes = read_clipboard_png()
+        .await?
+        .filter(|bytes| bytes.starts_with(PNG_SIGNATURE))
+        .ok_or("The clipboard does not contain an image.")?;
+    let size = bytes.len() as u64;
+    let result = tokio::task::spawn_blocking(move || store_attachment(&dir, &bytes, "png"))
+        .await
+        .map_err(|err| err.to_string())?
+        .map(|(path, deduplicated)| AttachedImage {
+            path: path.to_string_lossy().to_string(),
+            media_type: "image/png".to_string(),
+            size,
+            deduplicated,
+        });
+    crate::audit::record(
+        "attach_clipboard_image",
+        Some(&workspace_id),
+        json!({ "threadId": thread_id, "bytes": size }),
+        &result,
+    );
+    result
+}
+
+fn text_mime(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("md" | "markdown") => "text/markdown",
+        Some("json") => "application/json",
+        Some("csv") => "text/csv",
+        Some("html" | "htm") => "text/html",
+        Some("xml") => "application/xml",
+        Some("yaml" | "yml") => "application/yaml",
+        _ => "text/plain",
+    }
+}
+
+/// Check one dropped file and copy it into `dir`.
+fn stage_file(dir: &Path, source: &str) -> Result<StagedAttachment, String> {
+    let source_path = Path::new(source);
+    let metadata =
+        std::fs::metadata(source_path).map_err(|err| format!("Cannot read file: {err}"))?;
+    if !metadata.is_file() {
+        return Err("Only files can be attached.".to_string());
+    }
+    let size = metadata.len();
+    if size > MAX_STAGED_FILE_BYTES {
+        return Err(format!(
+            "File is larger than {} MB.",
+            MAX_STAGED_FILE_BYTES / (1024 * 1024)
+        ));
+    }
+    let sniffed = read_file_range(
+        source_path,
+        LineRange {
+            offset: None,
+            limit: Some(PREVIEW_LINES),
+        },
+        PREVIEW_BYTES,
+        "attachment",
+    )?;
+    let (mime, preview) = match sniffed.kind {
+        FileContentKind::Binary => {
+            let mime = sniffed.mime.unwrap_or_default();
+            if !STAGEABLE_BINARY_MIMES.contains(&mime.as_str()) {
+                return Err(format!("Binary files of type {mime} cannot be attached."));
+            }
+            (mime, None)
+        }
+        FileContentKind::Text => {
+            let preview = (size <= MAX_PREVIEW_FILE_BYTES && !sniffed.content.is_empty())
+                .then_some(sniffed.content);
+            (text_mime(source_path).to_string(), preview)
+        }
+    };
+    let bytes = std::fs::read(source_path).map_err(|err| format!("Cannot read file: {err}"))?;
+    let name = source_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| source.to_string());
+    let extension = source_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("bin");
+    let (path, deduplicated) = store_attachment(dir, &bytes, extension)?;
+    Ok(StagedAttachment {
+        name,
+        source_path: source.to_string(),
+        path: path.to_string_lossy().to_string(),
+        mime,
+        size,
+        kind: sniffed.kind,
+        preview,
+        deduplicated,
+    })
+}
+
+fn stage_files(dir: &Path, paths: &[String]) -> AttachmentStageResult {
+    let mut result = AttachmentStageResult::default();
+    for path in paths.iter().map(|path| path.trim()).filter(|path| !path.is_empty()) {
+        match stage_file(dir, path) {
+            Ok(staged) => result.staged.push(staged),
+            Err(reason) => result.rejected.push(RejectedAttachment {
+                path: path.to_string(),
+                reason,
+            }),
+        }
+    }
+    result
+}
+
+/// Validate files dropped on the composer and copy them into the thread's
+/// attachments dir. Files that are too large or binary in an unreadable format
+/// are rejected individually so the rest of the drop still goes through.
+#[tauri::command]
+pub(crate) async fn attachment_stage(
+    workspace_id: String,
+    thread_id: String,
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AttachmentStageResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "attachment_stage",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "paths": paths }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let dir = thread_attachments_dir(&state, &workspace_id, &thread_id)?;
+    let result = tokio::task::spawn_blocking(move || stage_files(&dir, &paths))
+        .await
+        .map_err(|err| err.to_string());
+    crate::audit::record(
+        "attachment_stage",
+        Some(&workspace_id),
+        json!({
+            "threadId": thread_id,
+            "staged": result.as_ref().map_or(0, |result| result.staged.len()),
+            "rejected": result.as_ref().map_or(0, |result| result.rejected.len()),
+        }),
+        &result,
+    );
+    result
+}
+
+/// Media types the API accepts for inline images.
+fn inline_media_type(path: &str) -> Option<&'static str> {
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Media type and base64 data for an image given as a path or a data URL.
+fn load_inline_image(image: &str) -> Option<(String, String)> {
+    if let Some(rest) = image.strip_prefix("data:") {
+        let (media_type, data) = rest.split_once(";base64,")?;
+        let supported = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+        if !supported.contains(&media_type) || data.len() / 4 * 3 > MAX_INLINE_IMAGE_BYTES {
+            return None;
+        }
+        return Some((media_type.to_string(), data.to_string()));
+    }
+    let media_type = inline_media_type(image)?;
+    let size = std::fs::metadata(image).ok()?.len();
+    if size > MAX_INLINE_IMAGE_BYTES as u64 {
+        return None;
+    }
+    let bytes = std::fs::read(image).ok()?;
+    Some((media_type.to_string(), STANDARD.encode(bytes)))
+}
+
+/// Content for a stream-json user message: plain text, or text plus image
+/// blocks when images are attached. Images that cannot be sent inline (too
+/// big, unsupported format, unreadable) are listed by path in the text.
+pub(crate) fn user_message_content(text: &str, images: &[String]) -> Value {
+    let mut image_blocks = Vec::new();
+    let mut unsent = Vec::new();
+    for image in images.iter().map(|image| image.trim()) {
+        if image.is_empty() {
+            continue;
+        }
+        match load_inline_image(image) {
+            Some((media_type, data)) => image_blocks.push(json!({
+                "type": "image",
+                "source": { "type": "base64", "media_type": media_type, "data": data },
+            })),
+            None => unsent.push(format!("[image] {image}")),
+        }
+    }
+    let mut text = text.trim().to_string();
+    if !unsent.is_empty() {
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str("Attached images:\n");
+        text.push_str(&unsent.join("\n"));
+    }
+    if image_blocks.is_empty() {
+        return Value::String(text);
+    }
+    let mut blocks = Vec::new();
+    if !text.is_empty() {
+        blocks.push(json!({ "type": "text", "text": text }));
+    }
+    blocks.extend(image_blocks);
+    Value::Array(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn decode_applescript_data_reads_hex_payload() {
+        assert_eq!(
+            decode_applescript_data("«data PNGf89504E47»\n"),
+            Some(vec![0x89, 0x50, 0x4E, 0x47])
+        );
+        assert_eq!(decode_applescript_data("«data TIFF4D4D»"), None);
+        assert_eq!(decode_applescript_data("«data PNGf895»"), None);
+    }
+
+    #[test]
+    fn store_attachment_dedupes_by_content() {
+        let dir = std::env::temp_dir().join(format!("attachments-{}", Uuid::new_v4()));
+        let (first, existed) = store_attachment(&dir, b"image-bytes", "png").unwrap();
+        assert!(!existed);
+        let (second, existed) = store_attachment(&dir, b"image-bytes", "png").unwrap();
+        assert!(existed);
+        assert_eq!(first, second);
+        let (other, _) = store_attachment(&dir, b"other-bytes", "png").unwrap();
+        assert_ne!(first, other);
+        assert!(path_segment("../escape").is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn stage_files_previews_text_and_rejects_unknown_binaries() {
+        let root = std::env::temp_dir().join(format!("attachments-{}", Uuid::new_v4()));
+        let dir = root.join("staged");
+        std::fs::create_dir_all(&root).unwrap();
+        let notes = root.join("notes.md");
+        std::fs::write(&notes, "# Notes\nline two\n").unwrap();
+        let image = root.join("shot.png");
+        std::fs::write(&image, b"\x89PNG\r\n\x1a\n\0\0rest").unwrap();
+        let blob = root.join("blob.bin");
+        std::fs::write(&blob, b"\0\x01\x02binary").unwrap();
+        let paths = [notes, image, blob, root.join("missing.txt")]
+            .map(|path| path.to_string_lossy().to_string())
+            .to_vec();
+
+        let result = stage_files(&dir, &paths);
+        assert_eq!(result.staged.len(), 2);
+        let notes = &result.staged[0];
+        assert_eq!(notes.name, "notes.md");
+        assert_eq!(notes.mime, "text/markdown");
+        assert_eq!(notes.preview.as_deref(), Some("# Notes\nline two\n"));
+        assert!(Path::new(&notes.path).starts_with(&dir));
+        let image = &result.staged[1];
+        assert_eq!((image.mime.as_str(), image.kind), ("image/png", FileContentKind::Binary));
+        assert_eq!(image.preview, None);
+        let rejected: Vec<_> = result.rejected.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(rejected, vec![paths[2].as_str(), paths[3].as_str()]);
+
+        let again = stage_files(&dir, &paths[..1]);
+        assert!(again.staged[0].deduplicated);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn user_message_content_inlines_supported_images() {
+        let dir = std::env::temp_dir().join(format!("attachments-{}", Uuid::new_v4()));
+        let (png, _) = store_attachment(&dir, b"png-bytes", "png").unwrap();
+        let (bmp, _) = store_attachment(&dir, b"bmp-bytes", "bmp").unwrap();
+        let png = png.to_string_lossy().to_string();
+        let bmp = bmp.to_string_lossy().to_string();
+
+        assert_eq!(user_message_content(" Hello ", &[]), json!("Hello"));
+        let content = user_message_content("What is this?", &[png, bmp.clone()]);
+        assert_eq!(
+            content,
+            json!([
+                { "type": "text", "text": format!("What is this?\n\nAttached images:\n[image] {bmp}") },
+                {
+                    "type": "image",
+                    "source": { "type": "base64", "media_type": "image/png", "data": STANDARD.encode(b"png-bytes") },
+                },
+            ])
+        );
+
+        let pasted = user_message_content("", &["data:image/jpeg;base64,AAAA".to_string()]);
+        assert_eq!(pasted[0]["source"]["media_type"], "image/jpeg");
+        assert_eq!(pasted.as_array().map(Vec::len), Some(1));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+const AUDIT_FILE_NAME: &str = "audit.jsonl";
+const DEFAULT_AUDIT_LIMIT: usize = 200;
+const MAX_AUDIT_LIMIT: usize = 5000;
+/// Identity recorded for calls made by this process; the daemon has no
+/// per-client identity to attach yet.
+const LOCAL_CLIENT: &str = "local";
+
+static AUDIT_PATH: OnceLock<PathBuf> = OnceLock::new();
+static AUDIT_WRITE: Mutex<()> = Mutex::new(());
+
+/// One mutating RPC as written to `audit.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AuditEntry {
+    pub(crate) timestamp: i64,
+    pub(crate) client: String,
+    pub(crate) action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub(crate) detail: Value,
+}
+
+/// Remember where the audit log lives. Called once at startup.
+pub(crate) fn init_audit_log(data_dir: &Path) {
+    let _ = AUDIT_PATH.set(data_dir.join(AUDIT_FILE_NAME));
+}
+
+fn append_entry(path: &Path, entry: &AuditEntry) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+    let _guard = AUDIT_WRITE.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| err.to_string())?;
+    writeln!(file, "{line}").map_err(|err| err.to_string())
+}
+
+/// Append the outcome of a mutating call. Detail should describe the call
+/// without copying user content.
+pub(crate) fn record<T>(
+    action: &str,
+    workspace_id: Option<&str>,
+    detail: Value,
+    result: &Result<T, String>,
+) {
+    let Some(path) = AUDIT_PATH.get() else {
+        return;
+    };
+    let entry = AuditEntry {
+        timestamp: now_millis(),
+        client: LOCAL_CLIENT.to_string(),
+        action: action.to_string(),
+        workspace_id: workspace_id.map(str::to_string),
+        ok: result.is_ok(),
+        error: result.as_ref().err().cloned(),
+        detail,
+    };
+    if let Err(error) = append_entry(path, &entry) {
+        tracing::warn!("[audit] Failed to append audit entry: {error}");
+    }
+}
+
+/// Newest entries first, optionally filtered by workspace and start time.
+fn read_audit_entries(
+    path: &Path,
+    workspace_id: Option<&str>,
+    since: Option<i64>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.to_string()),
+    };
+    let limit = limit
+        .unwrap_or(DEFAULT_AUDIT_LIMIT)
+        .clamp(1, MAX_AUDIT_LIMIT);
+    let mut entries: Vec<AuditEntry> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(&line).ok())
+        .filter(|entry| since.is_none_or(|since| entry.timestamp >= since))
+        .filter(|entry| workspace_id.is_none_or(|id| entry.workspace_id.as_deref() == Some(id)))
+        .collect();
+    entries.reverse();
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+/// The newest failed calls, for diagnostics.
+pub(crate) fn recent_failures(limit: usize) -> Vec<AuditEntry> {
+    let Some(path) = AUDIT_PATH.get() else {
+        return Vec::new();
+    };
+    let mut entries =
+        read_audit_entries(path, None, None, Some(MAX_AUDIT_LIMIT)).unwrap_or_default();
+    entries.retain(|entry| !entry.ok);
+    entries.truncate(limit);
+    entries
+}
+
+#[tauri::command]
+pub(crate) async fn get_audit_log(
+    workspace_id: Option<String>,
+    since: Option<i64>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<AuditEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_audit_log",
+            json!({ "workspaceId": workspace_id, "since": since, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let Some(path) = AUDIT_PATH.get().cloned() else {
+        return Ok(Vec::new());
+    };
+    tokio::task::spawn_blocking(move || {
+        read_audit_entries(&path, workspace_id.as_deref(), since, limit)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: i64, action: &str, workspace_id: &str, ok: bool) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            client: LOCAL_CLIENT.to_string(),
+            action: action.to_string(),
+            workspace_id: Some(workspace_id.to_string()),
+            ok,
+            error: (!ok).then(|| "failed".to_string()),
+            detail: Value::Null,
+        }
+    }
+
+    #[test]
+    fn audit_log_appends_and_reads_newest_first() {
+        let path = std::env::temp_dir()
+            .join(format!("claude-monitor-audit-{}", uuid::Uuid::new_v4()))
+            .join(AUDIT_FILE_NAME);
+        assert!(read_audit_entries(&path, None, None, None)
+            .unwrap()
+            .is_empty());
+
+        append_entry(&path, &entry(1, "send_user_message", "ws-1", true)).unwrap();
+        append_entry(&path, &entry(2, "push_git", "ws-2", false)).unwrap();
+        append_entry(&path, &entry(3, "file_write", "ws-1", true)).unwrap();
+
+        let all = read_audit_entries(&path, None, None, None).unwrap();
+        assert_eq!(
+            all.iter().map(|entry| entry.timestamp).collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+        let ws1 = read_audit_entries(&path, Some("ws-1"), Some(2), None).unwrap();
+        assert_eq!(ws1.len(), 1);
+        assert_eq!(ws1[0].action, "file_write");
+        assert_eq!(
+            read_audit_entries(&path, None, None, Some(1))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,16 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::io::ErrorKind;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use serde::Serialize;
 use serde_json::Value;
 use tokio::io::AsyncWriteExt;
 use tokio::process::{Child, ChildStdin, Command};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use tokio::time::timeout;
 
+use crate::container::check_container;
+use crate::process_registry::forget_process;
 use crate::types::WorkspaceEntry;
 
 pub(crate) struct ActiveTurn {
@@ -31,6 +34,76 @@ pub(crate) struct PersistentSession {
     /// The model this session was started with (e.g., "claude-sonnet-4-5-20250514")
     /// Used to detect when model changes and session needs restart
     pub(crate) model: Option<String>,
+    /// Started with edit prompts routed to the app so an edit scope can be
+    /// enforced even though its permission mode approves edits.
+    pub(crate) routes_edits: bool,
+}
+
+/// Outcome of a finished turn, broadcast to in-process listeners.
+#[derive(Debug, Clone)]
+pub(crate) struct TurnCompletion {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// "completed", "failed" or "interrupted"
+    pub(crate) status: String,
+    /// From the CLI's result event; `None` when the turn ended without one.
+    pub(crate) cost_usd: Option<f64>,
+}
+
+/// A `can_use_tool` control request waiting for the user's decision.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingPermissionRequest {
+    pub(crate) thread_id: String,
+    /// Control protocol request id to echo back in the control_response
+    pub(crate) request_id: String,
+    pub(crate) tool_name: String,
+    pub(crate) tool_input: Value,
+    /// Dangerous-command pattern that held this call in a full-access session.
+    pub(crate) dangerous_pattern: Option<String>,
+}
+
+/// The in-flight turn of a thread as the stdout reader has seen it so far.
+///
+/// Lets a UI that reconnects mid-turn re-hydrate without replaying the transcript.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LiveTurnState {
+    pub(crate) turn_id: String,
+    /// Id of the agentMessage item the text streams into
+    pub(crate) item_id: String,
+    /// Assistant text accumulated so far
+    pub(crate) text: String,
+    /// Tool items that have started but not produced a result yet, in start order
+    pub(crate) running_tools: Vec<Value>,
+}
+
+/// Token counts for one turn or one thread, in the shape of the frontend's
+/// `TokenUsageBreakdown`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TokenBreakdown {
+    pub(crate) total_tokens: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) reasoning_output_tokens: i64,
+}
+
+impl TokenBreakdown {
+    pub(crate) fn add(&mut self, other: &TokenBreakdown) {
+        self.total_tokens += other.total_tokens;
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_output_tokens += other.reasoning_output_tokens;
+    }
+}
+
+/// Token usage accumulated over every turn of a thread, overall and per model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ThreadTokenTotals {
+    pub(crate) total: TokenBreakdown,
+    pub(crate) per_model: BTreeMap<String, TokenBreakdown>,
 }
 
 pub(crate) struct WorkspaceSession {
@@ -41,6 +114,20 @@ pub(crate) struct WorkspaceSession {
     pub(crate) persistent_sessions: Mutex<HashMap<String, PersistentSession>>,
     /// Lock to prevent race conditions when initializing persistent sessions
     pub(crate) session_init_lock: Mutex<()>,
+    /// Turn completion notifications for backend consumers (e.g. task execution)
+    pub(crate) turn_events: broadcast::Sender<TurnCompletion>,
+    /// In-flight permission prompts keyed by tool_use_id
+    pub(crate) pending_permissions: Mutex<HashMap<String, PendingPermissionRequest>>,
+    /// Reader-side snapshot of each thread's active turn
+    pub(crate) live_turns: Mutex<HashMap<String, LiveTurnState>>,
+    /// Token usage summed over the turns each thread has completed
+    pub(crate) token_totals: Mutex<HashMap<String, ThreadTokenTotals>>,
+    /// Tool names from the most recent session init event
+    pub(crate) reported_tools: Mutex<Vec<String>>,
+    /// Working directory of threads scoped to a workspace subdirectory
+    pub(crate) thread_cwds: Mutex<HashMap<String, PathBuf>>,
+    /// Globs each thread may edit, as set by its latest message
+    pub(crate) edit_scopes: Mutex<HashMap<String, Vec<String>>>,
 }
 
 impl WorkspaceSession {
@@ -167,6 +254,196 @@ impl WorkspaceSession {
             .map_err(|e| e.to_string())
     }
 
+    /// Answer a control protocol request (e.g. `can_use_tool`) for a specific thread.
+    ///
+    /// ```json
+    /// {
+    ///   "type": "control_response",
+    ///   "response": { "subtype": "success", "request_id": "...", "response": <response> }
+    /// }
+    /// ```
+    pub(crate) async fn send_control_response(
+        &self,
+        thread_id: &str,
+        request_id: &str,
+        response: Value,
+    ) -> Result<(), String> {
+        let mut sessions = self.persistent_sessions.lock().await;
+        let session = sessions
+            .get_mut(thread_id)
+            .ok_or_else(|| format!("No persistent session for thread {}", thread_id))?;
+
+        let message = serde_json::json!({
+            "type": "control_response",
+            "response": {
+                "subtype": "success",
+                "request_id": request_id,
+                "response": response,
+            }
+        });
+
+        let mut line = serde_json::to_string(&message).map_err(|e| e.to_string())?;
+        line.push('\n');
+
+        session.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Remember a permission prompt until the user answers it.
+    pub(crate) async fn register_permission_request(
+        &self,
+        tool_use_id: String,
+        request: PendingPermissionRequest,
+    ) {
+        self.pending_permissions.lock().await.insert(tool_use_id, request);
+    }
+
+    /// Take the pending permission prompt for a tool use, if any.
+    pub(crate) async fn take_permission_request(
+        &self,
+        tool_use_id: &str,
+    ) -> Option<PendingPermissionRequest> {
+        self.pending_permissions.lock().await.remove(tool_use_id)
+    }
+
+    /// Drop every pending permission prompt for a thread, returning their tool_use_ids.
+    pub(crate) async fn clear_permission_requests(&self, thread_id: &str) -> Vec<String> {
+        let mut pending = self.pending_permissions.lock().await;
+        let ids: Vec<String> = pending
+            .iter()
+            .filter(|(_, request)| request.thread_id == thread_id)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &ids {
+            pending.remove(id);
+        }
+        ids
+    }
+
+    /// Pending permission prompts for a thread as (tool_use_id, request).
+    pub(crate) async fn permission_requests_for_thread(
+        &self,
+        thread_id: &str,
+    ) -> Vec<(String, PendingPermissionRequest)> {
+        let pending = self.pending_permissions.lock().await;
+        let mut requests: Vec<(String, PendingPermissionRequest)> = pending
+            .iter()
+            .filter(|(_, request)| request.thread_id == thread_id)
+            .map(|(id, request)| (id.clone(), request.clone()))
+            .collect();
+        requests.sort_by(|a, b| a.1.request_id.cmp(&b.1.request_id));
+        requests
+    }
+
+    /// Start tracking a new live turn for a thread, replacing any previous one.
+    pub(crate) async fn begin_live_turn(&self, thread_id: &str, turn_id: &str, item_id: &str) {
+        self.live_turns.lock().await.insert(
+            thread_id.to_string(),
+            LiveTurnState {
+                turn_id: turn_id.to_string(),
+                item_id: item_id.to_string(),
+                ..LiveTurnState::default()
+            },
+        );
+    }
+
+    /// Replace the accumulated assistant text of the live turn.
+    pub(crate) async fn update_live_turn_text(&self, thread_id: &str, item_id: &str, text: &str) {
+        if let Some(turn) = self.live_turns.lock().await.get_mut(thread_id) {
+            turn.item_id = item_id.to_string();
+            turn.text = text.to_string();
+        }
+    }
+
+    /// Record a tool item that started running in the live turn.
+    pub(crate) async fn add_live_tool(&self, thread_id: &str, item: Value) {
+        if let Some(turn) = self.live_turns.lock().await.get_mut(thread_id) {
+            turn.running_tools.push(item);
+        }
+    }
+
+    /// Drop a tool item from the live turn once its result arrived.
+    pub(crate) async fn finish_live_tool(&self, thread_id: &str, item_id: &str) {
+        if let Some(turn) = self.live_turns.lock().await.get_mut(thread_id) {
+            turn.running_tools
+                .retain(|item| item.get("id").and_then(|id| id.as_str()) != Some(item_id));
+        }
+    }
+
+    /// Forget the live turn of a thread once it has completed.
+    pub(crate) async fn end_live_turn(&self, thread_id: &str) {
+        self.live_turns.lock().await.remove(thread_id);
+    }
+
+    /// Add a completed turn's usage to its thread's running totals and return
+    /// the new totals.
+    pub(crate) async fn add_turn_token_usage(
+        &self,
+        thread_id: &str,
+        last: &TokenBreakdown,
+        per_model: &[(String, TokenBreakdown)],
+    ) -> ThreadTokenTotals {
+        let mut totals = self.token_totals.lock().await;
+        let thread = totals.entry(thread_id.to_string()).or_default();
+        thread.total.add(last);
+        for (model, usage) in per_model {
+            thread
+                .per_model
+                .entry(model.clone())
+                .or_default()
+                .add(usage);
+        }
+        thread.clone()
+    }
+
+    /// Remember the tools a session reported at init, replacing earlier ones.
+    pub(crate) async fn set_reported_tools(&self, tools: Vec<String>) {
+        *self.reported_tools.lock().await = tools;
+    }
+
+    /// Tools the CLI last reported, empty until a session has started.
+    pub(crate) async fn reported_tools(&self) -> Vec<String> {
+        self.reported_tools.lock().await.clone()
+    }
+
+    /// Set the directory a thread's CLI process runs in; `None` runs it in the
+    /// workspace root. Returns whether the directory changed.
+    pub(crate) async fn set_thread_cwd(&self, thread_id: &str, cwd: Option<PathBuf>) -> bool {
+        let mut cwds = self.thread_cwds.lock().await;
+        let previous = match cwd.clone() {
+            Some(cwd) => cwds.insert(thread_id.to_string(), cwd),
+            None => cwds.remove(thread_id),
+        };
+        previous != cwd
+    }
+
+    /// The directory a thread is scoped to, if it isn't the workspace root.
+    pub(crate) async fn thread_cwd(&self, thread_id: &str) -> Option<PathBuf> {
+        self.thread_cwds.lock().await.get(thread_id).cloned()
+    }
+
+    /// Limit the files a thread may edit until its next message; `None` lifts
+    /// the limit.
+    pub(crate) async fn set_edit_scope(&self, thread_id: &str, globs: Option<Vec<String>>) {
+        let mut scopes = self.edit_scopes.lock().await;
+        match globs {
+            Some(globs) => scopes.insert(thread_id.to_string(), globs),
+            None => scopes.remove(thread_id),
+        };
+    }
+
+    /// Globs the thread may currently edit, if it is limited.
+    pub(crate) async fn edit_scope(&self, thread_id: &str) -> Option<Vec<String>> {
+        self.edit_scopes.lock().await.get(thread_id).cloned()
+    }
+
+    /// The live turn of a thread, if one is in flight.
+    pub(crate) async fn live_turn(&self, thread_id: &str) -> Option<LiveTurnState> {
+        self.live_turns.lock().await.get(thread_id).cloned()
+    }
+
     /// Send a user message to the Claude CLI server for a specific thread.
     /// This is used for sending new messages in a persistent session.
     ///
@@ -175,6 +452,17 @@ impl WorkspaceSession {
     /// {"type":"user","message":{"role":"user","content":"Your message here"}}
     /// ```
     pub(crate) async fn send_message(&self, thread_id: &str, message: &str) -> Result<(), String> {
+        self.send_message_content(thread_id, Value::String(message.to_string()))
+            .await
+    }
+
+    /// Like [`Self::send_message`], but `content` may be an array of content
+    /// blocks, e.g. text followed by base64 image blocks.
+    pub(crate) async fn send_message_content(
+        &self,
+        thread_id: &str,
+        content: Value,
+    ) -> Result<(), String> {
         let mut sessions = self.persistent_sessions.lock().await;
         let session = sessions
             .get_mut(thread_id)
@@ -184,7 +472,7 @@ impl WorkspaceSession {
             "type": "user",
             "message": {
                 "role": "user",
-                "content": message
+                "content": content
             }
         });
 
@@ -218,9 +506,23 @@ impl WorkspaceSession {
             pending_turn_id: None,
             permission_mode,
             model,
+            routes_edits: false,
         });
     }
 
+    /// Record that a thread's persistent session routes edit prompts to the app.
+    pub(crate) async fn mark_persistent_session_routes_edits(&self, thread_id: &str) {
+        if let Some(session) = self.persistent_sessions.lock().await.get_mut(thread_id) {
+            session.routes_edits = true;
+        }
+    }
+
+    /// Whether a thread's persistent session routes edit prompts to the app.
+    pub(crate) async fn persistent_session_routes_edits(&self, thread_id: &str) -> bool {
+        let sessions = self.persistent_sessions.lock().await;
+        sessions.get(thread_id).is_some_and(|s| s.routes_edits)
+    }
+
     /// Get the permission mode for a thread's persistent session.
     /// Returns None if no session exists or if the session has no permission mode set.
     pub(crate) async fn get_persistent_session_permission_mode(&self, thread_id: &str) -> Option<String> {
@@ -228,6 +530,14 @@ impl WorkspaceSession {
         sessions.get(thread_id).and_then(|s| s.permission_mode.clone())
     }
 
+    /// Record a permission mode the running process switched to on its own
+    /// (e.g. leaving plan mode), so the next turn doesn't restart it.
+    pub(crate) async fn set_persistent_session_permission_mode(&self, thread_id: &str, mode: &str) {
+        if let Some(session) = self.persistent_sessions.lock().await.get_mut(thread_id) {
+            session.permission_mode = Some(mode.to_string());
+        }
+    }
+
     /// Get the model for a thread's persistent session.
     /// Returns None if no session exists or if the session has no model set.
     pub(crate) async fn get_persistent_session_model(&self, thread_id: &str) -> Option<String> {
@@ -253,6 +563,13 @@ impl WorkspaceSession {
     pub(crate) async fn kill_persistent_session(&self, thread_id: &str) -> Result<(), String> {
         let mut sessions = self.persistent_sessions.lock().await;
         if let Some(mut session) = sessions.remove(thread_id) {
+            if let Some(pid) = session.child.id() {
+                forget_process(pid);
+            }
+            // The reader can no longer claim a turn that hadn't produced output yet.
+            if let Some(turn_id) = session.pending_turn_id.take() {
+                self.notify_turn_completed(thread_id, &turn_id, "interrupted", None);
+            }
             // Flush stdin before killing to ensure pending writes are sent
             let _ = session.stdin.flush().await;
             session.child.kill().await.map_err(|e| e.to_string())?;
@@ -260,10 +577,69 @@ impl WorkspaceSession {
         Ok(())
     }
 
+    /// OS process ids of the live persistent sessions, keyed by thread id.
+    pub(crate) async fn persistent_session_pids(&self) -> Vec<(String, u32)> {
+        let sessions = self.persistent_sessions.lock().await;
+        sessions
+            .iter()
+            .filter_map(|(thread_id, session)| {
+                session.child.id().map(|pid| (thread_id.clone(), pid))
+            })
+            .collect()
+    }
+
+    /// True once every persistent session process has exited (used while shutting down).
+    pub(crate) async fn persistent_sessions_exited(&self) -> bool {
+        let mut sessions = self.persistent_sessions.lock().await;
+        sessions
+            .values_mut()
+            .all(|session| !matches!(session.child.try_wait(), Ok(None)))
+    }
+
+    /// Subscribe to turn completion notifications for every thread in this workspace.
+    pub(crate) fn subscribe_turn_events(&self) -> broadcast::Receiver<TurnCompletion> {
+        self.turn_events.subscribe()
+    }
+
+    /// Notify listeners that a turn finished. Having no listeners is not an error.
+    pub(crate) fn notify_turn_completed(
+        &self,
+        thread_id: &str,
+        turn_id: &str,
+        status: &str,
+        cost_usd: Option<f64>,
+    ) {
+        let _ = self.turn_events.send(TurnCompletion {
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            status: status.to_string(),
+            cost_usd,
+        });
+    }
+
+    /// Settle a turn the reader never started because it ended before any
+    /// assistant output (an API error, a crash). Returns its id, if one was pending.
+    pub(crate) async fn complete_pending_turn(
+        &self,
+        thread_id: &str,
+        status: &str,
+        cost_usd: Option<f64>,
+    ) -> Option<String> {
+        let turn_id = self.take_pending_turn_id(thread_id).await?;
+        self.notify_turn_completed(thread_id, &turn_id, status, cost_usd);
+        Some(turn_id)
+    }
+
     /// Kill all persistent sessions (used for workspace cleanup).
     pub(crate) async fn kill_all_persistent_sessions(&self) -> Result<(), String> {
         let mut sessions = self.persistent_sessions.lock().await;
-        for (_, mut session) in sessions.drain() {
+        for (thread_id, mut session) in sessions.drain() {
+            if let Some(pid) = session.child.id() {
+                forget_process(pid);
+            }
+            if let Some(turn_id) = session.pending_turn_id.take() {
+                self.notify_turn_completed(&thread_id, &turn_id, "interrupted", None);
+            }
             let _ = session.stdin.flush().await;
             let _ = session.child.kill().await;
         }
@@ -391,7 +767,12 @@ pub(crate) async fn spawn_workspace_session(
         .clone()
         .filter(|value| !value.trim().is_empty())
         .or(default_claude_bin);
-    let _ = check_claude_installation(claude_bin.clone()).await?;
+    match &entry.settings.container {
+        Some(container) => check_container(container).await?,
+        None => {
+            let _ = check_claude_installation(claude_bin.clone()).await?;
+        }
+    }
 
     Ok(Arc::new(WorkspaceSession {
         entry,
@@ -399,11 +780,18 @@ pub(crate) async fn spawn_workspace_session(
         active_turns: Mutex::new(HashMap::new()),
         persistent_sessions: Mutex::new(HashMap::new()),
         session_init_lock: Mutex::new(()),
+        turn_events: broadcast::channel(64).0,
+        pending_permissions: Mutex::new(HashMap::new()),
+        live_turns: Mutex::new(HashMap::new()),
+        token_totals: Mutex::new(HashMap::new()),
+        reported_tools: Mutex::new(Vec::new()),
+        thread_cwds: Mutex::new(HashMap::new()),
+        edit_scopes: Mutex::new(HashMap::new()),
     }))
 }
 
 #[cfg(all(test, unix))]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::types::{WorkspaceKind, WorkspaceSettings};
     use std::process::Stdio;
@@ -424,13 +812,20 @@ mod tests {
     }
 
     /// Create a test WorkspaceSession without checking Claude installation
-    fn create_test_workspace_session() -> WorkspaceSession {
+    pub(crate) fn create_test_workspace_session() -> WorkspaceSession {
         WorkspaceSession {
             entry: create_test_workspace_entry(),
             claude_bin: None,
             active_turns: Mutex::new(HashMap::new()),
             persistent_sessions: Mutex::new(HashMap::new()),
             session_init_lock: Mutex::new(()),
+            turn_events: broadcast::channel(64).0,
+            pending_permissions: Mutex::new(HashMap::new()),
+            live_turns: Mutex::new(HashMap::new()),
+            token_totals: Mutex::new(HashMap::new()),
+            reported_tools: Mutex::new(Vec::new()),
+            thread_cwds: Mutex::new(HashMap::new()),
+            edit_scopes: Mutex::new(HashMap::new()),
         }
     }
 
@@ -477,6 +872,45 @@ mod tests {
         assert!(session.has_persistent_session("thread-1").await);
     }
 
+    #[tokio::test]
+    async fn persistent_session_pids_lists_live_sessions() {
+        let session = create_test_workspace_session();
+        assert!(session.persistent_session_pids().await.is_empty());
+
+        let (stdin, child) = spawn_test_process().await;
+        let pid = child.id().expect("test process has a pid");
+        session
+            .set_persistent_session("thread-1".to_string(), stdin, child, None, None)
+            .await;
+        assert_eq!(
+            session.persistent_session_pids().await,
+            vec![("thread-1".to_string(), pid)]
+        );
+
+        session.kill_persistent_session("thread-1").await.unwrap();
+        assert!(session.persistent_session_pids().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn persistent_sessions_exited_waits_for_processes() {
+        let session = create_test_workspace_session();
+        assert!(session.persistent_sessions_exited().await);
+
+        let (stdin, child) = spawn_test_process().await;
+        session
+            .set_persistent_session("thread-1".to_string(), stdin, child, None, None)
+            .await;
+        assert!(!session.persistent_sessions_exited().await);
+
+        // The process exits while its session entry is still registered.
+        {
+            let mut sessions = session.persistent_sessions.lock().await;
+            let entry = sessions.get_mut("thread-1").unwrap();
+            entry.child.kill().await.unwrap();
+        }
+        assert!(session.persistent_sessions_exited().await);
+    }
+
     #[tokio::test]
     async fn multiple_threads_can_have_independent_sessions() {
         let session = create_test_workspace_session();
@@ -614,6 +1048,69 @@ mod tests {
         assert!(result.is_ok(), "Expected success for thread-Y: {:?}", result);
     }
 
+    #[tokio::test]
+    async fn permission_requests_are_taken_once_and_cleared_per_thread() {
+        let session = create_test_workspace_session();
+        let request = |thread_id: &str, request_id: &str| PendingPermissionRequest {
+            thread_id: thread_id.to_string(),
+            request_id: request_id.to_string(),
+            tool_name: "Bash".to_string(),
+            tool_input: serde_json::json!({ "command": "ls" }),
+            dangerous_pattern: None,
+        };
+        session
+            .register_permission_request("toolu_1".to_string(), request("thread-A", "req-1"))
+            .await;
+        session
+            .register_permission_request("toolu_2".to_string(), request("thread-A", "req-2"))
+            .await;
+        session
+            .register_permission_request("toolu_3".to_string(), request("thread-B", "req-3"))
+            .await;
+
+        let taken = session.take_permission_request("toolu_1").await.unwrap();
+        assert_eq!(taken.request_id, "req-1");
+        assert!(session.take_permission_request("toolu_1").await.is_none());
+
+        assert_eq!(session.clear_permission_requests("thread-A").await, vec!["toolu_2"]);
+        assert!(session.take_permission_request("toolu_3").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn live_turn_tracks_text_and_running_tools_until_ended() {
+        let session = create_test_workspace_session();
+        assert!(session.live_turn("thread-A").await.is_none());
+
+        session.begin_live_turn("thread-A", "turn-1", "turn-1-assistant").await;
+        session.update_live_turn_text("thread-A", "msg-1", "Hello").await;
+        session
+            .add_live_tool("thread-A", serde_json::json!({ "id": "toolu_1", "status": "running" }))
+            .await;
+        session
+            .add_live_tool("thread-A", serde_json::json!({ "id": "toolu_2", "status": "running" }))
+            .await;
+        session.finish_live_tool("thread-A", "toolu_1").await;
+
+        let live = session.live_turn("thread-A").await.unwrap();
+        assert_eq!(live.turn_id, "turn-1");
+        assert_eq!(live.item_id, "msg-1");
+        assert_eq!(live.text, "Hello");
+        assert_eq!(live.running_tools.len(), 1);
+        assert_eq!(live.running_tools[0]["id"], "toolu_2");
+
+        session.end_live_turn("thread-A").await;
+        assert!(session.live_turn("thread-A").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn send_control_response_fails_when_no_session_exists() {
+        let session = create_test_workspace_session();
+        let result = session
+            .send_control_response("missing", "req-1", serde_json::json!({ "behavior": "deny" }))
+            .await;
+        assert!(result.is_err());
+    }
+
     // ==========================================================================
     // Tests for pending_turn_id management
     // ==========================================================================
@@ -1148,6 +1645,91 @@ mod tests {
         assert!(path_env_none.is_some());
     }
 
+    // ==========================================================================
+    // Tests for turn completion notifications
+    // ==========================================================================
+
+    #[tokio::test]
+    async fn notify_turn_completed_reaches_subscribers() {
+        let session = create_test_workspace_session();
+
+        // Sending without subscribers must not panic
+        session.notify_turn_completed("thread-1", "turn-0", "completed", None);
+
+        let mut receiver = session.subscribe_turn_events();
+        session.notify_turn_completed("thread-1", "turn-1", "failed", Some(0.25));
+
+        let completion = receiver.recv().await.unwrap();
+        assert_eq!(completion.thread_id, "thread-1");
+        assert_eq!(completion.turn_id, "turn-1");
+        assert_eq!(completion.status, "failed");
+        assert_eq!(completion.cost_usd, Some(0.25));
+    }
+
+    #[tokio::test]
+    async fn pending_turn_is_reported_when_it_ends_without_output() {
+        let session = create_test_workspace_session();
+        let (stdin, child) = spawn_test_process().await;
+        session
+            .set_persistent_session("thread-1".to_string(), stdin, child, None, None)
+            .await;
+        let mut receiver = session.subscribe_turn_events();
+
+        assert!(session.complete_pending_turn("thread-1", "failed", None).await.is_none());
+
+        session.set_pending_turn_id("thread-1", "turn-1".to_string()).await;
+        let turn_id = session.complete_pending_turn("thread-1", "failed", Some(0.1)).await;
+        assert_eq!(turn_id.as_deref(), Some("turn-1"));
+        let completion = receiver.recv().await.unwrap();
+        assert_eq!(completion.turn_id, "turn-1");
+        assert_eq!(completion.status, "failed");
+
+        session.set_pending_turn_id("thread-1", "turn-2".to_string()).await;
+        session.kill_persistent_session("thread-1").await.unwrap();
+        let completion = receiver.recv().await.unwrap();
+        assert_eq!(completion.turn_id, "turn-2");
+        assert_eq!(completion.status, "interrupted");
+    }
+
+    #[tokio::test]
+    async fn turn_token_usage_accumulates_per_thread_and_model() {
+        let session = create_test_workspace_session();
+        let usage = |input: i64, output: i64| TokenBreakdown {
+            total_tokens: input + output,
+            input_tokens: input,
+            output_tokens: output,
+            ..TokenBreakdown::default()
+        };
+
+        session
+            .add_turn_token_usage(
+                "thread-1",
+                &usage(100, 20),
+                &[
+                    ("sonnet".to_string(), usage(90, 15)),
+                    ("haiku".to_string(), usage(10, 5)),
+                ],
+            )
+            .await;
+        let totals = session
+            .add_turn_token_usage(
+                "thread-1",
+                &usage(50, 10),
+                &[("sonnet".to_string(), usage(50, 10))],
+            )
+            .await;
+
+        assert_eq!(totals.total, usage(150, 30));
+        assert_eq!(totals.per_model["sonnet"], usage(140, 25));
+        assert_eq!(totals.per_model["haiku"], usage(10, 5));
+
+        let other = session
+            .add_turn_token_usage("thread-2", &usage(1, 1), &[])
+            .await;
+        assert_eq!(other.total, usage(1, 1));
+        assert!(other.per_model.is_empty());
+    }
+
     // ==========================================================================
     // Tests for concurrent session access
     // ==========================================================================
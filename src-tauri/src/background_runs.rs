@@ -0,0 +1,509 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{
+    ensure_workspace_session, ensure_workspace_thread_watcher, extract_file_paths,
+    extract_text_from_message, resolve_session_path,
+};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::turn_scheduler::start_tracked_turn;
+use crate::utils::now_millis;
+
+pub(crate) const EDIT_TOOLS: [&str; 4] = ["Edit", "MultiEdit", "Write", "NotebookEdit"];
+const TEST_COMMAND_MARKERS: [&str; 9] = [
+    "cargo test",
+    "npm test",
+    "npm run test",
+    "pnpm test",
+    "yarn test",
+    "vitest",
+    "jest",
+    "pytest",
+    "go test",
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackgroundRunOptions {
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    /// "allowlist" (default) only runs tools already allowed by the workspace's
+    /// permission rules; "full-access" approves everything.
+    #[serde(default)]
+    pub(crate) approval_policy: Option<String>,
+}
+
+/// Summary of what an unattended run did, built from its session log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunReport {
+    pub(crate) files_changed: Vec<String>,
+    pub(crate) commands_run: Vec<String>,
+    /// "passed", "failed" or "notRun"
+    pub(crate) tests_status: String,
+    #[serde(default)]
+    pub(crate) summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackgroundRun {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) turn_id: Option<String>,
+    pub(crate) prompt: String,
+    pub(crate) approval_policy: String,
+    /// "running", "completed", "failed" or "interrupted"
+    pub(crate) status: String,
+    pub(crate) started_at: i64,
+    #[serde(default)]
+    pub(crate) finished_at: Option<i64>,
+    #[serde(default)]
+    pub(crate) report: Option<RunReport>,
+}
+
+/// run_id -> run
+pub(crate) type BackgroundRuns = HashMap<String, BackgroundRun>;
+
+/// Serializes read-modify-write cycles on background_runs.json.
+static BACKGROUND_RUNS_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn background_runs_path(settings_path: &Path) -> Result<PathBuf, String> {
+    settings_path
+        .parent()
+        .map(|path| path.join("background_runs.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+pub(crate) fn read_background_runs(path: &Path) -> Result<BackgroundRuns, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+fn write_background_runs(path: &Path, data: &BackgroundRuns) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn store_background_run(path: &Path, run: &BackgroundRun) -> Result<(), String> {
+    let _guard = BACKGROUND_RUNS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut runs = read_background_runs(path)?;
+    runs.insert(run.id.clone(), run.clone());
+    write_background_runs(path, &runs)
+}
+
+/// Drop every run matching `remove`. Returns how many were dropped.
+pub(crate) fn remove_background_runs(
+    path: &Path,
+    remove: impl Fn(&BackgroundRun) -> bool,
+) -> Result<usize, String> {
+    let _guard = BACKGROUND_RUNS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut runs = read_background_runs(path)?;
+    let before = runs.len();
+    runs.retain(|_, run| !remove(run));
+    let removed = before - runs.len();
+    if removed > 0 {
+        write_background_runs(path, &runs)?;
+    }
+    Ok(removed)
+}
+
+/// Map the approval policy to an access mode that never stops to ask.
+pub(crate) fn access_mode_for_policy(policy: &str) -> Result<&'static str, String> {
+    match policy {
+        "allowlist" => Ok("dontAsk"),
+        "full-access" => Ok("full-access"),
+        other => Err(format!("Unknown approval policy: {other}")),
+    }
+}
+
+fn is_test_command(command: &str) -> bool {
+    let normalized = command.to_lowercase();
+    TEST_COMMAND_MARKERS
+        .iter()
+        .any(|marker| normalized.contains(marker))
+}
+
+/// Build a run report from session log entries.
+pub(crate) fn build_run_report<I: IntoIterator<Item = Value>>(entries: I) -> RunReport {
+    let mut files_changed: Vec<String> = Vec::new();
+    let mut seen_files: HashSet<String> = HashSet::new();
+    let mut commands_run: Vec<String> = Vec::new();
+    let mut test_tool_ids: HashSet<String> = HashSet::new();
+    let mut tests_ran = false;
+    let mut tests_failed = false;
+    let mut summary: Option<String> = None;
+
+    for entry in entries {
+        let entry_type = entry.get("type").and_then(|v| v.as_str()).unwrap_or("");
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        let content = message
+            .get("content")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        if entry_type == "assistant" {
+            for block in &content {
+                if block.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+                    continue;
+                }
+                let name = block.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                let input = block.get("input").cloned().unwrap_or(Value::Null);
+                if EDIT_TOOLS.contains(&name) {
+                    for path in extract_file_paths(&input) {
+                        if seen_files.insert(path.clone()) {
+                            files_changed.push(path);
+                        }
+                    }
+                } else if name == "Bash" {
+                    let command = input
+                        .get("command")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .trim()
+                        .to_string();
+                    if command.is_empty() {
+                        continue;
+                    }
+                    if is_test_command(&command) {
+                        if let Some(id) = block.get("id").and_then(|v| v.as_str()) {
+                            test_tool_ids.insert(id.to_string());
+                        }
+                    }
+                    commands_run.push(command);
+                }
+            }
+            let text = extract_text_from_message(message);
+            if !text.trim().is_empty() {
+                summary = Some(text.trim().to_string());
+            }
+        } else if entry_type == "user" {
+            for block in &content {
+                if block.get("type").and_then(|v| v.as_str()) != Some("tool_result") {
+                    continue;
+                }
+                let tool_use_id = block
+                    .get("tool_use_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                if !test_tool_ids.contains(tool_use_id) {
+                    continue;
+                }
+                tests_ran = true;
+                // Only the latest test run counts; a later pass clears an earlier failure.
+                tests_failed = block
+                    .get("is_error")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+            }
+        }
+    }
+
+    let tests_status = match (tests_ran, tests_failed) {
+        (false, _) => "notRun",
+        (true, true) => "failed",
+        (true, false) => "passed",
+    };
+    RunReport {
+        files_changed,
+        commands_run,
+        tests_status: tests_status.to_string(),
+        summary,
+    }
+}
+
+pub(crate) fn read_run_report(session_path: &Path) -> Result<RunReport, String> {
+    let file = std::fs::File::open(session_path).map_err(|err| err.to_string())?;
+    let entries = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok());
+    Ok(build_run_report(entries))
+}
+
+/// Start an unattended run in a new thread.
+///
+/// The run never waits for approvals: tools are either auto-approved or denied
+/// according to `approvalPolicy`. When the turn ends, a report is built from the
+/// session log and stored with the run record.
+#[tauri::command]
+pub(crate) async fn start_background_run(
+    workspace_id: String,
+    prompt: String,
+    options: Option<BackgroundRunOptions>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BackgroundRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "start_background_run",
+            json!({
+                "workspaceId": workspace_id,
+                "prompt": prompt,
+                "options": options.as_ref().map(|options| json!({
+                    "model": options.model,
+                    "approvalPolicy": options.approval_policy,
+                })),
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("Background run prompt is empty.".to_string());
+    }
+    let options = options.unwrap_or_default();
+    let approval_policy = options
+        .approval_policy
+        .clone()
+        .unwrap_or_else(|| "allowlist".to_string());
+    let access_mode = access_mode_for_policy(&approval_policy)?;
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone()).await;
+
+    let runs_path = background_runs_path(&state.settings_path)?;
+    let thread_id = Uuid::new_v4().to_string();
+    let event_sink = TauriEventSink::new(app.clone());
+    let turn = start_tracked_turn(
+        &state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &thread_id,
+        options.model.as_deref(),
+        Some(access_mode),
+        &prompt,
+    )
+    .await?;
+
+    let mut run = BackgroundRun {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: workspace_id.clone(),
+        thread_id: thread_id.clone(),
+        turn_id: Some(turn.turn_id.clone()),
+        prompt,
+        approval_policy,
+        status: "running".to_string(),
+        started_at: now_millis(),
+        finished_at: None,
+        report: None,
+    };
+    store_background_run(&runs_path, &run)?;
+
+    let response = run.clone();
+    tokio::spawn(async move {
+        let status = turn.completion().await.status;
+
+        // The run is over; don't keep an idle CLI process around for it.
+        let _ = session.kill_persistent_session(&run.thread_id).await;
+
+        let entry = session.entry.clone();
+        let thread_id = run.thread_id.clone();
+        let report = tokio::task::spawn_blocking(move || {
+            resolve_session_path(&entry, &thread_id)
+                .ok_or_else(|| "Session file not found".to_string())
+                .and_then(|path| read_run_report(&path))
+        })
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|result| result);
+        match report {
+            Ok(report) => run.report = Some(report),
+            Err(error) => tracing::warn!("[background_run] Failed to build report for {}: {error}", run.id),
+        }
+        run.status = status;
+        run.finished_at = Some(now_millis());
+        if let Err(error) = store_background_run(&runs_path, &run) {
+            tracing::warn!("[background_run] Failed to store run {}: {error}", run.id);
+        }
+
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: run.workspace_id.clone(),
+            message: json!({
+                "method": "backgroundRun/completed",
+                "params": run,
+            }),
+        });
+    });
+
+    Ok(response)
+}
+
+/// Fetch a background run with its report (the report is empty while it is running).
+#[tauri::command]
+pub(crate) async fn run_get_report(
+    run_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BackgroundRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "run_get_report",
+            json!({ "runId": run_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let path = background_runs_path(&state.settings_path)?;
+    read_background_runs(&path)?
+        .remove(&run_id)
+        .ok_or_else(|| format!("Background run {run_id} not found"))
+}
+
+/// List background runs, newest first, optionally for a single workspace.
+#[tauri::command]
+pub(crate) async fn background_run_list(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<BackgroundRun>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "background_run_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let path = background_runs_path(&state.settings_path)?;
+    let mut runs: Vec<BackgroundRun> = read_background_runs(&path)?
+        .into_values()
+        .filter(|run| {
+            workspace_id.is_none() || workspace_id.as_deref() == Some(run.workspace_id.as_str())
+        })
+        .collect();
+    runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_use(id: &str, name: &str, input: Value) -> Value {
+        json!({
+            "type": "assistant",
+            "message": { "role": "assistant", "content": [
+                { "type": "tool_use", "id": id, "name": name, "input": input }
+            ]}
+        })
+    }
+
+    fn tool_result(id: &str, is_error: bool) -> Value {
+        json!({
+            "type": "user",
+            "message": { "role": "user", "content": [
+                { "type": "tool_result", "tool_use_id": id, "is_error": is_error, "content": "" }
+            ]}
+        })
+    }
+
+    #[test]
+    fn build_run_report_collects_files_commands_and_tests() {
+        let entries = vec![
+            tool_use("1", "Edit", json!({ "file_path": "src/lib.rs" })),
+            tool_use("2", "Write", json!({ "file_path": "src/lib.rs" })),
+            tool_use("3", "Bash", json!({ "command": "cargo test" })),
+            tool_result("3", true),
+            tool_use("4", "Bash", json!({ "command": "cargo test -p core" })),
+            tool_result("4", false),
+            json!({
+                "type": "assistant",
+                "message": { "role": "assistant", "content": [
+                    { "type": "text", "text": "Fixed the parser." }
+                ]}
+            }),
+        ];
+        let report = build_run_report(entries);
+        assert_eq!(report.files_changed, vec!["src/lib.rs"]);
+        assert_eq!(report.commands_run, vec!["cargo test", "cargo test -p core"]);
+        assert_eq!(report.tests_status, "passed");
+        assert_eq!(report.summary.as_deref(), Some("Fixed the parser."));
+    }
+
+    #[test]
+    fn build_run_report_without_tests() {
+        let report = build_run_report(vec![
+            tool_use("1", "Bash", json!({ "command": "ls" })),
+            tool_result("1", true),
+        ]);
+        assert_eq!(report.tests_status, "notRun");
+        assert!(report.summary.is_none());
+    }
+
+    #[test]
+    fn access_mode_for_policy_rejects_unknown() {
+        assert_eq!(access_mode_for_policy("allowlist").unwrap(), "dontAsk");
+        assert_eq!(access_mode_for_policy("full-access").unwrap(), "full-access");
+        assert!(access_mode_for_policy("ask").is_err());
+    }
+
+    #[test]
+    fn background_runs_round_trip() {
+        let dir = std::env::temp_dir().join(format!("claude-background-runs-{}", Uuid::new_v4()));
+        let path = dir.join("background_runs.json");
+        let run = BackgroundRun {
+            id: "run-1".to_string(),
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            turn_id: None,
+            prompt: "Fix lint".to_string(),
+            approval_policy: "allowlist".to_string(),
+            status: "running".to_string(),
+            started_at: 1,
+            finished_at: None,
+            report: None,
+        };
+        store_background_run(&path, &run).unwrap();
+        assert_eq!(read_background_runs(&path).unwrap().get("run-1"), Some(&run));
+
+        let other = BackgroundRun {
+            id: "run-2".to_string(),
+            workspace_id: "ws-2".to_string(),
+            ..run.clone()
+        };
+        store_background_run(&path, &other).unwrap();
+        let removed = remove_background_runs(&path, |run| run.workspace_id == "ws-1").unwrap();
+        assert_eq!(removed, 1);
+        let runs = read_background_runs(&path).unwrap();
+        assert!(!runs.contains_key("run-1") && runs.contains_key("run-2"));
+        assert_eq!(remove_background_runs(&path, |_| false).unwrap(), 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
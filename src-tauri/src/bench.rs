@@ -0,0 +1,548 @@
+//! Benchmark runs: every case of a spec runs its prompt unattended in a fresh
+//! detached worktree of the workspace's `HEAD` (uncommitted changes are not
+//! included), one case at a time so they never compete for the machine.
+//! After each turn the optional `testCommand` runs in the worktree, and the
+//! worktree's diff is measured and saved as a patch. The report, a markdown
+//! comparison table and the patches are written to `bench/<bench id>/` in the
+//! app data dir.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use git2::{DiffFormat, DiffOptions, Repository};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::backend::claude_cli::spawn_workspace_session;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::background_runs::{access_mode_for_policy, read_run_report};
+use crate::claude::resolve_session_path;
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::terminal::shell_path;
+use crate::turn_scheduler::run_turn_until_complete;
+use crate::types::WorkspaceEntry;
+use crate::utils::now_millis;
+use crate::workspaces::run_git_command;
+
+pub(crate) const BENCH_DIR_NAME: &str = "bench";
+const MAX_BENCH_CASES: usize = 50;
+const TEST_COMMAND_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BenchCase {
+    pub(crate) name: String,
+    pub(crate) prompt: String,
+    /// Overrides the spec's model for this case.
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BenchSpec {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    pub(crate) cases: Vec<BenchCase>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    /// Same values as a background run's; defaults to "allowlist".
+    #[serde(default)]
+    pub(crate) approval_policy: Option<String>,
+    /// Shell command that decides success; it passes when it exits with 0.
+    /// Without one, tests the agent ran itself are used.
+    #[serde(default)]
+    pub(crate) test_command: Option<String>,
+    /// Leave each case's worktree on disk for inspection.
+    #[serde(default)]
+    pub(crate) keep_worktrees: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BenchCaseResult {
+    pub(crate) name: String,
+    pub(crate) prompt: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    /// "completed", "failed", "interrupted" or "error"
+    pub(crate) status: String,
+    /// "passed", "failed" or "notRun"
+    pub(crate) tests_status: String,
+    pub(crate) files_changed: usize,
+    pub(crate) insertions: usize,
+    pub(crate) deletions: usize,
+    #[serde(default)]
+    pub(crate) cost_usd: Option<f64>,
+    pub(crate) duration_ms: u64,
+    #[serde(default)]
+    pub(crate) patch_path: Option<String>,
+    #[serde(default)]
+    pub(crate) worktree_path: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BenchReport {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    /// "running" or "completed"
+    pub(crate) status: String,
+    pub(crate) started_at: i64,
+    #[serde(default)]
+    pub(crate) finished_at: Option<i64>,
+    pub(crate) results: Vec<BenchCaseResult>,
+}
+
+fn bench_dir(settings_path: &Path, bench_id: &str) -> Result<PathBuf, String> {
+    if Uuid::parse_str(bench_id).is_err() {
+        return Err(format!("Bench run {bench_id} not found"));
+    }
+    settings_path
+        .parent()
+        .map(|path| path.join(BENCH_DIR_NAME).join(bench_id))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+/// Check a spec and trim its cases.
+fn normalize_bench_spec(mut spec: BenchSpec) -> Result<BenchSpec, String> {
+    if spec.cases.is_empty() {
+        return Err("Bench spec has no cases.".to_string());
+    }
+    if spec.cases.len() > MAX_BENCH_CASES {
+        return Err(format!(
+            "A bench spec can have at most {MAX_BENCH_CASES} cases."
+        ));
+    }
+    for (index, case) in spec.cases.iter_mut().enumerate() {
+        case.name = case.name.trim().to_string();
+        case.prompt = case.prompt.trim().to_string();
+        if case.name.is_empty() {
+            case.name = format!("case-{}", index + 1);
+        }
+        if case.prompt.is_empty() {
+            return Err(format!("Bench case `{}` has an empty prompt.", case.name));
+        }
+    }
+    access_mode_for_policy(spec.approval_policy.as_deref().unwrap_or("allowlist"))?;
+    spec.test_command = spec
+        .test_command
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty());
+    Ok(spec)
+}
+
+fn format_cost(cost_usd: Option<f64>) -> String {
+    cost_usd.map_or_else(|| "-".to_string(), |cost| format!("${cost:.4}"))
+}
+
+/// The report as a markdown table, one row per case.
+pub(crate) fn render_bench_markdown(report: &BenchReport) -> String {
+    let mut out = format!(
+        "# Bench: {}\n\n",
+        report.name.as_deref().unwrap_or(&report.id)
+    );
+    out.push_str("| Case | Model | Status | Tests | Files | +/- | Cost | Duration |\n");
+    out.push_str("|---|---|---|---|---|---|---|---|\n");
+    for result in &report.results {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | +{}/-{} | {} | {:.1}s |\n",
+            result.name.replace('|', "\\|"),
+            result.model.as_deref().unwrap_or("default"),
+            result.status,
+            result.tests_status,
+            result.files_changed,
+            result.insertions,
+            result.deletions,
+            format_cost(result.cost_usd),
+            result.duration_ms as f64 / 1000.0,
+        ));
+    }
+    let total_cost: f64 = report.results.iter().filter_map(|r| r.cost_usd).sum();
+    let passed = report
+        .results
+        .iter()
+        .filter(|result| result.tests_status == "passed")
+        .count();
+    out.push_str(&format!(
+        "\n{passed}/{} passed tests, total cost {}.\n",
+        report.results.len(),
+        format_cost(Some(total_cost))
+    ));
+    out
+}
+
+fn write_bench_report(dir: &Path, report: &BenchReport) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    let contents = serde_json::to_string_pretty(report).map_err(|err| err.to_string())?;
+    std::fs::write(dir.join("report.json"), contents).map_err(|err| err.to_string())?;
+    std::fs::write(dir.join("report.md"), render_bench_markdown(report))
+        .map_err(|err| err.to_string())
+}
+
+pub(crate) fn read_bench_report(dir: &Path) -> Result<BenchReport, String> {
+    let contents = std::fs::read_to_string(dir.join("report.json"))
+        .map_err(|_| "Bench report not found.".to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+/// Everything the case changed relative to `HEAD`, new files included, as
+/// (files, insertions, deletions, patch).
+fn worktree_diff(worktree: &Path) -> Result<(usize, usize, usize, String), String> {
+    let repo = Repository::open(worktree).map_err(|err| err.to_string())?;
+    let head_tree = repo
+        .head()
+        .and_then(|head| head.peel_to_tree())
+        .map_err(|err| err.to_string())?;
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut options))
+        .map_err(|err| err.to_string())?;
+    let stats = diff.stats().map_err(|err| err.to_string())?;
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(|err| err.to_string())?;
+    Ok((
+        stats.files_changed(),
+        stats.insertions(),
+        stats.deletions(),
+        patch,
+    ))
+}
+
+async fn run_test_command(worktree: &Path, command: &str) -> &'static str {
+    let child = Command::new(shell_path())
+        .arg("-lc")
+        .arg(command)
+        .current_dir(worktree)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .status();
+    match tokio::time::timeout(TEST_COMMAND_TIMEOUT, child).await {
+        Ok(Ok(status)) if status.success() => "passed",
+        _ => "failed",
+    }
+}
+
+async fn run_case(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    parent: &WorkspaceEntry,
+    default_bin: Option<String>,
+    spec: &BenchSpec,
+    index: usize,
+    dir: &Path,
+) -> BenchCaseResult {
+    let case = &spec.cases[index];
+    let mut result = BenchCaseResult {
+        name: case.name.clone(),
+        prompt: case.prompt.clone(),
+        model: case.model.clone().or_else(|| spec.model.clone()),
+        status: "error".to_string(),
+        tests_status: "notRun".to_string(),
+        ..BenchCaseResult::default()
+    };
+    let parent_path = PathBuf::from(&parent.path);
+    let worktree = dir.join("worktrees").join(index.to_string());
+    let worktree_string = worktree.to_string_lossy().to_string();
+    if let Err(error) = run_git_command(
+        &parent_path,
+        &["worktree", "add", "--detach", &worktree_string, "HEAD"],
+    )
+    .await
+    {
+        result.error = Some(error);
+        return result;
+    }
+
+    // A private session for the worktree; its events are reported under the
+    // parent workspace.
+    let entry = WorkspaceEntry {
+        path: worktree_string.clone(),
+        ..parent.clone()
+    };
+    let started = Instant::now();
+    let outcome = match spawn_workspace_session(entry, default_bin).await {
+        Ok(session) => {
+            let thread_id = Uuid::new_v4().to_string();
+            result.thread_id = Some(thread_id.clone());
+            let access_mode =
+                access_mode_for_policy(spec.approval_policy.as_deref().unwrap_or("allowlist"))
+                    .unwrap_or("dontAsk");
+            let outcome = run_turn_until_complete(
+                state,
+                event_sink,
+                &session,
+                &parent.id,
+                &thread_id,
+                result.model.as_deref(),
+                Some(access_mode),
+                &case.prompt,
+            )
+            .await;
+            let _ = session.kill_all_persistent_sessions().await;
+            outcome.map(|outcome| (outcome, session, thread_id))
+        }
+        Err(error) => Err(error),
+    };
+    result.duration_ms = started.elapsed().as_millis() as u64;
+
+    match outcome {
+        Ok((completion, session, thread_id)) => {
+            result.status = completion.status;
+            result.cost_usd = completion.cost_usd;
+            result.tests_status = match &spec.test_command {
+                Some(command) => run_test_command(&worktree, command).await.to_string(),
+                None => {
+                    let entry = session.entry.clone();
+                    tokio::task::spawn_blocking(move || {
+                        resolve_session_path(&entry, &thread_id)
+                            .ok_or_else(|| "Session file not found".to_string())
+                            .and_then(|path| read_run_report(&path))
+                    })
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .map_or_else(|| "notRun".to_string(), |report| report.tests_status)
+                }
+            };
+            let diff_path = worktree.clone();
+            match tokio::task::spawn_blocking(move || worktree_diff(&diff_path))
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|diff| diff)
+            {
+                Ok((files, insertions, deletions, patch)) => {
+                    result.files_changed = files;
+                    result.insertions = insertions;
+                    result.deletions = deletions;
+                    let patch_path = dir.join(format!("{index}.patch"));
+                    if std::fs::write(&patch_path, patch).is_ok() {
+                        result.patch_path = Some(patch_path.to_string_lossy().to_string());
+                    }
+                }
+                Err(error) => result.error = Some(error),
+            }
+        }
+        Err(error) => result.error = Some(error),
+    }
+
+    if spec.keep_worktrees {
+        result.worktree_path = Some(worktree_string);
+    } else if let Err(error) = run_git_command(
+        &parent_path,
+        &["worktree", "remove", "--force", &worktree_string],
+    )
+    .await
+    {
+        tracing::warn!("[bench] Failed to remove worktree {worktree_string}: {error}");
+    }
+    result
+}
+
+/// Run every case of `spec` against a workspace and return the report,
+/// which stays "running" until a `bench/completed` event carries the final one.
+#[tauri::command]
+pub(crate) async fn bench_run(
+    workspace_id: String,
+    spec: BenchSpec,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BenchReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "bench_run",
+            json!({
+                "workspaceId": workspace_id,
+                "spec": {
+                    "name": spec.name,
+                    "cases": spec.cases.iter().map(|case| json!({
+                        "name": case.name,
+                        "prompt": case.prompt,
+                        "model": case.model,
+                    })).collect::<Vec<_>>(),
+                    "model": spec.model,
+                    "approvalPolicy": spec.approval_policy,
+                    "testCommand": spec.test_command,
+                    "keepWorktrees": spec.keep_worktrees,
+                },
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let spec = normalize_bench_spec(spec)?;
+    let parent = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let default_bin = state.app_settings.lock().await.claude_bin.clone();
+    let mut report = BenchReport {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: workspace_id.clone(),
+        name: spec.name.clone(),
+        status: "running".to_string(),
+        started_at: now_millis(),
+        finished_at: None,
+        results: Vec::new(),
+    };
+    let dir = bench_dir(&state.settings_path, &report.id)?;
+    write_bench_report(&dir, &report)?;
+
+    let response = report.clone();
+    tokio::spawn(async move {
+        let state = app.state::<AppState>();
+        let event_sink = TauriEventSink::new(app.clone());
+        for index in 0..spec.cases.len() {
+            let result = run_case(
+                &state,
+                &event_sink,
+                &parent,
+                default_bin.clone(),
+                &spec,
+                index,
+                &dir,
+            )
+            .await;
+            report.results.push(result);
+            if let Err(error) = write_bench_report(&dir, &report) {
+                tracing::warn!("[bench] Failed to store report {}: {error}", report.id);
+            }
+        }
+        let _ = run_git_command(
+            &PathBuf::from(&parent.path),
+            &["worktree", "prune", "--expire", "now"],
+        )
+        .await;
+        report.status = "completed".to_string();
+        report.finished_at = Some(now_millis());
+        if let Err(error) = write_bench_report(&dir, &report) {
+            tracing::warn!("[bench] Failed to store report {}: {error}", report.id);
+        }
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: report.workspace_id.clone(),
+            message: json!({
+                "method": "bench/completed",
+                "params": report,
+            }),
+        });
+    });
+
+    Ok(response)
+}
+
+/// Fetch a bench report; results fill in as cases finish.
+#[tauri::command]
+pub(crate) async fn bench_get_report(
+    bench_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BenchReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "bench_get_report",
+            json!({ "benchId": bench_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    read_bench_report(&bench_dir(&state.settings_path, &bench_id)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(cases: serde_json::Value) -> BenchSpec {
+        serde_json::from_value(json!({ "cases": cases, "testCommand": "  " })).unwrap()
+    }
+
+    #[test]
+    fn normalize_bench_spec_names_cases_and_rejects_empty_prompts() {
+        let normalized = normalize_bench_spec(spec(json!([
+            { "name": " fix-login ", "prompt": " Fix the login bug " },
+            { "name": "", "prompt": "Add a test", "model": "opus" },
+        ])))
+        .unwrap();
+        assert_eq!(normalized.cases[0].name, "fix-login");
+        assert_eq!(normalized.cases[0].prompt, "Fix the login bug");
+        assert_eq!(normalized.cases[1].name, "case-2");
+        assert!(normalized.test_command.is_none());
+
+        assert!(normalize_bench_spec(spec(json!([]))).is_err());
+        assert!(normalize_bench_spec(spec(json!([{ "name": "a", "prompt": " " }]))).is_err());
+        let mut bad_policy = spec(json!([{ "name": "a", "prompt": "b" }]));
+        bad_policy.approval_policy = Some("ask".to_string());
+        assert!(normalize_bench_spec(bad_policy).is_err());
+    }
+
+    #[test]
+    fn bench_markdown_lists_each_case() {
+        let report = BenchReport {
+            id: "bench-1".to_string(),
+            workspace_id: "ws-1".to_string(),
+            name: Some("auth fixes".to_string()),
+            status: "completed".to_string(),
+            started_at: 0,
+            finished_at: Some(1),
+            results: vec![
+                BenchCaseResult {
+                    name: "fix-login".to_string(),
+                    model: Some("opus".to_string()),
+                    status: "completed".to_string(),
+                    tests_status: "passed".to_string(),
+                    files_changed: 2,
+                    insertions: 10,
+                    deletions: 3,
+                    cost_usd: Some(0.5),
+                    duration_ms: 61_500,
+                    ..BenchCaseResult::default()
+                },
+                BenchCaseResult {
+                    name: "a|b".to_string(),
+                    status: "error".to_string(),
+                    tests_status: "notRun".to_string(),
+                    ..BenchCaseResult::default()
+                },
+            ],
+        };
+        let markdown = render_bench_markdown(&report);
+        assert!(markdown.starts_with("# Bench: auth fixes\n"));
+        assert!(markdown
+            .contains("| fix-login | opus | completed | passed | 2 | +10/-3 | $0.5000 | 61.5s |"));
+        assert!(markdown.contains("| a\\|b | default | error | notRun | 0 | +0/-0 | - | 0.0s |"));
+        assert!(markdown.contains("1/2 passed tests, total cost $0.5000."));
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use git2::Repository;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::git::github_repo_from_path;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+use crate::utils::now_millis;
+
+const CI_POLL_INTERVAL: Duration = Duration::from_secs(120);
+
+/// One check run or commit status reported for a branch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CiCheck {
+    pub(crate) name: String,
+    /// "success", "failure", "pending" or "skipped"
+    pub(crate) status: String,
+    pub(crate) url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CiStatus {
+    pub(crate) workspace_id: String,
+    pub(crate) branch: String,
+    pub(crate) provider: String,
+    /// Worst status across all checks; `None` when nothing has reported.
+    pub(crate) overall: Option<String>,
+    pub(crate) checks: Vec<CiCheck>,
+    pub(crate) updated_at: i64,
+}
+
+/// Normalize a GitHub check run or commit status (REST or GraphQL shape).
+pub(crate) fn classify_check(check: &Value) -> &'static str {
+    // Check runs carry status/conclusion; commit statuses carry state.
+    let conclusion = check
+        .get("conclusion")
+        .and_then(|value| value.as_str())
+        .filter(|value| !value.is_empty())
+        .or_else(|| check.get("state").and_then(|value| value.as_str()))
+        .unwrap_or("")
+        .to_uppercase();
+    match conclusion.as_str() {
+        "SUCCESS" => "success",
+        "NEUTRAL" | "SKIPPED" => "skipped",
+        "FAILURE" | "ERROR" | "CANCELLED" | "TIMED_OUT" | "ACTION_REQUIRED"
+        | "STARTUP_FAILURE" => "failure",
+        _ => "pending",
+    }
+}
+
+/// Failures win over pending checks, which win over successes.
+pub(crate) fn overall_status<'a>(statuses: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut overall: Option<&str> = None;
+    for status in statuses {
+        overall = match (overall, status) {
+            (_, "failure") | (Some("failure"), _) => Some("failure"),
+            (_, "pending") | (Some("pending"), _) => Some("pending"),
+            _ => Some("success"),
+        };
+    }
+    overall.map(|status| status.to_string())
+}
+
+fn parse_check_runs(value: &Value) -> Vec<CiCheck> {
+    value
+        .get("check_runs")
+        .and_then(|runs| runs.as_array())
+        .map(|runs| {
+            runs.iter()
+                .map(|run| CiCheck {
+                    name: run
+                        .get("name")
+                        .and_then(|name| name.as_str())
+                        .unwrap_or("check")
+                        .to_string(),
+                    status: classify_check(run).to_string(),
+                    url: run
+                        .get("html_url")
+                        .or_else(|| run.get("details_url"))
+                        .and_then(|url| url.as_str())
+                        .map(|url| url.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_commit_statuses(value: &Value) -> Vec<CiCheck> {
+    value
+        .get("statuses")
+        .and_then(|statuses| statuses.as_array())
+        .map(|statuses| {
+            statuses
+                .iter()
+                .map(|status| CiCheck {
+                    name: status
+                        .get("context")
+                        .and_then(|name| name.as_str())
+                        .unwrap_or("status")
+                        .to_string(),
+                    status: classify_check(status).to_string(),
+                    url: status
+                        .get("target_url")
+                        .and_then(|url| url.as_str())
+                        .map(|url| url.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn gh_api_json(repo_root: &Path, endpoint: &str) -> Result<Value, String> {
+    let output = Command::new("gh")
+        .args(["api", endpoint])
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn current_branch(repo_root: &Path) -> Result<String, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head = repo.head().map_err(|e| e.to_string())?;
+    if !head.is_branch() {
+        return Err("Workspace is not on a branch.".to_string());
+    }
+    head.shorthand()
+        .map(|name| name.to_string())
+        .ok_or_else(|| "Workspace is not on a branch.".to_string())
+}
+
+async fn fetch_ci_status(
+    entry: &WorkspaceEntry,
+    branch: Option<String>,
+) -> Result<CiStatus, String> {
+    let repo_root = resolve_git_root(entry)?;
+    let branch = match branch.filter(|value| !value.trim().is_empty()) {
+        Some(branch) => branch,
+        None => current_branch(&repo_root)?,
+    };
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let encoded_branch = branch.replace('/', "%2F");
+    let check_runs = gh_api_json(
+        &repo_root,
+        &format!("repos/{repo_name}/commits/{encoded_branch}/check-runs?per_page=100"),
+    )
+    .await?;
+    let statuses = gh_api_json(
+        &repo_root,
+        &format!("repos/{repo_name}/commits/{encoded_branch}/status"),
+    )
+    .await?;
+
+    let mut checks = parse_check_runs(&check_runs);
+    checks.extend(parse_commit_statuses(&statuses));
+    Ok(CiStatus {
+        workspace_id: entry.id.clone(),
+        branch,
+        provider: "github".to_string(),
+        overall: overall_status(checks.iter().map(|check| check.status.as_str())),
+        checks,
+        updated_at: now_millis(),
+    })
+}
+
+/// Per-check CI results for a branch (defaults to the workspace's checked-out branch).
+#[tauri::command]
+pub(crate) async fn get_ci_status(
+    workspace_id: String,
+    branch: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CiStatus, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    fetch_ci_status(&entry, branch).await
+}
+
+/// Poll CI for every workspace and emit `ci/statusChanged` when a result changes.
+pub(crate) fn spawn_ci_status_poller(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let event_sink = TauriEventSink::new(app.clone());
+        // workspace_id -> (branch, checks) from the last poll
+        let mut last_seen: HashMap<String, (String, Vec<CiCheck>)> = HashMap::new();
+        let mut ticker = tokio::time::interval(CI_POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let state = app.state::<AppState>();
+            if !state.app_settings.lock().await.ci_status_polling_enabled {
+                continue;
+            }
+            let entries: Vec<WorkspaceEntry> =
+                state.workspaces.lock().await.values().cloned().collect();
+            last_seen.retain(|id, _| entries.iter().any(|entry| &entry.id == id));
+            for entry in entries {
+                // Workspaces without a GitHub remote or a branch are skipped quietly.
+                let Ok(status) = fetch_ci_status(&entry, None).await else {
+                    continue;
+                };
+                let snapshot = (status.branch.clone(), status.checks.clone());
+                if last_seen.get(&entry.id) == Some(&snapshot) {
+                    continue;
+                }
+                last_seen.insert(entry.id.clone(), snapshot);
+                event_sink.emit_app_server_event(AppServerEvent {
+                    workspace_id: entry.id.clone(),
+                    message: json!({
+                        "method": "ci/statusChanged",
+                        "params": status,
+                    }),
+                });
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_check_handles_runs_and_statuses() {
+        assert_eq!(
+            classify_check(&json!({ "status": "completed", "conclusion": "success" })),
+            "success"
+        );
+        assert_eq!(
+            classify_check(&json!({ "status": "in_progress", "conclusion": null })),
+            "pending"
+        );
+        assert_eq!(classify_check(&json!({ "state": "error" })), "failure");
+        assert_eq!(classify_check(&json!({ "conclusion": "skipped" })), "skipped");
+    }
+
+    #[test]
+    fn overall_status_prefers_failures_then_pending() {
+        assert_eq!(overall_status(Vec::<&str>::new()), None);
+        assert_eq!(overall_status(["success", "skipped"]).as_deref(), Some("success"));
+        assert_eq!(overall_status(["success", "pending"]).as_deref(), Some("pending"));
+        assert_eq!(
+            overall_status(["failure", "pending", "success"]).as_deref(),
+            Some("failure")
+        );
+    }
+
+    #[test]
+    fn status_check_rollup_entries_combine_into_one_status() {
+        // `gh pr view --json statusCheckRollup` uses the GraphQL shape.
+        let passing = json!({ "status": "COMPLETED", "conclusion": "SUCCESS" });
+        let running = json!({ "status": "IN_PROGRESS", "conclusion": "" });
+        let failed = json!({ "state": "FAILURE" });
+        let rollup = |checks: &[Value]| overall_status(checks.iter().map(classify_check));
+        assert_eq!(rollup(&[]), None);
+        assert_eq!(rollup(&[passing.clone()]).as_deref(), Some("success"));
+        assert_eq!(rollup(&[passing.clone(), running.clone()]).as_deref(), Some("pending"));
+        assert_eq!(rollup(&[passing, running, failed]).as_deref(), Some("failure"));
+    }
+
+    #[test]
+    fn parses_check_runs_and_commit_statuses() {
+        let runs = json!({
+            "check_runs": [
+                { "name": "build", "conclusion": "failure", "html_url": "https://ci/build" }
+            ]
+        });
+        let statuses = json!({
+            "statuses": [{ "context": "lint", "state": "pending", "target_url": null }]
+        });
+        let checks = parse_check_runs(&runs);
+        assert_eq!(checks[0].name, "build");
+        assert_eq!(checks[0].status, "failure");
+        assert_eq!(checks[0].url.as_deref(), Some("https://ci/build"));
+        let statuses = parse_commit_statuses(&statuses);
+        assert_eq!(statuses[0].name, "lint");
+        assert_eq!(statuses[0].status, "pending");
+        assert!(statuses[0].url.is_none());
+    }
+}
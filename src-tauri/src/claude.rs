@@ -1,6 +1,6 @@
 use chrono::DateTime;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::io::{AsyncBufReadExt, BufReader as AsyncBufReader};
 #[cfg(target_os = "macos")]
 use tokio::process::Command;
@@ -20,16 +20,36 @@ use uuid::Uuid;
 
 
 pub(crate) use crate::backend::claude_cli::WorkspaceSession;
+use crate::attachments::user_message_content;
 use crate::backend::claude_cli::{
     build_claude_command_with_bin, build_claude_path_env, check_claude_installation,
-    spawn_workspace_session as spawn_workspace_session_inner,
+    spawn_workspace_session as spawn_workspace_session_inner, PendingPermissionRequest,
+    ThreadTokenTotals, TokenBreakdown,
 };
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::claude_home::{resolve_default_claude_home, resolve_workspace_claude_home};
+use crate::container::{build_container_claude_command, container_path};
+use crate::dangerous_commands::{active_patterns, match_dangerous_command};
+use crate::diff_stats::file_change_stats;
+use crate::edit_scope::{
+    edit_target, is_file_change_tool, normalize_edit_scope, path_in_edit_scope,
+};
+use crate::egress_policy::{check_tool_egress, emit_policy_violation, EgressCheck, WEB_TOOLS};
 use crate::event_sink::TauriEventSink;
+use crate::plan_mode::extract_proposed_plan;
+use crate::process_registry::record_spawned_process;
+use crate::redaction::{apply_redactions, workspace_redaction_rules};
 use crate::remote_backend;
+use crate::review_comments::{build_review_comments_prompt, track_review_comment_items};
+use crate::sandbox::{build_sandboxed_claude_command, sandbox_writable_paths};
 use crate::state::{AppState, WorkspaceWatcher};
-use crate::types::WorkspaceEntry;
+use crate::tool_images::{extract_tool_result_images, with_tool_images};
+use crate::turn_reports::{build_turn_report, record_turn_report};
+use crate::thread_summaries::summarize_thread;
+use crate::turn_scheduler::{dispatch_turn, dispatch_turn_content, start_tracked_turn};
+use crate::types::{SandboxSettings, WorkspaceEntry};
+use crate::utils::now_millis;
+use crate::workspaces::{ensure_allowed_workspace_path, resolve_additional_dir};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -72,6 +92,48 @@ pub(crate) async fn spawn_workspace_session(
     spawn_workspace_session_inner(entry, default_claude_bin).await
 }
 
+/// The registered workspace, whether or not a session was started for it.
+pub(crate) async fn workspace_entry(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<WorkspaceEntry, String> {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())
+}
+
+/// The workspace's session, started on first use when the workspace was
+/// never connected. Emits `claude/connected` like `connect_workspace`.
+pub(crate) async fn ensure_workspace_session(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+) -> Result<Arc<WorkspaceSession>, String> {
+    if let Some(session) = state.sessions.lock().await.get(workspace_id) {
+        return Ok(session.clone());
+    }
+    let entry = workspace_entry(state, workspace_id).await?;
+    let default_bin = state.app_settings.lock().await.claude_bin.clone();
+    let session = spawn_workspace_session(entry, default_bin).await?;
+    // Another caller may have connected while this one was spawning.
+    let session = state
+        .sessions
+        .lock()
+        .await
+        .entry(workspace_id.to_string())
+        .or_insert(session)
+        .clone();
+    TauriEventSink::new(app.clone()).emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({ "method": "claude/connected", "params": {} }),
+    });
+    Ok(session)
+}
+
 pub(crate) async fn ensure_workspace_thread_watcher(
     workspace_id: &str,
     entry: WorkspaceEntry,
@@ -137,6 +199,7 @@ pub(crate) async fn claude_doctor(
 #[tauri::command]
 pub(crate) async fn start_thread(
     workspace_id: String,
+    cwd: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -145,16 +208,17 @@ pub(crate) async fn start_thread(
             &*state,
             app,
             "start_thread",
-            json!({ "workspaceId": workspace_id }),
+            json!({ "workspaceId": workspace_id, "cwd": cwd }),
         )
         .await;
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let entry = workspace_entry(&state, &workspace_id).await?;
     let thread_id = Uuid::new_v4().to_string();
+    let thread_cwd = match cwd {
+        Some(cwd) => record_thread_cwd(&state, &entry, &thread_id, &cwd)?,
+        None => None,
+    };
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -164,7 +228,9 @@ pub(crate) async fn start_thread(
             "id": thread_id,
             "createdAt": timestamp,
             "updatedAt": timestamp,
-            "cwd": session.entry.path,
+            "cwd": thread_cwd
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or(entry.path),
         }
     }))
 }
@@ -186,14 +252,7 @@ pub(crate) async fn resume_thread(
         .await;
     }
 
-    let entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let entry = workspace_entry(&state, &workspace_id).await?;
 
     let thread_id_clone = thread_id.clone();
     let thread = tokio::task::spawn_blocking(move || {
@@ -205,11 +264,49 @@ pub(crate) async fn resume_thread(
     Ok(json!({ "thread": thread }))
 }
 
+/// Full output of a tool item whose live event carried a truncated copy.
+#[tauri::command]
+pub(crate) async fn get_item_full_output(
+    workspace_id: String,
+    thread_id: String,
+    item_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_item_full_output",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "itemId": item_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    tokio::task::spawn_blocking(move || {
+        let path = resolve_thread_transcript(&entry, &thread_id)
+            .ok_or_else(|| "Session file not found".to_string())?;
+        read_item_full_output(&entry.id, &path, &item_id)?
+            .ok_or_else(|| format!("No output recorded for item {item_id}"))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Fork a thread at `message_id`. With `replacement_text` the fork stops just
+/// before that user message and the new text is sent as a turn right away,
+/// using `model` and `access_mode` or, when omitted, those of the source
+/// thread's running session.
 #[tauri::command]
 pub(crate) async fn fork_thread_from_message(
     workspace_id: String,
     thread_id: String,
     message_id: String,
+    replacement_text: Option<String>,
+    model: Option<String>,
+    access_mode: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -221,29 +318,78 @@ pub(crate) async fn fork_thread_from_message(
             json!({
                 "workspaceId": workspace_id,
                 "threadId": thread_id,
-                "messageId": message_id
+                "messageId": message_id,
+                "replacementText": replacement_text,
+                "model": model,
+                "accessMode": access_mode
             }),
         )
         .await;
     }
 
-    let entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    let replacement_text = replacement_text.filter(|text| !text.trim().is_empty());
+    let edit = replacement_text.is_some();
+    let entry = session.entry.clone();
     let thread_id_clone = thread_id.clone();
     let message_id_clone = message_id.clone();
     let new_thread_id = tokio::task::spawn_blocking(move || {
-        fork_session_from_message(&entry, &thread_id_clone, &message_id_clone)
+        if edit {
+            fork_session_before_message(&entry, &thread_id_clone, &message_id_clone)
+        } else {
+            fork_session_from_message(&entry, &thread_id_clone, &message_id_clone)
+        }
     })
     .await
     .map_err(|err| err.to_string())??;
 
-    Ok(json!({ "threadId": new_thread_id }))
+    let Some(text) = replacement_text else {
+        return Ok(json!({ "threadId": new_thread_id }));
+    };
+
+    let model = match model {
+        Some(model) => Some(model),
+        None => session.get_persistent_session_model(&thread_id).await,
+    };
+    let access_mode = match access_mode {
+        Some(mode) => Some(mode),
+        None => {
+            session
+                .get_persistent_session_permission_mode(&thread_id)
+                .await
+        }
+    };
+
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone())
+        .await;
+    let prompt = text.trim();
+    let event_sink = TauriEventSink::new(app);
+    let turn_id = ensure_persistent_session(
+        &workspace_id,
+        &session,
+        &new_thread_id,
+        model.as_deref(),
+        access_mode.as_deref(),
+        None,
+        event_sink.clone(),
+    )
+    .await?;
+    let queue_position = dispatch_turn(
+        &state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &new_thread_id,
+        &turn_id,
+        prompt,
+    )
+    .await?;
+
+    Ok(json!({
+        "threadId": new_thread_id,
+        "turn": { "id": turn_id, "threadId": new_thread_id },
+        "queuePosition": queue_position,
+    }))
 }
 
 #[tauri::command]
@@ -268,13 +414,7 @@ pub(crate) async fn rewind_thread_files(
         .await;
     }
 
-    let session = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .clone()
-    };
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
 
     let default_bin = {
         let settings = state.app_settings.lock().await;
@@ -311,6 +451,7 @@ pub(crate) async fn rewind_thread_files(
         });
     }
 
+    crate::analytics::record_rewind(&state, &workspace_id, &thread_id, &message_id);
     Ok(json!({ "ok": true }))
 }
 
@@ -319,6 +460,7 @@ pub(crate) async fn list_threads(
     workspace_id: String,
     cursor: Option<String>,
     limit: Option<u32>,
+    tags: Option<Vec<String>>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -327,23 +469,21 @@ pub(crate) async fn list_threads(
             &*state,
             app,
             "list_threads",
-            json!({ "workspaceId": workspace_id, "cursor": cursor, "limit": limit }),
+            json!({
+                "workspaceId": workspace_id,
+                "cursor": cursor,
+                "limit": limit,
+                "tags": tags,
+            }),
         )
         .await;
     }
 
-    let workspace_entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let workspace_entry = workspace_entry(&state, &workspace_id).await?;
 
     let workspace_path = workspace_entry.path.clone();
-    let entries = load_sessions_index(&workspace_entry);
-    eprintln!(
+    let mut entries = load_sessions_index(&workspace_entry);
+    tracing::debug!(
         "[debug:sessions] list_threads: loaded {} total entries for workspace '{}'",
         entries.len(),
         workspace_id
@@ -357,25 +497,41 @@ pub(crate) async fn list_threads(
         .into_iter()
         .collect::<std::collections::HashSet<_>>();
     if !archived_set.is_empty() {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] list_threads: filtering out {} archived threads",
             archived_set.len()
         );
     }
+    let thread_meta = workspace_thread_meta(&state, &workspace_id);
+    let scoped = scoped_thread_sessions(&workspace_entry, &thread_meta, &entries);
+    entries.extend(scoped);
+    let tag_filter = tags
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|tag| normalize_thread_tag(tag))
+        .collect::<Vec<_>>();
     let total_before_filter = entries.len();
     let mut sorted = entries
         .into_iter()
         .filter(|entry| !archived_set.contains(&entry.session_id))
+        .filter(|entry| thread_has_tags(thread_meta.get(&entry.session_id), &tag_filter))
         .collect::<Vec<_>>();
     let filtered_count = total_before_filter - sorted.len();
     if filtered_count > 0 {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] list_threads: {} sessions removed by archive filter, {} remaining",
             filtered_count,
             sorted.len()
         );
     }
-    sorted.sort_by(|a, b| session_sort_key(b).cmp(&session_sort_key(a)));
+    // Pinned threads lead every page order, most recently pinned first.
+    sorted.sort_by(|a, b| {
+        let pinned_a = thread_meta.get(&a.session_id).and_then(|meta| meta.pinned_at);
+        let pinned_b = thread_meta.get(&b.session_id).and_then(|meta| meta.pinned_at);
+        pinned_b
+            .cmp(&pinned_a)
+            .then_with(|| session_sort_key(b).cmp(&session_sort_key(a)))
+    });
 
     let offset = cursor
         .as_ref()
@@ -383,7 +539,7 @@ pub(crate) async fn list_threads(
         .unwrap_or(0);
     let limit = limit.unwrap_or(20).clamp(1, 50) as usize;
     let end = (offset + limit).min(sorted.len());
-    eprintln!(
+    tracing::debug!(
         "[debug:sessions] list_threads: returning page offset={}, limit={}, total={}, has_more={}",
         offset,
         limit,
@@ -400,25 +556,12 @@ pub(crate) async fn list_threads(
     let mut threads = Vec::new();
     for entry in page_entries {
         let session_id = entry.session_id.clone();
-        let created_at = parse_iso_timestamp(entry.created.as_deref())
-            .or_else(|| entry.file_mtime)
-            .unwrap_or(0);
-        let updated_at = parse_iso_timestamp(entry.modified.as_deref())
-            .or_else(|| entry.file_mtime)
-            .unwrap_or(created_at);
         let cwd = entry
             .project_path
             .clone()
             .unwrap_or_else(|| workspace_path.clone());
-        threads.push(json!({
-            "id": session_id.clone(),
-            "preview": entry.first_prompt.unwrap_or_default(),
-            "messageCount": entry.message_count.unwrap_or(0),
-            "createdAt": created_at,
-            "updatedAt": updated_at,
-            "cwd": cwd,
-            "gitBranch": entry.git_branch,
-        }));
+        let meta = thread_meta.get(&session_id);
+        threads.push(session_thread_summary(entry, meta, &cwd));
         threads.extend(list_subagent_threads(&workspace_entry, &session_id, &cwd));
     }
 
@@ -445,14 +588,7 @@ pub(crate) async fn search_thread(
         .await;
     }
 
-    let workspace_entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let workspace_entry = workspace_entry(&state, &workspace_id).await?;
 
     let workspace_path = workspace_entry.path.clone();
     let entries = load_sessions_index(&workspace_entry);
@@ -465,14 +601,23 @@ pub(crate) async fn search_thread(
         .and_then(|archived| archived.get(&workspace_id).cloned())
         .unwrap_or_default();
     let archived_set: std::collections::HashSet<_> = archived_ids.into_iter().collect();
+    let thread_meta = workspace_thread_meta(&state, &workspace_id);
 
     let matching: Vec<_> = entries
         .into_iter()
         .filter(|entry| !archived_set.contains(&entry.session_id))
-        .filter(|entry| entry.session_id.to_lowercase().contains(&query_lower))
+        .filter(|entry| {
+            entry.session_id.to_lowercase().contains(&query_lower)
+                || thread_meta.get(&entry.session_id).is_some_and(|meta| {
+                    meta.title
+                        .as_deref()
+                        .is_some_and(|title| title.to_lowercase().contains(&query_lower))
+                        || meta.tags.iter().any(|tag| tag.contains(&query_lower))
+                })
+        })
         .collect();
 
-    eprintln!(
+    tracing::debug!(
         "[debug:sessions] search_thread: query='{}' matched {} sessions (excluded {} archived)",
         query,
         matching.len(),
@@ -481,26 +626,12 @@ pub(crate) async fn search_thread(
 
     let mut threads = Vec::new();
     for entry in matching {
-        let session_id = entry.session_id.clone();
-        let created_at = parse_iso_timestamp(entry.created.as_deref())
-            .or_else(|| entry.file_mtime)
-            .unwrap_or(0);
-        let updated_at = parse_iso_timestamp(entry.modified.as_deref())
-            .or_else(|| entry.file_mtime)
-            .unwrap_or(created_at);
         let cwd = entry
             .project_path
             .clone()
             .unwrap_or_else(|| workspace_path.clone());
-        threads.push(json!({
-            "id": session_id,
-            "preview": entry.first_prompt.unwrap_or_default(),
-            "messageCount": entry.message_count.unwrap_or(0),
-            "createdAt": created_at,
-            "updatedAt": updated_at,
-            "cwd": cwd,
-            "gitBranch": entry.git_branch,
-        }));
+        let meta = thread_meta.get(&entry.session_id);
+        threads.push(session_thread_summary(entry, meta, &cwd));
     }
 
     Ok(json!({
@@ -508,6 +639,89 @@ pub(crate) async fn search_thread(
     }))
 }
 
+/// Recent threads across every connected workspace, newest first, each tagged
+/// with the workspace it belongs to.
+#[tauri::command]
+pub(crate) async fn list_all_threads(
+    cursor: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "list_all_threads",
+            json!({ "cursor": cursor, "limit": limit }),
+        )
+        .await;
+    }
+
+    let workspace_entries = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .values()
+            .map(|session| session.entry.clone())
+            .collect::<Vec<_>>()
+    };
+    let archived = archived_threads_path(&state)
+        .ok()
+        .and_then(|path| read_archived_threads(&path).ok())
+        .unwrap_or_default();
+
+    let mut threads = Vec::new();
+    for workspace in &workspace_entries {
+        let archived_set = archived
+            .get(&workspace.id)
+            .map(|ids| ids.iter().collect::<HashSet<_>>())
+            .unwrap_or_default();
+        let thread_meta = workspace_thread_meta(&state, &workspace.id);
+        let workspace_branch = workspace
+            .worktree
+            .as_ref()
+            .map(|worktree| worktree.branch.clone());
+        for entry in load_sessions_index(workspace) {
+            if archived_set.contains(&entry.session_id) {
+                continue;
+            }
+            let cwd = entry
+                .project_path
+                .clone()
+                .unwrap_or_else(|| workspace.path.clone());
+            let meta = thread_meta.get(&entry.session_id);
+            let mut thread = session_thread_summary(entry, meta, &cwd);
+            if let Value::Object(ref mut map) = thread {
+                map.insert("workspaceId".to_string(), json!(workspace.id));
+                map.insert("workspaceName".to_string(), json!(workspace.name));
+                map.insert("workspaceBranch".to_string(), json!(workspace_branch));
+            }
+            threads.push(thread);
+        }
+    }
+    threads.sort_by_key(|thread| {
+        std::cmp::Reverse(thread.get("updatedAt").and_then(Value::as_i64).unwrap_or(0))
+    });
+
+    let offset = cursor
+        .as_ref()
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let limit = limit.unwrap_or(20).clamp(1, 50) as usize;
+    let end = (offset + limit).min(threads.len());
+    let next_cursor = (end < threads.len()).then(|| end.to_string());
+    let page = threads
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "data": page,
+        "nextCursor": next_cursor,
+    }))
+}
+
 #[tauri::command]
 pub(crate) async fn archive_thread(
     workspace_id: String,
@@ -535,16 +749,12 @@ pub(crate) async fn archive_thread(
     Ok(json!({ "ok": true }))
 }
 
+/// Rename a thread; an empty or missing title reverts to the prompt preview.
 #[tauri::command]
-pub(crate) async fn send_user_message(
+pub(crate) async fn set_thread_title(
     workspace_id: String,
     thread_id: String,
-    text: String,
-    model: Option<String>,
-    effort: Option<String>,
-    access_mode: Option<String>,
-    images: Option<Vec<String>>,
-    _collaboration_mode: Option<Value>,
+    title: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -552,63 +762,28 @@ pub(crate) async fn send_user_message(
         return remote_backend::call_remote(
             &*state,
             app,
-            "send_user_message",
-            json!({
-                "workspaceId": workspace_id,
-                "threadId": thread_id,
-                "text": text,
-                "model": model,
-                "effort": effort,
-                "accessMode": access_mode,
-                "images": images,
-            }),
+            "set_thread_title",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "title": title }),
         )
         .await;
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?
-        .clone();
-    drop(sessions);
-
-    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone()).await;
-
-    let prompt = build_prompt_with_images(text, images);
-    if prompt.trim().is_empty() {
-        return Err("empty user message".to_string());
-    }
-
-    let event_sink = TauriEventSink::new(app.clone());
-
-    // Ensure persistent session exists and get turn_id
-    let turn_id = ensure_persistent_session(
-        &workspace_id,
-        &session,
-        &thread_id,
-        model.as_deref(),
-        access_mode.as_deref(),
-        None, // max_thinking_tokens - use default
-        event_sink,
-    ).await?;
-
-    // Set the pending turn ID so the reader knows which turn_id to use
-    session.set_pending_turn_id(&thread_id, turn_id.clone()).await;
-
-    // Send the user message via stdin
-    session.send_message(&thread_id, &prompt).await?;
-
-    Ok(json!({
-        "result": {
-            "turn": { "id": turn_id, "threadId": thread_id }
-        }
-    }))
+    let title = title
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let path = thread_meta_path(&state)?;
+    let meta = update_thread_meta(&path, &workspace_id, &thread_id, |meta| {
+        meta.title = title;
+    })?;
+    serde_json::to_value(meta).map_err(|err| err.to_string())
 }
 
+/// Add a tag to a thread.
 #[tauri::command]
-pub(crate) async fn collaboration_mode_list(
+pub(crate) async fn tag_thread(
     workspace_id: String,
+    thread_id: String,
+    tag: String,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -616,19 +791,29 @@ pub(crate) async fn collaboration_mode_list(
         return remote_backend::call_remote(
             &*state,
             app,
-            "collaboration_mode_list",
-            json!({ "workspaceId": workspace_id }),
+            "tag_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "tag": tag }),
         )
         .await;
     }
-    Ok(json!({ "data": [] }))
+
+    let tag = normalize_thread_tag(&tag).ok_or("Tag cannot be empty")?;
+    let path = thread_meta_path(&state)?;
+    let meta = update_thread_meta(&path, &workspace_id, &thread_id, |meta| {
+        if !meta.tags.contains(&tag) {
+            meta.tags.push(tag);
+            meta.tags.sort();
+        }
+    })?;
+    serde_json::to_value(meta).map_err(|err| err.to_string())
 }
 
+/// Remove a tag from a thread.
 #[tauri::command]
-pub(crate) async fn turn_interrupt(
+pub(crate) async fn untag_thread(
     workspace_id: String,
     thread_id: String,
-    turn_id: String,
+    tag: String,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -636,26 +821,24 @@ pub(crate) async fn turn_interrupt(
         return remote_backend::call_remote(
             &*state,
             app,
-            "turn_interrupt",
-            json!({ "workspaceId": workspace_id, "threadId": thread_id, "turnId": turn_id }),
+            "untag_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "tag": tag }),
         )
         .await;
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    session.interrupt_turn(&thread_id, &turn_id).await?;
-    Ok(json!({ "ok": true }))
+    let tag = normalize_thread_tag(&tag).ok_or("Tag cannot be empty")?;
+    let path = thread_meta_path(&state)?;
+    let meta = update_thread_meta(&path, &workspace_id, &thread_id, |meta| {
+        meta.tags.retain(|existing| existing != &tag);
+    })?;
+    serde_json::to_value(meta).map_err(|err| err.to_string())
 }
 
+/// List the tags used in a workspace with per-tag thread counts.
 #[tauri::command]
-pub(crate) async fn start_review(
+pub(crate) async fn list_tags(
     workspace_id: String,
-    thread_id: String,
-    target: Value,
-    delivery: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -663,54 +846,26 @@ pub(crate) async fn start_review(
         return remote_backend::call_remote(
             &*state,
             app,
-            "start_review",
-            json!({
-                "workspaceId": workspace_id,
-                "threadId": thread_id,
-                "target": target,
-                "delivery": delivery,
-            }),
+            "list_tags",
+            json!({ "workspaceId": workspace_id }),
         )
         .await;
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?
-        .clone();
-    drop(sessions);
-
-    let prompt = build_review_prompt(&workspace_id, &target, &state).await?;
-    let event_sink = TauriEventSink::new(app.clone());
-
-    // Ensure persistent session exists and get turn_id
-    let turn_id = ensure_persistent_session(
-        &workspace_id,
-        &session,
-        &thread_id,
-        None,
-        None, // access_mode - use default
-        None, // max_thinking_tokens - use default
-        event_sink,
-    ).await?;
-
-    // Set the pending turn ID so the reader knows which turn_id to use
-    session.set_pending_turn_id(&thread_id, turn_id.clone()).await;
-
-    // Send the review prompt via stdin
-    session.send_message(&thread_id, &prompt).await?;
-
-    Ok(json!({
-        "result": {
-            "turn": { "id": turn_id, "threadId": thread_id }
-        }
-    }))
+    let meta = workspace_thread_meta(&state, &workspace_id);
+    let tags = count_thread_tags(&meta)
+        .into_iter()
+        .map(|(tag, count)| json!({ "tag": tag, "count": count }))
+        .collect::<Vec<_>>();
+    Ok(json!({ "data": tags }))
 }
 
+/// Pin or unpin a thread so it stays at the top of the thread list.
 #[tauri::command]
-pub(crate) async fn model_list(
+pub(crate) async fn pin_thread(
     workspace_id: String,
+    thread_id: String,
+    pinned: bool,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -718,24 +873,327 @@ pub(crate) async fn model_list(
         return remote_backend::call_remote(
             &*state,
             app,
-            "model_list",
-            json!({ "workspaceId": workspace_id }),
+            "pin_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "pinned": pinned }),
         )
         .await;
     }
 
-    let data = vec![
-        json!({
-            "id": "claude-opus-4-5-20251101",
-            "model": "claude-opus-4-5-20251101",
-            "displayName": "Claude Opus 4.5",
-            "description": "Highest quality reasoning model.",
-            "supportedReasoningEfforts": [],
-            "defaultReasoningEffort": "",
-            "isDefault": true,
-        }),
-        json!({
-            "id": "claude-sonnet-4-5-20250929",
+    let path = thread_meta_path(&state)?;
+    let now = now_millis();
+    let meta = update_thread_meta(&path, &workspace_id, &thread_id, |meta| {
+        if !pinned {
+            meta.pinned_at = None;
+        } else if meta.pinned_at.is_none() {
+            meta.pinned_at = Some(now);
+        }
+    })?;
+    serde_json::to_value(meta).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn send_user_message(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    images: Option<Vec<String>>,
+    _collaboration_mode: Option<Value>,
+    redact: Option<bool>,
+    cwd: Option<String>,
+    edit_scope: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "send_user_message",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "text": text,
+                "model": model,
+                "effort": effort,
+                "accessMode": access_mode,
+                "images": images,
+                "redact": redact,
+                "cwd": cwd,
+                "editScope": edit_scope,
+            }),
+        )
+        .await;
+    }
+
+    let image_count = images.as_ref().map_or(0, Vec::len);
+    let result = send_user_message_local(
+        &workspace_id,
+        &thread_id,
+        text,
+        model,
+        access_mode,
+        images,
+        redact,
+        cwd,
+        edit_scope,
+        &state,
+        app,
+    )
+    .await;
+    crate::audit::record(
+        "send_user_message",
+        Some(&workspace_id),
+        json!({ "threadId": thread_id, "images": image_count }),
+        &result,
+    );
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn send_user_message_local(
+    workspace_id: &str,
+    thread_id: &str,
+    text: String,
+    model: Option<String>,
+    access_mode: Option<String>,
+    images: Option<Vec<String>>,
+    redact: Option<bool>,
+    cwd: Option<String>,
+    edit_scope: Option<Vec<String>>,
+    state: &AppState,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let workspace_id = workspace_id.to_string();
+    let thread_id = thread_id.to_string();
+    let session = ensure_workspace_session(state, &app, &workspace_id).await?;
+
+    // A thread keeps the directory it was scoped to unless this message moves it.
+    let thread_cwd = match cwd {
+        Some(cwd) => record_thread_cwd(state, &session.entry, &thread_id, &cwd)?,
+        None => match workspace_thread_meta(state, &workspace_id)
+            .remove(&thread_id)
+            .and_then(|meta| meta.cwd)
+        {
+            Some(subpath) => resolve_thread_cwd(&session.entry.path, &subpath)?,
+            None => None,
+        },
+    };
+    if session.set_thread_cwd(&thread_id, thread_cwd).await
+        && session.has_persistent_session(&thread_id).await
+    {
+        // The CLI's working directory is fixed per process, like its permission mode.
+        session.kill_persistent_session(&thread_id).await?;
+    }
+    let edit_scope = edit_scope
+        .map(|globs| normalize_edit_scope(&session.entry.path, globs))
+        .transpose()?;
+    session.set_edit_scope(&thread_id, edit_scope).await;
+
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), state, app.clone()).await;
+
+    let images = images.unwrap_or_default();
+    let prompt = text.trim().to_string();
+    if prompt.is_empty() && images.iter().all(|image| image.trim().is_empty()) {
+        return Err("empty user message".to_string());
+    }
+
+    // Redaction is on by default; the composer can skip it for a single message.
+    let (prompt, redactions) = if redact.unwrap_or(true) {
+        let rules = workspace_redaction_rules(state, &workspace_id).await?;
+        let preview = apply_redactions(&prompt, &rules)?;
+        (preview.text, preview.matches)
+    } else {
+        (prompt, Vec::new())
+    };
+
+    let event_sink = TauriEventSink::new(app.clone());
+
+    // Ensure persistent session exists and get turn_id
+    let turn_id = ensure_persistent_session(
+        &workspace_id,
+        &session,
+        &thread_id,
+        model.as_deref(),
+        access_mode.as_deref(),
+        None, // max_thinking_tokens - use default
+        event_sink.clone(),
+    ).await?;
+
+    // Send now, or queue behind the global turn limit
+    let queue_position = dispatch_turn_content(
+        state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &thread_id,
+        &turn_id,
+        user_message_content(&prompt, &images),
+    )
+    .await?;
+
+    Ok(json!({
+        "result": {
+            "turn": { "id": turn_id, "threadId": thread_id },
+            "redactions": redactions,
+            "queuePosition": queue_position,
+        }
+    }))
+}
+
+#[tauri::command]
+pub(crate) async fn collaboration_mode_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "collaboration_mode_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+    Ok(json!({ "data": [] }))
+}
+
+#[tauri::command]
+pub(crate) async fn turn_interrupt(
+    workspace_id: String,
+    thread_id: String,
+    turn_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "turn_interrupt",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "turnId": turn_id }),
+        )
+        .await;
+    }
+
+    // A turn still waiting for a slot is simply dropped from the queue.
+    let event_sink = TauriEventSink::new(app.clone());
+    if state.turn_scheduler.cancel(&turn_id, &event_sink).await {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id,
+            message: json!({
+                "method": "turn/completed",
+                "params": {
+                    "threadId": thread_id,
+                    "turn": { "id": turn_id, "threadId": thread_id },
+                },
+            }),
+        });
+        return Ok(json!({ "ok": true }));
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    session.interrupt_turn(&thread_id, &turn_id).await?;
+    Ok(json!({ "ok": true }))
+}
+
+#[tauri::command]
+pub(crate) async fn start_review(
+    workspace_id: String,
+    thread_id: String,
+    target: Value,
+    delivery: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "start_review",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "target": target,
+                "delivery": delivery,
+            }),
+        )
+        .await;
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+
+    let target_type = target.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let review_threads = if target_type == "pullRequestComments" {
+        let pr_number = target.get("prNumber").and_then(|v| v.as_u64());
+        Some(crate::git::get_unresolved_review_threads(&workspace_id, pr_number, &state).await?)
+    } else {
+        None
+    };
+    let prompt = match &review_threads {
+        Some((pr_number, threads)) => build_review_comments_prompt(*pr_number, threads)?,
+        None => build_review_prompt(&workspace_id, &target, &state).await?,
+    };
+    let event_sink = TauriEventSink::new(app.clone());
+
+    // Send now, or queue behind the global turn limit
+    let turn = start_tracked_turn(
+        &state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &thread_id,
+        None,
+        None, // access_mode - use default
+        &prompt,
+    )
+    .await?;
+    let turn_id = turn.turn_id.clone();
+    let queue_position = turn.queue_position;
+
+    if let Some((_, threads)) = review_threads {
+        track_review_comment_items(event_sink, workspace_id.clone(), turn, threads);
+    }
+
+    Ok(json!({
+        "result": {
+            "turn": { "id": turn_id, "threadId": thread_id },
+            "queuePosition": queue_position,
+        }
+    }))
+}
+
+#[tauri::command]
+pub(crate) async fn model_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "model_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+
+    let data = vec![
+        json!({
+            "id": "claude-opus-4-5-20251101",
+            "model": "claude-opus-4-5-20251101",
+            "displayName": "Claude Opus 4.5",
+            "description": "Highest quality reasoning model.",
+            "supportedReasoningEfforts": [],
+            "defaultReasoningEffort": "",
+            "isDefault": true,
+        }),
+        json!({
+            "id": "claude-sonnet-4-5-20250929",
             "model": "claude-sonnet-4-5-20250929",
             "displayName": "Claude Sonnet 4.5",
             "description": "Fast, balanced model.",
@@ -854,14 +1312,86 @@ pub(crate) async fn respond_to_server_request(
         return Ok(());
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+
+    // Permission prompts are answered over the control protocol, everything
+    // else (AskUserQuestion) as a tool_result.
+    // The first answer wins; other clients attached to this backend drop the prompt.
+    if let Some(pending) = session.take_permission_request(&tool_use_id).await {
+        let response = build_permission_response(&result, &pending.tool_input);
+        session
+            .send_control_response(&pending.thread_id, &pending.request_id, response)
+            .await?;
+        if let Some(pattern) = &pending.dangerous_pattern {
+            crate::audit::record(
+                "dangerous_command_decision",
+                Some(&workspace_id),
+                json!({
+                    "threadId": thread_id,
+                    "toolUseId": tool_use_id,
+                    "pattern": pattern,
+                    "decision": result.get("decision").cloned().unwrap_or(Value::Null),
+                }),
+                &Ok::<(), String>(()),
+            );
+        }
+        emit_event(
+            &TauriEventSink::new(app),
+            &workspace_id,
+            "item/tool/approvalCancelled",
+            json!({ "threadId": thread_id, "toolUseId": tool_use_id, "reason": "answered" }),
+        );
+        return Ok(());
+    }
+    if result.get("decision").is_some() {
+        return Err("This approval was already answered.".to_string());
+    }
 
     session.send_response(&thread_id, tool_use_id, result).await
 }
 
+/// Snapshot of a thread's in-flight turn so a reconnecting UI can re-hydrate it.
+///
+/// Returns null when no turn is running.
+#[tauri::command]
+pub(crate) async fn get_live_turn_state(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "get_live_turn_state",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await;
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    let Some(live) = session.live_turn(&thread_id).await else {
+        return Ok(Value::Null);
+    };
+    let pending_permissions = session
+        .permission_requests_for_thread(&thread_id)
+        .await
+        .into_iter()
+        .map(|(tool_use_id, request)| {
+            json!({
+                "toolUseId": tool_use_id,
+                "toolName": request.tool_name,
+                "toolInput": request.tool_input,
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut response = serde_json::to_value(live).map_err(|err| err.to_string())?;
+    response["threadId"] = json!(thread_id);
+    response["pendingPermissions"] = json!(pending_permissions);
+    Ok(response)
+}
+
 /// Gets the diff content for commit message generation
 #[tauri::command]
 pub(crate) async fn get_commit_message_prompt(
@@ -870,9 +1400,10 @@ pub(crate) async fn get_commit_message_prompt(
 ) -> Result<String, String> {
     let diff = crate::git::get_workspace_diff(&workspace_id, &state).await?;
 
-    if diff.trim().is_empty() {
+    if diff.is_empty() {
         return Err("No changes to generate commit message for".to_string());
     }
+    let diff = diff.to_prompt_text();
 
     let prompt = format!(
         "Generate a concise git commit message for the following changes. \
@@ -941,9 +1472,10 @@ pub(crate) async fn generate_commit_message(
 ) -> Result<String, String> {
     let diff = crate::git::get_workspace_diff(&workspace_id, &state).await?;
 
-    if diff.trim().is_empty() {
+    if diff.is_empty() {
         return Err("No changes to generate commit message for".to_string());
     }
+    let diff = diff.to_prompt_text();
 
     let prompt = format!(
         "Generate a concise git commit message for the following changes. \
@@ -953,14 +1485,7 @@ Only output the commit message, nothing else.\n\n\
 Changes:\n{diff}"
     );
 
-    let entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let entry = workspace_entry(&state, &workspace_id).await?;
 
     let default_bin = {
         let settings = state.app_settings.lock().await;
@@ -979,20 +1504,60 @@ Changes:\n{diff}"
     Ok(response)
 }
 
+/// Drafts release notes from the commits since `since` (or the nearest tag).
+#[tauri::command]
+pub(crate) async fn generate_release_notes(
+    workspace_id: String,
+    since: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let (since, commits) =
+        crate::git::get_commits_since_tag(&workspace_id, since.as_deref(), &state).await?;
+    if commits.is_empty() {
+        return Err("No commits to generate release notes for".to_string());
+    }
+
+    let log = commits
+        .iter()
+        .map(|commit| format!("- {} ({})", commit.summary, commit.author))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let range = match since.as_deref() {
+        Some(tag) => format!("since {tag}"),
+        None => "in this repository".to_string(),
+    };
+    let prompt = format!(
+        "Write release notes in Markdown for the following commits {range}. \
+Group changes under headings such as Features, Fixes and Other. \
+Describe user-facing changes in plain language and drop noise like merge commits. \
+Only output the release notes, nothing else.\n\n\
+Commits:\n{log}"
+    );
+
+    let entry = workspace_entry(&state, &workspace_id).await?;
+
+    let default_bin = {
+        let settings = state.app_settings.lock().await;
+        settings.claude_bin.clone()
+    };
+
+    run_claude_prompt_once(
+        &entry.path,
+        default_bin,
+        prompt,
+        Some("dontAsk".to_string()),
+        Some("haiku".to_string()),
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn generate_run_metadata(
     workspace_id: String,
     prompt: String,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
-    let entry = {
-        let sessions = state.sessions.lock().await;
-        sessions
-            .get(&workspace_id)
-            .ok_or("workspace not connected")?
-            .entry
-            .clone()
-    };
+    let entry = workspace_entry(&state, &workspace_id).await?;
 
     let default_bin = {
         let settings = state.app_settings.lock().await;
@@ -1047,29 +1612,7 @@ User's task description:\n{prompt}"
     }
 }
 
-fn build_prompt_with_images(text: String, images: Option<Vec<String>>) -> String {
-    let mut prompt = text.trim().to_string();
-    if let Some(images) = images {
-        let mut image_lines = Vec::new();
-        for image in images {
-            let trimmed = image.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            image_lines.push(format!("[image] {trimmed}"));
-        }
-        if !image_lines.is_empty() {
-            if !prompt.is_empty() {
-                prompt.push_str("\n\n");
-            }
-            prompt.push_str("Attached images:\n");
-            prompt.push_str(&image_lines.join("\n"));
-        }
-    }
-    prompt
-}
-
-async fn run_claude_prompt_once(
+pub(crate) async fn run_claude_prompt_once(
     cwd: &str,
     claude_bin: Option<String>,
     prompt: String,
@@ -1131,6 +1674,20 @@ pub(crate) struct PersistentSessionReaders {
     pub stderr: AsyncBufReader<tokio::process::ChildStderr>,
 }
 
+/// The `--permission-mode` for a UI access mode, or `None` to leave the CLI
+/// default in place.
+pub(crate) fn cli_permission_mode(mode: &str) -> Option<&str> {
+    let mode_trimmed = mode.trim();
+    match mode_trimmed {
+        "read-only" => Some("plan"),
+        "full-access" => Some("bypassPermissions"),
+        "current" => None, // Use CLI default
+        // Direct CLI modes pass through
+        "acceptEdits" | "bypassPermissions" | "default" | "delegate" | "dontAsk" | "plan" => Some(mode_trimmed),
+        _ => None, // Unknown modes are ignored
+    }
+}
+
 /// Spawns a persistent Claude CLI session with bidirectional streaming.
 ///
 /// This function spawns Claude CLI with streaming JSON input/output format,
@@ -1142,6 +1699,10 @@ pub(crate) struct PersistentSessionReaders {
 /// * `model` - Optional model to use
 /// * `access_mode` - Optional permission mode (e.g., "dontAsk", "askEdits", etc.)
 /// * `max_thinking_tokens` - Optional max thinking tokens for extended thinking
+/// * `sandbox` - OS sandbox applied when the session bypasses permissions
+/// * `guard_full_access` - Hold dangerous commands even when bypassing permissions
+/// * `route_edits` - Send edit prompts to the app so an edit scope can be enforced
+/// * `allowed_roots` - Additional dirs outside these workspace roots are skipped
 ///
 /// # Returns
 /// Readers for both stdout and stderr (the child process is stored in the session for cleanup)
@@ -1151,9 +1712,79 @@ pub(crate) async fn spawn_persistent_claude_session(
     model: Option<&str>,
     access_mode: Option<&str>,
     max_thinking_tokens: Option<u32>,
+    sandbox: Option<&SandboxSettings>,
+    guard_full_access: bool,
+    route_edits: bool,
+    allowed_roots: &[String],
 ) -> Result<PersistentSessionReaders, String> {
-    let mut command = build_claude_command_with_bin(session.claude_bin.clone());
-    command.current_dir(&session.entry.path);
+    let workspace_path = &session.entry.path;
+    let thread_cwd = session.thread_cwd(thread_id).await;
+    let cwd = thread_cwd
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(workspace_path));
+    let mut add_dirs = Vec::new();
+    if thread_cwd.is_some() {
+        // Keep the rest of the workspace reachable from a scoped thread
+        add_dirs.push(PathBuf::from(workspace_path));
+    }
+    for dir in &session.entry.settings.additional_dirs {
+        let path = resolve_additional_dir(workspace_path, dir);
+        if !path.is_dir() {
+            tracing::warn!(
+                "[spawn_persistent_claude_session] Skipping missing additional dir {}",
+                path.display()
+            );
+        } else if let Err(error) = ensure_allowed_workspace_path(&path, allowed_roots) {
+            tracing::warn!("[spawn_persistent_claude_session] Skipping additional dir: {error}");
+        } else {
+            add_dirs.push(path);
+        }
+    }
+    let container = session.entry.settings.container.as_ref();
+    let mut command = match container {
+        Some(container) => {
+            let extra_mounts: Vec<PathBuf> = add_dirs
+                .iter()
+                .filter(|path| !path.starts_with(workspace_path))
+                .cloned()
+                .collect();
+            let claude_home = resolve_default_claude_home();
+            build_container_claude_command(
+                container,
+                workspace_path,
+                &cwd,
+                &extra_mounts,
+                claude_home.as_deref(),
+            )
+        }
+        None => {
+            // Full-access sessions may opt into an OS sandbox; containers
+            // already isolate the CLI.
+            let sandbox = sandbox.filter(|sandbox| {
+                sandbox.enabled
+                    && access_mode.and_then(cli_permission_mode) == Some("bypassPermissions")
+            });
+            let mut command = match sandbox {
+                Some(sandbox) => {
+                    let writable = sandbox_writable_paths(sandbox, workspace_path, &add_dirs);
+                    build_sandboxed_claude_command(sandbox, session.claude_bin.clone(), &writable)?
+                }
+                None => build_claude_command_with_bin(session.claude_bin.clone()),
+            };
+            command.current_dir(&cwd);
+            command
+        }
+    };
+    for path in &add_dirs {
+        match container {
+            Some(container) => {
+                command
+                    .arg("--add-dir")
+                    .arg(container_path(container, workspace_path, path))
+            }
+            None => command.arg("--add-dir").arg(path),
+        };
+    }
 
     // Set up streaming JSON input/output format
     command.arg("--print");
@@ -1175,19 +1806,27 @@ pub(crate) async fn spawn_persistent_claude_session(
     // - "current" → skip (use CLI default)
     // - "full-access" → "bypassPermissions" (bypass all permission checks)
     // Also accept direct CLI modes: acceptEdits, bypassPermissions, default, delegate, dontAsk, plan
-    if let Some(mode) = access_mode {
-        let mode_trimmed = mode.trim();
-        let mapped_mode = match mode_trimmed {
-            "read-only" => Some("plan"),
-            "full-access" => Some("bypassPermissions"),
-            "current" => None, // Use CLI default
-            // Direct CLI modes pass through
-            "acceptEdits" | "bypassPermissions" | "default" | "delegate" | "dontAsk" | "plan" => Some(mode_trimmed),
-            _ => None, // Unknown modes are ignored
-        };
-        if let Some(cli_mode) = mapped_mode {
-            command.arg("--permission-mode").arg(cli_mode);
-        }
+    let mapped_mode = access_mode.and_then(cli_permission_mode);
+    // Guarded full access prompts so dangerous commands can be held, and
+    // edit-scoped sessions prompt for edits; see `dangerous_commands` and
+    // `edit_scope`.
+    let mapped_mode = match mapped_mode {
+        Some("acceptEdits" | "bypassPermissions") if route_edits => Some("default"),
+        Some("bypassPermissions") if guard_full_access => Some("acceptEdits"),
+        other => other,
+    };
+    if let Some(cli_mode) = mapped_mode {
+        command.arg("--permission-mode").arg(cli_mode);
+    }
+
+    // Route permission prompts over the stdio control protocol so the app can
+    // answer them in-flight. Modes that never prompt don't need it.
+    if !matches!(mapped_mode, Some("bypassPermissions") | Some("dontAsk")) {
+        command.arg("--permission-prompt-tool").arg("stdio");
+    } else if session.entry.settings.web_allowed_domains.is_some() {
+        // The egress policy is enforced on permission prompts, so web tools
+        // are unavailable to sessions that never prompt.
+        command.arg("--disallowedTools").arg(WEB_TOOLS.join(","));
     }
 
     // Set max thinking tokens (default to 31999, Claude's default)
@@ -1199,6 +1838,7 @@ pub(crate) async fn spawn_persistent_claude_session(
         command.arg("--resume").arg(thread_id);
     } else {
         command.arg("--session-id").arg(thread_id);
+        crate::retention::record_app_session(&session.entry.id, thread_id);
     }
 
     // Configure stdio for bidirectional communication
@@ -1211,6 +1851,10 @@ pub(crate) async fn spawn_persistent_claude_session(
         format!("Failed to spawn Claude CLI: {}", err)
     })?;
 
+    if let Some(pid) = child.id() {
+        record_spawned_process(pid, &session.entry.id, thread_id);
+    }
+
     // Take stdin for bidirectional communication
     let stdin = child.stdin.take().ok_or("Failed to capture stdin")?;
 
@@ -1224,17 +1868,15 @@ pub(crate) async fn spawn_persistent_claude_session(
 
     // Store the persistent session for this thread (stdin + child + permission_mode + model)
     // Convert access_mode to the CLI permission mode for storage
-    let stored_permission_mode = access_mode.map(|mode| {
-        match mode {
-            "read-only" => "plan".to_string(),
-            "full-access" => "bypassPermissions".to_string(),
-            "current" => "default".to_string(),
-            other => other.to_string(),
-        }
-    });
+    let permission_mode = access_mode.map(stored_permission_mode);
     // Store the model for detecting changes
     let stored_model = model.map(|m| m.to_string());
-    session.set_persistent_session(thread_id.to_string(), stdin, child, stored_permission_mode, stored_model).await;
+    session.set_persistent_session(thread_id.to_string(), stdin, child, permission_mode, stored_model).await;
+    if route_edits {
+        session
+            .mark_persistent_session_routes_edits(thread_id)
+            .await;
+    }
 
     Ok(PersistentSessionReaders {
         stdout: stdout_reader,
@@ -1242,6 +1884,17 @@ pub(crate) async fn spawn_persistent_claude_session(
     })
 }
 
+/// Permission mode stored on a persistent session for an app access mode,
+/// with "current" recorded as the CLI's "default".
+pub(crate) fn stored_permission_mode(access_mode: &str) -> String {
+    match access_mode {
+        "read-only" => "plan".to_string(),
+        "full-access" => "bypassPermissions".to_string(),
+        "current" => "default".to_string(),
+        other => other.to_string(),
+    }
+}
+
 /// Ensures a persistent session exists for the given workspace and thread.
 /// If no session exists for this thread, spawns one and starts the background stdout reader.
 ///
@@ -1250,7 +1903,7 @@ pub(crate) async fn spawn_persistent_claude_session(
 /// spawn duplicate sessions for the same thread.
 ///
 /// Returns the turn_id for the current turn.
-async fn ensure_persistent_session(
+pub(crate) async fn ensure_persistent_session(
     workspace_id: &str,
     session: &Arc<WorkspaceSession>,
     thread_id: &str,
@@ -1263,20 +1916,21 @@ async fn ensure_persistent_session(
     let _init_guard = session.session_init_lock.lock().await;
 
     // Convert requested access_mode to CLI permission mode for comparison
-    let requested_permission_mode = access_mode.map(|mode| {
-        match mode {
-            "read-only" => "plan".to_string(),
-            "full-access" => "bypassPermissions".to_string(),
-            "current" => "default".to_string(),
-            other => other.to_string(),
-        }
-    });
+    let requested_permission_mode = access_mode.map(stored_permission_mode);
 
     // Convert requested model for comparison (normalize empty strings to None)
     let requested_model = model
         .filter(|m| !m.trim().is_empty())
         .map(|m| m.to_string());
 
+    // Modes that approve edits by themselves route them to the app while the
+    // thread has an edit scope; see `edit_scope`.
+    let route_edits = session.edit_scope(thread_id).await.is_some()
+        && matches!(
+            requested_permission_mode.as_deref(),
+            Some("acceptEdits" | "bypassPermissions")
+        );
+
     // Check if a persistent session already exists for THIS thread
     if session.has_persistent_session(thread_id).await {
         // Check if permission mode changed - if so, we need to restart the session
@@ -1290,12 +1944,14 @@ async fn ensure_persistent_session(
 
         let permission_mode_changed = current_mode != requested_mode;
         let model_changed = current_model != requested_model;
+        let edit_routing_missing =
+            route_edits && !session.persistent_session_routes_edits(thread_id).await;
 
         if permission_mode_changed {
             // Permission mode changed - kill the old session and spawn a new one
             // This follows Claude CLI behavior: permission mode is per-process,
             // so changing it requires starting a new process with --resume
-            eprintln!(
+            tracing::info!(
                 "[ensure_persistent_session] Permission mode changed from '{}' to '{}' for thread {}, restarting session",
                 current_mode, requested_mode, thread_id
             );
@@ -1304,11 +1960,19 @@ async fn ensure_persistent_session(
             // Model changed - kill the old session and spawn a new one
             // This follows Claude CLI behavior: model is per-process,
             // so changing it requires starting a new process with --resume --model
-            eprintln!(
+            tracing::info!(
                 "[ensure_persistent_session] Model changed from '{:?}' to '{:?}' for thread {}, restarting session",
                 current_model, requested_model, thread_id
             );
             session.kill_persistent_session(thread_id).await?;
+        } else if edit_routing_missing {
+            // An edit scope is enforced on permission prompts, which this
+            // process skips for edits
+            tracing::info!(
+                "[ensure_persistent_session] Edit scope set for thread {}, restarting session to route edits",
+                thread_id
+            );
+            session.kill_persistent_session(thread_id).await?;
         } else {
             // Session exists with same permission mode and model, just return a new turn_id
             return Ok(Uuid::new_v4().to_string());
@@ -1318,7 +1982,28 @@ async fn ensure_persistent_session(
     let turn_id = Uuid::new_v4().to_string();
 
     // Spawn a new persistent session for this thread
-    let readers = spawn_persistent_claude_session(session, thread_id, model, access_mode, max_thinking_tokens).await?;
+    let (sandbox, guard_full_access, allowed_roots) = {
+        let state = event_sink.app_handle().state::<AppState>();
+        let settings = state.app_settings.lock().await;
+        let patterns = active_patterns(&settings);
+        (
+            settings.full_access_sandbox.clone(),
+            !patterns.is_empty(),
+            settings.allowed_workspace_roots.clone(),
+        )
+    };
+    let readers = spawn_persistent_claude_session(
+        session,
+        thread_id,
+        model,
+        access_mode,
+        max_thinking_tokens,
+        sandbox.as_ref(),
+        guard_full_access,
+        route_edits,
+        &allowed_roots,
+    )
+    .await?;
 
     // Spawn background task to read stdout and emit events
     let workspace_id_owned = workspace_id.to_string();
@@ -1373,6 +2058,7 @@ async fn read_persistent_stdout(
     let mut last_model: Option<String> = None;
     let mut tool_names: HashMap<String, String> = HashMap::new();
     let mut tool_inputs: HashMap<String, Value> = HashMap::new();
+    let mut turn_tool_calls: Vec<(String, Value)> = Vec::new();
     let mut tool_counter: usize = 0;
     let mut thinking_counter: usize = 0;
     let mut request_id_counter: u64 = 0;
@@ -1396,7 +2082,24 @@ async fn read_persistent_stdout(
                             "turn": { "id": current_turn_id, "threadId": thread_id },
                         }),
                     );
+                    session.notify_turn_completed(
+                        &thread_id,
+                        &current_turn_id,
+                        "interrupted",
+                        None,
+                    );
+                } else {
+                    finish_pending_turn(
+                        &session,
+                        &event_sink,
+                        &workspace_id,
+                        &thread_id,
+                        "interrupted",
+                        None,
+                    )
+                    .await;
                 }
+                session.end_live_turn(&thread_id).await;
                 break;
             }
             Ok(_) => {
@@ -1418,6 +2121,21 @@ async fn read_persistent_stdout(
                 let event_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
                 let subtype = value.get("subtype").and_then(|v| v.as_str()).unwrap_or("");
 
+                // Control protocol requests from the CLI (permission prompts)
+                if event_type == "control_request" || event_type == "control_cancel_request" {
+                    handle_control_request(
+                        &value,
+                        &workspace_id,
+                        &thread_id,
+                        &current_turn_id,
+                        &session,
+                        &event_sink,
+                        &mut request_id_counter,
+                    )
+                    .await;
+                    continue;
+                }
+
                 // Handle system init event
                 if event_type == "system" {
                     if subtype == "init" {
@@ -1434,6 +2152,9 @@ async fn read_persistent_stdout(
                             .get("tools")
                             .and_then(|v| v.as_array())
                             .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect::<Vec<_>>());
+                        if let Some(tools) = &tools {
+                            session.set_reported_tools(tools.clone()).await;
+                        }
 
                         // Emit session initialized event
                         emit_event(
@@ -1468,9 +2189,11 @@ async fn read_persistent_stdout(
                     last_model = None;
                     tool_names.clear();
                     tool_inputs.clear();
+                    turn_tool_calls.clear();
                     tool_counter = 0;
                     thinking_counter = 0;
                     permission_denial_ids.clear();
+                    session.begin_live_turn(&thread_id, &current_turn_id, &item_id).await;
 
                     emit_event(
                         &event_sink,
@@ -1548,6 +2271,7 @@ async fn read_persistent_stdout(
                                     tool_names.insert(tool_id.to_string(), tool_name.clone());
                                     tool_inputs.insert(tool_id.to_string(), tool_input.clone());
                                 }
+                                turn_tool_calls.push((tool_name.clone(), tool_input.clone()));
                                 let item_id_tool = if tool_id.is_empty() {
                                     tool_counter += 1;
                                     format!("{current_turn_id}-tool-{tool_counter}")
@@ -1592,32 +2316,56 @@ async fn read_persistent_stdout(
                                     emit_event_with_id(
                                         &event_sink,
                                         &workspace_id,
-                                        "item/tool/requestUserInput",
-                                        request_id_counter,
+                                        "item/tool/requestUserInput",
+                                        request_id_counter,
+                                        json!({
+                                            "threadId": thread_id,
+                                            "turnId": current_turn_id,
+                                            "itemId": item_id_tool,
+                                            "toolUseId": tool_id,
+                                            "questions": questions,
+                                        }),
+                                    );
+                                }
+                                // TodoWrite replaces the whole list; publish it as the turn plan
+                                if is_todo_write(&tool_name) {
+                                    let todos = parse_todo_entries(&tool_input);
+                                    let plan = todos
+                                        .iter()
+                                        .map(|todo| json!({ "step": todo["content"], "status": todo["status"] }))
+                                        .collect::<Vec<_>>();
+                                    emit_event(
+                                        &event_sink,
+                                        &workspace_id,
+                                        "turn/plan/updated",
                                         json!({
                                             "threadId": thread_id,
                                             "turnId": current_turn_id,
                                             "itemId": item_id_tool,
-                                            "toolUseId": tool_id,
-                                            "questions": questions,
+                                            "explanation": Value::Null,
+                                            "plan": plan,
+                                            "todos": todos,
                                         }),
                                     );
                                 }
 
+                                let tool_item = build_tool_item(
+                                    &item_id_tool,
+                                    &tool_name,
+                                    &tool_input,
+                                    "running",
+                                    None,
+                                    None,
+                                    None,
+                                );
+                                session.add_live_tool(&thread_id, tool_item.clone()).await;
                                 emit_event(
                                     &event_sink,
                                     &workspace_id,
                                     "item/started",
                                     json!({
                                         "threadId": thread_id,
-                                        "item": build_tool_item(
-                                            &item_id_tool,
-                                            &tool_name,
-                                            &tool_input,
-                                            "running",
-                                            None,
-                                            None,
-                                        ),
+                                        "item": tool_item,
                                     }),
                                 );
                             }
@@ -1642,6 +2390,7 @@ async fn read_persistent_stdout(
                                     }),
                                 );
                                 last_text = full_text.clone();
+                                session.update_live_turn_text(&thread_id, &item_id, &full_text).await;
                             }
                         }
                         if let Some(usage) = message.get("usage") {
@@ -1660,13 +2409,16 @@ async fn read_persistent_stdout(
                                     .or_else(|| entry.get("toolUseId"))
                                     .and_then(|v| v.as_str())
                                     .unwrap_or("");
-                                let content_value = entry.get("content").cloned().unwrap_or(Value::Null);
+                                let (content_value, images) = extract_tool_result_images(
+                                    &workspace_id,
+                                    &entry.get("content").cloned().unwrap_or(Value::Null),
+                                );
                                 let mut output = tool_result_output(&content_value);
                                 let is_error = entry
                                     .get("is_error")
                                     .and_then(|v| v.as_bool())
                                     .unwrap_or(false);
-                                if output.trim().is_empty() {
+                                if output.trim().is_empty() && images.is_empty() {
                                     if let Some(fallback) = value
                                         .get("toolUseResult")
                                         .or_else(|| value.get("tool_use_result"))
@@ -1720,19 +2472,28 @@ async fn read_persistent_stdout(
                                 } else {
                                     tool_use_id.to_string()
                                 };
+                                session.finish_live_tool(&thread_id, &item_id_result).await;
+                                let output_limit = event_output_limit(&event_sink).await;
                                 emit_event(
                                     &event_sink,
                                     &workspace_id,
                                     "item/completed",
                                     json!({
                                         "threadId": thread_id,
-                                        "item": build_tool_item(
-                                            &item_id_result,
-                                            &command,
-                                            &tool_input,
-                                            "completed",
-                                            Some(output.as_str()),
-                                            Some(&result_value),
+                                        "item": truncate_item_output(
+                                            with_tool_images(
+                                                build_tool_item(
+                                                    &item_id_result,
+                                                    &command,
+                                                    &tool_input,
+                                                    "completed",
+                                                    Some(output.as_str()),
+                                                    Some(&result_value),
+                                                    tool_use_result(&value),
+                                                ),
+                                                images,
+                                            ),
+                                            output_limit,
                                         ),
                                     }),
                                 );
@@ -1802,8 +2563,43 @@ async fn read_persistent_stdout(
                     }
 
                     // Result event signals end of turn
-                    if turn_active {
-                        if let Some(usage) = last_usage.take().and_then(|u| format_token_usage(u, last_model_usage.as_ref())) {
+                    let is_error = value
+                        .get("is_error")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false)
+                        || subtype.starts_with("error");
+                    let status = if is_error { "failed" } else { "completed" };
+                    let cost_usd = value
+                        .get("total_cost_usd")
+                        .or_else(|| value.get("totalCostUsd"))
+                        .and_then(|v| v.as_f64());
+                    if is_error {
+                        crate::metrics::record_turn_error();
+                    }
+                    if !turn_active {
+                        finish_pending_turn(
+                            &session,
+                            &event_sink,
+                            &workspace_id,
+                            &thread_id,
+                            status,
+                            cost_usd,
+                        )
+                        .await;
+                    } else {
+                        if let Some(last) =
+                            last_usage.take().as_ref().and_then(turn_token_breakdown)
+                        {
+                            let models = model_turn_usage(last_model_usage.as_ref());
+                            let per_model: Vec<(String, TokenBreakdown)> = models
+                                .iter()
+                                .map(|entry| (entry.model.clone(), entry.usage))
+                                .collect();
+                            let totals = session
+                                .add_turn_token_usage(&thread_id, &last, &per_model)
+                                .await;
+                            let usage =
+                                format_token_usage(&last, &models, &totals, last_model.as_deref());
                             emit_event(
                                 &event_sink,
                                 &workspace_id,
@@ -1838,8 +2634,29 @@ async fn read_persistent_stdout(
                                 "turn": { "id": current_turn_id, "threadId": thread_id },
                             }),
                         );
+                        session.notify_turn_completed(
+                            &thread_id,
+                            &current_turn_id,
+                            status,
+                            cost_usd,
+                        );
+                        record_turn_report(
+                            &event_sink,
+                            build_turn_report(
+                                &workspace_id,
+                                &thread_id,
+                                &current_turn_id,
+                                status,
+                                &turn_tool_calls,
+                                &value,
+                            ),
+                        );
+                        if status == "completed" {
+                            summarize_thread(&event_sink, &session, &workspace_id, &thread_id);
+                        }
 
                         turn_active = false;
+                        session.end_live_turn(&thread_id).await;
                     }
                 }
             }
@@ -1855,11 +2672,397 @@ async fn read_persistent_stdout(
                             "turn": { "id": current_turn_id, "threadId": thread_id },
                         }),
                     );
+                    session.notify_turn_completed(
+                        &thread_id,
+                        &current_turn_id,
+                        "interrupted",
+                        None,
+                    );
+                } else {
+                    finish_pending_turn(
+                        &session,
+                        &event_sink,
+                        &workspace_id,
+                        &thread_id,
+                        "interrupted",
+                        None,
+                    )
+                    .await;
                 }
+                session.end_live_turn(&thread_id).await;
                 break;
             }
         }
     }
+
+    // The process is gone, so any prompt still on screen can no longer be answered.
+    for tool_use_id in session.clear_permission_requests(&thread_id).await {
+        emit_event(
+            &event_sink,
+            &workspace_id,
+            "item/tool/approvalCancelled",
+            json!({ "threadId": thread_id, "toolUseId": tool_use_id }),
+        );
+    }
+}
+
+/// Report a turn that ended before the reader saw any assistant output, so its
+/// scheduler slot and anyone waiting on it are released.
+async fn finish_pending_turn(
+    session: &WorkspaceSession,
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    thread_id: &str,
+    status: &str,
+    cost_usd: Option<f64>,
+) {
+    let Some(turn_id) = session.complete_pending_turn(thread_id, status, cost_usd).await else {
+        return;
+    };
+    emit_event(
+        event_sink,
+        workspace_id,
+        "turn/completed",
+        json!({
+            "threadId": thread_id,
+            "turn": { "id": turn_id, "threadId": thread_id },
+        }),
+    );
+}
+
+/// Build the `can_use_tool` answer from the decision the app sent back.
+///
+/// Accepts `{ "decision": "accept" | "decline" | "cancel", "message"?, "updatedInput"? }`.
+fn build_permission_response(result: &Value, tool_input: &Value) -> Value {
+    let decision = result
+        .get("decision")
+        .and_then(|v| v.as_str())
+        .unwrap_or("decline");
+    if matches!(decision, "accept" | "acceptForSession" | "allow") {
+        let updated_input = result
+            .get("updatedInput")
+            .filter(|v| v.is_object())
+            .cloned()
+            .unwrap_or_else(|| tool_input.clone());
+        return json!({ "behavior": "allow", "updatedInput": updated_input });
+    }
+    let message = result
+        .get("message")
+        .and_then(|v| v.as_str())
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .unwrap_or("The user denied this tool call.");
+    let mut response = json!({ "behavior": "deny", "message": message });
+    if decision == "cancel" {
+        response["interrupt"] = Value::Bool(true);
+    }
+    response
+}
+
+fn timeout_decision_result(decision: &str, timeout_secs: u64) -> Value {
+    let decision = if decision == "accept" { "accept" } else { "decline" };
+    json!({
+        "decision": decision,
+        "message": format!("No one answered this permission prompt within {timeout_secs}s."),
+    })
+}
+
+/// Settle a permission prompt nobody answered with the configured default
+/// decision, so an unattended remote session doesn't hang forever.
+fn spawn_permission_timeout(
+    session: Arc<WorkspaceSession>,
+    event_sink: TauriEventSink,
+    workspace_id: String,
+    tool_use_id: String,
+) {
+    tauri::async_runtime::spawn(async move {
+        let (timeout_secs, decision) = {
+            let state = event_sink.app_handle().state::<AppState>();
+            let settings = state.app_settings.lock().await;
+            (
+                settings.approval_timeout_secs,
+                settings.approval_timeout_decision.clone(),
+            )
+        };
+        if timeout_secs == 0 {
+            return;
+        }
+        sleep(Duration::from_secs(timeout_secs)).await;
+        let Some(pending) = session.take_permission_request(&tool_use_id).await else {
+            return;
+        };
+        // A held dangerous command is never approved by default.
+        let decision = if pending.dangerous_pattern.is_some() {
+            "decline"
+        } else {
+            decision.as_str()
+        };
+        let result = timeout_decision_result(decision, timeout_secs);
+        let response = build_permission_response(&result, &pending.tool_input);
+        if let Err(error) = session
+            .send_control_response(&pending.thread_id, &pending.request_id, response)
+            .await
+        {
+            tracing::warn!("[spawn_permission_timeout] Failed to answer {tool_use_id}: {error}");
+        }
+        emit_event(
+            &event_sink,
+            &workspace_id,
+            "item/tool/approvalCancelled",
+            json!({
+                "threadId": pending.thread_id,
+                "toolUseId": tool_use_id,
+                "reason": "timeout",
+                "decision": result["decision"],
+            }),
+        );
+    });
+}
+
+/// Handle a control protocol message from the CLI.
+///
+/// `can_use_tool` prompts are surfaced as `item/tool/requestApproval` and held until
+/// `respond_to_server_request` answers them. ExitPlanMode is surfaced as
+/// `plan/proposed` and held until `approve_plan` / `reject_plan` answers it.
+/// AskUserQuestion has its own UI, so it is settled here.
+async fn handle_control_request(
+    value: &Value,
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    session: &Arc<WorkspaceSession>,
+    event_sink: &TauriEventSink,
+    request_id_counter: &mut u64,
+) {
+    let request_id = value
+        .get("request_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    if request_id.is_empty() {
+        return;
+    }
+
+    if value.get("type").and_then(|v| v.as_str()) == Some("control_cancel_request") {
+        let cancelled = {
+            let pending = session.pending_permissions.lock().await;
+            pending
+                .iter()
+                .find(|(_, request)| request.request_id == request_id)
+                .map(|(tool_use_id, _)| tool_use_id.clone())
+        };
+        if let Some(tool_use_id) = cancelled {
+            session.take_permission_request(&tool_use_id).await;
+            emit_event(
+                event_sink,
+                workspace_id,
+                "item/tool/approvalCancelled",
+                json!({ "threadId": thread_id, "toolUseId": tool_use_id }),
+            );
+        }
+        return;
+    }
+
+    let request = value.get("request").cloned().unwrap_or(Value::Null);
+    if request.get("subtype").and_then(|v| v.as_str()) != Some("can_use_tool") {
+        // Unsupported control requests must still be answered or the CLI will wait.
+        let message = json!({ "behavior": "deny", "message": "Unsupported control request." });
+        let _ = session.send_control_response(thread_id, &request_id, message).await;
+        return;
+    }
+
+    let tool_name = request
+        .get("tool_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let mut tool_input = request.get("input").cloned().unwrap_or(Value::Null);
+    let tool_use_id = request
+        .get("tool_use_id")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .unwrap_or(request_id.as_str())
+        .to_string();
+
+    if tool_name == "AskUserQuestion" {
+        let response = json!({ "behavior": "allow", "updatedInput": tool_input });
+        if let Err(error) = session.send_control_response(thread_id, &request_id, response).await {
+            tracing::warn!("[handle_control_request] Failed to answer {tool_name}: {error}");
+        }
+        return;
+    }
+
+    if tool_name == "ExitPlanMode" {
+        // No approval timeout: a default decision would settle a plan nobody read.
+        session
+            .register_permission_request(
+                tool_use_id.clone(),
+                PendingPermissionRequest {
+                    thread_id: thread_id.to_string(),
+                    request_id: request_id.clone(),
+                    tool_name: tool_name.clone(),
+                    tool_input: tool_input.clone(),
+                    dangerous_pattern: None,
+                },
+            )
+            .await;
+        emit_event(
+            event_sink,
+            workspace_id,
+            "plan/proposed",
+            json!({
+                "threadId": thread_id,
+                "turnId": turn_id,
+                "itemId": tool_use_id,
+                "toolUseId": tool_use_id,
+                "plan": extract_proposed_plan(&tool_input).unwrap_or_default(),
+            }),
+        );
+        return;
+    }
+
+    if let Some(allowlist) = &session.entry.settings.web_allowed_domains {
+        match check_tool_egress(&tool_name, &tool_input, allowlist) {
+            Some(EgressCheck::Blocked(domain)) => {
+                let response = json!({
+                    "behavior": "deny",
+                    "message": format!("Network access to {domain} is not allowed in this workspace."),
+                });
+                if let Err(error) = session
+                    .send_control_response(thread_id, &request_id, response)
+                    .await
+                {
+                    tracing::warn!("[handle_control_request] Failed to deny {tool_name}: {error}");
+                }
+                emit_policy_violation(
+                    event_sink,
+                    workspace_id,
+                    thread_id,
+                    &tool_use_id,
+                    &tool_name,
+                    &domain,
+                );
+                return;
+            }
+            Some(EgressCheck::Allowed(input)) => tool_input = input,
+            None => {}
+        }
+    }
+
+    if is_file_change_tool(&tool_name) {
+        if let Some(scope) = session.edit_scope(thread_id).await {
+            let workspace_path = &session.entry.path;
+            let target = edit_target(workspace_path, &tool_name, &tool_input);
+            if !target
+                .as_deref()
+                .is_some_and(|target| path_in_edit_scope(workspace_path, &scope, target))
+            {
+                let target = target.map_or("This file".to_string(), |t| t.display().to_string());
+                let response = json!({
+                    "behavior": "deny",
+                    "message": format!(
+                        "{target} is outside this turn's edit scope ({}). Only change files matching it.",
+                        scope.join(", ")
+                    ),
+                });
+                if let Err(error) = session
+                    .send_control_response(thread_id, &request_id, response)
+                    .await
+                {
+                    tracing::warn!("[handle_control_request] Failed to deny {tool_name}: {error}");
+                }
+                return;
+            }
+        }
+    }
+
+    // bypassPermissions sessions only ask when guarded or edit-scoped; approve
+    // everything but dangerous commands. acceptEdits sessions only ask about
+    // edits when edit-scoped, and the edit is in scope by now.
+    let stored_mode = session
+        .get_persistent_session_permission_mode(thread_id)
+        .await;
+    if stored_mode.as_deref() == Some("acceptEdits")
+        && is_file_change_tool(&tool_name)
+        && session.persistent_session_routes_edits(thread_id).await
+    {
+        let response = json!({ "behavior": "allow", "updatedInput": tool_input });
+        if let Err(error) = session
+            .send_control_response(thread_id, &request_id, response)
+            .await
+        {
+            tracing::warn!("[handle_control_request] Failed to approve {tool_name}: {error}");
+        }
+        return;
+    }
+    let mut dangerous_pattern = None;
+    if stored_mode.as_deref() == Some("bypassPermissions") {
+        let patterns = {
+            let state = event_sink.app_handle().state::<AppState>();
+            let settings = state.app_settings.lock().await;
+            active_patterns(&settings)
+        };
+        dangerous_pattern = tool_input
+            .get("command")
+            .and_then(|v| v.as_str())
+            .filter(|_| tool_name == "Bash")
+            .and_then(|command| match_dangerous_command(command, &patterns));
+        let Some(pattern) = &dangerous_pattern else {
+            let response = json!({ "behavior": "allow", "updatedInput": tool_input });
+            if let Err(error) = session
+                .send_control_response(thread_id, &request_id, response)
+                .await
+            {
+                tracing::warn!("[handle_control_request] Failed to approve {tool_name}: {error}");
+            }
+            return;
+        };
+        tracing::info!(
+            "[handle_control_request] Holding {tool_use_id} in {thread_id} for approval: matched `{pattern}`"
+        );
+        crate::audit::record(
+            "dangerous_command_held",
+            Some(workspace_id),
+            json!({ "threadId": thread_id, "toolUseId": tool_use_id, "pattern": pattern }),
+            &Ok::<(), String>(()),
+        );
+    }
+
+    session
+        .register_permission_request(
+            tool_use_id.clone(),
+            PendingPermissionRequest {
+                thread_id: thread_id.to_string(),
+                request_id: request_id.clone(),
+                tool_name: tool_name.clone(),
+                tool_input: tool_input.clone(),
+                dangerous_pattern: dangerous_pattern.clone(),
+            },
+        )
+        .await;
+    spawn_permission_timeout(
+        session.clone(),
+        event_sink.clone(),
+        workspace_id.to_string(),
+        tool_use_id.clone(),
+    );
+    *request_id_counter += 1;
+    emit_event_with_id(
+        event_sink,
+        workspace_id,
+        "item/tool/requestApproval",
+        *request_id_counter,
+        json!({
+            "threadId": thread_id,
+            "turnId": turn_id,
+            "itemId": tool_use_id,
+            "toolUseId": tool_use_id,
+            "toolName": tool_name,
+            "toolInput": tool_input,
+            "suggestions": request.get("permission_suggestions").cloned().unwrap_or(Value::Null),
+            "dangerousPattern": dangerous_pattern,
+        }),
+    );
 }
 
 /// Background task that reads stderr from the persistent Claude CLI session
@@ -1925,15 +3128,19 @@ fn emit_event_with_id(event_sink: &TauriEventSink, workspace_id: &str, method: &
     });
 }
 
-fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<Value, String> {
-    let session_path = if let Some((parent_id, agent_id)) =
-        parse_subagent_thread_id(thread_id)
-    {
+/// The JSONL transcript behind a thread, whether a session or a subagent.
+pub(crate) fn resolve_thread_transcript(entry: &WorkspaceEntry, thread_id: &str) -> Option<PathBuf> {
+    if let Some((parent_id, agent_id)) = parse_subagent_thread_id(thread_id) {
         resolve_subagent_path(entry, &parent_id, &agent_id)
     } else {
         resolve_session_path(entry, thread_id)
     }
-    .ok_or_else(|| "Session file not found".to_string())?;
+}
+
+fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<Value, String> {
+    let workspace_id = entry.id.as_str();
+    let session_path = resolve_thread_transcript(entry, thread_id)
+        .ok_or_else(|| "Session file not found".to_string())?;
     let file = File::open(&session_path).map_err(|err| err.to_string())?;
     let reader = BufReader::new(file);
     let mut items: Vec<Value> = Vec::new();
@@ -1944,18 +3151,26 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
     let mut preview: Option<String> = None;
     let mut created_at: Option<i64> = None;
     let mut updated_at: Option<i64> = None;
+    // Lines the CLI will also choke on when resuming; see verify_session.
+    let mut malformed_lines: usize = 0;
 
     for line in reader.lines() {
         let line = match line {
             Ok(line) => line,
-            Err(_) => continue,
+            Err(_) => {
+                malformed_lines += 1;
+                continue;
+            }
         };
         if line.trim().is_empty() {
             continue;
         }
         let value: Value = match serde_json::from_str(&line) {
             Ok(value) => value,
-            Err(_) => continue,
+            Err(_) => {
+                malformed_lines += 1;
+                continue;
+            }
         };
         let event_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
         if event_type != "user" && event_type != "assistant" {
@@ -1984,7 +3199,7 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
                 items.push(json!({
                     "id": value.get("uuid").and_then(|v| v.as_str()).unwrap_or(thread_id),
                     "type": "userMessage",
-                    "content": content.clone(),
+                    "content": cache_user_message_images(workspace_id, &content),
                 }));
             }
             for entry in content.iter() {
@@ -1996,9 +3211,12 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
                     .or_else(|| entry.get("toolUseId"))
                     .and_then(|v| v.as_str())
                     .unwrap_or("");
-                let content_value = entry.get("content").cloned().unwrap_or(Value::Null);
+                let (content_value, images) = extract_tool_result_images(
+                    workspace_id,
+                    &entry.get("content").cloned().unwrap_or(Value::Null),
+                );
                 let mut output = tool_result_output(&content_value);
-                if output.trim().is_empty() {
+                if output.trim().is_empty() && images.is_empty() {
                     if let Some(fallback) = value
                         .get("toolUseResult")
                         .or_else(|| value.get("tool_use_result"))
@@ -2030,13 +3248,17 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
                     tool_use_id.to_string()
                 };
                 let item_id = id.clone();
-                let item = build_tool_item(
-                    &id,
-                    &command,
-                    &tool_input,
-                    "completed",
-                    Some(output.as_str()),
-                    Some(&result_value),
+                let item = with_tool_images(
+                    build_tool_item(
+                        &id,
+                        &command,
+                        &tool_input,
+                        "completed",
+                        Some(output.as_str()),
+                        Some(&result_value),
+                        tool_use_result(&value),
+                    ),
+                    images,
                 );
                 if let Some(index) = tool_item_indices.get(&item_id) {
                     items[*index] = item;
@@ -2110,6 +3332,7 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
                         "running",
                         None,
                         None,
+                        None,
                     );
                     if let Some(index) = tool_item_indices.get(&item_id) {
                         items[*index] = item;
@@ -2135,6 +3358,8 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
         }
     }
 
+    link_subagent_items(entry, thread_id, &mut items);
+
     let metadata = load_sessions_index(entry)
         .into_iter()
         .find(|entry| entry.session_id == thread_id);
@@ -2159,6 +3384,7 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
         "createdAt": created_at,
         "updatedAt": updated_at,
         "cwd": entry.path,
+        "malformedLines": malformed_lines,
         "turns": [
             {
                 "id": thread_id,
@@ -2168,23 +3394,38 @@ fn build_thread_from_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<
     }))
 }
 
+/// (thread id, preview, updated at) for every indexed session of a workspace.
+pub(crate) fn thread_previews(entry: &WorkspaceEntry) -> Vec<(String, String, i64)> {
+    load_sessions_index(entry)
+        .into_iter()
+        .map(|session| {
+            let updated_at = session_sort_key(&session);
+            (
+                session.session_id,
+                session.first_prompt.unwrap_or_default(),
+                updated_at,
+            )
+        })
+        .collect()
+}
+
 fn load_sessions_index(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
     let index_path = resolve_sessions_index_path(entry);
     let mut entries = match &index_path {
         Some(path) => {
-            eprintln!("[debug:sessions] Loading sessions index from {:?}", path);
+            tracing::debug!("[debug:sessions] Loading sessions index from {:?}", path);
             match fs::read_to_string(path) {
                 Ok(data) => match serde_json::from_str::<Value>(&data) {
                     Ok(value) => {
                         let parsed = parse_sessions_value(&value);
-                        eprintln!(
+                        tracing::debug!(
                             "[debug:sessions] Parsed {} entries from sessions index",
                             parsed.len()
                         );
                         parsed
                     }
                     Err(err) => {
-                        eprintln!(
+                        tracing::debug!(
                             "[debug:sessions] Failed to parse sessions index JSON at {:?}: {}",
                             path, err
                         );
@@ -2192,7 +3433,7 @@ fn load_sessions_index(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
                     }
                 },
                 Err(err) => {
-                    eprintln!(
+                    tracing::debug!(
                         "[debug:sessions] Failed to read sessions index at {:?}: {}",
                         path, err
                     );
@@ -2201,7 +3442,7 @@ fn load_sessions_index(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
             }
         }
         None => {
-            eprintln!(
+            tracing::debug!(
                 "[debug:sessions] No sessions index found for workspace {:?}, falling back to filesystem scan",
                 entry.path
             );
@@ -2211,14 +3452,14 @@ fn load_sessions_index(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
 
     let scanned = scan_project_sessions(entry);
     if entries.is_empty() {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] Index was empty, using {} scanned entries only",
             scanned.len()
         );
         return scanned;
     }
 
-    eprintln!(
+    tracing::debug!(
         "[debug:sessions] Merging {} index entries with {} scanned entries",
         entries.len(),
         scanned.len()
@@ -2259,7 +3500,7 @@ fn load_sessions_index(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
         }
     }
 
-    eprintln!(
+    tracing::debug!(
         "[debug:sessions] Merge complete: {} total sessions after merging index + scan",
         merged.len()
     );
@@ -2291,7 +3532,7 @@ fn parse_sessions_entries(entries: &[Value]) -> Vec<ClaudeSessionEntry> {
                     .or_else(|| entry.get("session_id"))
                     .and_then(|v| v.as_str())
                     .unwrap_or("<unknown>");
-                eprintln!(
+                tracing::debug!(
                     "[debug:sessions] Failed to deserialize session entry '{}': {} | raw keys: {:?}",
                     session_id,
                     err,
@@ -2301,7 +3542,7 @@ fn parse_sessions_entries(entries: &[Value]) -> Vec<ClaudeSessionEntry> {
         }
     }
     if skipped > 0 {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] Skipped {} of {} entries due to deserialization failures",
             skipped,
             entries.len()
@@ -2312,18 +3553,18 @@ fn parse_sessions_entries(entries: &[Value]) -> Vec<ClaudeSessionEntry> {
 
 fn scan_project_sessions(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
     let Some(project_dir) = resolve_project_dir(entry) else {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] Could not resolve project dir for workspace {:?}",
             entry.path
         );
         return Vec::new();
     };
-    eprintln!("[debug:sessions] Scanning project sessions in {:?}", project_dir);
+    tracing::debug!("[debug:sessions] Scanning project sessions in {:?}", project_dir);
     let mut entries = Vec::new();
     let dir_entries = match fs::read_dir(&project_dir) {
         Ok(dir_entries) => dir_entries,
         Err(err) => {
-            eprintln!(
+            tracing::debug!(
                 "[debug:sessions] Failed to read project directory {:?}: {}",
                 project_dir, err
             );
@@ -2358,7 +3599,7 @@ fn scan_project_sessions(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
             is_sidechain: Some(false),
         });
     }
-    eprintln!(
+    tracing::debug!(
         "[debug:sessions] Filesystem scan found {} .jsonl session files in {:?}",
         entries.len(),
         project_dir
@@ -2366,7 +3607,7 @@ fn scan_project_sessions(entry: &WorkspaceEntry) -> Vec<ClaudeSessionEntry> {
     entries
 }
 
-fn list_session_files(entry: &WorkspaceEntry) -> Vec<(String, PathBuf, i64)> {
+pub(crate) fn list_session_files(entry: &WorkspaceEntry) -> Vec<(String, PathBuf, i64)> {
     let Some(project_dir) = resolve_project_dir(entry) else {
         return Vec::new();
     };
@@ -2400,7 +3641,7 @@ fn scan_session_metadata(path: &Path) -> (Option<String>, Option<i64>, Option<St
     let file = match File::open(path) {
         Ok(file) => file,
         Err(err) => {
-            eprintln!(
+            tracing::debug!(
                 "[debug:sessions] Failed to open session file {:?}: {}",
                 path, err
             );
@@ -2421,7 +3662,7 @@ fn scan_session_metadata(path: &Path) -> (Option<String>, Option<i64>, Option<St
             Err(err) => {
                 line_errors += 1;
                 if line_errors == 1 {
-                    eprintln!(
+                    tracing::debug!(
                         "[debug:sessions] Read error in session file {:?} at line {}: {}",
                         path, total_lines, err
                     );
@@ -2437,7 +3678,7 @@ fn scan_session_metadata(path: &Path) -> (Option<String>, Option<i64>, Option<St
             Err(err) => {
                 json_errors += 1;
                 if json_errors == 1 {
-                    eprintln!(
+                    tracing::debug!(
                         "[debug:sessions] JSON parse error in session file {:?} at line {}: {}",
                         path, total_lines, err
                     );
@@ -2466,7 +3707,7 @@ fn scan_session_metadata(path: &Path) -> (Option<String>, Option<i64>, Option<St
     }
 
     if line_errors > 0 || json_errors > 0 {
-        eprintln!(
+        tracing::debug!(
             "[debug:sessions] Session file {:?}: {} total lines, {} read errors, {} JSON parse errors",
             path, total_lines, line_errors, json_errors
         );
@@ -2489,12 +3730,28 @@ fn subagent_thread_id(parent_id: &str, agent_id: &str) -> String {
     format!("{parent_id}{SUBAGENT_THREAD_MARKER}{agent_id}")
 }
 
+/// Identifies a transcript file regardless of how deeply it is nested.
+fn subagent_key(session_id: &str, agent_id: &str) -> String {
+    format!("{session_id}/{agent_id}")
+}
+
+/// Splits a subagent thread id into its session id and innermost agent id.
+/// Nested subagents chain the marker, e.g. `session::subagent::a::subagent::b`,
+/// but every transcript lives in the session's `subagents` folder.
 fn parse_subagent_thread_id(thread_id: &str) -> Option<(String, String)> {
-    let (parent_id, agent_id) = thread_id.split_once(SUBAGENT_THREAD_MARKER)?;
-    if parent_id.is_empty() || agent_id.is_empty() {
+    let (session_id, _) = thread_id.split_once(SUBAGENT_THREAD_MARKER)?;
+    let (_, agent_id) = thread_id.rsplit_once(SUBAGENT_THREAD_MARKER)?;
+    if session_id.is_empty() || agent_id.is_empty() {
         return None;
     }
-    Some((parent_id.to_string(), agent_id.to_string()))
+    Some((session_id.to_string(), agent_id.to_string()))
+}
+
+/// The session a thread belongs to; subagent threads share their session's.
+pub(crate) fn root_session_id(thread_id: &str) -> &str {
+    thread_id
+        .split_once(SUBAGENT_THREAD_MARKER)
+        .map_or(thread_id, |(session_id, _)| session_id)
 }
 
 fn resolve_subagent_path(
@@ -2530,53 +3787,281 @@ fn list_subagent_files(
         let path = dir_entry.path();
         if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
             continue;
-        }
-        let agent_id = match path.file_stem().and_then(|stem| stem.to_str()) {
-            Some(stem) if !stem.is_empty() => stem.to_string(),
-            _ => continue,
+        }
+        let agent_id = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ => continue,
+        };
+        let file_mtime = dir_entry
+            .metadata()
+            .ok()
+            .and_then(|meta| meta.modified().ok())
+            .and_then(|timestamp| timestamp.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0);
+        files.push((agent_id, path, file_mtime));
+    }
+    files
+}
+
+/// Transcript files are named `agent-<id>.jsonl` while tool results report
+/// the bare `<id>`; accept either form.
+fn subagent_file_matches(file_agent_id: &str, reported_id: &str) -> bool {
+    file_agent_id == reported_id
+        || file_agent_id.strip_prefix("agent-") == Some(reported_id)
+}
+
+/// What a transcript says about the subagents it spawned, keyed to the Task
+/// tool use that spawned each: the prompts of its Task calls, seen as soon as
+/// they start, and the `agentId` of each Task result, seen once they finish.
+#[derive(Default)]
+struct SpawnedSubagents {
+    prompts: HashMap<String, String>,
+    agent_ids: Vec<(String, String)>,
+}
+
+impl SpawnedSubagents {
+    /// The id of the Task tool use that spawned this transcript's subagent.
+    fn tool_use_for(&self, agent_id: &str, first_prompt: Option<&str>) -> Option<String> {
+        self.agent_ids
+            .iter()
+            .find(|(reported, _)| subagent_file_matches(agent_id, reported))
+            .map(|(_, tool_use_id)| tool_use_id.clone())
+            .or_else(|| {
+                let prompt = first_prompt?.trim();
+                self.prompts.get(prompt).cloned()
+            })
+    }
+}
+
+fn scan_spawned_subagents(path: &Path) -> SpawnedSubagents {
+    let mut spawned = SpawnedSubagents::default();
+    let Ok(file) = File::open(path) else {
+        return spawned;
+    };
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let content = value
+            .get("message")
+            .map(normalize_message_content)
+            .unwrap_or_default();
+        if let Some(agent_id) = extract_subagent_id(&value) {
+            let tool_use_id = content.iter().find_map(|block| {
+                block
+                    .get("tool_use_id")
+                    .or_else(|| block.get("toolUseId"))
+                    .and_then(|v| v.as_str())
+            });
+            if let Some(tool_use_id) = tool_use_id {
+                spawned.agent_ids.push((agent_id, tool_use_id.to_string()));
+            }
+            continue;
+        }
+        if value.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+            continue;
+        }
+        for block in content {
+            if block.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+                continue;
+            }
+            let name = block.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let input = block.get("input").cloned().unwrap_or(Value::Null);
+            if !is_subagent_task(name, &input) {
+                continue;
+            }
+            let prompt = input.get("prompt").and_then(|v| v.as_str());
+            let tool_use_id = block.get("id").and_then(|v| v.as_str());
+            if let (Some(prompt), Some(tool_use_id)) = (prompt, tool_use_id) {
+                spawned
+                    .prompts
+                    .insert(prompt.trim().to_string(), tool_use_id.to_string());
+            }
+        }
+    }
+    spawned
+}
+
+/// Every subagent transcript of a session, with thread ids that chain
+/// through the subagent that spawned it. Subagents whose spawner cannot be
+/// found hang off the session itself.
+fn list_session_subagents(
+    entry: &WorkspaceEntry,
+    session_id: &str,
+    session_path: Option<&Path>,
+) -> Vec<SubagentFile> {
+    subagent_lineage(session_id, session_path, list_subagent_files(entry, session_id))
+}
+
+fn subagent_lineage(
+    session_id: &str,
+    session_path: Option<&Path>,
+    files: Vec<(String, PathBuf, i64)>,
+) -> Vec<SubagentFile> {
+    let spawned: Vec<SpawnedSubagents> = files
+        .iter()
+        .map(|(_, path, _)| scan_spawned_subagents(path))
+        .collect();
+    let session_spawned = session_path.map(scan_spawned_subagents).unwrap_or_default();
+    let mut spawner: HashMap<usize, usize> = HashMap::new();
+    let mut spawn_tool_use: HashMap<usize, String> = HashMap::new();
+    for (child_index, (agent_id, path, _)) in files.iter().enumerate() {
+        let first_prompt = scan_session_metadata(path).0;
+        let first_prompt = first_prompt.as_deref();
+        let from_subagent = spawned.iter().enumerate().find_map(|(index, record)| {
+            if index == child_index {
+                return None;
+            }
+            record
+                .tool_use_for(agent_id, first_prompt)
+                .map(|tool_use_id| (index, tool_use_id))
+        });
+        match from_subagent {
+            Some((parent_index, tool_use_id)) => {
+                spawner.insert(child_index, parent_index);
+                spawn_tool_use.insert(child_index, tool_use_id);
+            }
+            None => {
+                if let Some(tool_use_id) = session_spawned.tool_use_for(agent_id, first_prompt) {
+                    spawn_tool_use.insert(child_index, tool_use_id);
+                }
+            }
+        }
+    }
+
+    let chain = |index: usize| {
+        let mut chain = vec![index];
+        let mut current = index;
+        while let Some(&parent) = spawner.get(&current) {
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        chain.reverse();
+        chain
+    };
+
+    files
+        .iter()
+        .enumerate()
+        .map(|(index, (agent_id, path, file_mtime))| {
+            let mut parent_thread_id = session_id.to_string();
+            let mut parent_path = session_path.map(Path::to_path_buf);
+            let lineage = chain(index);
+            for &ancestor in &lineage[..lineage.len() - 1] {
+                parent_thread_id = subagent_thread_id(&parent_thread_id, &files[ancestor].0);
+                parent_path = Some(files[ancestor].1.clone());
+            }
+            SubagentFile {
+                agent_id: agent_id.clone(),
+                thread_id: subagent_thread_id(&parent_thread_id, agent_id),
+                parent_thread_id,
+                path: path.clone(),
+                parent_path,
+                spawn_tool_use_id: spawn_tool_use.get(&index).cloned(),
+                file_mtime: *file_mtime,
+            }
+        })
+        .collect()
+}
+
+/// Points each Task item of `thread_id` at the subagent thread it spawned.
+fn link_subagent_items(entry: &WorkspaceEntry, thread_id: &str, items: &mut [Value]) {
+    let session_id = parse_subagent_thread_id(thread_id)
+        .map(|(session_id, _)| session_id)
+        .unwrap_or_else(|| thread_id.to_string());
+    let session_path = resolve_session_path(entry, &session_id);
+    let links: HashMap<String, String> =
+        list_session_subagents(entry, &session_id, session_path.as_deref())
+            .into_iter()
+            .filter(|subagent| subagent.parent_thread_id == thread_id)
+            .filter_map(|subagent| Some((subagent.spawn_tool_use_id?, subagent.thread_id)))
+            .collect();
+    if links.is_empty() {
+        return;
+    }
+    for item in items.iter_mut() {
+        let Some(subagent_thread_id) = item
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| links.get(id))
+        else {
+            continue;
         };
-        let file_mtime = dir_entry
-            .metadata()
-            .ok()
-            .and_then(|meta| meta.modified().ok())
-            .and_then(|timestamp| timestamp.duration_since(UNIX_EPOCH).ok())
-            .map(|duration| duration.as_millis() as i64)
-            .unwrap_or(0);
-        files.push((agent_id, path, file_mtime));
+        item["subagentThreadId"] = json!(subagent_thread_id);
     }
-    files
 }
 
-fn build_subagent_thread(
-    parent_id: &str,
-    agent_id: &str,
-    cwd: &str,
-    path: &Path,
-    file_mtime: i64,
-) -> Value {
-    let (first_prompt, message_count, git_branch) = scan_session_metadata(path);
-    let preview = first_prompt.unwrap_or_else(|| format!("Subagent {agent_id}"));
+fn build_subagent_thread(subagent: &SubagentFile, cwd: &str) -> Value {
+    let (first_prompt, message_count, git_branch) = scan_session_metadata(&subagent.path);
+    let preview =
+        first_prompt.unwrap_or_else(|| format!("Subagent {}", subagent.agent_id));
     json!({
-        "id": subagent_thread_id(parent_id, agent_id),
+        "id": subagent.thread_id,
         "preview": preview,
         "messageCount": message_count.unwrap_or(0),
-        "createdAt": file_mtime,
-        "updatedAt": file_mtime,
+        "createdAt": subagent.file_mtime,
+        "updatedAt": subagent.file_mtime,
         "cwd": cwd,
         "gitBranch": git_branch,
-        "parentId": parent_id,
+        "parentId": subagent.parent_thread_id,
     })
 }
 
 fn list_subagent_threads(entry: &WorkspaceEntry, parent_id: &str, cwd: &str) -> Vec<Value> {
-    list_subagent_files(entry, parent_id)
-        .into_iter()
-        .map(|(agent_id, path, file_mtime)| {
-            build_subagent_thread(parent_id, &agent_id, cwd, &path, file_mtime)
-        })
+    let session_path = resolve_session_path(entry, parent_id);
+    list_session_subagents(entry, parent_id, session_path.as_deref())
+        .iter()
+        .map(|subagent| build_subagent_thread(subagent, cwd))
         .collect()
 }
 
+/// The status of a `result` event, which ends a subagent's own transcript.
+fn subagent_result_status(value: &Value) -> Option<&'static str> {
+    if value.get("type").and_then(|v| v.as_str()) != Some("result") {
+        return None;
+    }
+    let failed = value.get("is_error").and_then(|v| v.as_bool()) == Some(true)
+        || value
+            .get("subtype")
+            .and_then(|v| v.as_str())
+            .is_some_and(|subtype| subtype.starts_with("error"));
+    Some(if failed { "failed" } else { "completed" })
+}
+
+/// The status the spawning transcript reports for `agent_id` through the
+/// Task tool result that carries its `agentId`.
+fn spawner_reported_status(value: &Value, agent_id: &str) -> Option<&'static str> {
+    if value.get("type").and_then(|v| v.as_str()) != Some("user") {
+        return None;
+    }
+    let reported_id = extract_subagent_id(value)?;
+    if !subagent_file_matches(agent_id, &reported_id) {
+        return None;
+    }
+    let is_error = value
+        .get("message")
+        .map(normalize_message_content)
+        .unwrap_or_default()
+        .iter()
+        .any(|block| block.get("is_error").and_then(|v| v.as_bool()) == Some(true));
+    let status = value
+        .get("toolUseResult")
+        .or_else(|| value.get("tool_use_result"))
+        .and_then(|result| result.get("status"))
+        .and_then(|v| v.as_str());
+    Some(match status {
+        _ if is_error => "failed",
+        Some("interrupted" | "cancelled") => "interrupted",
+        Some("failed" | "error") => "failed",
+        _ => "completed",
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 fn process_subagent_line(
     workspace_id: &str,
     thread_id: &str,
@@ -2586,6 +4071,7 @@ fn process_subagent_line(
     tool_names: &mut HashMap<String, String>,
     tool_inputs: &mut HashMap<String, Value>,
     tool_counter: &mut usize,
+    output_limit: usize,
 ) {
     let event_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
     if event_type != "user" && event_type != "assistant" {
@@ -2610,7 +4096,7 @@ fn process_subagent_line(
                     "item": {
                         "id": message_id,
                         "type": "userMessage",
-                        "content": content.clone(),
+                        "content": cache_user_message_images(workspace_id, &content),
                     }
                 }),
             );
@@ -2625,9 +4111,12 @@ fn process_subagent_line(
                 .or_else(|| entry.get("toolUseId"))
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
-            let content_value = entry.get("content").cloned().unwrap_or(Value::Null);
+            let (content_value, images) = extract_tool_result_images(
+                workspace_id,
+                &entry.get("content").cloned().unwrap_or(Value::Null),
+            );
             let mut output = tool_result_output(&content_value);
-            if output.trim().is_empty() {
+            if output.trim().is_empty() && images.is_empty() {
                 if let Some(fallback) = value
                     .get("toolUseResult")
                     .or_else(|| value.get("tool_use_result"))
@@ -2660,13 +4149,20 @@ fn process_subagent_line(
                 "item/completed",
                 json!({
                     "threadId": thread_id,
-                    "item": build_tool_item(
-                        &item_id,
-                        &command,
-                        &tool_input,
-                        "completed",
-                        Some(output.as_str()),
-                        Some(&result_value),
+                    "item": truncate_item_output(
+                        with_tool_images(
+                            build_tool_item(
+                                &item_id,
+                                &command,
+                                &tool_input,
+                                "completed",
+                                Some(output.as_str()),
+                                Some(&result_value),
+                                tool_use_result(value),
+                            ),
+                            images,
+                        ),
+                        output_limit,
                     ),
                 }),
             );
@@ -2711,6 +4207,7 @@ fn process_subagent_line(
                             "running",
                             None,
                             None,
+                            None,
                         ),
                     }),
                 );
@@ -2772,13 +4269,40 @@ fn process_subagent_line(
     }
 }
 
+/// Reads whatever complete lines the spawning transcript has gained and
+/// returns the status it reports for `agent_id`, if any.
+async fn poll_spawner_status(
+    reader: &mut AsyncBufReader<tokio::fs::File>,
+    pending: &mut String,
+    agent_id: &str,
+) -> Option<&'static str> {
+    loop {
+        match reader.read_line(pending).await {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {
+                if !pending.ends_with('\n') {
+                    // Partial write; finish the line on the next poll.
+                    return None;
+                }
+                let status = serde_json::from_str::<Value>(pending.trim())
+                    .ok()
+                    .and_then(|value| spawner_reported_status(&value, agent_id));
+                pending.clear();
+                if status.is_some() {
+                    return status;
+                }
+            }
+        }
+    }
+}
+
 async fn tail_subagent_thread(
     workspace_id: String,
-    thread_id: String,
-    path: PathBuf,
+    subagent: SubagentFile,
     event_sink: TauriEventSink,
     shutdown: watch::Receiver<bool>,
 ) {
+    let thread_id = subagent.thread_id.clone();
     let turn_id = Uuid::new_v4().to_string();
     emit_event(
         &event_sink,
@@ -2790,34 +4314,78 @@ async fn tail_subagent_thread(
         }),
     );
 
-    let file = match tokio::fs::File::open(&path).await {
-        Ok(file) => file,
-        Err(_) => {
-            emit_event(
-                &event_sink,
+    let status = match tokio::fs::File::open(&subagent.path).await {
+        Ok(file) => {
+            follow_subagent_transcript(
                 &workspace_id,
-                "turn/completed",
-                json!({
-                    "threadId": thread_id.clone(),
-                    "turn": { "id": turn_id, "threadId": thread_id.clone() },
-                }),
-            );
-            return;
+                &thread_id,
+                &turn_id,
+                file,
+                &subagent,
+                &event_sink,
+                &shutdown,
+            )
+            .await
         }
+        Err(_) => "failed",
     };
+
+    emit_event(
+        &event_sink,
+        &workspace_id,
+        "turn/completed",
+        json!({
+            "threadId": thread_id.clone(),
+            "turn": { "id": turn_id, "threadId": thread_id.clone(), "status": status },
+            "status": status,
+        }),
+    );
+}
+
+/// Streams a subagent transcript until it finishes and returns how it ended:
+/// `completed`, `failed`, or `interrupted` when the app shuts down first.
+async fn follow_subagent_transcript(
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    file: tokio::fs::File,
+    subagent: &SubagentFile,
+    event_sink: &TauriEventSink,
+    shutdown: &watch::Receiver<bool>,
+) -> &'static str {
     let mut reader = AsyncBufReader::new(file);
     let mut line = String::new();
     let mut tool_names: HashMap<String, String> = HashMap::new();
     let mut tool_inputs: HashMap<String, Value> = HashMap::new();
     let mut tool_counter: usize = 0;
+    let mut spawner = match &subagent.parent_path {
+        Some(path) => tokio::fs::File::open(path).await.ok().map(AsyncBufReader::new),
+        None => None,
+    };
+    let mut spawner_line = String::new();
+    let mut reported: Option<&'static str> = None;
+    let output_limit = event_output_limit(event_sink).await;
 
     loop {
         if *shutdown.borrow() {
-            break;
+            return "interrupted";
         }
         line.clear();
         match reader.read_line(&mut line).await {
             Ok(0) => {
+                // The spawner reports completion after the transcript's last
+                // line is written, so one more empty read means we are done.
+                if let Some(status) = reported {
+                    return status;
+                }
+                if let Some(spawner) = spawner.as_mut() {
+                    reported =
+                        poll_spawner_status(spawner, &mut spawner_line, &subagent.agent_id)
+                            .await;
+                    if reported.is_some() {
+                        continue;
+                    }
+                }
                 sleep(Duration::from_millis(120)).await;
             }
             Ok(_) => {
@@ -2829,30 +4397,24 @@ async fn tail_subagent_thread(
                     Ok(value) => value,
                     Err(_) => continue,
                 };
+                if let Some(status) = subagent_result_status(&value) {
+                    return status;
+                }
                 process_subagent_line(
-                    &workspace_id,
-                    &thread_id,
-                    &turn_id,
+                    workspace_id,
+                    thread_id,
+                    turn_id,
                     &value,
-                    &event_sink,
+                    event_sink,
                     &mut tool_names,
                     &mut tool_inputs,
                     &mut tool_counter,
+                    output_limit,
                 );
             }
-            Err(_) => break,
+            Err(_) => return "failed",
         }
     }
-
-    emit_event(
-        &event_sink,
-        &workspace_id,
-        "turn/completed",
-        json!({
-            "threadId": thread_id.clone(),
-            "turn": { "id": turn_id, "threadId": thread_id.clone() },
-        }),
-    );
 }
 
 async fn watch_workspace_threads(
@@ -2872,8 +4434,7 @@ async fn watch_workspace_threads(
     }
     for (session_id, _, _) in &initial_sessions {
         for (agent_id, _, _) in list_subagent_files(&entry, session_id) {
-            let thread_id = subagent_thread_id(session_id, &agent_id);
-            known_subagents.insert(thread_id);
+            known_subagents.insert(subagent_key(session_id, &agent_id));
         }
     }
 
@@ -2905,28 +4466,54 @@ async fn watch_workspace_threads(
             }
         }
 
-        for (session_id, _, _) in &sessions {
-            for (agent_id, path, file_mtime) in list_subagent_files(&entry, session_id) {
-                let thread_id = subagent_thread_id(session_id, &agent_id);
-                if known_subagents.insert(thread_id.clone()) {
-                    let thread =
-                        build_subagent_thread(session_id, &agent_id, &cwd, &path, file_mtime);
+        for (session_id, session_path, _) in &sessions {
+            let files = list_subagent_files(&entry, session_id);
+            let has_new = files.iter().any(|(agent_id, _, _)| {
+                !known_subagents.contains(&subagent_key(session_id, agent_id))
+            });
+            if !has_new {
+                continue;
+            }
+            let subagents =
+                list_session_subagents(&entry, session_id, Some(session_path.as_path()));
+            for subagent in subagents {
+                let key = subagent_key(session_id, &subagent.agent_id);
+                if known_subagents.contains(&key) {
+                    continue;
+                }
+                let thread = build_subagent_thread(&subagent, &cwd);
+                // Its first prompt tells us who spawned it; wait for it.
+                if thread["messageCount"].as_i64().unwrap_or(0) == 0 {
+                    continue;
+                }
+                known_subagents.insert(key);
+                emit_event(
+                    &event_sink,
+                    &workspace_id,
+                    "thread/created",
+                    json!({ "thread": thread }),
+                );
+                if let Some(item_id) = &subagent.spawn_tool_use_id {
                     emit_event(
                         &event_sink,
                         &workspace_id,
-                        "thread/created",
-                        json!({ "thread": thread }),
+                        "item/updated",
+                        json!({
+                            "threadId": subagent.parent_thread_id,
+                            "itemId": item_id,
+                            "subagentThreadId": subagent.thread_id,
+                        }),
                     );
-
-                    let handle = tokio::spawn(tail_subagent_thread(
-                        workspace_id.clone(),
-                        thread_id.clone(),
-                        path,
-                        event_sink.clone(),
-                        shutdown.clone(),
-                    ));
-                    active_subagents.insert(thread_id, handle);
                 }
+
+                let thread_id = subagent.thread_id.clone();
+                let handle = tokio::spawn(tail_subagent_thread(
+                    workspace_id.clone(),
+                    subagent,
+                    event_sink.clone(),
+                    shutdown.clone(),
+                ));
+                active_subagents.insert(thread_id, handle);
             }
         }
 
@@ -2938,6 +4525,54 @@ async fn watch_workspace_threads(
     }
 }
 
+/// The thread-list entry for one indexed session.
+fn session_thread_summary(
+    entry: ClaudeSessionEntry,
+    meta: Option<&ThreadMeta>,
+    cwd: &str,
+) -> Value {
+    let created_at = parse_iso_timestamp(entry.created.as_deref())
+        .or(entry.file_mtime)
+        .unwrap_or(0);
+    let updated_at = parse_iso_timestamp(entry.modified.as_deref())
+        .or(entry.file_mtime)
+        .unwrap_or(created_at);
+    let meta = meta.cloned().unwrap_or_default();
+    json!({
+        "id": entry.session_id,
+        "preview": meta.summary.or(entry.first_prompt).unwrap_or_default(),
+        "title": meta.title,
+        "pinned": meta.pinned_at.is_some(),
+        "pinnedAt": meta.pinned_at,
+        "tags": meta.tags,
+        "messageCount": entry.message_count.unwrap_or(0),
+        "createdAt": created_at,
+        "updatedAt": updated_at,
+        "cwd": cwd,
+        "gitBranch": entry.git_branch,
+    })
+}
+
+/// When a thread was last updated, for feeds that don't need its summary.
+pub(crate) struct ThreadActivity {
+    pub(crate) thread_id: String,
+    pub(crate) updated_at: i64,
+    pub(crate) first_prompt: Option<String>,
+    pub(crate) message_count: Option<i64>,
+}
+
+pub(crate) fn workspace_thread_activity(entry: &WorkspaceEntry) -> Vec<ThreadActivity> {
+    load_sessions_index(entry)
+        .into_iter()
+        .map(|session| ThreadActivity {
+            updated_at: session_sort_key(&session),
+            thread_id: session.session_id,
+            first_prompt: session.first_prompt,
+            message_count: session.message_count,
+        })
+        .collect()
+}
+
 fn session_sort_key(entry: &ClaudeSessionEntry) -> i64 {
     parse_iso_timestamp(entry.modified.as_deref())
         .or(entry.file_mtime)
@@ -2959,9 +4594,20 @@ fn value_to_millis(value: &Value) -> Option<i64> {
     }
 }
 
-fn resolve_project_dir(entry: &WorkspaceEntry) -> Option<PathBuf> {
+/// The path the CLI sees for `host_path` in this workspace, which names the
+/// project dir its sessions are filed under. A container with its own
+/// `workdir` moves the workspace there.
+fn session_project_path(entry: &WorkspaceEntry, host_path: &Path) -> String {
+    match entry.settings.container.as_ref() {
+        Some(container) => container_path(container, &entry.path, host_path),
+        None => host_path.to_string_lossy().to_string(),
+    }
+}
+
+pub(crate) fn resolve_project_dir(entry: &WorkspaceEntry) -> Option<PathBuf> {
     let projects_root = resolve_default_claude_home()?.join("projects");
-    Some(projects_root.join(encode_project_path(&entry.path)))
+    let project_path = session_project_path(entry, Path::new(&entry.path));
+    Some(projects_root.join(encode_project_path(&project_path)))
 }
 
 fn resolve_sessions_index_path(entry: &WorkspaceEntry) -> Option<PathBuf> {
@@ -2974,10 +4620,37 @@ fn resolve_sessions_index_path(entry: &WorkspaceEntry) -> Option<PathBuf> {
     }
 }
 
+/// Copy a whole session into a new thread that continues from its last message.
+pub(crate) fn fork_session(entry: &WorkspaceEntry, thread_id: &str) -> Result<String, String> {
+    copy_session(entry, thread_id, None, true)
+}
+
 fn fork_session_from_message(
     entry: &WorkspaceEntry,
     thread_id: &str,
     message_id: &str,
+) -> Result<String, String> {
+    copy_session(entry, thread_id, Some(message_id), true)
+}
+
+/// Copy a session up to, but not including, the user message `message_id`,
+/// so a different prompt can be sent in its place. The copy is
+/// removed when nothing precedes the message, so the new thread starts fresh.
+fn fork_session_before_message(
+    entry: &WorkspaceEntry,
+    thread_id: &str,
+    message_id: &str,
+) -> Result<String, String> {
+    copy_session(entry, thread_id, Some(message_id), false)
+}
+
+/// Copy a session under a new id, up to `until` when given, including it only
+/// when `include_until` is set.
+fn copy_session(
+    entry: &WorkspaceEntry,
+    thread_id: &str,
+    until: Option<&str>,
+    include_until: bool,
 ) -> Result<String, String> {
     let session_path = resolve_session_path(entry, thread_id)
         .ok_or_else(|| "Session file not found".to_string())?;
@@ -2986,11 +4659,38 @@ fn fork_session_from_message(
     let new_thread_id = Uuid::new_v4().to_string();
     let new_path = project_dir.join(format!("{new_thread_id}.jsonl"));
 
-    let file = File::open(&session_path).map_err(|err| err.to_string())?;
+    let written = copy_session_lines(
+        &session_path,
+        &new_path,
+        &new_thread_id,
+        until,
+        include_until,
+    )?;
+    if written == 0 && !include_until {
+        let _ = fs::remove_file(&new_path);
+    } else {
+        crate::retention::record_app_session(&entry.id, &new_thread_id);
+    }
+
+    Ok(new_thread_id)
+}
+
+/// Write the lines of `session_path` to `new_path` under `new_thread_id`,
+/// stopping at `until` as `copy_session` describes. Returns how many lines
+/// were written; `new_path` is removed on error.
+fn copy_session_lines(
+    session_path: &Path,
+    new_path: &Path,
+    new_thread_id: &str,
+    until: Option<&str>,
+    include_until: bool,
+) -> Result<usize, String> {
+    let file = File::open(session_path).map_err(|err| err.to_string())?;
     let reader = BufReader::new(file);
-    let output = File::create(&new_path).map_err(|err| err.to_string())?;
+    let output = File::create(new_path).map_err(|err| err.to_string())?;
     let mut writer = BufWriter::new(output);
     let mut found = false;
+    let mut written = 0usize;
 
     for line in reader.lines() {
         let line = line.map_err(|err| err.to_string())?;
@@ -2998,17 +4698,25 @@ fn fork_session_from_message(
             continue;
         }
         if let Ok(mut value) = serde_json::from_str::<Value>(&line) {
-            rewrite_session_id(&mut value, &new_thread_id);
+            let is_until =
+                until.is_some() && value.get("uuid").and_then(|uuid| uuid.as_str()) == until;
+            if is_until && !include_until {
+                if value.get("type").and_then(Value::as_str) != Some("user") {
+                    drop(writer);
+                    let _ = fs::remove_file(new_path);
+                    return Err("Only user messages can be edited".to_string());
+                }
+                found = true;
+                break;
+            }
+            rewrite_session_id(&mut value, new_thread_id);
             let serialized = serde_json::to_string(&value).map_err(|err| err.to_string())?;
             writer
                 .write_all(serialized.as_bytes())
                 .and_then(|_| writer.write_all(b"\n"))
                 .map_err(|err| err.to_string())?;
-            if value
-                .get("uuid")
-                .and_then(|uuid| uuid.as_str())
-                .is_some_and(|uuid| uuid == message_id)
-            {
+            written += 1;
+            if is_until {
                 found = true;
                 break;
             }
@@ -3017,18 +4725,19 @@ fn fork_session_from_message(
                 .write_all(line.as_bytes())
                 .and_then(|_| writer.write_all(b"\n"))
                 .map_err(|err| err.to_string())?;
+            written += 1;
         }
     }
 
     writer.flush().map_err(|err| err.to_string())?;
     drop(writer); // Close file handle before potential delete (required on Windows)
 
-    if !found {
-        let _ = fs::remove_file(&new_path);
+    if until.is_some() && !found {
+        let _ = fs::remove_file(new_path);
         return Err("Message not found in session".to_string());
     }
 
-    Ok(new_thread_id)
+    Ok(written)
 }
 
 fn rewrite_session_id(value: &mut Value, new_session_id: &str) {
@@ -3043,7 +4752,7 @@ fn rewrite_session_id(value: &mut Value, new_session_id: &str) {
     }
 }
 
-fn resolve_session_path(entry: &WorkspaceEntry, thread_id: &str) -> Option<PathBuf> {
+pub(crate) fn resolve_session_path(entry: &WorkspaceEntry, thread_id: &str) -> Option<PathBuf> {
     if let Some(index_path) = resolve_sessions_index_path(entry) {
         if let Ok(data) = std::fs::read_to_string(index_path) {
             if let Ok(value) = serde_json::from_str::<Value>(&data) {
@@ -3065,10 +4774,68 @@ fn resolve_session_path(entry: &WorkspaceEntry, thread_id: &str) -> Option<PathB
     if candidate.exists() {
         Some(candidate)
     } else {
-        None
+        scoped_session_path(entry, thread_id)
     }
 }
 
+/// Threads scoped to a subdirectory are filed under that directory's project
+/// dir. Session ids are unique, so matching any subdirectory by file name is safe.
+fn scoped_session_path(entry: &WorkspaceEntry, thread_id: &str) -> Option<PathBuf> {
+    let projects_root = resolve_default_claude_home()?.join("projects");
+    let project_path = session_project_path(entry, Path::new(&entry.path));
+    let prefix = format!("{}-", encode_project_path(&project_path));
+    fs::read_dir(projects_root)
+        .ok()?
+        .flatten()
+        .filter(|dir| dir.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|dir| dir.path().join(format!("{thread_id}.jsonl")))
+        .find(|path| path.exists())
+}
+
+/// Sessions of threads scoped to a subdirectory that the workspace's own
+/// project dir doesn't list.
+fn scoped_thread_sessions(
+    entry: &WorkspaceEntry,
+    thread_meta: &HashMap<String, ThreadMeta>,
+    known: &[ClaudeSessionEntry],
+) -> Vec<ClaudeSessionEntry> {
+    let Some(projects_root) = resolve_default_claude_home().map(|home| home.join("projects"))
+    else {
+        return Vec::new();
+    };
+    let known: HashSet<&str> = known.iter().map(|entry| entry.session_id.as_str()).collect();
+    thread_meta
+        .iter()
+        .filter(|(thread_id, _)| !known.contains(thread_id.as_str()))
+        .filter_map(|(thread_id, meta)| {
+            let cwd = Path::new(&entry.path).join(meta.cwd.as_ref()?);
+            let project_path = session_project_path(entry, &cwd);
+            let cwd = cwd.to_string_lossy().to_string();
+            let path = projects_root
+                .join(encode_project_path(&project_path))
+                .join(format!("{thread_id}.jsonl"));
+            let file_mtime = fs::metadata(&path)
+                .ok()?
+                .modified()
+                .ok()
+                .and_then(|timestamp| timestamp.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64);
+            let (first_prompt, message_count, git_branch) = scan_session_metadata(&path);
+            Some(ClaudeSessionEntry {
+                session_id: thread_id.clone(),
+                file_mtime,
+                first_prompt,
+                message_count,
+                created: None,
+                modified: None,
+                git_branch,
+                project_path: Some(cwd),
+                is_sidechain: Some(false),
+            })
+        })
+        .collect()
+}
+
 fn session_exists(entry: &WorkspaceEntry, thread_id: &str) -> bool {
     resolve_session_path(entry, thread_id).is_some()
 }
@@ -3082,7 +4849,105 @@ fn encode_project_path(path: &str) -> String {
     }
 }
 
-fn extract_text_from_message(message: &Value) -> String {
+/// Move the Claude project dirs of a workspace that moved from `old_path` to
+/// `new_path`, including those of threads scoped to the subdirectories in
+/// `scoped_cwds`, so its threads can still be listed and resumed. Sessions
+/// already present at the destination are left alone. Returns the number of
+/// entries moved.
+pub(crate) fn migrate_project_sessions(
+    old_path: &str,
+    new_path: &str,
+    scoped_cwds: &[String],
+) -> Result<usize, String> {
+    let projects_root = resolve_default_claude_home()
+        .ok_or("Unable to resolve Claude home")?
+        .join("projects");
+    migrate_project_dirs(&projects_root, old_path, new_path, scoped_cwds)
+}
+
+fn migrate_project_dirs(
+    projects_root: &Path,
+    old_path: &str,
+    new_path: &str,
+    scoped_cwds: &[String],
+) -> Result<usize, String> {
+    let mut pairs = vec![(old_path.to_string(), new_path.to_string())];
+    for cwd in scoped_cwds {
+        let scoped = |root: &str| Path::new(root).join(cwd).to_string_lossy().to_string();
+        pairs.push((scoped(old_path), scoped(new_path)));
+    }
+    let mut moved = 0;
+    for (from, to) in pairs {
+        let source = projects_root.join(encode_project_path(&from));
+        let target = projects_root.join(encode_project_path(&to));
+        if source == target || !source.is_dir() {
+            continue;
+        }
+        fs::create_dir_all(&target).map_err(|err| err.to_string())?;
+        let items = fs::read_dir(&source).map_err(|err| err.to_string())?;
+        for item in items.flatten() {
+            let name = item.file_name();
+            if name == "sessions-index.json" {
+                continue;
+            }
+            let destination = target.join(&name);
+            if destination.exists() {
+                continue;
+            }
+            fs::rename(item.path(), &destination).map_err(|err| err.to_string())?;
+            moved += 1;
+        }
+        migrate_sessions_index(&source, &target, &from, &to)?;
+        // Only removed when nothing was left behind because of a conflict.
+        let _ = fs::remove_dir(&source);
+    }
+    Ok(moved)
+}
+
+/// Carry the sessions index over, pointing its entries at the new locations.
+/// An index already at the destination wins; the CLI rebuilds stale entries.
+fn migrate_sessions_index(
+    source: &Path,
+    target: &Path,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), String> {
+    let source_index = source.join("sessions-index.json");
+    let target_index = target.join("sessions-index.json");
+    if !source_index.exists() {
+        return Ok(());
+    }
+    if target_index.exists() {
+        return fs::remove_file(&source_index).map_err(|err| err.to_string());
+    }
+    let data = fs::read_to_string(&source_index).map_err(|err| err.to_string())?;
+    let mut value: Value = serde_json::from_str(&data).map_err(|err| err.to_string())?;
+    let old_dir = source.to_string_lossy().to_string();
+    let new_dir = target.to_string_lossy().to_string();
+    let replace_prefix = |value: &mut Value, from: &str, to: &str| {
+        if let Some(rest) = value.as_str().and_then(|text| text.strip_prefix(from)) {
+            *value = Value::String(format!("{to}{rest}"));
+        }
+    };
+    if let Some(entries) = value.get_mut("entries").and_then(Value::as_array_mut) {
+        for entry in entries {
+            if let Some(full_path) = entry.get_mut("fullPath") {
+                replace_prefix(full_path, &old_dir, &new_dir);
+            }
+            if let Some(project_path) = entry.get_mut("projectPath") {
+                replace_prefix(project_path, old_path, new_path);
+            }
+        }
+    }
+    if let Some(original_path) = value.get_mut("originalPath") {
+        replace_prefix(original_path, old_path, new_path);
+    }
+    let serialized = serde_json::to_string_pretty(&value).map_err(|err| err.to_string())?;
+    fs::write(&target_index, serialized).map_err(|err| err.to_string())?;
+    fs::remove_file(&source_index).map_err(|err| err.to_string())
+}
+
+pub(crate) fn extract_text_from_message(message: &Value) -> String {
     let content = normalize_message_content(message);
     extract_text_from_content(&content)
 }
@@ -3147,6 +5012,14 @@ fn tool_result_output(value: &Value) -> String {
         if !text_entries.is_empty() {
             return text_entries.join("\n");
         }
+        // Images are attached to the item separately; don't dump them as JSON.
+        if !array.is_empty()
+            && array
+                .iter()
+                .all(|entry| entry.get("type").and_then(|v| v.as_str()) == Some("image"))
+        {
+            return String::new();
+        }
     }
     if value.is_null() {
         return String::new();
@@ -3230,7 +5103,7 @@ fn extract_path_from_value(value: &Value) -> Option<String> {
     None
 }
 
-fn extract_file_paths(tool_input: &Value) -> Vec<String> {
+pub(crate) fn extract_file_paths(tool_input: &Value) -> Vec<String> {
     let mut paths: Vec<String> = Vec::new();
     let Some(map) = tool_input.as_object() else {
         return paths;
@@ -3270,6 +5143,105 @@ fn extract_file_paths(tool_input: &Value) -> Vec<String> {
     deduped
 }
 
+/// Cut a tool item's `aggregatedOutput` to `limit` bytes for the event
+/// channel; `get_item_full_output` reads the rest back from the transcript.
+fn truncate_item_output(mut item: Value, limit: usize) -> Value {
+    if limit == 0 {
+        return item;
+    }
+    let Some(map) = item.as_object_mut() else {
+        return item;
+    };
+    let Some(output) = map.get("aggregatedOutput").and_then(|v| v.as_str()) else {
+        return item;
+    };
+    if output.len() <= limit {
+        return item;
+    }
+    let mut end = limit;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    let total = output.len();
+    let kept = output[..end].to_string();
+    map.insert("aggregatedOutput".to_string(), Value::String(kept));
+    map.insert("outputTruncated".to_string(), Value::Bool(true));
+    map.insert("outputBytes".to_string(), json!(total));
+    item
+}
+
+async fn event_output_limit(event_sink: &TauriEventSink) -> usize {
+    let state = event_sink.app_handle().state::<AppState>();
+    let limit = state.app_settings.lock().await.event_output_limit_bytes;
+    limit
+}
+
+/// Output of the tool call `item_id` exactly as history rebuilds it.
+fn read_item_full_output(
+    workspace_id: &str,
+    path: &Path,
+    item_id: &str,
+) -> Result<Option<String>, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let mut command = "Tool".to_string();
+    let mut tool_input = Value::Null;
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let content = value
+            .get("message")
+            .map(normalize_message_content)
+            .unwrap_or_default();
+        for entry in &content {
+            match entry.get("type").and_then(|v| v.as_str()) {
+                Some("tool_use") if entry.get("id").and_then(|v| v.as_str()) == Some(item_id) => {
+                    if let Some(name) = entry.get("name").and_then(|v| v.as_str()) {
+                        command = name.to_string();
+                    }
+                    tool_input = entry.get("input").cloned().unwrap_or(Value::Null);
+                }
+                Some("tool_result") => {
+                    let tool_use_id = entry
+                        .get("tool_use_id")
+                        .or_else(|| entry.get("toolUseId"))
+                        .and_then(|v| v.as_str());
+                    if tool_use_id != Some(item_id) {
+                        continue;
+                    }
+                    let (content_value, images) = extract_tool_result_images(
+                        workspace_id,
+                        &entry.get("content").cloned().unwrap_or(Value::Null),
+                    );
+                    let mut output = tool_result_output(&content_value);
+                    if output.trim().is_empty() && images.is_empty() {
+                        if let Some(fallback) = value
+                            .get("toolUseResult")
+                            .or_else(|| value.get("tool_use_result"))
+                        {
+                            output = fallback
+                                .get("content")
+                                .map(tool_result_output)
+                                .unwrap_or_else(|| tool_result_output(fallback));
+                        }
+                    }
+                    return Ok(Some(collapse_subagent_output(
+                        output,
+                        &command,
+                        &tool_input,
+                        &value,
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(None)
+}
+
 fn build_tool_item(
     id: &str,
     tool_name: &str,
@@ -3277,6 +5249,7 @@ fn build_tool_item(
     status: &str,
     output: Option<&str>,
     result_value: Option<&Value>,
+    tool_use_result: Option<&Value>,
 ) -> Value {
     if let Some((server, tool)) = parse_mcp_tool_name(tool_name) {
         let mut item = json!({
@@ -3301,6 +5274,28 @@ fn build_tool_item(
         return item;
     }
 
+    if is_todo_write(tool_name) {
+        let todos = parse_todo_entries(tool_input);
+        let completed = todos
+            .iter()
+            .filter(|todo| todo.get("status").and_then(|v| v.as_str()) == Some("completed"))
+            .count();
+        let mut item = json!({
+            "id": id,
+            "type": "todoList",
+            "status": status,
+            "todos": todos,
+            "completedCount": completed,
+            "totalCount": todos.len(),
+        });
+        if let Value::Object(ref mut map) = item {
+            if let Some(output) = output {
+                map.insert("aggregatedOutput".to_string(), Value::String(output.to_string()));
+            }
+        }
+        return item;
+    }
+
     let normalized = tool_name.trim().to_lowercase();
     if normalized == "websearch" {
         let query = tool_input
@@ -3329,9 +5324,23 @@ fn build_tool_item(
     ) {
         let paths = extract_file_paths(tool_input);
         let kind = if normalized == "write" { "add" } else { "modify" };
+        // Every file tool edits a single path, so the stats belong to it.
+        let stats = if paths.len() == 1 {
+            file_change_stats(&normalized, tool_input, tool_use_result)
+        } else {
+            None
+        };
         let changes = paths
             .into_iter()
-            .map(|path| json!({ "path": path, "kind": kind }))
+            .map(|path| match stats {
+                Some((additions, deletions)) => json!({
+                    "path": path,
+                    "kind": kind,
+                    "additions": additions,
+                    "deletions": deletions,
+                }),
+                None => json!({ "path": path, "kind": kind }),
+            })
             .collect::<Vec<_>>();
         let mut item = json!({
             "id": id,
@@ -3361,10 +5370,221 @@ fn build_tool_item(
         if let Some(output) = output {
             map.insert("aggregatedOutput".to_string(), Value::String(output.to_string()));
         }
+        if let Some(details) = tool_details(tool_name, tool_input, output, tool_use_result) {
+            map.insert("details".to_string(), details);
+        }
     }
     item
 }
 
+/// The CLI's structured copy of a tool result, kept beside the text content.
+fn tool_use_result(value: &Value) -> Option<&Value> {
+    value
+        .get("toolUseResult")
+        .or_else(|| value.get("tool_use_result"))
+}
+
+/// Structured fields for the tools the UI renders natively. Counts and codes
+/// come from `toolUseResult` when the CLI recorded one, else from the output.
+fn tool_details(
+    tool_name: &str,
+    tool_input: &Value,
+    output: Option<&str>,
+    tool_use_result: Option<&Value>,
+) -> Option<Value> {
+    let input_str = |key: &str| tool_input.get(key).and_then(|v| v.as_str());
+    let result_u64 = |key: &str| {
+        tool_use_result
+            .and_then(|result| result.get(key))
+            .and_then(|v| v.as_u64())
+    };
+    match tool_name.trim().to_lowercase().as_str() {
+        "bash" => Some(json!({
+            "kind": "bash",
+            "command": input_str("command").unwrap_or(""),
+            "description": input_str("description"),
+            "exitCode": bash_exit_code(output, tool_use_result),
+        })),
+        "grep" => {
+            let mode = input_str("output_mode").unwrap_or("files_with_matches");
+            let (match_count, file_count) = match output {
+                Some(output) => grep_counts(mode, output, tool_use_result),
+                None => (None, None),
+            };
+            Some(json!({
+                "kind": "grep",
+                "pattern": input_str("pattern").unwrap_or(""),
+                "path": input_str("path").or_else(|| input_str("glob")),
+                "outputMode": mode,
+                "matchCount": match_count,
+                "fileCount": file_count,
+            }))
+        }
+        "read" => {
+            let (line_start, line_end, total_lines) =
+                read_line_range(tool_input, output, tool_use_result);
+            Some(json!({
+                "kind": "read",
+                "path": input_str("file_path").or_else(|| input_str("path")).unwrap_or(""),
+                "lineStart": line_start,
+                "lineEnd": line_end,
+                "totalLines": total_lines,
+            }))
+        }
+        "webfetch" => Some(json!({
+            "kind": "webFetch",
+            "url": input_str("url").unwrap_or(""),
+            "status": result_u64("code"),
+            "statusText": tool_use_result
+                .and_then(|result| result.get("codeText"))
+                .and_then(|v| v.as_str()),
+        })),
+        _ => None,
+    }
+}
+
+/// Failed commands come back as "Exit code N" (optionally after "Error: ");
+/// anything else that finished without being interrupted exited 0.
+fn bash_exit_code(output: Option<&str>, tool_use_result: Option<&Value>) -> Option<i64> {
+    let output = output?;
+    let interrupted = tool_use_result
+        .and_then(|result| result.get("interrupted"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if interrupted {
+        return None;
+    }
+    let trimmed = output.trim_start();
+    let rest = trimmed.strip_prefix("Error: ").unwrap_or(trimmed);
+    match rest.strip_prefix("Exit code ") {
+        Some(code) => code
+            .split(|ch: char| !ch.is_ascii_digit() && ch != '-')
+            .next()
+            .and_then(|code| code.parse().ok()),
+        None => Some(0),
+    }
+}
+
+fn grep_counts(
+    mode: &str,
+    output: &str,
+    tool_use_result: Option<&Value>,
+) -> (Option<u64>, Option<u64>) {
+    let result_u64 = |key: &str| {
+        tool_use_result
+            .and_then(|result| result.get(key))
+            .and_then(|v| v.as_u64())
+    };
+    let trimmed = output.trim();
+    let empty = trimmed.is_empty()
+        || trimmed.starts_with("No files found")
+        || trimmed.starts_with("No matches found");
+    let file_count = result_u64("numFiles").or_else(|| {
+        if empty {
+            return Some(0);
+        }
+        let rest = trimmed.strip_prefix("Found ")?;
+        let (count, unit) = rest.split_once(' ')?;
+        if unit.starts_with("file") {
+            count.parse().ok()
+        } else {
+            None
+        }
+    });
+    let match_count = match mode {
+        "content" => result_u64("numLines").or_else(|| {
+            if empty {
+                return Some(0);
+            }
+            Some(trimmed.lines().filter(|line| !line.trim().is_empty()).count() as u64)
+        }),
+        "count" => result_u64("numMatches").or_else(|| {
+            if empty {
+                return Some(0);
+            }
+            Some(
+                trimmed
+                    .lines()
+                    .filter_map(|line| line.rsplit_once(':'))
+                    .filter_map(|(_, count)| count.trim().parse::<u64>().ok())
+                    .sum(),
+            )
+        }),
+        _ => None,
+    };
+    (match_count, file_count)
+}
+
+/// Line range a Read call covered, 1-based and inclusive.
+fn read_line_range(
+    tool_input: &Value,
+    output: Option<&str>,
+    tool_use_result: Option<&Value>,
+) -> (Option<u64>, Option<u64>, Option<u64>) {
+    if let Some(file) = tool_use_result.and_then(|result| result.get("file")) {
+        let start = file.get("startLine").and_then(|v| v.as_u64());
+        let count = file.get("numLines").and_then(|v| v.as_u64());
+        let total = file.get("totalLines").and_then(|v| v.as_u64());
+        if let (Some(start), Some(count)) = (start, count) {
+            let end = (count > 0).then(|| start + count - 1);
+            return (Some(start), end, total);
+        }
+    }
+    // Read prints `cat -n` style lines: right-aligned number, then `→` or a tab.
+    let numbered: Vec<u64> = output
+        .unwrap_or("")
+        .lines()
+        .filter_map(|line| {
+            let (number, _) = line.split_once(|ch| ch == '→' || ch == '\t')?;
+            number.trim().parse().ok()
+        })
+        .collect();
+    let offset = tool_input.get("offset").and_then(|v| v.as_u64());
+    let limit = tool_input.get("limit").and_then(|v| v.as_u64());
+    let start = numbered.first().copied().or(offset).unwrap_or(1);
+    let end = numbered.last().copied().or_else(|| {
+        let limit = limit.filter(|limit| *limit > 0)?;
+        Some(offset.unwrap_or(1) + limit - 1)
+    });
+    (Some(start), end, None)
+}
+
+fn is_todo_write(tool_name: &str) -> bool {
+    tool_name.trim().eq_ignore_ascii_case("todowrite")
+}
+
+/// Normalize TodoWrite input (`{"todos": [{content, status, activeForm}]}`) into todo entries.
+fn parse_todo_entries(tool_input: &Value) -> Vec<Value> {
+    let Some(todos) = tool_input.get("todos").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    todos
+        .iter()
+        .filter_map(|todo| {
+            let content = todo
+                .get("content")
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())?;
+            let status = match todo.get("status").and_then(|v| v.as_str()) {
+                Some("completed") => "completed",
+                Some("in_progress") => "in_progress",
+                _ => "pending",
+            };
+            let active_form = todo
+                .get("activeForm")
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty());
+            Some(json!({
+                "content": content,
+                "status": status,
+                "activeForm": active_form,
+            }))
+        })
+        .collect()
+}
+
 fn extract_subagent_id(value: &Value) -> Option<String> {
     value
         .get("toolUseResult")
@@ -3413,44 +5633,115 @@ fn has_user_message_content(content: &[Value]) -> bool {
     })
 }
 
-fn format_token_usage(raw: Value, model_usage: Option<&Value>) -> Option<Value> {
+/// Pasted images are sent inline as base64; hand the UI cached file paths instead.
+fn cache_user_message_images(workspace_id: &str, content: &[Value]) -> Value {
+    extract_tool_result_images(workspace_id, &Value::Array(content.to_vec())).0
+}
+
+fn turn_token_breakdown(raw: &Value) -> Option<TokenBreakdown> {
     let Value::Object(map) = raw else {
         return None;
     };
-    let input_tokens = usage_number(&map, &["input_tokens", "inputTokens"]);
-    let output_tokens = usage_number(&map, &["output_tokens", "outputTokens"]);
-    let cached_read = usage_number(&map, &["cache_read_input_tokens", "cacheReadInputTokens"]);
-    let cached_create =
-        usage_number(&map, &["cache_creation_input_tokens", "cacheCreationInputTokens"]);
+    Some(usage_breakdown(map))
+}
+
+fn usage_breakdown(map: &Map<String, Value>) -> TokenBreakdown {
+    let input_tokens = usage_number(map, &["input_tokens", "inputTokens"]);
+    let output_tokens = usage_number(map, &["output_tokens", "outputTokens"]);
+    let cached_read = usage_number(map, &["cache_read_input_tokens", "cacheReadInputTokens"]);
+    let cached_create = usage_number(
+        map,
+        &["cache_creation_input_tokens", "cacheCreationInputTokens"],
+    );
     let cached_input_tokens = cached_read + cached_create;
     let reasoning_output_tokens =
-        usage_number(&map, &["reasoning_output_tokens", "reasoningOutputTokens"]);
-    let total_tokens = input_tokens + output_tokens + cached_input_tokens;
-
-    // Extract modelContextWindow from modelUsage (first model's contextWindow)
-    let model_context_window = model_usage
-        .and_then(|mu| mu.as_object())
-        .and_then(|obj| obj.values().next())
-        .and_then(|model_data| model_data.get("contextWindow"))
-        .and_then(|cw| cw.as_i64());
-
-    Some(json!({
-        "total": {
-            "totalTokens": total_tokens,
-            "inputTokens": input_tokens,
-            "cachedInputTokens": cached_input_tokens,
-            "outputTokens": output_tokens,
-            "reasoningOutputTokens": reasoning_output_tokens,
-        },
-        "last": {
-            "totalTokens": total_tokens,
-            "inputTokens": input_tokens,
-            "cachedInputTokens": cached_input_tokens,
-            "outputTokens": output_tokens,
-            "reasoningOutputTokens": reasoning_output_tokens,
-        },
-        "modelContextWindow": model_context_window
-    }))
+        usage_number(map, &["reasoning_output_tokens", "reasoningOutputTokens"]);
+    TokenBreakdown {
+        total_tokens: input_tokens + output_tokens + cached_input_tokens,
+        input_tokens,
+        cached_input_tokens,
+        output_tokens,
+        reasoning_output_tokens,
+    }
+}
+
+/// One model's share of a turn, from the result event's `modelUsage`.
+struct ModelTurnUsage {
+    model: String,
+    usage: TokenBreakdown,
+    context_window: Option<i64>,
+    cost_usd: Option<f64>,
+}
+
+fn model_turn_usage(model_usage: Option<&Value>) -> Vec<ModelTurnUsage> {
+    let Some(models) = model_usage.and_then(|mu| mu.as_object()) else {
+        return Vec::new();
+    };
+    models
+        .iter()
+        .filter_map(|(model, data)| {
+            let map = data.as_object()?;
+            Some(ModelTurnUsage {
+                model: model.clone(),
+                usage: usage_breakdown(map),
+                context_window: map.get("contextWindow").and_then(|cw| cw.as_i64()),
+                cost_usd: map
+                    .get("costUSD")
+                    .or_else(|| map.get("costUsd"))
+                    .and_then(|cost| cost.as_f64()),
+            })
+        })
+        .collect()
+}
+
+/// The model that ran the turn itself rather than its subagents: the one
+/// the assistant messages named, else whichever used the most tokens.
+fn primary_model<'a>(models: &'a [ModelTurnUsage], last_model: Option<&str>) -> Option<&'a str> {
+    last_model
+        .and_then(|last| {
+            models
+                .iter()
+                .find(|entry| entry.model == last || last.starts_with(&entry.model))
+        })
+        .or_else(|| models.iter().max_by_key(|entry| entry.usage.total_tokens))
+        .map(|entry| entry.model.as_str())
+}
+
+/// The `thread/tokenUsage/updated` payload: `last` is this turn, `total`
+/// the whole thread, and `perModel` splits both by model.
+fn format_token_usage(
+    last: &TokenBreakdown,
+    models: &[ModelTurnUsage],
+    totals: &ThreadTokenTotals,
+    last_model: Option<&str>,
+) -> Value {
+    let primary = primary_model(models, last_model);
+    let model_context_window = models
+        .iter()
+        .find(|entry| Some(entry.model.as_str()) == primary)
+        .and_then(|entry| entry.context_window);
+    let per_model: Vec<Value> = totals
+        .per_model
+        .iter()
+        .map(|(model, total)| {
+            let turn = models.iter().find(|entry| &entry.model == model);
+            json!({
+                "model": model,
+                "primary": Some(model.as_str()) == primary,
+                "last": turn.map(|entry| entry.usage).unwrap_or_default(),
+                "total": total,
+                "contextWindow": turn.and_then(|entry| entry.context_window),
+                "costUsd": turn.and_then(|entry| entry.cost_usd),
+            })
+        })
+        .collect();
+
+    json!({
+        "total": totals.total,
+        "last": last,
+        "modelContextWindow": model_context_window,
+        "perModel": per_model,
+    })
 }
 
 fn usage_number(map: &Map<String, Value>, keys: &[&str]) -> i64 {
@@ -3488,9 +5779,10 @@ async fn build_review_prompt(
     }
 
     let diff = crate::git::get_workspace_diff(workspace_id, state).await?;
-    if diff.trim().is_empty() {
+    if diff.is_empty() {
         return Err("No changes to review".to_string());
     }
+    let diff = diff.to_prompt_text();
 
     let label = match target_type {
         "baseBranch" => target
@@ -3550,7 +5842,7 @@ fn write_settings_json(path: &Path, settings: &Map<String, Value>) -> Result<(),
     std::fs::write(path, contents).map_err(|err| err.to_string())
 }
 
-fn archived_threads_path(state: &State<'_, AppState>) -> Result<PathBuf, String> {
+pub(crate) fn archived_threads_path(state: &AppState) -> Result<PathBuf, String> {
     state
         .settings_path
         .parent()
@@ -3558,7 +5850,7 @@ fn archived_threads_path(state: &State<'_, AppState>) -> Result<PathBuf, String>
         .ok_or_else(|| "Unable to resolve app data dir.".to_string())
 }
 
-fn read_archived_threads(path: &Path) -> Result<HashMap<String, Vec<String>>, String> {
+pub(crate) fn read_archived_threads(path: &Path) -> Result<HashMap<String, Vec<String>>, String> {
     if !path.exists() {
         return Ok(HashMap::new());
     }
@@ -3566,7 +5858,7 @@ fn read_archived_threads(path: &Path) -> Result<HashMap<String, Vec<String>>, St
     serde_json::from_str(&contents).map_err(|err| err.to_string())
 }
 
-fn write_archived_threads(
+pub(crate) fn write_archived_threads(
     path: &Path,
     data: &HashMap<String, Vec<String>>,
 ) -> Result<(), String> {
@@ -3576,3 +5868,719 @@ fn write_archived_threads(
     let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
     std::fs::write(path, contents).map_err(|err| err.to_string())
 }
+
+/// User-set title, pin state, tags and working directory for a thread, plus its
+/// generated summary, stored in `thread_meta.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pinned_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<String>,
+    /// Directory the thread runs in, relative to the workspace root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cwd: Option<String>,
+    /// Generated one-line title, shown instead of the first prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) summary: Option<String>,
+    /// When any of the above last changed; data retention expires titles by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) updated_at: Option<i64>,
+}
+
+const MAX_THREAD_TAG_CHARS: usize = 32;
+
+/// Tags are compared case-insensitively and shown kebab-cased, so "Prod incident"
+/// and "prod-incident" are the same tag.
+fn normalize_thread_tag(tag: &str) -> Option<String> {
+    let normalized = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !ch.is_control())
+        .take(MAX_THREAD_TAG_CHARS)
+        .collect::<String>();
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
+fn thread_has_tags(meta: Option<&ThreadMeta>, required: &[String]) -> bool {
+    if required.is_empty() {
+        return true;
+    }
+    meta.is_some_and(|meta| required.iter().all(|tag| meta.tags.contains(tag)))
+}
+
+/// Tags used in a workspace with the number of threads carrying each, most used first.
+fn count_thread_tags(meta: &HashMap<String, ThreadMeta>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in meta.values() {
+        for tag in &entry.tags {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    let mut counts = counts
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+pub(crate) type ThreadMetaStore = HashMap<String, HashMap<String, ThreadMeta>>;
+
+pub(crate) fn thread_meta_path(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|path| path.join("thread_meta.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+pub(crate) fn read_thread_meta(path: &Path) -> Result<ThreadMetaStore, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+pub(crate) fn write_thread_meta(path: &Path, data: &ThreadMetaStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+pub(crate) fn workspace_thread_meta(state: &AppState, workspace_id: &str) -> HashMap<String, ThreadMeta> {
+    thread_meta_path(state)
+        .ok()
+        .and_then(|path| read_thread_meta(&path).ok())
+        .and_then(|mut store| store.remove(workspace_id))
+        .unwrap_or_default()
+}
+
+/// Apply `update` to one thread's metadata and persist it, dropping entries
+/// that no longer carry a title or pin.
+pub(crate) fn update_thread_meta(
+    path: &Path,
+    workspace_id: &str,
+    thread_id: &str,
+    update: impl FnOnce(&mut ThreadMeta),
+) -> Result<ThreadMeta, String> {
+    let mut store = read_thread_meta(path)?;
+    let workspace = store.entry(workspace_id.to_string()).or_default();
+    let mut meta = workspace.remove(thread_id).unwrap_or_default();
+    update(&mut meta);
+    meta.updated_at = None;
+    if meta != ThreadMeta::default() {
+        meta.updated_at = Some(now_millis());
+        workspace.insert(thread_id.to_string(), meta.clone());
+    }
+    if workspace.is_empty() {
+        store.remove(workspace_id);
+    }
+    write_thread_meta(path, &store)?;
+    Ok(meta)
+}
+
+/// Resolve a thread directory given relative to the workspace root. It must be
+/// an existing directory inside the workspace; the root itself yields `None`.
+pub(crate) fn resolve_thread_cwd(
+    workspace_path: &str,
+    subpath: &str,
+) -> Result<Option<PathBuf>, String> {
+    let subpath = subpath.trim();
+    if subpath.is_empty() || subpath == "." {
+        return Ok(None);
+    }
+    if Path::new(subpath).is_absolute() {
+        return Err("Thread directory must be relative to the workspace.".to_string());
+    }
+    let root = Path::new(workspace_path)
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace path: {err}"))?;
+    let resolved = root
+        .join(subpath)
+        .canonicalize()
+        .map_err(|_| format!("Directory not found: {subpath}"))?;
+    let relative = resolved
+        .strip_prefix(&root)
+        .map_err(|_| "Thread directory must be inside the workspace.".to_string())?;
+    if !resolved.is_dir() {
+        return Err(format!("Not a directory: {subpath}"));
+    }
+    if relative.as_os_str().is_empty() {
+        return Ok(None);
+    }
+    // Keep the workspace path as given so the CLI's project dir matches ours.
+    Ok(Some(Path::new(workspace_path).join(relative)))
+}
+
+/// Validate `cwd` and store it as the thread's working directory.
+fn record_thread_cwd(
+    state: &AppState,
+    entry: &WorkspaceEntry,
+    thread_id: &str,
+    cwd: &str,
+) -> Result<Option<PathBuf>, String> {
+    let resolved = resolve_thread_cwd(&entry.path, cwd)?;
+    let subpath = resolved.as_ref().and_then(|path| {
+        path.strip_prefix(&entry.path)
+            .ok()
+            .map(|relative| relative.to_string_lossy().to_string())
+    });
+    let path = thread_meta_path(state)?;
+    update_thread_meta(&path, &entry.id, thread_id, |meta| {
+        meta.cwd = subpath;
+    })?;
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContainerMode, ContainerSettings, WorkspaceKind, WorkspaceSettings};
+
+    #[test]
+    fn nested_subagent_thread_ids_resolve_to_the_innermost_agent() {
+        let nested = subagent_thread_id(&subagent_thread_id("s1", "agent-a"), "agent-b");
+        assert_eq!(nested, "s1::subagent::agent-a::subagent::agent-b");
+        assert_eq!(
+            parse_subagent_thread_id(&nested),
+            Some(("s1".to_string(), "agent-b".to_string()))
+        );
+        assert_eq!(
+            parse_subagent_thread_id("s1::subagent::agent-a"),
+            Some(("s1".to_string(), "agent-a".to_string()))
+        );
+        assert_eq!(parse_subagent_thread_id("s1"), None);
+        assert_eq!(parse_subagent_thread_id("s1::subagent::"), None);
+    }
+
+    #[test]
+    fn subagent_lineage_nests_subagents_under_their_spawner() {
+        let dir = std::env::temp_dir().join(format!("subagents-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, lines: &[Value]| {
+            let path = dir.join(format!("{name}.jsonl"));
+            let body: Vec<String> = lines.iter().map(Value::to_string).collect();
+            std::fs::write(&path, body.join("\n")).unwrap();
+            path
+        };
+        let prompt = |text: &str| json!({"type": "user", "message": {"role": "user", "content": text}});
+        let task = |id: &str, text: &str| json!({
+            "type": "assistant",
+            "message": {"role": "assistant", "content": [{
+                "type": "tool_use", "id": id, "name": "Task",
+                "input": {"prompt": text, "subagent_type": "general"},
+            }]},
+        });
+        let session = write("s1", &[task("tu1", "Review auth")]);
+        let outer = write("agent-a", &[prompt("Review auth"), task("tu2", "Check tokens")]);
+        // Still running, so only its prompt links it to agent-a.
+        let inner = write("agent-b", &[prompt("Check tokens")]);
+        let finished = write("agent-c", &[prompt("Unrelated")]);
+        std::fs::write(
+            &outer,
+            format!(
+                "{}\n{}",
+                std::fs::read_to_string(&outer).unwrap(),
+                json!({
+                    "type": "user",
+                    "message": {"role": "user", "content": [
+                        {"type": "tool_result", "tool_use_id": "tu3", "content": "done"},
+                    ]},
+                    "toolUseResult": {"agentId": "c"},
+                })
+            ),
+        )
+        .unwrap();
+
+        let files = vec![
+            ("agent-a".to_string(), outer.clone(), 1),
+            ("agent-b".to_string(), inner, 2),
+            ("agent-c".to_string(), finished, 3),
+        ];
+        let lineage = subagent_lineage("s1", Some(session.as_path()), files);
+        let by_agent = |agent: &str| {
+            lineage
+                .iter()
+                .find(|subagent| subagent.agent_id == agent)
+                .unwrap()
+        };
+        assert_eq!(by_agent("agent-a").parent_thread_id, "s1");
+        assert_eq!(by_agent("agent-a").parent_path.as_deref(), Some(session.as_path()));
+        assert_eq!(by_agent("agent-b").parent_thread_id, "s1::subagent::agent-a");
+        assert_eq!(
+            by_agent("agent-b").thread_id,
+            "s1::subagent::agent-a::subagent::agent-b"
+        );
+        assert_eq!(by_agent("agent-b").parent_path.as_deref(), Some(outer.as_path()));
+        assert_eq!(by_agent("agent-c").parent_thread_id, "s1::subagent::agent-a");
+        assert_eq!(by_agent("agent-a").spawn_tool_use_id.as_deref(), Some("tu1"));
+        assert_eq!(by_agent("agent-b").spawn_tool_use_id.as_deref(), Some("tu2"));
+        assert_eq!(by_agent("agent-c").spawn_tool_use_id.as_deref(), Some("tu3"));
+        let thread = build_subagent_thread(by_agent("agent-b"), "/repo");
+        assert_eq!(thread["parentId"], "s1::subagent::agent-a");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn subagent_completion_comes_from_results_or_the_spawner() {
+        assert_eq!(
+            subagent_result_status(&json!({"type": "result", "subtype": "success"})),
+            Some("completed")
+        );
+        assert_eq!(
+            subagent_result_status(&json!({"type": "result", "subtype": "error_max_turns"})),
+            Some("failed")
+        );
+        assert_eq!(subagent_result_status(&json!({"type": "assistant"})), None);
+
+        let reported = |result: Value, is_error: bool| json!({
+            "type": "user",
+            "message": {"role": "user", "content": [{
+                "type": "tool_result", "tool_use_id": "tu1", "content": "done",
+                "is_error": is_error,
+            }]},
+            "toolUseResult": result,
+        });
+        let done = reported(json!({"agentId": "a", "status": "completed"}), false);
+        assert_eq!(spawner_reported_status(&done, "agent-a"), Some("completed"));
+        assert_eq!(spawner_reported_status(&done, "agent-b"), None);
+        let failed = reported(json!({"agentId": "a"}), true);
+        assert_eq!(spawner_reported_status(&failed, "a"), Some("failed"));
+        let stopped = reported(json!({"agentId": "a", "status": "interrupted"}), false);
+        assert_eq!(spawner_reported_status(&stopped, "a"), Some("interrupted"));
+    }
+
+    #[test]
+    fn token_usage_splits_models_and_reports_thread_totals() {
+        let model_usage = json!({
+            "claude-haiku-4-5": {
+                "inputTokens": 400, "outputTokens": 50,
+                "cacheReadInputTokens": 0, "cacheCreationInputTokens": 0,
+                "contextWindow": 200000, "costUSD": 0.001,
+            },
+            "claude-sonnet-4-5": {
+                "inputTokens": 100, "outputTokens": 20,
+                "cacheReadInputTokens": 1000, "cacheCreationInputTokens": 0,
+                "contextWindow": 1000000, "costUSD": 0.01,
+            },
+        });
+        let models = model_turn_usage(Some(&model_usage));
+        assert_eq!(models.len(), 2);
+        assert_eq!(
+            primary_model(&models, Some("claude-sonnet-4-5-20250929")),
+            Some("claude-sonnet-4-5")
+        );
+        // Without an assistant model, the heaviest user wins.
+        assert_eq!(primary_model(&models, None), Some("claude-sonnet-4-5"));
+
+        let last = turn_token_breakdown(&json!({
+            "input_tokens": 500, "output_tokens": 70, "cache_read_input_tokens": 1000,
+        }))
+        .unwrap();
+        assert_eq!(last.total_tokens, 1570);
+
+        let mut totals = ThreadTokenTotals::default();
+        totals.total.add(&last);
+        totals.total.add(&last);
+        for entry in &models {
+            totals.per_model.insert(entry.model.clone(), entry.usage);
+        }
+        let usage = format_token_usage(&last, &models, &totals, Some("claude-sonnet-4-5"));
+        assert_eq!(usage["last"]["totalTokens"], 1570);
+        assert_eq!(usage["total"]["totalTokens"], 3140);
+        assert_eq!(usage["modelContextWindow"], 1000000);
+        let per_model = usage["perModel"].as_array().unwrap();
+        assert_eq!(per_model[0]["model"], "claude-haiku-4-5");
+        assert_eq!(per_model[0]["primary"], false);
+        assert_eq!(per_model[0]["last"]["totalTokens"], 450);
+        assert_eq!(per_model[1]["primary"], true);
+        assert_eq!(per_model[1]["total"]["cachedInputTokens"], 1000);
+    }
+
+    #[test]
+    fn update_thread_meta_persists_and_prunes_entries() {
+        let dir = std::env::temp_dir().join(format!("thread-meta-{}", Uuid::new_v4()));
+        let path = dir.join("thread_meta.json");
+        let meta = update_thread_meta(&path, "ws-1", "t1", |meta| {
+            meta.title = Some("Auth refactor".to_string());
+            meta.pinned_at = Some(10);
+        })
+        .expect("update meta");
+        assert_eq!(meta.title.as_deref(), Some("Auth refactor"));
+        let stored = read_thread_meta(&path).expect("read meta");
+        assert_eq!(stored["ws-1"]["t1"].pinned_at, Some(10));
+
+        update_thread_meta(&path, "ws-1", "t1", |meta| {
+            meta.title = None;
+            meta.pinned_at = None;
+        })
+        .expect("clear meta");
+        assert!(read_thread_meta(&path).expect("read meta").is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn resolve_thread_cwd_stays_inside_the_workspace() {
+        let dir = std::env::temp_dir().join(format!("thread-cwd-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("packages").join("foo")).unwrap();
+        std::fs::write(dir.join("README.md"), "readme").unwrap();
+        let root = dir.to_string_lossy().to_string();
+
+        assert_eq!(
+            resolve_thread_cwd(&root, "packages/foo/").unwrap(),
+            Some(dir.join("packages").join("foo"))
+        );
+        assert_eq!(resolve_thread_cwd(&root, " . ").unwrap(), None);
+        assert_eq!(resolve_thread_cwd(&root, "packages/..").unwrap(), None);
+        assert!(resolve_thread_cwd(&root, "../").is_err());
+        assert!(resolve_thread_cwd(&root, "/etc").is_err());
+        assert!(resolve_thread_cwd(&root, "missing").is_err());
+        assert!(resolve_thread_cwd(&root, "README.md").is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn thread_tags_normalize_filter_and_count() {
+        assert_eq!(normalize_thread_tag("  Prod   Incident "), Some("prod-incident".to_string()));
+        assert_eq!(normalize_thread_tag("   "), None);
+        assert_eq!(
+            normalize_thread_tag(&"x".repeat(50)).map(|tag| tag.len()),
+            Some(MAX_THREAD_TAG_CHARS)
+        );
+
+        let tagged = |tags: &[&str]| ThreadMeta {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..ThreadMeta::default()
+        };
+        let mut meta = HashMap::new();
+        meta.insert("t1".to_string(), tagged(&["bug", "prod-incident"]));
+        meta.insert("t2".to_string(), tagged(&["bug"]));
+        meta.insert("t3".to_string(), tagged(&["experiment"]));
+
+        let wanted = vec!["bug".to_string(), "prod-incident".to_string()];
+        assert!(thread_has_tags(meta.get("t1"), &wanted));
+        assert!(!thread_has_tags(meta.get("t2"), &wanted));
+        assert!(!thread_has_tags(None, &wanted));
+        assert!(thread_has_tags(None, &[]));
+
+        assert_eq!(
+            count_thread_tags(&meta),
+            vec![
+                ("bug".to_string(), 2),
+                ("experiment".to_string(), 1),
+                ("prod-incident".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn session_thread_summary_includes_meta_and_falls_back_to_mtime() {
+        let entry: ClaudeSessionEntry = serde_json::from_value(json!({
+            "sessionId": "s1",
+            "fileMtime": 1_700_000_000_000i64,
+            "firstPrompt": "Fix the login flow",
+            "gitBranch": "main",
+        }))
+        .expect("session entry");
+        let meta = ThreadMeta {
+            title: Some("Login".to_string()),
+            pinned_at: Some(5),
+            tags: vec!["bug".to_string()],
+            cwd: None,
+            summary: None,
+            updated_at: None,
+        };
+        let summary = session_thread_summary(entry, Some(&meta), "/repo");
+        assert_eq!(summary["id"], "s1");
+        assert_eq!(summary["title"], "Login");
+        assert_eq!(summary["pinned"], true);
+        assert_eq!(summary["tags"], json!(["bug"]));
+        assert_eq!(summary["updatedAt"], 1_700_000_000_000i64);
+        assert_eq!(summary["cwd"], "/repo");
+    }
+
+    #[test]
+    fn tool_result_output_skips_cached_images() {
+        let content = json!([
+            { "type": "text", "text": "Screenshot saved" },
+            { "type": "image", "path": "/tmp/a.png", "mediaType": "image/png" }
+        ]);
+        assert_eq!(tool_result_output(&content), "Screenshot saved");
+        let images_only = json!([{ "type": "image", "path": "/tmp/a.png" }]);
+        assert_eq!(tool_result_output(&images_only), "");
+    }
+
+    #[test]
+    fn build_tool_item_maps_todo_write_to_todo_list() {
+        let input = json!({
+            "todos": [
+                { "content": "Write tests", "status": "completed", "activeForm": "Writing tests" },
+                { "content": "Fix bug", "status": "in_progress", "activeForm": "Fixing bug" },
+                { "content": "  ", "status": "pending" },
+                { "content": "Ship it", "status": "unknown" }
+            ]
+        });
+        let item = build_tool_item("tool-1", "TodoWrite", &input, "running", None, None, None);
+        assert_eq!(item["type"], "todoList");
+        assert_eq!(item["totalCount"], 3);
+        assert_eq!(item["completedCount"], 1);
+        assert_eq!(item["todos"][1]["status"], "in_progress");
+        assert_eq!(item["todos"][1]["activeForm"], "Fixing bug");
+        assert_eq!(item["todos"][2]["status"], "pending");
+    }
+
+    #[test]
+    fn tool_details_parse_common_tools() {
+        let bash = json!({ "command": "cargo test", "description": "Run tests" });
+        let failed = tool_details("Bash", &bash, Some("Exit code 101\nerror"), None).unwrap();
+        assert_eq!(failed["command"], "cargo test");
+        assert_eq!(failed["exitCode"], 101);
+        let passed = tool_details("Bash", &bash, Some("ok"), None).unwrap();
+        assert_eq!(passed["exitCode"], 0);
+        let interrupted = json!({ "interrupted": true });
+        let stopped = tool_details("Bash", &bash, Some(""), Some(&interrupted)).unwrap();
+        assert!(stopped["exitCode"].is_null());
+        assert!(tool_details("Bash", &bash, None, None).unwrap()["exitCode"].is_null());
+
+        let grep = json!({ "pattern": "fn main", "output_mode": "count" });
+        let counts = tool_details("Grep", &grep, Some("src/a.rs:2\nsrc/b.rs:3"), None).unwrap();
+        assert_eq!(counts["matchCount"], 5);
+        let files = json!({ "pattern": "fn main" });
+        let found = tool_details("Grep", &files, Some("Found 2 files\na.rs\nb.rs"), None).unwrap();
+        assert_eq!(found["fileCount"], 2);
+        assert!(found["matchCount"].is_null());
+
+        let read = json!({ "file_path": "/repo/src/lib.rs", "offset": 10 });
+        let range = tool_details("Read", &read, Some("    10→a\n    11→b"), None).unwrap();
+        assert_eq!(range["path"], "/repo/src/lib.rs");
+        assert_eq!(range["lineStart"], 10);
+        assert_eq!(range["lineEnd"], 11);
+
+        let fetch = json!({ "url": "https://example.com" });
+        let result = json!({ "code": 404, "codeText": "Not Found" });
+        let fetched = tool_details("WebFetch", &fetch, Some("missing"), Some(&result)).unwrap();
+        assert_eq!(fetched["status"], 404);
+        assert_eq!(fetched["statusText"], "Not Found");
+
+        assert!(tool_details("Glob", &json!({}), None, None).is_none());
+    }
+
+    #[test]
+    fn truncate_item_output_cuts_on_char_boundary() {
+        let item = build_tool_item(
+            "tool-1",
+            "Bash",
+            &Value::Null,
+            "completed",
+            Some("aé-rest"),
+            None,
+            None,
+        );
+        let cut = truncate_item_output(item.clone(), 2);
+        assert_eq!(cut["aggregatedOutput"], "a");
+        assert_eq!(cut["outputTruncated"], true);
+        assert_eq!(cut["outputBytes"], 8);
+
+        assert_eq!(truncate_item_output(item.clone(), 0), item);
+        assert_eq!(truncate_item_output(item.clone(), 64), item);
+    }
+
+    #[test]
+    fn read_item_full_output_finds_tool_result() {
+        let dir = std::env::temp_dir().join(format!("full-output-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let lines = [
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "tool_use", "id": "toolu_1", "name": "Bash", "input": {"command": "ls"}},
+            ]}}),
+            json!({"type": "user", "message": {"content": [
+                {"type": "tool_result", "tool_use_id": "toolu_1", "content": "line one\nline two"},
+            ]}}),
+        ];
+        let body: Vec<String> = lines.iter().map(Value::to_string).collect();
+        fs::write(&path, format!("not json\n{}\n", body.join("\n"))).unwrap();
+
+        assert_eq!(
+            read_item_full_output("ws", &path, "toolu_1").unwrap().as_deref(),
+            Some("line one\nline two")
+        );
+        assert_eq!(read_item_full_output("ws", &path, "toolu_2").unwrap(), None);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn build_permission_response_allows_or_denies() {
+        let input = json!({ "command": "ls" });
+        let allowed = build_permission_response(&json!({ "decision": "accept" }), &input);
+        assert_eq!(allowed["behavior"], "allow");
+        assert_eq!(allowed["updatedInput"], input);
+
+        let edited = build_permission_response(
+            &json!({ "decision": "accept", "updatedInput": { "command": "ls -la" } }),
+            &input,
+        );
+        assert_eq!(edited["updatedInput"]["command"], "ls -la");
+
+        let denied = build_permission_response(&json!({ "decision": "decline" }), &input);
+        assert_eq!(denied["behavior"], "deny");
+        assert!(denied.get("interrupt").is_none());
+
+        let cancelled = build_permission_response(
+            &json!({ "decision": "cancel", "message": "Stop" }),
+            &input,
+        );
+        assert_eq!(cancelled["message"], "Stop");
+        assert_eq!(cancelled["interrupt"], true);
+    }
+
+    #[test]
+    fn timed_out_approvals_use_the_default_decision() {
+        let input = json!({ "command": "rm -rf build" });
+        let declined = build_permission_response(&timeout_decision_result("decline", 60), &input);
+        assert_eq!(declined["behavior"], "deny");
+        assert_eq!(
+            declined["message"],
+            "No one answered this permission prompt within 60s."
+        );
+        assert!(declined.get("interrupt").is_none());
+
+        let accepted = build_permission_response(&timeout_decision_result("accept", 60), &input);
+        assert_eq!(accepted["behavior"], "allow");
+        // Anything unexpected falls back to declining.
+        let fallback = timeout_decision_result("cancel", 5);
+        assert_eq!(fallback["decision"], "decline");
+    }
+
+    #[test]
+    fn container_workdir_names_the_session_project_dir() {
+        let mut entry = WorkspaceEntry {
+            id: "ws".to_string(),
+            name: "repo".to_string(),
+            path: "/code/repo".to_string(),
+            claude_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let scoped = Path::new("/code/repo/web");
+        assert_eq!(session_project_path(&entry, scoped), "/code/repo/web");
+
+        entry.settings.container = Some(ContainerSettings {
+            mode: ContainerMode::Run,
+            target: "image".to_string(),
+            workdir: None,
+            runtime: None,
+            claude_bin: None,
+        });
+        assert_eq!(session_project_path(&entry, scoped), "/code/repo/web");
+
+        if let Some(container) = entry.settings.container.as_mut() {
+            container.workdir = Some("/workspace".to_string());
+        }
+        assert_eq!(
+            session_project_path(&entry, Path::new(&entry.path)),
+            "/workspace"
+        );
+        assert_eq!(session_project_path(&entry, scoped), "/workspace/web");
+        assert_eq!(
+            encode_project_path(&session_project_path(&entry, scoped)),
+            "-workspace-web"
+        );
+    }
+
+    #[test]
+    fn migrate_project_dirs_moves_sessions_and_rewrites_index() {
+        let root = std::env::temp_dir().join(format!("projects-{}", Uuid::new_v4()));
+        let old_dir = root.join(encode_project_path("/code/old"));
+        let new_dir = root.join(encode_project_path("/code/new"));
+        let old_scoped = root.join(encode_project_path("/code/old/web"));
+        std::fs::create_dir_all(&old_dir).unwrap();
+        std::fs::create_dir_all(&old_scoped).unwrap();
+        std::fs::create_dir_all(&new_dir).unwrap();
+        std::fs::write(old_dir.join("s1.jsonl"), "{}\n").unwrap();
+        std::fs::write(old_dir.join("s2.jsonl"), "old\n").unwrap();
+        std::fs::write(new_dir.join("s2.jsonl"), "new\n").unwrap();
+        std::fs::write(old_scoped.join("s3.jsonl"), "{}\n").unwrap();
+        let index = json!({"entries": [{
+            "sessionId": "s1",
+            "fullPath": old_dir.join("s1.jsonl").to_string_lossy(),
+            "projectPath": "/code/old",
+        }]});
+        std::fs::write(old_dir.join("sessions-index.json"), index.to_string()).unwrap();
+
+        let moved =
+            migrate_project_dirs(&root, "/code/old", "/code/new", &["web".to_string()]).unwrap();
+        assert_eq!(moved, 2);
+        assert!(new_dir.join("s1.jsonl").exists());
+        assert_eq!(
+            std::fs::read_to_string(new_dir.join("s2.jsonl")).unwrap(),
+            "new\n"
+        );
+        assert!(old_dir.join("s2.jsonl").exists());
+        let new_scoped = root.join(encode_project_path("/code/new/web"));
+        assert!(new_scoped.join("s3.jsonl").exists());
+        assert!(!old_scoped.exists());
+        let index: Value = serde_json::from_str(
+            &std::fs::read_to_string(new_dir.join("sessions-index.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            index["entries"][0]["fullPath"],
+            new_dir.join("s1.jsonl").to_string_lossy().as_ref()
+        );
+        assert_eq!(index["entries"][0]["projectPath"], "/code/new");
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn copy_session_lines_stops_before_an_edited_message() {
+        let dir = std::env::temp_dir().join(format!("fork-edit-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.jsonl");
+        let lines = [
+            json!({ "type": "user", "uuid": "u1", "sessionId": "source" }),
+            json!({ "type": "assistant", "uuid": "a1", "sessionId": "source" }),
+            json!({ "type": "user", "uuid": "u2", "sessionId": "source" }),
+            json!({ "type": "assistant", "uuid": "a2", "sessionId": "source" }),
+        ];
+        let contents: Vec<String> = lines.iter().map(Value::to_string).collect();
+        std::fs::write(&source, contents.join("\n")).unwrap();
+
+        let fork = dir.join("fork.jsonl");
+        let written = copy_session_lines(&source, &fork, "fork", Some("u2"), false).unwrap();
+        assert_eq!(written, 2);
+        let copied: Vec<Value> = std::fs::read_to_string(&fork)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let uuids: Vec<&str> = copied
+            .iter()
+            .map(|value| value["uuid"].as_str().unwrap())
+            .collect();
+        assert_eq!(uuids, vec!["u1", "a1"]);
+        assert!(copied.iter().all(|value| value["sessionId"] == "fork"));
+
+        let written = copy_session_lines(&source, &fork, "fork", Some("u1"), false).unwrap();
+        assert_eq!(written, 0);
+        assert!(copy_session_lines(&source, &fork, "fork", Some("a1"), false).is_err());
+        assert!(!fork.exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -36,7 +36,7 @@ pub(crate) fn resolve_default_claude_home() -> Option<PathBuf> {
     resolve_home_dir().map(|home| home.join(".claude"))
 }
 
-fn resolve_home_dir() -> Option<PathBuf> {
+pub(crate) fn resolve_home_dir() -> Option<PathBuf> {
     if let Ok(value) = env::var("HOME") {
         if !value.trim().is_empty() {
             return Some(PathBuf::from(value));
@@ -0,0 +1,340 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::claude_home::{resolve_default_claude_home, resolve_home_dir};
+use crate::file_io::{FileVersion, FileWriteResult, LineRange, TextFileResponse};
+use crate::file_policy::{FileKind, FileScope};
+use crate::files::{file_read_impl, file_write_impl, resolve_root};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// The CLI follows `@file` imports at most this many hops deep.
+pub(crate) const MAX_IMPORT_DEPTH: usize = 5;
+const CLAUDE_MD_FILENAME: &str = "CLAUDE.md";
+
+/// An `@path` reference in a CLAUDE.md file, resolved the way the CLI would.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClaudeMdImport {
+    /// The reference as written, without the `@`.
+    pub(crate) reference: String,
+    pub(crate) path: String,
+    /// File containing the reference.
+    pub(crate) source: String,
+    /// One-based line of the reference in `source`.
+    pub(crate) line: usize,
+    /// 1 for imports in the edited file, 2 for imports inside those, and so on.
+    pub(crate) depth: usize,
+    pub(crate) estimated_tokens: usize,
+    /// Why the import won't be loaded; `None` when it resolves.
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClaudeMdDocument {
+    pub(crate) file: TextFileResponse,
+    pub(crate) imports: Vec<ClaudeMdImport>,
+    /// This file plus everything it imports.
+    pub(crate) estimated_tokens: usize,
+    /// Global and (for workspace scope) project memory as assembled at session start.
+    pub(crate) context_tokens: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClaudeMdWriteResponse {
+    pub(crate) result: FileWriteResult,
+    /// Imports of the content that was submitted, for flagging broken references.
+    pub(crate) imports: Vec<ClaudeMdImport>,
+}
+
+/// Rough token count used for context budgeting; about four characters per token.
+pub(crate) fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// `@path` references with their one-based lines, skipping code blocks and
+/// inline code spans as the CLI does.
+fn import_references(content: &str) -> Vec<(usize, String)> {
+    let mut references = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let outside_code = line
+            .split('`')
+            .step_by(2)
+            .collect::<Vec<_>>()
+            .join(" ");
+        for word in outside_code.split_whitespace() {
+            let Some(reference) = word.strip_prefix('@') else {
+                continue;
+            };
+            let reference = reference.trim_end_matches([',', ';', ':', ')', '!', '?']);
+            if reference.is_empty() || reference.starts_with('@') {
+                continue;
+            }
+            references.push((index + 1, reference.to_string()));
+        }
+    }
+    references
+}
+
+fn resolve_import_path(base_dir: &Path, reference: &str) -> PathBuf {
+    if let Some(rest) = reference.strip_prefix("~/") {
+        if let Some(home) = resolve_home_dir() {
+            return home.join(rest);
+        }
+    }
+    let path = Path::new(reference);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.join(path)
+    }
+}
+
+fn collect_imports(
+    source: &Path,
+    content: &str,
+    depth: usize,
+    seen: &mut HashSet<PathBuf>,
+    out: &mut Vec<ClaudeMdImport>,
+) {
+    let base_dir = source.parent().unwrap_or(Path::new("."));
+    for (line, reference) in import_references(content) {
+        let path = resolve_import_path(base_dir, &reference);
+        let mut import = ClaudeMdImport {
+            reference,
+            path: path.to_string_lossy().to_string(),
+            source: source.to_string_lossy().to_string(),
+            line,
+            depth,
+            estimated_tokens: 0,
+            error: None,
+        };
+        if depth > MAX_IMPORT_DEPTH {
+            import.error = Some(format!("Imports nest more than {MAX_IMPORT_DEPTH} levels deep."));
+            out.push(import);
+            continue;
+        }
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if path.is_file() => {
+                import.error = Some("File is not valid UTF-8 text.".to_string());
+                out.push(import);
+                continue;
+            }
+            Err(_) => {
+                import.error = Some("File not found.".to_string());
+                out.push(import);
+                continue;
+            }
+        };
+        let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !seen.insert(key) {
+            import.error = Some("Already imported.".to_string());
+            out.push(import);
+            continue;
+        }
+        import.estimated_tokens = estimate_tokens(&text);
+        out.push(import);
+        collect_imports(&path, &text, depth + 1, seen, out);
+    }
+}
+
+/// Every import reachable from `content`, depth-first in file order.
+pub(crate) fn resolve_imports(source: &Path, content: &str) -> Vec<ClaudeMdImport> {
+    let mut seen = HashSet::new();
+    if let Ok(canonical) = source.canonicalize() {
+        seen.insert(canonical);
+    }
+    let mut imports = Vec::new();
+    collect_imports(source, content, 1, &mut seen, &mut imports);
+    imports
+}
+
+/// Tokens for a memory file and the imports it pulls in.
+pub(crate) fn memory_tokens(content: &str, imports: &[ClaudeMdImport]) -> usize {
+    estimate_tokens(content)
+        + imports
+            .iter()
+            .filter(|import| import.error.is_none())
+            .map(|import| import.estimated_tokens)
+            .sum::<usize>()
+}
+
+fn file_tokens(path: &Path) -> usize {
+    fs::read_to_string(path)
+        .map(|content| memory_tokens(&content, &resolve_imports(path, &content)))
+        .unwrap_or(0)
+}
+
+/// Read a CLAUDE.md file along with its resolved imports and token estimates.
+#[tauri::command]
+pub(crate) async fn claude_md_read(
+    scope: FileScope,
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ClaudeMdDocument, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "claude_md_read",
+            json!({ "scope": scope, "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let root = resolve_root(scope, workspace_id.as_deref(), &*state).await?;
+    let file = file_read_impl(
+        scope,
+        FileKind::ClaudeMd,
+        workspace_id,
+        LineRange::default(),
+        &*state,
+        &app,
+    )
+    .await?;
+    let path = root.join(CLAUDE_MD_FILENAME);
+    let imports = resolve_imports(&path, &file.content);
+    let estimated_tokens = memory_tokens(&file.content, &imports);
+    let context_tokens = match scope {
+        FileScope::Global => estimated_tokens,
+        FileScope::Workspace => {
+            estimated_tokens
+                + resolve_default_claude_home()
+                    .map(|home| file_tokens(&home.join(CLAUDE_MD_FILENAME)))
+                    .unwrap_or(0)
+        }
+    };
+    Ok(ClaudeMdDocument {
+        file,
+        imports,
+        estimated_tokens,
+        context_tokens,
+    })
+}
+
+/// Write a CLAUDE.md file. Broken imports don't block the write; they come
+/// back in `imports` with an `error` so the editor can flag them.
+#[tauri::command]
+pub(crate) async fn claude_md_write(
+    scope: FileScope,
+    workspace_id: Option<String>,
+    content: String,
+    expected_version: Option<FileVersion>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ClaudeMdWriteResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "claude_md_write",
+            json!({
+                "scope": scope,
+                "workspaceId": workspace_id,
+                "content": content,
+                "expectedVersion": expected_version,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let root = resolve_root(scope, workspace_id.as_deref(), &*state).await?;
+    let imports = resolve_imports(&root.join(CLAUDE_MD_FILENAME), &content);
+    let result = file_write_impl(
+        scope,
+        FileKind::ClaudeMd,
+        workspace_id,
+        content,
+        expected_version,
+        &*state,
+        &app,
+    )
+    .await?;
+    Ok(ClaudeMdWriteResponse { result, imports })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claude-md-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn import_references_skip_code_and_emails() {
+        let content = "See @docs/style.md, and `@not/this`.\n\
+```\n@inside/fence.md\n```\nMail ada@example.com or @notes.md";
+        assert_eq!(
+            import_references(content),
+            vec![(1, "docs/style.md".to_string()), (5, "notes.md".to_string())]
+        );
+    }
+
+    #[test]
+    fn resolve_imports_follows_nested_files_and_flags_problems() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/style.md"), "Use tabs. @rules.md @../CLAUDE.md").unwrap();
+        fs::write(dir.join("docs/rules.md"), "12345678").unwrap();
+        fs::write(dir.join("CLAUDE.md"), "@docs/style.md @missing.md").unwrap();
+
+        let content = fs::read_to_string(dir.join("CLAUDE.md")).unwrap();
+        let imports = resolve_imports(&dir.join("CLAUDE.md"), &content);
+        let summary: Vec<_> = imports
+            .iter()
+            .map(|import| (import.reference.as_str(), import.depth, import.error.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("docs/style.md", 1, false),
+                ("rules.md", 2, false),
+                ("../CLAUDE.md", 2, true),
+                ("missing.md", 1, true),
+            ]
+        );
+        assert_eq!(imports[1].estimated_tokens, 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resolve_imports_stops_past_max_depth() {
+        let dir = temp_dir();
+        for level in 0..=MAX_IMPORT_DEPTH {
+            fs::write(
+                dir.join(format!("{level}.md")),
+                format!("@{}.md", level + 1),
+            )
+            .unwrap();
+        }
+        let imports = resolve_imports(&dir.join("root.md"), "@0.md");
+        let last = imports.last().expect("imports");
+        assert_eq!(last.depth, MAX_IMPORT_DEPTH + 1);
+        assert!(last.error.as_deref().unwrap().contains("levels deep"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 use crate::claude_home::resolve_default_claude_home;
 
@@ -41,6 +42,42 @@ fn get_tasks_dir(session_id: &str) -> Option<PathBuf> {
     }
 }
 
+/// Tasks in `dir` whose file changed at or after `since` (ms), with that time.
+fn read_tasks_updated_since(dir: &Path, since: i64) -> Vec<(ClaudeTask, i64)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok()?
+                .duration_since(UNIX_EPOCH)
+                .ok()?
+                .as_millis() as i64;
+            if modified < since {
+                return None;
+            }
+            let content = fs::read_to_string(&path).ok()?;
+            let task = serde_json::from_str::<ClaudeTask>(&content).ok()?;
+            Some((task, modified))
+        })
+        .collect()
+}
+
+/// Tasks of a session that changed at or after `since` (ms).
+pub(crate) fn session_tasks_updated_since(
+    session_id: &str,
+    since: i64,
+) -> Vec<(ClaudeTask, i64)> {
+    get_tasks_dir(session_id)
+        .map(|dir| read_tasks_updated_since(&dir, since))
+        .unwrap_or_default()
+}
+
 /// Read all tasks for a given session (thread) ID
 #[tauri::command]
 pub async fn get_claude_tasks(session_id: String) -> Result<ClaudeTasksResponse, String> {
@@ -69,12 +106,12 @@ pub async fn get_claude_tasks(session_id: String) -> Result<ClaudeTasksResponse,
                         match serde_json::from_str::<ClaudeTask>(&content) {
                             Ok(task) => tasks.push(task),
                             Err(e) => {
-                                eprintln!("Failed to parse task file {:?}: {}", path, e);
+                                tracing::warn!("Failed to parse task file {:?}: {}", path, e);
                             }
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to read task file {:?}: {}", path, e);
+                        tracing::warn!("Failed to read task file {:?}: {}", path, e);
                     }
                 }
             }
@@ -133,4 +170,22 @@ mod tests {
         assert_eq!(task.status, "");
         assert!(task.blocks.is_empty());
     }
+
+    #[test]
+    fn test_read_tasks_updated_since() {
+        let dir = std::env::temp_dir().join(format!("claude-tasks-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("1.json"),
+            r#"{"id":"1","subject":"Ship","status":"completed"}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let tasks = read_tasks_updated_since(&dir, 0);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].0.status, "completed");
+        assert!(read_tasks_updated_since(&dir, i64::MAX).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,590 @@
+//! Fenced code blocks in assistant replies, for answers given in chat rather
+//! than as file edits. A block is applied either as the whole new content of
+//! the target file or, for `diff`/`patch` blocks, as hunks matched against
+//! the file by their context lines, since line numbers in chat patches are
+//! often off.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Component, Path, PathBuf};
+
+use git2::{DiffOptions, Patch};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::{extract_text_from_message, resolve_thread_transcript, workspace_entry};
+use crate::file_io::content_hash;
+use crate::remote_backend;
+use crate::state::AppState;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodeBlock {
+    pub(crate) index: usize,
+    pub(crate) language: Option<String>,
+    /// Target named in the fence info (`rust:src/lib.rs`, `path=...`) or by
+    /// a patch's `+++` header.
+    pub(crate) path: Option<String>,
+    pub(crate) code: String,
+    pub(crate) is_patch: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodeBlockApplication {
+    pub(crate) path: String,
+    /// The file did not exist before.
+    pub(crate) created: bool,
+    /// Unified diff from the current file to the result.
+    pub(crate) diff: String,
+    /// Hash of the file the diff was computed against; pass it back to apply
+    /// only if the file has not changed since the preview.
+    pub(crate) base_hash: String,
+    pub(crate) applied: bool,
+}
+
+fn looks_like_path(token: &str) -> bool {
+    token.contains('/')
+        || token
+            .rsplit_once('.')
+            .is_some_and(|(stem, _)| !stem.is_empty())
+}
+
+/// Language and target path from a fence's info string.
+fn parse_fence_info(info: &str) -> (Option<String>, Option<String>) {
+    let mut tokens = info.split_whitespace();
+    let Some(first) = tokens.next() else {
+        return (None, None);
+    };
+    let (mut language, mut path) = match first.split_once(':') {
+        Some((language, path)) if !path.is_empty() => {
+            (Some(language.to_string()), Some(path.to_string()))
+        }
+        _ if first.contains('/') => (None, Some(first.to_string())),
+        _ => (Some(first.to_string()), None),
+    };
+    for token in tokens {
+        let value = ["path=", "file=", "title="]
+            .iter()
+            .find_map(|key| token.strip_prefix(key))
+            .map(|value| value.trim_matches(|ch| ch == '"' || ch == '\''));
+        match value {
+            Some(value) if !value.is_empty() => path = Some(value.to_string()),
+            None if path.is_none() && looks_like_path(token) => path = Some(token.to_string()),
+            _ => {}
+        }
+    }
+    if language.as_deref() == Some("") {
+        language = None;
+    }
+    (language, path)
+}
+
+fn patch_target(code: &str) -> Option<String> {
+    code.lines()
+        .take_while(|line| !line.starts_with("@@"))
+        .find_map(|line| line.strip_prefix("+++ "))
+        .map(|target| target.trim())
+        .filter(|target| *target != "/dev/null")
+        .map(|target| target.strip_prefix("b/").unwrap_or(target).to_string())
+}
+
+/// Every fenced block in `text`, in order. An unclosed block runs to the end.
+pub(crate) fn parse_code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, String, Vec<&str>)> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|ch| *ch == '`' || *ch == '~');
+        let fence_len = fence_char.map_or(0, |ch| trimmed.chars().take_while(|c| *c == ch).count());
+        match open.as_mut() {
+            Some((ch, len, _, lines)) => {
+                if fence_char == Some(*ch)
+                    && fence_len >= *len
+                    && trimmed[fence_len..].trim().is_empty()
+                {
+                    let (_, _, info, lines) = open.take().unwrap_or_default();
+                    blocks.push(build_block(blocks.len(), &info, &lines));
+                } else {
+                    lines.push(line);
+                }
+            }
+            None => {
+                if let Some(ch) = fence_char.filter(|_| fence_len >= 3) {
+                    let info = trimmed[fence_len..].trim().to_string();
+                    open = Some((ch, fence_len, info, Vec::new()));
+                }
+            }
+        }
+    }
+    if let Some((_, _, info, lines)) = open {
+        blocks.push(build_block(blocks.len(), &info, &lines));
+    }
+    blocks
+}
+
+fn build_block(index: usize, info: &str, lines: &[&str]) -> CodeBlock {
+    let (language, path) = parse_fence_info(info);
+    let mut code = lines.join("\n");
+    if !code.is_empty() {
+        code.push('\n');
+    }
+    let is_patch = matches!(language.as_deref(), Some("diff" | "patch"))
+        || code.starts_with("diff --git")
+        || code.starts_with("--- ")
+        || code.starts_with("@@ ");
+    let path = path.or_else(|| if is_patch { patch_target(&code) } else { None });
+    CodeBlock {
+        index,
+        language,
+        path,
+        code,
+        is_patch,
+    }
+}
+
+struct Hunk {
+    old_start: usize,
+    /// Line counts from the `@@` header, when it has them
+    old_len: Option<usize>,
+    new_len: Option<usize>,
+    old: Vec<String>,
+    new: Vec<String>,
+}
+
+impl Hunk {
+    /// The header's line counts say more lines belong to this hunk. Without
+    /// counts the hunk runs until the next header.
+    fn expects_more(&self) -> bool {
+        match (self.old_len, self.new_len) {
+            (Some(old_len), Some(new_len)) => self.old.len() < old_len || self.new.len() < new_len,
+            _ => false,
+        }
+    }
+}
+
+/// Parse a `-start,len` or `+start,len` range; a missing length means 1.
+fn parse_range(range: &str, sign: char) -> Option<(usize, usize)> {
+    let range = range.strip_prefix(sign)?;
+    let (start, len) = range.split_once(',').unwrap_or((range, "1"));
+    Some((start.parse().ok()?, len.parse().ok()?))
+}
+
+/// Whether line `index` starts another file's headers: a `diff --git` line,
+/// or a `---` line directly followed by `+++`.
+fn starts_file_header(lines: &[&str], index: usize) -> bool {
+    let line = lines[index];
+    line.starts_with("diff --git ")
+        || (line.starts_with("--- ")
+            && lines
+                .get(index + 1)
+                .is_some_and(|next| next.starts_with("+++ ")))
+}
+
+fn parse_hunks(patch: &str) -> Result<Vec<Hunk>, String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut files = 0;
+    // Between a file's headers and its first `@@`
+    let mut in_headers = true;
+    for (index, line) in lines.iter().enumerate() {
+        let in_hunk = hunks.last().is_some_and(Hunk::expects_more);
+        if !in_hunk && starts_file_header(&lines, index) {
+            if !in_headers || files == 0 {
+                files += 1;
+            }
+            if files > 1 {
+                return Err("The patch changes more than one file.".to_string());
+            }
+            in_headers = true;
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("@@") {
+            let mut ranges = header.split_whitespace();
+            let old = ranges.next().and_then(|range| parse_range(range, '-'));
+            let new = ranges.next().and_then(|range| parse_range(range, '+'));
+            hunks.push(Hunk {
+                old_start: old.map_or(1, |(start, _)| start),
+                old_len: old.map(|(_, len)| len),
+                new_len: new.map(|(_, len)| len),
+                old: Vec::new(),
+                new: Vec::new(),
+            });
+            in_headers = false;
+            continue;
+        }
+        if in_headers || line.starts_with('\\') {
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        match line.chars().next() {
+            Some('-') => hunk.old.push(line[1..].to_string()),
+            Some('+') => hunk.new.push(line[1..].to_string()),
+            // Chat often strips the space off blank context lines.
+            Some(' ') | None => {
+                let context = line.get(1..).unwrap_or("").to_string();
+                hunk.old.push(context.clone());
+                hunk.new.push(context);
+            }
+            Some(_) => return Err(format!("Unexpected line in patch: {line}")),
+        }
+    }
+    if hunks.is_empty() {
+        return Err("The patch has no hunks.".to_string());
+    }
+    Ok(hunks)
+}
+
+/// Apply a unified diff to `original`, placing each hunk where its removed and
+/// context lines match, nearest to the line it names.
+pub(crate) fn apply_patch_text(original: &str, patch: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = original.lines().map(str::to_string).collect();
+    let mut offset: isize = 0;
+    for (number, hunk) in parse_hunks(patch)?.into_iter().enumerate() {
+        let expected = (hunk.old_start as isize - 1 + offset).max(0) as usize;
+        let position = if hunk.old.is_empty() {
+            Some(expected.min(lines.len()))
+        } else {
+            (0..=lines.len().saturating_sub(hunk.old.len()))
+                .filter(|start| lines[*start..].starts_with(&hunk.old))
+                .min_by_key(|start| start.abs_diff(expected))
+        };
+        let Some(position) = position else {
+            return Err(format!("Hunk {} does not match the file.", number + 1));
+        };
+        offset += hunk.new.len() as isize - hunk.old.len() as isize;
+        lines.splice(position..position + hunk.old.len(), hunk.new);
+    }
+    // Keep Windows line endings; `lines()` dropped the carriage returns.
+    let newline = if original.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut patched = lines.join(newline);
+    if !patched.is_empty() && (original.is_empty() || original.ends_with('\n')) {
+        patched.push_str(newline);
+    }
+    Ok(patched)
+}
+
+fn unified_diff(path: &str, old: &str, new: &str) -> Result<String, String> {
+    let mut options = DiffOptions::new();
+    options.context_lines(3);
+    let old_label = format!("a/{path}");
+    let new_label = format!("b/{path}");
+    let mut patch = Patch::from_buffers(
+        old.as_bytes(),
+        Some(Path::new(&old_label)),
+        new.as_bytes(),
+        Some(Path::new(&new_label)),
+        Some(&mut options),
+    )
+    .map_err(|err| err.to_string())?;
+    let buffer = patch.to_buf().map_err(|err| err.to_string())?;
+    Ok(buffer.as_str().unwrap_or_default().to_string())
+}
+
+/// `path` relative to the workspace with `.` and `..` resolved lexically.
+/// Absolute paths and paths that climb out of the workspace are refused.
+fn resolve_target(path: &str) -> Result<PathBuf, String> {
+    let mut relative = PathBuf::new();
+    for component in Path::new(path.trim()).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !relative.pop() {
+                    return Err(format!("Invalid target path: {path}"));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("Invalid target path: {path}"));
+            }
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(format!("Invalid target path: {path}"));
+    }
+    Ok(relative)
+}
+
+/// Refuse a target that is a symlink or whose existing ancestors resolve
+/// outside `root`. Runs before anything is read or created, so a missing
+/// directory is only made once its nearest existing parent checked out.
+fn check_target(root: &Path, target: &Path, relative: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid target path: {relative}");
+    if std::fs::symlink_metadata(target).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        return Err(invalid());
+    }
+    let canonical_root = root.canonicalize().map_err(|err| err.to_string())?;
+    let existing = target
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(invalid)?;
+    let canonical = existing.canonicalize().map_err(|err| err.to_string())?;
+    if !canonical.starts_with(&canonical_root) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn read_message_text(transcript: &Path, item_id: &str) -> Result<String, String> {
+    let file = File::open(transcript).map_err(|err| err.to_string())?;
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .find(|entry| {
+            entry.get("type").and_then(Value::as_str) == Some("assistant")
+                && entry.get("uuid").and_then(Value::as_str) == Some(item_id)
+        })
+        .and_then(|entry| entry.get("message").map(extract_text_from_message))
+        .ok_or_else(|| format!("Message {item_id} not found"))
+}
+
+async fn message_code_blocks(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: String,
+    item_id: String,
+) -> Result<(PathBuf, Vec<CodeBlock>), String> {
+    let entry = workspace_entry(state, workspace_id).await?;
+    let root = PathBuf::from(&entry.path);
+    tokio::task::spawn_blocking(move || {
+        let transcript = resolve_thread_transcript(&entry, &thread_id)
+            .ok_or_else(|| "Session file not found".to_string())?;
+        let text = read_message_text(&transcript, &item_id)?;
+        Ok((root, parse_code_blocks(&text)))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// The fenced code blocks of an assistant message.
+#[tauri::command]
+pub(crate) async fn extract_code_blocks(
+    workspace_id: String,
+    thread_id: String,
+    item_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<CodeBlock>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "extract_code_blocks",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "itemId": item_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(
+        message_code_blocks(&state, &workspace_id, thread_id, item_id)
+            .await?
+            .1,
+    )
+}
+
+/// Write a code block from an assistant message to `path`, relative to the
+/// workspace. With `dry_run` only the diff is returned. `expected_hash` from a
+/// preview makes the write fail if the file changed in between.
+#[tauri::command]
+pub(crate) async fn apply_code_block(
+    workspace_id: String,
+    thread_id: String,
+    item_id: String,
+    block_index: usize,
+    path: String,
+    dry_run: Option<bool>,
+    expected_hash: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CodeBlockApplication, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "apply_code_block",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "itemId": item_id,
+                "blockIndex": block_index,
+                "path": path,
+                "dryRun": dry_run,
+                "expectedHash": expected_hash,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let (root, blocks) = message_code_blocks(&state, &workspace_id, thread_id, item_id).await?;
+    let block = blocks
+        .into_iter()
+        .nth(block_index)
+        .ok_or_else(|| format!("Code block {block_index} not found"))?;
+    let relative_path = resolve_target(&path)?;
+    let target = root.join(&relative_path);
+    let dry_run = dry_run.unwrap_or(false);
+    let relative = relative_path.to_string_lossy().to_string();
+
+    let result = tokio::task::spawn_blocking(move || {
+        check_target(&root, &target, &relative)?;
+        let current = match std::fs::read(&target) {
+            Ok(bytes) => Some(bytes),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(format!("Failed to read {relative}: {err}")),
+        };
+        let base_hash = content_hash(current.as_deref().unwrap_or_default());
+        let old = match &current {
+            Some(bytes) => String::from_utf8(bytes.clone())
+                .map_err(|_| format!("{relative} is not a text file"))?,
+            None => String::new(),
+        };
+        let new = if block.is_patch {
+            apply_patch_text(&old, &block.code)?
+        } else {
+            block.code.clone()
+        };
+        let diff = unified_diff(&relative, &old, &new)?;
+        if !dry_run {
+            if expected_hash.is_some_and(|expected| expected != base_hash) {
+                return Err(format!("{relative} changed since the preview."));
+            }
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+            }
+            std::fs::write(&target, &new)
+                .map_err(|err| format!("Failed to write {relative}: {err}"))?;
+        }
+        Ok(CodeBlockApplication {
+            path: relative,
+            created: current.is_none(),
+            diff,
+            base_hash,
+            applied: !dry_run,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+
+    if !dry_run {
+        crate::audit::record(
+            "apply_code_block",
+            Some(&workspace_id),
+            json!({ "path": path, "blockIndex": block_index }),
+            &result,
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_code_blocks_reads_fences_and_targets() {
+        let text = "Try this:\n\n```rust:src/lib.rs\nfn a() {}\n```\n\n~~~diff\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1 +1 @@\n-old\n+new\n~~~\n\n```python title=\"tools/run.py\"\nprint(1)\n``` not a fence\nstill open";
+        let blocks = parse_code_blocks(text);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(blocks[0].path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(blocks[0].code, "fn a() {}\n");
+        assert!(!blocks[0].is_patch);
+        assert!(blocks[1].is_patch);
+        assert_eq!(blocks[1].path.as_deref(), Some("src/main.rs"));
+        assert_eq!(blocks[2].path.as_deref(), Some("tools/run.py"));
+        assert_eq!(blocks[2].code, "print(1)\n``` not a fence\nstill open\n");
+    }
+
+    #[test]
+    fn apply_patch_text_matches_hunks_by_context() {
+        let original = "one\ntwo\nthree\n\nfour\nfive\n";
+        // Line numbers are off and the blank context line lost its space.
+        let patch = "@@ -10,3 +10,3 @@\n three\n\n-four\n+FOUR\n";
+        assert_eq!(
+            apply_patch_text(original, patch).unwrap(),
+            "one\ntwo\nthree\n\nFOUR\nfive\n"
+        );
+        assert!(apply_patch_text(original, "@@ -1 +1 @@\n-missing\n+x\n").is_err());
+        assert_eq!(
+            apply_patch_text("", "@@ -0,0 +1,2 @@\n+a\n+b\n").unwrap(),
+            "a\nb\n"
+        );
+    }
+
+    #[test]
+    fn apply_patch_text_reads_dashed_lines_inside_hunks_as_changes() {
+        // Removing a SQL comment and adding a `++` line look like file headers.
+        let original = "-- setup\nselect 1;\n";
+        let patch = "--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1,2 @@\n--- setup\n+++ counter\n select 1;\n";
+        assert_eq!(
+            apply_patch_text(original, patch).unwrap(),
+            "++ counter\nselect 1;\n"
+        );
+
+        let two_files = "diff --git a/a b/a\n--- a/a\n+++ b/a\n@@ -1 +1 @@\n-x\n+y\ndiff --git a/b b/b\nindex 1..2\n--- a/b\n+++ b/b\n@@ -1 +1 @@\n-x\n+y\n";
+        assert_eq!(
+            apply_patch_text("x\n", two_files).unwrap_err(),
+            "The patch changes more than one file."
+        );
+        assert_eq!(patch_target(patch).as_deref(), Some("q.sql"));
+        assert_eq!(patch_target("@@ -1 +1 @@\n+++ x\n"), None);
+    }
+
+    #[test]
+    fn apply_patch_text_keeps_crlf_line_endings() {
+        let original = "one\r\ntwo\r\nthree\r\n";
+        let patch = "@@ -2 +2 @@\n-two\n+TWO\n";
+        assert_eq!(
+            apply_patch_text(original, patch).unwrap(),
+            "one\r\nTWO\r\nthree\r\n"
+        );
+        let crlf_patch = "@@ -2 +2 @@\r\n-two\r\n+TWO\r\n";
+        assert_eq!(
+            apply_patch_text(original, crlf_patch).unwrap(),
+            "one\r\nTWO\r\nthree\r\n"
+        );
+    }
+
+    #[test]
+    fn resolve_target_normalizes_and_refuses_traversal() {
+        assert_eq!(
+            resolve_target("./src/../lib/a.rs").unwrap(),
+            PathBuf::from("lib/a.rs")
+        );
+        assert!(resolve_target("../etc/passwd").is_err());
+        assert!(resolve_target("src/../../etc/passwd").is_err());
+        assert!(resolve_target("/etc/passwd").is_err());
+        assert!(resolve_target("src/..").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_target_refuses_symlinks_and_creates_nothing() {
+        use std::os::unix::fs::symlink;
+
+        let base = std::env::temp_dir().join(format!("code-blocks-{}", uuid::Uuid::new_v4()));
+        let root = base.join("repo");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "secret").unwrap();
+        symlink(outside.join("secret.txt"), root.join("link.txt")).unwrap();
+        symlink(&outside, root.join("out")).unwrap();
+
+        assert!(check_target(&root, &root.join("link.txt"), "link.txt").is_err());
+        assert!(check_target(&root, &root.join("out/new.rs"), "out/new.rs").is_err());
+        assert!(check_target(&root, &root.join("out/a/b.rs"), "out/a/b.rs").is_err());
+        assert!(!outside.join("a").exists());
+
+        assert!(check_target(&root, &root.join("src/a/b.rs"), "src/a/b.rs").is_ok());
+        assert!(!root.join("src").exists());
+        let _ = std::fs::remove_dir_all(base);
+    }
+}
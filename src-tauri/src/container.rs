@@ -0,0 +1,232 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::backend::claude_cli::build_claude_path_env;
+use crate::types::{ContainerMode, ContainerSettings};
+
+const DEFAULT_RUNTIME: &str = "docker";
+const DEFAULT_CLAUDE_BIN: &str = "claude";
+/// Host variables passed through to the container by name, so their values
+/// never appear in the process arguments.
+const FORWARDED_ENV: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_BASE_URL",
+    "CLAUDE_CODE_USE_BEDROCK",
+];
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+pub(crate) fn validate_container_settings(settings: &ContainerSettings) -> Result<(), String> {
+    if settings.target.trim().is_empty() {
+        return Err(match settings.mode {
+            ContainerMode::Exec => "Container name cannot be empty.".to_string(),
+            ContainerMode::Run => "Container image cannot be empty.".to_string(),
+        });
+    }
+    if let Some(workdir) = non_empty(&settings.workdir) {
+        if !workdir.starts_with('/') {
+            return Err(format!("Container workdir must be absolute: {workdir}"));
+        }
+    }
+    Ok(())
+}
+
+fn container_workdir(settings: &ContainerSettings, workspace_path: &str) -> String {
+    non_empty(&settings.workdir)
+        .unwrap_or(workspace_path)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Where `host_path` is seen inside the container. Paths under the workspace
+/// move with its mount; anything else is assumed to be at the same path.
+pub(crate) fn container_path(
+    settings: &ContainerSettings,
+    workspace_path: &str,
+    host_path: &Path,
+) -> String {
+    match host_path.strip_prefix(workspace_path) {
+        Ok(relative) if relative.as_os_str().is_empty() => {
+            container_workdir(settings, workspace_path)
+        }
+        Ok(relative) => format!(
+            "{}/{}",
+            container_workdir(settings, workspace_path),
+            relative.to_string_lossy().replace('\\', "/")
+        ),
+        Err(_) => host_path.to_string_lossy().to_string(),
+    }
+}
+
+/// Container CLI arguments that start the Claude CLI in `cwd`, before any
+/// Claude arguments. `run` mounts the workspace, `extra_mounts` at their own
+/// paths and the Claude home, so transcripts and logins stay on the host.
+pub(crate) fn container_args(
+    settings: &ContainerSettings,
+    workspace_path: &str,
+    cwd: &Path,
+    extra_mounts: &[PathBuf],
+    claude_home: Option<&Path>,
+) -> Vec<String> {
+    let mut args = Vec::new();
+    match settings.mode {
+        ContainerMode::Exec => args.extend(["exec".to_string(), "-i".to_string()]),
+        ContainerMode::Run => {
+            args.extend(["run".to_string(), "--rm".to_string(), "-i".to_string()]);
+            args.push("-v".to_string());
+            args.push(format!(
+                "{workspace_path}:{}",
+                container_workdir(settings, workspace_path)
+            ));
+            for mount in extra_mounts {
+                let mount = mount.to_string_lossy();
+                args.extend(["-v".to_string(), format!("{mount}:{mount}")]);
+            }
+            if let Some(home) = claude_home {
+                let home = home.to_string_lossy();
+                args.extend(["-v".to_string(), format!("{home}:{home}")]);
+                args.extend(["-e".to_string(), format!("CLAUDE_CONFIG_DIR={home}")]);
+            }
+        }
+    }
+    for name in FORWARDED_ENV {
+        args.extend(["-e".to_string(), name.to_string()]);
+    }
+    args.push("-w".to_string());
+    args.push(container_path(settings, workspace_path, cwd));
+    args.push(settings.target.trim().to_string());
+    args.push(
+        non_empty(&settings.claude_bin)
+            .unwrap_or(DEFAULT_CLAUDE_BIN)
+            .to_string(),
+    );
+    args
+}
+
+fn runtime_command(settings: &ContainerSettings) -> Command {
+    let runtime = non_empty(&settings.runtime).unwrap_or(DEFAULT_RUNTIME);
+    let mut command = Command::new(runtime);
+    if let Some(path_env) = build_claude_path_env(None) {
+        command.env("PATH", path_env);
+    }
+    command
+}
+
+/// A command running the Claude CLI inside the workspace's container; append
+/// Claude arguments with host paths mapped through `container_path`.
+pub(crate) fn build_container_claude_command(
+    settings: &ContainerSettings,
+    workspace_path: &str,
+    cwd: &Path,
+    extra_mounts: &[PathBuf],
+    claude_home: Option<&Path>,
+) -> Command {
+    let mut command = runtime_command(settings);
+    command.args(container_args(
+        settings,
+        workspace_path,
+        cwd,
+        extra_mounts,
+        claude_home,
+    ));
+    command
+}
+
+/// Check that the container (or image) is usable before a session starts.
+pub(crate) async fn check_container(settings: &ContainerSettings) -> Result<(), String> {
+    validate_container_settings(settings)?;
+    let target = settings.target.trim();
+    let mut command = runtime_command(settings);
+    match settings.mode {
+        ContainerMode::Exec => {
+            command.args(["exec", target]);
+            command.arg(non_empty(&settings.claude_bin).unwrap_or(DEFAULT_CLAUDE_BIN));
+            command.arg("--version");
+        }
+        ContainerMode::Run => {
+            command.args(["image", "inspect", target]);
+        }
+    }
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+
+    let output = timeout(Duration::from_secs(15), command.output())
+        .await
+        .map_err(|_| format!("Timed out while checking container {target}."))?
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                "Container runtime not found. Install Docker or set the runtime in workspace settings."
+                    .to_string()
+            } else {
+                err.to_string()
+            }
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(match settings.mode {
+        ContainerMode::Exec => {
+            format!("Claude CLI is not runnable in container {target}: {stderr}")
+        }
+        ContainerMode::Run => format!("Container image {target} is not available: {stderr}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(mode: ContainerMode, workdir: Option<&str>) -> ContainerSettings {
+        ContainerSettings {
+            mode,
+            target: "sandbox".to_string(),
+            workdir: workdir.map(str::to_string),
+            runtime: None,
+            claude_bin: None,
+        }
+    }
+
+    #[test]
+    fn container_args_mount_workspace_and_map_paths() {
+        let run = settings(ContainerMode::Run, Some("/workspace"));
+        let args = container_args(
+            &run,
+            "/repo",
+            Path::new("/repo/packages/app"),
+            &[PathBuf::from("/shared")],
+            Some(Path::new("/home/me/.claude")),
+        );
+        let args = args.join(" ");
+        assert!(args.starts_with(
+            "run --rm -i -v /repo:/workspace -v /shared:/shared -v /home/me/.claude:/home/me/.claude -e CLAUDE_CONFIG_DIR=/home/me/.claude"
+        ));
+        assert!(args.ends_with("-w /workspace/packages/app sandbox claude"));
+        assert!(args.contains("-e ANTHROPIC_API_KEY"));
+
+        let exec = settings(ContainerMode::Exec, None);
+        let args = container_args(&exec, "/repo", Path::new("/repo"), &[], None);
+        assert_eq!(args[..2], ["exec", "-i"]);
+        assert!(args.join(" ").ends_with("-w /repo sandbox claude"));
+
+        assert_eq!(
+            container_path(&run, "/repo", Path::new("/repo")),
+            "/workspace"
+        );
+        assert_eq!(container_path(&run, "/repo", Path::new("/other")), "/other");
+        assert!(validate_container_settings(&settings(ContainerMode::Run, Some("rel"))).is_err());
+        assert!(validate_container_settings(&ContainerSettings {
+            target: " ".to_string(),
+            ..run
+        })
+        .is_err());
+    }
+}
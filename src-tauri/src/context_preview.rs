@@ -0,0 +1,456 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::cli_permission_mode;
+use crate::claude_home::{resolve_default_claude_home, resolve_home_dir};
+use crate::claude_md::{estimate_tokens, memory_tokens, resolve_imports, ClaudeMdImport};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// A memory file the CLI loads at session start.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextMemoryFile {
+    /// `user`, `project` (the workspace or one of its ancestors) or `local`.
+    pub(crate) scope: String,
+    pub(crate) path: String,
+    pub(crate) imports: Vec<ClaudeMdImport>,
+    /// The file plus everything it imports.
+    pub(crate) estimated_tokens: usize,
+}
+
+/// A skill whose name and description are listed in the system prompt. The
+/// body is only read when the skill is invoked.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextSkill {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    /// `user` or `project`.
+    pub(crate) scope: String,
+    pub(crate) path: String,
+    pub(crate) estimated_tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextMcpServer {
+    pub(crate) name: String,
+    /// `user`, `project` (`.mcp.json`) or `local` (this project in `~/.claude.json`).
+    pub(crate) scope: String,
+    /// Tools the CLI last reported for this server; empty until a session has
+    /// started in the workspace.
+    pub(crate) tools: Vec<String>,
+    /// Tokens for the tool names only; schemas aren't reported by the CLI.
+    pub(crate) estimated_tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPermissionMode {
+    pub(crate) mode: String,
+    /// `app`, `localSettings`, `projectSettings`, `userSettings` or `cliDefault`.
+    pub(crate) source: String,
+}
+
+/// What a new session in a workspace starts with, before any user message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPreview {
+    pub(crate) workspace_id: String,
+    /// In load order: global, then ancestors down to the workspace, then local.
+    pub(crate) memory: Vec<ContextMemoryFile>,
+    pub(crate) skills: Vec<ContextSkill>,
+    pub(crate) mcp_servers: Vec<ContextMcpServer>,
+    pub(crate) permission_mode: ContextPermissionMode,
+    pub(crate) memory_tokens: usize,
+    pub(crate) skills_tokens: usize,
+    pub(crate) mcp_tokens: usize,
+    pub(crate) total_tokens: usize,
+}
+
+fn memory_file(scope: &str, path: &Path) -> Option<ContextMemoryFile> {
+    let content = fs::read_to_string(path).ok()?;
+    let imports = resolve_imports(path, &content);
+    Some(ContextMemoryFile {
+        scope: scope.to_string(),
+        path: path.to_string_lossy().to_string(),
+        estimated_tokens: memory_tokens(&content, &imports),
+        imports,
+    })
+}
+
+/// The CLAUDE.md chain for `workspace`. The CLI reads `CLAUDE.md` and
+/// `.claude/CLAUDE.md` in the workspace and every ancestor, outermost first,
+/// then `CLAUDE.local.md`. A file reachable two ways is only loaded once.
+fn memory_chain(claude_home: Option<&Path>, workspace: &Path) -> Vec<ContextMemoryFile> {
+    let mut candidates = Vec::new();
+    if let Some(home) = claude_home {
+        candidates.push(("user", home.join("CLAUDE.md")));
+    }
+    let mut ancestors: Vec<&Path> = workspace.ancestors().collect();
+    ancestors.reverse();
+    for dir in ancestors {
+        candidates.push(("project", dir.join("CLAUDE.md")));
+        candidates.push(("project", dir.join(".claude").join("CLAUDE.md")));
+    }
+    candidates.push(("local", workspace.join("CLAUDE.local.md")));
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|(_, path)| path.is_file())
+        .filter(|(_, path)| seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())))
+        .filter_map(|(scope, path)| memory_file(scope, &path))
+        .collect()
+}
+
+/// `name` and `description` from a SKILL.md frontmatter block.
+fn skill_frontmatter(content: &str) -> (Option<String>, Option<String>) {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim) != Some("---") {
+        return (None, None);
+    }
+    let mut name = None;
+    let mut description = None;
+    for line in lines {
+        if line.trim() == "---" {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches(['"', '\'']).to_string();
+        match key.trim() {
+            "name" => name = Some(value),
+            "description" => description = Some(value),
+            _ => {}
+        }
+    }
+    (name, description)
+}
+
+fn skills_in(scope: &str, skills_dir: &Path) -> Vec<ContextSkill> {
+    let Ok(entries) = fs::read_dir(skills_dir) else {
+        return Vec::new();
+    };
+    let mut skills: Vec<ContextSkill> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path().join("SKILL.md");
+            let content = fs::read_to_string(&path).ok()?;
+            let (name, description) = skill_frontmatter(&content);
+            let name = name.unwrap_or_else(|| entry.file_name().to_string_lossy().to_string());
+            let description = description.unwrap_or_default();
+            Some(ContextSkill {
+                estimated_tokens: estimate_tokens(&format!("{name}: {description}")),
+                name,
+                description,
+                scope: scope.to_string(),
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn server_names(config: Option<&Value>) -> Vec<String> {
+    let mut names: Vec<String> = config
+        .and_then(|value| value.get("mcpServers"))
+        .and_then(Value::as_object)
+        .map(|servers| servers.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Configured MCP servers, with the tools the CLI reported for each. A name
+/// configured in more than one scope resolves local, then project, then user.
+fn mcp_servers(
+    user_config: Option<&Value>,
+    workspace: &Path,
+    reported_tools: &[String],
+) -> Vec<ContextMcpServer> {
+    let project_key = workspace.to_string_lossy().to_string();
+    let local_config = user_config
+        .and_then(|value| value.get("projects"))
+        .and_then(|projects| projects.get(project_key.as_str()));
+    let project_config = read_json(&workspace.join(".mcp.json"));
+
+    let mut seen = HashSet::new();
+    let mut servers = Vec::new();
+    for (scope, config) in [
+        ("local", local_config),
+        ("project", project_config.as_ref()),
+        ("user", user_config),
+    ] {
+        for name in server_names(config) {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let prefix = format!("mcp__{name}__");
+            let tools: Vec<String> = reported_tools
+                .iter()
+                .filter(|tool| tool.starts_with(&prefix))
+                .cloned()
+                .collect();
+            servers.push(ContextMcpServer {
+                estimated_tokens: tools.iter().map(|tool| estimate_tokens(tool)).sum(),
+                name,
+                scope: scope.to_string(),
+                tools,
+            });
+        }
+    }
+    servers
+}
+
+/// `permissions.defaultMode` from the CLI settings files, most specific first.
+fn settings_permission_mode(
+    claude_home: Option<&Path>,
+    workspace: &Path,
+) -> Option<ContextPermissionMode> {
+    let project_dir = workspace.join(".claude");
+    let mut sources = vec![
+        ("localSettings", project_dir.join("settings.local.json")),
+        ("projectSettings", project_dir.join("settings.json")),
+    ];
+    if let Some(home) = claude_home {
+        sources.push(("userSettings", home.join("settings.json")));
+    }
+    sources.into_iter().find_map(|(source, path)| {
+        let mode = read_json(&path)?
+            .get("permissions")?
+            .get("defaultMode")?
+            .as_str()?
+            .to_string();
+        Some(ContextPermissionMode {
+            mode,
+            source: source.to_string(),
+        })
+    })
+}
+
+fn permission_mode(
+    access_mode: &str,
+    claude_home: Option<&Path>,
+    workspace: &Path,
+) -> ContextPermissionMode {
+    if let Some(mode) = cli_permission_mode(access_mode) {
+        return ContextPermissionMode {
+            mode: mode.to_string(),
+            source: "app".to_string(),
+        };
+    }
+    settings_permission_mode(claude_home, workspace).unwrap_or_else(|| ContextPermissionMode {
+        mode: "default".to_string(),
+        source: "cliDefault".to_string(),
+    })
+}
+
+fn build_preview(
+    workspace_id: String,
+    workspace: &Path,
+    claude_home: Option<&Path>,
+    user_config: Option<&Value>,
+    reported_tools: &[String],
+    access_mode: &str,
+) -> ContextPreview {
+    let memory = memory_chain(claude_home, workspace);
+    let mut skills = claude_home
+        .map(|home| skills_in("user", &home.join("skills")))
+        .unwrap_or_default();
+    skills.extend(skills_in(
+        "project",
+        &workspace.join(".claude").join("skills"),
+    ));
+    let mcp_servers = mcp_servers(user_config, workspace, reported_tools);
+
+    let memory_tokens = memory.iter().map(|file| file.estimated_tokens).sum();
+    let skills_tokens = skills.iter().map(|skill| skill.estimated_tokens).sum();
+    let mcp_tokens = mcp_servers.iter().map(|server| server.estimated_tokens).sum();
+    ContextPreview {
+        workspace_id,
+        memory,
+        skills,
+        mcp_servers,
+        permission_mode: permission_mode(access_mode, claude_home, workspace),
+        memory_tokens,
+        skills_tokens,
+        mcp_tokens,
+        total_tokens: memory_tokens + skills_tokens + mcp_tokens,
+    }
+}
+
+/// Reconstruct the memory files, skills, MCP tools and permission mode a new
+/// session in the workspace starts with, with token estimates for each.
+#[tauri::command]
+pub(crate) async fn get_context_preview(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ContextPreview, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_context_preview",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let workspace = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    let session = state.sessions.lock().await.get(&workspace_id).cloned();
+    let reported_tools = match session {
+        Some(session) => session.reported_tools().await,
+        None => Vec::new(),
+    };
+    let access_mode = state.app_settings.lock().await.default_access_mode.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let claude_home = resolve_default_claude_home();
+        let user_config = resolve_home_dir().and_then(|home| read_json(&home.join(".claude.json")));
+        build_preview(
+            workspace_id,
+            &workspace,
+            claude_home.as_deref(),
+            user_config.as_ref(),
+            &reported_tools,
+            &access_mode,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("context-preview-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn memory_chain_orders_global_ancestors_then_local() {
+        let root = temp_dir();
+        let home = root.join("home");
+        let workspace = root.join("repo").join("app");
+        fs::create_dir_all(&home).unwrap();
+        fs::create_dir_all(workspace.join(".claude")).unwrap();
+        fs::write(home.join("CLAUDE.md"), "global").unwrap();
+        fs::write(root.join("repo").join("CLAUDE.md"), "repo @notes.md").unwrap();
+        fs::write(root.join("repo").join("notes.md"), "12345678").unwrap();
+        fs::write(workspace.join(".claude").join("CLAUDE.md"), "app").unwrap();
+        fs::write(workspace.join("CLAUDE.local.md"), "mine").unwrap();
+
+        let chain = memory_chain(Some(&home), &workspace);
+        let summary: Vec<_> = chain
+            .iter()
+            .map(|file| (file.scope.as_str(), file.estimated_tokens))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("user", 2), ("project", 4 + 2), ("project", 1), ("local", 1)]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn skills_use_frontmatter_and_fall_back_to_dir_name() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join("review")).unwrap();
+        fs::create_dir_all(dir.join("plain")).unwrap();
+        fs::create_dir_all(dir.join("empty")).unwrap();
+        fs::write(
+            dir.join("review").join("SKILL.md"),
+            "---\nname: code-review\ndescription: \"Review a diff\"\n---\nBody",
+        )
+        .unwrap();
+        fs::write(dir.join("plain").join("SKILL.md"), "No frontmatter").unwrap();
+
+        let skills = skills_in("user", &dir);
+        let summary: Vec<_> = skills
+            .iter()
+            .map(|skill| (skill.name.as_str(), skill.description.as_str()))
+            .collect();
+        assert_eq!(summary, vec![("code-review", "Review a diff"), ("plain", "")]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn mcp_servers_prefer_local_scope_and_match_reported_tools() {
+        let workspace = temp_dir();
+        fs::write(
+            workspace.join(".mcp.json"),
+            r#"{"mcpServers":{"github":{},"docs":{}}}"#,
+        )
+        .unwrap();
+        let project = workspace.to_string_lossy().to_string();
+        let user_config = json!({
+            "mcpServers": { "docs": {}, "search": {} },
+            "projects": { project: { "mcpServers": { "github": {} } } }
+        });
+        let tools = vec![
+            "Read".to_string(),
+            "mcp__github__create_issue".to_string(),
+            "mcp__docs__lookup".to_string(),
+        ];
+
+        let servers = mcp_servers(Some(&user_config), &workspace, &tools);
+        let summary: Vec<_> = servers
+            .iter()
+            .map(|server| (server.name.as_str(), server.scope.as_str(), server.tools.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("github", "local", 1), ("docs", "project", 1), ("search", "user", 0)]
+        );
+        let _ = fs::remove_dir_all(&workspace);
+    }
+
+    #[test]
+    fn permission_mode_falls_back_to_settings_files() {
+        let workspace = temp_dir();
+        fs::create_dir_all(workspace.join(".claude")).unwrap();
+        fs::write(
+            workspace.join(".claude").join("settings.json"),
+            r#"{"permissions":{"defaultMode":"acceptEdits"}}"#,
+        )
+        .unwrap();
+
+        let app = permission_mode("read-only", None, &workspace);
+        assert_eq!((app.mode.as_str(), app.source.as_str()), ("plan", "app"));
+        let settings = permission_mode("current", None, &workspace);
+        assert_eq!(
+            (settings.mode.as_str(), settings.source.as_str()),
+            ("acceptEdits", "projectSettings")
+        );
+        fs::remove_file(workspace.join(".claude").join("settings.json")).unwrap();
+        let fallback = permission_mode("current", None, &workspace);
+        assert_eq!(fallback.source, "cliDefault");
+        let _ = fs::remove_dir_all(&workspace);
+    }
+}
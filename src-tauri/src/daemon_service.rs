@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::claude_home::resolve_home_dir;
+use crate::state::AppState;
+use crate::utils::find_in_path;
+
+const DAEMON_BINARY: &str = "codex_monitor_daemon";
+const SYSTEMD_UNIT: &str = "codex-monitor-daemon.service";
+const LAUNCHD_LABEL: &str = "com.codexmonitor.daemon";
+const DEFAULT_LISTEN: &str = "127.0.0.1:4732";
+const TOKEN_ENV: &str = "CODEX_MONITOR_DAEMON_TOKEN";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ServiceManager {
+    Systemd,
+    Launchd,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DaemonStatus {
+    /// "systemd" or "launchd"
+    pub(crate) manager: String,
+    pub(crate) installed: bool,
+    pub(crate) running: bool,
+    pub(crate) service_path: String,
+    pub(crate) binary_path: Option<String>,
+}
+
+fn service_manager() -> Result<ServiceManager, String> {
+    if cfg!(target_os = "macos") {
+        Ok(ServiceManager::Launchd)
+    } else if cfg!(target_os = "linux") {
+        Ok(ServiceManager::Systemd)
+    } else {
+        Err("Daemon service management is only supported on macOS and Linux.".to_string())
+    }
+}
+
+fn service_path(manager: ServiceManager) -> Result<PathBuf, String> {
+    let home = resolve_home_dir().ok_or("Unable to resolve home directory.")?;
+    Ok(match manager {
+        ServiceManager::Systemd => home
+            .join(".config")
+            .join("systemd")
+            .join("user")
+            .join(SYSTEMD_UNIT),
+        ServiceManager::Launchd => home
+            .join("Library")
+            .join("LaunchAgents")
+            .join(format!("{LAUNCHD_LABEL}.plist")),
+    })
+}
+
+/// Look for the daemon next to the app executable first, then on PATH.
+fn resolve_daemon_binary(explicit: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(path) = explicit.map(|value| value.trim()).filter(|value| !value.is_empty()) {
+        let path = PathBuf::from(path);
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!("Daemon binary not found: {}", path.display()))
+        };
+    }
+    if let Some(sibling) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(DAEMON_BINARY)))
+        .filter(|path| path.is_file())
+    {
+        return Ok(sibling);
+    }
+    find_in_path(DAEMON_BINARY)
+        .ok_or_else(|| format!("{DAEMON_BINARY} was not found next to the app or on PATH."))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_systemd_unit(binary: &Path, listen: &str, token: Option<&str>) -> String {
+    let mut unit = format!(
+        "[Unit]\nDescription=Codex Monitor daemon\nAfter=network.target\n\n\
+[Service]\nExecStart=\"{}\" --listen {listen}\nRestart=on-failure\nRestartSec=5\n",
+        binary.display()
+    );
+    if let Some(token) = token {
+        unit.push_str(&format!("Environment={TOKEN_ENV}={token}\n"));
+    }
+    unit.push_str("\n[Install]\nWantedBy=default.target\n");
+    unit
+}
+
+fn render_launchd_plist(binary: &Path, listen: &str, token: Option<&str>) -> String {
+    let environment = token
+        .map(|token| {
+            format!(
+                "  <key>EnvironmentVariables</key>\n  <dict>\n    <key>{TOKEN_ENV}</key>\n    <string>{}</string>\n  </dict>\n",
+                escape_xml(token)
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+<plist version=\"1.0\">\n<dict>\n\
+  <key>Label</key>\n  <string>{LAUNCHD_LABEL}</string>\n\
+  <key>ProgramArguments</key>\n  <array>\n    <string>{}</string>\n    <string>--listen</string>\n    <string>{}</string>\n  </array>\n\
+{environment}\
+  <key>RunAtLoad</key>\n  <true/>\n\
+  <key>KeepAlive</key>\n  <true/>\n\
+</dict>\n</plist>\n",
+        escape_xml(&binary.to_string_lossy()),
+        escape_xml(listen),
+    )
+}
+
+async fn run_service_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = stderr.trim();
+    if detail.is_empty() {
+        Err(format!("{program} {} failed.", args.join(" ")))
+    } else {
+        Err(detail.to_string())
+    }
+}
+
+fn read_installed_binary(manager: ServiceManager, contents: &str) -> Option<String> {
+    match manager {
+        ServiceManager::Systemd => contents
+            .lines()
+            .find_map(|line| line.strip_prefix("ExecStart="))
+            .and_then(|rest| rest.strip_prefix('"'))
+            .and_then(|rest| rest.split('"').next())
+            .map(|path| path.to_string()),
+        ServiceManager::Launchd => contents
+            .split("<key>ProgramArguments</key>")
+            .nth(1)
+            .and_then(|rest| rest.split("<string>").nth(1))
+            .and_then(|rest| rest.split("</string>").next())
+            .map(|path| path.replace("&amp;", "&")),
+    }
+}
+
+async fn current_status() -> Result<DaemonStatus, String> {
+    let manager = service_manager()?;
+    let path = service_path(manager)?;
+    let contents = std::fs::read_to_string(&path).ok();
+    let running = match manager {
+        ServiceManager::Systemd => {
+            run_service_command("systemctl", &["--user", "is-active", SYSTEMD_UNIT])
+                .await
+                .map(|state| state == "active")
+                .unwrap_or(false)
+        }
+        ServiceManager::Launchd => run_service_command("launchctl", &["list", LAUNCHD_LABEL])
+            .await
+            .map(|output| output.contains("\"PID\""))
+            .unwrap_or(false),
+    };
+    Ok(DaemonStatus {
+        manager: match manager {
+            ServiceManager::Systemd => "systemd",
+            ServiceManager::Launchd => "launchd",
+        }
+        .to_string(),
+        installed: contents.is_some(),
+        running,
+        service_path: path.to_string_lossy().to_string(),
+        binary_path: contents
+            .as_deref()
+            .and_then(|contents| read_installed_binary(manager, contents)),
+    })
+}
+
+/// Install the daemon as a user service listening on the configured remote host.
+#[tauri::command]
+pub(crate) async fn daemon_install(
+    binary_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<DaemonStatus, String> {
+    let manager = service_manager()?;
+    let binary = resolve_daemon_binary(binary_path.as_deref())?;
+    let (host, token) = {
+        let settings = state.app_settings.lock().await;
+        (
+            settings.remote_backend_host.clone(),
+            settings.remote_backend_token.clone(),
+        )
+    };
+    let listen = if host.trim().is_empty() {
+        DEFAULT_LISTEN.to_string()
+    } else {
+        host.trim().to_string()
+    };
+    let token = token.as_deref().map(|value| value.trim()).filter(|value| !value.is_empty());
+
+    let path = service_path(manager)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = match manager {
+        ServiceManager::Systemd => render_systemd_unit(&binary, &listen, token),
+        ServiceManager::Launchd => render_launchd_plist(&binary, &listen, token),
+    };
+    std::fs::write(&path, contents).map_err(|err| err.to_string())?;
+    if manager == ServiceManager::Systemd {
+        run_service_command("systemctl", &["--user", "daemon-reload"]).await?;
+        run_service_command("systemctl", &["--user", "enable", SYSTEMD_UNIT]).await?;
+    }
+    current_status().await
+}
+
+#[tauri::command]
+pub(crate) async fn daemon_start() -> Result<DaemonStatus, String> {
+    let manager = service_manager()?;
+    let path = service_path(manager)?;
+    if !path.exists() {
+        return Err("Daemon service is not installed.".to_string());
+    }
+    match manager {
+        ServiceManager::Systemd => {
+            run_service_command("systemctl", &["--user", "start", SYSTEMD_UNIT]).await?;
+        }
+        ServiceManager::Launchd => {
+            let path = path.to_string_lossy().to_string();
+            run_service_command("launchctl", &["load", "-w", &path]).await?;
+        }
+    }
+    current_status().await
+}
+
+#[tauri::command]
+pub(crate) async fn daemon_stop() -> Result<DaemonStatus, String> {
+    let manager = service_manager()?;
+    let path = service_path(manager)?;
+    if !path.exists() {
+        return Err("Daemon service is not installed.".to_string());
+    }
+    match manager {
+        ServiceManager::Systemd => {
+            run_service_command("systemctl", &["--user", "stop", SYSTEMD_UNIT]).await?;
+        }
+        ServiceManager::Launchd => {
+            let path = path.to_string_lossy().to_string();
+            run_service_command("launchctl", &["unload", "-w", &path]).await?;
+        }
+    }
+    current_status().await
+}
+
+#[tauri::command]
+pub(crate) async fn daemon_status() -> Result<DaemonStatus, String> {
+    current_status().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn systemd_unit_includes_listen_address_and_token() {
+        let unit = render_systemd_unit(
+            Path::new("/opt/monitor/codex_monitor_daemon"),
+            "0.0.0.0:4732",
+            Some("secret"),
+        );
+        assert!(unit
+            .contains("ExecStart=\"/opt/monitor/codex_monitor_daemon\" --listen 0.0.0.0:4732"));
+        assert!(unit.contains("Environment=CODEX_MONITOR_DAEMON_TOKEN=secret"));
+        assert!(unit.ends_with("WantedBy=default.target\n"));
+        assert_eq!(
+            read_installed_binary(ServiceManager::Systemd, &unit).as_deref(),
+            Some("/opt/monitor/codex_monitor_daemon")
+        );
+    }
+
+    #[test]
+    fn launchd_plist_escapes_values() {
+        let plist = render_launchd_plist(
+            Path::new("/Applications/A&B.app/codex_monitor_daemon"),
+            "127.0.0.1:4732",
+            None,
+        );
+        assert!(plist
+            .contains("<string>/Applications/A&amp;B.app/codex_monitor_daemon</string>"));
+        assert!(!plist.contains("EnvironmentVariables"));
+        assert_eq!(
+            read_installed_binary(ServiceManager::Launchd, &plist).as_deref(),
+            Some("/Applications/A&B.app/codex_monitor_daemon")
+        );
+    }
+}
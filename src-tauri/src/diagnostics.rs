@@ -0,0 +1,312 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::audit::recent_failures;
+use crate::claude_home::resolve_home_dir;
+use crate::logging::{logs_dir, read_log_entries, LogEntry};
+use crate::remote_backend;
+use crate::retention::exports_dir;
+use crate::state::AppState;
+use crate::types::AppSettings;
+
+const MAX_LOG_ENTRIES: usize = 2000;
+const MAX_ERROR_EVENTS: usize = 200;
+
+/// What went into a diagnostics bundle.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiagnosticsReport {
+    pub(crate) path: String,
+    pub(crate) files: Vec<String>,
+    /// Secrets and personal paths replaced across all files.
+    pub(crate) redactions: usize,
+}
+
+/// Patterns and replacements for the redaction pass: credentials first, then
+/// anything naming the user through their home directory.
+fn diagnostics_redaction_rules(home: Option<&Path>) -> Vec<(Regex, String)> {
+    let mut rules = vec![
+        (r"sk-ant-[A-Za-z0-9_\-]+".to_string(), "[REDACTED:key]"),
+        (
+            r"\b(?:gh[pousr]|github_pat)_[A-Za-z0-9_]{20,}".to_string(),
+            "[REDACTED:token]",
+        ),
+        (
+            r"(?i)\bbearer\s+[A-Za-z0-9._~+/=\-]+".to_string(),
+            "Bearer [REDACTED]",
+        ),
+        (
+            r#"(?i)"([A-Za-z_]*(?:token|secret|password|api_?key)[A-Za-z_]*)"\s*:\s*"[^"]*""#
+                .to_string(),
+            r#""$1": "[REDACTED]""#,
+        ),
+        (
+            r"(?i)\b([A-Z_]*(?:TOKEN|SECRET|PASSWORD|API_KEY))=\S+".to_string(),
+            "$1=[REDACTED]",
+        ),
+    ];
+    if let Some(home) = home.map(|home| home.to_string_lossy().to_string()) {
+        if !home.is_empty() && home != "/" {
+            rules.push((regex::escape(&home), "~"));
+        }
+    }
+    rules.push((
+        r"(?:/Users|/home|[A-Z]:\\Users)[/\\][^/\\\s]+".to_string(),
+        "~",
+    ));
+    rules
+        .into_iter()
+        .filter_map(|(pattern, replacement)| {
+            Some((Regex::new(&pattern).ok()?, replacement.to_string()))
+        })
+        .collect()
+}
+
+/// Apply the redaction pass to one file. Returns the text and the number of
+/// replacements made.
+fn redact(text: &str, rules: &[(Regex, String)]) -> (String, usize) {
+    let mut redacted = text.to_string();
+    let mut count = 0;
+    for (regex, replacement) in rules {
+        count += regex.find_iter(&redacted).count();
+        redacted = regex
+            .replace_all(&redacted, replacement.as_str())
+            .into_owned();
+    }
+    (redacted, count)
+}
+
+/// Settings without credentials or machine-specific connection details.
+fn anonymized_settings(settings: &AppSettings) -> Value {
+    let mut settings = settings.clone();
+    settings.claude_bin = settings.claude_bin.map(|_| "[set]".to_string());
+    settings.remote_backend_token = settings.remote_backend_token.map(|_| "[set]".to_string());
+    if let Some(tunnel) = settings.remote_ssh_tunnel.as_mut() {
+        tunnel.host = "[host]".to_string();
+    }
+    serde_json::to_value(settings).unwrap_or(Value::Null)
+}
+
+fn log_lines(entries: &[LogEntry]) -> String {
+    entries
+        .iter()
+        .filter_map(|entry| serde_json::to_string(entry).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Redact `files` and write them into a zip archive at `path`.
+fn write_bundle(
+    path: &Path,
+    files: Vec<(String, String)>,
+    rules: &[(Regex, String)],
+) -> Result<DiagnosticsReport, String> {
+    if path.is_dir() {
+        return Err(format!("{} is a directory, not a file.", path.display()));
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut archive = ZipWriter::new(file);
+    // Bundles are small text files; storing them keeps the zip dependency lean.
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut redactions = 0;
+    let mut names = Vec::new();
+    for (name, contents) in files {
+        let (contents, count) = redact(&contents, rules);
+        redactions += count;
+        let write_error = |err: &dyn std::fmt::Display| {
+            format!("Failed to write diagnostics file {name}: {err}")
+        };
+        archive
+            .start_file(name.as_str(), options)
+            .map_err(|err| write_error(&err))?;
+        archive
+            .write_all(contents.as_bytes())
+            .map_err(|err| write_error(&err))?;
+        names.push(name);
+    }
+    archive.finish().map_err(|err| err.to_string())?;
+    Ok(DiagnosticsReport {
+        path: path.to_string_lossy().to_string(),
+        files: names,
+        redactions,
+    })
+}
+
+fn app_data_dir(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|path| path.to_path_buf())
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+fn pretty(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Collect logs, `claude_doctor` output, anonymized settings and recent errors
+/// into a zip archive to attach to a bug report. Every file goes through a redaction pass
+/// for tokens and home-directory paths before it is written.
+#[tauri::command]
+pub(crate) async fn generate_diagnostics(
+    path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<DiagnosticsReport, String> {
+    let data_dir = app_data_dir(&state)?;
+    let mut files: Vec<(String, String)> = Vec::new();
+
+    let log_dir = logs_dir(&data_dir);
+    let (app_logs, warnings) = tokio::task::spawn_blocking(move || {
+        (
+            read_log_entries(&log_dir, None, None, None, None, Some(MAX_LOG_ENTRIES)),
+            read_log_entries(
+                &log_dir,
+                Some("warn"),
+                None,
+                None,
+                None,
+                Some(MAX_ERROR_EVENTS),
+            ),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+    files.push((
+        "app.log".to_string(),
+        log_lines(&app_logs.unwrap_or_default()),
+    ));
+
+    if remote_backend::is_remote_mode(&*state).await {
+        let daemon_logs = remote_backend::call_remote(
+            &*state,
+            app.clone(),
+            "get_daemon_logs",
+            json!({ "limit": MAX_LOG_ENTRIES }),
+        )
+        .await
+        .and_then(|value| {
+            serde_json::from_value::<Vec<LogEntry>>(value).map_err(|err| err.to_string())
+        });
+        let contents = match daemon_logs {
+            Ok(entries) => log_lines(&entries),
+            Err(error) => format!("Failed to fetch daemon logs: {error}"),
+        };
+        files.push(("daemon.log".to_string(), contents));
+    }
+
+    let doctor = crate::claude::claude_doctor(None, state.clone())
+        .await
+        .unwrap_or_else(|error| json!({ "ok": false, "error": error }));
+    files.push(("doctor.json".to_string(), pretty(&doctor)));
+
+    let settings = anonymized_settings(&*state.app_settings.lock().await);
+    files.push(("settings.json".to_string(), pretty(&settings)));
+
+    let errors = json!({
+        "failedCalls": recent_failures(MAX_ERROR_EVENTS),
+        "warnings": warnings.unwrap_or_default(),
+    });
+    files.push(("errors.json".to_string(), pretty(&errors)));
+
+    let workspace_count = state.workspaces.lock().await.len();
+    let system = json!({
+        "appVersion": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "remoteMode": remote_backend::is_remote_mode(&*state).await,
+        "workspaces": workspace_count,
+        "generatedAt": Local::now().to_rfc3339(),
+    });
+    files.push(("system.json".to_string(), pretty(&system)));
+
+    let target = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => exports_dir(&data_dir).join(format!(
+            "diagnostics-{}.zip",
+            Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+    let rules = diagnostics_redaction_rules(resolve_home_dir().as_deref());
+    let write_target = target.clone();
+    let result = tokio::task::spawn_blocking(move || write_bundle(&write_target, files, &rules))
+        .await
+        .map_err(|err| err.to_string())?;
+    crate::audit::record(
+        "generate_diagnostics",
+        None,
+        json!({ "path": target.to_string_lossy() }),
+        &result,
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_hides_tokens_and_home_paths() {
+        let rules = diagnostics_redaction_rules(Some(Path::new("/home/ada")));
+        let text = r#"{"remoteBackendToken": "abc123", "cwd": "/home/ada/src/app"}
+auth: Bearer eyJhbGciOi.payload
+key sk-ant-api03-XYZ_abc and GITHUB_TOKEN=ghp_aaaaaaaaaaaaaaaaaaaaaaaa
+other /Users/grace/project"#;
+        let (redacted, count) = redact(text, &rules);
+        assert_eq!(
+            redacted,
+            r#"{"remoteBackendToken": "[REDACTED]", "cwd": "~/src/app"}
+auth: Bearer [REDACTED]
+key [REDACTED:key] and GITHUB_TOKEN=[REDACTED]
+other ~/project"#
+        );
+        // The GitHub token is caught twice: by value, then by variable name.
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn write_bundle_writes_redacted_files_into_a_zip() {
+        use std::io::Read;
+
+        let dir = std::env::temp_dir().join(format!("diagnostics-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("bundle.zip");
+        let rules = diagnostics_redaction_rules(Some(Path::new("/home/ada")));
+        let report = write_bundle(
+            &path,
+            vec![
+                ("app.log".to_string(), "cwd /home/ada/src".to_string()),
+                ("settings.json".to_string(), "{}".to_string()),
+            ],
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(report.files, vec!["app.log", "settings.json"]);
+        assert_eq!(report.redactions, 1);
+
+        let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut contents = String::new();
+        archive
+            .by_name("app.log")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "cwd ~/src");
+        assert!(!contents.contains("/home/ada"));
+
+        assert!(write_bundle(&dir, Vec::new(), &rules).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -8,6 +8,7 @@ use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::oneshot;
 
+use crate::dictation_commands::{parse_voice_commands, DictationCommand, ParsedDictation};
 use crate::state::AppState;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
@@ -196,6 +197,11 @@ pub(crate) enum DictationEvent {
     State { state: DictationSessionState },
     Level { value: f32 },
     Transcript { text: String },
+    /// A spoken command, with any text dictated before it.
+    Command {
+        command: DictationCommand,
+        text: String,
+    },
     Error { message: String },
     Canceled { message: String },
 }
@@ -1000,11 +1006,32 @@ pub(crate) async fn dictation_stop(
 
         match outcome {
             Ok(text) => {
-                if !text.trim().is_empty() {
-                    emit_event(
+                let voice_commands = state_handle
+                    .app_settings
+                    .lock()
+                    .await
+                    .dictation_voice_commands;
+                let parsed = if voice_commands {
+                    parse_voice_commands(&text)
+                } else {
+                    ParsedDictation {
+                        text,
+                        command: None,
+                    }
+                };
+                match parsed.command {
+                    Some(command) => emit_event(
                         &app_handle,
-                        DictationEvent::Transcript { text },
-                    );
+                        DictationEvent::Command {
+                            command,
+                            text: parsed.text,
+                        },
+                    ),
+                    None if !parsed.text.trim().is_empty() => emit_event(
+                        &app_handle,
+                        DictationEvent::Transcript { text: parsed.text },
+                    ),
+                    None => {}
                 }
             }
             Err(message) => {
@@ -1232,7 +1259,7 @@ fn start_capture_thread(
         }
     });
 
-    eprintln!(
+    tracing::debug!(
         "dictation: capture started (rate={}Hz, channels={}, format={:?})",
         sample_rate, channels, sample_format
     );
@@ -1340,7 +1367,7 @@ fn transcribe_audio(
             *value = (*value * gain).clamp(-1.0, 1.0);
         }
     }
-    eprintln!(
+    tracing::debug!(
         "dictation: captured {} samples ({:.2}s), max={:.4}, rms={:.4}, gain={:.2}",
         samples.len(),
         duration,
@@ -1407,7 +1434,7 @@ fn transcribe_audio(
     let segments = state
         .full_n_segments()
         .map_err(|error| format!("Failed to read segments: {error}"))?;
-    eprintln!("dictation: whisper segments={}", segments);
+    tracing::debug!("dictation: whisper segments={}", segments);
     let mut transcript = String::new();
     for index in 0..segments {
         let segment = state
@@ -1417,7 +1444,7 @@ fn transcribe_audio(
     }
     let cleaned = transcript.trim().to_string();
     if cleaned.is_empty() {
-        eprintln!(
+        tracing::debug!(
             "dictation: no speech detected (rms={:.4}, max={:.4}, duration={:.2}s, segments={})",
             rms, max, duration, segments
         );
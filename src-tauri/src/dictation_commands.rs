@@ -0,0 +1,159 @@
+use serde::Serialize;
+
+const SEND_PHRASES: &[&[&str]] = &[&["send", "it"], &["send"], &["submit"]];
+const NEW_LINE_PHRASES: &[&[&str]] = &[&["new", "line"], &["newline"]];
+
+/// A composer action spoken at the end of a dictation.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DictationCommand {
+    Send,
+    NewLine,
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParsedDictation {
+    pub(crate) text: String,
+    pub(crate) command: Option<DictationCommand>,
+}
+
+/// Lowercased word with Whisper's punctuation stripped ("Send." -> "send").
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Length of the "new line" phrase starting at `index`, if there is one.
+fn new_line_at(words: &[String], index: usize) -> Option<usize> {
+    match words.get(index).map(String::as_str) {
+        Some("newline") => Some(1),
+        Some("new") if words.get(index + 1).map(String::as_str) == Some("line") => Some(2),
+        _ => None,
+    }
+}
+
+/// Split a transcript into dictated text and a trailing command.
+///
+/// "cancel" only counts as the whole utterance and "send" only as the last
+/// words, so they can still be dictated inside a sentence. "new line" inside
+/// the text becomes a line break.
+pub(crate) fn parse_voice_commands(transcript: &str) -> ParsedDictation {
+    let raw: Vec<&str> = transcript.split_whitespace().collect();
+    let words: Vec<String> = raw.iter().map(|word| normalize_word(word)).collect();
+    let spoken: Vec<&str> = words
+        .iter()
+        .map(String::as_str)
+        .filter(|word| !word.is_empty())
+        .collect();
+    if matches!(spoken.as_slice(), ["cancel"] | ["cancel", "that"]) {
+        return ParsedDictation {
+            text: String::new(),
+            command: Some(DictationCommand::Cancel),
+        };
+    }
+
+    let mut end = raw.len();
+    let mut command = None;
+    let ends_with = |end: usize, phrase: &[&str]| -> bool {
+        end >= phrase.len()
+            && words[end - phrase.len()..end]
+                .iter()
+                .zip(phrase)
+                .all(|(word, expected)| word.as_str() == *expected)
+    };
+    for phrase in SEND_PHRASES {
+        if ends_with(end, *phrase) {
+            end -= phrase.len();
+            command = Some(DictationCommand::Send);
+            break;
+        }
+    }
+    if command.is_none() {
+        for phrase in NEW_LINE_PHRASES {
+            if ends_with(end, *phrase) {
+                end -= phrase.len();
+                command = Some(DictationCommand::NewLine);
+                break;
+            }
+        }
+    }
+
+    let mut text = String::new();
+    let mut index = 0;
+    while index < end {
+        if let Some(len) = new_line_at(&words[..end], index) {
+            text.truncate(text.trim_end_matches(' ').len());
+            text.push('\n');
+            index += len;
+            continue;
+        }
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push(' ');
+        }
+        text.push_str(raw[index]);
+        index += 1;
+    }
+    ParsedDictation {
+        text: text.trim().to_string(),
+        command,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(text: &str, command: Option<DictationCommand>) -> ParsedDictation {
+        ParsedDictation {
+            text: text.to_string(),
+            command,
+        }
+    }
+
+    #[test]
+    fn plain_dictation_has_no_command() {
+        assert_eq!(
+            parse_voice_commands(" Please send the report to Ada. "),
+            parsed("Please send the report to Ada.", None)
+        );
+    }
+
+    #[test]
+    fn trailing_send_submits_the_text() {
+        assert_eq!(
+            parse_voice_commands("Fix the failing test. Send."),
+            parsed("Fix the failing test.", Some(DictationCommand::Send))
+        );
+        assert_eq!(
+            parse_voice_commands("Send it!"),
+            parsed("", Some(DictationCommand::Send))
+        );
+    }
+
+    #[test]
+    fn new_line_breaks_text_and_trails_as_command() {
+        assert_eq!(
+            parse_voice_commands("First step. New line. Second step."),
+            parsed("First step.\nSecond step.", None)
+        );
+        assert_eq!(
+            parse_voice_commands("First step, newline"),
+            parsed("First step,", Some(DictationCommand::NewLine))
+        );
+    }
+
+    #[test]
+    fn cancel_only_counts_as_the_whole_utterance() {
+        assert_eq!(
+            parse_voice_commands("Cancel that."),
+            parsed("", Some(DictationCommand::Cancel))
+        );
+        assert_eq!(
+            parse_voice_commands("Cancel the build"),
+            parsed("Cancel the build", None)
+        );
+    }
+}
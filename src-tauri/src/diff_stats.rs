@@ -0,0 +1,157 @@
+use serde_json::Value;
+
+/// Past this many line pairs the middle of an edit is counted as fully
+/// replaced instead of running the quadratic LCS.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Lines added and removed going from `old` to `new`.
+pub(crate) fn line_diff_stats(old: &str, new: &str) -> (u64, u64) {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_rest = &old_lines[prefix..];
+    let new_rest = &new_lines[prefix..];
+    let suffix = old_rest
+        .iter()
+        .rev()
+        .zip(new_rest.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_rest[..old_rest.len() - suffix];
+    let new_mid = &new_rest[..new_rest.len() - suffix];
+    let common = if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        0
+    } else {
+        lcs_len(old_mid, new_mid)
+    };
+    (
+        (new_mid.len() - common) as u64,
+        (old_mid.len() - common) as u64,
+    )
+}
+
+fn lcs_len(a: &[&str], b: &[&str]) -> usize {
+    let mut row = vec![0usize; b.len() + 1];
+    for x in a {
+        let mut diag = 0;
+        for (j, y) in b.iter().enumerate() {
+            let up = row[j + 1];
+            row[j + 1] = if x == y { diag + 1 } else { up.max(row[j]) };
+            diag = up;
+        }
+    }
+    row[b.len()]
+}
+
+/// Counts from the CLI's `structuredPatch` hunks (`+`/`-`/` ` prefixed lines).
+fn patch_stats(patch: &Value) -> Option<(u64, u64)> {
+    let mut added = 0;
+    let mut removed = 0;
+    for hunk in patch.as_array()? {
+        let Some(lines) = hunk.get("lines").and_then(|v| v.as_array()) else {
+            continue;
+        };
+        for line in lines.iter().filter_map(|line| line.as_str()) {
+            if line.starts_with('+') {
+                added += 1;
+            } else if line.starts_with('-') {
+                removed += 1;
+            }
+        }
+    }
+    Some((added, removed))
+}
+
+/// Lines a Write/Edit/MultiEdit/NotebookEdit call added and removed. The
+/// patch the CLI records after applying the edit wins over the tool input.
+pub(crate) fn file_change_stats(
+    tool_name: &str,
+    tool_input: &Value,
+    tool_use_result: Option<&Value>,
+) -> Option<(u64, u64)> {
+    let from_patch = tool_use_result
+        .and_then(|result| result.get("structuredPatch"))
+        .and_then(patch_stats)
+        .filter(|stats| *stats != (0, 0));
+    if from_patch.is_some() {
+        return from_patch;
+    }
+    let input_str = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    match tool_name.trim().to_lowercase().as_str() {
+        "write" => Some((input_str(tool_input, "content").lines().count() as u64, 0)),
+        "edit" => Some(line_diff_stats(
+            &input_str(tool_input, "old_string"),
+            &input_str(tool_input, "new_string"),
+        )),
+        "multiedit" => {
+            let edits = tool_input.get("edits")?.as_array()?;
+            Some(edits.iter().fold((0, 0), |(added, removed), edit| {
+                let (a, r) = line_diff_stats(
+                    &input_str(edit, "old_string"),
+                    &input_str(edit, "new_string"),
+                );
+                (added + a, removed + r)
+            }))
+        }
+        "notebookedit" => match tool_input.get("edit_mode").and_then(|v| v.as_str()) {
+            Some("insert") => Some((
+                input_str(tool_input, "new_source").lines().count() as u64,
+                0,
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn line_diff_counts_changed_lines_only() {
+        assert_eq!(line_diff_stats("a\nb\nc", "a\nb\nc"), (0, 0));
+        assert_eq!(line_diff_stats("a\nb\nc", "a\nB\nc"), (1, 1));
+        assert_eq!(line_diff_stats("a\nc", "a\nb1\nb2\nc"), (2, 0));
+        assert_eq!(line_diff_stats("x\na\ny\nb", "a\nb"), (0, 2));
+        assert_eq!(line_diff_stats("", "one\ntwo"), (2, 0));
+    }
+
+    #[test]
+    fn file_change_stats_prefers_structured_patch() {
+        let input = json!({ "old_string": "a", "new_string": "b" });
+        let result = json!({ "structuredPatch": [
+            { "lines": [" ctx", "-a", "+b", "+c"] },
+        ]});
+        assert_eq!(
+            file_change_stats("Edit", &input, Some(&result)),
+            Some((2, 1))
+        );
+        assert_eq!(file_change_stats("Edit", &input, None), Some((1, 1)));
+
+        let write = json!({ "content": "one\ntwo\nthree\n" });
+        let created = json!({ "type": "create", "structuredPatch": [] });
+        assert_eq!(
+            file_change_stats("Write", &write, Some(&created)),
+            Some((3, 0))
+        );
+
+        let multi = json!({ "edits": [
+            { "old_string": "a", "new_string": "a\nb" },
+            { "old_string": "x\ny", "new_string": "" },
+        ]});
+        assert_eq!(file_change_stats("MultiEdit", &multi, None), Some((1, 2)));
+        assert_eq!(file_change_stats("Read", &json!({}), None), None);
+    }
+}
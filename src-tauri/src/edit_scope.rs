@@ -0,0 +1,122 @@
+//! A message can limit the files its turn may change to a list of
+//! gitignore-style globs relative to the workspace root; the next message
+//! replaces or lifts the limit. File changes are checked when the CLI asks
+//! permission for them, so sessions whose mode approves edits by itself are
+//! restarted to route edit prompts to the app while a scope is set, and the
+//! app approves in-scope edits for them.
+
+use std::path::{Component, Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde_json::Value;
+
+/// Tools that change files, with the input field naming the target.
+const FILE_CHANGE_TOOLS: &[(&str, &str)] = &[
+    ("Edit", "file_path"),
+    ("MultiEdit", "file_path"),
+    ("Write", "file_path"),
+    ("NotebookEdit", "notebook_path"),
+];
+
+pub(crate) fn is_file_change_tool(tool_name: &str) -> bool {
+    FILE_CHANGE_TOOLS.iter().any(|(name, _)| *name == tool_name)
+}
+
+fn scope_matcher(workspace_path: &str, globs: &[String]) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new(workspace_path);
+    for glob in globs {
+        builder
+            .add_line(None, glob)
+            .map_err(|err| format!("Invalid edit scope glob `{glob}`: {err}"))?;
+    }
+    builder.build().map_err(|err| err.to_string())
+}
+
+/// Trim and check a requested scope.
+pub(crate) fn normalize_edit_scope(
+    workspace_path: &str,
+    globs: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let globs: Vec<String> = globs
+        .into_iter()
+        .map(|glob| glob.trim().to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect();
+    if globs.is_empty() {
+        return Err("Edit scope needs at least one glob.".to_string());
+    }
+    scope_matcher(workspace_path, &globs)?;
+    Ok(globs)
+}
+
+/// Resolve `.` and `..` without touching the filesystem, since the target of
+/// a write may not exist yet.
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// The file a file-change tool call targets, resolved against the workspace.
+pub(crate) fn edit_target(workspace_path: &str, tool_name: &str, input: &Value) -> Option<PathBuf> {
+    let (_, field) = FILE_CHANGE_TOOLS
+        .iter()
+        .find(|(name, _)| *name == tool_name)?;
+    let path = input.get(*field).and_then(|v| v.as_str())?;
+    Some(lexical_normalize(
+        &Path::new(workspace_path).join(path.trim()),
+    ))
+}
+
+/// Whether `target` is inside the workspace and matches one of `globs`.
+pub(crate) fn path_in_edit_scope(workspace_path: &str, globs: &[String], target: &Path) -> bool {
+    let Ok(relative) = target.strip_prefix(workspace_path) else {
+        return false;
+    };
+    match scope_matcher(workspace_path, globs) {
+        Ok(matcher) => matcher
+            .matched_path_or_any_parents(relative, false)
+            .is_ignore(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edit_scope_matches_globs_inside_the_workspace() {
+        let scope = normalize_edit_scope(
+            "/repo",
+            vec![
+                "src/auth/**".to_string(),
+                " *.md ".to_string(),
+                String::new(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(scope, vec!["src/auth/**", "*.md"]);
+        assert!(normalize_edit_scope("/repo", vec![" ".to_string()]).is_err());
+
+        let target = |tool: &str, input: Value| edit_target("/repo", tool, &input).unwrap();
+        let inside = target("Edit", json!({ "file_path": "/repo/src/auth/login.rs" }));
+        assert!(path_in_edit_scope("/repo", &scope, &inside));
+        let doc = target("Write", json!({ "file_path": "docs/guide.md" }));
+        assert!(path_in_edit_scope("/repo", &scope, &doc));
+        let escaped = target("Edit", json!({ "file_path": "/repo/src/auth/../db.rs" }));
+        assert!(!path_in_edit_scope("/repo", &scope, &escaped));
+        let outside = target("NotebookEdit", json!({ "notebook_path": "/other/a.md" }));
+        assert!(!path_in_edit_scope("/repo", &scope, &outside));
+        assert!(edit_target("/repo", "Bash", &json!({ "command": "ls" })).is_none());
+    }
+}
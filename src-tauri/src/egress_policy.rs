@@ -0,0 +1,170 @@
+//! Per-workspace allowlist of domains the agent's web tools may reach.
+//! Calls are checked when the CLI asks permission for them: blocked ones are
+//! denied without prompting, and web searches are narrowed to allowed
+//! domains. Sessions that never ask permission run without web tools while
+//! a policy is set.
+
+use serde_json::{json, Value};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+
+/// Tools that reach the network on the agent's behalf.
+pub(crate) const WEB_TOOLS: &[&str] = &["WebFetch", "WebSearch"];
+
+pub(crate) enum EgressCheck {
+    /// The call may proceed with this input.
+    Allowed(Value),
+    /// The call targets `domain`, which is not allowed.
+    Blocked(String),
+}
+
+fn normalize_domain(domain: &str) -> String {
+    domain
+        .trim()
+        .trim_start_matches("*.")
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// Whether `host` is an allowed domain or one of its subdomains.
+pub(crate) fn domain_allowed(host: &str, allowlist: &[String]) -> bool {
+    let host = normalize_domain(host);
+    allowlist
+        .iter()
+        .map(|domain| normalize_domain(domain))
+        .any(|domain| {
+            !domain.is_empty()
+                && (host == domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.')))
+        })
+}
+
+fn url_host(url: &str) -> Option<String> {
+    tauri::Url::parse(url.trim())
+        .ok()?
+        .host_str()
+        .map(str::to_string)
+}
+
+/// Check a tool call against the allowlist; `None` for tools that are not
+/// web tools.
+pub(crate) fn check_tool_egress(
+    tool_name: &str,
+    input: &Value,
+    allowlist: &[String],
+) -> Option<EgressCheck> {
+    match tool_name {
+        "WebFetch" => {
+            let url = input.get("url").and_then(|v| v.as_str()).unwrap_or("");
+            Some(match url_host(url) {
+                Some(host) if domain_allowed(&host, allowlist) => {
+                    EgressCheck::Allowed(input.clone())
+                }
+                Some(host) => EgressCheck::Blocked(host),
+                None => EgressCheck::Blocked(url.to_string()),
+            })
+        }
+        "WebSearch" => {
+            let requested: Option<Vec<String>> = input
+                .get("allowed_domains")
+                .and_then(|v| v.as_array())
+                .filter(|domains| !domains.is_empty())
+                .map(|domains| {
+                    domains
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(str::to_string)
+                        .collect()
+                });
+            let domains: Vec<String> = match &requested {
+                Some(requested) => requested
+                    .iter()
+                    .filter(|domain| domain_allowed(domain, allowlist))
+                    .cloned()
+                    .collect(),
+                None => allowlist
+                    .iter()
+                    .map(|domain| normalize_domain(domain))
+                    .filter(|domain| !domain.is_empty())
+                    .collect(),
+            };
+            if domains.is_empty() {
+                let target =
+                    requested.map_or("any domain".to_string(), |domains| domains.join(", "));
+                return Some(EgressCheck::Blocked(target));
+            }
+            let mut input = input.clone();
+            if let Some(object) = input.as_object_mut() {
+                object.insert("allowed_domains".to_string(), json!(domains));
+            }
+            Some(EgressCheck::Allowed(input))
+        }
+        _ => None,
+    }
+}
+
+/// Emit `policy/violation` for a web tool call outside the allowlist.
+pub(crate) fn emit_policy_violation(
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    thread_id: &str,
+    tool_use_id: &str,
+    tool_name: &str,
+    domain: &str,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": "policy/violation",
+            "params": {
+                "threadId": thread_id,
+                "toolUseId": tool_use_id,
+                "toolName": tool_name,
+                "policy": "egress",
+                "domain": domain,
+            },
+        }),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn egress_check_blocks_unlisted_domains_and_narrows_searches() {
+        let allowlist = vec!["docs.rs".to_string(), "*.github.com".to_string()];
+        assert!(domain_allowed("docs.rs", &allowlist));
+        assert!(domain_allowed("api.GitHub.com", &allowlist));
+        assert!(!domain_allowed("notdocs.rs", &allowlist));
+
+        let fetch = |url: &str| check_tool_egress("WebFetch", &json!({ "url": url }), &allowlist);
+        assert!(matches!(
+            fetch("https://docs.rs/serde"),
+            Some(EgressCheck::Allowed(_))
+        ));
+        assert!(matches!(
+            fetch("https://evil.example/x"),
+            Some(EgressCheck::Blocked(domain)) if domain == "evil.example"
+        ));
+
+        let Some(EgressCheck::Allowed(input)) =
+            check_tool_egress("WebSearch", &json!({ "query": "serde" }), &allowlist)
+        else {
+            panic!("search should be allowed");
+        };
+        assert_eq!(input["allowed_domains"], json!(["docs.rs", "github.com"]));
+        assert!(matches!(
+            check_tool_egress(
+                "WebSearch",
+                &json!({ "query": "x", "allowed_domains": ["example.com"] }),
+                &allowlist
+            ),
+            Some(EgressCheck::Blocked(_))
+        ));
+        assert!(check_tool_egress("Bash", &json!({}), &allowlist).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+//! Every app-server event is stamped with a `seq` that increases by one per
+//! workspace, starting at 1 when the app starts. A client tracking the last
+//! seq it handled per workspace can drop anything at or below it as a
+//! duplicate, and treat a jump of more than one as a gap: it should call
+//! `replay_events` with the last seq it handled, and reload the thread if the
+//! replay is not `complete`. A seq lower than expected after a restart of the
+//! app means the stream started over.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+
+use crate::backend::events::AppServerEvent;
+
+/// Events kept per workspace. Streaming deltas dominate, so this covers the
+/// tail of a long turn rather than whole threads.
+const MAX_EVENTS_PER_WORKSPACE: usize = 2000;
+
+static REPLAY_BUFFER: OnceLock<Mutex<ReplayBuffer>> = OnceLock::new();
+
+fn replay_buffer() -> &'static Mutex<ReplayBuffer> {
+    REPLAY_BUFFER.get_or_init(|| Mutex::new(ReplayBuffer::new(MAX_EVENTS_PER_WORKSPACE)))
+}
+
+/// An event as emitted to the webview, with its position in the workspace's stream.
+#[derive(Clone, Serialize)]
+pub(crate) struct SequencedEvent {
+    pub(crate) seq: u64,
+    #[serde(flatten)]
+    pub(crate) event: AppServerEvent,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EventReplay {
+    pub(crate) events: Vec<SequencedEvent>,
+    /// Seq of the newest event emitted for the workspace, 0 if none yet.
+    pub(crate) latest_seq: u64,
+    /// False when events after `since_seq` were already evicted, in which case
+    /// the caller should reload thread state instead of relying on the replay.
+    pub(crate) complete: bool,
+}
+
+#[derive(Default)]
+struct WorkspaceEvents {
+    last_seq: u64,
+    events: VecDeque<SequencedEvent>,
+}
+
+/// Recent app-server events per workspace, numbered from 1.
+struct ReplayBuffer {
+    capacity: usize,
+    workspaces: HashMap<String, WorkspaceEvents>,
+}
+
+impl ReplayBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            workspaces: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, event: AppServerEvent) -> SequencedEvent {
+        let entry = self
+            .workspaces
+            .entry(event.workspace_id.clone())
+            .or_default();
+        entry.last_seq += 1;
+        if entry.events.len() == self.capacity {
+            entry.events.pop_front();
+        }
+        let event = SequencedEvent {
+            seq: entry.last_seq,
+            event,
+        };
+        entry.events.push_back(event.clone());
+        event
+    }
+
+    fn since(&self, workspace_id: &str, since_seq: u64) -> EventReplay {
+        let Some(entry) = self.workspaces.get(workspace_id) else {
+            return EventReplay {
+                events: Vec::new(),
+                latest_seq: 0,
+                complete: since_seq == 0,
+            };
+        };
+        let oldest = entry
+            .events
+            .front()
+            .map_or(entry.last_seq + 1, |event| event.seq);
+        EventReplay {
+            events: entry
+                .events
+                .iter()
+                .filter(|event| event.seq > since_seq)
+                .cloned()
+                .collect(),
+            latest_seq: entry.last_seq,
+            complete: since_seq + 1 >= oldest && since_seq <= entry.last_seq,
+        }
+    }
+}
+
+/// Number an event for emission and keep it for replay.
+pub(crate) fn sequence_event(event: AppServerEvent) -> SequencedEvent {
+    let mut buffer = replay_buffer()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    buffer.push(event)
+}
+
+/// Events emitted for `workspace_id` after `since_seq`, oldest first, so a
+/// reloaded webview can catch up on in-flight turns. Remote events pass
+/// through the local event sink, so this never needs the daemon.
+#[tauri::command]
+pub(crate) async fn replay_events(
+    workspace_id: String,
+    since_seq: Option<u64>,
+) -> Result<EventReplay, String> {
+    let buffer = replay_buffer().lock().map_err(|err| err.to_string())?;
+    Ok(buffer.since(&workspace_id, since_seq.unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(workspace_id: &str, n: u64) -> AppServerEvent {
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({ "method": "item/agentMessage/delta", "params": { "n": n } }),
+        }
+    }
+
+    #[test]
+    fn replay_returns_events_after_seq_and_flags_evictions() {
+        let mut buffer = ReplayBuffer::new(3);
+        for n in 1..=5 {
+            assert_eq!(buffer.push(event("ws-1", n)).seq, n);
+        }
+        assert_eq!(buffer.push(event("ws-2", 1)).seq, 1);
+
+        let replay = buffer.since("ws-1", 3);
+        assert_eq!(
+            replay
+                .events
+                .iter()
+                .map(|event| event.seq)
+                .collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(replay.latest_seq, 5);
+        assert!(replay.complete);
+
+        let replay = buffer.since("ws-1", 1);
+        assert_eq!(replay.events.len(), 3);
+        assert!(!replay.complete);
+
+        assert!(buffer.since("ws-1", 5).events.is_empty());
+        assert!(buffer.since("ws-1", 5).complete);
+        assert_eq!(buffer.since("ws-2", 0).latest_seq, 1);
+        assert!(buffer.since("ws-3", 0).complete);
+    }
+}
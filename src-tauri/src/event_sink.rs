@@ -1,6 +1,12 @@
 use tauri::{AppHandle, Emitter};
 
 use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use crate::event_replay;
+use crate::metrics;
+use crate::tray::observe_app_server_event;
+use crate::turn_checkpoints;
+use crate::turn_recordings;
+use crate::window::{thread_window_event_name, thread_window_targets};
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -11,10 +17,22 @@ impl TauriEventSink {
     pub(crate) fn new(app: AppHandle) -> Self {
         Self { app }
     }
+
+    pub(crate) fn app_handle(&self) -> &AppHandle {
+        &self.app
+    }
 }
 
 impl EventSink for TauriEventSink {
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        observe_app_server_event(&self.app, &event);
+        metrics::observe_app_server_event(&event);
+        turn_recordings::observe_app_server_event(&event);
+        turn_checkpoints::observe_app_server_event(&event);
+        let event = event_replay::sequence_event(event);
+        for label in thread_window_targets(&event.event) {
+            let _ = self.app.emit(&thread_window_event_name(&label), event.clone());
+        }
         let _ = self.app.emit("app-server-event", event);
     }
 
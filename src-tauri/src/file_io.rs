@@ -1,14 +1,106 @@
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Cursor, Read};
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Bytes inspected to pick an encoding or flag a file as binary.
+const SNIFF_BYTES: usize = 8 * 1024;
+/// Default cap on decoded text returned over IPC.
+pub(crate) const MAX_TEXT_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+/// UTF-16 files are decoded in memory, so they get a hard ceiling.
+const MAX_UTF16_DECODE_BYTES: u64 = 32 * 1024 * 1024;
+const SKIP_CHUNK_BYTES: u64 = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FileContentKind {
+    #[default]
+    Text,
+    Binary,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub(crate) struct TextFileResponse {
     pub exists: bool,
     pub content: String,
+    /// More lines or bytes remain after `content`
+    pub truncated: bool,
+    #[serde(default)]
+    pub kind: FileContentKind,
+    #[serde(default)]
+    pub size: u64,
+    /// Sniffed MIME type, only set for binary files
+    #[serde(default)]
+    pub mime: Option<String>,
+    /// Detected text encoding, e.g. "utf-8" or "utf-16le"
+    #[serde(default)]
+    pub encoding: Option<String>,
+    /// Zero-based index of the first line in `content`
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub line_count: usize,
+    /// On-disk version to hand back to `file_write` for conflict detection
+    #[serde(default)]
+    pub version: Option<FileVersion>,
+}
+
+/// What a reader saw on disk. Writes compare `exists` and `hash`; the mtime is
+/// informational since touching a file without changing it isn't a conflict.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileVersion {
+    pub exists: bool,
+    pub modified_at: Option<i64>,
+    pub hash: String,
+    /// The read stopped before the end of the file
+    #[serde(default)]
     pub truncated: bool,
+    /// Zero-based line the read started at
+    #[serde(default)]
+    pub offset: usize,
+    /// Encoding the file is stored in; writes are encoded back to it
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub(crate) enum FileWriteResult {
+    Written { version: FileVersion },
+    /// The file changed since `expected` was read; nothing was written.
+    Conflict { current: TextFileResponse },
+}
+
+/// Zero-based line window; `None` means from the start / to the end.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct LineRange {
+    pub(crate) offset: Option<usize>,
+    pub(crate) limit: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl TextEncoding {
+    fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => "utf-8",
+            TextEncoding::Utf16Le => "utf-16le",
+            TextEncoding::Utf16Be => "utf-16be",
+            TextEncoding::Latin1 => "latin1",
+        }
+    }
 }
 
 fn missing_response() -> TextFileResponse {
@@ -16,9 +108,317 @@ fn missing_response() -> TextFileResponse {
         exists: false,
         content: String::new(),
         truncated: false,
+        kind: FileContentKind::Text,
+        size: 0,
+        mime: None,
+        encoding: None,
+        offset: 0,
+        line_count: 0,
+        version: None,
+    }
+}
+
+/// Hex SHA-256 of `bytes`. It names content-addressed files as well as
+/// detecting on-disk changes, so collisions must not be practical.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn missing_version() -> FileVersion {
+    FileVersion {
+        exists: false,
+        modified_at: None,
+        hash: String::new(),
+        truncated: false,
+        offset: 0,
+        encoding: None,
+    }
+}
+
+pub(crate) fn file_version(path: &Path) -> Result<FileVersion, String> {
+    if !path.exists() {
+        return Ok(missing_version());
+    }
+    let bytes = std::fs::read(path).map_err(|err| format!("Failed to read file: {err}"))?;
+    let modified_at = std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64);
+    let encoding = sniff_encoding(&bytes[..bytes.len().min(SNIFF_BYTES)]);
+    Ok(FileVersion {
+        exists: true,
+        modified_at,
+        hash: content_hash(&bytes),
+        truncated: false,
+        offset: 0,
+        encoding: encoding.map(|encoding| encoding.label().to_string()),
+    })
+}
+
+/// The on-disk version, marked with how much of the file `response` holds.
+fn read_version(response: &TextFileResponse, path: &Path) -> Result<FileVersion, String> {
+    Ok(FileVersion {
+        truncated: response.truncated,
+        offset: response.offset,
+        ..file_version(path)?
+    })
+}
+
+/// Pick an encoding from the first bytes of a file; `None` means binary.
+fn sniff_encoding(sample: &[u8]) -> Option<TextEncoding> {
+    if sample.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some(TextEncoding::Utf8Bom);
+    }
+    if sample.starts_with(&[0xFF, 0xFE]) {
+        return Some(TextEncoding::Utf16Le);
+    }
+    if sample.starts_with(&[0xFE, 0xFF]) {
+        return Some(TextEncoding::Utf16Be);
+    }
+    if sample.contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => return Some(TextEncoding::Utf8),
+        // Only the tail was cut mid-character by the sniff window.
+        Err(error) if error.error_len().is_none() => return Some(TextEncoding::Utf8),
+        Err(_) => {}
+    }
+    let control = sample
+        .iter()
+        .filter(|byte| **byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0C | 0x1B))
+        .count();
+    if control * 10 > sample.len() {
+        None
+    } else {
+        Some(TextEncoding::Latin1)
+    }
+}
+
+fn sniff_mime(sample: &[u8]) -> &'static str {
+    if sample.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if sample.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if sample.starts_with(b"GIF87a") || sample.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if sample.len() >= 12 && &sample[..4] == b"RIFF" && &sample[8..12] == b"WEBP" {
+        "image/webp"
+    } else if sample.starts_with(b"%PDF-") {
+        "application/pdf"
+    } else if sample.starts_with(b"PK\x03\x04") {
+        "application/zip"
+    } else if sample.starts_with(&[0x1F, 0x8B]) {
+        "application/gzip"
+    } else if sample.starts_with(b"\0asm") {
+        "application/wasm"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+fn decode_line(bytes: &[u8], encoding: TextEncoding) -> String {
+    match encoding {
+        TextEncoding::Latin1 => bytes.iter().map(|byte| *byte as char).collect(),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Encode `content` the way a file in `encoding` is stored, BOM included.
+/// Latin-1 files can't hold characters above U+00FF, so those writes fail.
+fn encode_text(content: &str, encoding: TextEncoding) -> Result<Vec<u8>, String> {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    match encoding {
+        TextEncoding::Utf8 => Ok(content.as_bytes().to_vec()),
+        TextEncoding::Utf8Bom => {
+            let mut bytes = vec![0xEF, 0xBB, 0xBF];
+            bytes.extend_from_slice(content.as_bytes());
+            Ok(bytes)
+        }
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let little_endian = encoding == TextEncoding::Utf16Le;
+            let mut bytes = if little_endian {
+                vec![0xFF, 0xFE]
+            } else {
+                vec![0xFE, 0xFF]
+            };
+            for unit in content.encode_utf16() {
+                if little_endian {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+            Ok(bytes)
+        }
+        TextEncoding::Latin1 => content
+            .chars()
+            .map(|ch| u8::try_from(u32::from(ch)).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| "contains characters that latin1 can't represent".to_string()),
+    }
+}
+
+/// Sniff the encoding of the file currently at `path`. New files are UTF-8;
+/// `None` means the file is binary.
+fn current_encoding(path: &Path) -> Result<Option<TextEncoding>, String> {
+    if !path.exists() {
+        return Ok(Some(TextEncoding::Utf8));
+    }
+    let mut sample = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(SNIFF_BYTES as u64).read_to_end(&mut sample))
+        .map_err(|err| format!("Failed to read file: {err}"))?;
+    Ok(sniff_encoding(&sample))
+}
+
+fn truncate_to_char_boundary(value: &str, max_bytes: usize) -> &str {
+    if value.len() <= max_bytes {
+        return value;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+/// Read up to `cap` bytes of the next line into `buf`. Returns false at EOF.
+fn read_line_bounded(reader: &mut impl BufRead, buf: &mut Vec<u8>, cap: u64) -> std::io::Result<bool> {
+    buf.clear();
+    let read = reader.by_ref().take(cap).read_until(b'\n', buf)?;
+    Ok(read > 0)
+}
+
+/// Discard the rest of a line whose start was already consumed.
+fn skip_rest_of_line(reader: &mut impl BufRead, buf: &mut Vec<u8>) -> std::io::Result<()> {
+    while !buf.ends_with(b"\n") {
+        if !read_line_bounded(reader, buf, SKIP_CHUNK_BYTES)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Collect the lines in `range`, stopping once `max_bytes` of text is gathered.
+/// Returns (content, line count, truncated).
+fn collect_lines(
+    mut reader: impl BufRead,
+    encoding: TextEncoding,
+    range: LineRange,
+    max_bytes: usize,
+) -> std::io::Result<(String, usize, bool)> {
+    let offset = range.offset.unwrap_or(0);
+    let limit = range.limit.unwrap_or(usize::MAX);
+    let mut buf = Vec::new();
+    for _ in 0..offset {
+        if !read_line_bounded(&mut reader, &mut buf, SKIP_CHUNK_BYTES)? {
+            return Ok((String::new(), 0, false));
+        }
+        skip_rest_of_line(&mut reader, &mut buf)?;
+    }
+
+    let mut content = String::new();
+    let mut count = 0;
+    loop {
+        let remaining = max_bytes.saturating_sub(content.len()) as u64;
+        if !read_line_bounded(&mut reader, &mut buf, remaining + 1)? {
+            return Ok((content, count, false));
+        }
+        if count >= limit {
+            return Ok((content, count, true));
+        }
+        let line = decode_line(&buf, encoding);
+        if content.len() + line.len() > max_bytes {
+            let partial = truncate_to_char_boundary(&line, max_bytes - content.len());
+            if !partial.is_empty() {
+                content.push_str(partial);
+                count += 1;
+            }
+            return Ok((content, count, true));
+        }
+        content.push_str(&line);
+        count += 1;
     }
 }
 
+/// Read a line window of a file, flagging binary files instead of decoding them.
+pub(crate) fn read_file_range(
+    path: &Path,
+    range: LineRange,
+    max_bytes: usize,
+    file_context: &str,
+) -> Result<TextFileResponse, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|err| format!("Failed to read {file_context} metadata: {err}"))?
+        .len();
+    let file = File::open(path).map_err(|err| format!("Failed to open {file_context}: {err}"))?;
+    let mut reader = BufReader::new(file);
+    let mut sample = Vec::new();
+    reader
+        .by_ref()
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)
+        .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+
+    let mut response = TextFileResponse {
+        exists: true,
+        size,
+        offset: range.offset.unwrap_or(0),
+        ..missing_response()
+    };
+    let Some(encoding) = sniff_encoding(&sample) else {
+        response.kind = FileContentKind::Binary;
+        response.mime = Some(sniff_mime(&sample).to_string());
+        return Ok(response);
+    };
+    response.encoding = Some(encoding.label().to_string());
+
+    let result = match encoding {
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let mut bytes = sample;
+            reader
+                .take(MAX_UTF16_DECODE_BYTES.saturating_sub(bytes.len() as u64))
+                .read_to_end(&mut bytes)
+                .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+            let text = decode_utf16(&bytes[2..], encoding == TextEncoding::Utf16Le);
+            let capped = size > bytes.len() as u64;
+            collect_lines(Cursor::new(text.into_bytes()), TextEncoding::Utf8, range, max_bytes)
+                .map(|(content, count, truncated)| (content, count, truncated || capped))
+        }
+        _ => {
+            let skip = if encoding == TextEncoding::Utf8Bom { 3 } else { 0 };
+            let mut head = Cursor::new(sample);
+            head.set_position(skip);
+            collect_lines(BufReader::new(head.chain(reader)), encoding, range, max_bytes)
+        }
+    };
+    let (content, line_count, truncated) =
+        result.map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+    response.content = content;
+    response.line_count = line_count;
+    response.truncated = truncated;
+    Ok(response)
+}
+
 fn resolve_root(
     root: &Path,
     root_context: &str,
@@ -51,17 +451,18 @@ fn resolve_or_create_root(root: &Path, root_context: &str) -> Result<PathBuf, St
 pub(crate) fn read_text_file_within(
     root: &Path,
     filename: &str,
+    range: LineRange,
     root_may_be_missing: bool,
     root_context: &str,
     file_context: &str,
 ) -> Result<TextFileResponse, String> {
     let Some(canonical_root) = resolve_root(root, root_context, root_may_be_missing)? else {
-        return Ok(missing_response());
+        return Ok(missing_with_version());
     };
 
     let candidate = canonical_root.join(filename);
     if !candidate.exists() {
-        return Ok(missing_response());
+        return Ok(missing_with_version());
     }
 
     let canonical_path = candidate
@@ -71,29 +472,33 @@ pub(crate) fn read_text_file_within(
         return Err(format!("Invalid {file_context} path"));
     }
 
-    let mut file =
-        File::open(&canonical_path).map_err(|err| format!("Failed to open {file_context}: {err}"))?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
-        .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
-    let content = String::from_utf8(buffer)
-        .map_err(|_| format!("{file_context} is not valid UTF-8"))?;
+    let mut response =
+        read_file_range(&canonical_path, range, MAX_TEXT_RESPONSE_BYTES, file_context)?;
+    response.version = Some(read_version(&response, &canonical_path)?);
+    Ok(response)
+}
 
-    Ok(TextFileResponse {
-        exists: true,
-        content,
-        truncated: false,
-    })
+fn missing_with_version() -> TextFileResponse {
+    TextFileResponse {
+        version: Some(missing_version()),
+        ..missing_response()
+    }
 }
 
 pub(crate) fn write_text_file_within(
     root: &Path,
     filename: &str,
     content: &str,
+    expected: Option<&FileVersion>,
     create_root: bool,
     root_context: &str,
     file_context: &str,
-) -> Result<(), String> {
+) -> Result<FileWriteResult, String> {
+    if expected.is_some_and(|version| version.truncated || version.offset > 0) {
+        return Err(format!(
+            "Only part of the {file_context} was loaded; reload the whole file before saving"
+        ));
+    }
     let canonical_root = if create_root {
         resolve_or_create_root(root, root_context)?
     } else {
@@ -118,8 +523,38 @@ pub(crate) fn write_text_file_within(
         candidate
     };
 
-    std::fs::write(&target_path, content)
-        .map_err(|err| format!("Failed to write {file_context}: {err}"))
+    if let Some(expected) = expected {
+        let current = file_version(&target_path)?;
+        if current.exists != expected.exists || current.hash != expected.hash {
+            let mut current_response = if current.exists {
+                read_file_range(
+                    &target_path,
+                    LineRange::default(),
+                    MAX_TEXT_RESPONSE_BYTES,
+                    file_context,
+                )?
+            } else {
+                missing_response()
+            };
+            current_response.version = Some(FileVersion {
+                truncated: current_response.truncated,
+                ..current
+            });
+            return Ok(FileWriteResult::Conflict {
+                current: current_response,
+            });
+        }
+    }
+
+    let encoding = current_encoding(&target_path)?
+        .ok_or_else(|| format!("Refusing to overwrite binary {file_context}"))?;
+    let bytes = encode_text(content, encoding)
+        .map_err(|err| format!("Failed to write {file_context}: {err}"))?;
+    std::fs::write(&target_path, bytes)
+        .map_err(|err| format!("Failed to write {file_context}: {err}"))?;
+    Ok(FileWriteResult::Written {
+        version: file_version(&target_path)?,
+    })
 }
 
 #[cfg(test)]
@@ -135,7 +570,7 @@ mod tests {
     #[test]
     fn read_returns_missing_when_root_absent() {
         let root = temp_dir();
-        let response = read_text_file_within(&root, "CLAUDE.md", true, "CLAUDE_HOME", "CLAUDE.md")
+        let response = read_text_file_within(&root, "CLAUDE.md", LineRange::default(), true, "CLAUDE_HOME", "CLAUDE.md")
             .expect("read should succeed");
         assert!(!response.exists);
         assert!(response.content.is_empty());
@@ -144,10 +579,10 @@ mod tests {
     #[test]
     fn write_creates_root_and_round_trips() {
         let root = temp_dir();
-        write_text_file_within(&root, "CLAUDE.md", "hello", true, "CLAUDE_HOME", "CLAUDE.md")
+        write_text_file_within(&root, "CLAUDE.md", "hello", None, true, "CLAUDE_HOME", "CLAUDE.md")
             .expect("write should succeed");
         let response =
-            read_text_file_within(&root, "CLAUDE.md", false, "CLAUDE_HOME", "CLAUDE.md")
+            read_text_file_within(&root, "CLAUDE.md", LineRange::default(), false, "CLAUDE_HOME", "CLAUDE.md")
                 .expect("read should succeed");
         assert!(response.exists);
         assert_eq!(response.content, "hello");
@@ -169,7 +604,7 @@ mod tests {
         let link_path = root.join("CLAUDE.md");
         symlink(&outside_file, &link_path).expect("create symlink");
 
-        let error = write_text_file_within(&root, "CLAUDE.md", "updated", false, "workspace root", "CLAUDE.md")
+        let error = write_text_file_within(&root, "CLAUDE.md", "updated", None, false, "workspace root", "CLAUDE.md")
             .expect_err("should reject symlink escape");
         assert!(error.contains("Invalid CLAUDE.md path"));
     }
@@ -190,8 +625,171 @@ mod tests {
         let link_path = root.join("CLAUDE.md");
         symlink(&outside_file, &link_path).expect("create symlink");
 
-        let error = read_text_file_within(&root, "CLAUDE.md", false, "workspace root", "CLAUDE.md")
+        let error = read_text_file_within(&root, "CLAUDE.md", LineRange::default(), false, "workspace root", "CLAUDE.md")
             .expect_err("should reject symlink escape");
         assert!(error.contains("Invalid CLAUDE.md path"));
     }
+
+    fn write_temp(name: &str, bytes: &[u8]) -> PathBuf {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        let path = root.join(name);
+        std::fs::write(&path, bytes).expect("write file");
+        path
+    }
+
+    #[test]
+    fn read_file_range_returns_requested_lines() {
+        let path = write_temp("log.txt", b"one\ntwo\nthree\nfour\n");
+        let range = LineRange {
+            offset: Some(1),
+            limit: Some(2),
+        };
+        let response = read_file_range(&path, range, 1024, "file").expect("read");
+        assert_eq!(response.content, "two\nthree\n");
+        assert_eq!(response.offset, 1);
+        assert_eq!(response.line_count, 2);
+        assert!(response.truncated);
+        assert_eq!(response.encoding.as_deref(), Some("utf-8"));
+
+        let tail = LineRange {
+            offset: Some(3),
+            limit: Some(10),
+        };
+        let response = read_file_range(&path, tail, 1024, "file").expect("read");
+        assert_eq!(response.content, "four\n");
+        assert!(!response.truncated);
+    }
+
+    #[test]
+    fn read_file_range_caps_bytes_on_char_boundary() {
+        let path = write_temp("wide.txt", "ééééé\nnext\n".as_bytes());
+        let response = read_file_range(&path, LineRange::default(), 5, "file").expect("read");
+        assert_eq!(response.content, "éé");
+        assert!(response.truncated);
+    }
+
+    #[test]
+    fn read_file_range_flags_binary_files() {
+        let path = write_temp("image.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+        let response = read_file_range(&path, LineRange::default(), 1024, "file").expect("read");
+        assert_eq!(response.kind, FileContentKind::Binary);
+        assert_eq!(response.mime.as_deref(), Some("image/png"));
+        assert_eq!(response.size, 16);
+        assert!(response.content.is_empty());
+    }
+
+    #[test]
+    fn read_file_range_decodes_other_encodings() {
+        let path = write_temp("latin1.txt", b"caf\xe9\n");
+        let response = read_file_range(&path, LineRange::default(), 1024, "file").expect("read");
+        assert_eq!(response.content, "café\n");
+        assert_eq!(response.encoding.as_deref(), Some("latin1"));
+
+        let path = write_temp("utf16.txt", b"\xff\xfeh\0i\0\n\0");
+        let response = read_file_range(&path, LineRange::default(), 1024, "file").expect("read");
+        assert_eq!(response.content, "hi\n");
+        assert_eq!(response.encoding.as_deref(), Some("utf-16le"));
+
+        let path = write_temp("bom.txt", b"\xef\xbb\xbfhello");
+        let response = read_file_range(&path, LineRange::default(), 1024, "file").expect("read");
+        assert_eq!(response.content, "hello");
+    }
+
+    #[test]
+    fn content_hash_is_hex_sha256() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn write_detects_conflicting_changes() {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        let read = |root: &PathBuf| {
+            read_text_file_within(root, "CLAUDE.md", LineRange::default(), false, "workspace root", "CLAUDE.md")
+                .expect("read")
+        };
+        let missing = read(&root).version.expect("missing version");
+        assert!(!missing.exists);
+
+        let written = write_text_file_within(&root, "CLAUDE.md", "mine", Some(&missing), false, "workspace root", "CLAUDE.md")
+            .expect("write");
+        let FileWriteResult::Written { version } = written else {
+            panic!("expected write to succeed");
+        };
+        assert_eq!(read(&root).version, Some(version.clone()));
+
+        std::fs::write(root.join("CLAUDE.md"), "agent edit").expect("external edit");
+        let result = write_text_file_within(&root, "CLAUDE.md", "stale", Some(&version), false, "workspace root", "CLAUDE.md")
+            .expect("write");
+        let FileWriteResult::Conflict { current } = result else {
+            panic!("expected conflict");
+        };
+        assert_eq!(current.content, "agent edit");
+        assert_eq!(std::fs::read_to_string(root.join("CLAUDE.md")).unwrap(), "agent edit");
+
+        let retried = write_text_file_within(&root, "CLAUDE.md", "merged", current.version.as_ref(), false, "workspace root", "CLAUDE.md")
+            .expect("write");
+        assert!(matches!(retried, FileWriteResult::Written { .. }));
+    }
+
+    #[test]
+    fn write_rejects_partial_reads() {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::write(root.join("notes.md"), "one\ntwo\nthree\n").expect("seed file");
+        let read = |offset: usize| {
+            let range = LineRange {
+                offset: Some(offset),
+                limit: Some(1),
+            };
+            read_text_file_within(&root, "notes.md", range, false, "workspace root", "file")
+                .expect("read")
+                .version
+                .expect("version")
+        };
+        for version in [read(0), read(1)] {
+            let error = write_text_file_within(&root, "notes.md", "two\n", Some(&version), false, "workspace root", "file")
+                .expect_err("partial read should not be written back");
+            assert!(error.contains("reload the whole file"));
+        }
+        assert_eq!(std::fs::read_to_string(root.join("notes.md")).unwrap(), "one\ntwo\nthree\n");
+    }
+
+    #[test]
+    fn write_keeps_the_file_encoding() {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        let write = |name: &str, content: &str| {
+            let version = read_text_file_within(&root, name, LineRange::default(), false, "workspace root", "file")
+                .expect("read")
+                .version;
+            write_text_file_within(&root, name, content, version.as_ref(), false, "workspace root", "file")
+        };
+
+        std::fs::write(root.join("latin1.txt"), b"caf\xe9\n").expect("seed latin1");
+        write("latin1.txt", "déjà vu\n").expect("write latin1");
+        assert_eq!(std::fs::read(root.join("latin1.txt")).unwrap(), b"d\xe9j\xe0 vu\n");
+        let error = write("latin1.txt", "snow ☃\n").expect_err("latin1 can't hold a snowman");
+        assert!(error.contains("latin1"));
+
+        std::fs::write(root.join("utf16.txt"), b"\xff\xfeh\0i\0").expect("seed utf16");
+        let written = write("utf16.txt", "hé").expect("write utf16");
+        assert_eq!(std::fs::read(root.join("utf16.txt")).unwrap(), b"\xff\xfeh\0\xe9\0");
+        let FileWriteResult::Written { version } = written else {
+            panic!("expected write to succeed");
+        };
+        assert_eq!(version.encoding.as_deref(), Some("utf-16le"));
+
+        std::fs::write(root.join("bom.txt"), b"\xef\xbb\xbfold").expect("seed bom");
+        write("bom.txt", "new").expect("write bom");
+        assert_eq!(std::fs::read(root.join("bom.txt")).unwrap(), b"\xef\xbb\xbfnew");
+
+        std::fs::write(root.join("image.png"), b"\x89PNG\r\n\x1a\n\0\0").expect("seed binary");
+        let error = write("image.png", "text").expect_err("binary files are not overwritten");
+        assert!(error.contains("binary"));
+    }
 }
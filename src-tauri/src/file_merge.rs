@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+/// Above this many DP cells the middle of a file is treated as one changed block.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreeWayMerge {
+    /// Merged text; conflicting blocks are wrapped in git-style markers
+    pub(crate) content: String,
+    pub(crate) conflicts: usize,
+    pub(crate) clean: bool,
+}
+
+fn clean(content: &str) -> ThreeWayMerge {
+    ThreeWayMerge {
+        content: content.to_string(),
+        conflicts: 0,
+        clean: true,
+    }
+}
+
+/// For each line of `base`, the index of the matching line in `other` along a
+/// longest common subsequence.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    let prefix = base
+        .iter()
+        .zip(other)
+        .take_while(|(a, b)| a == b)
+        .count();
+    for (index, slot) in matches.iter_mut().enumerate().take(prefix) {
+        *slot = Some(index);
+    }
+    let base_rest = &base[prefix..];
+    let other_rest = &other[prefix..];
+    let suffix = base_rest
+        .iter()
+        .rev()
+        .zip(other_rest.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for offset in 1..=suffix {
+        matches[base.len() - offset] = Some(other.len() - offset);
+    }
+
+    let base_mid = &base_rest[..base_rest.len() - suffix];
+    let other_mid = &other_rest[..other_rest.len() - suffix];
+    let (rows, cols) = (base_mid.len(), other_mid.len());
+    if rows == 0 || cols == 0 || rows.saturating_mul(cols) > MAX_LCS_CELLS {
+        return matches;
+    }
+    let width = cols + 1;
+    let mut lengths = vec![0u32; (rows + 1) * width];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lengths[i * width + j] = if base_mid[i] == other_mid[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if base_mid[i] == other_mid[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+    }
+}
+
+fn push_conflict_side(out: &mut String, lines: &[&str]) {
+    push_lines(out, lines);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+fn merge_chunk(out: &mut String, base: &[&str], ours: &[&str], theirs: &[&str]) -> bool {
+    if ours == theirs || base == theirs {
+        push_lines(out, ours);
+    } else if base == ours {
+        push_lines(out, theirs);
+    } else {
+        out.push_str("<<<<<<< ours\n");
+        push_conflict_side(out, ours);
+        out.push_str("=======\n");
+        push_conflict_side(out, theirs);
+        out.push_str(">>>>>>> theirs\n");
+        return false;
+    }
+    true
+}
+
+/// Line-based diff3: changes made on only one side are applied, overlapping
+/// changes become conflict blocks.
+pub(crate) fn merge_three_way(base: &str, ours: &str, theirs: &str) -> ThreeWayMerge {
+    if ours == theirs || base == theirs {
+        return clean(ours);
+    }
+    if base == ours {
+        return clean(theirs);
+    }
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+    let ours_matches = match_lines(&base_lines, &ours_lines);
+    let theirs_matches = match_lines(&base_lines, &theirs_lines);
+
+    let mut content = String::new();
+    let mut conflicts = 0;
+    let (mut base_start, mut ours_start, mut theirs_start) = (0, 0, 0);
+    for index in 0..=base_lines.len() {
+        // Lines kept unchanged on both sides anchor the chunks between them.
+        let anchor = if index == base_lines.len() {
+            Some((ours_lines.len(), theirs_lines.len()))
+        } else {
+            match (ours_matches[index], theirs_matches[index]) {
+                (Some(ours_index), Some(theirs_index))
+                    if ours_index >= ours_start && theirs_index >= theirs_start =>
+                {
+                    Some((ours_index, theirs_index))
+                }
+                _ => None,
+            }
+        };
+        let Some((ours_end, theirs_end)) = anchor else {
+            continue;
+        };
+        if !merge_chunk(
+            &mut content,
+            &base_lines[base_start..index],
+            &ours_lines[ours_start..ours_end],
+            &theirs_lines[theirs_start..theirs_end],
+        ) {
+            conflicts += 1;
+        }
+        if index < base_lines.len() {
+            content.push_str(base_lines[index]);
+        }
+        base_start = index + 1;
+        ours_start = ours_end + 1;
+        theirs_start = theirs_end + 1;
+    }
+
+    ThreeWayMerge {
+        content,
+        conflicts,
+        clean: conflicts == 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_applies_non_overlapping_edits() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let ours = "ONE\ntwo\nthree\nfour\n";
+        let theirs = "one\ntwo\nthree\nFOUR\nfive\n";
+        let merged = merge_three_way(base, ours, theirs);
+        assert!(merged.clean);
+        assert_eq!(merged.content, "ONE\ntwo\nthree\nFOUR\nfive\n");
+    }
+
+    #[test]
+    fn merge_marks_overlapping_edits() {
+        let base = "one\ntwo\nthree\n";
+        let ours = "one\nmine\nthree\n";
+        let theirs = "one\nagent\nthree\n";
+        let merged = merge_three_way(base, ours, theirs);
+        assert!(!merged.clean);
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.content,
+            "one\n<<<<<<< ours\nmine\n=======\nagent\n>>>>>>> theirs\nthree\n"
+        );
+    }
+
+    #[test]
+    fn merge_short_circuits_when_one_side_is_unchanged() {
+        assert_eq!(merge_three_way("a\n", "a\n", "b\n").content, "b\n");
+        assert_eq!(merge_three_way("a\n", "b\n", "a\n").content, "b\n");
+        assert_eq!(merge_three_way("a\n", "c\n", "c\n").content, "c\n");
+    }
+
+    #[test]
+    fn merge_keeps_identical_changes_once() {
+        let merged = merge_three_way("a\nb\nc\n", "a\nx\nc\nd\n", "a\nx\nc\n");
+        assert!(merged.clean);
+        assert_eq!(merged.content, "a\nx\nc\nd\n");
+    }
+}
@@ -1,12 +1,20 @@
 use std::path::PathBuf;
 
-use crate::file_io::{read_text_file_within, write_text_file_within, TextFileResponse};
+use crate::file_io::{
+    read_text_file_within, write_text_file_within, FileVersion, FileWriteResult, LineRange,
+    TextFileResponse,
+};
 use crate::file_policy::FilePolicy;
 
-pub(crate) fn read_with_policy(root: &PathBuf, policy: FilePolicy) -> Result<TextFileResponse, String> {
+pub(crate) fn read_with_policy(
+    root: &PathBuf,
+    policy: FilePolicy,
+    range: LineRange,
+) -> Result<TextFileResponse, String> {
     read_text_file_within(
         root,
         policy.filename,
+        range,
         policy.root_may_be_missing,
         policy.root_context,
         policy.filename,
@@ -17,11 +25,13 @@ pub(crate) fn write_with_policy(
     root: &PathBuf,
     policy: FilePolicy,
     content: &str,
-) -> Result<(), String> {
+    expected: Option<&FileVersion>,
+) -> Result<FileWriteResult, String> {
     write_text_file_within(
         root,
         policy.filename,
         content,
+        expected,
         policy.create_root,
         policy.root_context,
         policy.filename,
@@ -34,6 +44,7 @@ mod tests {
 
     use uuid::Uuid;
 
+    use crate::file_io::LineRange;
     use crate::file_policy::{policy_for, FileKind, FileScope};
 
     use super::{read_with_policy, write_with_policy};
@@ -52,8 +63,8 @@ mod tests {
         fs::create_dir_all(&root).expect("create workspace root");
         let policy = policy_for(FileScope::Workspace, FileKind::ClaudeMd).expect("policy");
 
-        write_with_policy(&root, policy, "workspace claude md").expect("write claude md");
-        let response = read_with_policy(&root, policy).expect("read claude md");
+        write_with_policy(&root, policy, "workspace claude md", None).expect("write claude md");
+        let response = read_with_policy(&root, policy, LineRange::default()).expect("read claude md");
 
         assert!(response.exists);
         assert_eq!(response.content, "workspace claude md");
@@ -67,7 +78,7 @@ mod tests {
         let root = temp_dir("workspace-missing-root");
         let policy = policy_for(FileScope::Workspace, FileKind::ClaudeMd).expect("policy");
 
-        let result = write_with_policy(&root, policy, "should fail");
+        let result = write_with_policy(&root, policy, "should fail", None);
         assert!(result.is_err());
     }
 
@@ -76,11 +87,11 @@ mod tests {
         let root = temp_dir("global-claude-md");
         let policy = policy_for(FileScope::Global, FileKind::ClaudeMd).expect("policy");
 
-        let initial = read_with_policy(&root, policy).expect("initial read");
+        let initial = read_with_policy(&root, policy, LineRange::default()).expect("initial read");
         assert!(!initial.exists);
 
-        write_with_policy(&root, policy, "global claude md").expect("write claude md");
-        let response = read_with_policy(&root, policy).expect("read claude md");
+        write_with_policy(&root, policy, "global claude md", None).expect("write claude md");
+        let response = read_with_policy(&root, policy, LineRange::default()).expect("read claude md");
 
         assert!(response.exists);
         assert_eq!(response.content, "global claude md");
@@ -94,8 +105,8 @@ mod tests {
         let root = temp_dir("global-settings");
         let policy = policy_for(FileScope::Global, FileKind::Settings).expect("policy");
 
-        write_with_policy(&root, policy, "{\"theme\": \"dark\"}\n").expect("write settings");
-        let response = read_with_policy(&root, policy).expect("read settings");
+        write_with_policy(&root, policy, "{\"theme\": \"dark\"}\n", None).expect("write settings");
+        let response = read_with_policy(&root, policy, LineRange::default()).expect("read settings");
 
         assert!(response.exists);
         assert!(response.content.contains("\"theme\""));
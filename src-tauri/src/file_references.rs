@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Characters that end a path token in prose and markdown.
+const TOKEN_DELIMITERS: &[char] = &[
+    '`', '"', '\'', '(', ')', '[', ']', '<', '>', '{', '}', ',', '|',
+];
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?'];
+
+/// A file mentioned in a message, checked against the workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileReference {
+    /// The reference as written, e.g. `src/lib.rs:42:5`.
+    pub(crate) text: String,
+    /// Path relative to the workspace root.
+    pub(crate) path: String,
+    /// Canonical path when the file exists, otherwise the resolved path.
+    pub(crate) absolute_path: String,
+    pub(crate) line: Option<u32>,
+    pub(crate) column: Option<u32>,
+    pub(crate) exists: bool,
+}
+
+/// A path-like token split into its path and optional `:line:column` or
+/// `#L<line>` suffix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PathToken {
+    text: String,
+    path: String,
+    line: Option<u32>,
+    column: Option<u32>,
+}
+
+fn split_location(token: &str) -> (&str, Option<u32>, Option<u32>) {
+    if let Some((path, anchor)) = token.split_once("#L") {
+        let line = anchor.split('-').next().and_then(|line| line.parse().ok());
+        return (path, line, None);
+    }
+    let mut parts = token.rsplitn(3, ':').collect::<Vec<_>>();
+    parts.reverse();
+    let number = |part: &str| -> Option<u32> {
+        // `path:10-20` ranges point at their first line.
+        part.split('-').next().and_then(|value| value.parse().ok())
+    };
+    match *parts.as_slice() {
+        [path, line, column] if number(line).is_some() && number(column).is_some() => {
+            (path, number(line), number(column))
+        }
+        [_, .., line] if number(line).is_some() => {
+            let path = &token[..token.len() - line.len() - 1];
+            (path, number(line), None)
+        }
+        _ => (token, None, None),
+    }
+}
+
+/// Whether a token reads as a file path rather than a word, URL or module path.
+fn looks_like_path(path: &str) -> bool {
+    if path.is_empty() || path.contains("://") || path.contains("::") || path.starts_with('@') {
+        return false;
+    }
+    let has_dir = path.contains('/');
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    // A bare `e.g` or `1.2` is prose; a bare `main.rs` is a file.
+    let has_extension = file_name.rsplit_once('.').is_some_and(|(stem, ext)| {
+        (has_dir || stem.chars().count() > 1)
+            && ext.starts_with(|ch: char| ch.is_ascii_alphabetic())
+            && ext.chars().all(|ch| ch.is_ascii_alphanumeric())
+    });
+    has_extension || (has_dir && !file_name.is_empty())
+}
+
+/// Path-like tokens in `text`, in order of appearance.
+fn extract_path_tokens(text: &str) -> Vec<PathToken> {
+    text.split(|ch: char| ch.is_whitespace() || TOKEN_DELIMITERS.contains(&ch))
+        .filter_map(|raw| {
+            let token = raw.trim_end_matches(TRAILING_PUNCTUATION);
+            let (path, line, column) = split_location(token);
+            let path = path.trim_end_matches(TRAILING_PUNCTUATION);
+            if !looks_like_path(path) || path.chars().all(|ch| ch == '.' || ch == '/') {
+                return None;
+            }
+            Some(PathToken {
+                text: token.to_string(),
+                path: path.to_string(),
+                line,
+                column,
+            })
+        })
+        .collect()
+}
+
+/// `path` relative to `root`, resolving `.` and `..` without touching the
+/// disk. `None` when it points outside the root.
+fn workspace_relative(root: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    let path = if path.is_absolute() {
+        path.strip_prefix(root).ok()?
+    } else {
+        path
+    };
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !relative.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+fn resolve_references(root: &Path, text: &str) -> Vec<FileReference> {
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let mut seen = HashSet::new();
+    let mut references = Vec::new();
+    for token in extract_path_tokens(text) {
+        let Some(relative) = workspace_relative(root, &token.path)
+            .or_else(|| workspace_relative(&canonical_root, &token.path))
+        else {
+            continue;
+        };
+        let joined = canonical_root.join(&relative);
+        // A symlink may still lead outside the workspace.
+        let canonical = joined
+            .canonicalize()
+            .ok()
+            .filter(|path| path.starts_with(&canonical_root));
+        let exists = canonical.as_ref().is_some_and(|path| path.is_file());
+        if canonical.is_none() && joined.exists() {
+            continue;
+        }
+        if !seen.insert((relative.clone(), token.line, token.column)) {
+            continue;
+        }
+        references.push(FileReference {
+            text: token.text,
+            path: relative.to_string_lossy().replace('\\', "/"),
+            absolute_path: canonical.unwrap_or(joined).to_string_lossy().to_string(),
+            line: token.line,
+            column: token.column,
+            exists,
+        });
+    }
+    references
+}
+
+/// Find file paths and `path:line` references in a message and check them
+/// against the workspace, so the UI can open them in an editor.
+#[tauri::command]
+pub(crate) async fn resolve_file_reference(
+    workspace_id: String,
+    text: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<FileReference>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "resolve_file_reference",
+            json!({ "workspaceId": workspace_id, "text": text }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    tokio::task::spawn_blocking(move || resolve_references(&root, &text))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use uuid::Uuid;
+
+    type Location = (String, Option<u32>, Option<u32>);
+
+    fn token(path: &str, line: Option<u32>, column: Option<u32>) -> Location {
+        (path.to_string(), line, column)
+    }
+
+    #[test]
+    fn extract_path_tokens_reads_locations_and_skips_prose() {
+        let text = "See `src/lib.rs:42:5`, then (docs/guide.md#L10-L20). \
+Also Cargo.toml: and https://example.com/a.rs or std::fs::read, e.g. the end.";
+        let tokens: Vec<_> = extract_path_tokens(text)
+            .into_iter()
+            .map(|token| (token.path, token.line, token.column))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                token("src/lib.rs", Some(42), Some(5)),
+                token("docs/guide.md", Some(10), None),
+                token("Cargo.toml", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_references_flags_missing_files_and_drops_outside_paths() {
+        let root = std::env::temp_dir().join(format!("file-refs-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+        let absolute = root.join("src").join("main.rs");
+        let text = format!(
+            "Edited src/main.rs:3 and {}:4; see src/gone.rs and ../secret.txt",
+            absolute.display()
+        );
+
+        let references = resolve_references(&root, &text);
+        let summary: Vec<_> = references
+            .iter()
+            .map(|reference| (reference.path.as_str(), reference.line, reference.exists))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/main.rs", Some(3), true),
+                ("src/main.rs", Some(4), true),
+                ("src/gone.rs", None, false),
+            ]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use git2::{Repository, StatusOptions};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use tokio::task;
+
+use crate::git::{status_for_index, status_for_workdir};
+use crate::git_utils::resolve_git_root;
+use crate::palette::file_score;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+use crate::utils::normalize_git_path;
+use crate::workspaces::{list_workspace_files_inner, should_skip_dir};
+
+const MAX_TREE_DEPTH: u32 = 4;
+/// Upper bound on entries returned for one directory level.
+const MAX_DIR_ENTRIES: usize = 5_000;
+/// Much larger than the flat list cap: the index only holds paths.
+const MAX_INDEXED_FILES: usize = 500_000;
+const FILE_INDEX_TTL: Duration = Duration::from_secs(30);
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+const MAX_SEARCH_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WorkspaceDirEntryKind {
+    Dir,
+    File,
+}
+
+/// One entry of a lazily expanded workspace directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceDirEntry {
+    pub(crate) name: String,
+    /// Workspace-relative path with forward slashes
+    pub(crate) path: String,
+    pub(crate) kind: WorkspaceDirEntryKind,
+    /// File size in bytes; `None` for directories
+    pub(crate) size: Option<u64>,
+    /// Git status letter; directories report "M" when anything below them changed
+    pub(crate) git_status: Option<String>,
+    /// Present for directories expanded within the requested depth
+    pub(crate) children: Option<Vec<WorkspaceDirEntry>>,
+    /// Set when the level was cut off at `MAX_DIR_ENTRIES`
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceFileMatch {
+    pub(crate) path: String,
+    pub(crate) name: String,
+    pub(crate) score: i64,
+}
+
+struct FileIndex {
+    root: PathBuf,
+    files: Vec<String>,
+    built_at: Instant,
+}
+
+static FILE_INDEXES: OnceLock<Mutex<HashMap<String, FileIndex>>> = OnceLock::new();
+
+fn file_indexes() -> &'static Mutex<HashMap<String, FileIndex>> {
+    FILE_INDEXES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolve a workspace-relative directory, rejecting anything outside the root.
+fn resolve_dir(root: &Path, relative: &str) -> Result<(PathBuf, PathBuf), String> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let relative = relative.trim().trim_matches('/');
+    let candidate = if relative.is_empty() {
+        canonical_root.clone()
+    } else {
+        canonical_root.join(relative)
+    };
+    let canonical_dir = candidate
+        .canonicalize()
+        .map_err(|err| format!("Failed to open directory: {err}"))?;
+    if !canonical_dir.starts_with(&canonical_root) {
+        return Err("Invalid directory path".to_string());
+    }
+    if !canonical_dir.is_dir() {
+        return Err("Path is not a directory".to_string());
+    }
+    Ok((canonical_root, canonical_dir))
+}
+
+/// Git status letters keyed by workspace-relative path. Empty when the
+/// workspace isn't a repository.
+fn collect_git_statuses(entry: &WorkspaceEntry, canonical_root: &Path) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    let Ok(repo_root) = resolve_git_root(entry).and_then(|root| {
+        root.canonicalize().map_err(|err| err.to_string())
+    }) else {
+        return result;
+    };
+    let Ok(repo) = Repository::open(&repo_root) else {
+        return result;
+    };
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let Ok(statuses) = repo.statuses(Some(&mut options)) else {
+        return result;
+    };
+    for status_entry in statuses.iter() {
+        let Some(path) = status_entry.path() else {
+            continue;
+        };
+        let status = status_entry.status();
+        let Some(letter) = status_for_workdir(status).or_else(|| status_for_index(status)) else {
+            continue;
+        };
+        let absolute = repo_root.join(path);
+        if let Ok(relative) = absolute.strip_prefix(canonical_root) {
+            let relative = normalize_git_path(&relative.to_string_lossy());
+            result.insert(relative.trim_end_matches('/').to_string(), letter.to_string());
+        }
+    }
+    result
+}
+
+fn dir_git_status(statuses: &HashMap<String, String>, relative: &str) -> Option<String> {
+    let prefix = format!("{relative}/");
+    statuses
+        .keys()
+        .any(|path| path == relative || path.starts_with(&prefix))
+        .then(|| "M".to_string())
+}
+
+fn sort_entries(entries: &mut [WorkspaceDirEntry]) {
+    entries.sort_by(|a, b| {
+        (a.kind != WorkspaceDirEntryKind::Dir)
+            .cmp(&(b.kind != WorkspaceDirEntryKind::Dir))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// List one directory level (ignore-aware), recursing while `depth > 1`.
+fn read_dir_level(
+    canonical_root: &Path,
+    dir: &Path,
+    depth: u32,
+    statuses: &HashMap<String, String>,
+) -> (Vec<WorkspaceDirEntry>, bool) {
+    let walker = WalkBuilder::new(dir)
+        .hidden(false)
+        .follow_links(false)
+        .require_git(false)
+        .max_depth(Some(1))
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                let name = entry.file_name().to_string_lossy();
+                return !should_skip_dir(&name);
+            }
+            true
+        })
+        .build();
+
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    for entry in walker {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if entry.depth() == 0 {
+            continue;
+        }
+        if entries.len() >= MAX_DIR_ENTRIES {
+            truncated = true;
+            break;
+        }
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        let Ok(relative) = entry.path().strip_prefix(canonical_root) else {
+            continue;
+        };
+        let relative = normalize_git_path(&relative.to_string_lossy());
+        let name = entry.file_name().to_string_lossy().to_string();
+        if file_type.is_dir() {
+            let (children, children_truncated) = if depth > 1 {
+                let (children, truncated) =
+                    read_dir_level(canonical_root, entry.path(), depth - 1, statuses);
+                (Some(children), truncated)
+            } else {
+                (None, false)
+            };
+            entries.push(WorkspaceDirEntry {
+                git_status: dir_git_status(statuses, &relative),
+                name,
+                path: relative,
+                kind: WorkspaceDirEntryKind::Dir,
+                size: None,
+                children,
+                truncated: children_truncated,
+            });
+        } else {
+            entries.push(WorkspaceDirEntry {
+                git_status: statuses.get(&relative).cloned(),
+                name,
+                path: relative,
+                kind: WorkspaceDirEntryKind::File,
+                size: entry.metadata().ok().map(|meta| meta.len()),
+                children: None,
+                truncated: false,
+            });
+        }
+    }
+    sort_entries(&mut entries);
+    (entries, truncated)
+}
+
+pub(crate) fn list_workspace_dir_inner(
+    entry: &WorkspaceEntry,
+    relative: &str,
+    depth: u32,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    let (canonical_root, dir) = resolve_dir(Path::new(&entry.path), relative)?;
+    let statuses = collect_git_statuses(entry, &canonical_root);
+    let depth = depth.clamp(1, MAX_TREE_DEPTH);
+    Ok(read_dir_level(&canonical_root, &dir, depth, &statuses).0)
+}
+
+fn rank_files(files: &[String], query: &str, limit: usize) -> Vec<WorkspaceFileMatch> {
+    let mut matches: Vec<WorkspaceFileMatch> = files
+        .iter()
+        .filter_map(|path| {
+            let score = file_score(query, path)?;
+            Some(WorkspaceFileMatch {
+                name: path.rsplit('/').next().unwrap_or(path).to_string(),
+                path: path.clone(),
+                score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    matches.truncate(limit);
+    matches
+}
+
+/// Search the workspace's cached file index, rebuilding it when stale.
+fn search_file_index(
+    workspace_id: &str,
+    root: PathBuf,
+    query: &str,
+    limit: usize,
+    refresh: bool,
+) -> Vec<WorkspaceFileMatch> {
+    let mut indexes = file_indexes().lock().unwrap_or_else(|err| err.into_inner());
+    let stale = match indexes.get(workspace_id) {
+        Some(index) => {
+            refresh || index.root != root || index.built_at.elapsed() >= FILE_INDEX_TTL
+        }
+        None => true,
+    };
+    if stale {
+        let files = list_workspace_files_inner(&root, MAX_INDEXED_FILES);
+        indexes.insert(
+            workspace_id.to_string(),
+            FileIndex {
+                root,
+                files,
+                built_at: Instant::now(),
+            },
+        );
+    }
+    indexes
+        .get(workspace_id)
+        .map(|index| rank_files(&index.files, query, limit))
+        .unwrap_or_default()
+}
+
+/// Drop a workspace's file index, e.g. when it is removed.
+pub(crate) fn forget_file_index(workspace_id: &str) {
+    file_indexes()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(workspace_id);
+}
+
+async fn workspace_entry(state: &AppState, workspace_id: &str) -> Result<WorkspaceEntry, String> {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())
+}
+
+/// One level of the workspace tree (or `depth` levels), for lazy expansion in
+/// the file explorer. `path` is workspace-relative; empty means the root.
+#[tauri::command]
+pub(crate) async fn list_workspace_dir(
+    workspace_id: String,
+    path: Option<String>,
+    depth: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_workspace_dir",
+            json!({ "workspaceId": workspace_id, "path": path, "depth": depth }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let relative = path.unwrap_or_default();
+    task::spawn_blocking(move || list_workspace_dir_inner(&entry, &relative, depth.unwrap_or(1)))
+        .await
+        .map_err(|_| "listing directory failed".to_string())?
+}
+
+/// Fuzzy file search over a per-workspace index that is rebuilt every
+/// `FILE_INDEX_TTL` or when `refresh` is set.
+#[tauri::command]
+pub(crate) async fn search_workspace_files(
+    workspace_id: String,
+    query: String,
+    limit: Option<u32>,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceFileMatch>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "search_workspace_files",
+            json!({
+                "workspaceId": workspace_id,
+                "query": query,
+                "limit": limit,
+                "refresh": refresh,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let limit = limit
+        .map(|value| value as usize)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let query = query.trim().to_string();
+    task::spawn_blocking(move || {
+        search_file_index(
+            &workspace_id,
+            PathBuf::from(&entry.path),
+            &query,
+            limit,
+            refresh.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|_| "file search failed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("file-tree-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+        std::fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        std::fs::write(dir.join("README.md"), "hello").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.join("src/nested/deep.rs"), "").unwrap();
+        std::fs::write(dir.join("node_modules/pkg/index.js"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn read_dir_level_lists_one_level_dirs_first() {
+        let dir = temp_dir();
+        let root = dir.canonicalize().unwrap();
+        let mut statuses = HashMap::new();
+        statuses.insert("src/nested/deep.rs".to_string(), "A".to_string());
+        let (entries, truncated) = read_dir_level(&root, &root, 1, &statuses);
+        assert!(!truncated);
+        let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "README.md"]);
+        assert_eq!(entries[0].git_status.as_deref(), Some("M"));
+        assert!(entries[0].children.is_none());
+        assert_eq!(entries[1].size, Some(5));
+        assert!(entries[1].git_status.is_none());
+
+        let (entries, _) = read_dir_level(&root, &root, 2, &statuses);
+        let children = entries[0].children.as_ref().unwrap();
+        assert_eq!(children[0].path, "src/nested");
+        assert_eq!(children[1].path, "src/main.rs");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn resolve_dir_rejects_paths_outside_root() {
+        let dir = temp_dir();
+        assert!(resolve_dir(&dir, "src").is_ok());
+        assert!(resolve_dir(&dir, "").is_ok());
+        assert!(resolve_dir(&dir, "..").is_err());
+        assert!(resolve_dir(&dir, "README.md").is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rank_files_prefers_name_matches_and_truncates() {
+        let files = vec![
+            "apps/web/index.ts".to_string(),
+            "src/App.tsx".to_string(),
+            "docs/readme.md".to_string(),
+        ];
+        let matches = rank_files(&files, "app", 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, "src/App.tsx");
+        assert_eq!(matches[0].name, "App.tsx");
+        assert!(rank_files(&files, "zzz", 10).is_empty());
+    }
+}
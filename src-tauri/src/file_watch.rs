@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use git2::{DiffOptions, Patch};
+use notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use tokio::sync::{mpsc, watch, Mutex};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::file_io::{file_version, FileVersion};
+use crate::remote_backend;
+use crate::state::AppState;
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(150);
+/// Files above this size get change events without a diff.
+const MAX_DIFF_BYTES: u64 = 512 * 1024;
+
+struct FileWatch {
+    subscribers: usize,
+    shutdown: watch::Sender<bool>,
+}
+
+/// Open-file watchers keyed by workspace and relative path. Several panes can
+/// watch the same file; the watcher stops when the last one unsubscribes.
+#[derive(Default)]
+pub(crate) struct FileWatches {
+    watches: Mutex<HashMap<(String, String), FileWatch>>,
+}
+
+impl FileWatches {
+    /// Stop every watcher belonging to a workspace, e.g. when it is removed.
+    pub(crate) async fn stop_workspace(&self, workspace_id: &str) {
+        let mut watches = self.watches.lock().await;
+        watches.retain(|(id, _), watch| {
+            if id == workspace_id {
+                let _ = watch.shutdown.send(true);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Resolve a workspace-relative file path that may not exist yet.
+fn resolve_watch_path(root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let candidate = canonical_root.join(relative);
+    let file_name = candidate
+        .file_name()
+        .ok_or_else(|| "Invalid file path".to_string())?
+        .to_owned();
+    let parent = candidate
+        .parent()
+        .ok_or_else(|| "Invalid file path".to_string())?
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve file directory: {err}"))?;
+    if !parent.starts_with(&canonical_root) {
+        return Err("Invalid file path".to_string());
+    }
+    Ok(parent.join(file_name))
+}
+
+fn read_for_diff(path: &Path) -> Option<Vec<u8>> {
+    let size = std::fs::metadata(path).ok()?.len();
+    if size > MAX_DIFF_BYTES {
+        return None;
+    }
+    std::fs::read(path).ok()
+}
+
+/// Unified diff between two versions of a file; `None` for binary content.
+fn unified_diff(path: &str, old: &[u8], new: &[u8]) -> Option<String> {
+    let mut options = DiffOptions::new();
+    options.context_lines(3);
+    let path = Path::new(path);
+    let mut patch =
+        Patch::from_buffers(old, Some(path), new, Some(path), Some(&mut options)).ok()?;
+    if patch.delta().flags().is_binary() {
+        return None;
+    }
+    let buffer = patch.to_buf().ok()?;
+    buffer.as_str().map(|text| text.to_string())
+}
+
+fn emit_file_changed(
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    path: &str,
+    version: &FileVersion,
+    diff: Option<String>,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": "file/changed",
+            "params": {
+                "path": path,
+                "version": version,
+                "diff": diff,
+            },
+        }),
+    });
+}
+
+async fn run_file_watch(
+    workspace_id: String,
+    relative: String,
+    absolute: PathBuf,
+    event_sink: TauriEventSink,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<DebounceEventResult>();
+    let mut debouncer = match new_debouncer(WATCH_DEBOUNCE, move |result| {
+        let _ = tx.send(result);
+    }) {
+        Ok(debouncer) => debouncer,
+        Err(error) => {
+            tracing::warn!("[file_watch] Failed to create debouncer: {error}");
+            return;
+        }
+    };
+    // Watch the directory so editors that save by renaming over the file are seen.
+    let Some(dir) = absolute.parent() else {
+        return;
+    };
+    if let Err(error) = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive) {
+        tracing::warn!("[file_watch] Failed to watch {}: {error}", dir.display());
+        return;
+    }
+
+    let mut last_version = file_version(&absolute).ok();
+    let mut last_content = read_for_diff(&absolute);
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => break,
+            result = rx.recv() => {
+                let Some(result) = result else {
+                    break;
+                };
+                let events = match result {
+                    Ok(events) => events,
+                    Err(error) => {
+                        tracing::debug!("[file_watch] watcher error: {error:?}");
+                        continue;
+                    }
+                };
+                if !events.iter().any(|event| event.path == absolute) {
+                    continue;
+                }
+                let Ok(version) = file_version(&absolute) else {
+                    continue;
+                };
+                if last_version.as_ref().is_some_and(|last| {
+                    last.exists == version.exists && last.hash == version.hash
+                }) {
+                    continue;
+                }
+                let content = read_for_diff(&absolute);
+                let diff = match (&last_content, &content) {
+                    (Some(old), Some(new)) => unified_diff(&relative, old, new),
+                    _ => None,
+                };
+                emit_file_changed(&event_sink, &workspace_id, &relative, &version, diff);
+                last_version = Some(version);
+                last_content = content;
+            }
+        }
+    }
+}
+
+/// Subscribe to `file/changed` events for a workspace file. Returns the file's
+/// current version so the caller can tell later events apart from its own read.
+#[tauri::command]
+pub(crate) async fn file_watch(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<FileVersion, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "file_watch",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    let absolute = resolve_watch_path(&root, &path)?;
+    let version = file_version(&absolute)?;
+
+    let mut watches = state.file_watches.watches.lock().await;
+    let key = (workspace_id.clone(), path.clone());
+    if let Some(existing) = watches.get_mut(&key) {
+        existing.subscribers += 1;
+        return Ok(version);
+    }
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    watches.insert(
+        key,
+        FileWatch {
+            subscribers: 1,
+            shutdown: shutdown_tx,
+        },
+    );
+    tokio::spawn(run_file_watch(
+        workspace_id,
+        path,
+        absolute,
+        TauriEventSink::new(app),
+        shutdown_rx,
+    ));
+    Ok(version)
+}
+
+/// Drop one `file_watch` subscription.
+#[tauri::command]
+pub(crate) async fn file_unwatch(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "file_unwatch",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut watches = state.file_watches.watches.lock().await;
+    let key = (workspace_id, path);
+    let Some(existing) = watches.get_mut(&key) else {
+        return Ok(());
+    };
+    existing.subscribers = existing.subscribers.saturating_sub(1);
+    if existing.subscribers == 0 {
+        if let Some(watch) = watches.remove(&key) {
+            let _ = watch.shutdown.send(true);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_reports_changed_lines() {
+        let diff = unified_diff("notes.md", b"one\ntwo\n", b"one\nthree\n").unwrap();
+        assert!(diff.contains("-two"));
+        assert!(diff.contains("+three"));
+        assert!(diff.contains("notes.md"));
+        assert!(unified_diff("blob.bin", b"\0\x01\x02", b"\0\x03").is_none());
+    }
+
+    #[test]
+    fn resolve_watch_path_allows_missing_files_inside_root() {
+        let root = std::env::temp_dir().join(format!("file-watch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let resolved = resolve_watch_path(&root, "src/new.rs").unwrap();
+        assert!(resolved.ends_with("src/new.rs"));
+        assert!(resolve_watch_path(&root, "../outside.rs").is_err());
+        assert!(resolve_watch_path(&root, "missing/dir.rs").is_err());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -4,7 +4,8 @@ use serde_json::json;
 use tauri::{AppHandle, State};
 
 use crate::claude_home;
-use crate::file_io::TextFileResponse;
+use crate::file_io::{FileVersion, FileWriteResult, LineRange, TextFileResponse};
+use crate::file_merge::{merge_three_way, ThreeWayMerge};
 use crate::file_ops::{read_with_policy, write_with_policy};
 use crate::file_policy::{policy_for, FileKind, FileScope};
 use crate::remote_backend;
@@ -23,7 +24,7 @@ async fn resolve_workspace_root(workspace_id: &str, state: &AppState) -> Result<
     Ok(PathBuf::from(&entry.path))
 }
 
-async fn resolve_root(
+pub(crate) async fn resolve_root(
     scope: FileScope,
     workspace_id: Option<&str>,
     state: &AppState,
@@ -37,10 +38,11 @@ async fn resolve_root(
     }
 }
 
-async fn file_read_impl(
+pub(crate) async fn file_read_impl(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    range: LineRange,
     state: &AppState,
     app: &AppHandle,
 ) -> Result<TextFileResponse, String> {
@@ -49,7 +51,13 @@ async fn file_read_impl(
             state,
             app.clone(),
             "file_read",
-            json!({ "scope": scope, "kind": kind, "workspaceId": workspace_id }),
+            json!({
+                "scope": scope,
+                "kind": kind,
+                "workspaceId": workspace_id,
+                "offset": range.offset,
+                "limit": range.limit,
+            }),
         )
         .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
@@ -57,19 +65,20 @@ async fn file_read_impl(
 
     let policy = policy_for(scope, kind)?;
     let root = resolve_root(scope, workspace_id.as_deref(), state).await?;
-    read_with_policy(&root, policy)
+    read_with_policy(&root, policy, range)
 }
 
-async fn file_write_impl(
+pub(crate) async fn file_write_impl(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
     content: String,
+    expected_version: Option<FileVersion>,
     state: &AppState,
     app: &AppHandle,
-) -> Result<(), String> {
+) -> Result<FileWriteResult, String> {
     if remote_backend::is_remote_mode(state).await {
-        remote_backend::call_remote(
+        let response = remote_backend::call_remote(
             state,
             app.clone(),
             "file_write",
@@ -78,36 +87,70 @@ async fn file_write_impl(
                 "kind": kind,
                 "workspaceId": workspace_id,
                 "content": content,
+                "expectedVersion": expected_version,
             }),
         )
         .await?;
-        return Ok(());
+        return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let policy = policy_for(scope, kind)?;
-    let root = resolve_root(scope, workspace_id.as_deref(), state).await?;
-    write_with_policy(&root, policy, &content)
+    let result = match policy_for(scope, kind) {
+        Ok(policy) => match resolve_root(scope, workspace_id.as_deref(), state).await {
+            Ok(root) => write_with_policy(&root, policy, &content, expected_version.as_ref()),
+            Err(error) => Err(error),
+        },
+        Err(error) => Err(error),
+    };
+    crate::audit::record(
+        "file_write",
+        workspace_id.as_deref(),
+        json!({ "scope": scope, "kind": kind, "bytes": content.len() }),
+        &result,
+    );
+    result
 }
 
+/// Read a policy file, optionally limited to `limit` lines starting at line
+/// `offset` (zero-based). Binary files come back with `kind: "binary"`.
 #[tauri::command]
 pub(crate) async fn file_read(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<TextFileResponse, String> {
-    file_read_impl(scope, kind, workspace_id, &*state, &app).await
+    let range = LineRange { offset, limit };
+    file_read_impl(scope, kind, workspace_id, range, &*state, &app).await
 }
 
+/// Write a policy file. When `expected_version` (from `file_read`) is given and
+/// the file changed on disk since, nothing is written and a conflict carrying
+/// the current contents is returned instead.
 #[tauri::command]
 pub(crate) async fn file_write(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
     content: String,
+    expected_version: Option<FileVersion>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
-    file_write_impl(scope, kind, workspace_id, content, &*state, &app).await
+) -> Result<FileWriteResult, String> {
+    file_write_impl(scope, kind, workspace_id, content, expected_version, &*state, &app).await
+}
+
+/// Three-way merge of an editor buffer (`ours`) with what is now on disk
+/// (`theirs`), relative to the content originally read (`base`).
+#[tauri::command]
+pub(crate) async fn file_merge_three_way(
+    base: String,
+    ours: String,
+    theirs: String,
+) -> Result<ThreeWayMerge, String> {
+    tokio::task::spawn_blocking(move || merge_three_way(&base, &ours, &theirs))
+        .await
+        .map_err(|_| "merge failed".to_string())
 }
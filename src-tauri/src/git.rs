@@ -2,20 +2,29 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
+use git2::{
+    BlameOptions, BranchType, DescribeFormatOptions, DescribeOptions, DiffOptions, ErrorCode,
+    Repository, Sort, Status, StatusOptions,
+};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use serde_json::json;
 use tauri::State;
 use tokio::process::Command;
 
+use crate::ci_status::{classify_check, overall_status};
+use crate::git_status_cache::StatusKey;
 use crate::git_utils::{
     checkout_branch, commit_to_entry, diff_patch_to_string, diff_stats_for_path,
     image_mime_type, list_git_roots as scan_git_roots, parse_github_repo, resolve_git_root,
 };
 use crate::state::AppState;
 use crate::types::{
-    BranchInfo, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
-    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
-    GitHubPullRequestsResponse, GitLogResponse,
+    BranchInfo, GitBlame, GitBlameCommit, GitBlameLine, GitCommitDiff, GitFileDiff,
+    GitFileStatus, GitHubIssue, GitHubIssuesResponse,
+    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff, GitHubReviewThread,
+    GitHubReviewThreadComment,
+    GitHubPullRequestsResponse, GitLogEntry, GitLogResponse, GitTag, WorkspaceEntry, WorktreePullRequest,
+    WorktreeRebaseResult, WorktreeSummary,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
@@ -182,7 +191,7 @@ async fn push_with_upstream(repo_root: &Path) -> Result<(), String> {
     run_git_command(repo_root, &["push"]).await
 }
 
-fn status_for_index(status: Status) -> Option<&'static str> {
+pub(crate) fn status_for_index(status: Status) -> Option<&'static str> {
     if status.contains(Status::INDEX_NEW) {
         Some("A")
     } else if status.contains(Status::INDEX_MODIFIED) {
@@ -198,7 +207,7 @@ fn status_for_index(status: Status) -> Option<&'static str> {
     }
 }
 
-fn status_for_workdir(status: Status) -> Option<&'static str> {
+pub(crate) fn status_for_workdir(status: Status) -> Option<&'static str> {
     if status.contains(Status::WT_NEW) {
         Some("A")
     } else if status.contains(Status::WT_MODIFIED) {
@@ -225,8 +234,52 @@ fn status_for_delta(status: git2::Delta) -> &'static str {
     }
 }
 
-fn build_combined_diff(diff: &git2::Diff) -> String {
-    let mut combined_diff = String::new();
+/// A workspace diff for prompts, minus files matching the noise patterns.
+#[derive(Debug, Default)]
+pub(crate) struct PromptDiff {
+    pub(crate) diff: String,
+    /// Changed paths left out because they matched an exclude pattern.
+    pub(crate) excluded: Vec<String>,
+}
+
+impl PromptDiff {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.diff.trim().is_empty() && self.excluded.is_empty()
+    }
+
+    /// The diff followed by a note naming the excluded files, if any.
+    pub(crate) fn to_prompt_text(&self) -> String {
+        if self.excluded.is_empty() {
+            return self.diff.clone();
+        }
+        let mut text = self.diff.clone();
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(&format!(
+            "(Also changed, but left out of this diff as generated or vendored files: {})",
+            self.excluded.join(", ")
+        ));
+        text
+    }
+}
+
+fn prompt_diff_matcher(repo_root: &Path, patterns: &[String]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(repo_root);
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            continue;
+        }
+        if let Err(error) = builder.add_line(None, pattern) {
+            tracing::warn!("[git] Ignoring prompt diff exclude {pattern:?}: {error}");
+        }
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn build_combined_diff(diff: &git2::Diff, excludes: &Gitignore) -> PromptDiff {
+    let mut result = PromptDiff::default();
     for (index, delta) in diff.deltas().enumerate() {
         let path = delta
             .new_file()
@@ -235,6 +288,15 @@ fn build_combined_diff(diff: &git2::Diff) -> String {
         let Some(path) = path else {
             continue;
         };
+        if excludes
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+        {
+            result
+                .excluded
+                .push(normalize_git_path(&path.to_string_lossy()));
+            continue;
+        }
         let patch = match git2::Patch::from_diff(diff, index) {
             Ok(patch) => patch,
             Err(_) => continue,
@@ -249,17 +311,18 @@ fn build_combined_diff(diff: &git2::Diff) -> String {
         if content.trim().is_empty() {
             continue;
         }
-        if !combined_diff.is_empty() {
-            combined_diff.push_str("\n\n");
+        if !result.diff.is_empty() {
+            result.diff.push_str("\n\n");
         }
-        combined_diff.push_str(&format!("=== {} ===\n", path.display()));
-        combined_diff.push_str(&content);
+        result.diff.push_str(&format!("=== {} ===\n", path.display()));
+        result.diff.push_str(&content);
     }
-    combined_diff
+    result
 }
 
-fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
+fn collect_workspace_diff(repo_root: &Path, excludes: &[String]) -> Result<PromptDiff, String> {
     let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let excludes = prompt_diff_matcher(repo_root, excludes);
     let head_tree = repo
         .head()
         .ok()
@@ -275,8 +338,8 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
             .diff_tree_to_index(None, Some(&index), Some(&mut options))
             .map_err(|e| e.to_string())?,
     };
-    let combined_diff = build_combined_diff(&diff);
-    if !combined_diff.trim().is_empty() {
+    let combined_diff = build_combined_diff(&diff, &excludes);
+    if !combined_diff.is_empty() {
         return Ok(combined_diff);
     }
 
@@ -293,10 +356,10 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
             .diff_tree_to_workdir_with_index(None, Some(&mut options))
             .map_err(|e| e.to_string())?,
     };
-    Ok(build_combined_diff(&diff))
+    Ok(build_combined_diff(&diff, &excludes))
 }
 
-fn github_repo_from_path(path: &Path) -> Result<String, String> {
+pub(crate) fn github_repo_from_path(path: &Path) -> Result<String, String> {
     let repo = Repository::open(path).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
@@ -424,6 +487,11 @@ pub(crate) async fn get_git_status(
 
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let key = StatusKey::for_repo(&repo);
+    let generation = match state.git_status_cache.lookup(&workspace_id, &repo_root, &key) {
+        Ok(cached) => return Ok(cached),
+        Err(generation) => generation,
+    };
 
     let branch_name = repo
         .head()
@@ -531,14 +599,18 @@ pub(crate) async fn get_git_status(
         }
     }
 
-    Ok(json!({
+    let status = json!({
         "branchName": branch_name,
         "files": files,
         "stagedFiles": staged_files,
         "unstagedFiles": unstaged_files,
         "totalAdditions": total_additions,
         "totalDeletions": total_deletions,
-    }))
+    });
+    state
+        .git_status_cache
+        .store(&workspace_id, key, generation, status.clone());
+    Ok(status)
 }
 
 #[tauri::command]
@@ -662,6 +734,128 @@ pub(crate) async fn commit_git(
     run_git_command(&repo_root, &["commit", "-m", &message]).await
 }
 
+/// Whether HEAD is already on a remote-tracking branch, so rewriting it would
+/// diverge from history others may have pulled.
+fn head_is_published(repo: &Repository) -> Result<bool, String> {
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let head_oid = head.id();
+    for branch in repo
+        .branches(Some(BranchType::Remote))
+        .map_err(|e| e.to_string())?
+    {
+        let (branch, _) = branch.map_err(|e| e.to_string())?;
+        // `origin/HEAD` is symbolic and has no direct target.
+        let Some(target) = branch.get().target() else {
+            continue;
+        };
+        if target == head_oid
+            || repo
+                .graph_descendant_of(target, head_oid)
+                .map_err(|e| e.to_string())?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Refuse to rewrite the last commit mid-merge/rebase, or once it is pushed
+/// unless the caller forces it.
+fn ensure_head_rewritable(repo: &Repository, force: bool) -> Result<(), String> {
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err("Finish the merge, rebase or cherry-pick in progress first.".to_string());
+    }
+    if !force && head_is_published(repo)? {
+        return Err(
+            "The last commit is already pushed. Rewriting it needs a force push afterwards."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn amend_git_commit(
+    workspace_id: String,
+    message: Option<String>,
+    include_staged: Option<bool>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        ensure_head_rewritable(&repo, force.unwrap_or(false))?;
+    }
+
+    let message = message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    let mut args = vec!["commit", "--amend"];
+    // `--only` without paths leaves the index out and just rewords.
+    if !include_staged.unwrap_or(true) {
+        args.push("--only");
+    }
+    match message.as_deref() {
+        Some(message) => args.extend(["-m", message]),
+        None => args.push("--no-edit"),
+    }
+    let result = run_git_command(&repo_root, &args).await;
+    crate::audit::record(
+        "amend_git_commit",
+        Some(&workspace_id),
+        json!({ "reworded": message.is_some(), "force": force.unwrap_or(false) }),
+        &result,
+    );
+    result
+}
+
+#[tauri::command]
+pub(crate) async fn undo_last_commit(
+    workspace_id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        ensure_head_rewritable(&repo, force.unwrap_or(false))?;
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|e| e.to_string())?;
+        if head.parent_count() == 0 {
+            return Err("The first commit in a repository can't be undone.".to_string());
+        }
+    }
+    // A soft reset keeps the commit's changes staged.
+    let result = run_git_command(&repo_root, &["reset", "--soft", "HEAD~1"]).await;
+    crate::audit::record(
+        "undo_last_commit",
+        Some(&workspace_id),
+        json!({ "force": force.unwrap_or(false) }),
+        &result,
+    );
+    result
+}
+
 #[tauri::command]
 pub(crate) async fn push_git(
     workspace_id: String,
@@ -674,7 +868,9 @@ pub(crate) async fn push_git(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    push_with_upstream(&repo_root).await
+    let result = push_with_upstream(&repo_root).await;
+    crate::audit::record("push_git", Some(&workspace_id), serde_json::Value::Null, &result);
+    result
 }
 
 #[tauri::command]
@@ -730,7 +926,7 @@ pub(crate) async fn list_git_roots(
 pub(crate) async fn get_workspace_diff(
     workspace_id: &str,
     state: &State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<PromptDiff, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(workspace_id)
@@ -738,8 +934,9 @@ pub(crate) async fn get_workspace_diff(
         .clone();
     drop(workspaces);
 
+    let excludes = state.app_settings.lock().await.prompt_diff_excludes.clone();
     let repo_root = resolve_git_root(&entry)?;
-    collect_workspace_diff(&repo_root)
+    collect_workspace_diff(&repo_root, &excludes)
 }
 
 #[tauri::command]
@@ -973,6 +1170,181 @@ pub(crate) async fn get_git_log(
     })
 }
 
+/// Blame `path` at `rev`, or the working tree copy (uncommitted lines
+/// included) when no revision is given.
+fn blame_path(repo: &Repository, path: &str, rev: Option<&str>) -> Result<GitBlame, String> {
+    let path = normalize_git_path(path);
+    let target = Path::new(&path);
+    let mut options = BlameOptions::new();
+    let content = match rev {
+        Some(rev) => {
+            let commit = repo
+                .revparse_single(rev)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|e| e.to_string())?;
+            options.newest_commit(commit.id());
+            let tree = commit.tree().map_err(|e| e.to_string())?;
+            let entry = tree.get_path(target).map_err(|e| e.to_string())?;
+            let blob = repo.find_blob(entry.id()).map_err(|e| e.to_string())?;
+            blob.content().to_vec()
+        }
+        None => {
+            let workdir = repo.workdir().ok_or("Repository has no working tree")?;
+            fs::read(workdir.join(target)).map_err(|e| e.to_string())?
+        }
+    };
+    if content.contains(&0) {
+        return Err("Cannot blame a binary file".to_string());
+    }
+
+    let committed = match repo.blame_file(target, Some(&mut options)) {
+        Ok(blame) => Some(blame),
+        // A file that was never committed has no history to blame yet.
+        Err(err) if rev.is_none() && err.code() == ErrorCode::NotFound => None,
+        Err(err) => return Err(err.to_string()),
+    };
+    let with_buffer;
+    let blame = match committed.as_ref() {
+        Some(committed) if rev.is_none() => {
+            with_buffer = committed
+                .blame_buffer(&content)
+                .map_err(|e| e.to_string())?;
+            Some(&with_buffer)
+        }
+        other => other,
+    };
+
+    let text = String::from_utf8_lossy(&content);
+    let mut lines = Vec::new();
+    let mut shas: Vec<git2::Oid> = Vec::new();
+    for (index, content) in text.lines().enumerate() {
+        let line = index + 1;
+        let oid = blame
+            .and_then(|blame| blame.get_line(line))
+            .map(|hunk| hunk.final_commit_id())
+            .filter(|oid| !oid.is_zero());
+        if let Some(oid) = oid {
+            if !shas.contains(&oid) {
+                shas.push(oid);
+            }
+        }
+        lines.push(GitBlameLine {
+            line,
+            sha: oid.map(|oid| oid.to_string()),
+            content: content.to_string(),
+        });
+    }
+
+    let mut commits = Vec::new();
+    for oid in shas {
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let author = commit.author();
+        commits.push(GitBlameCommit {
+            sha: oid.to_string(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            author: author.name().unwrap_or("").to_string(),
+            email: author.email().unwrap_or("").to_string(),
+            timestamp: commit.time().seconds(),
+        });
+    }
+    commits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    Ok(GitBlame {
+        path,
+        rev: rev.map(|rev| rev.to_string()),
+        lines,
+        commits,
+    })
+}
+
+/// Commits reachable from HEAD that changed `path`, newest first. Like
+/// `git log -- <path>`, merges are skipped unless they differ from every parent.
+fn file_history(
+    repo: &Repository,
+    path: &str,
+    limit: usize,
+) -> Result<Vec<GitLogEntry>, String> {
+    let path = normalize_git_path(path);
+    let target = Path::new(&path);
+    let entry_id = |commit: &git2::Commit| {
+        commit
+            .tree()
+            .ok()
+            .and_then(|tree| tree.get_path(target).ok())
+            .map(|entry| entry.id())
+    };
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push_head().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for oid_result in revwalk {
+        if entries.len() >= limit {
+            break;
+        }
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let current = entry_id(&commit);
+        let changed = if commit.parent_count() == 0 {
+            current.is_some()
+        } else {
+            commit.parents().all(|parent| entry_id(&parent) != current)
+        };
+        if changed {
+            entries.push(commit_to_entry(commit));
+        }
+    }
+    Ok(entries)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_blame(
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitBlame, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        blame_path(&repo, &path, rev.as_deref().filter(|rev| !rev.trim().is_empty()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_log(
+    workspace_id: String,
+    path: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitLogEntry>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        file_history(&repo, &path, limit.unwrap_or(100))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 pub(crate) async fn get_git_commit_diff(
     workspace_id: String,
@@ -1353,6 +1725,148 @@ pub(crate) async fn get_github_pull_request_comments(
     Ok(comments)
 }
 
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!) { \
+repository(owner: $owner, name: $name) { pullRequest(number: $number) { \
+reviewThreads(first: 100) { nodes { id isResolved isOutdated path line originalLine \
+comments(first: 50) { nodes { body url diffHunk author { login } } } } } } } }";
+
+fn gh_failure_detail(output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        "GitHub CLI command failed.".to_string()
+    } else {
+        detail.to_string()
+    }
+}
+
+/// Keep the unresolved threads from a `reviewThreads` GraphQL response.
+fn parse_review_threads(response: &serde_json::Value) -> Vec<GitHubReviewThread> {
+    let nodes = response
+        .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_default();
+    nodes
+        .iter()
+        .filter(|node| !node.get("isResolved").and_then(|v| v.as_bool()).unwrap_or(false))
+        .filter_map(|node| {
+            let comment_nodes = node
+                .pointer("/comments/nodes")
+                .and_then(|value| value.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let first = comment_nodes.first()?;
+            let comments = comment_nodes
+                .iter()
+                .map(|comment| GitHubReviewThreadComment {
+                    author: comment
+                        .pointer("/author/login")
+                        .and_then(|v| v.as_str())
+                        .map(|login| login.to_string()),
+                    body: comment
+                        .get("body")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                })
+                .collect();
+            Some(GitHubReviewThread {
+                id: node.get("id")?.as_str()?.to_string(),
+                path: node.get("path")?.as_str()?.to_string(),
+                line: node
+                    .get("line")
+                    .and_then(|v| v.as_u64())
+                    .or_else(|| node.get("originalLine").and_then(|v| v.as_u64())),
+                diff_hunk: first
+                    .get("diffHunk")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                url: first
+                    .get("url")
+                    .and_then(|v| v.as_str())
+                    .map(|url| url.to_string()),
+                is_outdated: node
+                    .get("isOutdated")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                comments,
+            })
+        })
+        .collect()
+}
+
+/// Fetch unresolved review threads for a PR, defaulting to the current branch's PR.
+///
+/// Returns the PR number alongside the threads.
+pub(crate) async fn get_unresolved_review_threads(
+    workspace_id: &str,
+    pr_number: Option<u64>,
+    state: &State<'_, AppState>,
+) -> Result<(u64, Vec<GitHubReviewThread>), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let (owner, name) = repo_name
+        .split_once('/')
+        .ok_or_else(|| format!("Unexpected GitHub repo name: {repo_name}"))?;
+
+    let pr_number = match pr_number {
+        Some(number) => number,
+        None => {
+            let output = Command::new("gh")
+                .args(["pr", "view", "--json", "number", "--jq", ".number"])
+                .current_dir(&repo_root)
+                .output()
+                .await
+                .map_err(|e| format!("Failed to run gh: {e}"))?;
+            if !output.status.success() {
+                return Err(gh_failure_detail(&output));
+            }
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| "No pull request found for the current branch.".to_string())?
+        }
+    };
+
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "graphql",
+            "-f",
+            &format!("query={REVIEW_THREADS_QUERY}"),
+            "-f",
+            &format!("owner={owner}"),
+            "-f",
+            &format!("name={name}"),
+            "-F",
+            &format!("number={pr_number}"),
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        return Err(gh_failure_detail(&output));
+    }
+    let response: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    Ok((pr_number, parse_review_threads(&response)))
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
@@ -1423,6 +1937,601 @@ pub(crate) async fn create_git_branch(
     checkout_branch(&repo, &name).map_err(|e| e.to_string())
 }
 
+fn collect_tags(repo: &Repository) -> Result<Vec<GitTag>, String> {
+    let names = repo.tag_names(None).map_err(|e| e.to_string())?;
+    let mut tags = Vec::new();
+    for name in names.iter().flatten() {
+        let Ok(reference) = repo.find_reference(&format!("refs/tags/{name}")) else {
+            continue;
+        };
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+        let annotation = reference.peel_to_tag().ok();
+        let message = annotation
+            .as_ref()
+            .and_then(|tag| tag.message())
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty());
+        let timestamp = annotation
+            .as_ref()
+            .and_then(|tag| tag.tagger())
+            .map(|tagger| tagger.when().seconds())
+            .unwrap_or_else(|| commit.time().seconds());
+        tags.push(GitTag {
+            name: name.to_string(),
+            sha: commit.id().to_string(),
+            message,
+            timestamp,
+        });
+    }
+    tags.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.name.cmp(&a.name)));
+    Ok(tags)
+}
+
+/// The nearest tag reachable from HEAD, if any.
+fn latest_reachable_tag(repo: &Repository) -> Option<String> {
+    let mut options = DescribeOptions::new();
+    options.describe_tags();
+    let describe = repo.describe(&options).ok()?;
+    let mut format = DescribeFormatOptions::new();
+    format.abbreviated_size(0);
+    describe.format(Some(&format)).ok()
+}
+
+/// Commits on HEAD since `since` (or the nearest tag), newest first.
+fn commits_since_tag(
+    repo: &Repository,
+    since: Option<&str>,
+    limit: usize,
+) -> Result<(Option<String>, Vec<GitLogEntry>), String> {
+    let since = since
+        .map(|tag| tag.to_string())
+        .or_else(|| latest_reachable_tag(repo));
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push_head().map_err(|e| e.to_string())?;
+    if let Some(tag) = since.as_deref() {
+        let oid = repo
+            .revparse_single(&format!("refs/tags/{tag}"))
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| format!("Tag not found: {tag}"))?
+            .id();
+        revwalk.hide(oid).map_err(|e| e.to_string())?;
+    }
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(|e| e.to_string())?;
+    let mut entries = Vec::new();
+    for oid_result in revwalk.take(limit) {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        entries.push(commit_to_entry(commit));
+    }
+    Ok((since, entries))
+}
+
+/// Helper for release note generation: the previous tag and the commits after it.
+pub(crate) async fn get_commits_since_tag(
+    workspace_id: &str,
+    since: Option<&str>,
+    state: &State<'_, AppState>,
+) -> Result<(Option<String>, Vec<GitLogEntry>), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    commits_since_tag(&repo, since, 200)
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_tags(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitTag>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    collect_tags(&repo)
+}
+
+/// Tag `target` (HEAD by default). A message makes it an annotated tag.
+#[tauri::command]
+pub(crate) async fn create_git_tag(
+    workspace_id: String,
+    name: String,
+    message: Option<String>,
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitTag, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Tag name is required.".to_string());
+    }
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let target = target
+        .as_deref()
+        .map(str::trim)
+        .filter(|target| !target.is_empty())
+        .unwrap_or("HEAD");
+    let object = repo
+        .revparse_single(target)
+        .and_then(|object| object.peel(git2::ObjectType::Commit))
+        .map_err(|e| e.to_string())?;
+    let message = message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    let result = match message.as_deref() {
+        Some(message) => repo
+            .signature()
+            .and_then(|tagger| repo.tag(&name, &object, &tagger, message, false))
+            .map(|_| ()),
+        None => repo.tag_lightweight(&name, &object, false).map(|_| ()),
+    }
+    .map_err(|e| e.to_string());
+    crate::audit::record(
+        "create_git_tag",
+        Some(&workspace_id),
+        json!({ "name": name, "target": target, "annotated": message.is_some() }),
+        &result,
+    );
+    result?;
+    collect_tags(&repo)?
+        .into_iter()
+        .find(|tag| tag.name == name)
+        .ok_or_else(|| format!("Tag not found: {name}"))
+}
+
+/// Push a single tag to the current branch's upstream remote (or `origin`).
+#[tauri::command]
+pub(crate) async fn push_git_tag(
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let remote = match remote.filter(|remote| !remote.trim().is_empty()) {
+        Some(remote) => remote,
+        None => upstream_remote_and_branch(&repo_root)?
+            .map(|(remote, _)| remote)
+            .unwrap_or_else(|| "origin".to_string()),
+    };
+    let refspec = format!("refs/tags/{name}");
+    let result = run_git_command(&repo_root, &["push", remote.as_str(), refspec.as_str()]).await;
+    crate::audit::record(
+        "push_git_tag",
+        Some(&workspace_id),
+        json!({ "name": name, "remote": remote }),
+        &result,
+    );
+    result
+}
+
+fn conflict_entry_path(conflict: &git2::IndexConflict) -> Option<String> {
+    conflict
+        .our
+        .as_ref()
+        .or(conflict.their.as_ref())
+        .or(conflict.ancestor.as_ref())
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+}
+
+/// Compare the checked-out branch (including uncommitted work) against `base_branch`.
+fn summarize_against_base(
+    repo: &Repository,
+    workspace_id: &str,
+    branch: &str,
+    base_branch: &str,
+) -> Result<WorktreeSummary, String> {
+    let head_commit = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let base_commit = repo
+        .find_branch(base_branch, BranchType::Local)
+        .and_then(|branch| branch.get().peel_to_commit())
+        .map_err(|_| format!("Base branch not found: {base_branch}"))?;
+    let (ahead, behind) = repo
+        .graph_ahead_behind(head_commit.id(), base_commit.id())
+        .map_err(|e| e.to_string())?;
+    let merge_base = repo
+        .merge_base(head_commit.id(), base_commit.id())
+        .map_err(|e| e.to_string())?;
+    let base_tree = repo
+        .find_commit(merge_base)
+        .and_then(|commit| commit.tree())
+        .map_err(|e| e.to_string())?;
+
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let stats = diff.stats().map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let (additions, deletions) = git2::Patch::from_diff(&diff, index)
+            .ok()
+            .flatten()
+            .and_then(|patch| patch.line_stats().ok())
+            .map(|(_, additions, deletions)| (additions as i64, deletions as i64))
+            .unwrap_or((0, 0));
+        files.push(GitFileStatus {
+            path: normalize_git_path(&path.to_string_lossy()),
+            status: status_for_delta(delta.status()).to_string(),
+            additions,
+            deletions,
+        });
+    }
+
+    // Only committed work takes part in the merge prediction.
+    let merged = repo
+        .merge_commits(&base_commit, &head_commit, None)
+        .map_err(|e| e.to_string())?;
+    let mut conflict_paths: Vec<String> = Vec::new();
+    if merged.has_conflicts() {
+        for conflict in merged.conflicts().map_err(|e| e.to_string())? {
+            let conflict = conflict.map_err(|e| e.to_string())?;
+            if let Some(path) = conflict_entry_path(&conflict) {
+                conflict_paths.push(path);
+            }
+        }
+        conflict_paths.sort();
+        conflict_paths.dedup();
+    }
+
+    Ok(WorktreeSummary {
+        workspace_id: workspace_id.to_string(),
+        branch: branch.to_string(),
+        base_branch: base_branch.to_string(),
+        files,
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+        ahead,
+        behind,
+        conflict_paths,
+        pull_request: None,
+    })
+}
+
+async fn fetch_pull_request_status(repo_root: &Path, branch: &str) -> Option<WorktreePullRequest> {
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "view",
+            branch,
+            "--json",
+            "number,url,state,statusCheckRollup",
+        ])
+        .current_dir(repo_root)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let ci_status = value
+        .get("statusCheckRollup")
+        .and_then(|value| value.as_array())
+        .and_then(|checks| overall_status(checks.iter().map(classify_check)));
+    Some(WorktreePullRequest {
+        number: value.get("number")?.as_u64()?,
+        url: value.get("url")?.as_str()?.to_string(),
+        state: value
+            .get("state")
+            .and_then(|value| value.as_str())
+            .unwrap_or("OPEN")
+            .to_string(),
+        ci_status,
+    })
+}
+
+async fn worktree_with_parent(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<(WorkspaceEntry, WorkspaceEntry), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    if !entry.kind.is_worktree() {
+        return Err("Workspace is not a worktree.".to_string());
+    }
+    let parent = entry
+        .parent_id
+        .as_ref()
+        .and_then(|parent_id| workspaces.get(parent_id))
+        .ok_or("worktree parent not found")?
+        .clone();
+    Ok((entry, parent))
+}
+
+/// The branch the parent workspace has checked out, which worktrees branch from.
+fn parent_base_branch(parent_root: &Path) -> Result<String, String> {
+    let parent_repo = Repository::open(parent_root).map_err(|e| e.to_string())?;
+    let branch = parent_repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(|name| name.to_string()))
+        .ok_or("Parent workspace is not on a branch.")?;
+    Ok(branch)
+}
+
+/// Summarize a worktree against the branch its parent workspace has checked out.
+#[tauri::command]
+pub(crate) async fn get_worktree_summary(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorktreeSummary, String> {
+    let (entry, parent) = worktree_with_parent(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
+    let summary = {
+        let base_branch = parent_base_branch(&parent_root)?;
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let branch = entry
+            .worktree
+            .as_ref()
+            .map(|info| info.branch.clone())
+            .or_else(|| {
+                repo.head()
+                    .ok()
+                    .and_then(|head| head.shorthand().map(|name| name.to_string()))
+            })
+            .unwrap_or_default();
+        summarize_against_base(&repo, &workspace_id, &branch, &base_branch)?
+    };
+
+    let pull_request = fetch_pull_request_status(&repo_root, &summary.branch).await;
+    Ok(WorktreeSummary {
+        pull_request,
+        ..summary
+    })
+}
+
+/// Where to rebase a worktree: the parent's local base branch, or its upstream
+/// when the local branch is only behind it, so a fetch brings in what others
+/// merged without skipping commits the parent hasn't pushed yet.
+fn rebase_target(repo: &Repository, base_branch: &str) -> Result<String, String> {
+    let branch = repo
+        .find_branch(base_branch, BranchType::Local)
+        .map_err(|e| e.to_string())?;
+    let upstream = branch.upstream().ok().and_then(|upstream| {
+        let name = upstream.get().shorthand()?.to_string();
+        Some((name, upstream.get().target()?))
+    });
+    let (Some(local_oid), Some((upstream_name, upstream_oid))) = (branch.get().target(), upstream)
+    else {
+        return Ok(base_branch.to_string());
+    };
+    let only_behind = local_oid != upstream_oid
+        && repo
+            .graph_descendant_of(upstream_oid, local_oid)
+            .map_err(|e| e.to_string())?;
+    Ok(if only_behind {
+        upstream_name
+    } else {
+        base_branch.to_string()
+    })
+}
+
+fn has_tracked_changes(repo: &Repository) -> Result<bool, String> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    Ok(!statuses.is_empty())
+}
+
+/// Describe a stopped rebase: the conflicted paths and the commit being applied.
+fn rebase_conflicts(repo: &Repository, onto: &str) -> Result<WorktreeRebaseResult, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut conflicts = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            let path = normalize_git_path(&String::from_utf8_lossy(&entry.path));
+            if !conflicts.contains(&path) {
+                conflicts.push(path);
+            }
+        }
+    }
+    let stopped_at = repo
+        .find_reference("REBASE_HEAD")
+        .ok()
+        .and_then(|reference| reference.peel_to_commit().ok())
+        .map(commit_to_entry);
+    Ok(WorktreeRebaseResult {
+        status: "conflicts".to_string(),
+        onto: onto.to_string(),
+        conflicts,
+        stopped_at,
+    })
+}
+
+fn rebase_in_progress(repo: &Repository) -> bool {
+    matches!(
+        repo.state(),
+        git2::RepositoryState::Rebase
+            | git2::RepositoryState::RebaseMerge
+            | git2::RepositoryState::RebaseInteractive
+    )
+}
+
+/// Fetch, then rebase a worktree branch onto its parent's base branch.
+/// Conflicts come back as data; the rebase stays stopped until it is
+/// continued or aborted.
+#[tauri::command]
+pub(crate) async fn rebase_worktree_onto_parent(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorktreeRebaseResult, String> {
+    let (entry, parent) = worktree_with_parent(&state, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
+    let base_branch = parent_base_branch(&parent_root)?;
+
+    let has_remotes = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        if rebase_in_progress(&repo) {
+            return Err(
+                "A rebase is already in progress. Continue or abort it first.".to_string(),
+            );
+        }
+        if has_tracked_changes(&repo)? {
+            return Err("Commit or stash the worktree's changes before rebasing.".to_string());
+        }
+        repo.remotes()
+            .map(|remotes| !remotes.is_empty())
+            .unwrap_or(false)
+    };
+    if has_remotes {
+        run_git_command(&repo_root, &["fetch", "--prune"]).await?;
+    }
+
+    let onto = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let onto = rebase_target(&repo, &base_branch)?;
+        let onto_oid = repo
+            .revparse_single(&onto)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|e| e.to_string())?
+            .id();
+        let head_oid = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|e| e.to_string())?
+            .id();
+        let merge_base = repo
+            .merge_base(head_oid, onto_oid)
+            .map_err(|e| e.to_string())?;
+        if merge_base == onto_oid {
+            return Ok(WorktreeRebaseResult {
+                status: "upToDate".to_string(),
+                onto,
+                conflicts: Vec::new(),
+                stopped_at: None,
+            });
+        }
+        onto
+    };
+
+    let result = run_git_command(&repo_root, &["rebase", &onto]).await;
+    crate::audit::record(
+        "rebase_worktree_onto_parent",
+        Some(&workspace_id),
+        json!({ "onto": onto }),
+        &result,
+    );
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    match result {
+        Ok(()) => Ok(WorktreeRebaseResult {
+            status: "rebased".to_string(),
+            onto,
+            conflicts: Vec::new(),
+            stopped_at: None,
+        }),
+        Err(_) if rebase_in_progress(&repo) => rebase_conflicts(&repo, &onto),
+        Err(err) => Err(err),
+    }
+}
+
+/// Resume a stopped worktree rebase once its conflicts are resolved and staged.
+#[tauri::command]
+pub(crate) async fn continue_worktree_rebase(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorktreeRebaseResult, String> {
+    let (entry, parent) = worktree_with_parent(&state, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
+    let onto = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        if !rebase_in_progress(&repo) {
+            return Err("No rebase is in progress.".to_string());
+        }
+        rebase_target(&repo, &parent_base_branch(&parent_root)?)?
+    };
+    // Keep each commit's message instead of opening an editor.
+    let result = run_git_command(
+        &repo_root,
+        &["-c", "core.editor=true", "rebase", "--continue"],
+    )
+    .await;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    match result {
+        Ok(()) => Ok(WorktreeRebaseResult {
+            status: "rebased".to_string(),
+            onto,
+            conflicts: Vec::new(),
+            stopped_at: None,
+        }),
+        Err(_) if rebase_in_progress(&repo) => rebase_conflicts(&repo, &onto),
+        Err(err) => Err(err),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn abort_worktree_rebase(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (entry, _) = worktree_with_parent(&state, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        if !rebase_in_progress(&repo) {
+            return Err("No rebase is in progress.".to_string());
+        }
+    }
+    let result = run_git_command(&repo_root, &["rebase", "--abort"]).await;
+    crate::audit::record(
+        "abort_worktree_rebase",
+        Some(&workspace_id),
+        serde_json::Value::Null,
+        &result,
+    );
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1438,6 +2547,162 @@ mod tests {
         (root, repo)
     }
 
+    fn commit_file(repo: &Repository, root: &Path, name: &str, contents: &str, message: &str) {
+        fs::write(root.join(name), contents).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new(name)).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let signature = git2::Signature::now("Ada", "ada@example.com").expect("signature");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parent_refs = parents.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parent_refs)
+            .expect("commit");
+    }
+
+    #[test]
+    fn blame_path_attributes_committed_and_working_lines() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "notes.txt", "one\ntwo\n", "first");
+        commit_file(&repo, &root, "notes.txt", "one\nTWO\n", "second");
+        fs::write(root.join("notes.txt"), "one\nTWO\nthree\n").expect("edit file");
+
+        let blame = blame_path(&repo, "notes.txt", None).expect("blame working tree");
+        assert_eq!(blame.lines.len(), 3);
+        assert_eq!(blame.commits.len(), 2);
+        let sha_of = |summary: &str| {
+            blame
+                .commits
+                .iter()
+                .find(|commit| commit.summary == summary)
+                .map(|commit| commit.sha.clone())
+        };
+        assert_eq!(blame.lines[0].sha, sha_of("first"));
+        assert_eq!(blame.lines[1].sha, sha_of("second"));
+        assert!(blame.lines[2].sha.is_none());
+
+        let at_first = blame_path(&repo, "notes.txt", Some("HEAD~1")).expect("blame HEAD~1");
+        assert_eq!(at_first.lines[1].content, "two");
+        assert_eq!(at_first.commits.len(), 1);
+    }
+
+    #[test]
+    fn head_is_published_tracks_remote_branches() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "a.txt", "a\n", "first");
+        assert!(!head_is_published(&repo).expect("unpublished"));
+
+        let head = repo.head().expect("head").target().expect("head oid");
+        repo.reference("refs/remotes/origin/main", head, true, "test")
+            .expect("remote ref");
+        assert!(head_is_published(&repo).expect("published"));
+        assert!(ensure_head_rewritable(&repo, false).is_err());
+        assert!(ensure_head_rewritable(&repo, true).is_ok());
+
+        commit_file(&repo, &root, "a.txt", "b\n", "second");
+        assert!(!head_is_published(&repo).expect("ahead of remote"));
+    }
+
+    #[test]
+    fn rebase_target_keeps_unpushed_base_commits() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "a.txt", "a\n", "first");
+        let branch = repo
+            .head()
+            .expect("head")
+            .shorthand()
+            .expect("branch name")
+            .to_string();
+        assert_eq!(rebase_target(&repo, &branch).expect("local target"), branch);
+
+        repo.remote("origin", "https://example.com/repo.git")
+            .expect("add remote");
+        let head = repo.head().expect("head").target().expect("head oid");
+        repo.reference(&format!("refs/remotes/origin/{branch}"), head, true, "test")
+            .expect("remote ref");
+        repo.find_branch(&branch, BranchType::Local)
+            .expect("local branch")
+            .set_upstream(Some(&format!("origin/{branch}")))
+            .expect("set upstream");
+        assert_eq!(
+            rebase_target(&repo, &branch).expect("same as upstream"),
+            branch
+        );
+
+        let first = head;
+        commit_file(&repo, &root, "a.txt", "b\n", "second");
+        assert_eq!(
+            rebase_target(&repo, &branch).expect("unpushed commits"),
+            branch
+        );
+
+        let second = repo.head().expect("head").target().expect("head oid");
+        repo.reference(
+            &format!("refs/remotes/origin/{branch}"),
+            second,
+            true,
+            "test",
+        )
+        .expect("advance remote");
+        repo.reference(&format!("refs/heads/{branch}"), first, true, "test")
+            .expect("rewind local");
+        assert_eq!(
+            rebase_target(&repo, &branch).expect("behind upstream"),
+            format!("origin/{branch}")
+        );
+    }
+
+    #[test]
+    fn has_tracked_changes_ignores_untracked_files() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "a.txt", "a\n", "first");
+        fs::write(root.join("scratch.txt"), "notes").expect("write untracked");
+        assert!(!has_tracked_changes(&repo).expect("clean"));
+        fs::write(root.join("a.txt"), "changed\n").expect("edit tracked");
+        assert!(has_tracked_changes(&repo).expect("dirty"));
+    }
+
+    #[test]
+    fn commits_since_tag_stops_at_the_nearest_tag() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "a.txt", "a\n", "first");
+        let head = repo.head().expect("head").peel_to_commit().expect("commit");
+        repo.tag_lightweight("v0.1.0", head.as_object(), false)
+            .expect("tag");
+        commit_file(&repo, &root, "a.txt", "b\n", "second");
+        commit_file(&repo, &root, "a.txt", "c\n", "third");
+
+        let (since, entries) = commits_since_tag(&repo, None, 50).expect("since latest");
+        assert_eq!(since.as_deref(), Some("v0.1.0"));
+        let summaries: Vec<_> = entries.iter().map(|entry| entry.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["third", "second"]);
+
+        let tags = collect_tags(&repo).expect("tags");
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].sha, head.id().to_string());
+        assert!(tags[0].message.is_none());
+        assert!(commits_since_tag(&repo, Some("v9"), 50).is_err());
+    }
+
+    #[test]
+    fn file_history_lists_commits_touching_path() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "a.txt", "a\n", "add a");
+        commit_file(&repo, &root, "b.txt", "b\n", "add b");
+        commit_file(&repo, &root, "a.txt", "a2\n", "edit a");
+
+        let history = file_history(&repo, "a.txt", 10).expect("history");
+        let summaries: Vec<&str> = history.iter().map(|entry| entry.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["edit a", "add a"]);
+        assert_eq!(file_history(&repo, "a.txt", 1).expect("history").len(), 1);
+    }
+
     #[test]
     fn collect_workspace_diff_prefers_staged_changes() {
         let (root, repo) = create_temp_repo();
@@ -1447,7 +2712,7 @@ mod tests {
         index.add_path(Path::new("staged.txt")).expect("add path");
         index.write().expect("write index");
 
-        let diff = collect_workspace_diff(&root).expect("collect diff");
+        let diff = collect_workspace_diff(&root, &[]).expect("collect diff").diff;
         assert!(diff.contains("staged.txt"));
         assert!(diff.contains("staged"));
     }
@@ -1458,11 +2723,29 @@ mod tests {
         let file_path = root.join("unstaged.txt");
         fs::write(&file_path, "unstaged\n").expect("write unstaged file");
 
-        let diff = collect_workspace_diff(&root).expect("collect diff");
+        let diff = collect_workspace_diff(&root, &[]).expect("collect diff").diff;
         assert!(diff.contains("unstaged.txt"));
         assert!(diff.contains("unstaged"));
     }
 
+    #[test]
+    fn collect_workspace_diff_notes_excluded_files() {
+        let (root, _repo) = create_temp_repo();
+        fs::write(root.join("main.rs"), "fn main() {}\n").expect("write source");
+        fs::write(root.join("package-lock.json"), "{}\n").expect("write lockfile");
+        fs::create_dir_all(root.join("vendor/lib")).expect("create vendor dir");
+        fs::write(root.join("vendor/lib/dep.go"), "package lib\n").expect("write vendored");
+
+        let excludes = vec!["package-lock.json".to_string(), "vendor/".to_string()];
+        let diff = collect_workspace_diff(&root, &excludes).expect("collect diff");
+        assert!(diff.diff.contains("main.rs"));
+        assert!(!diff.diff.contains("package-lock.json"));
+        assert_eq!(diff.excluded, vec!["package-lock.json", "vendor/lib/dep.go"]);
+        let text = diff.to_prompt_text();
+        assert!(text.contains("left out of this diff"));
+        assert!(text.contains("vendor/lib/dep.go"));
+    }
+
     #[test]
     fn action_paths_for_file_expands_renames() {
         let (root, repo) = create_temp_repo();
@@ -1494,4 +2777,86 @@ mod tests {
         let paths = action_paths_for_file(&root, "b.txt");
         assert_eq!(paths, vec!["a.txt".to_string(), "b.txt".to_string()]);
     }
+
+    fn commit_file(repo: &Repository, root: &Path, name: &str, contents: &str, message: &str) {
+        fs::write(root.join(name), contents).expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new(name)).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parent_refs = parents.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .expect("commit");
+    }
+
+    #[test]
+    fn summarize_against_base_counts_changes_and_predicts_conflicts() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, &root, "shared.txt", "base\n", "init");
+        let base_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("base", &base_commit, false).expect("create base");
+        repo.branch("feature", &base_commit, false).expect("create feature");
+
+        checkout_branch(&repo, "base").expect("checkout base");
+        commit_file(&repo, &root, "shared.txt", "from base\n", "base change");
+
+        checkout_branch(&repo, "feature").expect("checkout feature");
+        commit_file(&repo, &root, "shared.txt", "from feature\n", "feature change");
+        fs::write(root.join("notes.txt"), "one\ntwo\n").expect("write untracked file");
+
+        let summary = summarize_against_base(&repo, "ws", "feature", "base").expect("summary");
+        assert_eq!(summary.ahead, 1);
+        assert_eq!(summary.behind, 1);
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!(summary.insertions, 3);
+        assert_eq!(summary.deletions, 1);
+        assert_eq!(summary.conflict_paths, vec!["shared.txt".to_string()]);
+    }
+
+    #[test]
+    fn parse_review_threads_skips_resolved_threads() {
+        let response = json!({
+            "data": { "repository": { "pullRequest": { "reviewThreads": { "nodes": [
+                {
+                    "id": "T1",
+                    "isResolved": false,
+                    "isOutdated": false,
+                    "path": "src/lib.rs",
+                    "line": null,
+                    "originalLine": 12,
+                    "comments": { "nodes": [
+                        {
+                            "body": "Handle the error here",
+                            "url": "https://github.com/o/r/pull/1#r1",
+                            "diffHunk": "@@ -10,3 +10,3 @@",
+                            "author": { "login": "ana" }
+                        },
+                        { "body": "+1", "author": null }
+                    ] }
+                },
+                {
+                    "id": "T2",
+                    "isResolved": true,
+                    "path": "src/main.rs",
+                    "comments": { "nodes": [{ "body": "done" }] }
+                }
+            ] } } } }
+        });
+        let threads = parse_review_threads(&response);
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "T1");
+        assert_eq!(threads[0].line, Some(12));
+        assert_eq!(threads[0].diff_hunk, "@@ -10,3 +10,3 @@");
+        assert_eq!(threads[0].comments.len(), 2);
+        assert_eq!(threads[0].comments[0].author.as_deref(), Some("ana"));
+        assert!(threads[0].comments[1].author.is_none());
+    }
 }
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncReadExt;
+use tokio::process::{ChildStderr, Command};
+use tokio::sync::oneshot;
+
+use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
+
+/// Partial clone filters offered for big repositories.
+const ALLOWED_FILTERS: &[&str] = &["blob:none", "tree:0"];
+/// Non-progress stderr lines kept for the error message.
+const MAX_ERROR_LINES: usize = 20;
+
+static RUNNING_CLONES: OnceLock<Mutex<HashMap<String, oneshot::Sender<()>>>> = OnceLock::new();
+
+fn running_clones() -> &'static Mutex<HashMap<String, oneshot::Sender<()>>> {
+    RUNNING_CLONES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A `clone-progress` event, emitted when the phase or percentage changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CloneProgress {
+    pub(crate) clone_id: String,
+    /// Git's phase label, e.g. "Receiving objects" or "Resolving deltas".
+    pub(crate) phase: String,
+    pub(crate) percent: Option<u8>,
+    pub(crate) current: Option<u64>,
+    pub(crate) total: Option<u64>,
+}
+
+/// Shallow and partial clone settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CloneOptions {
+    pub(crate) depth: Option<u32>,
+    pub(crate) filter: Option<String>,
+}
+
+impl CloneOptions {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.depth == Some(0) {
+            return Err("Clone depth must be at least 1.".to_string());
+        }
+        match self.filter.as_deref() {
+            Some(filter) if !ALLOWED_FILTERS.contains(&filter) => {
+                Err(format!("Unsupported clone filter: {filter}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_reduced(&self) -> bool {
+        self.depth.is_some() || self.filter.is_some()
+    }
+}
+
+fn file_url(path: &str) -> String {
+    let path = normalize_git_path(path);
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else {
+        format!("file:///{path}")
+    }
+}
+
+/// Arguments for cloning the local repository at `source`. Git ignores
+/// `--depth` and `--filter` for plain local paths, so those go through a
+/// `file://` URL, with filters allowed for the local upload-pack.
+pub(crate) fn clone_args(source: &str, destination: &str, options: &CloneOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    if options.filter.is_some() {
+        args.extend(["-c".to_string(), "uploadpack.allowFilter=true".to_string()]);
+    }
+    args.extend(["clone".to_string(), "--progress".to_string()]);
+    if let Some(depth) = options.depth {
+        args.extend(["--depth".to_string(), depth.to_string()]);
+    }
+    if let Some(filter) = &options.filter {
+        args.push(format!("--filter={filter}"));
+    }
+    args.push(if options.is_reduced() {
+        file_url(source)
+    } else {
+        source.to_string()
+    });
+    args.push(destination.to_string());
+    args
+}
+
+/// Parse a progress line such as `Receiving objects:  45% (450/1000), 1.20 MiB`
+/// into its phase, percentage and counts.
+fn parse_progress_line(line: &str) -> Option<(String, Option<u8>, Option<u64>, Option<u64>)> {
+    let line = line.trim();
+    let line = line.strip_prefix("remote:").map(str::trim).unwrap_or(line);
+    let (phase, rest) = line.split_once(':')?;
+    let rest = rest.trim();
+    let percent = rest
+        .split_once('%')
+        .and_then(|(value, _)| value.trim().parse::<u8>().ok());
+    let counts = rest
+        .split_once('(')
+        .and_then(|(_, value)| value.split_once(')'))
+        .and_then(|(value, _)| value.split_once('/'))
+        .and_then(|(current, total)| Some((current.parse().ok()?, total.parse().ok()?)));
+    if percent.is_none() && counts.is_none() {
+        return None;
+    }
+    Some((
+        phase.trim().to_string(),
+        percent,
+        counts.map(|(current, _)| current),
+        counts.map(|(_, total)| total),
+    ))
+}
+
+/// Emit progress from git's stderr, which separates updates with `\r`, and
+/// return the other lines for error reporting.
+async fn forward_progress(mut stderr: ChildStderr, app: &AppHandle, clone_id: &str) -> Vec<String> {
+    let mut buffer = [0u8; 4096];
+    let mut pending = String::new();
+    let mut messages: Vec<String> = Vec::new();
+    let mut last: Option<(String, Option<u8>)> = None;
+    loop {
+        let read = match stderr.read(&mut buffer).await {
+            Ok(0) | Err(_) => 0,
+            Ok(read) => read,
+        };
+        if read > 0 {
+            pending.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        } else {
+            pending.push('\n');
+        }
+        while let Some(index) = pending.find(['\r', '\n']) {
+            let line: String = pending.drain(..=index).collect();
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((phase, percent, current, total)) = parse_progress_line(line) else {
+                if messages.len() == MAX_ERROR_LINES {
+                    messages.remove(0);
+                }
+                messages.push(line.to_string());
+                continue;
+            };
+            let key = (phase.clone(), percent);
+            if last.as_ref() == Some(&key) {
+                continue;
+            }
+            last = Some(key);
+            let _ = app.emit(
+                "clone-progress",
+                CloneProgress {
+                    clone_id: clone_id.to_string(),
+                    phase,
+                    percent,
+                    current,
+                    total,
+                },
+            );
+        }
+        if read == 0 {
+            return messages;
+        }
+    }
+}
+
+/// Run `git` with `args` in `cwd`, emitting `clone-progress` events, until it
+/// finishes or `cancel_clone` is called with `clone_id`.
+pub(crate) async fn run_clone(
+    app: &AppHandle,
+    clone_id: &str,
+    cwd: &Path,
+    args: &[String],
+) -> Result<(), String> {
+    let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
+    let mut child = Command::new(git_bin)
+        .args(args)
+        .current_dir(cwd)
+        .env("PATH", git_env_path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    let stderr = child.stderr.take().ok_or("Failed to capture git output")?;
+
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+    running_clones()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(clone_id.to_string(), cancel_tx);
+
+    let finished = tokio::select! {
+        messages = forward_progress(stderr, app, clone_id) => {
+            Some((child.wait().await, messages))
+        }
+        _ = &mut cancel_rx => None,
+    };
+    running_clones()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(clone_id);
+
+    match finished {
+        None => {
+            let _ = child.kill().await;
+            Err("Clone cancelled.".to_string())
+        }
+        Some((Ok(status), _)) if status.success() => Ok(()),
+        Some((Ok(_), messages)) => {
+            let detail = messages.join("\n");
+            if detail.is_empty() {
+                Err("Git command failed.".to_string())
+            } else {
+                Err(detail)
+            }
+        }
+        Some((Err(error), _)) => Err(format!("Failed to run git: {error}")),
+    }
+}
+
+/// Stop a clone started by `add_clone` with this `clone_id`.
+#[tauri::command]
+pub(crate) async fn cancel_clone(clone_id: String) -> Result<(), String> {
+    let sender = running_clones()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(&clone_id)
+        .ok_or("Clone not running.")?;
+    let _ = sender.send(());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_progress_line_reads_phase_and_counts() {
+        assert_eq!(
+            parse_progress_line("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s"),
+            Some((
+                "Receiving objects".to_string(),
+                Some(45),
+                Some(450),
+                Some(1000)
+            ))
+        );
+        assert_eq!(
+            parse_progress_line("remote: Counting objects: 100% (12/12), done."),
+            Some((
+                "Counting objects".to_string(),
+                Some(100),
+                Some(12),
+                Some(12)
+            ))
+        );
+        assert_eq!(parse_progress_line("Cloning into 'copy'..."), None);
+        assert_eq!(parse_progress_line("fatal: repository not found"), None);
+    }
+
+    #[test]
+    fn clone_args_use_file_url_for_shallow_and_partial_clones() {
+        let plain = clone_args("/repo", "/copies/a", &CloneOptions::default());
+        assert_eq!(plain, vec!["clone", "--progress", "/repo", "/copies/a"]);
+
+        let options = CloneOptions {
+            depth: Some(1),
+            filter: Some("blob:none".to_string()),
+        };
+        assert!(options.validate().is_ok());
+        assert_eq!(
+            clone_args("/repo", "/copies/a", &options),
+            vec![
+                "-c",
+                "uploadpack.allowFilter=true",
+                "clone",
+                "--progress",
+                "--depth",
+                "1",
+                "--filter=blob:none",
+                "file:///repo",
+                "/copies/a",
+            ]
+        );
+        assert!(CloneOptions {
+            depth: Some(0),
+            filter: None
+        }
+        .validate()
+        .is_err());
+        assert!(CloneOptions {
+            depth: None,
+            filter: Some("sparse:oid=x".to_string())
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use git2::Repository;
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde_json::Value;
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// What a cached status was computed against. HEAD and index changes are
+/// caught here; working tree edits are caught by the watcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StatusKey {
+    head: Option<String>,
+    index_mtime: Option<SystemTime>,
+    index_len: u64,
+}
+
+impl StatusKey {
+    pub(crate) fn for_repo(repo: &Repository) -> Self {
+        let head = repo.head().ok().map(|head| {
+            format!(
+                "{}@{}",
+                head.name().unwrap_or(""),
+                head.target().map(|oid| oid.to_string()).unwrap_or_default()
+            )
+        });
+        let index = std::fs::metadata(repo.path().join("index")).ok();
+        Self {
+            head,
+            index_mtime: index.as_ref().and_then(|meta| meta.modified().ok()),
+            index_len: index.map(|meta| meta.len()).unwrap_or(0),
+        }
+    }
+}
+
+struct RepoStatus {
+    root: PathBuf,
+    /// Bumped by the watcher on every working tree change.
+    generation: Arc<AtomicU64>,
+    /// `None` when the watcher could not start; such repos are never cached.
+    watcher: Option<Debouncer<RecommendedWatcher>>,
+    cached: Option<(StatusKey, u64, Value)>,
+}
+
+/// `get_git_status` results per workspace, so polling a large repo doesn't
+/// rescan the working tree when nothing changed.
+#[derive(Default)]
+pub(crate) struct GitStatusCache {
+    repos: Mutex<HashMap<String, RepoStatus>>,
+}
+
+impl GitStatusCache {
+    /// The cached status if it is still current, else the generation to pass
+    /// to [`GitStatusCache::store`] once the status has been recomputed.
+    pub(crate) fn lookup(
+        &self,
+        workspace_id: &str,
+        root: &Path,
+        key: &StatusKey,
+    ) -> Result<Value, u64> {
+        let mut repos = self.repos.lock().unwrap_or_else(|e| e.into_inner());
+        let repo = repos
+            .entry(workspace_id.to_string())
+            .or_insert_with(|| watch_repo(root));
+        if repo.root != root {
+            *repo = watch_repo(root);
+        }
+        let generation = repo.generation.load(Ordering::SeqCst);
+        if repo.watcher.is_none() {
+            return Err(generation);
+        }
+        match &repo.cached {
+            Some((cached_key, cached_generation, value))
+                if cached_key == key && *cached_generation == generation =>
+            {
+                Ok(value.clone())
+            }
+            _ => Err(generation),
+        }
+    }
+
+    /// Remember a status computed at `generation`. Changes seen while it was
+    /// being computed leave the generation ahead, so the next lookup misses.
+    pub(crate) fn store(&self, workspace_id: &str, key: StatusKey, generation: u64, value: Value) {
+        let mut repos = self.repos.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(repo) = repos.get_mut(workspace_id) {
+            repo.cached = Some((key, generation, value));
+        }
+    }
+
+    /// Drop the cache and watcher for a workspace, e.g. when it is removed.
+    pub(crate) fn stop_workspace(&self, workspace_id: &str) {
+        let mut repos = self.repos.lock().unwrap_or_else(|e| e.into_inner());
+        repos.remove(workspace_id);
+    }
+}
+
+/// Changes under `.git` are covered by [`StatusKey`]; object and log writes
+/// there would otherwise invalidate on every fetch.
+fn is_working_tree_change(root: &Path, path: &Path) -> bool {
+    !path
+        .strip_prefix(root)
+        .ok()
+        .and_then(|relative| relative.components().next())
+        .is_some_and(|first| first.as_os_str() == ".git")
+}
+
+fn watch_repo(root: &Path) -> RepoStatus {
+    let generation = Arc::new(AtomicU64::new(0));
+    let counter = Arc::clone(&generation);
+    let watched_root = root.to_path_buf();
+    let watcher = new_debouncer(WATCH_DEBOUNCE, move |result: DebounceEventResult| {
+        let changed = match result {
+            Ok(events) => events
+                .iter()
+                .any(|event| is_working_tree_change(&watched_root, &event.path)),
+            // A watcher error may mean dropped events; assume something changed.
+            Err(_) => true,
+        };
+        if changed {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    })
+    .and_then(|mut debouncer| {
+        debouncer
+            .watcher()
+            .watch(root, RecursiveMode::Recursive)
+            .map(|_| debouncer)
+    });
+    let watcher = match watcher {
+        Ok(watcher) => Some(watcher),
+        Err(error) => {
+            tracing::warn!(
+                "[git_status_cache] Failed to watch {}: {error}",
+                root.display()
+            );
+            None
+        }
+    };
+    RepoStatus {
+        root: root.to_path_buf(),
+        generation,
+        watcher,
+        cached: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn working_tree_changes_exclude_git_dir() {
+        let root = Path::new("/repo");
+        assert!(is_working_tree_change(root, Path::new("/repo/src/main.rs")));
+        assert!(is_working_tree_change(root, Path::new("/repo/.gitignore")));
+        assert!(!is_working_tree_change(root, Path::new("/repo/.git/index")));
+        assert!(!is_working_tree_change(root, Path::new("/repo/.git/objects/ab/cd")));
+    }
+
+    #[test]
+    fn status_key_tracks_head_and_index() {
+        let root = std::env::temp_dir().join(format!("git-status-cache-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let repo = Repository::init(&root).unwrap();
+        let empty = StatusKey::for_repo(&repo);
+        assert_eq!(empty, StatusKey::for_repo(&repo));
+
+        std::fs::write(root.join("a.txt"), "a\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let staged = StatusKey::for_repo(&repo);
+        assert_ne!(empty, staged);
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Ada", "ada@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "first", &tree, &[])
+            .unwrap();
+        assert_ne!(staged.head, StatusKey::for_repo(&repo).head);
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,310 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::claude::{
+    ensure_persistent_session, ensure_workspace_session, ensure_workspace_thread_watcher,
+    generate_run_metadata,
+};
+use crate::event_sink::TauriEventSink;
+use crate::git::github_repo_from_path;
+use crate::git_utils::resolve_git_root;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::turn_scheduler::dispatch_turn;
+use crate::utils::now_millis;
+use crate::workspaces::add_worktree;
+
+const MAX_ISSUE_COMMENTS: usize = 20;
+const MAX_COMMENT_CHARS: usize = 4000;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssueThreadOptions {
+    /// Run the thread in a new worktree named from the issue.
+    #[serde(default)]
+    pub(crate) create_worktree: bool,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct IssueAuthor {
+    #[serde(default)]
+    pub(crate) login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct IssueComment {
+    #[serde(default)]
+    pub(crate) author: Option<IssueAuthor>,
+    #[serde(default)]
+    pub(crate) body: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct IssueDetails {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) body: String,
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) comments: Vec<IssueComment>,
+}
+
+fn truncate_chars(text: &str, limit: usize) -> String {
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= limit {
+        return trimmed.to_string();
+    }
+    let mut truncated: String = trimmed.chars().take(limit).collect();
+    truncated.push_str("\n[truncated]");
+    truncated
+}
+
+/// Build the opening prompt from the issue, keeping only the latest comments.
+pub(crate) fn build_issue_prompt(issue: &IssueDetails) -> String {
+    let mut prompt = format!(
+        "Work on GitHub issue #{}: {}\n{}\n",
+        issue.number, issue.title, issue.url
+    );
+    let body = issue.body.trim();
+    prompt.push_str("\n## Description\n");
+    prompt.push_str(if body.is_empty() {
+        "(no description)"
+    } else {
+        body
+    });
+    prompt.push('\n');
+
+    let comments: Vec<&IssueComment> = issue
+        .comments
+        .iter()
+        .filter(|comment| !comment.body.trim().is_empty())
+        .collect();
+    if !comments.is_empty() {
+        prompt.push_str("\n## Comments\n");
+        let skipped = comments.len().saturating_sub(MAX_ISSUE_COMMENTS);
+        if skipped > 0 {
+            prompt.push_str(&format!("({skipped} earlier comments omitted)\n"));
+        }
+        for comment in comments.into_iter().skip(skipped) {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|author| author.login.as_str())
+                .filter(|login| !login.is_empty())
+                .unwrap_or("unknown");
+            prompt.push_str(&format!(
+                "\n@{author}:\n{}\n",
+                truncate_chars(&comment.body, MAX_COMMENT_CHARS)
+            ));
+        }
+    }
+
+    prompt.push_str(
+        "\nInvestigate the codebase, implement a fix or the requested change, \
+and summarize what you changed.",
+    );
+    prompt
+}
+
+async fn fetch_issue(
+    state: &AppState,
+    workspace_id: &str,
+    issue_number: u64,
+) -> Result<IssueDetails, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let output = Command::new("gh")
+        .args([
+            "issue",
+            "view",
+            &issue_number.to_string(),
+            "--repo",
+            &repo_name,
+            "--json",
+            "number,title,body,url,comments",
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn fallback_worktree_name(issue_number: u64) -> String {
+    format!("fix/issue-{issue_number}")
+}
+
+/// Fetch a GitHub issue, optionally branch a worktree for it, and start a thread
+/// whose first message carries the issue context.
+#[tauri::command]
+pub(crate) async fn start_thread_from_issue(
+    workspace_id: String,
+    issue_number: u64,
+    options: Option<IssueThreadOptions>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "start_thread_from_issue",
+            json!({
+                "workspaceId": workspace_id,
+                "issueNumber": issue_number,
+                "options": options.as_ref().map(|options| json!({
+                    "createWorktree": options.create_worktree,
+                    "model": options.model,
+                    "accessMode": options.access_mode,
+                })),
+            }),
+        )
+        .await;
+    }
+
+    let options = options.unwrap_or_default();
+    let issue = fetch_issue(&state, &workspace_id, issue_number).await?;
+    let prompt = build_issue_prompt(&issue);
+
+    let worktree = if options.create_worktree {
+        let metadata = generate_run_metadata(
+            workspace_id.clone(),
+            format!("#{} {}\n\n{}", issue.number, issue.title, issue.body),
+            state.clone(),
+        )
+        .await
+        .unwrap_or(Value::Null);
+        let branch = metadata
+            .get("worktreeName")
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| fallback_worktree_name(issue.number));
+        Some(add_worktree(workspace_id.clone(), branch, state.clone(), app.clone()).await?)
+    } else {
+        None
+    };
+    let target_id = worktree
+        .as_ref()
+        .map(|info| info.id.clone())
+        .unwrap_or_else(|| workspace_id.clone());
+
+    let session = ensure_workspace_session(&state, &app, &target_id).await?;
+    ensure_workspace_thread_watcher(&target_id, session.entry.clone(), &state, app.clone()).await;
+
+    let thread_id = Uuid::new_v4().to_string();
+    let event_sink = TauriEventSink::new(app);
+    let turn_id = ensure_persistent_session(
+        &target_id,
+        &session,
+        &thread_id,
+        options.model.as_deref(),
+        options.access_mode.as_deref(),
+        None,
+        event_sink.clone(),
+    )
+    .await?;
+    let queue_position = dispatch_turn(
+        &state,
+        &event_sink,
+        &session,
+        &target_id,
+        &thread_id,
+        &turn_id,
+        &prompt,
+    )
+    .await?;
+
+    let timestamp = now_millis();
+    Ok(json!({
+        "workspaceId": target_id,
+        "worktree": worktree,
+        "issue": { "number": issue.number, "title": issue.title, "url": issue.url },
+        "thread": {
+            "id": thread_id,
+            "createdAt": timestamp,
+            "updatedAt": timestamp,
+            "cwd": session.entry.path,
+        },
+        "turn": { "id": turn_id, "threadId": thread_id },
+        "queuePosition": queue_position,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(login: &str, body: &str) -> IssueComment {
+        IssueComment {
+            author: Some(IssueAuthor {
+                login: login.to_string(),
+            }),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn build_issue_prompt_includes_title_body_and_comments() {
+        let issue = IssueDetails {
+            number: 42,
+            title: "Crash on save".to_string(),
+            body: "Steps to reproduce".to_string(),
+            url: "https://github.com/o/r/issues/42".to_string(),
+            comments: vec![comment("ana", "Also on Linux"), comment("bo", "  ")],
+        };
+        let prompt = build_issue_prompt(&issue);
+        assert!(prompt.starts_with("Work on GitHub issue #42: Crash on save"));
+        assert!(prompt.contains("## Description\nSteps to reproduce"));
+        assert!(prompt.contains("@ana:\nAlso on Linux"));
+        assert!(!prompt.contains("@bo"));
+    }
+
+    #[test]
+    fn build_issue_prompt_keeps_latest_comments() {
+        let comments = (0..MAX_ISSUE_COMMENTS + 3)
+            .map(|index| comment("dev", &format!("comment {index}")))
+            .collect();
+        let issue = IssueDetails {
+            number: 7,
+            title: "Flaky test".to_string(),
+            body: String::new(),
+            url: "https://github.com/o/r/issues/7".to_string(),
+            comments,
+        };
+        let prompt = build_issue_prompt(&issue);
+        assert!(prompt.contains("(no description)"));
+        assert!(prompt.contains("(3 earlier comments omitted)"));
+        assert!(!prompt.contains("comment 2\n"));
+        assert!(prompt.contains(&format!("comment {}", MAX_ISSUE_COMMENTS + 2)));
+    }
+
+    #[test]
+    fn truncate_chars_marks_long_text() {
+        assert_eq!(truncate_chars(" short ", 10), "short");
+        assert_eq!(truncate_chars("abcdef", 3), "abc\n[truncated]");
+    }
+}
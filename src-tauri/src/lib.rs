@@ -1,36 +1,94 @@
-use tauri::Manager;
+use tauri::{Manager, RunEvent};
 #[cfg(target_os = "macos")]
-use tauri::{RunEvent, WindowEvent};
+use tauri::WindowEvent;
 
+mod activity;
+mod analytics;
+mod attachments;
+mod audit;
 mod backend;
+mod background_runs;
+mod bench;
+mod ci_status;
 mod claude;
 mod claude_tasks;
 mod claude_home;
 mod claude_config;
+mod claude_md;
+mod code_blocks;
+mod container;
+mod context_preview;
+mod daemon_service;
+mod dangerous_commands;
+mod diagnostics;
+mod diff_stats;
 mod file_io;
+mod file_merge;
 mod file_ops;
 mod file_policy;
+mod file_references;
+mod file_tree;
+mod file_watch;
 mod files;
 mod task_manager;
+mod task_runner;
 #[cfg(not(target_os = "windows"))]
 #[path = "dictation.rs"]
 mod dictation;
 #[cfg(target_os = "windows")]
 #[path = "dictation_stub.rs"]
 mod dictation;
+#[cfg(not(target_os = "windows"))]
+mod dictation_commands;
+mod edit_scope;
+mod egress_policy;
+mod event_replay;
 mod event_sink;
 mod git;
+mod git_clone;
+mod git_status_cache;
 mod git_utils;
+mod issue_threads;
 mod local_usage;
+mod logging;
 mod menu;
+mod metrics;
+mod model_compare;
+mod palette;
+mod plan_mode;
+mod presence;
+mod process_registry;
 mod prompts;
+mod redaction;
 mod remote_backend;
+mod retention;
+mod review_comments;
+mod sandbox;
+mod secrets;
+mod selection_context;
+mod session_integrity;
+mod session_resources;
 mod settings;
+mod settings_bundle;
+mod shutdown;
+mod ssh_tunnel;
+mod startup_commands;
 mod state;
 mod terminal;
+mod thread_drafts;
+mod thread_presets;
+mod thread_summaries;
+mod tool_images;
+mod tray;
+mod updates;
 mod window;
 mod storage;
+mod storage_report;
 mod task_watcher;
+mod turn_checkpoints;
+mod turn_recordings;
+mod turn_reports;
+mod turn_scheduler;
 mod types;
 mod utils;
 mod workspaces;
@@ -61,11 +119,39 @@ pub fn run() {
             }
         })
         .setup(|app| {
-            let state = state::AppState::load(&app.handle());
+            let mut state = state::AppState::load(&app.handle());
+            if let Some(data_dir) = state.settings_path.parent().map(|dir| dir.to_path_buf()) {
+                logging::init_logging(&data_dir, state.app_settings.get_mut());
+                // Nothing has been spawned yet, so anything in the registry is an orphan.
+                let report = process_registry::recover_orphaned_processes(&data_dir);
+                *state.recovery_report.get_mut() = Some(report);
+                tool_images::init_tool_image_cache(&data_dir);
+                audit::init_audit_log(&data_dir);
+                retention::init_app_sessions(&data_dir);
+                turn_recordings::init_turn_recordings(
+                    &data_dir,
+                    state.app_settings.get_mut().turn_recording_enabled,
+                );
+                turn_checkpoints::set_turn_checkpoints_enabled(
+                    state.app_settings.get_mut().turn_checkpoints_enabled,
+                );
+            }
             app.manage(state);
+            process_registry::emit_recovery_report(app.handle().clone());
             app.manage(task_watcher::TaskWatcherState::default());
+            local_usage::spawn_local_usage_ingestion(app.handle().clone());
+            retention::spawn_retention_job(app.handle().clone());
+            ci_status::spawn_ci_status_poller(app.handle().clone());
+            remote_backend::spawn_remote_heartbeat(app.handle().clone());
+            ssh_tunnel::spawn_ssh_tunnel_supervisor(app.handle().clone());
+            session_resources::spawn_session_resource_monitor(app.handle().clone());
+            metrics::spawn_otlp_exporter(app.handle().clone());
+            turn_checkpoints::spawn_checkpoint_writer(app.handle().clone());
+            workspaces::spawn_auto_connect(app.handle().clone());
+            menu::apply_saved_accelerators(app.handle().clone());
             #[cfg(desktop)]
             {
+                tray::setup_tray(app.handle())?;
                 app.handle()
                     .plugin(tauri_plugin_updater::Builder::new().build())?;
             }
@@ -83,15 +169,32 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             settings::get_app_settings,
             settings::update_app_settings,
+            settings_bundle::export_app_settings,
+            settings_bundle::import_app_settings,
+            settings_bundle::list_settings_profiles,
+            settings_bundle::save_settings_profile,
+            settings_bundle::apply_settings_profile,
+            settings_bundle::delete_settings_profile,
             menu::menu_set_accelerators,
+            menu::menu_reset_accelerators,
             claude::claude_doctor,
+            updates::check_for_updates,
+            updates::install_update,
             workspaces::list_workspaces,
             workspaces::is_workspace_path_dir,
             workspaces::add_workspace,
+            workspaces::set_allowed_workspace_roots,
             workspaces::add_clone,
             workspaces::add_worktree,
             workspaces::remove_workspace,
             workspaces::remove_worktree,
+            git_clone::cancel_clone,
+            workspaces::rename_workspace,
+            startup_commands::run_startup_commands,
+            startup_commands::stop_workspace_startup_commands,
+            workspaces::move_workspace_path,
+            workspaces::list_external_worktrees,
+            workspaces::adopt_worktree,
             workspaces::rename_worktree,
             workspaces::rename_worktree_upstream,
             workspaces::apply_worktree_changes,
@@ -99,25 +202,66 @@ pub fn run() {
             workspaces::update_workspace_claude_bin,
             claude::start_thread,
             claude::send_user_message,
+            redaction::redaction_preview,
             claude::turn_interrupt,
             claude::start_review,
             claude::respond_to_server_request,
+            claude::get_live_turn_state,
+            window::open_thread_window,
+            palette::palette_query,
+            file_references::resolve_file_reference,
+            selection_context::build_selection_context,
+            attachments::attach_clipboard_image,
+            attachments::attachment_stage,
+            plan_mode::approve_plan,
+            plan_mode::reject_plan,
+            background_runs::start_background_run,
+            background_runs::run_get_report,
+            background_runs::background_run_list,
+            bench::bench_run,
+            bench::bench_get_report,
+            code_blocks::extract_code_blocks,
+            code_blocks::apply_code_block,
+            model_compare::compare_models,
+            turn_reports::get_turn_report,
+            turn_checkpoints::get_turn_diff,
+            turn_checkpoints::which_turn_changed,
             claude::remember_approval_rule,
             claude::get_commit_message_prompt,
             claude::generate_commit_message,
+            claude::generate_release_notes,
             claude::generate_run_metadata,
+            issue_threads::start_thread_from_issue,
+            thread_presets::start_thread_from_preset,
             claude::resume_thread,
+            claude::get_item_full_output,
             claude::fork_thread_from_message,
             claude::rewind_thread_files,
             claude::list_threads,
             claude::search_thread,
+            claude::list_all_threads,
             claude::archive_thread,
+            claude::set_thread_title,
+            claude::pin_thread,
+            claude::tag_thread,
+            claude::untag_thread,
+            claude::list_tags,
             claude::collaboration_mode_list,
             workspaces::connect_workspace,
             git::get_git_status,
             git::list_git_roots,
             git::get_git_diffs,
             git::get_git_log,
+            git::get_git_file_log,
+            git::get_git_blame,
+            git::get_worktree_summary,
+            git::rebase_worktree_onto_parent,
+            git::continue_worktree_rebase,
+            git::abort_worktree_rebase,
+            git::list_git_tags,
+            git::create_git_tag,
+            git::push_git_tag,
+            ci_status::get_ci_status,
             git::get_git_commit_diff,
             git::get_git_remote,
             git::stage_git_file,
@@ -126,6 +270,8 @@ pub fn run() {
             git::revert_git_file,
             git::revert_git_all,
             git::commit_git,
+            git::amend_git_commit,
+            git::undo_last_commit,
             git::push_git,
             git::pull_git,
             git::sync_git,
@@ -134,6 +280,8 @@ pub fn run() {
             git::get_github_pull_request_diff,
             git::get_github_pull_request_comments,
             workspaces::list_workspace_files,
+            file_tree::list_workspace_dir,
+            file_tree::search_workspace_files,
             workspaces::read_workspace_file,
             workspaces::open_workspace_in,
             git::list_git_branches,
@@ -162,6 +310,16 @@ pub fn run() {
             dictation::dictation_stop,
             dictation::dictation_cancel,
             local_usage::local_usage_snapshot,
+            analytics::usage_analytics,
+            analytics::usage_heatmap,
+            activity::get_workspace_activity,
+            audit::get_audit_log,
+            diagnostics::generate_diagnostics,
+            event_replay::replay_events,
+            turn_recordings::list_turn_recordings,
+            turn_recordings::replay_turn,
+            turn_recordings::cancel_turn_replay,
+            presence::presence_update,
             claude_tasks::get_claude_tasks,
             task_watcher::task_watcher_start,
             task_watcher::task_watcher_stop,
@@ -171,13 +329,46 @@ pub fn run() {
             task_manager::task_update,
             task_manager::task_delete,
             task_manager::task_lists_available,
+            task_manager::task_ready_list,
+            task_runner::task_execute,
+            remote_backend::remote_backend_health,
+            ssh_tunnel::ssh_tunnel_status,
+            logging::get_daemon_logs,
+            logging::get_app_logs,
+            turn_scheduler::get_turn_queue,
+            session_integrity::verify_session,
+            session_integrity::prune_thread_outputs,
+            session_resources::session_resource_snapshot,
+            session_resources::kill_session_process,
+            process_registry::get_recovery_report,
+            daemon_service::daemon_install,
+            daemon_service::daemon_start,
+            daemon_service::daemon_stop,
+            daemon_service::daemon_status,
+            retention::data_retention_purge,
+            retention::purge_workspace_data,
+            storage_report::workspace_storage_report,
+            storage_report::delete_old_sessions,
+            storage_report::prune_app_caches,
             files::file_read,
-            files::file_write
+            files::file_write,
+            files::file_merge_three_way,
+            claude_md::claude_md_read,
+            claude_md::claude_md_write,
+            context_preview::get_context_preview,
+            file_watch::file_watch,
+            file_watch::file_unwatch,
+            thread_drafts::thread_draft_save,
+            thread_drafts::thread_draft_get
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application");
 
     app.run(|_app_handle, _event| {
+        if let RunEvent::ExitRequested { code, api, .. } = &_event {
+            shutdown::handle_exit_requested(_app_handle, *code, api);
+        }
+
         // macOS dock icon click handling
         #[cfg(target_os = "macos")]
         if let RunEvent::Reopen { .. } = _event {
@@ -2,11 +2,13 @@ use chrono::{DateTime, Duration, Local, TimeZone, Utc};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use tauri::{AppHandle, Emitter};
+
 use crate::claude_home::resolve_default_claude_home;
 use crate::types::{LocalUsageDay, LocalUsageModel, LocalUsageSnapshot, LocalUsageTotals};
 
@@ -26,11 +28,37 @@ struct UsageTotals {
     output: i64,
 }
 
+/// Parser state carried between scans so appended lines can be ingested
+/// without re-reading the whole file.
+#[derive(Default, Clone)]
+struct FileScanState {
+    offset: u64,
+    previous_totals: Option<UsageTotals>,
+    current_model: Option<String>,
+    last_activity_ms: Option<i64>,
+    seen_runs: HashSet<i64>,
+    match_known: bool,
+    matches_workspace: bool,
+    /// Set once the file is known not to belong to the workspace filter.
+    skipped: bool,
+}
+
+impl FileScanState {
+    fn new(workspace_path: Option<&Path>) -> Self {
+        Self {
+            match_known: workspace_path.is_none(),
+            matches_workspace: workspace_path.is_none(),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 struct CachedFileUsage {
     file_mtime: i64,
     daily: HashMap<String, DailyTotals>,
     model_totals: HashMap<String, i64>,
+    scan_state: FileScanState,
 }
 
 #[derive(Default)]
@@ -44,6 +72,8 @@ static LOCAL_USAGE_CACHE: OnceLock<Mutex<LocalUsageCache>> = OnceLock::new();
 
 const MAX_ACTIVITY_GAP_MS: i64 = 2 * 60 * 1000;
 
+const INGEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
 #[tauri::command]
 pub(crate) async fn local_usage_snapshot(
     days: Option<u32>,
@@ -66,7 +96,53 @@ pub(crate) async fn local_usage_snapshot(
     Ok(snapshot)
 }
 
+/// Keep the usage cache warm by tailing session files in the background.
+///
+/// Once the UI has requested a snapshot, new JSONL lines are ingested every few
+/// seconds and a `local-usage-updated` event carries the refreshed snapshot, so
+/// the dashboard never has to wait on a full rescan.
+pub(crate) fn spawn_local_usage_ingestion(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(INGEST_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match tokio::task::spawn_blocking(ingest_local_usage).await {
+                Ok(Ok(Some(snapshot))) => {
+                    let _ = app.emit("local-usage-updated", snapshot);
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(error)) => tracing::warn!("[local_usage] ingestion failed: {error}"),
+                Err(error) => tracing::warn!("[local_usage] ingestion task failed: {error}"),
+            }
+        }
+    });
+}
+
+/// Refresh the cache for the last requested range. Returns a snapshot only when
+/// new usage was ingested or files disappeared.
+fn ingest_local_usage() -> Result<Option<LocalUsageSnapshot>, String> {
+    let (days, workspace_key) = {
+        let cache = LOCAL_USAGE_CACHE.get_or_init(|| Mutex::new(LocalUsageCache::default()));
+        let cache = cache.lock().map_err(|_| "local usage cache lock poisoned")?;
+        if cache.days == 0 {
+            // Nothing has been requested yet.
+            return Ok(None);
+        }
+        (cache.days, cache.workspace_key.clone())
+    };
+    let workspace_path = workspace_key.map(PathBuf::from);
+    let (snapshot, changed) = refresh_local_usage(days, workspace_path.as_deref())?;
+    Ok(if changed { Some(snapshot) } else { None })
+}
+
 fn scan_local_usage(days: u32, workspace_path: Option<&Path>) -> Result<LocalUsageSnapshot, String> {
+    refresh_local_usage(days, workspace_path).map(|(snapshot, _)| snapshot)
+}
+
+fn refresh_local_usage(
+    days: u32,
+    workspace_path: Option<&Path>,
+) -> Result<(LocalUsageSnapshot, bool), String> {
     let updated_at = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -82,20 +158,24 @@ fn scan_local_usage(days: u32, workspace_path: Option<&Path>) -> Result<LocalUsa
     let workspace_key = workspace_path.map(|path| path.to_string_lossy().to_string());
     let cache = LOCAL_USAGE_CACHE.get_or_init(|| Mutex::new(LocalUsageCache::default()));
     let mut cache = cache.lock().map_err(|_| "local usage cache lock poisoned")?;
+    let mut changed = false;
     if cache.days != days || cache.workspace_key != workspace_key {
         cache.days = days;
         cache.workspace_key = workspace_key;
         cache.files.clear();
+        changed = true;
     }
 
     let Some(root) = resolve_claude_projects_root() else {
-        return Ok(build_snapshot(updated_at, day_keys, daily, HashMap::new()));
+        return Ok((build_snapshot(updated_at, day_keys, daily, HashMap::new()), changed));
     };
 
     let mut files = Vec::new();
     let entries = match std::fs::read_dir(&root) {
         Ok(entries) => entries,
-        Err(_) => return Ok(build_snapshot(updated_at, day_keys, daily, model_totals)),
+        Err(_) => {
+            return Ok((build_snapshot(updated_at, day_keys, daily, model_totals), changed))
+        }
     };
     for entry in entries.flatten() {
         let project_dir = entry.path();
@@ -109,16 +189,35 @@ fn scan_local_usage(days: u32, workspace_path: Option<&Path>) -> Result<LocalUsa
     for path in files {
         seen_files.insert(path.clone());
         let file_mtime = file_mtime(&path);
-        let cached = cache
-            .files
-            .get(&path)
-            .filter(|cached| cached.file_mtime == file_mtime);
-        let usage = if let Some(cached) = cached {
-            cached.clone()
-        } else {
+        let file_len = path.metadata().map(|meta| meta.len()).unwrap_or(0);
+        let needs_full_scan = match cache.files.get_mut(&path) {
+            Some(cached) if cached.file_mtime == file_mtime => false,
+            Some(cached) if file_len >= cached.scan_state.offset => {
+                // Session files are append-only; ingest just the new lines.
+                for key in &day_keys {
+                    cached.daily.entry(key.clone()).or_default();
+                }
+                scan_file_from(
+                    &path,
+                    &mut cached.scan_state,
+                    &mut cached.daily,
+                    &mut cached.model_totals,
+                    workspace_path,
+                )?;
+                cached.file_mtime = file_mtime;
+                changed = true;
+                false
+            }
+            // New file, or it shrank (rewritten), so start over.
+            _ => true,
+        };
+        if needs_full_scan {
             let usage = scan_file_usage(&path, &day_keys, workspace_path)?;
-            cache.files.insert(path.clone(), usage.clone());
-            usage
+            cache.files.insert(path.clone(), usage);
+            changed = true;
+        }
+        let Some(usage) = cache.files.get(&path) else {
+            continue;
         };
 
         for (day_key, totals) in usage.daily.iter() {
@@ -136,9 +235,11 @@ fn scan_local_usage(days: u32, workspace_path: Option<&Path>) -> Result<LocalUsa
         }
     }
 
+    let cached_count = cache.files.len();
     cache.files.retain(|path, _| seen_files.contains(path));
+    changed |= cache.files.len() != cached_count;
 
-    Ok(build_snapshot(updated_at, day_keys, daily, model_totals))
+    Ok((build_snapshot(updated_at, day_keys, daily, model_totals), changed))
 }
 
 fn collect_project_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
@@ -178,11 +279,13 @@ fn scan_file_usage(
         .map(|key| (key.clone(), DailyTotals::default()))
         .collect();
     let mut model_totals: HashMap<String, i64> = HashMap::new();
-    scan_file(path, &mut daily, &mut model_totals, workspace_path)?;
+    let mut scan_state = FileScanState::new(workspace_path);
+    scan_file_from(path, &mut scan_state, &mut daily, &mut model_totals, workspace_path)?;
     Ok(CachedFileUsage {
         file_mtime: file_mtime(path),
         daily,
         model_totals,
+        scan_state,
     })
 }
 
@@ -265,36 +368,75 @@ fn build_snapshot(
     }
 }
 
+#[cfg(test)]
 fn scan_file(
     path: &Path,
     daily: &mut HashMap<String, DailyTotals>,
     model_totals: &mut HashMap<String, i64>,
     workspace_path: Option<&Path>,
 ) -> Result<(), String> {
-    let file = match File::open(path) {
+    let mut state = FileScanState::new(workspace_path);
+    scan_file_from(path, &mut state, daily, model_totals, workspace_path)
+}
+
+/// Scan a session file starting at `state.offset`, advancing the offset past
+/// every complete line. A trailing partial line is left for the next scan.
+fn scan_file_from(
+    path: &Path,
+    state: &mut FileScanState,
+    daily: &mut HashMap<String, DailyTotals>,
+    model_totals: &mut HashMap<String, i64>,
+    workspace_path: Option<&Path>,
+) -> Result<(), String> {
+    if state.skipped {
+        return Ok(());
+    }
+    let mut file = match File::open(path) {
         Ok(file) => file,
         Err(_) => {
             return Ok(());
         }
     };
-    let reader = BufReader::new(file);
-    let mut previous_totals: Option<UsageTotals> = None;
-    let mut current_model: Option<String> = None;
-    let mut last_activity_ms: Option<i64> = None;
-    let mut seen_runs: HashSet<i64> = HashSet::new();
-    let mut match_known = workspace_path.is_none();
-    let mut matches_workspace = workspace_path.is_none();
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(line) => line,
+    if file.seek(SeekFrom::Start(state.offset)).is_err() {
+        return Ok(());
+    }
+    let mut reader = BufReader::new(file);
+    let FileScanState {
+        offset,
+        previous_totals,
+        current_model,
+        last_activity_ms,
+        seen_runs,
+        match_known,
+        matches_workspace,
+        skipped,
+    } = state;
+    let mut buffer: Vec<u8> = Vec::new();
+
+    loop {
+        buffer.clear();
+        let read = match reader.read_until(b'\n', &mut buffer) {
+            Ok(read) => read,
+            Err(_) => break,
+        };
+        if read == 0 {
+            break;
+        }
+        let complete = buffer.ends_with(b"\n");
+        if !complete && serde_json::from_slice::<Value>(&buffer).is_err() {
+            // The writer is mid-line; pick it up on the next scan.
+            break;
+        }
+        *offset += read as u64;
+        let line = match std::str::from_utf8(&buffer) {
+            Ok(line) => line.trim_end(),
             Err(_) => continue,
         };
         if line.len() > 512_000 {
             continue;
         }
 
-        let value = match serde_json::from_str::<Value>(&line) {
+        let value = match serde_json::from_str::<Value>(line) {
             Ok(value) => value,
             Err(_) => continue,
         };
@@ -305,9 +447,10 @@ fn scan_file(
 
         if let Some(cwd) = extract_cwd(&value) {
             if let Some(filter) = workspace_path {
-                matches_workspace = path_matches_workspace(&cwd, filter);
-                match_known = true;
-                if !matches_workspace {
+                *matches_workspace = path_matches_workspace(&cwd, filter);
+                *match_known = true;
+                if !*matches_workspace {
+                    *skipped = true;
                     break;
                 }
             }
@@ -315,7 +458,7 @@ fn scan_file(
 
         if entry_type == "turn_context" {
             if let Some(model) = extract_model_from_turn_context(&value) {
-                current_model = Some(model);
+                *current_model = Some(model);
             }
             continue;
         }
@@ -324,14 +467,15 @@ fn scan_file(
             continue;
         }
 
-        if !matches_workspace {
-            if match_known {
+        if !*matches_workspace {
+            if *match_known {
+                *skipped = true;
                 break;
             }
             continue;
         }
 
-        if !match_known {
+        if !*match_known {
             continue;
         }
 
@@ -345,7 +489,7 @@ fn scan_file(
                         }
                     }
                 }
-                track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                track_activity(daily, last_activity_ms, timestamp_ms);
             }
 
             let message = value.get("message");
@@ -399,14 +543,14 @@ fn scan_file(
                             }
                         }
                     }
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                    track_activity(daily, last_activity_ms, timestamp_ms);
                 }
                 continue;
             }
 
             if payload_type == Some("agent_reasoning") {
                 if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                    track_activity(daily, last_activity_ms, timestamp_ms);
                 }
                 continue;
             }
@@ -471,7 +615,7 @@ fn scan_file(
                     cached: (cached - prev.cached).max(0),
                     output: (output - prev.output).max(0),
                 };
-                previous_totals = Some(UsageTotals { input, cached, output });
+                *previous_totals = Some(UsageTotals { input, cached, output });
             } else {
                 // Some streams emit `last_token_usage` deltas between `total_token_usage` snapshots.
                 // Treat those as already-counted to avoid double-counting when the next total arrives.
@@ -479,7 +623,7 @@ fn scan_file(
                 next.input += delta.input;
                 next.cached += delta.cached;
                 next.output += delta.output;
-                previous_totals = Some(next);
+                *previous_totals = Some(next);
             }
 
             if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
@@ -503,7 +647,7 @@ fn scan_file(
             }
 
             if let Some(timestamp_ms) = timestamp_ms {
-                track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                track_activity(daily, last_activity_ms, timestamp_ms);
             }
             continue;
         }
@@ -527,11 +671,11 @@ fn scan_file(
                             }
                         }
                     }
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                    track_activity(daily, last_activity_ms, timestamp_ms);
                 }
             } else if payload_type != Some("message") {
                 if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
+                    track_activity(daily, last_activity_ms, timestamp_ms);
                 }
             }
         }
@@ -827,4 +971,44 @@ mod tests {
         assert_eq!(totals.agent_ms, 0);
         assert_eq!(totals.input, 0);
     }
+
+    #[test]
+    fn scan_file_from_ingests_only_appended_lines() {
+        let day_key = "2026-01-19";
+        let path = write_temp_jsonl(&[
+            r#"{"type":"assistant","timestamp":"2026-01-19T12:00:00.000Z","message":{"usage":{"input_tokens":10,"output_tokens":5},"model":"claude-sonnet-4-5"}}"#,
+        ]);
+
+        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
+        daily.insert(day_key.to_string(), DailyTotals::default());
+        let mut model_totals: HashMap<String, i64> = HashMap::new();
+        let mut state = FileScanState::new(None);
+        scan_file_from(&path, &mut state, &mut daily, &mut model_totals, None).expect("scan file");
+        let first_offset = state.offset;
+        assert_eq!(first_offset, std::fs::metadata(&path).unwrap().len());
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open temp jsonl");
+        writeln!(
+            file,
+            r#"{{"type":"assistant","timestamp":"2026-01-19T12:01:00.000Z","message":{{"usage":{{"input_tokens":4,"output_tokens":2}},"model":"claude-sonnet-4-5"}}}}"#
+        )
+        .expect("append line");
+        // A partially written line must not be consumed yet.
+        write!(file, r#"{{"type":"assistant","timestamp":"#).expect("append partial line");
+        drop(file);
+
+        scan_file_from(&path, &mut state, &mut daily, &mut model_totals, None).expect("rescan file");
+
+        let totals = daily.get(day_key).copied().unwrap_or_default();
+        assert_eq!(totals.input, 14);
+        assert_eq!(totals.output, 7);
+        assert_eq!(totals.agent_runs, 2);
+        assert_eq!(totals.agent_ms, 60_000);
+        assert_eq!(model_totals.get("claude-sonnet-4-5"), Some(&21));
+        assert!(state.offset > first_offset);
+        assert!(state.offset < std::fs::metadata(&path).unwrap().len());
+    }
 }
@@ -0,0 +1,522 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, State};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::AppSettings;
+use crate::utils::now_millis;
+
+const LOG_FILE_NAME: &str = "monitor.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_LOG_FILES: usize = 5;
+const DEFAULT_LOG_LIMIT: usize = 500;
+const MAX_LOG_LIMIT: usize = 5000;
+/// Overrides the default level from settings, e.g. `CLAUDE_MONITOR_LOG=debug`.
+const LOG_LEVEL_ENV: &str = "CLAUDE_MONITOR_LOG";
+const CRATE_PREFIX: &str = concat!(env!("CARGO_CRATE_NAME"), "::");
+
+static LOG_FILTER: RwLock<LogFilter> = RwLock::new(LogFilter {
+    default: Level::INFO,
+    modules: Vec::new(),
+});
+
+/// Minimum recorded level, with overrides per module.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogFilter {
+    default: Level,
+    /// Module paths such as `claude` or `backend::claude_cli`, longest first so
+    /// the most specific override wins. Targets outside this crate can be
+    /// named in full, e.g. `tauri`.
+    modules: Vec<(String, Level)>,
+}
+
+/// Whether `target` is `module` or inside it. Targets in this crate are
+/// matched without the crate name.
+fn module_matches(target: &str, module: &str) -> bool {
+    let local = target.strip_prefix(CRATE_PREFIX).unwrap_or(target);
+    [local, target].into_iter().any(|candidate| {
+        candidate
+            .strip_prefix(module)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+impl LogFilter {
+    fn level_for(&self, target: &str) -> Level {
+        self.modules
+            .iter()
+            .find(|(module, _)| module_matches(target, module))
+            .map_or(self.default, |(_, level)| *level)
+    }
+}
+
+/// Build the filter for `settings`, failing on unknown level names.
+/// `CLAUDE_MONITOR_LOG` takes precedence over the default level.
+pub(crate) fn log_filter_for(settings: &AppSettings) -> Result<LogFilter, String> {
+    let env_level = std::env::var(LOG_LEVEL_ENV)
+        .ok()
+        .and_then(|value| Level::from_str(value.trim()).ok());
+    let default = match env_level {
+        Some(level) => level,
+        None => match settings.log_level.as_deref().map(str::trim) {
+            Some(level) if !level.is_empty() => {
+                Level::from_str(level).map_err(|_| format!("Unknown log level: {level}"))?
+            }
+            _ => Level::INFO,
+        },
+    };
+    let mut modules = module_levels(&settings.log_levels)?;
+    modules.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+    Ok(LogFilter { default, modules })
+}
+
+fn module_levels(levels: &BTreeMap<String, String>) -> Result<Vec<(String, Level)>, String> {
+    levels
+        .iter()
+        .map(|(module, level)| {
+            let module = module.trim().trim_start_matches(CRATE_PREFIX);
+            if module.is_empty() {
+                return Err("Log level overrides need a module name.".to_string());
+            }
+            let level = Level::from_str(level.trim())
+                .map_err(|_| format!("Unknown log level for {module}: {level}"))?;
+            Ok((module.to_string(), level))
+        })
+        .collect()
+}
+
+/// Swap in a new filter; takes effect for the next event.
+pub(crate) fn set_log_filter(filter: LogFilter) {
+    if let Ok(mut current) = LOG_FILTER.write() {
+        *current = filter;
+    }
+}
+
+/// One structured log line as written to `logs/monitor.log`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogEntry {
+    pub(crate) timestamp: i64,
+    pub(crate) level: String,
+    pub(crate) target: String,
+    pub(crate) message: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub(crate) fields: Map<String, Value>,
+}
+
+pub(crate) fn logs_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("logs")
+}
+
+fn rotated_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(LOG_FILE_NAME)
+    } else {
+        dir.join(format!("{LOG_FILE_NAME}.{index}"))
+    }
+}
+
+/// Size-rotated log file: `monitor.log`, then `monitor.log.1` (newest) up to `.{MAX_LOG_FILES - 1}`.
+struct LogFile {
+    dir: PathBuf,
+    max_bytes: u64,
+    file: Option<File>,
+    size: u64,
+}
+
+impl LogFile {
+    fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            dir,
+            max_bytes,
+            file: None,
+            size: 0,
+        }
+    }
+
+    fn open(&mut self) -> std::io::Result<&mut File> {
+        if self.file.is_none() {
+            fs::create_dir_all(&self.dir)?;
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(rotated_path(&self.dir, 0))?;
+            self.size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("log file opened"))
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        let _ = fs::remove_file(rotated_path(&self.dir, MAX_LOG_FILES - 1));
+        for index in (0..MAX_LOG_FILES - 1).rev() {
+            let from = rotated_path(&self.dir, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.dir, index + 1))?;
+            }
+        }
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        self.open()?;
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        let file = self.open()?;
+        writeln!(file, "{line}")?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(text) => text,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::from(format!("{value:?}")));
+    }
+}
+
+/// Writes events as JSON lines to the rotating log file and echoes them to stderr.
+struct FileLogger {
+    file: Mutex<LogFile>,
+    next_span_id: AtomicU64,
+}
+
+impl Subscriber for FileLogger {
+    /// Levels change with settings, so no callsite is cached as always or never.
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        LOG_FILTER
+            .read()
+            .map(|filter| *metadata.level() <= filter.level_for(metadata.target()))
+            .unwrap_or(true)
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            timestamp: now_millis(),
+            level: metadata.level().as_str().to_lowercase(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        if entry.fields.is_empty() {
+            eprintln!("[{}] {}: {}", entry.level, entry.target, entry.message);
+        } else {
+            eprintln!(
+                "[{}] {}: {} {}",
+                entry.level,
+                entry.target,
+                entry.message,
+                Value::Object(entry.fields.clone())
+            );
+        }
+        let Ok(line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_line(&line);
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Install the global logger with the levels from `settings`; later calls are ignored.
+pub(crate) fn init_logging(data_dir: &Path, settings: &AppSettings) {
+    let filter = log_filter_for(settings);
+    if let Ok(filter) = &filter {
+        set_log_filter(filter.clone());
+    }
+    let logger = FileLogger {
+        file: Mutex::new(LogFile::new(logs_dir(data_dir), MAX_LOG_BYTES)),
+        // Span ids must be non-zero.
+        next_span_id: AtomicU64::new(1),
+    };
+    let _ = tracing::subscriber::set_global_default(logger);
+    if let Err(error) = filter {
+        tracing::warn!("[logging] Ignoring log levels from settings: {error}");
+    }
+}
+
+fn parse_level(level: Option<&str>) -> Result<Level, String> {
+    match level
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    {
+        Some(value) => Level::from_str(value).map_err(|_| format!("Unknown log level: {value}")),
+        None => Ok(Level::TRACE),
+    }
+}
+
+/// Entries at or above `level` within `[since, until]`, optionally from one
+/// module, oldest first, keeping the newest `limit`.
+pub(crate) fn read_log_entries(
+    dir: &Path,
+    level: Option<&str>,
+    module: Option<&str>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<usize>,
+) -> Result<Vec<LogEntry>, String> {
+    let module = module.map(str::trim).filter(|module| !module.is_empty());
+    let min_level = parse_level(level)?;
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+    let mut entries = Vec::new();
+    for index in (0..MAX_LOG_FILES).rev() {
+        let Ok(file) = File::open(rotated_path(dir, index)) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let Ok(entry) = serde_json::from_str::<LogEntry>(&line) else {
+                continue;
+            };
+            let Ok(entry_level) = Level::from_str(&entry.level) else {
+                continue;
+            };
+            if entry_level > min_level
+                || module.is_some_and(|module| !module_matches(&entry.target, module))
+                || since.is_some_and(|since| entry.timestamp < since)
+                || until.is_some_and(|until| entry.timestamp > until)
+            {
+                continue;
+            }
+            entries.push(entry);
+        }
+    }
+    let skip = entries.len().saturating_sub(limit);
+    Ok(entries.split_off(skip))
+}
+
+/// Recent backend log entries, so a remote daemon can be debugged without shell access.
+#[tauri::command]
+pub(crate) async fn get_daemon_logs(
+    level: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<LogEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_daemon_logs",
+            json!({ "level": level, "since": since, "until": until, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = state
+        .settings_path
+        .parent()
+        .ok_or("Unable to resolve app data directory")?;
+    let dir = logs_dir(data_dir);
+    tokio::task::spawn_blocking(move || {
+        read_log_entries(&dir, level.as_deref(), None, since, until, limit)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// This app's own log entries for the log viewer, optionally narrowed to one
+/// module such as `claude` or `backend::claude_cli`.
+#[tauri::command]
+pub(crate) async fn get_app_logs(
+    level: Option<String>,
+    module: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LogEntry>, String> {
+    let data_dir = state
+        .settings_path
+        .parent()
+        .ok_or("Unable to resolve app data directory")?;
+    let dir = logs_dir(data_dir);
+    tokio::task::spawn_blocking(move || {
+        read_log_entries(
+            &dir,
+            level.as_deref(),
+            module.as_deref(),
+            since,
+            until,
+            limit,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_logs_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("claude-monitor-logs-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn entry(timestamp: i64, level: &str, message: &str) -> String {
+        serde_json::to_string(&LogEntry {
+            timestamp,
+            level: level.to_string(),
+            target: "test".to_string(),
+            message: message.to_string(),
+            fields: Map::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn log_file_rotates_when_full() {
+        let dir = temp_logs_dir();
+        let mut log = LogFile::new(dir.clone(), 64);
+        for index in 0..6 {
+            log.write_line(&entry(index, "info", "0123456789")).unwrap();
+        }
+        assert!(rotated_path(&dir, 0).exists());
+        assert!(rotated_path(&dir, 1).exists());
+        assert!(!rotated_path(&dir, MAX_LOG_FILES).exists());
+
+        let entries = read_log_entries(&dir, None, None, None, None, None).unwrap();
+        let timestamps: Vec<i64> = entries.iter().map(|entry| entry.timestamp).collect();
+        let mut sorted = timestamps.clone();
+        sorted.sort();
+        assert_eq!(timestamps, sorted);
+        assert_eq!(timestamps.last(), Some(&5));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn read_log_entries_filters_by_level_time_and_limit() {
+        let dir = temp_logs_dir();
+        let mut log = LogFile::new(dir.clone(), MAX_LOG_BYTES);
+        log.write_line(&entry(10, "debug", "noise")).unwrap();
+        log.write_line(&entry(20, "warn", "slow")).unwrap();
+        log.write_line(&entry(30, "error", "boom")).unwrap();
+        log.write_line("not json").unwrap();
+        log.write_line(&entry(40, "info", "ok")).unwrap();
+
+        let warnings = read_log_entries(&dir, Some("warn"), None, None, None, None).unwrap();
+        let messages: Vec<&str> = warnings
+            .iter()
+            .map(|entry| entry.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["slow", "boom"]);
+
+        let window = read_log_entries(&dir, None, None, Some(20), Some(30), None).unwrap();
+        assert_eq!(window.len(), 2);
+
+        let latest = read_log_entries(&dir, None, None, None, None, Some(1)).unwrap();
+        assert_eq!(latest[0].message, "ok");
+
+        assert!(read_log_entries(&dir, Some("loud"), None, None, None, None).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn log_filter_prefers_most_specific_module() {
+        let settings = AppSettings {
+            log_level: Some("warn".to_string()),
+            log_levels: BTreeMap::from([
+                ("backend".to_string(), "debug".to_string()),
+                (
+                    format!("{CRATE_PREFIX}backend::claude_cli"),
+                    "trace".to_string(),
+                ),
+                ("tauri".to_string(), "error".to_string()),
+            ]),
+            ..AppSettings::default()
+        };
+        let filter = log_filter_for(&settings).unwrap();
+        let level = |target: &str| filter.level_for(target);
+        assert_eq!(
+            level(&format!("{CRATE_PREFIX}backend::claude_cli")),
+            Level::TRACE
+        );
+        assert_eq!(
+            level(&format!("{CRATE_PREFIX}backend::events")),
+            Level::DEBUG
+        );
+        assert_eq!(level(&format!("{CRATE_PREFIX}backends")), Level::WARN);
+        assert_eq!(level("tauri::manager"), Level::ERROR);
+        assert_eq!(level(&format!("{CRATE_PREFIX}claude")), Level::WARN);
+
+        let invalid = AppSettings {
+            log_levels: BTreeMap::from([("claude".to_string(), "loud".to_string())]),
+            ..AppSettings::default()
+        };
+        assert!(log_filter_for(&invalid).is_err());
+    }
+}
@@ -1,9 +1,54 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Mutex;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tauri::menu::{Menu, MenuItem, MenuItemBuilder, PredefinedMenuItem, Submenu};
-use tauri::{Emitter, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State, WebviewUrl, WebviewWindowBuilder};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::state::AppState;
+use crate::storage::write_settings;
+use crate::types::AppSettings;
+
+/// Customizable menu items and their default accelerators.
+const DEFAULT_ACCELERATORS: &[(&str, Option<&str>)] = &[
+    ("file_new_agent", Some("CmdOrCtrl+N")),
+    ("file_new_worktree_agent", Some("CmdOrCtrl+Shift+N")),
+    ("file_new_clone_agent", Some("CmdOrCtrl+Alt+N")),
+    ("view_toggle_projects_sidebar", Some("CmdOrCtrl+Shift+P")),
+    ("view_toggle_git_sidebar", Some("CmdOrCtrl+Shift+G")),
+    ("view_toggle_debug_panel", Some("CmdOrCtrl+Shift+D")),
+    ("view_toggle_terminal", Some("CmdOrCtrl+Shift+T")),
+    ("view_next_agent", Some("Cmd+Ctrl+Down")),
+    ("view_prev_agent", Some("Cmd+Ctrl+Up")),
+    ("view_next_workspace", Some("CmdOrCtrl+Shift+Down")),
+    ("view_prev_workspace", Some("CmdOrCtrl+Shift+Up")),
+    ("composer_cycle_model", Some("CmdOrCtrl+Shift+M")),
+    ("composer_cycle_access", Some("CmdOrCtrl+Shift+A")),
+    ("composer_cycle_reasoning", Some("CmdOrCtrl+Shift+R")),
+];
+
+/// Shortcuts the OS or the standard Edit/Window menus already own.
+const RESERVED_ACCELERATORS: &[&str] = &[
+    "CmdOrCtrl+Q",
+    "CmdOrCtrl+W",
+    "CmdOrCtrl+H",
+    "CmdOrCtrl+Alt+H",
+    "CmdOrCtrl+M",
+    "CmdOrCtrl+C",
+    "CmdOrCtrl+V",
+    "CmdOrCtrl+X",
+    "CmdOrCtrl+Z",
+    "CmdOrCtrl+Shift+Z",
+    "CmdOrCtrl+A",
+    "CmdOrCtrl+,",
+    "CmdOrCtrl+Tab",
+    "CmdOrCtrl+Space",
+    "Alt+F4",
+    "Alt+Tab",
+];
 
 pub struct MenuItemRegistry<R: Runtime> {
     items: Mutex<HashMap<String, MenuItem<R>>>,
@@ -38,24 +83,298 @@ impl<R: Runtime> MenuItemRegistry<R> {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MenuAcceleratorUpdate {
     pub id: String,
     pub accelerator: Option<String>,
 }
 
-#[tauri::command]
-pub fn menu_set_accelerators<R: Runtime>(
-    app: tauri::AppHandle<R>,
+/// An accelerator that was rejected or could not be registered.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceleratorFailure {
+    pub id: String,
+    pub accelerator: String,
+    /// "invalid", "reserved", "duplicate" or "unregistered"
+    pub reason: String,
+    /// The other menu item using the same shortcut, for duplicates.
+    pub conflicts_with: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceleratorReport {
+    pub applied: Vec<MenuAcceleratorUpdate>,
+    pub failed: Vec<AcceleratorFailure>,
+}
+
+/// Canonical form of an accelerator for comparisons, e.g. `cmd+shift+m` and
+/// `Shift+CmdOrCtrl+M` both become `CmdOrCtrl+Shift+M`.
+fn normalize_accelerator(value: &str) -> Option<String> {
+    let parts: Vec<String> = value
+        .split('+')
+        .map(|part| part.trim().to_lowercase())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (key, modifiers) = parts.split_last()?;
+    let mut cmd_or_ctrl = false;
+    let mut ctrl = false;
+    let mut alt = false;
+    let mut shift = false;
+    let mut cmd = false;
+    for modifier in modifiers {
+        match modifier.as_str() {
+            "cmdorctrl" | "commandorcontrol" => cmd_or_ctrl = true,
+            "cmd" | "command" | "super" | "meta" => cmd = true,
+            "ctrl" | "control" => ctrl = true,
+            "alt" | "option" => alt = true,
+            "shift" => shift = true,
+            _ => return None,
+        }
+    }
+    if matches!(key.as_str(), "cmd" | "ctrl" | "alt" | "shift" | "meta") {
+        return None;
+    }
+    // Cmd and Ctrl collapse onto CmdOrCtrl on the platform where they are the same key.
+    if cfg!(target_os = "macos") && cmd && !cmd_or_ctrl {
+        cmd = false;
+        cmd_or_ctrl = true;
+    } else if !cfg!(target_os = "macos") && ctrl && !cmd_or_ctrl {
+        ctrl = false;
+        cmd_or_ctrl = true;
+    }
+    let mut out = Vec::new();
+    if cmd_or_ctrl {
+        out.push("CmdOrCtrl".to_string());
+    }
+    if cmd {
+        out.push("Cmd".to_string());
+    }
+    if ctrl {
+        out.push("Ctrl".to_string());
+    }
+    if alt {
+        out.push("Alt".to_string());
+    }
+    if shift {
+        out.push("Shift".to_string());
+    }
+    let mut chars = key.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(ch), None) => ch.to_uppercase().to_string(),
+        _ => {
+            let mut chars = key.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    };
+    out.push(key);
+    Some(out.join("+"))
+}
+
+/// Split accelerators into ones safe to register and ones rejected as invalid,
+/// reserved by the system, or already used by an earlier entry.
+fn validate_accelerators(
+    entries: &[MenuAcceleratorUpdate],
+) -> (Vec<MenuAcceleratorUpdate>, Vec<AcceleratorFailure>) {
+    let reserved: Vec<String> = RESERVED_ACCELERATORS
+        .iter()
+        .filter_map(|value| normalize_accelerator(value))
+        .collect();
+    let mut taken: HashMap<String, String> = HashMap::new();
+    let mut valid = Vec::new();
+    let mut failed = Vec::new();
+    for entry in entries {
+        let Some(accelerator) = entry
+            .accelerator
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            valid.push(MenuAcceleratorUpdate {
+                id: entry.id.clone(),
+                accelerator: None,
+            });
+            continue;
+        };
+        let failure = |reason: &str, conflicts_with: Option<String>| AcceleratorFailure {
+            id: entry.id.clone(),
+            accelerator: accelerator.to_string(),
+            reason: reason.to_string(),
+            conflicts_with,
+        };
+        let Some(normalized) = normalize_accelerator(accelerator) else {
+            failed.push(failure("invalid", None));
+            continue;
+        };
+        if reserved.contains(&normalized) {
+            failed.push(failure("reserved", None));
+            continue;
+        }
+        if let Some(owner) = taken.get(&normalized) {
+            failed.push(failure("duplicate", Some(owner.clone())));
+            continue;
+        }
+        taken.insert(normalized, entry.id.clone());
+        valid.push(MenuAcceleratorUpdate {
+            id: entry.id.clone(),
+            accelerator: Some(accelerator.to_string()),
+        });
+    }
+    (valid, failed)
+}
+
+/// Saved accelerators that aren't being changed come first so an update can't
+/// steal a shortcut another item already holds.
+fn merge_accelerators(
+    saved: &BTreeMap<String, Option<String>>,
     updates: Vec<MenuAcceleratorUpdate>,
+) -> Vec<MenuAcceleratorUpdate> {
+    let mut merged: Vec<MenuAcceleratorUpdate> = saved
+        .iter()
+        .filter(|(id, _)| !updates.iter().any(|update| &update.id == *id))
+        .map(|(id, accelerator)| MenuAcceleratorUpdate {
+            id: id.clone(),
+            accelerator: accelerator.clone(),
+        })
+        .collect();
+    merged.extend(updates);
+    merged
+}
+
+/// Validate and register accelerators, clearing the ones that fail.
+fn register_accelerators(app: &AppHandle, entries: Vec<MenuAcceleratorUpdate>) -> AcceleratorReport {
+    let registry = app.state::<MenuItemRegistry<tauri::Wry>>();
+    let (valid, mut failed) = validate_accelerators(&entries);
+    for failure in &failed {
+        let _ = registry.set_accelerator(&failure.id, None);
+    }
+    let mut applied = Vec::new();
+    for entry in valid {
+        match registry.set_accelerator(&entry.id, entry.accelerator.as_deref()) {
+            Ok(true) => applied.push(entry),
+            Ok(false) => {}
+            Err(error) => {
+                let _ = registry.set_accelerator(&entry.id, None);
+                failed.push(AcceleratorFailure {
+                    id: entry.id.clone(),
+                    accelerator: entry.accelerator.clone().unwrap_or_default(),
+                    reason: "unregistered".to_string(),
+                    conflicts_with: None,
+                });
+                tracing::warn!("[menu] Failed to register accelerator for {}: {error}", entry.id);
+            }
+        }
+    }
+    if !failed.is_empty() {
+        TauriEventSink::new(app.clone()).emit_app_server_event(AppServerEvent {
+            workspace_id: String::new(),
+            message: json!({
+                "method": "menu/acceleratorsFailed",
+                "params": { "failed": failed },
+            }),
+        });
+    }
+    AcceleratorReport { applied, failed }
+}
+
+async fn save_accelerators(
+    state: &AppState,
+    report: &AcceleratorReport,
+    reset: impl FnOnce(&mut AppSettings),
 ) -> Result<(), String> {
-    let registry = app.state::<MenuItemRegistry<R>>();
-    for update in updates {
-        registry
-            .set_accelerator(&update.id, update.accelerator.as_deref())
-            .map_err(|error| error.to_string())?;
+    let mut settings = state.app_settings.lock().await;
+    reset(&mut settings);
+    for entry in &report.applied {
+        settings
+            .accelerators
+            .insert(entry.id.clone(), entry.accelerator.clone());
     }
-    Ok(())
+    for failure in &report.failed {
+        settings.accelerators.remove(&failure.id);
+    }
+    write_settings(&state.settings_path, &settings)
+}
+
+#[tauri::command]
+pub async fn menu_set_accelerators(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    updates: Vec<MenuAcceleratorUpdate>,
+) -> Result<AcceleratorReport, String> {
+    let saved = state.app_settings.lock().await.accelerators.clone();
+    let report = register_accelerators(&app, merge_accelerators(&saved, updates));
+    save_accelerators(&state, &report, |_| {}).await?;
+    Ok(report)
+}
+
+/// Restore every customizable shortcut, and the matching shortcut settings, to its default.
+#[tauri::command]
+pub async fn menu_reset_accelerators(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AcceleratorReport, String> {
+    let defaults = DEFAULT_ACCELERATORS
+        .iter()
+        .map(|(id, accelerator)| MenuAcceleratorUpdate {
+            id: id.to_string(),
+            accelerator: accelerator.map(str::to_string),
+        })
+        .collect();
+    let report = register_accelerators(&app, defaults);
+    save_accelerators(&state, &report, |settings| {
+        let defaults = AppSettings::default();
+        settings.accelerators.clear();
+        settings.composer_model_shortcut = defaults.composer_model_shortcut;
+        settings.composer_access_shortcut = defaults.composer_access_shortcut;
+        settings.composer_reasoning_shortcut = defaults.composer_reasoning_shortcut;
+        settings.new_agent_shortcut = defaults.new_agent_shortcut;
+        settings.new_worktree_agent_shortcut = defaults.new_worktree_agent_shortcut;
+        settings.new_clone_agent_shortcut = defaults.new_clone_agent_shortcut;
+        settings.toggle_projects_sidebar_shortcut = defaults.toggle_projects_sidebar_shortcut;
+        settings.toggle_git_sidebar_shortcut = defaults.toggle_git_sidebar_shortcut;
+        settings.toggle_debug_panel_shortcut = defaults.toggle_debug_panel_shortcut;
+        settings.toggle_terminal_shortcut = defaults.toggle_terminal_shortcut;
+        settings.cycle_agent_next_shortcut = defaults.cycle_agent_next_shortcut;
+        settings.cycle_agent_prev_shortcut = defaults.cycle_agent_prev_shortcut;
+        settings.cycle_workspace_next_shortcut = defaults.cycle_workspace_next_shortcut;
+        settings.cycle_workspace_prev_shortcut = defaults.cycle_workspace_prev_shortcut;
+    })
+    .await?;
+    Ok(report)
+}
+
+/// Replace every customizable shortcut with `accelerators`, falling back to
+/// the default for items it does not mention.
+pub(crate) async fn replace_accelerators(
+    app: &AppHandle,
+    state: &AppState,
+    accelerators: &BTreeMap<String, Option<String>>,
+) -> Result<AcceleratorReport, String> {
+    let entries = DEFAULT_ACCELERATORS
+        .iter()
+        .filter(|(id, _)| !accelerators.contains_key(*id))
+        .map(|(id, accelerator)| MenuAcceleratorUpdate {
+            id: id.to_string(),
+            accelerator: accelerator.map(str::to_string),
+        })
+        .collect();
+    let report = register_accelerators(app, merge_accelerators(accelerators, entries));
+    save_accelerators(state, &report, |settings| settings.accelerators.clear()).await?;
+    Ok(report)
+}
+
+/// Re-register the accelerators saved by a previous session.
+pub(crate) fn apply_saved_accelerators(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let saved = app.state::<AppState>().app_settings.lock().await.accelerators.clone();
+        if saved.is_empty() {
+            return;
+        }
+        register_accelerators(&app, merge_accelerators(&saved, Vec::new()));
+    });
 }
 
 pub(crate) fn build_menu<R: tauri::Runtime>(
@@ -387,3 +706,91 @@ fn emit_menu_event<R: tauri::Runtime>(app: &tauri::AppHandle<R>, event: &str) {
         let _ = app.emit(event, ());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(id: &str, accelerator: Option<&str>) -> MenuAcceleratorUpdate {
+        MenuAcceleratorUpdate {
+            id: id.to_string(),
+            accelerator: accelerator.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn normalize_accelerator_orders_modifiers_and_rejects_garbage() {
+        assert_eq!(
+            normalize_accelerator("shift+cmdorctrl+m").as_deref(),
+            Some("CmdOrCtrl+Shift+M")
+        );
+        assert_eq!(
+            normalize_accelerator("CmdOrCtrl+Alt+down").as_deref(),
+            Some("CmdOrCtrl+Alt+Down")
+        );
+        assert!(normalize_accelerator("CmdOrCtrl+Shift").is_none());
+        assert!(normalize_accelerator("Hyper+K").is_none());
+        assert!(normalize_accelerator("").is_none());
+    }
+
+    #[test]
+    fn validate_accelerators_flags_duplicates_and_reserved_shortcuts() {
+        let (valid, failed) = validate_accelerators(&[
+            update("file_new_agent", Some("CmdOrCtrl+N")),
+            update("view_toggle_terminal", Some("cmdorctrl+n")),
+            update("view_toggle_debug_panel", Some("CmdOrCtrl+Q")),
+            update("view_next_agent", Some("CmdOrCtrl+Nope+K")),
+            update("view_prev_agent", None),
+        ]);
+        assert_eq!(
+            valid,
+            vec![
+                update("file_new_agent", Some("CmdOrCtrl+N")),
+                update("view_prev_agent", None),
+            ]
+        );
+        let reasons: Vec<(&str, &str)> = failed
+            .iter()
+            .map(|failure| (failure.id.as_str(), failure.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("view_toggle_terminal", "duplicate"),
+                ("view_toggle_debug_panel", "reserved"),
+                ("view_next_agent", "invalid"),
+            ]
+        );
+        assert_eq!(failed[0].conflicts_with.as_deref(), Some("file_new_agent"));
+    }
+
+    #[test]
+    fn merge_accelerators_keeps_saved_entries_ahead_of_updates() {
+        let mut saved = BTreeMap::new();
+        saved.insert("file_new_agent".to_string(), Some("CmdOrCtrl+N".to_string()));
+        saved.insert("view_toggle_terminal".to_string(), Some("CmdOrCtrl+T".to_string()));
+        let merged = merge_accelerators(
+            &saved,
+            vec![update("view_toggle_terminal", Some("CmdOrCtrl+N"))],
+        );
+        assert_eq!(
+            merged,
+            vec![
+                update("file_new_agent", Some("CmdOrCtrl+N")),
+                update("view_toggle_terminal", Some("CmdOrCtrl+N")),
+            ]
+        );
+        let (_, failed) = validate_accelerators(&merged);
+        assert_eq!(failed[0].id, "view_toggle_terminal");
+    }
+
+    #[test]
+    fn default_accelerators_pass_validation() {
+        let defaults: Vec<MenuAcceleratorUpdate> = DEFAULT_ACCELERATORS
+            .iter()
+            .map(|(id, accelerator)| update(id, *accelerator))
+            .collect();
+        let (_, failed) = validate_accelerators(&defaults);
+        assert!(failed.is_empty(), "{failed:?}");
+    }
+}
@@ -0,0 +1,383 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::backend::events::AppServerEvent;
+use crate::state::AppState;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_SERVICE_NAME: &str = "claude-code-monitor";
+const SCOPE_NAME: &str = "claude_code_monitor";
+/// Cumulative temporality in the OTLP JSON encoding.
+const AGGREGATION_CUMULATIVE: u8 = 2;
+
+/// Process-wide counters fed from app-server events.
+#[derive(Default)]
+pub(crate) struct Metrics {
+    turns_started: AtomicU64,
+    turns_completed: AtomicU64,
+    errors: AtomicU64,
+    input_tokens: AtomicU64,
+    output_tokens: AtomicU64,
+}
+
+static METRICS: Metrics = Metrics {
+    turns_started: AtomicU64::new(0),
+    turns_completed: AtomicU64::new(0),
+    errors: AtomicU64::new(0),
+    input_tokens: AtomicU64::new(0),
+    output_tokens: AtomicU64::new(0),
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MetricsSnapshot {
+    pub(crate) turns_started: u64,
+    pub(crate) turns_completed: u64,
+    pub(crate) errors: u64,
+    pub(crate) input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) active_sessions: u64,
+}
+
+impl Metrics {
+    fn observe(&self, message: &Value) {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params");
+        match method {
+            "turn/started" => {
+                self.turns_started.fetch_add(1, Ordering::Relaxed);
+            }
+            "turn/completed" => {
+                self.turns_completed.fetch_add(1, Ordering::Relaxed);
+            }
+            "thread/tokenUsage/updated" => {
+                // `last` is the usage of the turn that just finished.
+                let last = params
+                    .and_then(|params| params.get("tokenUsage"))
+                    .and_then(|usage| usage.get("last"));
+                let count = |key: &str| {
+                    last.and_then(|last| last.get(key))
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0)
+                };
+                self.input_tokens
+                    .fetch_add(count("inputTokens"), Ordering::Relaxed);
+                self.output_tokens
+                    .fetch_add(count("outputTokens"), Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+
+    fn snapshot(&self, active_sessions: u64) -> MetricsSnapshot {
+        MetricsSnapshot {
+            turns_started: self.turns_started.load(Ordering::Relaxed),
+            turns_completed: self.turns_completed.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            input_tokens: self.input_tokens.load(Ordering::Relaxed),
+            output_tokens: self.output_tokens.load(Ordering::Relaxed),
+            active_sessions,
+        }
+    }
+}
+
+pub(crate) fn observe_app_server_event(event: &AppServerEvent) {
+    METRICS.observe(&event.message);
+}
+
+/// Count a turn that ended with an error result.
+pub(crate) fn record_turn_error() {
+    METRICS.errors.fetch_add(1, Ordering::Relaxed);
+}
+
+/// OTLP export settings, from the `otlpEndpoint` app setting and the standard
+/// OpenTelemetry environment variables.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OtlpConfig {
+    /// Collector base URL, e.g. `http://otel-collector:4318`
+    pub(crate) endpoint: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) service_name: String,
+}
+
+/// `OTEL_METRIC_EXPORT_INTERVAL` in milliseconds, at least a second.
+fn export_interval(env: impl Fn(&str) -> Option<String>) -> Duration {
+    env("OTEL_METRIC_EXPORT_INTERVAL")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(|millis| Duration::from_millis(millis.max(1000)))
+        .unwrap_or(DEFAULT_INTERVAL)
+}
+
+impl OtlpConfig {
+    /// The endpoint is the `otlpEndpoint` setting, else
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`. Headers and service name come from
+    /// `OTEL_EXPORTER_OTLP_HEADERS` (`key=value,...`) and `OTEL_SERVICE_NAME`.
+    /// Returns `None` when there is no endpoint.
+    pub(crate) fn resolve(
+        setting: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Option<Self> {
+        let endpoint = setting
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .or_else(|| env("OTEL_EXPORTER_OTLP_ENDPOINT").map(|value| value.trim().to_string()))
+            .filter(|value| !value.is_empty())?;
+        let headers = env("OTEL_EXPORTER_OTLP_HEADERS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .filter_map(|pair| pair.split_once('='))
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .filter(|(key, _)| !key.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let service_name = env("OTEL_SERVICE_NAME")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+        Some(Self {
+            endpoint,
+            headers,
+            service_name,
+        })
+    }
+
+    fn metrics_url(&self) -> String {
+        let base = self.endpoint.trim_end_matches('/');
+        if base.ends_with("/v1/metrics") {
+            base.to_string()
+        } else {
+            format!("{base}/v1/metrics")
+        }
+    }
+}
+
+fn now_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+fn data_point(value: u64, start_ns: u128, time_ns: u128) -> Value {
+    // int64 and fixed64 fields are strings in the OTLP JSON encoding.
+    json!({
+        "asInt": value.to_string(),
+        "startTimeUnixNano": start_ns.to_string(),
+        "timeUnixNano": time_ns.to_string(),
+    })
+}
+
+fn counter(name: &str, description: &str, unit: &str, point: Value) -> Value {
+    json!({
+        "name": name,
+        "description": description,
+        "unit": unit,
+        "sum": {
+            "aggregationTemporality": AGGREGATION_CUMULATIVE,
+            "isMonotonic": true,
+            "dataPoints": [point],
+        },
+    })
+}
+
+/// Build an OTLP/HTTP JSON `ExportMetricsServiceRequest`.
+pub(crate) fn build_otlp_payload(
+    snapshot: &MetricsSnapshot,
+    service_name: &str,
+    start_ns: u128,
+    time_ns: u128,
+) -> Value {
+    let point = |value| data_point(value, start_ns, time_ns);
+    let metrics = vec![
+        counter(
+            "monitor.turns.started",
+            "Agent turns started",
+            "{turn}",
+            point(snapshot.turns_started),
+        ),
+        counter(
+            "monitor.turns.completed",
+            "Agent turns completed",
+            "{turn}",
+            point(snapshot.turns_completed),
+        ),
+        counter(
+            "monitor.errors",
+            "Agent turns that ended in an error",
+            "{error}",
+            point(snapshot.errors),
+        ),
+        counter(
+            "monitor.tokens.input",
+            "Input tokens used",
+            "{token}",
+            point(snapshot.input_tokens),
+        ),
+        counter(
+            "monitor.tokens.output",
+            "Output tokens used",
+            "{token}",
+            point(snapshot.output_tokens),
+        ),
+        json!({
+            "name": "monitor.sessions.active",
+            "description": "Connected workspace sessions",
+            "unit": "{session}",
+            "gauge": {
+                "dataPoints": [{
+                    "asInt": snapshot.active_sessions.to_string(),
+                    "timeUnixNano": time_ns.to_string(),
+                }],
+            },
+        }),
+    ];
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [{
+                    "key": "service.name",
+                    "value": { "stringValue": service_name },
+                }],
+            },
+            "scopeMetrics": [{
+                "scope": { "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+fn process_env(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+/// Push metrics to the configured collector for the life of the process. The
+/// endpoint is re-read every interval, so changing the setting takes effect
+/// without a restart; nothing is sent while none is configured.
+pub(crate) fn spawn_otlp_exporter(app: AppHandle) {
+    let interval = export_interval(process_env);
+    tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::new();
+        let start_ns = now_nanos();
+        let mut exporting_to: Option<String> = None;
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let state = app.state::<AppState>();
+            let setting = state.app_settings.lock().await.otlp_endpoint.clone();
+            let Some(config) = OtlpConfig::resolve(setting.as_deref(), process_env) else {
+                exporting_to = None;
+                continue;
+            };
+            let url = config.metrics_url();
+            if exporting_to.as_deref() != Some(url.as_str()) {
+                tracing::info!("[metrics] Exporting OTLP metrics to {url}");
+                exporting_to = Some(url.clone());
+            }
+            let active_sessions = state.sessions.lock().await.len() as u64;
+            let snapshot = METRICS.snapshot(active_sessions);
+            let payload =
+                build_otlp_payload(&snapshot, &config.service_name, start_ns, now_nanos());
+            let mut request = client
+                .post(&url)
+                .header("content-type", "application/json")
+                .body(payload.to_string());
+            for (key, value) in &config.headers {
+                request = request.header(key.as_str(), value.as_str());
+            }
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    tracing::warn!("[metrics] OTLP export rejected: {}", response.status());
+                }
+                Err(error) => tracing::warn!("[metrics] OTLP export failed: {error}"),
+                Ok(_) => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        move |key| {
+            vars.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn otlp_config_prefers_the_setting_over_the_environment() {
+        assert!(OtlpConfig::resolve(None, env(&[])).is_none());
+        assert!(OtlpConfig::resolve(Some("  "), env(&[])).is_none());
+
+        let vars = env(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://env-collector:4318"),
+            ("OTEL_METRIC_EXPORT_INTERVAL", "10000"),
+            (
+                "OTEL_EXPORTER_OTLP_HEADERS",
+                "authorization=Bearer abc, x-team = a",
+            ),
+        ]);
+        let config = OtlpConfig::resolve(None, &vars).unwrap();
+        assert_eq!(config.metrics_url(), "http://env-collector:4318/v1/metrics");
+        assert_eq!(export_interval(&vars), Duration::from_secs(10));
+        assert_eq!(
+            config.headers,
+            vec![
+                ("authorization".to_string(), "Bearer abc".to_string()),
+                ("x-team".to_string(), "a".to_string()),
+            ]
+        );
+        assert_eq!(config.service_name, DEFAULT_SERVICE_NAME);
+
+        let config = OtlpConfig::resolve(Some("http://collector:4318/"), &vars).unwrap();
+        assert_eq!(config.metrics_url(), "http://collector:4318/v1/metrics");
+    }
+
+    #[test]
+    fn metrics_count_turns_errors_and_tokens() {
+        let metrics = Metrics::default();
+        metrics.observe(&json!({ "method": "turn/started", "params": {} }));
+        metrics.observe(&json!({ "method": "turn/completed", "params": {} }));
+        metrics.errors.fetch_add(1, Ordering::Relaxed);
+        metrics.observe(&json!({
+            "method": "thread/tokenUsage/updated",
+            "params": { "tokenUsage": { "last": { "inputTokens": 120, "outputTokens": 30 } } }
+        }));
+        let snapshot = metrics.snapshot(2);
+        assert_eq!(
+            snapshot,
+            MetricsSnapshot {
+                turns_started: 1,
+                turns_completed: 1,
+                errors: 1,
+                input_tokens: 120,
+                output_tokens: 30,
+                active_sessions: 2,
+            }
+        );
+
+        let payload = build_otlp_payload(&snapshot, "fleet-a", 1, 2);
+        let resource = &payload["resourceMetrics"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "fleet-a"
+        );
+        let metrics = resource["scopeMetrics"][0]["metrics"].as_array().unwrap();
+        assert_eq!(metrics[0]["name"], "monitor.turns.started");
+        assert_eq!(metrics[0]["sum"]["dataPoints"][0]["asInt"], "1");
+        assert_eq!(metrics[5]["gauge"]["dataPoints"][0]["asInt"], "2");
+    }
+}
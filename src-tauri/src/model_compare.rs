@@ -0,0 +1,348 @@
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use git2::{DiffOptions, Patch};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::{
+    ensure_workspace_session, ensure_workspace_thread_watcher, extract_text_from_message,
+    fork_session, resolve_session_path,
+};
+use crate::event_sink::TauriEventSink;
+use crate::plan_mode::extract_proposed_plan;
+use crate::redaction::redact_outgoing_prompt;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::turn_scheduler::start_tracked_turn;
+
+const MAX_COMPARE_MODELS: usize = 4;
+/// Compared threads can look but not touch, so they never race on the working tree.
+const COMPARE_ACCESS_MODE: &str = "read-only";
+
+/// One model's side of a comparison.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelCompareRun {
+    pub(crate) model: String,
+    pub(crate) thread_id: String,
+    /// "completed", "failed" or "interrupted"
+    pub(crate) status: String,
+    /// The plan the model proposed, or its final answer if it proposed none.
+    pub(crate) output: Option<String>,
+}
+
+/// Unified diff from the first model's output to another model's.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelCompareDiff {
+    pub(crate) base_model: String,
+    pub(crate) model: String,
+    pub(crate) diff: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelComparison {
+    pub(crate) source_thread_id: Option<String>,
+    pub(crate) prompt: String,
+    pub(crate) runs: Vec<ModelCompareRun>,
+    pub(crate) diffs: Vec<ModelCompareDiff>,
+}
+
+/// Trimmed, de-duplicated model list, in the order given.
+fn normalize_models(models: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for model in models {
+        let model = model.trim().to_string();
+        if !model.is_empty() && !normalized.contains(&model) {
+            normalized.push(model);
+        }
+    }
+    if normalized.len() < 2 {
+        return Err("Pick at least two models to compare.".to_string());
+    }
+    if normalized.len() > MAX_COMPARE_MODELS {
+        return Err(format!("Compare at most {MAX_COMPARE_MODELS} models at once."));
+    }
+    Ok(normalized)
+}
+
+/// What a model produced in answer to `prompt`: the last plan it proposed, or
+/// failing that its last reply. Earlier turns copied from a forked thread are skipped.
+fn produced_output<I: IntoIterator<Item = Value>>(entries: I, prompt: &str) -> Option<String> {
+    let mut plan = None;
+    let mut reply = None;
+    for entry in entries {
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        match entry.get("type").and_then(|v| v.as_str()) {
+            Some("user") if extract_text_from_message(message).trim() == prompt => {
+                plan = None;
+                reply = None;
+            }
+            Some("assistant") => {
+                let blocks = message.get("content").and_then(|v| v.as_array());
+                for block in blocks.into_iter().flatten() {
+                    if block.get("name").and_then(|v| v.as_str()) == Some("ExitPlanMode") {
+                        if let Some(proposed) = block.get("input").and_then(extract_proposed_plan) {
+                            plan = Some(proposed);
+                        }
+                    }
+                }
+                let text = extract_text_from_message(message);
+                if !text.trim().is_empty() {
+                    reply = Some(text.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    plan.or(reply)
+}
+
+fn read_output(session_path: &Path, prompt: &str) -> Option<String> {
+    let file = std::fs::File::open(session_path).ok()?;
+    let entries = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok());
+    produced_output(entries, prompt)
+}
+
+/// Unified diff between two models' outputs, labelled with the model names.
+fn output_diff(base_model: &str, base: &str, model: &str, output: &str) -> Option<String> {
+    let mut options = DiffOptions::new();
+    options.context_lines(3);
+    let mut patch = Patch::from_buffers(
+        base.as_bytes(),
+        Some(Path::new(base_model)),
+        output.as_bytes(),
+        Some(Path::new(model)),
+        Some(&mut options),
+    )
+    .ok()?;
+    let buffer = patch.to_buf().ok()?;
+    buffer.as_str().map(|text| text.to_string())
+}
+
+fn compare_diffs(runs: &[ModelCompareRun]) -> Vec<ModelCompareDiff> {
+    let Some((base, others)) = runs.split_first() else {
+        return Vec::new();
+    };
+    let base_output = base.output.as_deref().unwrap_or("");
+    others
+        .iter()
+        .filter_map(|run| {
+            let diff = output_diff(
+                &base.model,
+                base_output,
+                &run.model,
+                run.output.as_deref().unwrap_or(""),
+            )?;
+            Some(ModelCompareDiff {
+                base_model: base.model.clone(),
+                model: run.model.clone(),
+                diff,
+            })
+        })
+        .collect()
+}
+
+/// Run the same prompt on several models side by side and diff what they produce.
+///
+/// Each model gets its own thread, forked from `thread_id` when given so it
+/// sees the same conversation, and runs read-only. Returns once every run has
+/// finished.
+#[tauri::command]
+pub(crate) async fn compare_models(
+    workspace_id: String,
+    thread_id: Option<String>,
+    prompt: String,
+    models: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ModelComparison, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "compare_models",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "prompt": prompt,
+                "models": models,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("Comparison prompt is empty.".to_string());
+    }
+    let models = normalize_models(models)?;
+    // `dispatch_turn` masks the prompt, so the transcripts hold this form.
+    let sent_prompt = redact_outgoing_prompt(&state, &workspace_id, &prompt).await?;
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone()).await;
+
+    let mut thread_ids = Vec::new();
+    for _ in &models {
+        let new_thread_id = match &thread_id {
+            Some(source) => {
+                let entry = session.entry.clone();
+                let source = source.clone();
+                tokio::task::spawn_blocking(move || fork_session(&entry, &source))
+                    .await
+                    .map_err(|err| err.to_string())??
+            }
+            None => uuid::Uuid::new_v4().to_string(),
+        };
+        thread_ids.push(new_thread_id);
+    }
+
+    let event_sink = TauriEventSink::new(app.clone());
+    let mut turns = tokio::task::JoinSet::new();
+    for (index, (model, compare_thread_id)) in models.iter().zip(&thread_ids).enumerate() {
+        let turn = start_tracked_turn(
+            &state,
+            &event_sink,
+            &session,
+            &workspace_id,
+            compare_thread_id,
+            Some(model),
+            Some(COMPARE_ACCESS_MODE),
+            &prompt,
+        )
+        .await?;
+        turns.spawn(async move { (index, turn.completion().await.status) });
+    }
+
+    let mut statuses = vec!["interrupted".to_string(); models.len()];
+    while let Some(finished) = turns.join_next().await {
+        if let Ok((index, status)) = finished {
+            statuses[index] = status;
+        }
+    }
+    for compare_thread_id in &thread_ids {
+        let _ = session.kill_persistent_session(compare_thread_id).await;
+    }
+
+    let entry = session.entry.clone();
+    let source_thread_id = thread_id;
+    tokio::task::spawn_blocking(move || {
+        let runs: Vec<ModelCompareRun> = models
+            .into_iter()
+            .zip(thread_ids)
+            .zip(statuses)
+            .map(|((model, thread_id), status)| ModelCompareRun {
+                output: resolve_session_path(&entry, &thread_id)
+                    .and_then(|path| read_output(&path, &sent_prompt)),
+                model,
+                thread_id,
+                status,
+            })
+            .collect();
+        ModelComparison {
+            source_thread_id,
+            diffs: compare_diffs(&runs),
+            prompt: sent_prompt,
+            runs,
+        }
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redaction::apply_redactions;
+    use crate::types::RedactionRule;
+
+    fn user(text: &str) -> Value {
+        json!({ "type": "user", "message": { "role": "user", "content": text } })
+    }
+
+    fn assistant(content: Value) -> Value {
+        json!({ "type": "assistant", "message": { "role": "assistant", "content": content } })
+    }
+
+    #[test]
+    fn normalize_models_dedupes_and_bounds_the_list() {
+        assert_eq!(
+            normalize_models(vec![" opus ".into(), "sonnet".into(), "opus".into()]).unwrap(),
+            vec!["opus".to_string(), "sonnet".to_string()]
+        );
+        assert!(normalize_models(vec!["opus".into(), " opus".into()]).is_err());
+        let too_many = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        assert!(normalize_models(too_many).is_err());
+    }
+
+    #[test]
+    fn produced_output_prefers_plans_after_the_prompt() {
+        let entries = vec![
+            user("Earlier question"),
+            assistant(json!([{ "type": "text", "text": "Earlier answer" }])),
+            user("Fix the login bug"),
+            assistant(json!([
+                { "type": "text", "text": "Here is my plan." },
+                { "type": "tool_use", "name": "ExitPlanMode", "input": { "plan": "1. Patch auth.rs" } },
+            ])),
+        ];
+        assert_eq!(
+            produced_output(entries, "Fix the login bug").as_deref(),
+            Some("1. Patch auth.rs")
+        );
+        let earlier_only = vec![
+            user("Earlier question"),
+            assistant(json!([{ "type": "text", "text": "Earlier answer" }])),
+            user("Fix the login bug"),
+        ];
+        assert_eq!(produced_output(earlier_only, "Fix the login bug"), None);
+    }
+
+    #[test]
+    fn produced_output_matches_the_redacted_prompt() {
+        let rules = vec![RedactionRule {
+            label: "key".to_string(),
+            pattern: r"sk-\w+".to_string(),
+            enabled: true,
+            replacement: None,
+        }];
+        let sent = apply_redactions("Rotate sk-live123 today", &rules)
+            .unwrap()
+            .text;
+        assert_eq!(sent, "Rotate [REDACTED:key] today");
+        let entries = vec![
+            user("Earlier question"),
+            assistant(json!([{ "type": "text", "text": "Earlier answer" }])),
+            user(&sent),
+        ];
+        assert_eq!(produced_output(entries, &sent), None);
+    }
+
+    #[test]
+    fn compare_diffs_are_relative_to_the_first_model() {
+        let run = |model: &str, output: &str| ModelCompareRun {
+            model: model.to_string(),
+            thread_id: format!("t-{model}"),
+            status: "completed".to_string(),
+            output: Some(output.to_string()),
+        };
+        let runs = vec![
+            run("opus", "1. Patch auth.rs\n2. Add a test\n"),
+            run("sonnet", "1. Patch auth.rs\n2. Update docs\n"),
+        ];
+        let diffs = compare_diffs(&runs);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].base_model, "opus");
+        assert!(diffs[0].diff.contains("-2. Add a test"));
+        assert!(diffs[0].diff.contains("+2. Update docs"));
+    }
+}
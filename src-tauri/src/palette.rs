@@ -0,0 +1,339 @@
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use tokio::task;
+
+use crate::claude::{archived_threads_path, read_archived_threads, thread_previews};
+use crate::prompts::discover_prompts;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+use crate::workspaces::list_workspace_files_inner;
+
+const DEFAULT_LIMIT: usize = 30;
+const MAX_LIMIT: usize = 200;
+/// Cap on files considered per query so huge monorepos stay responsive.
+const MAX_PALETTE_FILES: usize = 20_000;
+const MAX_TITLE_CHARS: usize = 120;
+const DAY_MS: i64 = 86_400_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PaletteKind {
+    Workspace,
+    Thread,
+    Prompt,
+    File,
+}
+
+/// One ranked palette hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PaletteResult {
+    pub(crate) kind: PaletteKind,
+    /// Workspace id, thread id, prompt path or workspace-relative file path
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) subtitle: Option<String>,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) score: i64,
+}
+
+fn is_boundary(ch: char) -> bool {
+    matches!(ch, ' ' | '/' | '\\' | '-' | '_' | '.' | ':')
+}
+
+/// Case-insensitive subsequence match. Consecutive runs, word starts and
+/// prefixes score higher; `None` when the query doesn't match at all.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+    for (index, ch) in candidate.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if !ch.to_lowercase().eq(std::iter::once(query[query_index])) {
+            continue;
+        }
+        score += 1;
+        if previous_match == Some(index.wrapping_sub(1)) {
+            score += 5;
+        }
+        if index == 0 || is_boundary(candidate[index - 1]) {
+            score += 8;
+        }
+        previous_match = Some(index);
+        query_index += 1;
+    }
+    if query_index < query.len() {
+        return None;
+    }
+    let lowered: String = candidate.iter().flat_map(|ch| ch.to_lowercase()).collect();
+    let query: String = query.into_iter().collect();
+    if lowered.starts_with(&query) {
+        score += 15;
+    } else if lowered.contains(&query) {
+        score += 8;
+    }
+    // Prefer shorter candidates among otherwise equal matches.
+    Some(score - (candidate.len() as i64 / 16))
+}
+
+fn recency_bonus(updated_at: i64, now: i64) -> i64 {
+    let age = now - updated_at;
+    if updated_at <= 0 || age < 0 {
+        0
+    } else if age < DAY_MS {
+        10
+    } else if age < 7 * DAY_MS {
+        5
+    } else if age < 30 * DAY_MS {
+        2
+    } else {
+        0
+    }
+}
+
+fn truncate_title(value: &str) -> String {
+    let single_line = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.chars().count() <= MAX_TITLE_CHARS {
+        return single_line;
+    }
+    let mut truncated: String = single_line.chars().take(MAX_TITLE_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Score a file by its name first and fall back to the full path.
+pub(crate) fn file_score(query: &str, path: &str) -> Option<i64> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let by_name = fuzzy_score(query, name).map(|score| score + 10);
+    let by_path = fuzzy_score(query, path);
+    by_name.max(by_path)
+}
+
+fn rank(mut results: Vec<PaletteResult>, limit: usize) -> Vec<PaletteResult> {
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.kind.cmp(&b.kind))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    results.truncate(limit);
+    results
+}
+
+fn recent_files(root: PathBuf, query: &str, workspace_id: &str, now: i64) -> Vec<PaletteResult> {
+    list_workspace_files_inner(&root, MAX_PALETTE_FILES)
+        .into_iter()
+        .filter_map(|path| {
+            let score = file_score(query, &path)?;
+            let modified = std::fs::metadata(root.join(&path))
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or(0);
+            let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+            Some(PaletteResult {
+                kind: PaletteKind::File,
+                title: name,
+                subtitle: Some(path.clone()),
+                id: path,
+                workspace_id: Some(workspace_id.to_string()),
+                score: score + recency_bonus(modified, now),
+            })
+        })
+        .collect()
+}
+
+/// Fuzzy search workspaces, thread previews, prompts and files in one call.
+///
+/// Threads, prompts and files are limited to `workspace_id` when given; files
+/// are only searched for that workspace.
+#[tauri::command]
+pub(crate) async fn palette_query(
+    query: String,
+    workspace_id: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<PaletteResult>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "palette_query",
+            json!({ "query": query, "workspaceId": workspace_id, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let limit = limit
+        .map(|value| value as usize)
+        .unwrap_or(DEFAULT_LIMIT)
+        .clamp(1, MAX_LIMIT);
+    let query = query.trim().to_string();
+    let now = now_millis();
+    let workspaces: Vec<_> = state.workspaces.lock().await.values().cloned().collect();
+    let active_workspace = workspace_id
+        .as_ref()
+        .and_then(|id| workspaces.iter().find(|entry| &entry.id == id).cloned());
+    let archived = archived_threads_path(&state)
+        .ok()
+        .and_then(|path| read_archived_threads(&path).ok())
+        .unwrap_or_default();
+
+    let mut results = Vec::new();
+    for entry in &workspaces {
+        if let Some(score) = fuzzy_score(&query, &entry.name) {
+            results.push(PaletteResult {
+                kind: PaletteKind::Workspace,
+                id: entry.id.clone(),
+                title: entry.name.clone(),
+                subtitle: Some(entry.path.clone()),
+                workspace_id: Some(entry.id.clone()),
+                score,
+            });
+        }
+    }
+
+    for prompt in discover_prompts(&state, active_workspace.as_ref()) {
+        let Some(score) = fuzzy_score(&query, &prompt.name) else {
+            continue;
+        };
+        results.push(PaletteResult {
+            kind: PaletteKind::Prompt,
+            id: prompt.path,
+            title: prompt.name,
+            subtitle: prompt.description,
+            workspace_id: match prompt.scope.as_deref() {
+                Some("workspace") => workspace_id.clone(),
+                _ => None,
+            },
+            score,
+        });
+    }
+
+    let thread_workspaces = match &active_workspace {
+        Some(entry) => vec![entry.clone()],
+        None => workspaces.clone(),
+    };
+    let file_query = query.clone();
+    let file_workspace = active_workspace.clone();
+    let (threads, files) = task::spawn_blocking(move || {
+        let mut threads = Vec::new();
+        for entry in &thread_workspaces {
+            let archived_ids = archived.get(&entry.id);
+            for (thread_id, preview, updated_at) in thread_previews(entry) {
+                if preview.trim().is_empty()
+                    || archived_ids.is_some_and(|ids| ids.contains(&thread_id))
+                {
+                    continue;
+                }
+                let Some(score) = fuzzy_score(&file_query, &preview) else {
+                    continue;
+                };
+                threads.push(PaletteResult {
+                    kind: PaletteKind::Thread,
+                    id: thread_id,
+                    title: truncate_title(&preview),
+                    subtitle: Some(entry.name.clone()),
+                    workspace_id: Some(entry.id.clone()),
+                    score: score + recency_bonus(updated_at, now),
+                });
+            }
+        }
+        let files = file_workspace
+            .map(|entry| recent_files(PathBuf::from(&entry.path), &file_query, &entry.id, now))
+            .unwrap_or_default();
+        (threads, files)
+    })
+    .await
+    .map_err(|_| "palette search failed".to_string())?;
+    results.extend(threads);
+    results.extend(files);
+
+    Ok(rank(results, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(kind: PaletteKind, title: &str, score: i64) -> PaletteResult {
+        PaletteResult {
+            kind,
+            id: title.to_string(),
+            title: title.to_string(),
+            subtitle: None,
+            workspace_id: None,
+            score,
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_prefixes_and_word_starts() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert!(fuzzy_score("xyz", "settings").is_none());
+        let prefix = fuzzy_score("set", "settings.rs").unwrap();
+        let inner = fuzzy_score("set", "reset.rs").unwrap();
+        let scattered = fuzzy_score("set", "some_extra_thing").unwrap();
+        assert!(prefix > inner);
+        assert!(inner > scattered);
+        assert!(fuzzy_score("SeT", "settings").is_some());
+    }
+
+    #[test]
+    fn file_score_favors_file_name_matches() {
+        let name_match = file_score("app", "src/App.tsx").unwrap();
+        let dir_match = file_score("app", "apps/web/index.ts").unwrap();
+        assert!(name_match > dir_match);
+    }
+
+    #[test]
+    fn recency_bonus_decays_with_age() {
+        let now = 100 * DAY_MS;
+        assert_eq!(recency_bonus(now - 1_000, now), 10);
+        assert_eq!(recency_bonus(now - 3 * DAY_MS, now), 5);
+        assert_eq!(recency_bonus(now - 20 * DAY_MS, now), 2);
+        assert_eq!(recency_bonus(now - 90 * DAY_MS, now), 0);
+        assert_eq!(recency_bonus(0, now), 0);
+    }
+
+    #[test]
+    fn rank_orders_by_score_then_kind_and_truncates() {
+        let ranked = rank(
+            vec![
+                result(PaletteKind::File, "b", 5),
+                result(PaletteKind::Workspace, "a", 5),
+                result(PaletteKind::Thread, "c", 9),
+            ],
+            2,
+        );
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].title, "c");
+        assert_eq!(ranked[1].kind, PaletteKind::Workspace);
+    }
+
+    #[test]
+    fn truncate_title_collapses_whitespace_and_caps_length() {
+        assert_eq!(truncate_title("fix\n  the   bug"), "fix the bug");
+        let long = "x".repeat(MAX_TITLE_CHARS + 10);
+        assert_eq!(truncate_title(&long).chars().count(), MAX_TITLE_CHARS + 1);
+    }
+}
@@ -0,0 +1,228 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::backend::claude_cli::{PendingPermissionRequest, WorkspaceSession};
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{ensure_workspace_session, stored_permission_mode};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Pull the plan markdown out of an ExitPlanMode tool input.
+pub(crate) fn extract_proposed_plan(tool_input: &Value) -> Option<String> {
+    tool_input
+        .get("plan")
+        .and_then(|value| value.as_str())
+        .map(|plan| plan.trim())
+        .filter(|plan| !plan.is_empty())
+        .map(|plan| plan.to_string())
+}
+
+fn build_rejection_message(feedback: Option<&str>) -> String {
+    let mut message = "I don't approve this plan yet. Stay in plan mode and revise it.".to_string();
+    if let Some(feedback) = feedback.map(|value| value.trim()).filter(|value| !value.is_empty()) {
+        message.push_str("\n\nFeedback:\n");
+        message.push_str(feedback);
+    }
+    message
+}
+
+/// Plan approval leaves plan mode, so the rest of the turn runs with a
+/// permissive mode unless the caller picked one.
+fn approved_access_mode(access_mode: Option<&str>) -> &str {
+    match access_mode.map(|mode| mode.trim()) {
+        Some("read-only") | Some("plan") | None | Some("") => "current",
+        Some(mode) => mode,
+    }
+}
+
+fn build_approval_response(tool_input: &Value, permission_mode: &str) -> Value {
+    json!({
+        "behavior": "allow",
+        "updatedInput": tool_input,
+        "updatedPermissions": [
+            { "type": "setMode", "mode": permission_mode, "destination": "session" }
+        ],
+    })
+}
+
+fn build_rejection_response(feedback: Option<&str>) -> Value {
+    json!({ "behavior": "deny", "message": build_rejection_message(feedback) })
+}
+
+/// Take the held ExitPlanMode permission request for a tool use.
+async fn take_plan_request(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    tool_use_id: &str,
+) -> Result<PendingPermissionRequest, String> {
+    let pending = session
+        .take_permission_request(tool_use_id)
+        .await
+        .ok_or_else(|| "This plan was already answered.".to_string())?;
+    if pending.tool_name != "ExitPlanMode" || pending.thread_id != thread_id {
+        // Not ours to settle; put it back for respond_to_server_request.
+        session
+            .register_permission_request(tool_use_id.to_string(), pending)
+            .await;
+        return Err(format!("No plan is waiting for approval as {tool_use_id}."));
+    }
+    Ok(pending)
+}
+
+fn emit_plan_resolved(
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    thread_id: &str,
+    tool_use_id: &str,
+    decision: &str,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": "plan/resolved",
+            "params": {
+                "threadId": thread_id,
+                "toolUseId": tool_use_id,
+                "decision": decision,
+            }
+        }),
+    });
+}
+
+/// The plan is answered inside the turn that proposed it, which keeps running.
+async fn turn_response(session: &WorkspaceSession, thread_id: &str) -> Value {
+    let turn_id = session
+        .live_turn(thread_id)
+        .await
+        .map(|live| live.turn_id)
+        .unwrap_or_default();
+    json!({
+        "result": {
+            "turn": { "id": turn_id, "threadId": thread_id }
+        }
+    })
+}
+
+/// Approve a plan proposed through ExitPlanMode and let the agent implement it.
+#[tauri::command]
+pub(crate) async fn approve_plan(
+    workspace_id: String,
+    thread_id: String,
+    tool_use_id: String,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "approve_plan",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "toolUseId": tool_use_id,
+                "accessMode": access_mode,
+            }),
+        )
+        .await;
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    let pending = take_plan_request(&session, &thread_id, &tool_use_id).await?;
+    let permission_mode = stored_permission_mode(approved_access_mode(access_mode.as_deref()));
+    session
+        .send_control_response(
+            &thread_id,
+            &pending.request_id,
+            build_approval_response(&pending.tool_input, &permission_mode),
+        )
+        .await?;
+    session
+        .set_persistent_session_permission_mode(&thread_id, &permission_mode)
+        .await;
+    let event_sink = TauriEventSink::new(app);
+    emit_plan_resolved(&event_sink, &workspace_id, &thread_id, &tool_use_id, "approved");
+    Ok(turn_response(&session, &thread_id).await)
+}
+
+/// Reject a proposed plan; the agent stays in plan mode and revises it.
+#[tauri::command]
+pub(crate) async fn reject_plan(
+    workspace_id: String,
+    thread_id: String,
+    tool_use_id: String,
+    feedback: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "reject_plan",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "toolUseId": tool_use_id,
+                "feedback": feedback,
+            }),
+        )
+        .await;
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    let pending = take_plan_request(&session, &thread_id, &tool_use_id).await?;
+    session
+        .send_control_response(
+            &thread_id,
+            &pending.request_id,
+            build_rejection_response(feedback.as_deref()),
+        )
+        .await?;
+    let event_sink = TauriEventSink::new(app);
+    emit_plan_resolved(&event_sink, &workspace_id, &thread_id, &tool_use_id, "rejected");
+    Ok(turn_response(&session, &thread_id).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_proposed_plan_requires_text() {
+        assert_eq!(
+            extract_proposed_plan(&json!({ "plan": "  ## Steps\n1. Do it  " })).as_deref(),
+            Some("## Steps\n1. Do it")
+        );
+        assert!(extract_proposed_plan(&json!({ "plan": "   " })).is_none());
+        assert!(extract_proposed_plan(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn approved_access_mode_leaves_plan_mode() {
+        assert_eq!(approved_access_mode(None), "current");
+        assert_eq!(approved_access_mode(Some("read-only")), "current");
+        assert_eq!(approved_access_mode(Some("full-access")), "full-access");
+    }
+
+    #[test]
+    fn rejection_message_includes_feedback() {
+        assert!(!build_rejection_message(None).contains("Feedback"));
+        assert!(build_rejection_message(Some("Add tests first")).ends_with("Add tests first"));
+    }
+
+    #[test]
+    fn plan_responses_answer_the_permission_request() {
+        let input = json!({ "plan": "1. Do it" });
+        let approval = build_approval_response(&input, "default");
+        assert_eq!(approval["behavior"], "allow");
+        assert_eq!(approval["updatedInput"], input);
+        assert_eq!(approval["updatedPermissions"][0]["mode"], "default");
+
+        let rejection = build_rejection_response(Some("Add tests first"));
+        assert_eq!(rejection["behavior"], "deny");
+        assert!(rejection["message"].as_str().unwrap().ends_with("Add tests first"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+static CLIENT_ID: OnceLock<String> = OnceLock::new();
+
+/// Who is looking at which thread, and whether they are typing. The daemon
+/// rebroadcasts these to other clients as `presence/updated` events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Presence {
+    pub(crate) client_id: String,
+    pub(crate) name: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) typing: bool,
+    pub(crate) at: i64,
+}
+
+/// Random per-process id so a client can drop its own echoed updates.
+fn client_id() -> &'static str {
+    CLIENT_ID.get_or_init(|| Uuid::new_v4().to_string())
+}
+
+fn client_name_from(user: Option<String>) -> String {
+    user.map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "Someone".to_string())
+}
+
+fn client_name() -> String {
+    client_name_from(
+        std::env::var("USER")
+            .ok()
+            .or_else(|| std::env::var("USERNAME").ok()),
+    )
+}
+
+/// Announce this client's viewing and typing state. It is forwarded only in
+/// remote mode with `presenceSharingEnabled` set, and the daemon's error is
+/// returned when it does not handle presence. Otherwise the presence is just
+/// returned, so the caller learns its own client id.
+#[tauri::command]
+pub(crate) async fn presence_update(
+    workspace_id: String,
+    thread_id: Option<String>,
+    typing: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Presence, String> {
+    let presence = Presence {
+        client_id: client_id().to_string(),
+        name: client_name(),
+        workspace_id,
+        thread_id,
+        typing: typing.unwrap_or(false),
+        at: now_millis(),
+    };
+    let sharing = state.app_settings.lock().await.presence_sharing_enabled;
+    if sharing && remote_backend::is_remote_mode(&*state).await {
+        let params = serde_json::to_value(&presence).map_err(|err| err.to_string())?;
+        remote_backend::call_remote(&*state, app, "presence_update", params).await?;
+    }
+    Ok(presence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_name_falls_back_when_unset() {
+        assert_eq!(client_name_from(Some(" ana ".to_string())), "ana");
+        assert_eq!(client_name_from(Some("  ".to_string())), "Someone");
+        assert_eq!(client_name_from(None), "Someone");
+        assert_eq!(client_id(), client_id());
+    }
+}
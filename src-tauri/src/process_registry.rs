@@ -0,0 +1,282 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+const REGISTRY_FILE: &str = "session_processes.json";
+/// How far a process's start time may drift from the recorded spawn time and
+/// still be treated as ours rather than a reused pid.
+const START_TIME_TOLERANCE_MS: i64 = 10_000;
+
+static REGISTRY_PATH: OnceLock<PathBuf> = OnceLock::new();
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+/// A Claude CLI process spawned for a persistent session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpawnedProcess {
+    pub(crate) pid: u32,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) started_at: i64,
+}
+
+/// What startup recovery found in the registry left by the previous run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecoveryReport {
+    /// Orphans that were still running and have been terminated.
+    pub(crate) killed: Vec<SpawnedProcess>,
+    /// Processes that had already exited.
+    pub(crate) exited: Vec<SpawnedProcess>,
+    /// Pids now owned by an unrelated process; left alone.
+    pub(crate) skipped: Vec<SpawnedProcess>,
+    pub(crate) recovered_at: i64,
+}
+
+impl RecoveryReport {
+    fn is_empty(&self) -> bool {
+        self.killed.is_empty() && self.exited.is_empty() && self.skipped.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProcessState {
+    Gone,
+    Ours,
+    Reused,
+}
+
+fn read_registry(path: &Path) -> Vec<SpawnedProcess> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn write_registry(path: &Path, processes: &[SpawnedProcess]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(processes).map_err(|err| err.to_string())?;
+    std::fs::write(path, data).map_err(|err| err.to_string())
+}
+
+fn update_registry(update: impl FnOnce(&mut Vec<SpawnedProcess>)) {
+    let Some(path) = REGISTRY_PATH.get() else {
+        return;
+    };
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let mut processes = read_registry(path);
+    update(&mut processes);
+    if let Err(error) = write_registry(path, &processes) {
+        tracing::warn!("[process_registry] Failed to write registry: {error}");
+    }
+}
+
+/// Remember a spawned session process so a crash doesn't leak it.
+pub(crate) fn record_spawned_process(pid: u32, workspace_id: &str, thread_id: &str) {
+    update_registry(|processes| {
+        processes.retain(|process| process.pid != pid);
+        processes.push(SpawnedProcess {
+            pid,
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            started_at: now_millis(),
+        });
+    });
+}
+
+/// Drop a session process from the registry once it has been stopped.
+pub(crate) fn forget_process(pid: u32) {
+    update_registry(|processes| processes.retain(|process| process.pid != pid));
+}
+
+/// Parse ps `etime` (`[[dd-]hh:]mm:ss`) into seconds.
+fn parse_elapsed(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<i64>().ok()?, clock),
+        None => (0, value),
+    };
+    let parts: Vec<i64> = clock
+        .split(':')
+        .map(|part| part.parse::<i64>().ok())
+        .collect::<Option<_>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [minutes, seconds] => (0, *minutes, *seconds),
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        _ => return None,
+    };
+    Some(((days * 24 + hours) * 60 + minutes) * 60 + seconds)
+}
+
+fn classify_process(
+    process: &SpawnedProcess,
+    elapsed_secs: Option<i64>,
+    command: &str,
+    now_ms: i64,
+) -> ProcessState {
+    let Some(elapsed_secs) = elapsed_secs else {
+        return ProcessState::Gone;
+    };
+    let started_ms = now_ms - elapsed_secs * 1000;
+    if (started_ms - process.started_at).abs() > START_TIME_TOLERANCE_MS
+        || !command.contains("claude")
+    {
+        return ProcessState::Reused;
+    }
+    ProcessState::Ours
+}
+
+fn inspect_process(process: &SpawnedProcess) -> ProcessState {
+    let output = Command::new("ps")
+        .args(["-o", "etime=,command=", "-p", &process.pid.to_string()])
+        .output();
+    let Ok(output) = output else {
+        return ProcessState::Reused;
+    };
+    // ps exits non-zero when the pid no longer exists.
+    if !output.status.success() {
+        return ProcessState::Gone;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.trim();
+    let (elapsed, command) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    classify_process(process, parse_elapsed(elapsed), command, now_millis())
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> bool {
+    unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) == 0 }
+}
+
+#[cfg(not(unix))]
+fn terminate(_pid: u32) -> bool {
+    false
+}
+
+/// Terminate session processes left behind by a previous run and reset the registry.
+///
+/// Orphans can't be re-adopted: their stdin/stdout pipes belonged to the dead
+/// parent. Their threads resume with `--resume` on the next message instead.
+pub(crate) fn recover_orphaned_processes(data_dir: &Path) -> RecoveryReport {
+    let path = data_dir.join(REGISTRY_FILE);
+    let _ = REGISTRY_PATH.set(path.clone());
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let mut report = RecoveryReport {
+        recovered_at: now_millis(),
+        ..RecoveryReport::default()
+    };
+    for process in read_registry(&path) {
+        match inspect_process(&process) {
+            ProcessState::Gone => report.exited.push(process),
+            ProcessState::Reused => report.skipped.push(process),
+            ProcessState::Ours => {
+                if terminate(process.pid) {
+                    report.killed.push(process);
+                } else {
+                    report.skipped.push(process);
+                }
+            }
+        }
+    }
+    if let Err(error) = write_registry(&path, &[]) {
+        tracing::warn!("[process_registry] Failed to reset registry: {error}");
+    }
+    if !report.killed.is_empty() {
+        tracing::warn!(
+            "[process_registry] Terminated {} orphaned Claude processes",
+            report.killed.len()
+        );
+    }
+    report
+}
+
+/// Announce the startup recovery report as `session/recovery` if it found anything.
+pub(crate) fn emit_recovery_report(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let report = app.state::<AppState>().recovery_report.lock().await.clone();
+        let Some(report) = report.filter(|report| !report.is_empty()) else {
+            return;
+        };
+        TauriEventSink::new(app.clone()).emit_app_server_event(AppServerEvent {
+            workspace_id: String::new(),
+            message: json!({
+                "method": "session/recovery",
+                "params": report,
+            }),
+        });
+    });
+}
+
+/// The startup recovery report for this run.
+#[tauri::command]
+pub(crate) async fn get_recovery_report(
+    state: State<'_, AppState>,
+) -> Result<Option<RecoveryReport>, String> {
+    Ok(state.recovery_report.lock().await.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(started_at: i64) -> SpawnedProcess {
+        SpawnedProcess {
+            pid: 4242,
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            started_at,
+        }
+    }
+
+    #[test]
+    fn parse_elapsed_handles_all_ps_formats() {
+        assert_eq!(parse_elapsed("05:07"), Some(307));
+        assert_eq!(parse_elapsed("01:00:00"), Some(3600));
+        assert_eq!(parse_elapsed("2-00:00:01"), Some(2 * 86_400 + 1));
+        assert_eq!(parse_elapsed("bogus"), None);
+    }
+
+    #[test]
+    fn classify_process_detects_reused_pids() {
+        let now = 1_000_000_000;
+        let recorded = process(now - 60_000);
+        assert_eq!(
+            classify_process(&recorded, Some(60), "/usr/local/bin/claude --print", now),
+            ProcessState::Ours
+        );
+        assert_eq!(
+            classify_process(&recorded, Some(5), "/usr/local/bin/claude --print", now),
+            ProcessState::Reused
+        );
+        assert_eq!(
+            classify_process(&recorded, Some(60), "/usr/bin/python3", now),
+            ProcessState::Reused
+        );
+        assert_eq!(
+            classify_process(&recorded, None, "", now),
+            ProcessState::Gone
+        );
+    }
+
+    #[test]
+    fn registry_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!("process-registry-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(REGISTRY_FILE);
+        assert!(read_registry(&path).is_empty());
+        write_registry(&path, &[process(1)]).unwrap();
+        assert_eq!(read_registry(&path), vec![process(1)]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
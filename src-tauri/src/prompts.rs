@@ -25,7 +25,7 @@ fn resolve_claude_home() -> Option<PathBuf> {
     resolve_default_claude_home().and_then(|path| path.canonicalize().ok().or(Some(path)))
 }
 
-fn default_prompts_dir() -> Option<PathBuf> {
+pub(crate) fn default_prompts_dir() -> Option<PathBuf> {
     resolve_claude_home().map(|home| home.join("agents"))
 }
 
@@ -167,7 +167,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     (description, argument_hint, body)
 }
 
-fn build_prompt_contents(
+pub(crate) fn build_prompt_contents(
     description: Option<String>,
     argument_hint: Option<String>,
     content: String,
@@ -200,7 +200,7 @@ fn build_prompt_contents(
     output
 }
 
-fn sanitize_prompt_name(name: &str) -> Result<String, String> {
+pub(crate) fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err("Prompt name is required.".to_string());
@@ -214,7 +214,7 @@ fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
-fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
+pub(crate) fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
     let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
@@ -261,6 +261,21 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
     out
 }
 
+/// Workspace and global prompts without creating the prompt directories.
+pub(crate) fn discover_prompts(
+    state: &State<'_, AppState>,
+    entry: Option<&WorkspaceEntry>,
+) -> Vec<CustomPromptEntry> {
+    let mut out = Vec::new();
+    if let Some(dir) = entry.and_then(|entry| workspace_prompts_dir(state, entry).ok()) {
+        out.extend(discover_prompts_in(&dir, Some("workspace")));
+    }
+    if let Some(dir) = default_prompts_dir() {
+        out.extend(discover_prompts_in(&dir, Some("global")));
+    }
+    out
+}
+
 #[tauri::command]
 pub(crate) async fn prompts_list(
     state: State<'_, AppState>,
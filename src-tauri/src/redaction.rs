@@ -0,0 +1,185 @@
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::RedactionRule;
+
+/// One rule's effect on a prompt.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionMatch {
+    pub(crate) label: String,
+    pub(crate) count: usize,
+    /// Distinct matched values, so the user can check what is being hidden.
+    pub(crate) samples: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionPreview {
+    pub(crate) text: String,
+    pub(crate) matches: Vec<RedactionMatch>,
+}
+
+const MAX_SAMPLES: usize = 5;
+
+fn compile_rule(rule: &RedactionRule) -> Result<Regex, String> {
+    Regex::new(&rule.pattern)
+        .map_err(|err| format!("Invalid redaction pattern \"{}\": {err}", rule.label))
+}
+
+/// Check that every rule compiles before it is saved.
+pub(crate) fn validate_redaction_rules(rules: &[RedactionRule]) -> Result<(), String> {
+    for rule in rules {
+        if rule.label.trim().is_empty() {
+            return Err("Redaction rules need a label.".to_string());
+        }
+        if rule.pattern.is_empty() {
+            return Err(format!("Redaction rule \"{}\" has an empty pattern.", rule.label));
+        }
+        compile_rule(rule)?;
+    }
+    Ok(())
+}
+
+/// Apply enabled rules in order and report what each one replaced.
+pub(crate) fn apply_redactions(
+    text: &str,
+    rules: &[RedactionRule],
+) -> Result<RedactionPreview, String> {
+    let mut redacted = text.to_string();
+    let mut matches = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let regex = compile_rule(rule)?;
+        let mut count = 0;
+        let mut samples: Vec<String> = Vec::new();
+        for found in regex.find_iter(&redacted) {
+            if found.as_str().is_empty() {
+                continue;
+            }
+            count += 1;
+            if samples.len() < MAX_SAMPLES && !samples.iter().any(|s| s == found.as_str()) {
+                samples.push(found.as_str().to_string());
+            }
+        }
+        if count == 0 {
+            continue;
+        }
+        let replacement = rule
+            .replacement
+            .clone()
+            .unwrap_or_else(|| format!("[REDACTED:{}]", rule.label));
+        redacted = regex
+            .replace_all(&redacted, regex::NoExpand(&replacement))
+            .into_owned();
+        matches.push(RedactionMatch {
+            label: rule.label.clone(),
+            count,
+            samples,
+        });
+    }
+    Ok(RedactionPreview {
+        text: redacted,
+        matches,
+    })
+}
+
+pub(crate) async fn workspace_redaction_rules(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<Vec<RedactionRule>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces.get(workspace_id).ok_or("workspace not found")?;
+    Ok(entry.settings.redaction_rules.clone())
+}
+
+/// `prompt` with the workspace's redaction rules applied.
+pub(crate) async fn redact_outgoing_prompt(
+    state: &AppState,
+    workspace_id: &str,
+    prompt: &str,
+) -> Result<String, String> {
+    let rules = workspace_redaction_rules(state, workspace_id).await?;
+    Ok(apply_redactions(prompt, &rules)?.text)
+}
+
+/// Show what the workspace's redaction rules would do to a message before it is sent.
+#[tauri::command]
+pub(crate) async fn redaction_preview(
+    workspace_id: String,
+    text: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<RedactionPreview, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "redaction_preview",
+            json!({ "workspaceId": workspace_id, "text": text }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let rules = workspace_redaction_rules(&state, &workspace_id).await?;
+    apply_redactions(&text, &rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(label: &str, pattern: &str) -> RedactionRule {
+        RedactionRule {
+            label: label.to_string(),
+            pattern: pattern.to_string(),
+            enabled: true,
+            replacement: None,
+        }
+    }
+
+    #[test]
+    fn apply_redactions_masks_matches_and_reports_counts() {
+        let rules = vec![
+            rule("email", r"[\w.+-]+@example\.com"),
+            rule("ticket", r"OPS-\d+"),
+            RedactionRule {
+                enabled: false,
+                ..rule("host", r"db\d+\.internal")
+            },
+        ];
+        let preview = apply_redactions(
+            "Ask ana@example.com about OPS-12 and OPS-12 on db1.internal",
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.text,
+            "Ask [REDACTED:email] about [REDACTED:ticket] and [REDACTED:ticket] on db1.internal"
+        );
+        assert_eq!(preview.matches.len(), 2);
+        assert_eq!(preview.matches[1].count, 2);
+        assert_eq!(preview.matches[1].samples, vec!["OPS-12"]);
+    }
+
+    #[test]
+    fn apply_redactions_uses_literal_replacement() {
+        let rules = vec![RedactionRule {
+            replacement: Some("$host".to_string()),
+            ..rule("host", r"db\d+\.internal")
+        }];
+        let preview = apply_redactions("connect to db7.internal", &rules).unwrap();
+        assert_eq!(preview.text, "connect to $host");
+    }
+
+    #[test]
+    fn validate_redaction_rules_rejects_bad_patterns() {
+        assert!(validate_redaction_rules(&[rule("bad", "(")]).is_err());
+        assert!(validate_redaction_rules(&[rule(" ", "x")]).is_err());
+        assert!(validate_redaction_rules(&[rule("ok", r"\d+")]).is_ok());
+    }
+}
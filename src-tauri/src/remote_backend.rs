@@ -1,18 +1,38 @@
+use serde::Serialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot, Mutex};
 
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 use crate::types::BackendMode;
+use crate::utils::now_millis;
 
 const DEFAULT_REMOTE_HOST: &str = "127.0.0.1:4732";
 const DISCONNECTED_MESSAGE: &str = "remote backend disconnected";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const CALL_TIMEOUT: Duration = Duration::from_secs(300);
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Last known state of the remote daemon connection, kept by the heartbeat.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteHealth {
+    pub(crate) connected: bool,
+    pub(crate) latency_ms: Option<u64>,
+    pub(crate) last_error: Option<String>,
+    /// `None` until the first heartbeat in remote mode.
+    pub(crate) checked_at: Option<i64>,
+}
 
 type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, String>>>;
 
@@ -49,8 +69,29 @@ impl RemoteBackend {
             return Err(DISCONNECTED_MESSAGE.to_string());
         }
 
-        rx.await
-            .map_err(|_| DISCONNECTED_MESSAGE.to_string())?
+        match tokio::time::timeout(CALL_TIMEOUT, rx).await {
+            Ok(result) => result.map_err(|_| DISCONNECTED_MESSAGE.to_string())?,
+            Err(_) => {
+                self.inner.pending.lock().await.remove(&id);
+                Err(format!(
+                    "remote backend did not answer {method} within {}s",
+                    CALL_TIMEOUT.as_secs()
+                ))
+            }
+        }
+    }
+
+    /// Mark the connection dead and fail every call still waiting on it.
+    async fn disconnect(&self) {
+        self.inner.connected.store(false, Ordering::SeqCst);
+        fail_pending(&self.inner.pending).await;
+    }
+}
+
+async fn fail_pending(pending: &Mutex<PendingMap>) {
+    let mut pending = pending.lock().await;
+    for (_, sender) in pending.drain() {
+        let _ = sender.send(Err(DISCONNECTED_MESSAGE.to_string()));
     }
 }
 
@@ -83,22 +124,28 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
         }
     }
 
-    let (host, token) = {
+    let (host, token, tunnel_address) = {
         let settings = state.app_settings.lock().await;
         (
             settings.remote_backend_host.clone(),
             settings.remote_backend_token.clone(),
+            crate::ssh_tunnel::active_tunnel(&settings)
+                .map(crate::ssh_tunnel::tunnel_local_address),
         )
     };
 
-    let resolved_host = if host.trim().is_empty() {
+    // With a managed SSH tunnel the daemon is reached through the local forward.
+    let resolved_host = if let Some(address) = tunnel_address {
+        address
+    } else if host.trim().is_empty() {
         DEFAULT_REMOTE_HOST.to_string()
     } else {
         host
     };
 
-    let stream = TcpStream::connect(resolved_host.clone())
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(resolved_host.clone()))
         .await
+        .map_err(|_| format!("Timed out connecting to remote backend at {resolved_host}"))?
         .map_err(|err| format!("Failed to connect to remote backend at {resolved_host}: {err}"))?;
     let (reader, mut writer) = stream.into_split();
 
@@ -117,10 +164,7 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
                 || writer.write_all(b"\n").await.is_err()
             {
                 connected_for_writer.store(false, Ordering::SeqCst);
-                let mut pending = pending_for_writer.lock().await;
-                for (_, sender) in pending.drain() {
-                    let _ = sender.send(Err(DISCONNECTED_MESSAGE.to_string()));
-                }
+                fail_pending(&pending_for_writer).await;
                 break;
             }
         }
@@ -163,6 +207,19 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
     Ok(client)
 }
 
+/// Track the daemon's per-workspace `seq` and return `(expected, received)`
+/// when events were skipped, e.g. because the daemon's broadcast channel lagged.
+/// A lower seq means the daemon restarted and is not reported.
+fn remote_sequence_gap(
+    last_seqs: &mut HashMap<String, u64>,
+    workspace_id: &str,
+    seq: u64,
+) -> Option<(u64, u64)> {
+    let last = last_seqs.insert(workspace_id.to_string(), seq)?;
+    let expected = last + 1;
+    (seq > expected).then_some((expected, seq))
+}
+
 async fn read_loop(
     app: AppHandle,
     reader: tokio::net::tcp::OwnedReadHalf,
@@ -170,6 +227,7 @@ async fn read_loop(
     connected: Arc<AtomicBool>,
 ) {
     let mut lines = BufReader::new(reader).lines();
+    let mut remote_seqs: HashMap<String, u64> = HashMap::new();
 
     while let Ok(Some(line)) = lines.next_line().await {
         let trimmed = line.trim();
@@ -212,7 +270,33 @@ async fn read_loop(
         let params = message.get("params").cloned().unwrap_or(Value::Null);
         match method {
             "app-server-event" => {
-                let _ = app.emit("app-server-event", params);
+                // Go through the sink so turn bookkeeping and thread windows see remote events too.
+                match serde_json::from_value::<AppServerEvent>(params.clone()) {
+                    Ok(event) => {
+                        let event_sink = TauriEventSink::new(app.clone());
+                        // Events are renumbered locally, so surface daemon-side drops explicitly.
+                        let gap = params.get("seq").and_then(Value::as_u64).and_then(|seq| {
+                            remote_sequence_gap(&mut remote_seqs, &event.workspace_id, seq)
+                        });
+                        if let Some((expected, received)) = gap {
+                            tracing::warn!(
+                                "[remote] Missed daemon events for {}: expected seq {expected}, got {received}",
+                                event.workspace_id
+                            );
+                            event_sink.emit_app_server_event(AppServerEvent {
+                                workspace_id: event.workspace_id.clone(),
+                                message: json!({
+                                    "method": "events/gap",
+                                    "params": { "expected": expected, "received": received },
+                                }),
+                            });
+                        }
+                        event_sink.emit_app_server_event(event);
+                    }
+                    Err(_) => {
+                        let _ = app.emit("app-server-event", params);
+                    }
+                }
             }
             "terminal-output" => {
                 let _ = app.emit("terminal-output", params);
@@ -222,8 +306,131 @@ async fn read_loop(
     }
 
     connected.store(false, Ordering::SeqCst);
-    let mut pending = pending.lock().await;
-    for (_, sender) in pending.drain() {
-        let _ = sender.send(Err(DISCONNECTED_MESSAGE.to_string()));
+    fail_pending(&pending).await;
+}
+
+async fn ping(client: &RemoteBackend) -> Result<Duration, String> {
+    let started = Instant::now();
+    match tokio::time::timeout(HEARTBEAT_TIMEOUT, client.call("ping", json!({}))).await {
+        Err(_) => Err("remote backend heartbeat timed out".to_string()),
+        Ok(Err(err)) if err == DISCONNECTED_MESSAGE => Err(err),
+        // Any reply, even an error for an unknown method, means the daemon is alive.
+        Ok(_) => Ok(started.elapsed()),
+    }
+}
+
+/// Fold a heartbeat result into `health` and return the events to emit.
+fn apply_heartbeat(
+    health: &mut RemoteHealth,
+    result: Result<Duration, String>,
+    now_ms: i64,
+) -> Vec<(&'static str, Value)> {
+    let first_check = health.checked_at.is_none();
+    let was_connected = health.connected;
+    health.checked_at = Some(now_ms);
+    let mut events = Vec::new();
+    match result {
+        Ok(latency) => {
+            let latency_ms = latency.as_millis() as u64;
+            health.connected = true;
+            health.latency_ms = Some(latency_ms);
+            health.last_error = None;
+            if !was_connected {
+                events.push(("remote/connected", json!({})));
+            }
+            events.push(("remote/latency", json!({ "latencyMs": latency_ms })));
+        }
+        Err(error) => {
+            health.connected = false;
+            health.latency_ms = None;
+            health.last_error = Some(error.clone());
+            if was_connected || first_check {
+                events.push(("remote/disconnected", json!({ "error": error })));
+            }
+        }
+    }
+    events
+}
+
+async fn heartbeat_once(state: &AppState, app: AppHandle) -> Result<Duration, String> {
+    let client = ensure_remote_backend(state, app).await?;
+    let result = ping(&client).await;
+    if result.is_err() {
+        client.disconnect().await;
+        *state.remote_backend.lock().await = None;
+    }
+    result
+}
+
+/// Ping the daemon while in remote mode and report connection changes and latency.
+pub(crate) fn spawn_remote_heartbeat(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let event_sink = TauriEventSink::new(app.clone());
+        let mut ticker = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let state = app.state::<AppState>();
+            if !is_remote_mode(&state).await {
+                *state.remote_health.lock().await = RemoteHealth::default();
+                continue;
+            }
+            let result = heartbeat_once(&state, app.clone()).await;
+            let now_ms = now_millis();
+            let events = apply_heartbeat(&mut *state.remote_health.lock().await, result, now_ms);
+            for (method, params) in events {
+                event_sink.emit_app_server_event(AppServerEvent {
+                    workspace_id: String::new(),
+                    message: json!({ "method": method, "params": params }),
+                });
+            }
+        }
+    });
+}
+
+/// Current remote connection health as seen by the heartbeat.
+#[tauri::command]
+pub(crate) async fn remote_backend_health(
+    state: State<'_, AppState>,
+) -> Result<RemoteHealth, String> {
+    Ok(state.remote_health.lock().await.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_heartbeat_reports_transitions_once() {
+        let mut health = RemoteHealth::default();
+
+        let events = apply_heartbeat(&mut health, Err("refused".to_string()), 1);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, "remote/disconnected");
+        assert!(apply_heartbeat(&mut health, Err("refused".to_string()), 2).is_empty());
+
+        let events = apply_heartbeat(&mut health, Ok(Duration::from_millis(42)), 3);
+        let methods: Vec<&str> = events.iter().map(|(method, _)| *method).collect();
+        assert_eq!(methods, vec!["remote/connected", "remote/latency"]);
+        assert_eq!(health.latency_ms, Some(42));
+
+        let events = apply_heartbeat(&mut health, Ok(Duration::from_millis(7)), 4);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1["latencyMs"], 7);
+
+        let events = apply_heartbeat(&mut health, Err("timed out".to_string()), 5);
+        assert_eq!(events[0].0, "remote/disconnected");
+        assert!(!health.connected);
+        assert_eq!(health.last_error.as_deref(), Some("timed out"));
+    }
+
+    #[test]
+    fn remote_sequence_gap_reports_skipped_events_only() {
+        let mut seqs = HashMap::new();
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-1", 7), None);
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-1", 8), None);
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-2", 1), None);
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-1", 11), Some((9, 11)));
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-1", 1), None);
+        assert_eq!(remote_sequence_gap(&mut seqs, "ws-1", 2), None);
     }
 }
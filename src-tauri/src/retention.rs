@@ -0,0 +1,846 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::analytics::{read_rewinds, rewinds_path, write_rewinds};
+use crate::attachments::ATTACHMENTS_DIR_NAME;
+use crate::background_runs::{background_runs_path, remove_background_runs};
+use crate::bench::{read_bench_report, BenchReport, BENCH_DIR_NAME};
+use crate::claude::{
+    archived_threads_path, read_archived_threads, read_thread_meta, resolve_session_path,
+    thread_meta_path, workspace_entry, write_archived_threads, write_thread_meta, ThreadMeta,
+};
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::task_runner::{remove_task_thread_links, task_threads_path};
+use crate::tool_images::TOOL_IMAGES_DIR_NAME;
+use crate::turn_checkpoints::{
+    release_checkpoints, remove_turn_checkpoints, turn_checkpoints_path, TurnCheckpoint,
+};
+use crate::turn_recordings::RECORDINGS_DIR_NAME;
+use crate::turn_reports::{remove_turn_reports, turn_reports_path};
+use crate::utils::now_millis;
+
+const RETENTION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const APP_SESSIONS_FILE_NAME: &str = "app_sessions.json";
+
+static APP_SESSIONS_PATH: OnceLock<PathBuf> = OnceLock::new();
+/// Turns start concurrently, so recording a session is a locked read-modify-write.
+static APP_SESSIONS_LOCK: Mutex<()> = Mutex::new(());
+
+/// workspace_id -> ids of the Claude sessions the app created
+type AppSessions = HashMap<String, Vec<String>>;
+
+/// Counts of records removed by a purge.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PurgeReport {
+    pub(crate) task_links: usize,
+    pub(crate) background_runs: usize,
+    pub(crate) turn_reports: usize,
+    pub(crate) archived_threads: usize,
+    pub(crate) thread_titles: usize,
+    pub(crate) thread_drafts: usize,
+    pub(crate) rewinds: usize,
+    pub(crate) turn_checkpoints: usize,
+    pub(crate) turn_recordings: usize,
+    pub(crate) attachments: usize,
+    pub(crate) tool_images: usize,
+    pub(crate) bench_reports: usize,
+    pub(crate) exported_files: usize,
+    pub(crate) claude_session_files: usize,
+}
+
+/// Where each of the app's per-workspace stores lives.
+struct RecordPaths {
+    archived_threads: PathBuf,
+    thread_meta: PathBuf,
+    task_links: PathBuf,
+    background_runs: PathBuf,
+    turn_reports: PathBuf,
+    rewinds: PathBuf,
+    turn_checkpoints: PathBuf,
+    turn_recordings: PathBuf,
+    attachments: PathBuf,
+    tool_images: PathBuf,
+    bench: PathBuf,
+    exports: PathBuf,
+}
+
+impl RecordPaths {
+    fn resolve(state: &AppState) -> Result<Self, String> {
+        let data_dir = app_data_dir(state)?;
+        Ok(Self {
+            archived_threads: archived_threads_path(state)?,
+            thread_meta: thread_meta_path(state)?,
+            task_links: task_threads_path(&state.settings_path)?,
+            background_runs: background_runs_path(&state.settings_path)?,
+            turn_reports: turn_reports_path(&state.settings_path)?,
+            rewinds: rewinds_path(state)?,
+            turn_checkpoints: turn_checkpoints_path(&state.settings_path)?,
+            turn_recordings: data_dir.join(RECORDINGS_DIR_NAME),
+            attachments: data_dir.join(ATTACHMENTS_DIR_NAME),
+            tool_images: data_dir.join(TOOL_IMAGES_DIR_NAME),
+            bench: data_dir.join(BENCH_DIR_NAME),
+            exports: exports_dir(&data_dir),
+        })
+    }
+}
+
+/// Directory for artifacts the app writes on the user's behalf (reports, bundles, exports).
+pub(crate) fn exports_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("exports")
+}
+
+fn app_data_dir(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|path| path.to_path_buf())
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+fn cutoff_ms(retention_days: u32, now_ms: i64) -> i64 {
+    now_ms - i64::from(retention_days) * DAY_MS
+}
+
+/// Remember where the record of app-created sessions lives. Called once at startup.
+pub(crate) fn init_app_sessions(data_dir: &Path) {
+    let _ = APP_SESSIONS_PATH.set(data_dir.join(APP_SESSIONS_FILE_NAME));
+}
+
+fn read_app_sessions(path: &Path) -> Result<AppSessions, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+fn write_app_sessions(path: &Path, data: &AppSessions) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn add_app_session(path: &Path, workspace_id: &str, session_id: &str) -> Result<(), String> {
+    let _guard = APP_SESSIONS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut sessions = read_app_sessions(path)?;
+    let ids = sessions.entry(workspace_id.to_string()).or_default();
+    if ids.iter().any(|id| id == session_id) {
+        return Ok(());
+    }
+    ids.push(session_id.to_string());
+    write_app_sessions(path, &sessions)
+}
+
+fn take_app_sessions(path: &Path, workspace_id: &str) -> Result<Vec<String>, String> {
+    let _guard = APP_SESSIONS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut sessions = read_app_sessions(path)?;
+    let Some(ids) = sessions.remove(workspace_id) else {
+        return Ok(Vec::new());
+    };
+    write_app_sessions(path, &sessions)?;
+    Ok(ids)
+}
+
+/// Note that the app created `session_id`. Purging a workspace with
+/// `include_claude_sessions` deletes only these; sessions started from the CLI
+/// are never removed.
+pub(crate) fn record_app_session(workspace_id: &str, session_id: &str) {
+    let Some(path) = APP_SESSIONS_PATH.get() else {
+        return;
+    };
+    if let Err(error) = add_app_session(path, workspace_id, session_id) {
+        tracing::warn!("[retention] Failed to record app session: {error}");
+    }
+}
+
+fn purge_expired_task_links(path: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    remove_task_thread_links(path, |link| link.updated_at < cutoff_ms)
+}
+
+fn purge_expired_background_runs(path: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    // Running entries are kept no matter how old they are.
+    remove_background_runs(path, |run| {
+        run.status != "running" && run.started_at < cutoff_ms
+    })
+}
+
+fn purge_expired_turn_reports(path: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    remove_turn_reports(path, |report| report.created_at < cutoff_ms)
+}
+
+fn has_meta(meta: &ThreadMeta) -> bool {
+    let mut meta = meta.clone();
+    meta.updated_at = None;
+    meta != ThreadMeta::default()
+}
+
+/// Clear titles and summaries that have not changed since the cutoff. Entries
+/// saved before the change time was kept are stamped now and age from here.
+fn purge_expired_thread_titles(path: &Path, cutoff_ms: i64, now_ms: i64) -> Result<usize, String> {
+    let mut store = read_thread_meta(path)?;
+    let mut removed = 0;
+    let mut changed = false;
+    for threads in store.values_mut() {
+        for meta in threads.values_mut() {
+            match meta.updated_at {
+                None => {
+                    meta.updated_at = Some(now_ms);
+                    changed = true;
+                }
+                Some(at) if at < cutoff_ms && (meta.title.is_some() || meta.summary.is_some()) => {
+                    meta.title = None;
+                    meta.summary = None;
+                    removed += 1;
+                    changed = true;
+                }
+                Some(_) => {}
+            }
+        }
+        threads.retain(|_, meta| has_meta(meta));
+    }
+    if changed {
+        store.retain(|_, threads| !threads.is_empty());
+        write_thread_meta(path, &store)?;
+    }
+    Ok(removed)
+}
+
+fn purge_expired_rewinds(path: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    let mut log = read_rewinds(path)?;
+    let mut removed = 0;
+    for records in log.values_mut() {
+        let before = records.len();
+        records.retain(|record| record.at >= cutoff_ms);
+        removed += before - records.len();
+    }
+    if removed > 0 {
+        log.retain(|_, records| !records.is_empty());
+        write_rewinds(path, &log)?;
+    }
+    Ok(removed)
+}
+
+fn modified_before(path: &Path, cutoff_ms: i64) -> bool {
+    fs::symlink_metadata(path)
+        .ok()
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .is_some_and(|duration| (duration.as_millis() as i64) < cutoff_ms)
+}
+
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.map_err(|err| format!("Failed to remove {}: {err}", path.display()))
+}
+
+fn purge_expired_files(dir: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    purge_expired_nested(dir, 0, cutoff_ms)
+}
+
+/// Expire the entries `depth` directory levels below `dir`: 1 for stores filed
+/// per workspace (recordings, tool images), 2 for per-workspace, per-thread
+/// ones (attachments). Stray files higher up are expired as well.
+fn purge_expired_nested(dir: &Path, depth: usize, cutoff_ms: i64) -> Result<usize, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if depth > 0 && path.is_dir() {
+            removed += purge_expired_nested(&path, depth - 1, cutoff_ms)?;
+            continue;
+        }
+        if modified_before(&path, cutoff_ms) {
+            remove_path(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Remove the bench runs under `dir` whose report matches `remove`, worktrees
+/// and patches included.
+fn remove_bench_runs(dir: &Path, remove: impl Fn(&BenchReport) -> bool) -> Result<usize, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(report) = read_bench_report(&path) else {
+            continue;
+        };
+        if remove(&report) {
+            remove_path(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn purge_expired_bench_runs(dir: &Path, cutoff_ms: i64) -> Result<usize, String> {
+    // Running benches are kept no matter how old they are.
+    remove_bench_runs(dir, |report| {
+        report.status != "running" && report.finished_at.unwrap_or(report.started_at) < cutoff_ms
+    })
+}
+
+/// Remove app-side records older than `retention_days`, returning the
+/// checkpoints that were dropped so their git refs can be released.
+///
+/// Only data the app itself wrote is touched; the CLI's session files are left
+/// alone. Drafts live in memory and are expired by the caller.
+fn purge_expired_records(
+    paths: &RecordPaths,
+    retention_days: u32,
+    now_ms: i64,
+) -> Result<(PurgeReport, Vec<TurnCheckpoint>), String> {
+    let cutoff_ms = cutoff_ms(retention_days, now_ms);
+    let checkpoints = remove_turn_checkpoints(&paths.turn_checkpoints, |entry| {
+        entry.started_at < cutoff_ms
+    })?;
+    let report = PurgeReport {
+        task_links: purge_expired_task_links(&paths.task_links, cutoff_ms)?,
+        background_runs: purge_expired_background_runs(&paths.background_runs, cutoff_ms)?,
+        turn_reports: purge_expired_turn_reports(&paths.turn_reports, cutoff_ms)?,
+        thread_titles: purge_expired_thread_titles(&paths.thread_meta, cutoff_ms, now_ms)?,
+        rewinds: purge_expired_rewinds(&paths.rewinds, cutoff_ms)?,
+        turn_checkpoints: checkpoints.len(),
+        turn_recordings: purge_expired_nested(&paths.turn_recordings, 1, cutoff_ms)?,
+        attachments: purge_expired_nested(&paths.attachments, 2, cutoff_ms)?,
+        tool_images: purge_expired_nested(&paths.tool_images, 1, cutoff_ms)?,
+        bench_reports: purge_expired_bench_runs(&paths.bench, cutoff_ms)?,
+        exported_files: purge_expired_files(&paths.exports, cutoff_ms)?,
+        ..PurgeReport::default()
+    };
+    Ok((report, checkpoints))
+}
+
+/// The workspace's own directory under `root`, when the id is a plain name.
+fn workspace_subdir(root: &Path, workspace_id: &str) -> Option<PathBuf> {
+    let mut components = Path::new(workspace_id).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Some(root.join(workspace_id)),
+        _ => None,
+    }
+}
+
+fn count_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| {
+                    let path = entry.path();
+                    if path.is_dir() {
+                        count_files(&path)
+                    } else {
+                        1
+                    }
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Delete the workspace's directory under `root`. Returns how many files it held.
+fn remove_workspace_dir(root: &Path, workspace_id: &str) -> Result<usize, String> {
+    let Some(dir) = workspace_subdir(root, workspace_id) else {
+        return Ok(0);
+    };
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    let count = count_files(&dir);
+    remove_path(&dir)?;
+    Ok(count)
+}
+
+/// Remove every app-side record about a workspace, returning the checkpoints
+/// that were dropped so their git refs can be released.
+fn purge_workspace_records(
+    paths: &RecordPaths,
+    workspace_id: &str,
+) -> Result<(PurgeReport, Vec<TurnCheckpoint>), String> {
+    let mut report = PurgeReport::default();
+
+    let mut archived = read_archived_threads(&paths.archived_threads)?;
+    if let Some(ids) = archived.remove(workspace_id) {
+        report.archived_threads = ids.len();
+        write_archived_threads(&paths.archived_threads, &archived)?;
+    }
+
+    let mut thread_meta = read_thread_meta(&paths.thread_meta)?;
+    if let Some(threads) = thread_meta.remove(workspace_id) {
+        report.thread_titles = threads.len();
+        write_thread_meta(&paths.thread_meta, &thread_meta)?;
+    }
+
+    report.task_links =
+        remove_task_thread_links(&paths.task_links, |link| link.workspace_id == workspace_id)?;
+
+    report.background_runs = remove_background_runs(&paths.background_runs, |run| {
+        run.workspace_id == workspace_id
+    })?;
+
+    report.turn_reports = remove_turn_reports(&paths.turn_reports, |entry| {
+        entry.workspace_id == workspace_id
+    })?;
+
+    let mut rewinds = read_rewinds(&paths.rewinds)?;
+    if let Some(records) = rewinds.remove(workspace_id) {
+        report.rewinds = records.len();
+        write_rewinds(&paths.rewinds, &rewinds)?;
+    }
+
+    let checkpoints = remove_turn_checkpoints(&paths.turn_checkpoints, |entry| {
+        entry.workspace_id == workspace_id
+    })?;
+    report.turn_checkpoints = checkpoints.len();
+
+    report.turn_recordings = remove_workspace_dir(&paths.turn_recordings, workspace_id)?;
+    report.attachments = remove_workspace_dir(&paths.attachments, workspace_id)?;
+    report.tool_images = remove_workspace_dir(&paths.tool_images, workspace_id)?;
+    report.bench_reports =
+        remove_bench_runs(&paths.bench, |report| report.workspace_id == workspace_id)?;
+
+    Ok((report, checkpoints))
+}
+
+/// Delete the given session transcripts and the per-session directories
+/// (subagents, tool results) next to them. Nothing else in the project
+/// directory is touched.
+fn remove_session_files(paths: &[PathBuf]) -> Result<usize, String> {
+    let mut removed = 0;
+    for path in paths {
+        if !path.is_file() {
+            continue;
+        }
+        fs::remove_file(path)
+            .map_err(|err| format!("Failed to remove {}: {err}", path.display()))?;
+        removed += 1;
+        let session_dir = path.with_extension("");
+        if session_dir.is_dir() {
+            fs::remove_dir_all(&session_dir)
+                .map_err(|err| format!("Failed to remove {}: {err}", session_dir.display()))?;
+        }
+    }
+    Ok(removed)
+}
+
+async fn run_retention_pass(state: &AppState) -> Result<Option<PurgeReport>, String> {
+    let retention_days = state.app_settings.lock().await.data_retention_days;
+    let Some(retention_days) = retention_days else {
+        return Ok(None);
+    };
+    let paths = RecordPaths::resolve(state)?;
+    let now_ms = now_millis();
+    let (mut report, checkpoints) =
+        tokio::task::spawn_blocking(move || purge_expired_records(&paths, retention_days, now_ms))
+            .await
+            .map_err(|err| err.to_string())??;
+    release_checkpoints(state, checkpoints).await;
+    report.thread_drafts = state
+        .thread_drafts
+        .forget_saved_before(cutoff_ms(retention_days, now_ms))
+        .await;
+    Ok(Some(report))
+}
+
+/// Apply the retention policy at startup and then periodically.
+pub(crate) fn spawn_retention_job(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(RETENTION_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let state = app.state::<AppState>();
+            if let Err(error) = run_retention_pass(&state).await {
+                tracing::warn!("[retention] purge failed: {error}");
+            }
+        }
+    });
+}
+
+/// Apply the configured retention policy now.
+#[tauri::command]
+pub(crate) async fn data_retention_purge(
+    state: State<'_, AppState>,
+) -> Result<PurgeReport, String> {
+    Ok(run_retention_pass(&state).await?.unwrap_or_default())
+}
+
+/// Scrub every app-side record about a workspace.
+///
+/// The CLI's own session files are only removed when `include_claude_sessions`
+/// is set, and then only the sessions the app created. The audit log is kept.
+#[tauri::command]
+pub(crate) async fn purge_workspace_data(
+    workspace_id: String,
+    include_claude_sessions: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PurgeReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "purge_workspace_data",
+            json!({
+                "workspaceId": workspace_id,
+                "includeClaudeSessions": include_claude_sessions,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let session_paths = if include_claude_sessions.unwrap_or(false) {
+        let entry = workspace_entry(&state, &workspace_id).await?;
+        let sessions_path = APP_SESSIONS_PATH
+            .get()
+            .ok_or("Unable to resolve app data dir.")?;
+        let ids = read_app_sessions(sessions_path)?
+            .remove(&workspace_id)
+            .unwrap_or_default();
+        let paths: Vec<PathBuf> = ids
+            .iter()
+            .filter_map(|id| resolve_session_path(&entry, id))
+            .collect();
+        Some((sessions_path.clone(), paths))
+    } else {
+        None
+    };
+    let paths = RecordPaths::resolve(&state)?;
+    let target_id = workspace_id.clone();
+
+    let (mut report, checkpoints) = tokio::task::spawn_blocking(move || {
+        let (mut report, checkpoints) = purge_workspace_records(&paths, &target_id)?;
+        if let Some((sessions_path, session_paths)) = session_paths {
+            report.claude_session_files = remove_session_files(&session_paths)?;
+            take_app_sessions(&sessions_path, &target_id)?;
+        }
+        Ok::<_, String>((report, checkpoints))
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+    release_checkpoints(&state, checkpoints).await;
+    report.thread_drafts = state.thread_drafts.forget_workspace(&workspace_id).await;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task_runner::{
+        read_task_threads, write_task_threads, TaskThreadLink, TaskThreadLinks,
+    };
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claude-retention-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn record_paths(dir: &Path) -> RecordPaths {
+        RecordPaths {
+            archived_threads: dir.join("archived_threads.json"),
+            thread_meta: dir.join("thread_meta.json"),
+            task_links: dir.join("task_threads.json"),
+            background_runs: dir.join("background_runs.json"),
+            turn_reports: dir.join("turn_reports.json"),
+            rewinds: dir.join("rewinds.json"),
+            turn_checkpoints: dir.join("turn_checkpoints.json"),
+            turn_recordings: dir.join(RECORDINGS_DIR_NAME),
+            attachments: dir.join(ATTACHMENTS_DIR_NAME),
+            tool_images: dir.join(TOOL_IMAGES_DIR_NAME),
+            bench: dir.join(BENCH_DIR_NAME),
+            exports: exports_dir(dir),
+        }
+    }
+
+    fn write_json(path: &Path, value: serde_json::Value) {
+        fs::write(path, serde_json::to_string(&value).unwrap()).unwrap();
+    }
+
+    fn link(workspace_id: &str, updated_at: i64) -> TaskThreadLink {
+        TaskThreadLink {
+            workspace_id: workspace_id.to_string(),
+            thread_id: Uuid::new_v4().to_string(),
+            last_turn_id: None,
+            status: "completed".to_string(),
+            updated_at,
+        }
+    }
+
+    fn checkpoint(workspace_id: &str, turn_id: &str, started_at: i64) -> serde_json::Value {
+        json!({
+            "workspaceId": workspace_id,
+            "turnId": turn_id,
+            "startTree": "a",
+            "endTree": "b",
+            "startedAt": started_at,
+            "completedAt": started_at,
+        })
+    }
+
+    fn write_bench_run(
+        paths: &RecordPaths,
+        workspace_id: &str,
+        status: &str,
+        started_at: i64,
+    ) -> PathBuf {
+        let run_dir = paths.bench.join(Uuid::new_v4().to_string());
+        fs::create_dir_all(run_dir.join("worktrees")).unwrap();
+        write_json(
+            &run_dir.join("report.json"),
+            json!({
+                "id": run_dir.file_name().unwrap().to_string_lossy(),
+                "workspaceId": workspace_id,
+                "status": status,
+                "startedAt": started_at,
+                "finishedAt": if status == "running" { None } else { Some(started_at) },
+                "results": [],
+            }),
+        );
+        run_dir
+    }
+
+    #[test]
+    fn purge_expired_records_drops_old_task_links() {
+        let dir = temp_dir();
+        let paths = record_paths(&dir);
+        let mut links: TaskThreadLinks = HashMap::new();
+        let list = links.entry("list".to_string()).or_default();
+        list.insert("1".to_string(), link("ws", 0));
+        list.insert("2".to_string(), link("ws", 10 * DAY_MS));
+        write_task_threads(&paths.task_links, &links).unwrap();
+
+        let (report, _) = purge_expired_records(&paths, 5, 12 * DAY_MS).unwrap();
+        assert_eq!(report.task_links, 1);
+        let remaining = read_task_threads(&paths.task_links).unwrap();
+        assert!(remaining["list"].contains_key("2"));
+        assert!(!remaining["list"].contains_key("1"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn purge_expired_records_covers_titles_rewinds_checkpoints_and_recordings() {
+        let dir = temp_dir();
+        let paths = record_paths(&dir);
+        write_json(
+            &paths.thread_meta,
+            json!({ "ws": {
+                "old": { "title": "Old", "updatedAt": 0 },
+                "pinned": { "title": "Pinned", "pinnedAt": 1, "updatedAt": 0 },
+                "new": { "title": "New", "updatedAt": 10 * DAY_MS },
+                "legacy": { "title": "Legacy" },
+            }}),
+        );
+        write_json(
+            &paths.rewinds,
+            json!({ "ws": [
+                { "threadId": "t", "messageId": "m1", "at": 0 },
+                { "threadId": "t", "messageId": "m2", "at": 10 * DAY_MS },
+            ]}),
+        );
+        write_json(
+            &paths.turn_checkpoints,
+            json!({ "t": [checkpoint("ws", "old", 0), checkpoint("ws", "new", 10 * DAY_MS)] }),
+        );
+        let recordings = paths.turn_recordings.join("ws");
+        fs::create_dir_all(&recordings).unwrap();
+        fs::write(recordings.join("turn-1.jsonl"), "{}").unwrap();
+
+        let now = 12 * DAY_MS;
+        // The recording was just written, so only a cutoff in the future expires it.
+        let (report, _) = purge_expired_records(&paths, 5, now).unwrap();
+        assert_eq!(report.thread_titles, 2);
+        assert_eq!(report.rewinds, 1);
+        assert_eq!(report.turn_checkpoints, 1);
+        assert_eq!(report.turn_recordings, 0);
+
+        let meta = read_thread_meta(&paths.thread_meta).unwrap();
+        assert!(!meta["ws"].contains_key("old"));
+        assert_eq!(meta["ws"]["pinned"].title, None);
+        assert_eq!(meta["ws"]["pinned"].pinned_at, Some(1));
+        assert_eq!(meta["ws"]["new"].title.as_deref(), Some("New"));
+        assert_eq!(meta["ws"]["legacy"].updated_at, Some(now));
+        assert_eq!(read_rewinds(&paths.rewinds).unwrap()["ws"].len(), 1);
+
+        let far_future = now_millis() + 10 * DAY_MS;
+        let (report, _) = purge_expired_records(&paths, 1, far_future).unwrap();
+        assert_eq!(report.turn_recordings, 1);
+        assert!(!recordings.join("turn-1.jsonl").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn purge_expired_records_covers_attachments_tool_images_and_bench_runs() {
+        let dir = temp_dir();
+        let paths = record_paths(&dir);
+        let attachments = paths.attachments.join("ws").join("thread");
+        fs::create_dir_all(&attachments).unwrap();
+        fs::write(attachments.join("a.png"), "png").unwrap();
+        let images = paths.tool_images.join("ws");
+        fs::create_dir_all(&images).unwrap();
+        fs::write(images.join("b.png"), "png").unwrap();
+        // Cache files written before the cache was split per workspace.
+        fs::write(paths.tool_images.join("legacy.png"), "png").unwrap();
+        let old_run = write_bench_run(&paths, "ws", "completed", 0);
+        let running = write_bench_run(&paths, "ws", "running", 0);
+        let new_run = write_bench_run(&paths, "ws", "completed", 10 * DAY_MS);
+
+        let (report, _) = purge_expired_records(&paths, 5, 12 * DAY_MS).unwrap();
+        assert_eq!(report.bench_reports, 1);
+        assert_eq!(report.attachments, 0);
+        assert_eq!(report.tool_images, 0);
+        assert!(!old_run.exists());
+        assert!(running.exists());
+        assert!(new_run.exists());
+
+        let far_future = now_millis() + 10 * DAY_MS;
+        let (report, _) = purge_expired_records(&paths, 1, far_future).unwrap();
+        assert_eq!(report.attachments, 1);
+        assert_eq!(report.tool_images, 2);
+        assert!(!attachments.join("a.png").exists());
+        assert!(!images.join("b.png").exists());
+        assert!(!paths.tool_images.join("legacy.png").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn purge_workspace_records_only_touches_that_workspace() {
+        let dir = temp_dir();
+        let paths = record_paths(&dir);
+        let mut archived = HashMap::new();
+        archived.insert("ws-a".to_string(), vec!["t1".to_string(), "t2".to_string()]);
+        archived.insert("ws-b".to_string(), vec!["t3".to_string()]);
+        write_archived_threads(&paths.archived_threads, &archived).unwrap();
+        let mut links: TaskThreadLinks = HashMap::new();
+        let list = links.entry("list".to_string()).or_default();
+        list.insert("1".to_string(), link("ws-a", 1));
+        list.insert("2".to_string(), link("ws-b", 1));
+        write_task_threads(&paths.task_links, &links).unwrap();
+        write_json(
+            &paths.thread_meta,
+            json!({ "ws-a": { "t1": { "title": "A" } }, "ws-b": { "t3": { "title": "B" } } }),
+        );
+        write_json(
+            &paths.rewinds,
+            json!({ "ws-a": [{ "threadId": "t1", "messageId": "m", "at": 1 }] }),
+        );
+        write_json(
+            &paths.turn_checkpoints,
+            json!({
+                "t1": [checkpoint("ws-a", "turn-a", 1)],
+                "t3": [checkpoint("ws-b", "turn-b", 1)],
+            }),
+        );
+        for workspace_id in ["ws-a", "ws-b"] {
+            let recordings = paths.turn_recordings.join(workspace_id);
+            fs::create_dir_all(&recordings).unwrap();
+            fs::write(recordings.join("turn.jsonl"), "{}").unwrap();
+            let attachments = paths.attachments.join(workspace_id).join("t");
+            fs::create_dir_all(&attachments).unwrap();
+            fs::write(attachments.join("a.png"), "png").unwrap();
+            fs::write(attachments.join("b.txt"), "txt").unwrap();
+            let images = paths.tool_images.join(workspace_id);
+            fs::create_dir_all(&images).unwrap();
+            fs::write(images.join("c.png"), "png").unwrap();
+        }
+        let bench_a = write_bench_run(&paths, "ws-a", "completed", 1);
+        let bench_b = write_bench_run(&paths, "ws-b", "completed", 1);
+
+        let (report, checkpoints) = purge_workspace_records(&paths, "ws-a").unwrap();
+        assert_eq!(report.archived_threads, 2);
+        assert_eq!(report.task_links, 1);
+        assert_eq!(report.background_runs, 0);
+        assert_eq!(report.thread_titles, 1);
+        assert_eq!(report.rewinds, 1);
+        assert_eq!(report.turn_checkpoints, 1);
+        assert_eq!(checkpoints[0].turn_id, "turn-a");
+        assert_eq!(report.turn_recordings, 1);
+        assert_eq!(report.attachments, 2);
+        assert_eq!(report.tool_images, 1);
+        assert_eq!(report.bench_reports, 1);
+        let archived = read_archived_threads(&paths.archived_threads).unwrap();
+        assert!(!archived.contains_key("ws-a"));
+        assert!(archived.contains_key("ws-b"));
+        let links = read_task_threads(&paths.task_links).unwrap();
+        assert_eq!(links["list"].len(), 1);
+        let meta = read_thread_meta(&paths.thread_meta).unwrap();
+        assert!(!meta.contains_key("ws-a") && meta.contains_key("ws-b"));
+        assert!(!paths.turn_recordings.join("ws-a").exists());
+        assert!(paths.turn_recordings.join("ws-b").exists());
+        assert!(!paths.attachments.join("ws-a").exists());
+        assert!(paths.attachments.join("ws-b").exists());
+        assert!(!paths.tool_images.join("ws-a").exists());
+        assert!(paths.tool_images.join("ws-b").exists());
+        assert!(!bench_a.exists());
+        assert!(bench_b.exists());
+        assert!(workspace_subdir(&paths.turn_recordings, "../ws-b").is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn only_sessions_the_app_created_are_removed() {
+        let dir = temp_dir();
+        let sessions_path = dir.join(APP_SESSIONS_FILE_NAME);
+        let project_dir = dir.join("project");
+        fs::create_dir_all(project_dir.join("owned").join("subagents")).unwrap();
+        fs::write(project_dir.join("owned.jsonl"), "{}").unwrap();
+        fs::write(
+            project_dir.join("owned").join("subagents").join("a.jsonl"),
+            "{}",
+        )
+        .unwrap();
+        fs::write(project_dir.join("cli.jsonl"), "{}").unwrap();
+        fs::write(project_dir.join("sessions-index.json"), "{}").unwrap();
+        add_app_session(&sessions_path, "ws", "owned").unwrap();
+        add_app_session(&sessions_path, "ws", "owned").unwrap();
+
+        let ids = take_app_sessions(&sessions_path, "ws").unwrap();
+        assert_eq!(ids, ["owned"]);
+        let files: Vec<PathBuf> = ids
+            .iter()
+            .map(|id| project_dir.join(format!("{id}.jsonl")))
+            .collect();
+        assert_eq!(remove_session_files(&files).unwrap(), 1);
+        assert!(!project_dir.join("owned.jsonl").exists());
+        assert!(!project_dir.join("owned").exists());
+        assert!(project_dir.join("cli.jsonl").exists());
+        assert!(project_dir.join("sessions-index.json").exists());
+        assert!(take_app_sessions(&sessions_path, "ws").unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,172 @@
+use serde_json::{json, Value};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::turn_scheduler::TrackedTurn;
+use crate::types::GitHubReviewThread;
+
+/// Build a prompt asking the agent to address each unresolved review thread.
+pub(crate) fn build_review_comments_prompt(
+    pr_number: u64,
+    threads: &[GitHubReviewThread],
+) -> Result<String, String> {
+    if threads.is_empty() {
+        return Err(format!("No unresolved review comments on PR #{pr_number}"));
+    }
+    let mut prompt = format!(
+        "Address the unresolved review comments on pull request #{pr_number}. \
+For each thread, make the requested change in the working tree, or explain briefly \
+why no change is needed. Finish with one line per thread in the form \
+\"[thread N] addressed|skipped: summary\".\n"
+    );
+    for (index, thread) in threads.iter().enumerate() {
+        let location = match thread.line {
+            Some(line) => format!("{}:{line}", thread.path),
+            None => thread.path.clone(),
+        };
+        prompt.push_str(&format!("\n## [thread {}] {location}", index + 1));
+        if thread.is_outdated {
+            prompt.push_str(" (outdated: the code may have moved)");
+        }
+        prompt.push('\n');
+        if !thread.diff_hunk.trim().is_empty() {
+            prompt.push_str("```diff\n");
+            prompt.push_str(thread.diff_hunk.trim_end());
+            prompt.push_str("\n```\n");
+        }
+        for comment in &thread.comments {
+            let author = comment.author.as_deref().unwrap_or("unknown");
+            prompt.push_str(&format!("@{author}: {}\n", comment.body.trim()));
+        }
+    }
+    Ok(prompt)
+}
+
+fn review_comment_item_id(turn_id: &str, thread: &GitHubReviewThread) -> String {
+    format!("{turn_id}-review-{}", thread.id)
+}
+
+pub(crate) fn review_comment_item(
+    turn_id: &str,
+    thread: &GitHubReviewThread,
+    status: &str,
+) -> Value {
+    let first = thread.comments.first();
+    json!({
+        "id": review_comment_item_id(turn_id, thread),
+        "type": "reviewComment",
+        "threadId": thread.id,
+        "path": thread.path,
+        "line": thread.line,
+        "url": thread.url,
+        "author": first.and_then(|comment| comment.author.clone()),
+        "body": first.map(|comment| comment.body.clone()).unwrap_or_default(),
+        "replies": thread.comments.len().saturating_sub(1),
+        "status": status,
+    })
+}
+
+fn emit_review_items(
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    threads: &[GitHubReviewThread],
+    method: &str,
+    status: &str,
+) {
+    for thread in threads {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": method,
+                "params": {
+                    "threadId": thread_id,
+                    "item": review_comment_item(turn_id, thread, status),
+                }
+            }),
+        });
+    }
+}
+
+/// Show one item per review thread while the turn runs and settle them when it ends.
+pub(crate) fn track_review_comment_items(
+    event_sink: TauriEventSink,
+    workspace_id: String,
+    turn: TrackedTurn,
+    threads: Vec<GitHubReviewThread>,
+) {
+    emit_review_items(
+        &event_sink,
+        &workspace_id,
+        &turn.thread_id,
+        &turn.turn_id,
+        &threads,
+        "item/started",
+        "inProgress",
+    );
+    tokio::spawn(async move {
+        let completion = turn.completion().await;
+        let item_status = if completion.status == "completed" {
+            "completed"
+        } else {
+            "failed"
+        };
+        emit_review_items(
+            &event_sink,
+            &workspace_id,
+            &completion.thread_id,
+            &completion.turn_id,
+            &threads,
+            "item/completed",
+            item_status,
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GitHubReviewThreadComment;
+
+    fn thread(id: &str, line: Option<u64>) -> GitHubReviewThread {
+        GitHubReviewThread {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            line,
+            diff_hunk: "@@ -1,2 +1,2 @@\n-old\n+new".to_string(),
+            url: None,
+            is_outdated: false,
+            comments: vec![
+                GitHubReviewThreadComment {
+                    author: Some("ana".to_string()),
+                    body: "Rename this".to_string(),
+                },
+                GitHubReviewThreadComment {
+                    author: None,
+                    body: "Agreed".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn build_review_comments_prompt_numbers_threads_with_hunks() {
+        let prompt =
+            build_review_comments_prompt(9, &[thread("T1", Some(4)), thread("T2", None)]).unwrap();
+        assert!(prompt.contains("pull request #9"));
+        assert!(prompt.contains("## [thread 1] src/lib.rs:4\n```diff\n@@ -1,2 +1,2 @@"));
+        assert!(prompt.contains("## [thread 2] src/lib.rs\n"));
+        assert!(prompt.contains("@ana: Rename this\n@unknown: Agreed"));
+        assert!(build_review_comments_prompt(9, &[]).is_err());
+    }
+
+    #[test]
+    fn review_comment_item_uses_first_comment() {
+        let item = review_comment_item("turn-1", &thread("T1", Some(4)), "inProgress");
+        assert_eq!(item["id"], "turn-1-review-T1");
+        assert_eq!(item["type"], "reviewComment");
+        assert_eq!(item["author"], "ana");
+        assert_eq!(item["replies"], 1);
+    }
+}
@@ -0,0 +1,440 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::Command;
+
+use crate::backend::claude_cli::build_claude_path_env;
+use crate::claude_home::{resolve_default_claude_home, resolve_home_dir};
+use crate::types::{SandboxSettings, SandboxTool};
+use crate::utils::find_in_path;
+
+const SANDBOX_EXEC_BIN: &str = "/usr/bin/sandbox-exec";
+/// Hosts a network-restricted session may reach when `apiHosts` is empty:
+/// the API and the sign-in hosts the CLI refreshes its token against.
+const DEFAULT_API_HOSTS: &[&str] = &["api.anthropic.com", "console.anthropic.com", "claude.ai"];
+/// Largest CONNECT request head the egress proxy reads.
+const MAX_PROXY_HEAD: usize = 8 * 1024;
+
+/// Local HTTPS proxy network-restricted sessions are pointed at. One proxy
+/// serves every session, allowing the targets from the latest spawn.
+struct EgressProxy {
+    port: u16,
+    allowed: Arc<Mutex<Vec<String>>>,
+}
+
+static EGRESS_PROXY: Mutex<Option<EgressProxy>> = Mutex::new(None);
+
+fn tool_name(tool: SandboxTool) -> &'static str {
+    match tool {
+        SandboxTool::SandboxExec => "sandbox-exec",
+        SandboxTool::Bubblewrap => "bubblewrap (bwrap)",
+        SandboxTool::Firejail => "firejail",
+        SandboxTool::Auto => "auto",
+    }
+}
+
+fn resolve_sandbox_tool(tool: SandboxTool) -> Result<(SandboxTool, PathBuf), String> {
+    let resolved = match tool {
+        SandboxTool::Auto if cfg!(target_os = "macos") => SandboxTool::SandboxExec,
+        SandboxTool::Auto if cfg!(target_os = "linux") => {
+            if find_in_path("bwrap").is_some() {
+                SandboxTool::Bubblewrap
+            } else {
+                SandboxTool::Firejail
+            }
+        }
+        SandboxTool::Auto => {
+            return Err("No sandbox tool is available on this platform.".to_string());
+        }
+        other => other,
+    };
+    let bin = match resolved {
+        SandboxTool::SandboxExec => {
+            Some(PathBuf::from(SANDBOX_EXEC_BIN)).filter(|path| path.is_file())
+        }
+        SandboxTool::Bubblewrap => find_in_path("bwrap"),
+        SandboxTool::Firejail => find_in_path("firejail"),
+        SandboxTool::Auto => None,
+    };
+    let bin = bin.ok_or_else(|| {
+        format!(
+            "Full-access sandbox is enabled but {} was not found.",
+            tool_name(resolved)
+        )
+    })?;
+    Ok((resolved, bin))
+}
+
+/// Paths a sandboxed session may write: the workspace and its additional
+/// dirs, the Claude home and config, temp dirs and the configured extras.
+/// Missing paths are dropped and the rest canonicalized, since sandbox-exec
+/// matches resolved paths (`/private/var`, not `/var`).
+pub(crate) fn sandbox_writable_paths(
+    settings: &SandboxSettings,
+    workspace_path: &str,
+    add_dirs: &[PathBuf],
+) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(workspace_path)];
+    candidates.extend(add_dirs.iter().cloned());
+    candidates.extend(resolve_default_claude_home());
+    candidates.extend(resolve_home_dir().map(|home| home.join(".claude.json")));
+    candidates.push(std::env::temp_dir());
+    candidates.push(PathBuf::from("/tmp"));
+    for path in &settings.writable_paths {
+        let path = Path::new(path.trim());
+        if path.is_absolute() {
+            candidates.push(path.to_path_buf());
+        } else {
+            tracing::warn!(
+                "[sandbox] Ignoring relative writable path {}",
+                path.display()
+            );
+        }
+    }
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for path in candidates
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+    {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+fn sbpl_string(value: &Path) -> String {
+    let value = value.to_string_lossy();
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Seatbelt profile that allows everything except writes outside `writable`
+/// and, unless `allow_network`, outbound connections other than loopback.
+/// Seatbelt cannot match host names, so API traffic goes through the local
+/// egress proxy instead.
+pub(crate) fn sandbox_exec_profile(writable: &[PathBuf], allow_network: bool) -> String {
+    let mut profile = String::from("(version 1)\n(allow default)\n(deny file-write*)\n");
+    profile.push_str("(allow file-write*\n  (literal \"/dev/null\")\n  (regex #\"^/dev/tty\")");
+    for path in writable {
+        profile.push_str(&format!("\n  (subpath {})", sbpl_string(path)));
+    }
+    profile.push_str(")\n");
+    if !allow_network {
+        profile.push_str("(deny network-outbound)\n");
+        profile.push_str("(allow network-outbound (remote ip \"localhost:*\"))\n");
+    }
+    profile
+}
+
+/// Arguments for the sandbox tool, up to the program it should run.
+pub(crate) fn sandbox_args(
+    tool: SandboxTool,
+    writable: &[PathBuf],
+    allow_network: bool,
+) -> Vec<String> {
+    let mut args = Vec::new();
+    match tool {
+        SandboxTool::SandboxExec => {
+            args.push("-p".to_string());
+            args.push(sandbox_exec_profile(writable, allow_network));
+        }
+        SandboxTool::Bubblewrap => {
+            args.extend(
+                ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"].map(String::from),
+            );
+            for path in writable {
+                let path = path.to_string_lossy().to_string();
+                args.extend(["--bind".to_string(), path.clone(), path]);
+            }
+            args.extend(["--die-with-parent".to_string(), "--".to_string()]);
+        }
+        SandboxTool::Firejail => {
+            args.extend(["--quiet", "--noprofile", "--read-only=/"].map(String::from));
+            for path in writable {
+                args.push(format!("--read-write={}", path.to_string_lossy()));
+            }
+            args.push("--".to_string());
+        }
+        SandboxTool::Auto => {}
+    }
+    args
+}
+
+/// `host:port` targets the egress proxy lets through: `api_hosts` (or the
+/// defaults) on port 443, plus the host of `ANTHROPIC_BASE_URL`.
+fn allowed_api_targets(settings: &SandboxSettings) -> Vec<String> {
+    let configured: Vec<String> = settings
+        .api_hosts
+        .iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+    let hosts = if configured.is_empty() {
+        DEFAULT_API_HOSTS
+            .iter()
+            .map(|host| host.to_string())
+            .collect()
+    } else {
+        configured
+    };
+    let mut targets: Vec<String> = hosts
+        .into_iter()
+        .map(|host| {
+            if host.contains(':') {
+                host
+            } else {
+                format!("{host}:443")
+            }
+        })
+        .collect();
+    let base_url = std::env::var("ANTHROPIC_BASE_URL")
+        .ok()
+        .and_then(|value| reqwest::Url::parse(value.trim()).ok());
+    if let Some(url) = base_url {
+        if let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) {
+            let target = format!("{}:{port}", host.to_ascii_lowercase());
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    targets
+}
+
+/// The `host:port` of a `CONNECT` request head, lowercased. `None` for any
+/// other request, which the proxy refuses.
+fn parse_connect_target(head: &str) -> Option<String> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    if !parts.next()?.eq_ignore_ascii_case("CONNECT") {
+        return None;
+    }
+    let target = parts.next()?.to_ascii_lowercase();
+    target.rsplit_once(':')?.1.parse::<u16>().ok()?;
+    Some(target)
+}
+
+async fn read_proxy_head(client: &mut TcpStream) -> Result<String, String> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_PROXY_HEAD {
+            return Err("Proxy request head is too large".to_string());
+        }
+        let read = client
+            .read(&mut buffer)
+            .await
+            .map_err(|err| err.to_string())?;
+        if read == 0 {
+            return Err("Client closed the connection".to_string());
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+async fn proxy_connection(
+    mut client: TcpStream,
+    allowed: &Mutex<Vec<String>>,
+) -> Result<(), String> {
+    let head = read_proxy_head(&mut client).await?;
+    let target = parse_connect_target(&head).filter(|target| {
+        allowed
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .contains(target)
+    });
+    let Some(target) = target else {
+        let request = head.lines().next().unwrap_or_default();
+        tracing::warn!("[sandbox] Blocked outbound request: {request}");
+        client
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+            .await
+            .map_err(|err| err.to_string())?;
+        return Ok(());
+    };
+    let mut upstream = match TcpStream::connect(&target).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let _ = client
+                .write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+                .await;
+            return Err(format!("Failed to connect to {target}: {err}"));
+        }
+    };
+    client
+        .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+        .await
+        .map_err(|err| err.to_string())?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+async fn serve_egress_proxy(listener: TcpListener, allowed: Arc<Mutex<Vec<String>>>) {
+    loop {
+        let client = match listener.accept().await {
+            Ok((client, _)) => client,
+            Err(err) => {
+                tracing::warn!("[sandbox] Egress proxy failed to accept: {err}");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let allowed = Arc::clone(&allowed);
+        tokio::spawn(async move {
+            if let Err(err) = proxy_connection(client, &allowed).await {
+                tracing::debug!("[sandbox] Egress proxy connection ended: {err}");
+            }
+        });
+    }
+}
+
+/// Port of the egress proxy, started on first use, now allowing `allowed`.
+fn egress_proxy_port(allowed: Vec<String>) -> Result<u16, String> {
+    let mut proxy = EGRESS_PROXY.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(proxy) = proxy.as_ref() {
+        *proxy.allowed.lock().unwrap_or_else(|err| err.into_inner()) = allowed;
+        return Ok(proxy.port);
+    }
+    let start = || -> std::io::Result<(u16, TcpListener)> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        Ok((port, TcpListener::from_std(listener)?))
+    };
+    let (port, listener) =
+        start().map_err(|err| format!("Failed to start the sandbox egress proxy: {err}"))?;
+    let allowed = Arc::new(Mutex::new(allowed));
+    tokio::spawn(serve_egress_proxy(listener, Arc::clone(&allowed)));
+    *proxy = Some(EgressProxy { port, allowed });
+    Ok(port)
+}
+
+/// Like `build_claude_command_with_bin`, with the CLI started through the
+/// configured sandbox tool. Fails rather than run unsandboxed when the tool
+/// is missing, or when the network must be restricted and the tool cannot.
+pub(crate) fn build_sandboxed_claude_command(
+    settings: &SandboxSettings,
+    claude_bin: Option<String>,
+    writable: &[PathBuf],
+) -> Result<Command, String> {
+    let (tool, sandbox_bin) = resolve_sandbox_tool(settings.tool)?;
+    if !settings.allow_network && tool != SandboxTool::SandboxExec {
+        return Err(format!(
+            "The {} sandbox cannot limit network access to the API hosts. Turn on allowNetwork to run it without that restriction.",
+            tool_name(tool)
+        ));
+    }
+    let bin = claude_bin
+        .clone()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "claude".into());
+    let mut command = Command::new(sandbox_bin);
+    command.args(sandbox_args(tool, writable, settings.allow_network));
+    command.arg(bin);
+    if let Some(path_env) = build_claude_path_env(claude_bin.as_deref()) {
+        command.env("PATH", path_env);
+    }
+    if !settings.allow_network {
+        let proxy = format!(
+            "http://127.0.0.1:{}",
+            egress_proxy_port(allowed_api_targets(settings))?
+        );
+        for name in ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"] {
+            command.env(name, &proxy);
+        }
+        command.env_remove("NO_PROXY").env_remove("no_proxy");
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sandbox_args_limit_writes_to_given_paths() {
+        let writable = vec![PathBuf::from("/repo"), PathBuf::from("/tmp/a \"b\"")];
+
+        let profile = sandbox_exec_profile(&writable, false);
+        assert!(profile.contains("(deny file-write*)"));
+        assert!(profile.contains("(subpath \"/repo\")"));
+        assert!(profile.contains("(subpath \"/tmp/a \\\"b\\\"\")"));
+        assert!(profile.contains("(allow network-outbound (remote ip \"localhost:*\"))"));
+        assert!(!profile.contains(":443"));
+        assert!(!sandbox_exec_profile(&writable, true).contains("network-outbound"));
+
+        let bwrap = sandbox_args(SandboxTool::Bubblewrap, &writable[..1], false);
+        assert_eq!(
+            bwrap.join(" "),
+            "--ro-bind / / --dev /dev --proc /proc --bind /repo /repo --die-with-parent --"
+        );
+        let firejail = sandbox_args(SandboxTool::Firejail, &writable[..1], false);
+        assert_eq!(
+            firejail.join(" "),
+            "--quiet --noprofile --read-only=/ --read-write=/repo --"
+        );
+    }
+    fn settings(api_hosts: &[&str]) -> SandboxSettings {
+        SandboxSettings {
+            enabled: true,
+            tool: SandboxTool::Auto,
+            writable_paths: Vec::new(),
+            allow_network: false,
+            api_hosts: api_hosts.iter().map(|host| host.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn allowed_api_targets_default_to_the_anthropic_hosts() {
+        let targets = allowed_api_targets(&settings(&[]));
+        assert!(targets.contains(&"api.anthropic.com:443".to_string()));
+        let targets = allowed_api_targets(&settings(&[
+            " Gateway.Example.com ",
+            "proxy.local:8443",
+            "",
+        ]));
+        assert!(targets.starts_with(&[
+            "gateway.example.com:443".to_string(),
+            "proxy.local:8443".to_string(),
+        ]));
+        assert!(!targets.contains(&"api.anthropic.com:443".to_string()));
+    }
+
+    #[test]
+    fn parse_connect_target_accepts_only_connect_requests() {
+        assert_eq!(
+            parse_connect_target("CONNECT API.anthropic.com:443 HTTP/1.1\r\nHost: x\r\n\r\n"),
+            Some("api.anthropic.com:443".to_string())
+        );
+        assert_eq!(
+            parse_connect_target("GET http://example.com/ HTTP/1.1\r\n\r\n"),
+            None
+        );
+        assert_eq!(
+            parse_connect_target("CONNECT example.com HTTP/1.1\r\n\r\n"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn egress_proxy_refuses_hosts_outside_the_allow_list() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let allowed = Arc::new(Mutex::new(vec!["api.anthropic.com:443".to_string()]));
+        tokio::spawn(serve_egress_proxy(listener, allowed));
+
+        let mut client = TcpStream::connect(address).await.unwrap();
+        client
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 403"));
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::Mutex;
+
+use crate::types::AppSettings;
+
+const KEYCHAIN_SERVICE: &str = "com.claudecodemonitor.app";
+const REMOTE_BACKEND_TOKEN: &str = "remoteBackendToken";
+
+/// Where secret settings live instead of `settings.json`.
+pub(crate) trait SecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String>;
+    fn set(&self, key: &str, value: &str) -> Result<(), String>;
+    fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+/// The OS keychain: Keychain on macOS, Credential Manager on Windows and the
+/// Secret Service on Linux.
+pub(crate) struct KeychainStore;
+
+impl KeychainStore {
+    fn entry(key: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, key).map_err(|err| err.to_string())
+    }
+}
+
+impl SecretStore for KeychainStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        match Self::entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        Self::entry(key)?
+            .set_password(value)
+            .map_err(|err| err.to_string())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+/// Secret keys the store failed to read when settings were loaded (a locked
+/// keychain, no Secret Service). Their field is empty because the value is
+/// unknown, not because the user cleared it, so saving must not delete them.
+#[derive(Default)]
+pub(crate) struct UnreadSecrets(Mutex<Vec<&'static str>>);
+
+impl UnreadSecrets {
+    pub(crate) const fn new() -> Self {
+        Self(Mutex::new(Vec::new()))
+    }
+
+    fn set(&self, key: &'static str, unread: bool) {
+        let mut keys = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        keys.retain(|existing| *existing != key);
+        if unread {
+            keys.push(key);
+        }
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        let keys = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        keys.iter().any(|existing| *existing == key)
+    }
+}
+
+fn secret_fields(settings: &mut AppSettings) -> [(&'static str, &mut Option<String>); 1] {
+    [(REMOTE_BACKEND_TOKEN, &mut settings.remote_backend_token)]
+}
+
+/// Move secret fields into the store and blank them in `settings`, which is
+/// then safe to write as plaintext. A field the store rejects stays in place
+/// so the value is not lost. An empty field only deletes the stored secret
+/// when that secret was read successfully.
+pub(crate) fn stash_secrets(
+    store: &dyn SecretStore,
+    unread: &UnreadSecrets,
+    settings: &mut AppSettings,
+) {
+    for (key, field) in secret_fields(settings) {
+        let result = match field.as_deref().filter(|value| !value.is_empty()) {
+            Some(value) => store.set(key, value),
+            None if unread.contains(key) => {
+                tracing::warn!("[secrets] Not clearing {key}: it could not be read at startup");
+                Ok(())
+            }
+            None => store.delete(key),
+        };
+        match result {
+            Ok(()) => {
+                if field.is_some() {
+                    unread.set(key, false);
+                }
+                *field = None;
+            }
+            Err(error) => {
+                tracing::warn!("[secrets] Keeping {key} in settings.json: {error}");
+            }
+        }
+    }
+}
+
+/// Fill secret fields that `settings.json` no longer holds from the store.
+/// Returns true when the file still held a plaintext secret and should be
+/// rewritten so the secret moves to the store.
+pub(crate) fn restore_secrets(
+    store: &dyn SecretStore,
+    unread: &UnreadSecrets,
+    settings: &mut AppSettings,
+) -> bool {
+    let mut needs_migration = false;
+    for (key, field) in secret_fields(settings) {
+        if field.as_deref().is_some_and(|value| !value.is_empty()) {
+            unread.set(key, false);
+            needs_migration = true;
+            continue;
+        }
+        match store.get(key) {
+            Ok(value) => {
+                unread.set(key, false);
+                *field = value;
+            }
+            Err(error) => {
+                tracing::warn!("[secrets] Failed to read {key}: {error}");
+                unread.set(key, true);
+            }
+        }
+    }
+    needs_migration
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub(crate) struct MemoryStore {
+        pub(crate) values: Mutex<HashMap<String, String>>,
+        pub(crate) fail: bool,
+        pub(crate) fail_reads: bool,
+    }
+
+    impl SecretStore for MemoryStore {
+        fn get(&self, key: &str) -> Result<Option<String>, String> {
+            if self.fail_reads {
+                return Err("locked".to_string());
+            }
+            Ok(self.values.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &str) -> Result<(), String> {
+            if self.fail {
+                return Err("locked".to_string());
+            }
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), String> {
+            self.values.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stash_and_restore_round_trip_the_token() {
+        let store = MemoryStore::default();
+        let unread = UnreadSecrets::default();
+        let mut settings = AppSettings::default();
+        settings.remote_backend_token = Some("secret".to_string());
+
+        stash_secrets(&store, &unread, &mut settings);
+        assert!(settings.remote_backend_token.is_none());
+        assert!(!restore_secrets(&store, &unread, &mut settings));
+        assert_eq!(settings.remote_backend_token.as_deref(), Some("secret"));
+
+        settings.remote_backend_token = None;
+        stash_secrets(&store, &unread, &mut settings);
+        assert!(store.values.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failing_store_keeps_the_plaintext_value() {
+        let store = MemoryStore {
+            fail: true,
+            ..MemoryStore::default()
+        };
+        let unread = UnreadSecrets::default();
+        let mut settings = AppSettings::default();
+        settings.remote_backend_token = Some("secret".to_string());
+        stash_secrets(&store, &unread, &mut settings);
+        assert_eq!(settings.remote_backend_token.as_deref(), Some("secret"));
+        assert!(restore_secrets(&store, &unread, &mut settings));
+    }
+
+    #[test]
+    fn unreadable_secret_is_not_deleted_on_save() {
+        let mut store = MemoryStore::default();
+        store
+            .values
+            .lock()
+            .unwrap()
+            .insert(REMOTE_BACKEND_TOKEN.to_string(), "secret".to_string());
+        store.fail_reads = true;
+        let unread = UnreadSecrets::default();
+        let mut settings = AppSettings::default();
+
+        assert!(!restore_secrets(&store, &unread, &mut settings));
+        assert!(settings.remote_backend_token.is_none());
+        stash_secrets(&store, &unread, &mut settings);
+        assert_eq!(
+            store.values.lock().unwrap().get(REMOTE_BACKEND_TOKEN).map(String::as_str),
+            Some("secret")
+        );
+
+        // A new token replaces the stored one and makes later clears real again.
+        settings.remote_backend_token = Some("rotated".to_string());
+        stash_secrets(&store, &unread, &mut settings);
+        settings.remote_backend_token = None;
+        stash_secrets(&store, &unread, &mut settings);
+        assert!(store.values.lock().unwrap().is_empty());
+    }
+}
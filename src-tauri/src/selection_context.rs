@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::file_io::{read_text_file_within, FileContentKind, LineRange};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Lines shown above and below the selection.
+const CONTEXT_LINES: usize = 3;
+/// Longer selections belong in an attachment, not a prompt.
+const MAX_SELECTION_LINES: usize = 400;
+
+/// A selection formatted for a prompt, plus the pieces it was built from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SelectionContext {
+    pub(crate) path: String,
+    pub(crate) language: Option<String>,
+    /// One-based, inclusive; clamped to the end of the file.
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    /// The selected lines only.
+    pub(crate) snippet: String,
+    /// The selection with surrounding context in a fenced block, ready to paste
+    /// into a prompt.
+    pub(crate) block: String,
+}
+
+/// Fence language for a path, using the same names as the frontend highlighter.
+fn language_for_path(path: &str) -> Option<&'static str> {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    let language = match ext.to_ascii_lowercase().as_str() {
+        "bash" | "sh" => "bash",
+        "c" | "h" => "c",
+        "cpp" | "hpp" => "cpp",
+        "css" => "css",
+        "go" => "go",
+        "html" => "markup",
+        "java" => "java",
+        "js" | "mjs" => "javascript",
+        "json" => "json",
+        "jsx" => "jsx",
+        "kt" => "kotlin",
+        "md" => "markdown",
+        "py" => "python",
+        "rb" => "ruby",
+        "rs" => "rust",
+        "sass" | "scss" => "scss",
+        "swift" => "swift",
+        "toml" => "toml",
+        "ts" => "typescript",
+        "tsx" => "tsx",
+        "yaml" | "yml" => "yaml",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// A backtick fence longer than any run of backticks in `content`.
+fn fence_for(content: &str) -> String {
+    let longest = content
+        .split(|ch| ch != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Format `lines`, which start at one-based line `first_line`, as a fenced
+/// block with a gutter of line numbers. Selected lines are marked with `>`.
+fn format_block(
+    path: &str,
+    language: Option<&str>,
+    first_line: usize,
+    lines: &[&str],
+    start_line: usize,
+    end_line: usize,
+) -> String {
+    let last_line = first_line + lines.len().saturating_sub(1);
+    let width = last_line.to_string().len();
+    let body = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let number = first_line + index;
+            let marker = if (start_line..=end_line).contains(&number) {
+                '>'
+            } else {
+                ' '
+            };
+            format!("{marker} {number:>width$} | {line}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let range = if start_line == end_line {
+        format!("line {start_line}")
+    } else {
+        format!("lines {start_line}-{end_line}")
+    };
+    let fence = fence_for(&body);
+    format!(
+        "Selection from `{path}` ({range}):\n{fence}{}\n{body}\n{fence}",
+        language.unwrap_or("")
+    )
+}
+
+fn build_context(
+    root: &Path,
+    path: &str,
+    start_line: usize,
+    end_line: usize,
+) -> Result<SelectionContext, String> {
+    if start_line == 0 || end_line < start_line {
+        return Err("Selection must be a one-based line range.".to_string());
+    }
+    if end_line - start_line + 1 > MAX_SELECTION_LINES {
+        return Err(format!("Selection is longer than {MAX_SELECTION_LINES} lines."));
+    }
+    let first_line = start_line.saturating_sub(CONTEXT_LINES).max(1);
+    let range = LineRange {
+        offset: Some(first_line - 1),
+        limit: Some(end_line + CONTEXT_LINES - first_line + 1),
+    };
+    let response = read_text_file_within(root, path, range, false, "workspace", "file")?;
+    if !response.exists {
+        return Err(format!("File not found: {path}"));
+    }
+    if response.kind == FileContentKind::Binary {
+        return Err(format!("{path} is not a text file."));
+    }
+    let lines: Vec<&str> = response.content.lines().collect();
+    let last_line = first_line + lines.len();
+    if start_line >= last_line {
+        return Err(format!("{path} has fewer than {start_line} lines."));
+    }
+    let end_line = end_line.min(last_line - 1);
+    let snippet = lines[start_line - first_line..=end_line - first_line].join("\n");
+    let relative = Path::new(path)
+        .strip_prefix(root)
+        .map(|relative| relative.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string());
+    let language = language_for_path(&relative);
+    Ok(SelectionContext {
+        block: format_block(&relative, language, first_line, &lines, start_line, end_line),
+        path: relative,
+        language: language.map(str::to_string),
+        start_line,
+        end_line,
+        snippet,
+    })
+}
+
+/// Turn an editor selection into prompt context: the selected lines of a
+/// workspace file with a few lines either side, in one standard block.
+#[tauri::command]
+pub(crate) async fn build_selection_context(
+    workspace_id: String,
+    path: String,
+    start_line: usize,
+    end_line: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SelectionContext, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "build_selection_context",
+            json!({
+                "workspaceId": workspace_id,
+                "path": path,
+                "startLine": start_line,
+                "endLine": end_line,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    tokio::task::spawn_blocking(move || build_context(&root, &path, start_line, end_line))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn build_context_marks_the_selection_within_its_context() {
+        let root = std::env::temp_dir().join(format!("selection-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        let source = (1..=12)
+            .map(|line| format!("line {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(root.join("src").join("lib.rs"), source).unwrap();
+
+        let context = build_context(&root, "src/lib.rs", 2, 3).unwrap();
+        assert_eq!(context.snippet, "line 2\nline 3");
+        assert_eq!(context.language.as_deref(), Some("rust"));
+        let expected = [
+            "Selection from `src/lib.rs` (lines 2-3):",
+            "```rust",
+            "  1 | line 1",
+            "> 2 | line 2",
+            "> 3 | line 3",
+            "  4 | line 4",
+            "  5 | line 5",
+            "  6 | line 6",
+            "```",
+        ];
+        assert_eq!(context.block, expected.join("\n"));
+
+        let tail = build_context(&root, "src/lib.rs", 12, 20).unwrap();
+        assert_eq!((tail.start_line, tail.end_line), (12, 12));
+        assert!(build_context(&root, "src/lib.rs", 13, 14).is_err());
+        assert!(build_context(&root, "src/lib.rs", 0, 1).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn fence_for_outgrows_backticks_in_the_content() {
+        assert_eq!(fence_for("let x = 1;"), "```");
+        assert_eq!(fence_for("see ```rust``` and ````"), "`````");
+    }
+}
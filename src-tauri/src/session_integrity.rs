@@ -0,0 +1,591 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::{resolve_thread_transcript, root_session_id, workspace_entry};
+use crate::remote_backend;
+use crate::state::AppState;
+
+const PREVIEW_CHARS: usize = 120;
+const DEFAULT_PRUNE_THRESHOLD: usize = 32 * 1024;
+/// Smaller cuts would save little and leave placeholders larger than outputs.
+const MIN_PRUNE_THRESHOLD: usize = 4 * 1024;
+const PRUNED_PREVIEW_CHARS: usize = 2000;
+
+/// A transcript line the CLI cannot parse when resuming.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionIssue {
+    /// 1-based line number.
+    pub(crate) line: usize,
+    /// Byte offset of the line's first byte.
+    pub(crate) offset: u64,
+    pub(crate) length: usize,
+    pub(crate) error: String,
+    pub(crate) preview: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionCheck {
+    pub(crate) thread_id: String,
+    pub(crate) path: String,
+    pub(crate) total_lines: usize,
+    pub(crate) issues: Vec<SessionIssue>,
+    pub(crate) repaired: bool,
+    /// Where dropped lines were quarantined, so nothing is lost.
+    pub(crate) backup_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PruneReport {
+    pub(crate) thread_id: String,
+    pub(crate) path: String,
+    pub(crate) pruned_outputs: usize,
+    pub(crate) bytes_before: u64,
+    pub(crate) bytes_after: u64,
+    /// Where the full outputs that were cut now live.
+    pub(crate) outputs_dir: String,
+}
+
+struct Line<'a> {
+    number: usize,
+    offset: u64,
+    bytes: &'a [u8],
+    error: Option<String>,
+}
+
+fn line_error(bytes: &[u8]) -> Option<String> {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(err) => return Some(format!("invalid UTF-8: {err}")),
+    };
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(_)) => None,
+        Ok(_) => Some("not a JSON object".to_string()),
+        Err(err) if err.is_eof() => Some(format!("truncated: {err}")),
+        Err(err) => Some(err.to_string()),
+    }
+}
+
+/// Calls `visit` with every non-blank line, newline stripped. Returns the
+/// number of lines in the file.
+fn for_each_line(
+    path: &Path,
+    mut visit: impl FnMut(Line<'_>) -> Result<(), String>,
+) -> Result<usize, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+    let mut offset: u64 = 0;
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        let read = reader
+            .read_until(b'\n', &mut buffer)
+            .map_err(|err| err.to_string())?;
+        if read == 0 {
+            break;
+        }
+        number += 1;
+        let bytes = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+        let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+        if !bytes.iter().all(u8::is_ascii_whitespace) {
+            visit(Line {
+                number,
+                offset,
+                bytes,
+                error: line_error(bytes),
+            })?;
+        }
+        offset += read as u64;
+    }
+    Ok(number)
+}
+
+fn scan_session_file(path: &Path) -> Result<(usize, Vec<SessionIssue>), String> {
+    let mut issues = Vec::new();
+    let total_lines = for_each_line(path, |line| {
+        if let Some(error) = line.error {
+            issues.push(SessionIssue {
+                line: line.number,
+                offset: line.offset,
+                length: line.bytes.len(),
+                error,
+                preview: String::from_utf8_lossy(line.bytes)
+                    .chars()
+                    .take(PREVIEW_CHARS)
+                    .collect(),
+            });
+        }
+        Ok(())
+    })?;
+    Ok((total_lines, issues))
+}
+
+fn backup_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Writes `map`'s result for every line to a sibling temp file (dropping
+/// lines it maps to `None`) and returns that file; `commit_rewrite` swaps it in.
+fn rewrite_lines(
+    path: &Path,
+    mut map: impl FnMut(Line<'_>) -> Result<Option<Vec<u8>>, String>,
+) -> Result<PathBuf, String> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".rewrite");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut writer = BufWriter::new(File::create(&temp_path).map_err(|err| err.to_string())?);
+    let result = for_each_line(path, |line| {
+        let Some(bytes) = map(line)? else {
+            return Ok(());
+        };
+        writer
+            .write_all(&bytes)
+            .and_then(|_| writer.write_all(b"\n"))
+            .map_err(|err| err.to_string())
+    })
+    .and_then(|_| writer.flush().map_err(|err| err.to_string()));
+    drop(writer);
+    match result {
+        Ok(_) => Ok(temp_path),
+        Err(err) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(err)
+        }
+    }
+}
+
+fn commit_rewrite(temp_path: &Path, path: &Path) -> Result<(), String> {
+    fs::rename(temp_path, path).map_err(|err| {
+        let _ = fs::remove_file(temp_path);
+        err.to_string()
+    })
+}
+
+/// Rewrites the transcript without its malformed lines, appending those to
+/// `<file>.bak` with a header naming where each came from.
+fn repair_session_file(path: &Path) -> Result<PathBuf, String> {
+    let backup_path = backup_path_for(path);
+    let mut quarantined: Vec<u8> = Vec::new();
+    let temp_path = rewrite_lines(path, |line| {
+        let Some(error) = &line.error else {
+            return Ok(Some(line.bytes.to_vec()));
+        };
+        let header = json!({
+            "quarantinedAt": chrono::Utc::now().to_rfc3339(),
+            "line": line.number,
+            "error": error,
+        });
+        quarantined.extend_from_slice(format!("# {header}\n").as_bytes());
+        quarantined.extend_from_slice(line.bytes);
+        quarantined.push(b'\n');
+        Ok(None)
+    })?;
+
+    // Quarantine first so a failed rename never loses the dropped lines.
+    if let Err(err) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&backup_path)
+        .and_then(|mut backup| backup.write_all(&quarantined))
+    {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err.to_string());
+    }
+    commit_rewrite(&temp_path, path)?;
+    Ok(backup_path)
+}
+
+fn check_session_file(thread_id: &str, path: &Path, repair: bool) -> Result<SessionCheck, String> {
+    let (total_lines, issues) = scan_session_file(path)?;
+    let backup_path = if repair && !issues.is_empty() {
+        Some(repair_session_file(path)?)
+    } else {
+        None
+    };
+    Ok(SessionCheck {
+        thread_id: thread_id.to_string(),
+        path: path.to_string_lossy().to_string(),
+        total_lines,
+        repaired: backup_path.is_some(),
+        backup_path: backup_path.map(|path| path.to_string_lossy().to_string()),
+        issues,
+    })
+}
+
+/// Full outputs cut from `<session>.jsonl` live in `<session>.outputs/`.
+fn outputs_dir_for(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.outputs"))
+}
+
+fn output_file_name(id: &str, suffix: &str) -> String {
+    let safe: String = id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{safe}{suffix}")
+}
+
+fn result_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|block| block.get("text").and_then(|text| text.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => other.to_string(),
+    }
+}
+
+fn pruned_placeholder(text: &str, size: usize, file: &Path) -> String {
+    let preview: String = text.chars().take(PRUNED_PREVIEW_CHARS).collect();
+    format!(
+        "{preview}\n\n[Output pruned: {size} bytes moved to {}]",
+        file.display()
+    )
+}
+
+fn write_output(outputs_dir: &Path, name: &str, contents: &[u8]) -> Result<PathBuf, String> {
+    fs::create_dir_all(outputs_dir).map_err(|err| err.to_string())?;
+    let file = outputs_dir.join(name);
+    fs::write(&file, contents).map_err(|err| err.to_string())?;
+    Ok(file)
+}
+
+/// Replaces oversized tool results in one transcript line with a preview and
+/// the path of a file holding the full output. Returns how many were cut.
+fn prune_tool_results(
+    value: &mut Value,
+    threshold: usize,
+    fallback_id: &str,
+    outputs_dir: &Path,
+) -> Result<usize, String> {
+    if value.get("type").and_then(|v| v.as_str()) != Some("user") {
+        return Ok(0);
+    }
+    let mut pruned = 0;
+    let mut first_tool_use_id: Option<String> = None;
+    if let Some(blocks) = value
+        .pointer_mut("/message/content")
+        .and_then(|content| content.as_array_mut())
+    {
+        for (index, block) in blocks.iter_mut().enumerate() {
+            if block.get("type").and_then(|v| v.as_str()) != Some("tool_result") {
+                continue;
+            }
+            let id = block
+                .get("tool_use_id")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{fallback_id}-{index}"));
+            first_tool_use_id.get_or_insert_with(|| id.clone());
+            let Some(content) = block.get("content") else {
+                continue;
+            };
+            let serialized = content.to_string();
+            if serialized.len() <= threshold {
+                continue;
+            }
+            let text = result_text(content);
+            let file = match content {
+                Value::String(raw) => {
+                    write_output(outputs_dir, &output_file_name(&id, ".txt"), raw.as_bytes())?
+                }
+                _ => write_output(
+                    outputs_dir,
+                    &output_file_name(&id, ".json"),
+                    serialized.as_bytes(),
+                )?,
+            };
+            block["content"] = json!(pruned_placeholder(&text, serialized.len(), &file));
+            pruned += 1;
+        }
+    }
+
+    // The CLI repeats the output in `toolUseResult`; keep its small fields,
+    // which carry things like the subagent's agentId.
+    let id = first_tool_use_id.unwrap_or_else(|| fallback_id.to_string());
+    for key in ["toolUseResult", "tool_use_result"] {
+        let Some(result) = value.get_mut(key) else {
+            continue;
+        };
+        let serialized = result.to_string();
+        if serialized.len() <= threshold {
+            continue;
+        }
+        let file = write_output(
+            outputs_dir,
+            &output_file_name(&id, ".tool-use-result.json"),
+            serialized.as_bytes(),
+        )?;
+        *result = match &*result {
+            Value::Object(fields) => {
+                let mut slim: serde_json::Map<String, Value> = fields
+                    .iter()
+                    .filter(|(_, field)| match field {
+                        Value::String(text) => text.len() <= PRUNED_PREVIEW_CHARS,
+                        Value::Bool(_) | Value::Number(_) | Value::Null => true,
+                        _ => false,
+                    })
+                    .map(|(name, field)| (name.clone(), field.clone()))
+                    .collect();
+                slim.insert(
+                    "prunedOutputPath".to_string(),
+                    json!(file.to_string_lossy()),
+                );
+                Value::Object(slim)
+            }
+            other => json!(pruned_placeholder(
+                &result_text(other),
+                serialized.len(),
+                &file
+            )),
+        };
+        pruned += 1;
+    }
+    Ok(pruned)
+}
+
+fn prune_session_file(
+    thread_id: &str,
+    path: &Path,
+    threshold: usize,
+) -> Result<PruneReport, String> {
+    let bytes_before = fs::metadata(path).map_err(|err| err.to_string())?.len();
+    let outputs_dir = outputs_dir_for(path);
+    let mut pruned_outputs = 0;
+    let temp_path = rewrite_lines(path, |line| {
+        if line.error.is_some() || line.bytes.len() <= threshold {
+            return Ok(Some(line.bytes.to_vec()));
+        }
+        let Ok(mut value) = serde_json::from_slice::<Value>(line.bytes) else {
+            return Ok(Some(line.bytes.to_vec()));
+        };
+        let fallback_id = format!("line-{}", line.number);
+        let pruned = prune_tool_results(&mut value, threshold, &fallback_id, &outputs_dir)?;
+        if pruned == 0 {
+            return Ok(Some(line.bytes.to_vec()));
+        }
+        pruned_outputs += pruned;
+        serde_json::to_vec(&value)
+            .map(Some)
+            .map_err(|err| err.to_string())
+    })?;
+    if pruned_outputs == 0 {
+        let _ = fs::remove_file(&temp_path);
+    } else {
+        commit_rewrite(&temp_path, path)?;
+    }
+    let bytes_after = fs::metadata(path).map_err(|err| err.to_string())?.len();
+    Ok(PruneReport {
+        thread_id: thread_id.to_string(),
+        path: path.to_string_lossy().to_string(),
+        pruned_outputs,
+        bytes_before,
+        bytes_after,
+        outputs_dir: outputs_dir.to_string_lossy().to_string(),
+    })
+}
+
+/// Transcripts must not be rewritten while a CLI process is appending to them.
+async fn ensure_thread_idle(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<(), String> {
+    let session = state.sessions.lock().await.get(workspace_id).cloned();
+    if let Some(session) = session {
+        if session
+            .has_persistent_session(root_session_id(thread_id))
+            .await
+        {
+            return Err("Stop the thread's session before rewriting its transcript.".into());
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn verify_session(
+    workspace_id: String,
+    thread_id: String,
+    repair: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionCheck, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "verify_session",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "repair": repair }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let repair = repair.unwrap_or(false);
+    if repair {
+        ensure_thread_idle(&state, &workspace_id, &thread_id).await?;
+    }
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    tokio::task::spawn_blocking(move || {
+        let path = resolve_thread_transcript(&entry, &thread_id)
+            .ok_or_else(|| "Session file not found".to_string())?;
+        check_session_file(&thread_id, &path, repair)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn prune_thread_outputs(
+    workspace_id: String,
+    thread_id: String,
+    threshold_bytes: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PruneReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "prune_thread_outputs",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "thresholdBytes": threshold_bytes,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    ensure_thread_idle(&state, &workspace_id, &thread_id).await?;
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let threshold = threshold_bytes
+        .unwrap_or(DEFAULT_PRUNE_THRESHOLD)
+        .max(MIN_PRUNE_THRESHOLD);
+    tokio::task::spawn_blocking(move || {
+        let path = resolve_thread_transcript(&entry, &thread_id)
+            .ok_or_else(|| "Session file not found".to_string())?;
+        prune_session_file(&thread_id, &path, threshold)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_transcript(contents: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("session-check-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("s1.jsonl");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn scan_reports_positions_of_malformed_lines() {
+        let path = write_transcript(b"{\"type\":\"user\"}\n\n[1]\n{\"type\":\"assis");
+        let (total_lines, issues) = scan_session_file(&path).unwrap();
+        assert_eq!(total_lines, 4);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].line, 3);
+        assert_eq!(issues[0].offset, 17);
+        assert_eq!(issues[0].error, "not a JSON object");
+        assert_eq!(issues[1].line, 4);
+        assert_eq!(issues[1].offset, 21);
+        assert!(issues[1].error.starts_with("truncated"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn repair_keeps_good_lines_and_quarantines_the_rest() {
+        let path = write_transcript(b"{\"a\":1}\n{\"b\":\n{\"c\":3}\n");
+        let check = check_session_file("s1", &path, true).unwrap();
+        assert!(check.repaired);
+        assert_eq!(check.issues.len(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":1}\n{\"c\":3}\n");
+
+        let backup = fs::read_to_string(check.backup_path.unwrap()).unwrap();
+        let lines: Vec<&str> = backup.lines().collect();
+        assert!(lines[0].starts_with("# {") && lines[0].contains("\"line\":2"));
+        assert_eq!(lines[1], "{\"b\":");
+
+        let again = check_session_file("s1", &path, true).unwrap();
+        assert!(again.issues.is_empty());
+        assert!(!again.repaired);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn prune_moves_large_tool_results_into_files() {
+        let big = "x".repeat(10_000);
+        let lines = [
+            json!({"type": "assistant", "message": {"content": [{"type": "text", "text": big}]}}),
+            json!({
+                "type": "user",
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": big},
+                ]},
+                "toolUseResult": {"stdout": big, "agentId": "a1", "interrupted": false},
+            }),
+            json!({"type": "user", "message": {"content": [
+                {"type": "tool_result", "tool_use_id": "toolu_2", "content": "small"},
+            ]}}),
+        ];
+        let body: Vec<String> = lines.iter().map(Value::to_string).collect();
+        let path = write_transcript(format!("{}\n", body.join("\n")).as_bytes());
+
+        let report = prune_session_file("s1", &path, 4096).unwrap();
+        assert_eq!(report.pruned_outputs, 2);
+        assert!(report.bytes_after < report.bytes_before);
+
+        let rewritten: Vec<Value> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // Assistant text is never touched.
+        assert_eq!(rewritten[0], lines[0]);
+        let content = rewritten[1]["message"]["content"][0]["content"]
+            .as_str()
+            .unwrap();
+        assert!(content.contains("[Output pruned: 10002 bytes moved to"));
+        assert_eq!(rewritten[1]["toolUseResult"]["agentId"], "a1");
+        assert!(rewritten[1]["toolUseResult"].get("stdout").is_none());
+        assert_eq!(rewritten[2], lines[2]);
+
+        let outputs = outputs_dir_for(&path);
+        assert_eq!(
+            fs::read_to_string(outputs.join("toolu_1.txt")).unwrap(),
+            big
+        );
+        assert!(outputs.join("toolu_1.tool-use-result.json").exists());
+
+        let again = prune_session_file("s1", &path, 4096).unwrap();
+        assert_eq!(again.pruned_outputs, 0);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{ensure_workspace_session, WorkspaceSession};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcessSample {
+    pid: u32,
+    ppid: u32,
+    rss_kb: u64,
+    cpu_percent: f64,
+}
+
+/// CPU and memory of one Claude CLI session, including the processes it spawned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionResourceUsage {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) pid: u32,
+    pub(crate) process_count: usize,
+    pub(crate) rss_bytes: u64,
+    pub(crate) cpu_percent: f64,
+    pub(crate) sampled_at: i64,
+}
+
+/// Parse `ps -A -o pid=,ppid=,rss=,pcpu=` output, skipping lines that don't fit.
+fn parse_ps_output(output: &str) -> Vec<ProcessSample> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let pid = columns.next()?.parse().ok()?;
+            let ppid = columns.next()?.parse().ok()?;
+            let rss_kb = columns.next()?.parse().ok()?;
+            let cpu_percent = columns.next()?.replace(',', ".").parse().ok()?;
+            Some(ProcessSample {
+                pid,
+                ppid,
+                rss_kb,
+                cpu_percent,
+            })
+        })
+        .collect()
+}
+
+/// Sum a process and all of its descendants: (process count, rss kB, cpu %).
+fn aggregate_tree(root: u32, samples: &[ProcessSample]) -> Option<(usize, u64, f64)> {
+    let by_pid: HashMap<u32, &ProcessSample> =
+        samples.iter().map(|sample| (sample.pid, sample)).collect();
+    by_pid.get(&root)?;
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for sample in samples {
+        if sample.pid != sample.ppid {
+            children.entry(sample.ppid).or_default().push(sample.pid);
+        }
+    }
+
+    let (mut count, mut rss_kb, mut cpu) = (0, 0, 0.0);
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if let Some(sample) = by_pid.get(&pid) {
+            count += 1;
+            rss_kb += sample.rss_kb;
+            cpu += sample.cpu_percent;
+        }
+        if let Some(kids) = children.get(&pid) {
+            stack.extend(kids);
+        }
+    }
+    Some((count, rss_kb, cpu))
+}
+
+async fn sample_processes() -> Result<Vec<ProcessSample>, String> {
+    if cfg!(windows) {
+        return Err("Process resource sampling is not supported on Windows.".to_string());
+    }
+    let output = Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,rss=,pcpu="])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ps: {e}"))?;
+    if !output.status.success() {
+        return Err("ps failed to list processes.".to_string());
+    }
+    Ok(parse_ps_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+async fn collect_usage(state: &AppState) -> Result<Vec<SessionResourceUsage>, String> {
+    let sessions: Vec<(String, Arc<WorkspaceSession>)> = state
+        .sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(id, session)| (id.clone(), Arc::clone(session)))
+        .collect();
+    let mut roots = Vec::new();
+    for (workspace_id, session) in sessions {
+        for (thread_id, pid) in session.persistent_session_pids().await {
+            roots.push((workspace_id.clone(), thread_id, pid));
+        }
+    }
+    if roots.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let samples = sample_processes().await?;
+    let sampled_at = now_millis();
+    let mut usage: Vec<SessionResourceUsage> = roots
+        .into_iter()
+        .filter_map(|(workspace_id, thread_id, pid)| {
+            let (process_count, rss_kb, cpu_percent) = aggregate_tree(pid, &samples)?;
+            Some(SessionResourceUsage {
+                workspace_id,
+                thread_id,
+                pid,
+                process_count,
+                rss_bytes: rss_kb * 1024,
+                cpu_percent,
+                sampled_at,
+            })
+        })
+        .collect();
+    usage.sort_by(|a, b| b.rss_bytes.cmp(&a.rss_bytes));
+    Ok(usage)
+}
+
+/// CPU/RSS for every live Claude session, largest memory first.
+#[tauri::command]
+pub(crate) async fn session_resource_snapshot(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<SessionResourceUsage>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "session_resource_snapshot", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    collect_usage(&state).await
+}
+
+/// Kill the CLI process behind a thread; it is respawned with --resume on the next message.
+#[tauri::command]
+pub(crate) async fn kill_session_process(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "kill_session_process",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    session.kill_persistent_session(&thread_id).await
+}
+
+/// Sample session processes periodically and emit `session/resourceUsage` per workspace.
+pub(crate) fn spawn_session_resource_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let event_sink = TauriEventSink::new(app.clone());
+        let mut ticker = tokio::time::interval(RESOURCE_SAMPLE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let state = app.state::<AppState>();
+            let usage = match collect_usage(&state).await {
+                Ok(usage) => usage,
+                Err(error) => {
+                    tracing::debug!("[session_resources] sampling failed: {error}");
+                    continue;
+                }
+            };
+            let mut by_workspace: HashMap<String, Vec<SessionResourceUsage>> = HashMap::new();
+            for entry in usage {
+                by_workspace
+                    .entry(entry.workspace_id.clone())
+                    .or_default()
+                    .push(entry);
+            }
+            for (workspace_id, sessions) in by_workspace {
+                event_sink.emit_app_server_event(AppServerEvent {
+                    workspace_id,
+                    message: json!({
+                        "method": "session/resourceUsage",
+                        "params": { "sessions": sessions },
+                    }),
+                });
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ps_output_reads_columns_and_skips_noise() {
+        let samples = parse_ps_output("  10     1  2048  1.5\n 11 10 512 0,5\nbogus line\n");
+        assert_eq!(samples.len(), 2);
+        assert_eq!(
+            samples[0],
+            ProcessSample {
+                pid: 10,
+                ppid: 1,
+                rss_kb: 2048,
+                cpu_percent: 1.5
+            }
+        );
+        assert_eq!(samples[1].cpu_percent, 0.5);
+    }
+
+    #[test]
+    fn aggregate_tree_includes_descendants_only() {
+        let samples = parse_ps_output(
+            "1 0 100 0.0\n10 1 1000 10.0\n11 10 200 5.0\n12 11 50 1.0\n20 1 999 50.0\n",
+        );
+        let (count, rss_kb, cpu) = aggregate_tree(10, &samples).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(rss_kb, 1250);
+        assert!((cpu - 16.0).abs() < f64::EPSILON);
+        assert!(aggregate_tree(99, &samples).is_none());
+    }
+}
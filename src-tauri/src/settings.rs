@@ -1,6 +1,7 @@
-use tauri::{State, Window};
+use tauri::{AppHandle, Manager, State, Window};
 
 use crate::claude_config;
+use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 use crate::storage::write_settings;
 use crate::types::AppSettings;
@@ -25,18 +26,48 @@ pub(crate) async fn get_app_settings(
     Ok(settings)
 }
 
-#[tauri::command]
-pub(crate) async fn update_app_settings(
-    settings: AppSettings,
-    state: State<'_, AppState>,
-    window: Window,
+/// Persist settings and apply the parts that take effect without a reload.
+/// Accelerators and allowed workspace roots are left as saved.
+pub(crate) async fn store_app_settings(
+    mut settings: AppSettings,
+    state: &AppState,
+    app: &AppHandle,
 ) -> Result<AppSettings, String> {
     let _ = claude_config::write_collab_enabled(settings.experimental_collab_enabled);
     let _ = claude_config::write_steer_enabled(settings.experimental_steer_enabled);
     let _ = claude_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
-    write_settings(&state.settings_path, &settings)?;
+    let log_filter = crate::logging::log_filter_for(&settings)?;
+    if let Some(patterns) = &settings.dangerous_command_patterns {
+        crate::dangerous_commands::validate_dangerous_command_patterns(patterns)?;
+    }
     let mut current = state.app_settings.lock().await;
+    // Accelerators are only changed through the menu commands, which validate them.
+    settings.accelerators = current.accelerators.clone();
+    // The allow-list only changes through `set_allowed_workspace_roots`, which asks first.
+    settings.allowed_workspace_roots = current.allowed_workspace_roots.clone();
+    write_settings(&state.settings_path, &settings)?;
     *current = settings.clone();
+    drop(current);
+    crate::logging::set_log_filter(log_filter);
+    crate::turn_recordings::set_turn_recording_enabled(settings.turn_recording_enabled);
+    crate::turn_checkpoints::set_turn_checkpoints_enabled(settings.turn_checkpoints_enabled);
+    state
+        .turn_scheduler
+        .set_limit(
+            settings.max_parallel_turns,
+            &TauriEventSink::new(app.clone()),
+        )
+        .await;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub(crate) async fn update_app_settings(
+    settings: AppSettings,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<AppSettings, String> {
+    let settings = store_app_settings(settings, &state, window.app_handle()).await?;
     let _ = window::apply_window_appearance(&window, settings.theme.as_str());
     Ok(settings)
 }
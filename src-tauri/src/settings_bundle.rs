@@ -0,0 +1,550 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::claude_home::resolve_home_dir;
+use crate::menu;
+use crate::prompts;
+use crate::settings::store_app_settings;
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+use crate::workspaces::{check_allowed_workspace_path, expand_root};
+
+const BUNDLE_VERSION: u32 = 1;
+const PROFILES_DIR: &str = "settings-profiles";
+
+/// Everything needed to set the app up on another machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsBundle {
+    pub(crate) version: u32,
+    pub(crate) exported_at: i64,
+    pub(crate) settings: AppSettings,
+    #[serde(default)]
+    pub(crate) prompts: Vec<BundlePrompt>,
+    #[serde(default)]
+    pub(crate) workspaces: Vec<BundleWorkspace>,
+}
+
+/// A global prompt from the prompt library.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundlePrompt {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) argument_hint: Option<String>,
+    pub(crate) content: String,
+}
+
+/// A main workspace. Paths under the home directory are stored as `~/...`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleWorkspace {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) settings: WorkspaceSettings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportSummary {
+    pub(crate) settings: AppSettings,
+    pub(crate) prompts_imported: usize,
+    pub(crate) prompts_skipped: usize,
+    pub(crate) workspaces_imported: usize,
+    pub(crate) workspaces_skipped: usize,
+    pub(crate) accelerators_failed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsProfile {
+    pub(crate) name: String,
+    pub(crate) saved_at: i64,
+}
+
+/// Settings with the machine-specific binary path and the backend token removed.
+fn portable_settings(settings: &AppSettings) -> AppSettings {
+    let mut settings = settings.clone();
+    settings.claude_bin = None;
+    settings.remote_backend_token = None;
+    settings
+}
+
+/// Imported settings, keeping this machine's binary path and token when the
+/// bundle has none.
+fn merge_imported_settings(current: &AppSettings, imported: AppSettings) -> AppSettings {
+    let mut settings = imported;
+    if settings.claude_bin.is_none() {
+        settings.claude_bin = current.claude_bin.clone();
+    }
+    if settings.remote_backend_token.is_none() {
+        settings.remote_backend_token = current.remote_backend_token.clone();
+    }
+    settings
+}
+
+fn portable_path(path: &str, home: Option<&Path>) -> String {
+    match home.and_then(|home| Path::new(path).strip_prefix(home).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.to_string_lossy()),
+        None => path.to_string(),
+    }
+}
+
+/// Workspace settings with paths home-relative and the machine-specific
+/// startup commands and container config removed, since commands can carry
+/// credentials and containers are named per machine.
+fn portable_workspace_settings(
+    settings: &WorkspaceSettings,
+    home: Option<&Path>,
+) -> WorkspaceSettings {
+    let mut settings = settings.clone();
+    settings.git_root = settings.git_root.map(|root| portable_path(&root, home));
+    settings.additional_dirs = settings
+        .additional_dirs
+        .iter()
+        .map(|dir| portable_path(dir, home))
+        .collect();
+    settings.startup_commands = Vec::new();
+    settings.run_startup_commands = false;
+    settings.container = None;
+    settings
+}
+
+fn portable_workspaces<'a>(
+    entries: impl IntoIterator<Item = &'a WorkspaceEntry>,
+    home: Option<&Path>,
+) -> Vec<BundleWorkspace> {
+    let mut workspaces: Vec<BundleWorkspace> = entries
+        .into_iter()
+        .filter(|entry| matches!(entry.kind, WorkspaceKind::Main) && entry.parent_id.is_none())
+        .map(|entry| BundleWorkspace {
+            name: entry.name.clone(),
+            path: portable_path(&entry.path, home),
+            settings: portable_workspace_settings(&entry.settings, home),
+        })
+        .collect();
+    workspaces.sort_by(|a, b| {
+        a.settings
+            .sort_order
+            .cmp(&b.settings.sort_order)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    workspaces
+}
+
+fn global_prompts() -> Vec<BundlePrompt> {
+    let Some(dir) = prompts::default_prompts_dir() else {
+        return Vec::new();
+    };
+    prompts::discover_prompts_in(&dir, None)
+        .into_iter()
+        .map(|prompt| BundlePrompt {
+            name: prompt.name,
+            description: prompt.description,
+            argument_hint: prompt.argument_hint,
+            content: prompt.content,
+        })
+        .collect()
+}
+
+/// Write prompts that don't already exist in `dir`. Returns (imported, skipped).
+fn import_prompts(dir: &Path, bundle_prompts: &[BundlePrompt]) -> Result<(usize, usize), String> {
+    let mut imported = 0;
+    let mut skipped = 0;
+    for prompt in bundle_prompts {
+        let Ok(name) = prompts::sanitize_prompt_name(&prompt.name) else {
+            skipped += 1;
+            continue;
+        };
+        let path = dir.join(format!("{name}.md"));
+        if path.exists() {
+            skipped += 1;
+            continue;
+        }
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        let body = prompts::build_prompt_contents(
+            prompt.description.clone(),
+            prompt.argument_hint.clone(),
+            prompt.content.clone(),
+        );
+        fs::write(&path, body).map_err(|err| err.to_string())?;
+        imported += 1;
+    }
+    Ok((imported, skipped))
+}
+
+/// Register bundle workspaces whose folder exists here and isn't already added.
+/// Sessions are not started; the workspaces connect like any other on reload.
+async fn import_workspaces(
+    state: &AppState,
+    bundle_workspaces: &[BundleWorkspace],
+) -> Result<(usize, usize), String> {
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for workspace in bundle_workspaces {
+        let Some(path) = expand_root(&workspace.path).filter(|path| path.is_dir()) else {
+            skipped += 1;
+            continue;
+        };
+        let known = {
+            let workspaces = state.workspaces.lock().await;
+            workspaces
+                .values()
+                .chain(entries.iter())
+                .any(|entry| Path::new(&entry.path) == path)
+        };
+        if known || check_allowed_workspace_path(state, &path).await.is_err() {
+            skipped += 1;
+            continue;
+        }
+        let mut settings = workspace.settings.clone();
+        settings.git_root = settings
+            .git_root
+            .and_then(|root| expand_root(&root))
+            .map(|root| root.to_string_lossy().to_string());
+        settings.additional_dirs = settings
+            .additional_dirs
+            .iter()
+            .filter_map(|dir| expand_root(dir))
+            .map(|dir| dir.to_string_lossy().to_string())
+            .collect();
+        entries.push(WorkspaceEntry {
+            id: Uuid::new_v4().to_string(),
+            name: workspace.name.clone(),
+            path: path.to_string_lossy().to_string(),
+            claude_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings,
+        });
+    }
+    let imported = entries.len();
+    if imported > 0 {
+        let mut workspaces = state.workspaces.lock().await;
+        for entry in entries {
+            workspaces.insert(entry.id.clone(), entry);
+        }
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(&state.storage_path, &list)?;
+    }
+    Ok((imported, skipped))
+}
+
+async fn build_bundle(state: &AppState, include_library: bool) -> SettingsBundle {
+    let settings = portable_settings(&*state.app_settings.lock().await);
+    let (prompts, workspaces) = if include_library {
+        let workspaces = state.workspaces.lock().await;
+        let home = resolve_home_dir();
+        (
+            global_prompts(),
+            portable_workspaces(workspaces.values(), home.as_deref()),
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    SettingsBundle {
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        settings,
+        prompts,
+        workspaces,
+    }
+}
+
+async fn apply_bundle(
+    state: &AppState,
+    app: &AppHandle,
+    bundle: SettingsBundle,
+) -> Result<ImportSummary, String> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Settings bundle version {} is newer than this app supports.",
+            bundle.version
+        ));
+    }
+    let current = state.app_settings.lock().await.clone();
+    let accelerators = bundle.settings.accelerators.clone();
+    let merged = merge_imported_settings(&current, bundle.settings);
+    store_app_settings(merged, state, app).await?;
+    let report = menu::replace_accelerators(app, state, &accelerators).await?;
+
+    let (prompts_imported, prompts_skipped) = match prompts::default_prompts_dir() {
+        Some(dir) => import_prompts(&dir, &bundle.prompts)?,
+        None => (0, bundle.prompts.len()),
+    };
+    let (workspaces_imported, workspaces_skipped) =
+        import_workspaces(state, &bundle.workspaces).await?;
+    Ok(ImportSummary {
+        settings: state.app_settings.lock().await.clone(),
+        prompts_imported,
+        prompts_skipped,
+        workspaces_imported,
+        workspaces_skipped,
+        accelerators_failed: report.failed.len(),
+    })
+}
+
+fn read_bundle(path: &Path) -> Result<SettingsBundle, String> {
+    let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&data).map_err(|err| format!("Invalid settings bundle: {err}"))
+}
+
+fn write_bundle(path: &Path, bundle: &SettingsBundle) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(bundle).map_err(|err| err.to_string())?;
+    fs::write(path, data).map_err(|err| err.to_string())
+}
+
+fn profiles_dir(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .settings_path
+        .parent()
+        .map(|dir| dir.join(PROFILES_DIR))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+fn profile_path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is required.".to_string());
+    }
+    if name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err("Profile name cannot include path separators.".to_string());
+    }
+    Ok(dir.join(format!("{name}.json")))
+}
+
+fn list_profiles_in(dir: &Path) -> Vec<SettingsProfile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<SettingsProfile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            let bundle = read_bundle(&path).ok()?;
+            Some(SettingsProfile {
+                name,
+                saved_at: bundle.exported_at,
+            })
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// Export settings, global prompts and main workspaces to `path`.
+#[tauri::command]
+pub(crate) async fn export_app_settings(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<SettingsBundle, String> {
+    let bundle = build_bundle(&state, true).await;
+    write_bundle(Path::new(&path), &bundle)?;
+    Ok(bundle)
+}
+
+/// Apply a bundle written by `export_app_settings`. Existing prompts and
+/// workspaces are kept; only new ones are added.
+#[tauri::command]
+pub(crate) async fn import_app_settings(
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ImportSummary, String> {
+    let bundle = read_bundle(Path::new(&path))?;
+    apply_bundle(&state, &app, bundle).await
+}
+
+#[tauri::command]
+pub(crate) async fn list_settings_profiles(
+    state: State<'_, AppState>,
+) -> Result<Vec<SettingsProfile>, String> {
+    Ok(list_profiles_in(&profiles_dir(&state)?))
+}
+
+/// Save the current app settings under `name`, replacing an existing profile.
+#[tauri::command]
+pub(crate) async fn save_settings_profile(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<SettingsProfile, String> {
+    let path = profile_path(&profiles_dir(&state)?, &name)?;
+    let bundle = build_bundle(&state, false).await;
+    write_bundle(&path, &bundle)?;
+    Ok(SettingsProfile {
+        name: name.trim().to_string(),
+        saved_at: bundle.exported_at,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn apply_settings_profile(
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ImportSummary, String> {
+    let path = profile_path(&profiles_dir(&state)?, &name)?;
+    if !path.exists() {
+        return Err("Profile not found.".to_string());
+    }
+    let bundle = read_bundle(&path)?;
+    apply_bundle(&state, &app, bundle).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_settings_profile(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = profile_path(&profiles_dir(&state)?, &name)?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContainerMode, ContainerSettings};
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("claude-code-monitor-bundle-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn portable_settings_drop_machine_secrets() {
+        let mut current = AppSettings::default();
+        current.claude_bin = Some("/opt/claude".to_string());
+        current.remote_backend_token = Some("secret".to_string());
+
+        let exported = portable_settings(&current);
+        assert!(exported.claude_bin.is_none());
+        assert!(exported.remote_backend_token.is_none());
+
+        let merged = merge_imported_settings(&current, exported);
+        assert_eq!(merged.claude_bin.as_deref(), Some("/opt/claude"));
+        assert_eq!(merged.remote_backend_token.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn workspace_paths_are_home_relative() {
+        let home = Path::new("/home/ana");
+        let entry = |id: &str, path: &str, kind: WorkspaceKind| WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            claude_bin: Some("/opt/claude".to_string()),
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let mut app = entry("app", "/home/ana/code/app", WorkspaceKind::Main);
+        app.settings.git_root = Some("/home/ana/code".to_string());
+        app.settings.additional_dirs = vec![
+            "/home/ana/shared".to_string(),
+            "../docs".to_string(),
+            "/srv/data".to_string(),
+        ];
+        app.settings.startup_commands = vec!["API_TOKEN=secret npm run dev".to_string()];
+        app.settings.run_startup_commands = true;
+        app.settings.container = Some(ContainerSettings {
+            mode: ContainerMode::Exec,
+            target: "ana-dev".to_string(),
+            workdir: None,
+            runtime: Some("/home/ana/bin/podman".to_string()),
+            claude_bin: None,
+        });
+        let entries = vec![
+            app,
+            entry("srv", "/srv/site", WorkspaceKind::Main),
+            entry("wt", "/home/ana/code/app-wt", WorkspaceKind::Worktree),
+        ];
+        let workspaces = portable_workspaces(&entries, Some(home));
+        let paths: Vec<_> = workspaces.iter().map(|ws| ws.path.as_str()).collect();
+        assert_eq!(paths, vec!["~/code/app", "/srv/site"]);
+        let settings = &workspaces[0].settings;
+        assert_eq!(settings.git_root.as_deref(), Some("~/code"));
+        assert_eq!(
+            settings.additional_dirs,
+            vec!["~/shared", "../docs", "/srv/data"]
+        );
+        assert!(settings.startup_commands.is_empty());
+        assert!(!settings.run_startup_commands);
+        assert!(settings.container.is_none());
+        assert_eq!(portable_path("/home/ana", Some(home)), "~");
+    }
+
+    #[test]
+    fn import_prompts_skips_existing_files() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("review.md"), "keep me").unwrap();
+        let prompt = |name: &str| BundlePrompt {
+            name: name.to_string(),
+            description: Some("Summarize".to_string()),
+            argument_hint: None,
+            content: "Summarize the diff".to_string(),
+        };
+        let (imported, skipped) =
+            import_prompts(&dir, &[prompt("review"), prompt("summary"), prompt("a b")]).unwrap();
+        assert_eq!((imported, skipped), (1, 2));
+        assert_eq!(
+            fs::read_to_string(dir.join("review.md")).unwrap(),
+            "keep me"
+        );
+        assert!(fs::read_to_string(dir.join("summary.md"))
+            .unwrap()
+            .contains("description: \"Summarize\""));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn profiles_are_listed_by_name() {
+        let dir = temp_dir();
+        assert!(profile_path(&dir, " ").is_err());
+        assert!(profile_path(&dir, "../x").is_err());
+        let bundle = |exported_at| SettingsBundle {
+            version: BUNDLE_VERSION,
+            exported_at,
+            settings: AppSettings::default(),
+            prompts: Vec::new(),
+            workspaces: Vec::new(),
+        };
+        write_bundle(&profile_path(&dir, "work").unwrap(), &bundle(2)).unwrap();
+        write_bundle(&profile_path(&dir, "home").unwrap(), &bundle(1)).unwrap();
+        assert_eq!(
+            list_profiles_in(&dir),
+            vec![
+                SettingsProfile {
+                    name: "home".to_string(),
+                    saved_at: 1
+                },
+                SettingsProfile {
+                    name: "work".to_string(),
+                    saved_at: 2
+                },
+            ]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, ExitRequestApi, Manager};
+
+use crate::claude::WorkspaceSession;
+use crate::state::AppState;
+use crate::storage::{write_settings, write_workspaces};
+
+/// Upper bound on how long quitting waits for Claude processes to exit on their own.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+fn terminate(pid: u32) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(_pid: u32) {}
+
+async fn persist_state(state: &AppState) {
+    let settings = state.app_settings.lock().await.clone();
+    if let Err(error) = write_settings(&state.settings_path, &settings) {
+        tracing::warn!("[shutdown] Failed to write settings: {error}");
+    }
+    let workspaces: Vec<_> = state.workspaces.lock().await.values().cloned().collect();
+    if let Err(error) = write_workspaces(&state.storage_path, &workspaces) {
+        tracing::warn!("[shutdown] Failed to write workspaces: {error}");
+    }
+}
+
+/// SIGTERM every session process so the CLI can save its transcript, wait up to
+/// `SHUTDOWN_GRACE`, then kill whatever is left.
+async fn drain_sessions(sessions: &[Arc<WorkspaceSession>]) {
+    for session in sessions {
+        for (_, pid) in session.persistent_session_pids().await {
+            terminate(pid);
+        }
+    }
+
+    let deadline = Instant::now() + SHUTDOWN_GRACE;
+    loop {
+        let mut all_exited = true;
+        for session in sessions {
+            if !session.persistent_sessions_exited().await {
+                all_exited = false;
+                break;
+            }
+        }
+        if all_exited || Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+    }
+
+    for session in sessions {
+        let _ = session.kill_all_persistent_sessions().await;
+    }
+}
+
+async fn shutdown(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let sessions: Vec<Arc<WorkspaceSession>> =
+        state.sessions.lock().await.values().cloned().collect();
+    persist_state(&state).await;
+    drain_sessions(&sessions).await;
+}
+
+/// Hold the first exit request until active turns are drained and state is on disk,
+/// then exit for real. Later requests (including our own) pass through.
+pub(crate) fn handle_exit_requested(app: &AppHandle, code: Option<i32>, api: &ExitRequestApi) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    api.prevent_exit();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        shutdown(&app).await;
+        app.exit(code.unwrap_or(0));
+    });
+}
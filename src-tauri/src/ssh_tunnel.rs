@@ -0,0 +1,322 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+use tokio::process::Command;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::state::AppState;
+use crate::types::{AppSettings, BackendMode, SshTunnelSettings};
+
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const PROBE_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_RECONNECT_DELAY_SECS: u64 = 60;
+
+/// State of the managed SSH forward, reported as `remote/tunnel` events.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TunnelStatus {
+    /// "idle", "connecting", "connected" or "disconnected"
+    pub(crate) state: String,
+    pub(crate) local_address: Option<String>,
+    pub(crate) last_error: Option<String>,
+    pub(crate) reconnect_attempts: u32,
+}
+
+impl Default for TunnelStatus {
+    fn default() -> Self {
+        Self {
+            state: "idle".to_string(),
+            local_address: None,
+            last_error: None,
+            reconnect_attempts: 0,
+        }
+    }
+}
+
+enum TunnelExit {
+    /// Settings changed or the tunnel was turned off; restart without backoff.
+    Reconfigured,
+    Dropped { error: String, was_connected: bool },
+}
+
+/// The tunnel settings in effect, if remote mode should go through SSH.
+pub(crate) fn active_tunnel(settings: &AppSettings) -> Option<&SshTunnelSettings> {
+    if !matches!(settings.backend_mode, BackendMode::Remote) {
+        return None;
+    }
+    settings
+        .remote_ssh_tunnel
+        .as_ref()
+        .filter(|tunnel| tunnel.enabled && !tunnel.host.trim().is_empty())
+}
+
+pub(crate) fn tunnel_local_address(tunnel: &SshTunnelSettings) -> String {
+    format!("127.0.0.1:{}", tunnel.local_port)
+}
+
+fn build_ssh_args(tunnel: &SshTunnelSettings) -> Vec<String> {
+    let mut args = vec![
+        "-N".to_string(),
+        "-L".to_string(),
+        format!(
+            "127.0.0.1:{}:127.0.0.1:{}",
+            tunnel.local_port, tunnel.remote_port
+        ),
+        "-o".to_string(),
+        "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(),
+        "ServerAliveInterval=15".to_string(),
+        "-o".to_string(),
+        "ServerAliveCountMax=3".to_string(),
+        // Never hang on a password or host-key prompt nobody can answer.
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+    ];
+    if let Some(port) = tunnel.ssh_port {
+        args.push("-p".to_string());
+        args.push(port.to_string());
+    }
+    if let Some(key_path) = tunnel
+        .key_path
+        .as_deref()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    {
+        args.push("-i".to_string());
+        args.push(key_path.to_string());
+    }
+    let host = tunnel.host.trim();
+    let target = match tunnel
+        .user
+        .as_deref()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    {
+        Some(user) => format!("{user}@{host}"),
+        None => host.to_string(),
+    };
+    args.push(target);
+    args
+}
+
+fn reconnect_delay(attempt: u32) -> Duration {
+    let secs = 1u64
+        .checked_shl(attempt.min(16))
+        .unwrap_or(u64::MAX)
+        .min(MAX_RECONNECT_DELAY_SECS);
+    Duration::from_secs(secs)
+}
+
+async fn current_tunnel(state: &AppState) -> Option<SshTunnelSettings> {
+    let settings = state.app_settings.lock().await;
+    active_tunnel(&settings).cloned()
+}
+
+async fn publish_status(state: &AppState, event_sink: &TauriEventSink, status: TunnelStatus) {
+    {
+        let mut current = state.ssh_tunnel.lock().await;
+        if *current == status {
+            return;
+        }
+        *current = status.clone();
+    }
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: String::new(),
+        message: json!({
+            "method": "remote/tunnel",
+            "params": status,
+        }),
+    });
+}
+
+async fn run_tunnel(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    tunnel: &SshTunnelSettings,
+    attempts: u32,
+) -> TunnelExit {
+    let mut child = match Command::new("ssh")
+        .args(build_ssh_args(tunnel))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            return TunnelExit::Dropped {
+                error: format!("Failed to run ssh: {err}"),
+                was_connected: false,
+            }
+        }
+    };
+    let mut stderr = child.stderr.take();
+    let local_address = tunnel_local_address(tunnel);
+    let mut connected = false;
+
+    loop {
+        match tokio::time::timeout(PROBE_INTERVAL, child.wait()).await {
+            Ok(status) => {
+                let mut output = String::new();
+                if let Some(stderr) = stderr.as_mut() {
+                    let _ = stderr.read_to_string(&mut output).await;
+                }
+                let detail = output.trim();
+                let error = match status {
+                    Ok(status) if detail.is_empty() => format!("ssh exited with {status}"),
+                    Ok(_) => detail.to_string(),
+                    Err(err) => err.to_string(),
+                };
+                return TunnelExit::Dropped {
+                    error,
+                    was_connected: connected,
+                };
+            }
+            Err(_) => {
+                if current_tunnel(state).await.as_ref() != Some(tunnel) {
+                    let _ = child.kill().await;
+                    return TunnelExit::Reconfigured;
+                }
+                if !connected && TcpStream::connect(&local_address).await.is_ok() {
+                    connected = true;
+                    publish_status(
+                        state,
+                        event_sink,
+                        TunnelStatus {
+                            state: "connected".to_string(),
+                            local_address: Some(local_address.clone()),
+                            last_error: None,
+                            reconnect_attempts: attempts,
+                        },
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+}
+
+/// Keep the configured SSH forward up while remote mode uses it, reconnecting with backoff.
+pub(crate) fn spawn_ssh_tunnel_supervisor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let event_sink = TauriEventSink::new(app.clone());
+        let mut attempts: u32 = 0;
+        loop {
+            let state = app.state::<AppState>();
+            let Some(tunnel) = current_tunnel(&state).await else {
+                attempts = 0;
+                publish_status(&state, &event_sink, TunnelStatus::default()).await;
+                tokio::time::sleep(CONFIG_POLL_INTERVAL).await;
+                continue;
+            };
+
+            let local_address = tunnel_local_address(&tunnel);
+            publish_status(
+                &state,
+                &event_sink,
+                TunnelStatus {
+                    state: "connecting".to_string(),
+                    local_address: Some(local_address.clone()),
+                    last_error: None,
+                    reconnect_attempts: attempts,
+                },
+            )
+            .await;
+
+            match run_tunnel(&state, &event_sink, &tunnel, attempts).await {
+                TunnelExit::Reconfigured => attempts = 0,
+                TunnelExit::Dropped {
+                    error,
+                    was_connected,
+                } => {
+                    // A tunnel that was up gets a quick retry; repeated failures back off.
+                    attempts = if was_connected { 1 } else { attempts + 1 };
+                    publish_status(
+                        &state,
+                        &event_sink,
+                        TunnelStatus {
+                            state: "disconnected".to_string(),
+                            local_address: Some(local_address),
+                            last_error: Some(error),
+                            reconnect_attempts: attempts,
+                        },
+                    )
+                    .await;
+                    tokio::time::sleep(reconnect_delay(attempts)).await;
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) async fn ssh_tunnel_status(state: State<'_, AppState>) -> Result<TunnelStatus, String> {
+    Ok(state.ssh_tunnel.lock().await.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tunnel() -> SshTunnelSettings {
+        SshTunnelSettings {
+            enabled: true,
+            host: "dev.example.com".to_string(),
+            user: Some("ana".to_string()),
+            key_path: Some("~/.ssh/id_ed25519".to_string()),
+            ssh_port: Some(2222),
+            local_port: 4733,
+            remote_port: 4732,
+        }
+    }
+
+    #[test]
+    fn build_ssh_args_forwards_loopback_ports() {
+        let args = build_ssh_args(&tunnel());
+        assert_eq!(args[0], "-N");
+        assert_eq!(args[2], "127.0.0.1:4733:127.0.0.1:4732");
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        assert!(args.windows(2).any(|pair| pair == ["-i", "~/.ssh/id_ed25519"]));
+        assert_eq!(args.last().map(String::as_str), Some("ana@dev.example.com"));
+
+        let bare = SshTunnelSettings {
+            user: None,
+            key_path: None,
+            ssh_port: None,
+            ..tunnel()
+        };
+        let args = build_ssh_args(&bare);
+        assert!(!args.contains(&"-p".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("dev.example.com"));
+    }
+
+    #[test]
+    fn active_tunnel_requires_remote_mode_and_enabled_tunnel() {
+        let mut settings = AppSettings {
+            remote_ssh_tunnel: Some(tunnel()),
+            ..AppSettings::default()
+        };
+        assert!(active_tunnel(&settings).is_none());
+        settings.backend_mode = BackendMode::Remote;
+        assert!(active_tunnel(&settings).is_some());
+        settings.remote_ssh_tunnel = Some(SshTunnelSettings {
+            enabled: false,
+            ..tunnel()
+        });
+        assert!(active_tunnel(&settings).is_none());
+    }
+
+    #[test]
+    fn reconnect_delay_backs_off_to_a_cap() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(8));
+        assert_eq!(reconnect_delay(40), Duration::from_secs(MAX_RECONNECT_DELAY_SECS));
+    }
+}
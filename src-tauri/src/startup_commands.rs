@@ -0,0 +1,226 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::terminal::{close_terminal, close_terminals_with_prefix, find_terminal, open_terminal};
+use crate::types::WorkspaceEntry;
+
+/// Startup commands run in terminals with ids `startup-<index>`.
+const STARTUP_TERMINAL_PREFIX: &str = "startup-";
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const TERMINAL_COLS: u16 = 120;
+const TERMINAL_ROWS: u16 = 32;
+
+fn startup_terminal_id(index: usize) -> String {
+    format!("{STARTUP_TERMINAL_PREFIX}{index}")
+}
+
+/// Emit `workspace/startupCommand` with `status` one of "running", "exited",
+/// "failed" or "stopped".
+fn emit_status(
+    event_sink: &TauriEventSink,
+    workspace_id: &str,
+    index: usize,
+    command: &str,
+    status: &str,
+    exit_code: Option<u32>,
+    error: Option<String>,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": "workspace/startupCommand",
+            "params": {
+                "index": index,
+                "command": command,
+                "terminalId": startup_terminal_id(index),
+                "status": status,
+                "exitCode": exit_code,
+                "error": error,
+            },
+        }),
+    });
+}
+
+/// Report when startup command `index` exits or its terminal is closed.
+fn watch_startup_command(app: AppHandle, workspace_id: String, index: usize, command: String) {
+    tokio::spawn(async move {
+        let state = app.state::<AppState>();
+        let event_sink = TauriEventSink::new(app.clone());
+        let terminal_id = startup_terminal_id(index);
+        let Some(session) = find_terminal(&state, &workspace_id, &terminal_id).await else {
+            return;
+        };
+        loop {
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            let current = find_terminal(&state, &workspace_id, &terminal_id).await;
+            if !current.is_some_and(|current| Arc::ptr_eq(&current, &session)) {
+                emit_status(
+                    &event_sink,
+                    &workspace_id,
+                    index,
+                    &command,
+                    "stopped",
+                    None,
+                    None,
+                );
+                return;
+            }
+            let exit = session.child.lock().await.try_wait();
+            let status = match exit {
+                Ok(None) => continue,
+                Ok(Some(status)) => status,
+                Err(error) => {
+                    tracing::warn!(
+                        "[startup] Lost track of `{command}` in {workspace_id}: {error}"
+                    );
+                    return;
+                }
+            };
+            // Forget the finished terminal so the next run starts a fresh one.
+            close_terminal(&state, &workspace_id, &terminal_id).await;
+            let outcome = if status.success() { "exited" } else { "failed" };
+            emit_status(
+                &event_sink,
+                &workspace_id,
+                index,
+                &command,
+                outcome,
+                Some(status.exit_code()),
+                None,
+            );
+            return;
+        }
+    });
+}
+
+/// Start the workspace's startup commands that are not already running.
+async fn launch_startup_commands(entry: &WorkspaceEntry, state: &AppState, app: &AppHandle) {
+    let event_sink = TauriEventSink::new(app.clone());
+    for (index, command) in entry.settings.startup_commands.iter().enumerate() {
+        let command = command.trim();
+        if command.is_empty() {
+            continue;
+        }
+        let terminal_id = startup_terminal_id(index);
+        if find_terminal(state, &entry.id, &terminal_id)
+            .await
+            .is_some()
+        {
+            continue;
+        }
+        let opened = open_terminal(
+            state,
+            app.clone(),
+            &entry.id,
+            &terminal_id,
+            PathBuf::from(&entry.path),
+            TERMINAL_COLS,
+            TERMINAL_ROWS,
+            Some(command),
+        )
+        .await;
+        match opened {
+            Ok(_) => {
+                emit_status(
+                    &event_sink,
+                    &entry.id,
+                    index,
+                    command,
+                    "running",
+                    None,
+                    None,
+                );
+                watch_startup_command(app.clone(), entry.id.clone(), index, command.to_string());
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "[startup] Failed to start `{command}` in {}: {error}",
+                    entry.name
+                );
+                emit_status(
+                    &event_sink,
+                    &entry.id,
+                    index,
+                    command,
+                    "failed",
+                    None,
+                    Some(error),
+                );
+            }
+        }
+    }
+}
+
+/// Run startup commands for a workspace that just connected, if it opted in.
+pub(crate) async fn start_startup_commands(
+    entry: &WorkspaceEntry,
+    state: &AppState,
+    app: &AppHandle,
+) {
+    if entry.settings.run_startup_commands {
+        launch_startup_commands(entry, state, app).await;
+    }
+}
+
+/// Stop a workspace's startup commands, e.g. when it is removed.
+pub(crate) async fn stop_startup_commands(workspace_id: &str, state: &AppState) {
+    close_terminals_with_prefix(state, workspace_id, STARTUP_TERMINAL_PREFIX).await;
+}
+
+/// Start the workspace's startup commands now, whether or not it runs them on connect.
+#[tauri::command]
+pub(crate) async fn run_startup_commands(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "run_startup_commands",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let entry = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or("workspace not found")?;
+    launch_startup_commands(&entry, &state, &app).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn stop_workspace_startup_commands(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "stop_workspace_startup_commands",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    stop_startup_commands(&workspace_id, &state).await;
+    Ok(())
+}
@@ -16,6 +16,14 @@ pub(crate) struct AppState {
     pub(crate) terminal_sessions:
         Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
+    pub(crate) remote_health: Mutex<crate::remote_backend::RemoteHealth>,
+    pub(crate) ssh_tunnel: Mutex<crate::ssh_tunnel::TunnelStatus>,
+    pub(crate) turn_scheduler: Arc<crate::turn_scheduler::TurnScheduler>,
+    pub(crate) recovery_report: Mutex<Option<crate::process_registry::RecoveryReport>>,
+    pub(crate) turn_activity: crate::tray::TurnActivity,
+    pub(crate) file_watches: crate::file_watch::FileWatches,
+    pub(crate) git_status_cache: crate::git_status_cache::GitStatusCache,
+    pub(crate) thread_drafts: crate::thread_drafts::ThreadDrafts,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
@@ -44,6 +52,18 @@ impl AppState {
             thread_watchers: Mutex::new(HashMap::new()),
             terminal_sessions: Mutex::new(HashMap::new()),
             remote_backend: Mutex::new(None),
+            remote_health: Mutex::new(Default::default()),
+            ssh_tunnel: Mutex::new(Default::default()),
+            turn_scheduler: Arc::new(crate::turn_scheduler::TurnScheduler::new(
+                app_settings.max_parallel_turns,
+            )),
+            recovery_report: Mutex::new(None),
+            turn_activity: Default::default(),
+            file_watches: Default::default(),
+            git_status_cache: Default::default(),
+            thread_drafts: crate::thread_drafts::ThreadDrafts::load(
+                data_dir.join("thread-drafts.json"),
+            ),
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::secrets::{self, KeychainStore, SecretStore, UnreadSecrets};
+use crate::thread_drafts::ThreadDraft;
 use crate::types::{AppSettings, WorkspaceEntry};
 
 pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, WorkspaceEntry>, String> {
@@ -23,26 +25,75 @@ pub(crate) fn write_workspaces(path: &PathBuf, entries: &[WorkspaceEntry]) -> Re
     std::fs::write(path, data).map_err(|e| e.to_string())
 }
 
+/// Keychain secrets that could not be read when settings were loaded.
+static UNREAD_KEYCHAIN_SECRETS: UnreadSecrets = UnreadSecrets::new();
+
 pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
+    read_settings_with(path, &KeychainStore, &UNREAD_KEYCHAIN_SECRETS)
+}
+
+pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
+    write_settings_with(path, settings, &KeychainStore, &UNREAD_KEYCHAIN_SECRETS)
+}
+
+/// Secrets are kept in `store`; a file that still holds them in plaintext is
+/// rewritten on first read.
+fn read_settings_with(
+    path: &PathBuf,
+    store: &dyn SecretStore,
+    unread: &UnreadSecrets,
+) -> Result<AppSettings, String> {
     if !path.exists() {
         return Ok(AppSettings::default());
     }
     let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut settings: AppSettings = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    if secrets::restore_secrets(store, unread, &mut settings) {
+        write_settings_with(path, &settings, store, unread)?;
+    }
+    Ok(settings)
+}
+
+fn write_settings_with(
+    path: &PathBuf,
+    settings: &AppSettings,
+    store: &dyn SecretStore,
+    unread: &UnreadSecrets,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut stored = settings.clone();
+    secrets::stash_secrets(store, unread, &mut stored);
+    let data = serde_json::to_string_pretty(&stored).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_thread_drafts(path: &PathBuf) -> Result<HashMap<String, ThreadDraft>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
     serde_json::from_str(&data).map_err(|e| e.to_string())
 }
 
-pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
+pub(crate) fn write_thread_drafts(
+    path: &PathBuf,
+    drafts: &HashMap<String, ThreadDraft>,
+) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
-    let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    let data = serde_json::to_string_pretty(drafts).map_err(|e| e.to_string())?;
     std::fs::write(path, data).map_err(|e| e.to_string())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{read_workspaces, write_workspaces};
-    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use super::{read_settings_with, read_workspaces, write_settings_with, write_workspaces};
+    use crate::secrets::tests::MemoryStore;
+    use crate::secrets::UnreadSecrets;
+    use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
     use uuid::Uuid;
 
     #[test]
@@ -77,4 +128,27 @@ mod tests {
         assert!(stored.settings.sidebar_collapsed);
         assert_eq!(stored.settings.git_root.as_deref(), Some("/tmp"));
     }
+
+    #[test]
+    fn plaintext_token_migrates_out_of_settings_file() {
+        let temp_dir = std::env::temp_dir()
+            .join(format!("claude-code-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let path = temp_dir.join("settings.json");
+        std::fs::write(&path, r#"{ "remoteBackendToken": "secret" }"#).expect("write settings");
+
+        let store = MemoryStore::default();
+        let unread = UnreadSecrets::default();
+        let read = read_settings_with(&path, &store, &unread).expect("read settings");
+        assert_eq!(read.remote_backend_token.as_deref(), Some("secret"));
+        let on_disk = std::fs::read_to_string(&path).expect("read file");
+        assert!(!on_disk.contains("secret"));
+
+        let mut updated = AppSettings::default();
+        updated.remote_backend_token = Some("rotated".to_string());
+        write_settings_with(&path, &updated, &store, &unread).expect("write settings");
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("rotated"));
+        let read = read_settings_with(&path, &store, &unread).expect("read settings");
+        assert_eq!(read.remote_backend_token.as_deref(), Some("rotated"));
+    }
 }
@@ -0,0 +1,333 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::claude::resolve_project_dir;
+use crate::remote_backend;
+use crate::state::AppState;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Disk used on behalf of one workspace.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceStorage {
+    pub(crate) workspace_id: String,
+    pub(crate) name: String,
+    /// Worktrees the app created from this workspace.
+    pub(crate) worktree_bytes: u64,
+    /// The CLI's session transcripts for this workspace's project.
+    pub(crate) session_bytes: u64,
+    pub(crate) session_files: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageReport {
+    pub(crate) workspaces: Vec<WorkspaceStorage>,
+    pub(crate) worktrees_bytes: u64,
+    pub(crate) sessions_bytes: u64,
+    pub(crate) dictation_models_bytes: u64,
+    pub(crate) caches_bytes: u64,
+    pub(crate) total_bytes: u64,
+}
+
+/// What a cleanup command removed.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CleanupReport {
+    pub(crate) files_removed: usize,
+    pub(crate) bytes_freed: u64,
+}
+
+/// Total size of a file or directory tree. Symlinks are not followed.
+fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| path_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+fn is_session_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("jsonl")
+}
+
+/// Session transcripts in a Claude project dir, with their sizes and mtimes.
+fn session_files(project_dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(entries) = fs::read_dir(project_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_session_file(path))
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            // Subagent transcripts and tool results live next to the session file.
+            let companion = path.with_extension("");
+            Some((path, metadata.len() + path_size(&companion), modified))
+        })
+        .collect()
+}
+
+/// Remove sessions not written to since `cutoff`, with their companion dirs.
+fn remove_sessions_before(project_dir: &Path, cutoff: SystemTime) -> Result<CleanupReport, String> {
+    let mut report = CleanupReport::default();
+    for (path, bytes, modified) in session_files(project_dir) {
+        if modified >= cutoff {
+            continue;
+        }
+        fs::remove_file(&path)
+            .map_err(|err| format!("Failed to remove {}: {err}", path.display()))?;
+        let companion = path.with_extension("");
+        if companion.is_dir() {
+            let _ = fs::remove_dir_all(&companion);
+        }
+        report.files_removed += 1;
+        report.bytes_freed += bytes;
+    }
+    Ok(report)
+}
+
+/// Empty a cache directory, keeping the directory itself.
+fn clear_dir(dir: &Path) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return report;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let bytes = path_size(&path);
+        let removed = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        if removed.is_ok() {
+            report.files_removed += 1;
+            report.bytes_freed += bytes;
+        }
+    }
+    report
+}
+
+/// Interrupted model downloads left behind in the dictation model dir.
+fn partial_downloads(models_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(models_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("partial"))
+        .collect()
+}
+
+fn app_dirs(app: &AppHandle) -> Result<(PathBuf, PathBuf), String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve app cache dir: {e}"))?;
+    Ok((data_dir, cache_dir))
+}
+
+fn dictation_models_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("models").join("whisper")
+}
+
+/// Size the worktrees, Claude sessions, dictation models and caches the app
+/// keeps, broken down per workspace where it can be.
+#[tauri::command]
+pub(crate) async fn workspace_storage_report(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<StorageReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "workspace_storage_report", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let (data_dir, cache_dir) = app_dirs(&app)?;
+    let workspaces = {
+        let workspaces = state.workspaces.lock().await;
+        let mut entries: Vec<_> = workspaces.values().cloned().collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let worktrees_dir = data_dir.join("worktrees");
+        let mut report = StorageReport::default();
+        for entry in &workspaces {
+            let sessions = resolve_project_dir(entry)
+                .map(|dir| session_files(&dir))
+                .unwrap_or_default();
+            let usage = WorkspaceStorage {
+                workspace_id: entry.id.clone(),
+                name: entry.name.clone(),
+                worktree_bytes: path_size(&worktrees_dir.join(&entry.id)),
+                session_bytes: sessions.iter().map(|(_, bytes, _)| bytes).sum(),
+                session_files: sessions.len(),
+            };
+            report.sessions_bytes += usage.session_bytes;
+            report.workspaces.push(usage);
+        }
+        report.worktrees_bytes = path_size(&worktrees_dir);
+        report.dictation_models_bytes = path_size(&data_dir.join("models"));
+        report.caches_bytes = path_size(&cache_dir);
+        report.total_bytes = report.worktrees_bytes
+            + report.sessions_bytes
+            + report.dictation_models_bytes
+            + report.caches_bytes;
+        report
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+/// Delete a workspace's Claude session files not touched in `older_than_days`.
+#[tauri::command]
+pub(crate) async fn delete_old_sessions(
+    workspace_id: String,
+    older_than_days: u32,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CleanupReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_old_sessions",
+            json!({ "workspaceId": workspace_id, "olderThanDays": older_than_days }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    if older_than_days == 0 {
+        return Err("Keep at least one day of sessions.".to_string());
+    }
+    let project_dir = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        resolve_project_dir(entry).ok_or("Unable to resolve Claude project directory.")?
+    };
+    let cutoff = SystemTime::now() - DAY * older_than_days;
+    let result = tokio::task::spawn_blocking(move || remove_sessions_before(&project_dir, cutoff))
+        .await
+        .map_err(|err| err.to_string())?;
+    crate::audit::record(
+        "delete_old_sessions",
+        Some(&workspace_id),
+        json!({ "olderThanDays": older_than_days }),
+        &result,
+    );
+    result
+}
+
+/// Clear the app cache dir and interrupted dictation model downloads.
+#[tauri::command]
+pub(crate) async fn prune_app_caches(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CleanupReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "prune_app_caches", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let (data_dir, cache_dir) = app_dirs(&app)?;
+    tokio::task::spawn_blocking(move || {
+        let mut report = clear_dir(&cache_dir);
+        for path in partial_downloads(&dictation_models_dir(&data_dir)) {
+            let bytes = path_size(&path);
+            if fs::remove_file(&path).is_ok() {
+                report.files_removed += 1;
+                report.bytes_freed += bytes;
+            }
+        }
+        report
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claude-storage-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn session_files_include_companion_dirs() {
+        let dir = temp_dir();
+        fs::write(dir.join("a.jsonl"), "12345").unwrap();
+        fs::create_dir_all(dir.join("a").join("subagents")).unwrap();
+        fs::write(dir.join("a").join("subagents").join("x.jsonl"), "123").unwrap();
+        fs::write(dir.join("sessions-index.json"), "{}").unwrap();
+
+        let files = session_files(&dir);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].1, 8);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn remove_sessions_before_keeps_recent_sessions() {
+        let dir = temp_dir();
+        fs::write(dir.join("old.jsonl"), "old").unwrap();
+        fs::create_dir_all(dir.join("old")).unwrap();
+        fs::write(dir.join("new.jsonl"), "new").unwrap();
+        let old = fs::File::options().write(true).open(dir.join("old.jsonl")).unwrap();
+        old.set_modified(SystemTime::now() - DAY * 10).unwrap();
+        let new = fs::File::options().write(true).open(dir.join("new.jsonl")).unwrap();
+        new.set_modified(SystemTime::now()).unwrap();
+
+        let report = remove_sessions_before(&dir, SystemTime::now() - DAY * 5).unwrap();
+        assert_eq!(report.files_removed, 1);
+        assert_eq!(report.bytes_freed, 3);
+        assert!(!dir.join("old.jsonl").exists());
+        assert!(!dir.join("old").exists());
+        assert!(dir.join("new.jsonl").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clear_dir_keeps_the_directory() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("nested").join("blob"), "abcd").unwrap();
+        fs::write(dir.join("file"), "ab").unwrap();
+
+        let report = clear_dir(&dir);
+        assert_eq!(report.files_removed, 2);
+        assert_eq!(report.bytes_freed, 6);
+        assert!(dir.is_dir());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
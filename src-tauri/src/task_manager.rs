@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::claude_home::resolve_default_claude_home;
 
@@ -19,6 +19,34 @@ impl Default for TaskStatus {
     }
 }
 
+impl TaskStatus {
+    /// Whether a task may move from this status to `next`.
+    ///
+    /// Tasks can be started, finished, paused back to pending, or reopened
+    /// once completed. Jumping straight from pending to completed is rejected
+    /// so every finished task has passed through in_progress.
+    pub fn can_transition_to(&self, next: &TaskStatus) -> bool {
+        matches!(
+            (self, next),
+            (TaskStatus::Pending, TaskStatus::Pending)
+                | (TaskStatus::Pending, TaskStatus::InProgress)
+                | (TaskStatus::InProgress, TaskStatus::InProgress)
+                | (TaskStatus::InProgress, TaskStatus::Pending)
+                | (TaskStatus::InProgress, TaskStatus::Completed)
+                | (TaskStatus::Completed, TaskStatus::Completed)
+                | (TaskStatus::Completed, TaskStatus::InProgress)
+        )
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+        }
+    }
+}
+
 /// A task from Claude's task system stored in ~/.claude/tasks/<list-id>/<task-id>.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -36,12 +64,15 @@ pub struct Task {
     pub blocks: Vec<String>,
     #[serde(default)]
     pub blocked_by: Vec<String>,
+    /// Tasks that must be completed before this one can be started
+    #[serde(default)]
+    pub depends_on: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
 }
 
 /// Partial update structure for updating task fields
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskUpdate {
     pub subject: Option<String>,
@@ -51,6 +82,8 @@ pub struct TaskUpdate {
     pub owner: Option<String>,
     pub add_blocks: Option<Vec<String>>,
     pub add_blocked_by: Option<Vec<String>>,
+    pub add_depends_on: Option<Vec<String>>,
+    pub remove_depends_on: Option<Vec<String>>,
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -190,6 +223,7 @@ pub fn create_task(
         owner: None,
         blocks: Vec::new(),
         blocked_by: Vec::new(),
+        depends_on: Vec::new(),
         metadata: None,
     };
 
@@ -202,16 +236,25 @@ pub fn create_task(
 
 /// Read a single task from a list
 pub fn read_task(list_id: &str, task_id: &str) -> Result<Task, String> {
-    let task_path = get_task_file_path(list_id, task_id)?;
+    read_task_if_exists(list_id, task_id)?
+        .ok_or_else(|| format!("Task {} not found in list {}", task_id, list_id))
+}
 
-    if !task_path.exists() {
-        return Err(format!("Task {} not found in list {}", task_id, list_id));
-    }
+/// Read a single task, returning `None` only when its file does not exist.
+/// Unreadable or half-written files are errors.
+pub fn read_task_if_exists(list_id: &str, task_id: &str) -> Result<Option<Task>, String> {
+    read_task_file(&get_task_file_path(list_id, task_id)?)
+}
 
-    let content = fs::read_to_string(&task_path)
-        .map_err(|e| format!("Failed to read task file: {}", e))?;
+fn read_task_file(task_path: &Path) -> Result<Option<Task>, String> {
+    let content = match fs::read_to_string(task_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read task file: {}", e)),
+    };
 
     serde_json::from_str(&content)
+        .map(Some)
         .map_err(|e| format!("Failed to parse task file: {}", e))
 }
 
@@ -235,12 +278,12 @@ pub fn read_task_list(list_id: &str) -> Result<Vec<Task>, String> {
                     match serde_json::from_str::<Task>(&content) {
                         Ok(task) => tasks.push(task),
                         Err(e) => {
-                            eprintln!("Failed to parse task file {:?}: {}", path, e);
+                            tracing::warn!("Failed to parse task file {:?}: {}", path, e);
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to read task file {:?}: {}", path, e);
+                    tracing::warn!("Failed to read task file {:?}: {}", path, e);
                 }
             }
         }
@@ -272,9 +315,6 @@ pub fn update_task(list_id: &str, task_id: &str, updates: TaskUpdate) -> Result<
     if let Some(active_form) = updates.active_form {
         task.active_form = Some(active_form);
     }
-    if let Some(status) = updates.status {
-        task.status = status;
-    }
     if let Some(owner) = updates.owner {
         task.owner = Some(owner);
     }
@@ -292,6 +332,42 @@ pub fn update_task(list_id: &str, task_id: &str, updates: TaskUpdate) -> Result<
             }
         }
     }
+    if let Some(remove_depends_on) = updates.remove_depends_on {
+        task.depends_on.retain(|id| !remove_depends_on.contains(id));
+    }
+    if let Some(add_depends_on) = updates.add_depends_on {
+        if !add_depends_on.is_empty() {
+            let all_tasks = read_task_list(list_id)?;
+            for dependency_id in add_depends_on {
+                validate_dependency(&all_tasks, task_id, &dependency_id)?;
+                if !task.depends_on.contains(&dependency_id) {
+                    task.depends_on.push(dependency_id);
+                }
+            }
+        }
+    }
+    if let Some(status) = updates.status {
+        if !task.status.can_transition_to(&status) {
+            return Err(format!(
+                "Invalid status transition for task {}: {} -> {}",
+                task_id,
+                task.status.as_str(),
+                status.as_str()
+            ));
+        }
+        if status == TaskStatus::InProgress && task.status != TaskStatus::InProgress {
+            let all_tasks = read_task_list(list_id)?;
+            let pending = unmet_dependencies(&task, &all_tasks);
+            if !pending.is_empty() {
+                return Err(format!(
+                    "Task {} is blocked by incomplete tasks: {}",
+                    task_id,
+                    pending.join(", ")
+                ));
+            }
+        }
+        task.status = status;
+    }
     if let Some(metadata) = updates.metadata {
         // Merge metadata - if existing metadata exists, merge the new keys
         match (&mut task.metadata, metadata) {
@@ -330,6 +406,73 @@ pub fn update_task(list_id: &str, task_id: &str, updates: TaskUpdate) -> Result<
     Ok(task)
 }
 
+/// Ids of dependencies (dependsOn and blockedBy) that are not completed yet.
+/// Dependencies that no longer exist in the list count as unmet.
+fn unmet_dependencies(task: &Task, all_tasks: &[Task]) -> Vec<String> {
+    task.depends_on
+        .iter()
+        .chain(task.blocked_by.iter())
+        .filter(|dep_id| {
+            !all_tasks
+                .iter()
+                .any(|other| &other.id == *dep_id && other.status == TaskStatus::Completed)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Check that `task_id` may depend on `dependency_id` without creating a cycle
+fn validate_dependency(all_tasks: &[Task], task_id: &str, dependency_id: &str) -> Result<(), String> {
+    if dependency_id == task_id {
+        return Err(format!("Task {} cannot depend on itself", task_id));
+    }
+    if !all_tasks.iter().any(|task| task.id == dependency_id) {
+        return Err(format!("Dependency task {} not found", dependency_id));
+    }
+
+    // Walk the dependency graph from the new dependency, following both kinds of
+    // edge `unmet_dependencies` honours; reaching task_id means a cycle.
+    let mut stack = vec![dependency_id.to_string()];
+    let mut visited: Vec<String> = Vec::new();
+    while let Some(current) = stack.pop() {
+        if current == task_id {
+            return Err(format!(
+                "Adding dependency {} to task {} would create a cycle",
+                dependency_id, task_id
+            ));
+        }
+        if visited.contains(&current) {
+            continue;
+        }
+        if let Some(task) = all_tasks.iter().find(|task| task.id == current) {
+            stack.extend(
+                task.depends_on
+                    .iter()
+                    .chain(task.blocked_by.iter())
+                    .cloned(),
+            );
+        }
+        visited.push(current);
+    }
+    Ok(())
+}
+
+/// Pending tasks whose dependencies are all completed, in list order
+fn ready_tasks(all_tasks: &[Task]) -> Vec<Task> {
+    all_tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Pending)
+        .filter(|task| unmet_dependencies(task, all_tasks).is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Read the tasks in a list that are ready to be picked up
+pub fn read_ready_tasks(list_id: &str) -> Result<Vec<Task>, String> {
+    let all_tasks = read_task_list(list_id)?;
+    Ok(ready_tasks(&all_tasks))
+}
+
 /// Delete a task from a list
 pub fn delete_task(list_id: &str, task_id: &str) -> Result<(), String> {
     let _lock = FileLock::acquire(list_id)?;
@@ -417,6 +560,21 @@ pub async fn task_list_read(list_id: String) -> Result<TaskListResponse, String>
     .map_err(|e| e.to_string())?
 }
 
+/// Read only the unblocked, pending tasks in a list
+#[tauri::command]
+pub async fn task_ready_list(list_id: String) -> Result<TaskListResponse, String> {
+    let list_id_clone = list_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let tasks = read_ready_tasks(&list_id_clone)?;
+        Ok(TaskListResponse {
+            list_id: list_id_clone,
+            tasks,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Update an existing task with partial updates
 #[tauri::command]
 pub async fn task_update(
@@ -457,6 +615,27 @@ pub async fn task_lists_available() -> Result<Vec<String>, String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_task_file_only_reports_missing_files_as_none() {
+        let dir = std::env::temp_dir().join(format!("task-manager-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert!(read_task_file(&dir.join("1.json")).unwrap().is_none());
+
+        fs::write(dir.join("2.json"), "{\"id\": \"2\", \"sub").unwrap();
+        assert!(read_task_file(&dir.join("2.json")).is_err());
+
+        fs::write(
+            dir.join("3.json"),
+            r#"{"id": "3", "subject": "Ship it", "description": "", "status": "pending"}"#,
+        )
+        .unwrap();
+        let task = read_task_file(&dir.join("3.json")).unwrap().unwrap();
+        assert_eq!(task.subject, "Ship it");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_task_status_serialization() {
         let pending = TaskStatus::Pending;
@@ -495,6 +674,7 @@ mod tests {
             owner: None,
             blocks: vec!["2".to_string()],
             blocked_by: Vec::new(),
+            depends_on: Vec::new(),
             metadata: None,
         };
 
@@ -563,4 +743,94 @@ mod tests {
         assert!(update.subject.is_none());
         assert!(update.description.is_none());
     }
+
+    fn make_task(id: &str, status: TaskStatus, depends_on: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            subject: format!("Task {}", id),
+            description: String::new(),
+            active_form: None,
+            status,
+            owner: None,
+            blocks: Vec::new(),
+            blocked_by: Vec::new(),
+            depends_on: depends_on.iter().map(|id| id.to_string()).collect(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(TaskStatus::Pending.can_transition_to(&TaskStatus::InProgress));
+        assert!(TaskStatus::InProgress.can_transition_to(&TaskStatus::Completed));
+        assert!(TaskStatus::InProgress.can_transition_to(&TaskStatus::Pending));
+        assert!(TaskStatus::Completed.can_transition_to(&TaskStatus::InProgress));
+        assert!(!TaskStatus::Pending.can_transition_to(&TaskStatus::Completed));
+        assert!(!TaskStatus::Completed.can_transition_to(&TaskStatus::Pending));
+    }
+
+    #[test]
+    fn test_task_depends_on_deserialization() {
+        let json = r#"{
+            "id": "2",
+            "subject": "Dependent task",
+            "description": "",
+            "dependsOn": ["1"]
+        }"#;
+
+        let task: Task = serde_json::from_str(json).unwrap();
+        assert_eq!(task.depends_on, vec!["1"]);
+
+        let update: TaskUpdate =
+            serde_json::from_str(r#"{"addDependsOn": ["3"], "removeDependsOn": ["1"]}"#).unwrap();
+        assert_eq!(update.add_depends_on, Some(vec!["3".to_string()]));
+        assert_eq!(update.remove_depends_on, Some(vec!["1".to_string()]));
+    }
+
+    #[test]
+    fn test_ready_tasks_filters_blocked_and_started() {
+        let mut blocked = make_task("4", TaskStatus::Pending, &[]);
+        blocked.blocked_by = vec!["2".to_string()];
+        let tasks = vec![
+            make_task("1", TaskStatus::Completed, &[]),
+            make_task("2", TaskStatus::InProgress, &[]),
+            make_task("3", TaskStatus::Pending, &["1"]),
+            blocked,
+            make_task("5", TaskStatus::Pending, &["2"]),
+            make_task("6", TaskStatus::Pending, &["missing"]),
+            make_task("7", TaskStatus::Pending, &[]),
+        ];
+
+        let ready: Vec<String> = ready_tasks(&tasks).into_iter().map(|t| t.id).collect();
+        assert_eq!(ready, vec!["3", "7"]);
+    }
+
+    #[test]
+    fn test_validate_dependency_rejects_self_missing_and_cycles() {
+        let tasks = vec![
+            make_task("1", TaskStatus::Pending, &["2"]),
+            make_task("2", TaskStatus::Pending, &["3"]),
+            make_task("3", TaskStatus::Pending, &[]),
+        ];
+
+        assert!(validate_dependency(&tasks, "1", "1").is_err());
+        assert!(validate_dependency(&tasks, "1", "9").is_err());
+        assert!(validate_dependency(&tasks, "3", "1").is_err());
+        assert!(validate_dependency(&tasks, "1", "3").is_ok());
+    }
+
+    #[test]
+    fn test_validate_dependency_follows_blocked_by() {
+        let mut blocked = make_task("2", TaskStatus::Pending, &[]);
+        blocked.blocked_by = vec!["3".to_string()];
+        let tasks = vec![
+            make_task("1", TaskStatus::Pending, &["2"]),
+            blocked,
+            make_task("3", TaskStatus::Pending, &[]),
+        ];
+
+        assert!(validate_dependency(&tasks, "3", "1").is_err());
+        assert!(validate_dependency(&tasks, "3", "2").is_err());
+        assert!(validate_dependency(&tasks, "1", "3").is_ok());
+    }
 }
@@ -0,0 +1,375 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{ensure_workspace_session, ensure_workspace_thread_watcher};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::task_manager::{read_task, update_task, Task, TaskStatus, TaskUpdate};
+use crate::turn_scheduler::start_tracked_turn;
+use crate::utils::now_millis;
+
+/// Link between a task and the thread that executes it, persisted in task_threads.json
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskThreadLink {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) last_turn_id: Option<String>,
+    /// "running", "completed", "failed" or "interrupted"
+    pub(crate) status: String,
+    pub(crate) updated_at: i64,
+}
+
+/// list_id -> task_id -> link
+pub(crate) type TaskThreadLinks = HashMap<String, HashMap<String, TaskThreadLink>>;
+
+/// Serializes read-modify-write cycles on task_threads.json.
+static TASK_THREADS_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn task_threads_path(settings_path: &Path) -> Result<PathBuf, String> {
+    settings_path
+        .parent()
+        .map(|path| path.join("task_threads.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+pub(crate) fn read_task_threads(path: &Path) -> Result<TaskThreadLinks, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+pub(crate) fn write_task_threads(path: &Path, data: &TaskThreadLinks) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn store_task_thread_link(
+    path: &Path,
+    list_id: &str,
+    task_id: &str,
+    link: TaskThreadLink,
+) -> Result<(), String> {
+    let _guard = TASK_THREADS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut links = read_task_threads(path)?;
+    links
+        .entry(list_id.to_string())
+        .or_default()
+        .insert(task_id.to_string(), link);
+    write_task_threads(path, &links)
+}
+
+/// Drop every link matching `remove`. Returns how many were dropped.
+pub(crate) fn remove_task_thread_links(
+    path: &Path,
+    remove: impl Fn(&TaskThreadLink) -> bool,
+) -> Result<usize, String> {
+    let _guard = TASK_THREADS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut links = read_task_threads(path)?;
+    let mut removed = 0;
+    for tasks in links.values_mut() {
+        let before = tasks.len();
+        tasks.retain(|_, link| !remove(link));
+        removed += before - tasks.len();
+    }
+    if removed > 0 {
+        links.retain(|_, tasks| !tasks.is_empty());
+        write_task_threads(path, &links)?;
+    }
+    Ok(removed)
+}
+
+/// Acceptance criteria can be stored in task metadata as a string or a list of strings.
+fn acceptance_criteria(task: &Task) -> Vec<String> {
+    let Some(value) = task
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("acceptanceCriteria"))
+    else {
+        return Vec::new();
+    };
+    match value {
+        Value::String(text) => text
+            .lines()
+            .map(|line| line.trim().trim_start_matches(['-', '*']).trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .map(|item| item.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn build_task_prompt(task: &Task) -> String {
+    let mut prompt = format!("Work on task #{}: {}", task.id, task.subject.trim());
+    let description = task.description.trim();
+    if !description.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(description);
+    }
+    let criteria = acceptance_criteria(task);
+    if !criteria.is_empty() {
+        prompt.push_str("\n\nAcceptance criteria:");
+        for item in criteria {
+            prompt.push_str("\n- ");
+            prompt.push_str(&item);
+        }
+    }
+    prompt.push_str("\n\nWhen you are done, summarize what you changed and how the task was verified.");
+    prompt
+}
+
+fn status_update(status: TaskStatus) -> TaskUpdate {
+    TaskUpdate {
+        status: Some(status),
+        ..TaskUpdate::default()
+    }
+}
+
+/// Run a task from the task list in a dedicated thread.
+///
+/// Reuses the thread linked to the task when one exists for the same workspace,
+/// otherwise starts a new one. The task is marked in_progress while the turn runs,
+/// then completed on success or moved back to pending if the turn fails.
+#[tauri::command]
+pub(crate) async fn task_execute(
+    workspace_id: String,
+    list_id: String,
+    task_id: String,
+    access_mode: Option<String>,
+    model: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "task_execute",
+            json!({
+                "workspaceId": workspace_id,
+                "listId": list_id,
+                "taskId": task_id,
+                "accessMode": access_mode,
+                "model": model,
+            }),
+        )
+        .await;
+    }
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+
+    let task = {
+        let list_id = list_id.clone();
+        let task_id = task_id.clone();
+        tokio::task::spawn_blocking(move || read_task(&list_id, &task_id))
+            .await
+            .map_err(|err| err.to_string())??
+    };
+    if task.status == TaskStatus::Completed {
+        return Err(format!("Task {} is already completed", task.id));
+    }
+
+    let links_path = task_threads_path(&state.settings_path)?;
+    let existing_thread = read_task_threads(&links_path)?
+        .get(&list_id)
+        .and_then(|tasks| tasks.get(&task_id))
+        .filter(|link| link.workspace_id == workspace_id)
+        .map(|link| link.thread_id.clone());
+    let thread_id = existing_thread.unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    // Mark the task as started; this also rejects tasks with unmet dependencies.
+    {
+        let list_id = list_id.clone();
+        let task_id = task_id.clone();
+        let updates = TaskUpdate {
+            status: Some(TaskStatus::InProgress),
+            metadata: Some(json!({ "threadId": thread_id, "workspaceId": workspace_id })),
+            ..TaskUpdate::default()
+        };
+        tokio::task::spawn_blocking(move || update_task(&list_id, &task_id, updates))
+            .await
+            .map_err(|err| err.to_string())??;
+    }
+
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone()).await;
+
+    let prompt = build_task_prompt(&task);
+    let event_sink = TauriEventSink::new(app.clone());
+    let started = start_tracked_turn(
+        &state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &thread_id,
+        model.as_deref(),
+        access_mode.as_deref(),
+        &prompt,
+    )
+    .await;
+    let turn = match started {
+        Ok(turn) => turn,
+        Err(error) => {
+            let list_id = list_id.clone();
+            let task_id = task_id.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                update_task(&list_id, &task_id, status_update(TaskStatus::Pending))
+            })
+            .await;
+            return Err(error);
+        }
+    };
+
+    store_task_thread_link(
+        &links_path,
+        &list_id,
+        &task_id,
+        TaskThreadLink {
+            workspace_id: workspace_id.clone(),
+            thread_id: thread_id.clone(),
+            last_turn_id: Some(turn.turn_id.clone()),
+            status: "running".to_string(),
+            updated_at: now_millis(),
+        },
+    )?;
+
+    let turn_id = turn.turn_id.clone();
+    tokio::spawn(async move {
+        let completion = turn.completion().await;
+        let status = completion.status;
+
+        let next_status = if status == "completed" {
+            TaskStatus::Completed
+        } else {
+            TaskStatus::Pending
+        };
+        let update_list_id = list_id.clone();
+        let update_task_id = task_id.clone();
+        match tokio::task::spawn_blocking(move || {
+            update_task(&update_list_id, &update_task_id, status_update(next_status))
+        })
+        .await
+        {
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => tracing::warn!("[task_execute] Failed to update task {task_id}: {error}"),
+            Err(error) => tracing::warn!("[task_execute] Failed to update task {task_id}: {error}"),
+        }
+
+        if let Err(error) = store_task_thread_link(
+            &links_path,
+            &list_id,
+            &task_id,
+            TaskThreadLink {
+                workspace_id: workspace_id.clone(),
+                thread_id: completion.thread_id.clone(),
+                last_turn_id: Some(completion.turn_id.clone()),
+                status: status.clone(),
+                updated_at: now_millis(),
+            },
+        ) {
+            tracing::warn!("[task_execute] Failed to store task link: {error}");
+        }
+
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id,
+            message: json!({
+                "method": "task/executionCompleted",
+                "params": {
+                    "listId": list_id,
+                    "taskId": task_id,
+                    "threadId": completion.thread_id,
+                    "turnId": completion.turn_id,
+                    "status": status,
+                }
+            }),
+        });
+    });
+
+    Ok(json!({
+        "taskId": task.id,
+        "threadId": thread_id,
+        "turn": { "id": turn_id, "threadId": thread_id },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_task(description: &str, metadata: Option<Value>) -> Task {
+        Task {
+            id: "3".to_string(),
+            subject: "Add retry logic".to_string(),
+            description: description.to_string(),
+            active_form: None,
+            status: TaskStatus::Pending,
+            owner: None,
+            blocks: Vec::new(),
+            blocked_by: Vec::new(),
+            depends_on: Vec::new(),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn build_task_prompt_includes_description_and_criteria() {
+        let task = make_task(
+            "Retry failed uploads.",
+            Some(json!({ "acceptanceCriteria": ["Retries three times", " ", "Logs failures"] })),
+        );
+        let prompt = build_task_prompt(&task);
+        assert!(prompt.starts_with("Work on task #3: Add retry logic"));
+        assert!(prompt.contains("\n\nRetry failed uploads."));
+        assert!(prompt.contains("Acceptance criteria:\n- Retries three times\n- Logs failures"));
+    }
+
+    #[test]
+    fn acceptance_criteria_accepts_bulleted_string() {
+        let task = make_task("", Some(json!({ "acceptanceCriteria": "- first\n* second\n\n" })));
+        assert_eq!(acceptance_criteria(&task), vec!["first", "second"]);
+        assert!(!build_task_prompt(&make_task("", None)).contains("Acceptance criteria"));
+    }
+
+    #[test]
+    fn task_thread_links_round_trip() {
+        let dir = std::env::temp_dir().join(format!("claude-task-threads-{}", Uuid::new_v4()));
+        let path = dir.join("task_threads.json");
+        let link = TaskThreadLink {
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            last_turn_id: Some("turn-1".to_string()),
+            status: "running".to_string(),
+            updated_at: 1,
+        };
+
+        store_task_thread_link(&path, "list", "1", link.clone()).unwrap();
+        let links = read_task_threads(&path).unwrap();
+        assert_eq!(links.get("list").and_then(|tasks| tasks.get("1")), Some(&link));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
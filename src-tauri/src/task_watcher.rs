@@ -2,16 +2,37 @@
 //!
 //! Watches the ~/.claude/tasks/<list-id>/ directory for changes and emits
 //! Tauri events when task files are created, modified, or deleted.
+//!
+//! Besides the coarse "task-list-changed:<list-id>" signal, each change is
+//! reported on "task-event:<list-id>" as task/created, task/updated,
+//! task/statusChanged or task/deleted with the list of changed fields.
 
 use notify::RecursiveMode;
 use notify_debouncer_mini::new_debouncer;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::{mpsc, Mutex};
 
 use crate::claude_home::resolve_default_claude_home;
+use crate::task_manager::{read_task_if_exists, read_task_list, Task, TaskStatus};
+
+/// A single task change, emitted on "task-event:<list-id>"
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskChangeEvent {
+    /// "task/created", "task/updated", "task/statusChanged" or "task/deleted"
+    pub kind: String,
+    pub list_id: String,
+    pub task_id: String,
+    /// Current task contents; None for deletions
+    pub task: Option<Task>,
+    /// camelCase names of the fields that differ from the previous version
+    pub changed_fields: Vec<String>,
+    pub previous_status: Option<TaskStatus>,
+}
 
 /// Holds the shutdown sender for a task watcher
 pub struct TaskWatcher {
@@ -45,9 +66,77 @@ fn get_tasks_dir(list_id: &str) -> Option<PathBuf> {
     Some(claude_home.join("tasks").join(list_id))
 }
 
+/// Names of the fields that differ between two versions of a task
+fn diff_task_fields(previous: &Task, current: &Task) -> Vec<String> {
+    let mut changed = Vec::new();
+    if previous.subject != current.subject {
+        changed.push("subject");
+    }
+    if previous.description != current.description {
+        changed.push("description");
+    }
+    if previous.active_form != current.active_form {
+        changed.push("activeForm");
+    }
+    if previous.status != current.status {
+        changed.push("status");
+    }
+    if previous.owner != current.owner {
+        changed.push("owner");
+    }
+    if previous.blocks != current.blocks {
+        changed.push("blocks");
+    }
+    if previous.blocked_by != current.blocked_by {
+        changed.push("blockedBy");
+    }
+    if previous.depends_on != current.depends_on {
+        changed.push("dependsOn");
+    }
+    if previous.metadata != current.metadata {
+        changed.push("metadata");
+    }
+    changed.into_iter().map(|field| field.to_string()).collect()
+}
+
+/// Classify the change between the last known and current version of a task.
+/// Returns None when nothing observable changed (e.g. a rewrite with identical content).
+fn classify_task_change(
+    list_id: &str,
+    task_id: &str,
+    previous: Option<&Task>,
+    current: Option<&Task>,
+) -> Option<TaskChangeEvent> {
+    let (kind, changed_fields) = match (previous, current) {
+        (None, None) => return None,
+        (None, Some(_)) => ("task/created", Vec::new()),
+        (Some(_), None) => ("task/deleted", Vec::new()),
+        (Some(previous), Some(current)) => {
+            let changed = diff_task_fields(previous, current);
+            if changed.is_empty() {
+                return None;
+            }
+            if changed.iter().any(|field| field == "status") {
+                ("task/statusChanged", changed)
+            } else {
+                ("task/updated", changed)
+            }
+        }
+    };
+    Some(TaskChangeEvent {
+        kind: kind.to_string(),
+        list_id: list_id.to_string(),
+        task_id: task_id.to_string(),
+        task: current.cloned(),
+        changed_fields,
+        previous_status: previous.map(|task| task.status.clone()),
+    })
+}
+
 /// Start watching a task list directory for changes.
 ///
-/// Emits "task-list-changed:<list-id>" events when .json files change.
+/// Emits "task-list-changed:<list-id>" events when .json files change, and a
+/// "task-event:<list-id>" event per created, updated or deleted task.
 #[tauri::command]
 pub async fn task_watcher_start(list_id: String, app_handle: AppHandle) -> Result<(), String> {
     let state = app_handle.state::<TaskWatcherState>();
@@ -74,9 +163,17 @@ pub async fn task_watcher_start(list_id: String, app_handle: AppHandle) -> Resul
     let app_handle_clone = app_handle.clone();
     let tasks_dir_clone = tasks_dir.clone();
 
+    // Snapshot of the current tasks, used to diff subsequent changes
+    let mut known_tasks: HashMap<String, Task> = read_task_list(&list_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|task| (task.id.clone(), task))
+        .collect();
+
     // Spawn the watcher task
     tokio::spawn(async move {
         let event_name = format!("task-list-changed:{}", list_id_clone);
+        let task_event_name = format!("task-event:{}", list_id_clone);
 
         // Create a channel for debounced events
         let (tx, rx) = std::sync::mpsc::channel();
@@ -85,24 +182,24 @@ pub async fn task_watcher_start(list_id: String, app_handle: AppHandle) -> Resul
         let mut debouncer = match new_debouncer(Duration::from_millis(100), tx) {
             Ok(d) => d,
             Err(e) => {
-                eprintln!("Failed to create task watcher debouncer: {}", e);
+                tracing::warn!("Failed to create task watcher debouncer: {}", e);
                 return;
             }
         };
 
         // Start watching the directory
         if let Err(e) = debouncer.watcher().watch(&tasks_dir_clone, RecursiveMode::NonRecursive) {
-            eprintln!("Failed to watch tasks directory {:?}: {}", tasks_dir_clone, e);
+            tracing::warn!("Failed to watch tasks directory {:?}: {}", tasks_dir_clone, e);
             return;
         }
 
-        println!("Started watching tasks directory: {:?}", tasks_dir_clone);
+        tracing::debug!("Started watching tasks directory: {:?}", tasks_dir_clone);
 
         // Process events in a loop
         loop {
             tokio::select! {
                 _ = shutdown_rx.recv() => {
-                    println!("Stopping task watcher for list: {}", list_id_clone);
+                    tracing::debug!("Stopping task watcher for list: {}", list_id_clone);
                     break;
                 }
                 _ = tokio::time::sleep(Duration::from_millis(50)) => {
@@ -117,20 +214,60 @@ pub async fn task_watcher_start(list_id: String, app_handle: AppHandle) -> Resul
                             });
 
                             if has_json_change {
-                                println!("Task list changed: {}", list_id_clone);
+                                tracing::debug!("Task list changed: {}", list_id_clone);
                                 if let Err(e) = app_handle_clone.emit(&event_name, ()) {
-                                    eprintln!("Failed to emit task-list-changed event: {}", e);
+                                    tracing::warn!("Failed to emit task-list-changed event: {}", e);
+                                }
+
+                                let changed_ids: BTreeSet<String> = events
+                                    .iter()
+                                    .filter(|event| {
+                                        event.path.extension().map(|ext| ext == "json").unwrap_or(false)
+                                    })
+                                    .filter_map(|event| {
+                                        event.path.file_stem().and_then(|stem| stem.to_str()).map(|stem| stem.to_string())
+                                    })
+                                    .collect();
+                                for task_id in changed_ids {
+                                    // Only a missing file means the task was deleted; a file caught
+                                    // mid-write keeps its last snapshot until the next change.
+                                    let current = match read_task_if_exists(&list_id_clone, &task_id) {
+                                        Ok(current) => current,
+                                        Err(e) => {
+                                            tracing::debug!("Skipping task {} change: {}", task_id, e);
+                                            continue;
+                                        }
+                                    };
+                                    let change = classify_task_change(
+                                        &list_id_clone,
+                                        &task_id,
+                                        known_tasks.get(&task_id),
+                                        current.as_ref(),
+                                    );
+                                    match current {
+                                        Some(task) => {
+                                            known_tasks.insert(task_id.clone(), task);
+                                        }
+                                        None => {
+                                            known_tasks.remove(&task_id);
+                                        }
+                                    }
+                                    if let Some(change) = change {
+                                        if let Err(e) = app_handle_clone.emit(&task_event_name, change) {
+                                            tracing::warn!("Failed to emit task event: {}", e);
+                                        }
+                                    }
                                 }
                             }
                         }
                         Ok(Err(error)) => {
-                            eprintln!("Task watcher error: {:?}", error);
+                            tracing::warn!("Task watcher error: {:?}", error);
                         }
                         Err(std::sync::mpsc::TryRecvError::Empty) => {
                             // No events, continue
                         }
                         Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                            println!("Task watcher channel disconnected for list: {}", list_id_clone);
+                            tracing::warn!("Task watcher channel disconnected for list: {}", list_id_clone);
                             break;
                         }
                     }
@@ -158,7 +295,7 @@ pub async fn task_watcher_stop(list_id: String, app_handle: AppHandle) -> Result
 
     if let Some(watcher) = watchers.remove(&list_id) {
         watcher.stop().await;
-        println!("Stopped task watcher for list: {}", list_id);
+        tracing::debug!("Stopped task watcher for list: {}", list_id);
     }
 
     Ok(())
@@ -172,7 +309,7 @@ pub async fn stop_all_watchers(app_handle: &AppHandle) {
 
     for (list_id, watcher) in watchers.drain() {
         let _ = watcher.shutdown_tx.send(()).await;
-        println!("Stopped task watcher for list: {}", list_id);
+        tracing::debug!("Stopped task watcher for list: {}", list_id);
     }
 }
 
@@ -193,4 +330,54 @@ mod tests {
             assert!(path.to_string_lossy().contains(list_id));
         }
     }
+
+    fn make_task(status: TaskStatus) -> Task {
+        Task {
+            id: "1".to_string(),
+            subject: "Write docs".to_string(),
+            description: String::new(),
+            active_form: None,
+            status,
+            owner: None,
+            blocks: Vec::new(),
+            blocked_by: Vec::new(),
+            depends_on: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_task_change_kinds() {
+        let pending = make_task(TaskStatus::Pending);
+
+        let created = classify_task_change("list", "1", None, Some(&pending)).unwrap();
+        assert_eq!(created.kind, "task/created");
+        assert!(created.previous_status.is_none());
+
+        let deleted = classify_task_change("list", "1", Some(&pending), None).unwrap();
+        assert_eq!(deleted.kind, "task/deleted");
+        assert!(deleted.task.is_none());
+
+        assert!(classify_task_change("list", "1", Some(&pending), Some(&pending)).is_none());
+    }
+
+    #[test]
+    fn test_classify_task_change_reports_changed_fields() {
+        let pending = make_task(TaskStatus::Pending);
+
+        let mut renamed = pending.clone();
+        renamed.subject = "Write better docs".to_string();
+        renamed.owner = Some("agent-1".to_string());
+        let updated = classify_task_change("list", "1", Some(&pending), Some(&renamed)).unwrap();
+        assert_eq!(updated.kind, "task/updated");
+        assert_eq!(updated.changed_fields, vec!["subject", "owner"]);
+
+        let mut started = pending.clone();
+        started.status = TaskStatus::InProgress;
+        let status_changed =
+            classify_task_change("list", "1", Some(&pending), Some(&started)).unwrap();
+        assert_eq!(status_changed.kind, "task/statusChanged");
+        assert_eq!(status_changed.changed_fields, vec!["status"]);
+        assert_eq!(status_changed.previous_status, Some(TaskStatus::Pending));
+    }
 }
@@ -27,7 +27,7 @@ fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
     format!("{workspace_id}:{terminal_id}")
 }
 
-fn shell_path() -> String {
+pub(crate) fn shell_path() -> String {
     std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string())
 }
 
@@ -80,17 +80,44 @@ pub(crate) async fn terminal_open(
     if terminal_id.is_empty() {
         return Err("Terminal id is required".to_string());
     }
-    let key = terminal_key(&workspace_id, &terminal_id);
+    let cwd = get_workspace_path(&workspace_id, &state).await?;
+    let session = open_terminal(
+        &state,
+        app,
+        &workspace_id,
+        &terminal_id,
+        cwd,
+        cols,
+        rows,
+        None,
+    )
+    .await?;
+    Ok(TerminalSessionInfo {
+        id: session.id.clone(),
+    })
+}
+
+/// Open a pty in `cwd` running an interactive shell, or `command` through the
+/// login shell when given. An existing session with the same id is reused.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn open_terminal(
+    state: &AppState,
+    app: AppHandle,
+    workspace_id: &str,
+    terminal_id: &str,
+    cwd: PathBuf,
+    cols: u16,
+    rows: u16,
+    command: Option<&str>,
+) -> Result<Arc<TerminalSession>, String> {
+    let key = terminal_key(workspace_id, terminal_id);
     {
         let sessions = state.terminal_sessions.lock().await;
         if let Some(existing) = sessions.get(&key) {
-            return Ok(TerminalSessionInfo {
-                id: existing.id.clone(),
-            });
+            return Ok(existing.clone());
         }
     }
 
-    let cwd = get_workspace_path(&workspace_id, &state).await?;
     let pty_system = native_pty_system();
     let size = PtySize {
         rows: rows.max(2),
@@ -104,7 +131,13 @@ pub(crate) async fn terminal_open(
 
     let mut cmd = CommandBuilder::new(shell_path());
     cmd.cwd(cwd);
-    cmd.arg("-i");
+    match command {
+        Some(command) => {
+            cmd.arg("-lc");
+            cmd.arg(command);
+        }
+        None => cmd.arg("-i"),
+    }
     cmd.env("TERM", "xterm-256color");
 
     let child = pair
@@ -121,30 +154,77 @@ pub(crate) async fn terminal_open(
         .map_err(|e| format!("Failed to open pty writer: {e}"))?;
 
     let session = Arc::new(TerminalSession {
-        id: terminal_id.clone(),
+        id: terminal_id.to_string(),
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
     });
-    let session_id = session.id.clone();
 
     {
         let mut sessions = state.terminal_sessions.lock().await;
         if let Some(existing) = sessions.get(&key) {
             let mut child = session.child.lock().await;
             let _ = child.kill();
-            return Ok(TerminalSessionInfo {
-                id: existing.id.clone(),
-            });
+            return Ok(existing.clone());
         }
-        sessions.insert(key, session);
+        sessions.insert(key, session.clone());
     }
     let event_sink = TauriEventSink::new(app);
-    spawn_terminal_reader(event_sink, workspace_id, terminal_id, reader);
+    spawn_terminal_reader(
+        event_sink,
+        workspace_id.to_string(),
+        terminal_id.to_string(),
+        reader,
+    );
 
-    Ok(TerminalSessionInfo {
-        id: session_id,
-    })
+    Ok(session)
+}
+
+/// Kill and forget a terminal, returning whether it existed.
+pub(crate) async fn close_terminal(
+    state: &AppState,
+    workspace_id: &str,
+    terminal_id: &str,
+) -> bool {
+    let key = terminal_key(workspace_id, terminal_id);
+    let Some(session) = state.terminal_sessions.lock().await.remove(&key) else {
+        return false;
+    };
+    let mut child = session.child.lock().await;
+    let _ = child.kill();
+    true
+}
+
+/// The session registered under `terminal_id`, if any.
+pub(crate) async fn find_terminal(
+    state: &AppState,
+    workspace_id: &str,
+    terminal_id: &str,
+) -> Option<Arc<TerminalSession>> {
+    let key = terminal_key(workspace_id, terminal_id);
+    state.terminal_sessions.lock().await.get(&key).cloned()
+}
+
+/// Kill and forget every terminal of a workspace whose id starts with `prefix`.
+pub(crate) async fn close_terminals_with_prefix(
+    state: &AppState,
+    workspace_id: &str,
+    prefix: &str,
+) {
+    let key_prefix = terminal_key(workspace_id, prefix);
+    let closed: Vec<Arc<TerminalSession>> = {
+        let mut sessions = state.terminal_sessions.lock().await;
+        let keys: Vec<String> = sessions
+            .keys()
+            .filter(|key| key.starts_with(&key_prefix))
+            .cloned()
+            .collect();
+        keys.iter().filter_map(|key| sessions.remove(key)).collect()
+    };
+    for session in closed {
+        let mut child = session.child.lock().await;
+        let _ = child.kill();
+    }
 }
 
 #[tauri::command]
@@ -201,12 +281,9 @@ pub(crate) async fn terminal_close(
     terminal_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let key = terminal_key(&workspace_id, &terminal_id);
-    let mut sessions = state.terminal_sessions.lock().await;
-    let session = sessions
-        .remove(&key)
-        .ok_or_else(|| "Terminal session not found".to_string())?;
-    let mut child = session.child.lock().await;
-    let _ = child.kill();
-    Ok(())
+    if close_terminal(&state, &workspace_id, &terminal_id).await {
+        Ok(())
+    } else {
+        Err("Terminal session not found".to_string())
+    }
 }
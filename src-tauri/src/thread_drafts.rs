@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::storage::{read_thread_drafts, write_thread_drafts};
+use crate::utils::now_millis;
+
+/// Unsent composer contents for one thread.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadDraft {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) text: String,
+    /// Attached image paths
+    #[serde(default)]
+    pub(crate) attachments: Vec<String>,
+    #[serde(default)]
+    pub(crate) updated_at: i64,
+}
+
+impl ThreadDraft {
+    fn is_empty(&self) -> bool {
+        self.text.trim().is_empty() && self.attachments.is_empty()
+    }
+}
+
+/// Drafts keyed by thread id, mirrored to `thread-drafts.json`.
+pub(crate) struct ThreadDrafts {
+    path: PathBuf,
+    drafts: Mutex<HashMap<String, ThreadDraft>>,
+}
+
+impl ThreadDrafts {
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let drafts = read_thread_drafts(&path).unwrap_or_default();
+        Self {
+            path,
+            drafts: Mutex::new(drafts),
+        }
+    }
+
+    /// Drop every draft belonging to a workspace, e.g. when it is removed.
+    /// Returns how many were dropped.
+    pub(crate) async fn forget_workspace(&self, workspace_id: &str) -> usize {
+        self.forget(|draft| draft.workspace_id == workspace_id).await
+    }
+
+    /// Drop drafts last saved before `cutoff_ms`. Returns how many were dropped.
+    pub(crate) async fn forget_saved_before(&self, cutoff_ms: i64) -> usize {
+        self.forget(|draft| draft.updated_at < cutoff_ms).await
+    }
+
+    async fn forget(&self, matches: impl Fn(&ThreadDraft) -> bool) -> usize {
+        let mut drafts = self.drafts.lock().await;
+        let before = drafts.len();
+        drafts.retain(|_, draft| !matches(draft));
+        let removed = before - drafts.len();
+        if removed > 0 {
+            if let Err(error) = write_thread_drafts(&self.path, &drafts) {
+                tracing::warn!("[thread_drafts] Failed to persist drafts: {error}");
+            }
+        }
+        removed
+    }
+}
+
+/// Insert or clear a draft. Returns whether the map changed.
+fn apply_draft(
+    drafts: &mut HashMap<String, ThreadDraft>,
+    thread_id: &str,
+    draft: ThreadDraft,
+) -> bool {
+    if draft.is_empty() {
+        return drafts.remove(thread_id).is_some();
+    }
+    let unchanged = drafts.get(thread_id).is_some_and(|existing| {
+        existing.workspace_id == draft.workspace_id
+            && existing.text == draft.text
+            && existing.attachments == draft.attachments
+    });
+    if unchanged {
+        return false;
+    }
+    drafts.insert(thread_id.to_string(), draft);
+    true
+}
+
+/// Persist the composer text and attachments for a thread. Saving an empty
+/// draft deletes it.
+#[tauri::command]
+pub(crate) async fn thread_draft_save(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    attachments: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "thread_draft_save",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "text": text,
+                "attachments": attachments,
+            }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let draft = ThreadDraft {
+        workspace_id,
+        text,
+        attachments: attachments.unwrap_or_default(),
+        updated_at: now_millis(),
+    };
+    let store = &state.thread_drafts;
+    let mut drafts = store.drafts.lock().await;
+    if apply_draft(&mut drafts, &thread_id, draft) {
+        write_thread_drafts(&store.path, &drafts)?;
+    }
+    Ok(())
+}
+
+/// Load the saved draft for a thread, if any.
+#[tauri::command]
+pub(crate) async fn thread_draft_get(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<ThreadDraft>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "thread_draft_get",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let drafts = state.thread_drafts.drafts.lock().await;
+    Ok(drafts
+        .get(&thread_id)
+        .filter(|draft| draft.workspace_id == workspace_id)
+        .cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(text: &str, attachments: &[&str]) -> ThreadDraft {
+        ThreadDraft {
+            workspace_id: "ws-1".to_string(),
+            text: text.to_string(),
+            attachments: attachments.iter().map(|path| path.to_string()).collect(),
+            updated_at: 1,
+        }
+    }
+
+    #[test]
+    fn apply_draft_inserts_updates_and_clears() {
+        let mut drafts = HashMap::new();
+        assert!(apply_draft(&mut drafts, "t1", draft("half a thought", &[])));
+        assert!(!apply_draft(&mut drafts, "t1", draft("half a thought", &[])));
+        assert!(apply_draft(&mut drafts, "t1", draft("half a thought", &["/tmp/a.png"])));
+        assert_eq!(drafts["t1"].attachments, vec!["/tmp/a.png".to_string()]);
+        assert!(apply_draft(&mut drafts, "t1", draft("  ", &[])));
+        assert!(drafts.is_empty());
+        assert!(!apply_draft(&mut drafts, "t1", draft("", &[])));
+    }
+
+    #[test]
+    fn image_only_drafts_are_kept() {
+        let mut drafts = HashMap::new();
+        assert!(apply_draft(&mut drafts, "t1", draft("", &["/tmp/a.png"])));
+        assert!(drafts.contains_key("t1"));
+    }
+}
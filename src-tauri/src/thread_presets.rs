@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::claude::{
+    cli_permission_mode, ensure_persistent_session, ensure_workspace_session,
+    ensure_workspace_thread_watcher,
+};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::turn_scheduler::dispatch_turn;
+use crate::types::ThreadPreset;
+use crate::utils::now_millis;
+
+/// Same placeholder syntax as custom prompts; `$$NAME` is left alone.
+fn placeholder_regex() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\$+[A-Z][A-Z0-9_]*").expect("valid placeholder regex"))
+}
+
+pub(crate) fn validate_thread_presets(presets: &[ThreadPreset]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for preset in presets {
+        if preset.id.trim().is_empty() || preset.name.trim().is_empty() {
+            return Err("Thread presets need an id and a name.".to_string());
+        }
+        if !ids.insert(preset.id.as_str()) {
+            return Err(format!("Duplicate thread preset id \"{}\".", preset.id));
+        }
+        if preset.prompt_template.trim().is_empty() {
+            return Err(format!(
+                "Thread preset \"{}\" has an empty prompt.",
+                preset.name
+            ));
+        }
+        if let Some(mode) = &preset.access_mode {
+            if mode.trim() != "current" && cli_permission_mode(mode).is_none() {
+                return Err(format!(
+                    "Unknown access mode \"{mode}\" in preset \"{}\".",
+                    preset.name
+                ));
+            }
+        }
+        if let Some(skill) = preset
+            .skills
+            .iter()
+            .find(|skill| skill.is_empty() || skill.contains(char::is_whitespace))
+        {
+            return Err(format!(
+                "Invalid skill \"{skill}\" in preset \"{}\".",
+                preset.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fill the preset's placeholders and mention its skills ahead of the prompt.
+pub(crate) fn render_preset_prompt(
+    preset: &ThreadPreset,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut missing: Vec<String> = Vec::new();
+    let prompt =
+        placeholder_regex().replace_all(&preset.prompt_template, |caps: &regex::Captures| {
+            let token = &caps[0];
+            let name = token.trim_start_matches('$');
+            if token.len() - name.len() > 1 {
+                return token.to_string();
+            }
+            match variables.get(name) {
+                Some(value) => value.clone(),
+                None => {
+                    if !missing.iter().any(|entry| entry == name) {
+                        missing.push(name.to_string());
+                    }
+                    token.to_string()
+                }
+            }
+        });
+    if !missing.is_empty() {
+        return Err(format!(
+            "Missing values for preset \"{}\": {}.",
+            preset.name,
+            missing.join(", ")
+        ));
+    }
+    let prompt = prompt.trim();
+    if preset.skills.is_empty() {
+        return Ok(prompt.to_string());
+    }
+    let mentions: Vec<String> = preset
+        .skills
+        .iter()
+        .map(|skill| format!("${skill}"))
+        .collect();
+    Ok(format!("{}\n\n{prompt}", mentions.join(" ")))
+}
+
+/// Start a thread configured by one of the workspace's presets, sending its
+/// rendered prompt as the first message.
+#[tauri::command]
+pub(crate) async fn start_thread_from_preset(
+    workspace_id: String,
+    preset_id: String,
+    variables: Option<HashMap<String, String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "start_thread_from_preset",
+            json!({
+                "workspaceId": workspace_id,
+                "presetId": preset_id,
+                "variables": variables,
+            }),
+        )
+        .await;
+    }
+
+    let preset = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
+        entry
+            .settings
+            .thread_presets
+            .iter()
+            .find(|preset| preset.id == preset_id)
+            .cloned()
+            .ok_or_else(|| format!("Thread preset {preset_id} not found"))?
+    };
+    let prompt = render_preset_prompt(&preset, &variables.unwrap_or_default())?;
+
+    let session = ensure_workspace_session(&state, &app, &workspace_id).await?;
+    ensure_workspace_thread_watcher(&workspace_id, session.entry.clone(), &state, app.clone())
+        .await;
+
+    let thread_id = Uuid::new_v4().to_string();
+    let event_sink = TauriEventSink::new(app);
+    let turn_id = ensure_persistent_session(
+        &workspace_id,
+        &session,
+        &thread_id,
+        preset.model.as_deref(),
+        preset.access_mode.as_deref(),
+        None,
+        event_sink.clone(),
+    )
+    .await?;
+    let queue_position = dispatch_turn(
+        &state,
+        &event_sink,
+        &session,
+        &workspace_id,
+        &thread_id,
+        &turn_id,
+        &prompt,
+    )
+    .await?;
+
+    let timestamp = now_millis();
+    Ok(json!({
+        "workspaceId": workspace_id,
+        "preset": { "id": preset.id, "name": preset.name },
+        "thread": {
+            "id": thread_id,
+            "createdAt": timestamp,
+            "updatedAt": timestamp,
+            "cwd": session.entry.path,
+        },
+        "turn": { "id": turn_id, "threadId": thread_id },
+        "queuePosition": queue_position,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(template: &str, skills: &[&str]) -> ThreadPreset {
+        ThreadPreset {
+            id: "triage".to_string(),
+            name: "Triage build failure".to_string(),
+            model: None,
+            access_mode: Some("read-only".to_string()),
+            prompt_template: template.to_string(),
+            skills: skills.iter().map(|skill| skill.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn render_preset_prompt_fills_placeholders_and_mentions_skills() {
+        let variables = HashMap::from([("JOB".to_string(), "lint".to_string())]);
+        assert_eq!(
+            render_preset_prompt(
+                &preset("Why did $JOB fail? Costs $$PRICE.", &["ci-logs"]),
+                &variables
+            )
+            .unwrap(),
+            "$ci-logs\n\nWhy did lint fail? Costs $$PRICE."
+        );
+        let missing = render_preset_prompt(&preset("$JOB on $BRANCH", &[]), &variables);
+        assert!(missing.unwrap_err().contains("BRANCH"));
+    }
+
+    #[test]
+    fn validate_thread_presets_rejects_bad_entries() {
+        assert!(validate_thread_presets(&[preset("Go", &[])]).is_ok());
+        assert!(validate_thread_presets(&[preset("Go", &[]), preset("Again", &[])]).is_err());
+        assert!(validate_thread_presets(&[preset(" ", &[])]).is_err());
+        assert!(validate_thread_presets(&[preset("Go", &["two words"])]).is_err());
+        let mut unknown_mode = preset("Go", &[]);
+        unknown_mode.access_mode = Some("yolo".to_string());
+        assert!(validate_thread_presets(&[unknown_mode]).is_err());
+    }
+}
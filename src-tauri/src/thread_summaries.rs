@@ -0,0 +1,185 @@
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tauri::Manager;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{
+    extract_text_from_message, resolve_session_path, run_claude_prompt_once, thread_meta_path,
+    update_thread_meta, workspace_thread_meta, WorkspaceSession,
+};
+use crate::event_sink::TauriEventSink;
+use crate::state::AppState;
+
+const SUMMARY_MODEL: &str = "haiku";
+const MAX_SUMMARY_CHARS: usize = 80;
+/// Enough of the exchange to tell what the thread is about.
+const MAX_EXCERPT_CHARS: usize = 2_000;
+
+/// The first prompt and first reply in a session log.
+fn first_exchange<I: IntoIterator<Item = Value>>(entries: I) -> Option<(String, String)> {
+    let mut prompt = None;
+    for entry in entries {
+        if entry.get("isMeta").and_then(|v| v.as_bool()) == Some(true) {
+            continue;
+        }
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        let text = extract_text_from_message(message);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match (entry.get("type").and_then(|v| v.as_str()), &prompt) {
+            (Some("user"), None) => prompt = Some(text.to_string()),
+            (Some("assistant"), Some(prompt)) => return Some((prompt.clone(), text.to_string())),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn excerpt(text: &str) -> String {
+    text.chars().take(MAX_EXCERPT_CHARS).collect()
+}
+
+fn build_summary_prompt(prompt: &str, reply: &str) -> String {
+    format!(
+        "Write a title of at most eight words for this coding conversation. \
+Reply with the title only, no quotes or trailing punctuation.\n\n\
+User:\n{}\n\nAssistant:\n{}",
+        excerpt(prompt),
+        excerpt(reply)
+    )
+}
+
+/// First line of the model's answer with wrapping quotes and a trailing period removed.
+fn clean_summary(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = line
+        .trim_start_matches(['#', '*'])
+        .trim_matches(['"', '\'', '`', '*'])
+        .trim_end_matches('.')
+        .trim();
+    if line.is_empty() {
+        return None;
+    }
+    Some(line.chars().take(MAX_SUMMARY_CHARS).collect())
+}
+
+fn read_first_exchange(session_path: &Path) -> Option<(String, String)> {
+    let file = std::fs::File::open(session_path).ok()?;
+    let entries = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok());
+    first_exchange(entries)
+}
+
+/// Title a thread from its first exchange once a turn completes, if summaries
+/// are enabled and it has none yet. The title is stored with the thread's
+/// metadata, so `list_threads` returns it as the preview.
+pub(crate) fn summarize_thread(
+    event_sink: &TauriEventSink,
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    thread_id: &str,
+) {
+    let event_sink = event_sink.clone();
+    let session = Arc::clone(session);
+    let workspace_id = workspace_id.to_string();
+    let thread_id = thread_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = event_sink.app_handle().state::<AppState>();
+        if !state.app_settings.lock().await.thread_summaries_enabled {
+            return;
+        }
+        let has_summary = workspace_thread_meta(&state, &workspace_id)
+            .get(&thread_id)
+            .is_some_and(|meta| meta.summary.is_some());
+        if has_summary {
+            return;
+        }
+        let entry = session.entry.clone();
+        let lookup_id = thread_id.clone();
+        let exchange = tokio::task::spawn_blocking(move || {
+            resolve_session_path(&entry, &lookup_id).and_then(|path| read_first_exchange(&path))
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some((prompt, reply)) = exchange else {
+            return;
+        };
+        let generated = run_claude_prompt_once(
+            &session.entry.path,
+            session.claude_bin.clone(),
+            build_summary_prompt(&prompt, &reply),
+            Some("dontAsk".to_string()),
+            Some(SUMMARY_MODEL.to_string()),
+        )
+        .await;
+        let summary = match generated {
+            Ok(raw) => clean_summary(&raw),
+            Err(error) => {
+                tracing::warn!("[thread_summaries] Failed to summarize {thread_id}: {error}");
+                return;
+            }
+        };
+        let Some(summary) = summary else {
+            return;
+        };
+        let stored = thread_meta_path(&state).and_then(|path| {
+            update_thread_meta(&path, &workspace_id, &thread_id, |meta| {
+                meta.summary = Some(summary.clone());
+            })
+        });
+        if let Err(error) = stored {
+            tracing::warn!("[thread_summaries] Failed to store summary for {thread_id}: {error}");
+            return;
+        }
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.clone(),
+            message: json!({
+                "method": "thread/summarized",
+                "params": { "threadId": thread_id, "summary": summary },
+            }),
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_exchange_skips_meta_and_tool_entries() {
+        let entries = vec![
+            json!({ "type": "user", "isMeta": true, "message": { "content": "<caveat>" } }),
+            json!({ "type": "user", "message": { "content": "continue" } }),
+            json!({ "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "name": "Read", "input": {} },
+            ] } }),
+            json!({ "type": "assistant", "message": { "content": [
+                { "type": "text", "text": "Resuming the auth refactor." },
+            ] } }),
+        ];
+        assert_eq!(
+            first_exchange(entries),
+            Some(("continue".to_string(), "Resuming the auth refactor.".to_string()))
+        );
+    }
+
+    #[test]
+    fn clean_summary_strips_decoration() {
+        assert_eq!(
+            clean_summary("\n\"Refactor auth token refresh.\"\nExtra").as_deref(),
+            Some("Refactor auth token refresh")
+        );
+        assert_eq!(clean_summary("**Fix flaky CI**").as_deref(), Some("Fix flaky CI"));
+        assert_eq!(clean_summary("  \n "), None);
+    }
+}
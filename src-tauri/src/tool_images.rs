@@ -0,0 +1,196 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+
+use crate::file_io::content_hash;
+
+pub(crate) const TOOL_IMAGES_DIR_NAME: &str = "tool-images";
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Remember where tool result images are cached. Called once at startup.
+pub(crate) fn init_tool_image_cache(data_dir: &Path) {
+    let _ = CACHE_DIR.set(data_dir.join(TOOL_IMAGES_DIR_NAME));
+}
+
+fn extension_for(media_type: &str) -> &'static str {
+    match media_type {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        _ => "bin",
+    }
+}
+
+/// Write decoded image bytes into `dir`, named by content so repeated loads of
+/// the same transcript reuse the file.
+fn write_cached_image(dir: &Path, bytes: &[u8], media_type: &str) -> Result<PathBuf, String> {
+    let path = dir.join(format!(
+        "{}.{}",
+        content_hash(bytes),
+        extension_for(media_type)
+    ));
+    if path.exists() {
+        return Ok(path);
+    }
+    std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    std::fs::write(&path, bytes).map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
+/// Swap one base64 image block for a reference to its cached file.
+fn cache_image_block(dir: &Path, block: &Value) -> Option<(Value, Value)> {
+    if block.get("type").and_then(|v| v.as_str()) != Some("image") {
+        return None;
+    }
+    let source = block.get("source")?;
+    if source.get("type").and_then(|v| v.as_str()) != Some("base64") {
+        return None;
+    }
+    let media_type = source
+        .get("media_type")
+        .or_else(|| source.get("mediaType"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("image/png");
+    let data = source.get("data").and_then(|v| v.as_str())?;
+    let bytes = STANDARD.decode(data.trim()).ok()?;
+    let path = match write_cached_image(dir, &bytes, media_type) {
+        Ok(path) => path,
+        Err(error) => {
+            tracing::warn!("[tool_images] Failed to cache image: {error}");
+            return None;
+        }
+    };
+    let image = json!({
+        "path": path.to_string_lossy(),
+        "mediaType": media_type,
+        "bytes": bytes.len(),
+    });
+    let replacement = json!({
+        "type": "image",
+        "path": path.to_string_lossy(),
+        "mediaType": media_type,
+    });
+    Some((replacement, image))
+}
+
+fn extract_images_into(dir: &Path, content: &Value) -> (Value, Vec<Value>) {
+    let Some(blocks) = content.as_array() else {
+        return (content.clone(), Vec::new());
+    };
+    let mut images = Vec::new();
+    let sanitized = blocks
+        .iter()
+        .map(|block| match cache_image_block(dir, block) {
+            Some((replacement, image)) => {
+                images.push(image);
+                replacement
+            }
+            None => block.clone(),
+        })
+        .collect();
+    (Value::Array(sanitized), images)
+}
+
+/// The workspace's own cache directory, so purging a workspace can drop its images.
+fn workspace_cache_dir(root: &Path, workspace_id: &str) -> Option<PathBuf> {
+    let mut components = Path::new(workspace_id).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Some(root.join(workspace_id)),
+        _ => None,
+    }
+}
+
+/// Move base64 image blocks out of a tool_result's content into the
+/// workspace's cache dir.
+///
+/// Returns the content with each image replaced by `{type: "image", path,
+/// mediaType}` and the list of cached images to attach to the tool item.
+pub(crate) fn extract_tool_result_images(
+    workspace_id: &str,
+    content: &Value,
+) -> (Value, Vec<Value>) {
+    match CACHE_DIR
+        .get()
+        .and_then(|root| workspace_cache_dir(root, workspace_id))
+    {
+        Some(dir) => extract_images_into(&dir, content),
+        None => (content.clone(), Vec::new()),
+    }
+}
+
+/// Attach cached images to an emitted tool item.
+pub(crate) fn with_tool_images(mut item: Value, images: Vec<Value>) -> Value {
+    if images.is_empty() {
+        return item;
+    }
+    if let Value::Object(ref mut map) = item {
+        map.insert("images".to_string(), Value::Array(images));
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_images_caches_base64_blocks() {
+        let dir = std::env::temp_dir().join(format!("tool-images-{}", uuid::Uuid::new_v4()));
+        let content = json!([
+            { "type": "text", "text": "Took a screenshot" },
+            {
+                "type": "image",
+                "source": { "type": "base64", "media_type": "image/png", "data": STANDARD.encode(b"png-bytes") }
+            }
+        ]);
+        let (sanitized, images) = extract_images_into(&dir, &content);
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0]["mediaType"], "image/png");
+        assert_eq!(images[0]["bytes"], 9);
+        let path = PathBuf::from(images[0]["path"].as_str().unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), b"png-bytes");
+        assert_eq!(sanitized[0]["text"], "Took a screenshot");
+        assert_eq!(sanitized[1]["path"], images[0]["path"]);
+        assert!(sanitized[1].get("source").is_none());
+
+        // Same bytes map to the same cached file.
+        let (_, again) = extract_images_into(&dir, &content);
+        assert_eq!(again[0]["path"], images[0]["path"]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn extract_images_leaves_other_content_alone() {
+        let dir = std::env::temp_dir().join(format!("tool-images-{}", uuid::Uuid::new_v4()));
+        let text = json!("plain output");
+        assert_eq!(extract_images_into(&dir, &text), (text.clone(), Vec::new()));
+        let url_image = json!([{ "type": "image", "source": { "type": "url", "url": "https://x" } }]);
+        let (sanitized, images) = extract_images_into(&dir, &url_image);
+        assert!(images.is_empty());
+        assert_eq!(sanitized, url_image);
+    }
+
+    #[test]
+    fn workspace_cache_dir_rejects_paths() {
+        let root = Path::new("/cache");
+        assert_eq!(
+            workspace_cache_dir(root, "ws-1"),
+            Some(PathBuf::from("/cache/ws-1"))
+        );
+        assert!(workspace_cache_dir(root, "../ws").is_none());
+        assert!(workspace_cache_dir(root, "").is_none());
+    }
+
+    #[test]
+    fn with_tool_images_only_adds_non_empty_lists() {
+        let item = with_tool_images(json!({ "id": "tool-1" }), Vec::new());
+        assert!(item.get("images").is_none());
+        let item = with_tool_images(item, vec![json!({ "path": "/tmp/a.png" })]);
+        assert_eq!(item["images"][0]["path"], "/tmp/a.png");
+    }
+}
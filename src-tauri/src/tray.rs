@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::backend::events::AppServerEvent;
+use crate::state::AppState;
+use crate::utils::now_millis;
+use crate::window::set_running_turn_indicator;
+
+const TRAY_ID: &str = "main";
+const RATE_LIMIT_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// A turn that has started and not completed yet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunningTurn {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) started_at: i64,
+}
+
+/// Running turns, kept up to date from `turn/started` and `turn/completed` events.
+#[derive(Default)]
+pub(crate) struct TurnActivity {
+    running: Mutex<HashMap<(String, String), RunningTurn>>,
+}
+
+impl TurnActivity {
+    /// Apply a turn lifecycle event; returns true when the set of running turns changed.
+    pub(crate) fn observe(&self, workspace_id: &str, message: &Value) -> bool {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        if method != "turn/started" && method != "turn/completed" {
+            return false;
+        }
+        let params = message.get("params");
+        let turn = params.and_then(|params| params.get("turn"));
+        let Some(thread_id) = params
+            .and_then(|params| params.get("threadId"))
+            .or_else(|| turn.and_then(|turn| turn.get("threadId")))
+            .and_then(Value::as_str)
+        else {
+            return false;
+        };
+        let key = (workspace_id.to_string(), thread_id.to_string());
+        let mut running = self.running.lock().unwrap_or_else(|err| err.into_inner());
+        if method == "turn/completed" {
+            return running.remove(&key).is_some();
+        }
+        let turn_id = turn
+            .and_then(|turn| turn.get("id"))
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+        if running.get(&key).is_some_and(|existing| existing.turn_id == turn_id) {
+            return false;
+        }
+        running.insert(
+            key,
+            RunningTurn {
+                workspace_id: workspace_id.to_string(),
+                thread_id: thread_id.to_string(),
+                turn_id,
+                started_at: now_millis(),
+            },
+        );
+        true
+    }
+
+    /// Running turns, oldest first.
+    pub(crate) fn running(&self) -> Vec<RunningTurn> {
+        let running = self.running.lock().unwrap_or_else(|err| err.into_inner());
+        let mut turns: Vec<RunningTurn> = running.values().cloned().collect();
+        turns.sort_by_key(|turn| turn.started_at);
+        turns
+    }
+}
+
+/// Last rate-limit summary shown in the tray menu.
+#[derive(Default)]
+struct TrayRateLimits(Mutex<Option<String>>);
+
+enum TrayAction {
+    Show,
+    Quit,
+    OpenWorkspace(String),
+    Interrupt {
+        workspace_id: String,
+        thread_id: String,
+        turn_id: String,
+    },
+}
+
+fn interrupt_item_id(turn: &RunningTurn) -> String {
+    format!(
+        "tray_interrupt:{}:{}:{}",
+        turn.workspace_id, turn.thread_id, turn.turn_id
+    )
+}
+
+fn parse_tray_action(id: &str) -> Option<TrayAction> {
+    match id {
+        "tray_show" => return Some(TrayAction::Show),
+        "tray_quit" => return Some(TrayAction::Quit),
+        _ => {}
+    }
+    if let Some(workspace_id) = id.strip_prefix("tray_open:") {
+        return Some(TrayAction::OpenWorkspace(workspace_id.to_string()));
+    }
+    let rest = id.strip_prefix("tray_interrupt:")?;
+    let mut parts = rest.splitn(3, ':');
+    Some(TrayAction::Interrupt {
+        workspace_id: parts.next()?.to_string(),
+        thread_id: parts.next()?.to_string(),
+        turn_id: parts.next()?.to_string(),
+    })
+}
+
+/// "5h 42% · 7d 10%" from a `global_rate_limits` response.
+fn summarize_rate_limits(value: &Value) -> Option<String> {
+    let limits = value.get("rateLimits")?;
+    let parts: Vec<String> = [("5h", "primary"), ("7d", "secondary")]
+        .iter()
+        .filter_map(|(label, key)| {
+            let used = limits.get(key)?.get("usedPercent")?.as_f64()?;
+            Some(format!("{label} {}%", used.round() as i64))
+        })
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" · "))
+    }
+}
+
+fn running_label(count: usize) -> String {
+    match count {
+        0 => "No turns running".to_string(),
+        1 => "1 turn running".to_string(),
+        count => format!("{count} turns running"),
+    }
+}
+
+async fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let state = app.state::<AppState>();
+    let running = state.turn_activity.running();
+    let connected_ids: Vec<String> = state.sessions.lock().await.keys().cloned().collect();
+    let connected: Vec<(String, String)> = {
+        let workspaces = state.workspaces.lock().await;
+        let mut connected: Vec<(String, String)> = connected_ids
+            .into_iter()
+            .map(|id| {
+                let name = workspaces
+                    .get(&id)
+                    .map(|entry| entry.name.clone())
+                    .unwrap_or_else(|| id.clone());
+                (id, name)
+            })
+            .collect();
+        connected.sort_by(|a, b| a.1.cmp(&b.1));
+        connected
+    };
+    let rate_limits = app
+        .state::<TrayRateLimits>()
+        .0
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+
+    let mut menu = MenuBuilder::new(app).item(
+        &MenuItemBuilder::with_id("tray_status", running_label(running.len()))
+            .enabled(false)
+            .build(app)?,
+    );
+    if let Some(rate_limits) = rate_limits {
+        menu = menu.item(
+            &MenuItemBuilder::with_id("tray_rate_limits", format!("Rate limits: {rate_limits}"))
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    menu = menu.separator();
+    for (workspace_id, name) in &connected {
+        let turns: Vec<&RunningTurn> = running
+            .iter()
+            .filter(|turn| &turn.workspace_id == workspace_id)
+            .collect();
+        let title = if turns.is_empty() {
+            name.clone()
+        } else {
+            format!("{name} ({})", turns.len())
+        };
+        let mut submenu = SubmenuBuilder::new(app, title)
+            .text(format!("tray_open:{workspace_id}"), "Open Workspace");
+        if !turns.is_empty() {
+            submenu = submenu.separator();
+        }
+        for (index, turn) in turns.iter().enumerate() {
+            submenu = submenu.text(
+                interrupt_item_id(turn),
+                format!("Interrupt Turn {}", index + 1),
+            );
+        }
+        menu = menu.item(&submenu.build()?);
+    }
+    if !connected.is_empty() {
+        menu = menu.separator();
+    }
+    menu.text("tray_show", "Show Claude Code Monitor")
+        .text("tray_quit", "Quit")
+        .build()
+}
+
+/// Rebuild the tray menu and badge from the current running turns.
+pub(crate) fn refresh_tray(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        let count = app.state::<AppState>().turn_activity.running().len();
+        match build_tray_menu(&app).await {
+            Ok(menu) => {
+                let _ = tray.set_menu(Some(menu));
+            }
+            Err(error) => tracing::warn!("[tray] Failed to build menu: {error}"),
+        }
+        // The title shows next to the icon on macOS; other platforms only get the tooltip.
+        let badge = (count > 0).then(|| count.to_string());
+        let _ = tray.set_title(badge.as_deref());
+        let _ = tray.set_tooltip(Some(format!("Claude Code Monitor: {}", running_label(count))));
+    });
+}
+
+/// Track turn lifecycle events and refresh the tray and dock badge when running turns change.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if state
+        .turn_activity
+        .observe(&event.workspace_id, &event.message)
+    {
+        set_running_turn_indicator(app, state.turn_activity.running().len());
+        refresh_tray(app.clone());
+    }
+}
+
+fn show_main_window(app: &AppHandle) -> Option<tauri::WebviewWindow> {
+    let window = app.get_webview_window("main")?;
+    let _ = window.show();
+    let _ = window.unminimize();
+    let _ = window.set_focus();
+    Some(window)
+}
+
+fn handle_tray_menu_event(app: &AppHandle, event: MenuEvent) {
+    let Some(action) = parse_tray_action(event.id().as_ref()) else {
+        return;
+    };
+    match action {
+        TrayAction::Show => {
+            show_main_window(app);
+        }
+        TrayAction::Quit => app.exit(0),
+        TrayAction::OpenWorkspace(workspace_id) => {
+            if let Some(window) = show_main_window(app) {
+                let _ = window.emit("menu-open-workspace", workspace_id);
+            }
+        }
+        TrayAction::Interrupt {
+            workspace_id,
+            thread_id,
+            turn_id,
+        } => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<AppState>();
+                if let Err(error) = crate::claude::turn_interrupt(
+                    workspace_id,
+                    thread_id,
+                    turn_id,
+                    state,
+                    app.clone(),
+                )
+                .await
+                {
+                    tracing::warn!("[tray] Failed to interrupt turn: {error}");
+                }
+            });
+        }
+    }
+}
+
+/// Create the tray icon and keep its rate-limit line fresh.
+pub(crate) fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    app.manage(TrayRateLimits::default());
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(format!("Claude Code Monitor: {}", running_label(0)))
+        .on_menu_event(handle_tray_menu_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    refresh_tray(app.clone());
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(RATE_LIMIT_REFRESH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let summary = match crate::claude::global_rate_limits().await {
+                Ok(value) => summarize_rate_limits(&value),
+                Err(error) => {
+                    tracing::debug!("[tray] rate limit refresh failed: {error}");
+                    continue;
+                }
+            };
+            *app
+                .state::<TrayRateLimits>()
+                .0
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()) = summary;
+            refresh_tray(app.clone());
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn turn_event(method: &str, thread_id: &str, turn_id: &str) -> Value {
+        json!({
+            "method": method,
+            "params": {
+                "threadId": thread_id,
+                "turn": { "id": turn_id, "threadId": thread_id },
+            },
+        })
+    }
+
+    #[test]
+    fn turn_activity_tracks_started_and_completed_turns() {
+        let activity = TurnActivity::default();
+        assert!(activity.observe("ws-1", &turn_event("turn/started", "t-1", "turn-1")));
+        assert!(!activity.observe("ws-1", &turn_event("turn/started", "t-1", "turn-1")));
+        assert!(activity.observe("ws-2", &turn_event("turn/started", "t-2", "turn-2")));
+        assert!(!activity.observe("ws-1", &json!({ "method": "item/started", "params": {} })));
+        assert_eq!(activity.running().len(), 2);
+
+        assert!(activity.observe("ws-1", &turn_event("turn/completed", "t-1", "turn-1")));
+        assert!(!activity.observe("ws-1", &turn_event("turn/completed", "t-1", "turn-1")));
+        let running = activity.running();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].workspace_id, "ws-2");
+        assert_eq!(running[0].turn_id, "turn-2");
+    }
+
+    #[test]
+    fn parse_tray_action_round_trips_interrupt_ids() {
+        let turn = RunningTurn {
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            turn_id: "turn:1".to_string(),
+            started_at: 0,
+        };
+        match parse_tray_action(&interrupt_item_id(&turn)) {
+            Some(TrayAction::Interrupt {
+                workspace_id,
+                thread_id,
+                turn_id,
+            }) => {
+                assert_eq!(workspace_id, "ws-1");
+                assert_eq!(thread_id, "thread-1");
+                assert_eq!(turn_id, "turn:1");
+            }
+            _ => panic!("expected interrupt action"),
+        }
+        assert!(matches!(
+            parse_tray_action("tray_open:ws-9"),
+            Some(TrayAction::OpenWorkspace(id)) if id == "ws-9"
+        ));
+        assert!(parse_tray_action("file_quit").is_none());
+    }
+
+    #[test]
+    fn summarize_rate_limits_formats_known_windows() {
+        let value = json!({
+            "rateLimits": {
+                "primary": { "usedPercent": 41.6, "resetsAt": null },
+                "secondary": { "usedPercent": 10.0, "resetsAt": null },
+                "sonnet": null,
+            }
+        });
+        assert_eq!(summarize_rate_limits(&value).as_deref(), Some("5h 42% · 7d 10%"));
+        assert!(summarize_rate_limits(&json!({ "rateLimits": null })).is_none());
+    }
+}
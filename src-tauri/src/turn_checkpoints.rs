@@ -0,0 +1,774 @@
+//! With `turnCheckpointsEnabled` set, the working tree is checkpointed when a
+//! turn starts and completes, so the changes made during a single turn can be
+//! diffed afterwards. A checkpoint is a git tree written from an in-memory copy
+//! of the index; the on-disk index is never touched. Each tree is pinned under
+//! `refs/claude-monitor/checkpoints/` so `git gc` keeps it until the checkpoint
+//! is dropped.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use git2::{DiffOptions, IndexAddOption, Oid, Patch, Reference, Repository, Tree};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::mpsc;
+
+use crate::backend::events::AppServerEvent;
+use crate::claude::workspace_entry;
+use crate::git_utils::{diff_patch_to_string, resolve_git_root};
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::GitFileDiff;
+use crate::utils::{normalize_git_path, now_millis};
+
+/// Oldest checkpoints are dropped once a thread has more than this many.
+const MAX_CHECKPOINTS_PER_THREAD: usize = 200;
+const CHECKPOINT_REF_PREFIX: &str = "refs/claude-monitor/checkpoints";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnCheckpoint {
+    pub(crate) workspace_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) start_tree: String,
+    /// Missing while the turn runs, or when the app quit before it ended.
+    #[serde(default)]
+    pub(crate) end_tree: Option<String>,
+    pub(crate) started_at: i64,
+    #[serde(default)]
+    pub(crate) completed_at: Option<i64>,
+}
+
+/// thread_id -> checkpoints, oldest first
+pub(crate) type TurnCheckpoints = HashMap<String, Vec<TurnCheckpoint>>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnDiff {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// False while the turn runs; the diff then ends at the current tree.
+    pub(crate) complete: bool,
+    pub(crate) files: Vec<GitFileDiff>,
+}
+
+/// The turn that last changed a line, as found by `which_turn_changed`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LineTurn {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// The line's number right after the turn made it.
+    pub(crate) line: usize,
+    pub(crate) started_at: i64,
+    pub(crate) completed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    Start,
+    End,
+}
+
+#[derive(Debug)]
+struct CheckpointJob {
+    workspace_id: String,
+    thread_id: String,
+    turn_id: String,
+    mark: Mark,
+}
+
+/// Jobs run one at a time, in event order, so a turn's end checkpoint is
+/// never written before its start.
+static CHECKPOINT_QUEUE: OnceLock<mpsc::UnboundedSender<CheckpointJob>> = OnceLock::new();
+static CHECKPOINTS_ENABLED: AtomicBool = AtomicBool::new(false);
+/// Serializes read-modify-write cycles on turn_checkpoints.json between the
+/// writer task and retention.
+static CHECKPOINTS_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn set_turn_checkpoints_enabled(enabled: bool) {
+    CHECKPOINTS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn turn_checkpoints_path(settings_path: &Path) -> Result<PathBuf, String> {
+    settings_path
+        .parent()
+        .map(|path| path.join("turn_checkpoints.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+fn read_turn_checkpoints(path: &Path) -> Result<TurnCheckpoints, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+fn write_turn_checkpoints(path: &Path, data: &TurnCheckpoints) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+/// Write the working tree, including untracked files that are not ignored,
+/// as a tree object and return its id.
+fn snapshot_tree(root: &Path) -> Result<String, String> {
+    let repo = Repository::open(root).map_err(|err| err.to_string())?;
+    let mut index = repo.index().map_err(|err| err.to_string())?;
+    index
+        .add_all(["*"], IndexAddOption::DEFAULT, None)
+        .map_err(|err| err.to_string())?;
+    index
+        .update_all(["*"], None)
+        .map_err(|err| err.to_string())?;
+    let tree = index.write_tree().map_err(|err| err.to_string())?;
+    Ok(tree.to_string())
+}
+
+fn checkpoint_ref_name(turn_id: &str, mark: Mark) -> Result<String, String> {
+    let suffix = match mark {
+        Mark::Start => "start",
+        Mark::End => "end",
+    };
+    let name = format!("{CHECKPOINT_REF_PREFIX}/{turn_id}/{suffix}");
+    if turn_id.contains('/') || !Reference::is_valid_name(&name) {
+        return Err(format!("Turn id {turn_id} can't be used in a ref name."));
+    }
+    Ok(name)
+}
+
+/// Point the turn's checkpoint ref at `tree` so it stays reachable.
+fn pin_checkpoint_tree(root: &Path, turn_id: &str, mark: Mark, tree: &str) -> Result<(), String> {
+    let repo = Repository::open(root).map_err(|err| err.to_string())?;
+    let oid = Oid::from_str(tree).map_err(|err| err.to_string())?;
+    repo.reference(
+        &checkpoint_ref_name(turn_id, mark)?,
+        oid,
+        true,
+        "claude-monitor: turn checkpoint",
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+fn unpin_checkpoint_trees(root: &Path, turn_id: &str) -> Result<(), String> {
+    let repo = Repository::open(root).map_err(|err| err.to_string())?;
+    for mark in [Mark::Start, Mark::End] {
+        if let Ok(mut reference) = repo.find_reference(&checkpoint_ref_name(turn_id, mark)?) {
+            reference.delete().map_err(|err| err.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Record `tree` for the job's turn and return the checkpoints that fell off
+/// the end of the thread's history.
+fn apply_checkpoint(
+    checkpoints: &mut TurnCheckpoints,
+    job: &CheckpointJob,
+    tree: String,
+    now: i64,
+) -> Vec<TurnCheckpoint> {
+    let entries = checkpoints.entry(job.thread_id.clone()).or_default();
+    match job.mark {
+        Mark::Start => {
+            entries.retain(|entry| entry.turn_id != job.turn_id);
+            entries.push(TurnCheckpoint {
+                workspace_id: job.workspace_id.clone(),
+                turn_id: job.turn_id.clone(),
+                start_tree: tree,
+                end_tree: None,
+                started_at: now,
+                completed_at: None,
+            });
+            if entries.len() > MAX_CHECKPOINTS_PER_THREAD {
+                let excess = entries.len() - MAX_CHECKPOINTS_PER_THREAD;
+                return entries.drain(..excess).collect();
+            }
+        }
+        Mark::End => {
+            if let Some(entry) = entries
+                .iter_mut()
+                .rev()
+                .find(|entry| entry.turn_id == job.turn_id)
+            {
+                entry.end_tree = Some(tree);
+                entry.completed_at = Some(now);
+            }
+        }
+    }
+    Vec::new()
+}
+
+async fn record_checkpoint(state: &AppState, job: CheckpointJob) -> Result<(), String> {
+    let entry = workspace_entry(state, &job.workspace_id).await?;
+    let root = resolve_git_root(&entry)?;
+    let turn_id = job.turn_id.clone();
+    let mark = job.mark;
+    let tree = tokio::task::spawn_blocking(move || {
+        let tree = snapshot_tree(&root)?;
+        pin_checkpoint_tree(&root, &turn_id, mark, &tree)?;
+        Ok::<_, String>(tree)
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+    let path = turn_checkpoints_path(&state.settings_path)?;
+    let dropped = store_checkpoint(&path, &job, tree, now_millis())?;
+    release_checkpoints(state, dropped).await;
+    Ok(())
+}
+
+/// Record `job` in the store at `path`, returning checkpoints past the cap.
+fn store_checkpoint(
+    path: &Path,
+    job: &CheckpointJob,
+    tree: String,
+    now: i64,
+) -> Result<Vec<TurnCheckpoint>, String> {
+    let _guard = CHECKPOINTS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut checkpoints = read_turn_checkpoints(path)?;
+    let dropped = apply_checkpoint(&mut checkpoints, job, tree, now);
+    write_turn_checkpoints(path, &checkpoints)?;
+    Ok(dropped)
+}
+
+/// Remove the checkpoints `remove` matches from the store at `path` and
+/// return them, so their refs can be released.
+pub(crate) fn remove_turn_checkpoints(
+    path: &Path,
+    remove: impl Fn(&TurnCheckpoint) -> bool,
+) -> Result<Vec<TurnCheckpoint>, String> {
+    let _guard = CHECKPOINTS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut checkpoints = read_turn_checkpoints(path)?;
+    let mut removed = Vec::new();
+    for entries in checkpoints.values_mut() {
+        let (dropped, kept) = std::mem::take(entries).into_iter().partition(&remove);
+        *entries = kept;
+        removed.extend(dropped);
+    }
+    if !removed.is_empty() {
+        checkpoints.retain(|_, entries| !entries.is_empty());
+        write_turn_checkpoints(path, &checkpoints)?;
+    }
+    Ok(removed)
+}
+
+/// Drop the refs of checkpoints that are no longer kept, so git can collect
+/// their trees.
+pub(crate) async fn release_checkpoints(state: &AppState, checkpoints: Vec<TurnCheckpoint>) {
+    for checkpoint in checkpoints {
+        if let Err(error) = release_checkpoint(state, checkpoint).await {
+            tracing::debug!("[turn_checkpoints] Failed to unpin checkpoint: {error}");
+        }
+    }
+}
+
+async fn release_checkpoint(state: &AppState, checkpoint: TurnCheckpoint) -> Result<(), String> {
+    let entry = workspace_entry(state, &checkpoint.workspace_id).await?;
+    let root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || unpin_checkpoint_trees(&root, &checkpoint.turn_id))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+pub(crate) fn spawn_checkpoint_writer(app: AppHandle) {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<CheckpointJob>();
+    if CHECKPOINT_QUEUE.set(sender).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        while let Some(job) = receiver.recv().await {
+            let state = app.state::<AppState>();
+            if let Err(error) = record_checkpoint(&state, job).await {
+                tracing::debug!("[turn_checkpoints] Skipped checkpoint: {error}");
+            }
+        }
+    });
+}
+
+/// Queue a checkpoint for `turn/started` and `turn/completed` events. The
+/// snapshot itself runs on the writer task, off the event path.
+pub(crate) fn observe_app_server_event(event: &AppServerEvent) {
+    if !CHECKPOINTS_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Some(queue) = CHECKPOINT_QUEUE.get() else {
+        return;
+    };
+    let mark = match event.message.get("method").and_then(Value::as_str) {
+        Some("turn/started") => Mark::Start,
+        Some("turn/completed") => Mark::End,
+        _ => return,
+    };
+    let params = event.message.get("params");
+    let turn = params.and_then(|params| params.get("turn"));
+    let thread_id = params
+        .and_then(|params| params.get("threadId"))
+        .or_else(|| turn.and_then(|turn| turn.get("threadId")))
+        .and_then(Value::as_str);
+    let turn_id = turn.and_then(|turn| turn.get("id")).and_then(Value::as_str);
+    let (Some(thread_id), Some(turn_id)) = (thread_id, turn_id) else {
+        return;
+    };
+    let _ = queue.send(CheckpointJob {
+        workspace_id: event.workspace_id.clone(),
+        thread_id: thread_id.to_string(),
+        turn_id: turn_id.to_string(),
+        mark,
+    });
+}
+
+fn find_checkpoint_tree<'repo>(repo: &'repo Repository, id: &str) -> Result<Tree<'repo>, String> {
+    Oid::from_str(id)
+        .and_then(|oid| repo.find_tree(oid))
+        .map_err(|_| "The turn's checkpoint is no longer in the repository.".to_string())
+}
+
+/// Per-file diffs from the `start` tree to the `end` tree, or to the current
+/// working tree when `end` is not known yet.
+fn diff_checkpoints(
+    root: &Path,
+    start: &str,
+    end: Option<&str>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let end = match end {
+        Some(end) => end.to_string(),
+        None => snapshot_tree(root)?,
+    };
+    let repo = Repository::open(root).map_err(|err| err.to_string())?;
+    let old_tree = find_checkpoint_tree(&repo, start)?;
+    let new_tree = find_checkpoint_tree(&repo, &end)?;
+    let mut options = DiffOptions::new();
+    let diff = repo
+        .diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut options))
+        .map_err(|err| err.to_string())?;
+
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+            continue;
+        };
+        let path = normalize_git_path(&path.to_string_lossy());
+        let Ok(Some(mut patch)) = Patch::from_diff(&diff, index) else {
+            continue;
+        };
+        let is_binary = delta.flags().is_binary() || patch.delta().flags().is_binary();
+        let content = if is_binary {
+            String::new()
+        } else {
+            diff_patch_to_string(&mut patch).map_err(|err| err.to_string())?
+        };
+        files.push(GitFileDiff {
+            path,
+            diff: content,
+            is_binary,
+            is_image: false,
+            old_image_data: None,
+            new_image_data: None,
+            old_image_mime: None,
+            new_image_mime: None,
+        });
+    }
+    Ok(files)
+}
+
+/// What a single turn changed in the workspace, from the checkpoints taken
+/// when it started and completed.
+#[tauri::command]
+pub(crate) async fn get_turn_diff(
+    workspace_id: String,
+    thread_id: String,
+    turn_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TurnDiff, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_turn_diff",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "turnId": turn_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let path = turn_checkpoints_path(&state.settings_path)?;
+    let checkpoint = read_turn_checkpoints(&path)?
+        .remove(&thread_id)
+        .and_then(|entries| {
+            entries
+                .into_iter()
+                .rev()
+                .find(|entry| entry.turn_id == turn_id && entry.workspace_id == workspace_id)
+        })
+        .ok_or_else(|| format!("No checkpoint recorded for turn {turn_id}"))?;
+    let root = resolve_git_root(&entry)?;
+    let complete = checkpoint.end_tree.is_some();
+    let files = tokio::task::spawn_blocking(move || {
+        diff_checkpoints(
+            &root,
+            &checkpoint.start_tree,
+            checkpoint.end_tree.as_deref(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    Ok(TurnDiff {
+        workspace_id,
+        thread_id,
+        turn_id,
+        complete,
+        files,
+    })
+}
+
+/// Where 1-based `line` of `new` was in `old`, or `None` when the diff added it.
+fn map_line_back(old: &str, new: &str, line: usize) -> Result<Option<usize>, String> {
+    let mut options = DiffOptions::new();
+    options.context_lines(0);
+    let patch = Patch::from_buffers(
+        old.as_bytes(),
+        None,
+        new.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .map_err(|err| err.to_string())?;
+    let mut shift: isize = 0;
+    for index in 0..patch.num_hunks() {
+        let (hunk, _) = patch.hunk(index).map_err(|err| err.to_string())?;
+        let new_start = hunk.new_start() as usize;
+        let new_lines = hunk.new_lines() as usize;
+        // A pure deletion names the line it follows.
+        let before_line = if new_lines == 0 {
+            new_start < line
+        } else if line >= new_start + new_lines {
+            true
+        } else if line >= new_start {
+            return Ok(None);
+        } else {
+            false
+        };
+        if !before_line {
+            break;
+        }
+        shift += hunk.old_lines() as isize - new_lines as isize;
+    }
+    Ok(Some((line as isize + shift) as usize))
+}
+
+fn blob_text(repo: &Repository, tree: &Tree, path: &Path) -> String {
+    tree.get_path(path)
+        .ok()
+        .and_then(|entry| repo.find_blob(entry.id()).ok())
+        .map(|blob| String::from_utf8_lossy(blob.content()).to_string())
+        .unwrap_or_default()
+}
+
+/// Follow `line` of `current` back through `checkpoints`, newest first, to
+/// the turn whose own changes added it. Stops when the line was introduced
+/// between recorded turns. Checkpoints whose trees are gone from the
+/// repository are skipped.
+fn find_line_turn(
+    root: &Path,
+    path: &Path,
+    current: String,
+    line: usize,
+    checkpoints: &[(String, TurnCheckpoint)],
+) -> Result<Option<LineTurn>, String> {
+    let repo = Repository::open(root).map_err(|err| err.to_string())?;
+    let mut text = current;
+    let mut line = line;
+    for (thread_id, checkpoint) in checkpoints {
+        let Some(end_tree) = checkpoint.end_tree.as_deref() else {
+            continue;
+        };
+        let (Ok(end_tree), Ok(start_tree)) = (
+            find_checkpoint_tree(&repo, end_tree),
+            find_checkpoint_tree(&repo, &checkpoint.start_tree),
+        ) else {
+            continue;
+        };
+        let end_text = blob_text(&repo, &end_tree, path);
+        let Some(end_line) = map_line_back(&end_text, &text, line)? else {
+            return Ok(None);
+        };
+        let start_text = blob_text(&repo, &start_tree, path);
+        match map_line_back(&start_text, &end_text, end_line)? {
+            None => {
+                return Ok(Some(LineTurn {
+                    thread_id: thread_id.clone(),
+                    turn_id: checkpoint.turn_id.clone(),
+                    line: end_line,
+                    started_at: checkpoint.started_at,
+                    completed_at: checkpoint.completed_at,
+                }))
+            }
+            Some(start_line) => {
+                text = start_text;
+                line = start_line;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The recorded turn, across all of the workspace's threads, that last
+/// changed 1-based `line` of `path`. `None` when no recorded turn did.
+#[tauri::command]
+pub(crate) async fn which_turn_changed(
+    workspace_id: String,
+    path: String,
+    line: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<LineTurn>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "which_turn_changed",
+            json!({ "workspaceId": workspace_id, "path": path, "line": line }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    if line == 0 {
+        return Err("Line numbers start at 1.".to_string());
+    }
+    let relative = Path::new(path.trim());
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!("Invalid path: {path}"));
+    }
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let absolute = PathBuf::from(&entry.path).join(relative);
+    let root = resolve_git_root(&entry)?;
+    let repo_path = absolute
+        .strip_prefix(&root)
+        .map_err(|_| format!("{path} is outside the repository"))?
+        .to_path_buf();
+
+    let checkpoints_path = turn_checkpoints_path(&state.settings_path)?;
+    let mut checkpoints: Vec<(String, TurnCheckpoint)> = read_turn_checkpoints(&checkpoints_path)?
+        .into_iter()
+        .flat_map(|(thread_id, entries)| {
+            entries
+                .into_iter()
+                .map(move |entry| (thread_id.clone(), entry))
+        })
+        .filter(|(_, entry)| entry.workspace_id == workspace_id && entry.end_tree.is_some())
+        .collect();
+    checkpoints.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.completed_at));
+
+    tokio::task::spawn_blocking(move || {
+        let current = std::fs::read_to_string(&absolute)
+            .map_err(|err| format!("Failed to read {}: {err}", absolute.display()))?;
+        find_line_turn(&root, &repo_path, current, line, &checkpoints)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(turn_id: &str, mark: Mark) -> CheckpointJob {
+        CheckpointJob {
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            turn_id: turn_id.to_string(),
+            mark,
+        }
+    }
+
+    #[test]
+    fn apply_checkpoint_pairs_start_and_end() {
+        let mut checkpoints = TurnCheckpoints::new();
+        apply_checkpoint(&mut checkpoints, &job("turn-1", Mark::Start), "a".into(), 1);
+        apply_checkpoint(&mut checkpoints, &job("turn-1", Mark::End), "b".into(), 2);
+        apply_checkpoint(&mut checkpoints, &job("turn-2", Mark::End), "c".into(), 3);
+        let entries = &checkpoints["thread-1"];
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].start_tree, "a");
+        assert_eq!(entries[0].end_tree.as_deref(), Some("b"));
+        assert_eq!(entries[0].completed_at, Some(2));
+    }
+
+    #[test]
+    fn apply_checkpoint_returns_checkpoints_past_the_cap() {
+        let mut checkpoints = TurnCheckpoints::new();
+        for index in 0..=MAX_CHECKPOINTS_PER_THREAD {
+            let turn_id = format!("turn-{index}");
+            let dropped =
+                apply_checkpoint(&mut checkpoints, &job(&turn_id, Mark::Start), "a".into(), 1);
+            if index < MAX_CHECKPOINTS_PER_THREAD {
+                assert!(dropped.is_empty());
+            } else {
+                assert_eq!(dropped.len(), 1);
+                assert_eq!(dropped[0].turn_id, "turn-0");
+            }
+        }
+    }
+
+    #[test]
+    fn stored_checkpoints_can_be_removed() {
+        let dir = std::env::temp_dir().join(format!("turn-checkpoints-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("turn_checkpoints.json");
+        store_checkpoint(&path, &job("turn-1", Mark::Start), "a".into(), 1).unwrap();
+        store_checkpoint(&path, &job("turn-2", Mark::Start), "b".into(), 2).unwrap();
+
+        let removed = remove_turn_checkpoints(&path, |entry| entry.turn_id == "turn-1").unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].start_tree, "a");
+        let remaining = read_turn_checkpoints(&path).unwrap();
+        assert_eq!(remaining["thread-1"].len(), 1);
+        assert_eq!(remaining["thread-1"][0].turn_id, "turn-2");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn checkpoint_trees_are_pinned_until_released() {
+        let root = std::env::temp_dir().join(format!("turn-refs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        Repository::init(&root).unwrap();
+        std::fs::write(root.join("file.txt"), "text\n").unwrap();
+        let tree = snapshot_tree(&root).unwrap();
+        pin_checkpoint_tree(&root, "turn-1", Mark::Start, &tree).unwrap();
+
+        let repo = Repository::open(&root).unwrap();
+        let name = "refs/claude-monitor/checkpoints/turn-1/start";
+        let target = repo.find_reference(name).unwrap().target().unwrap();
+        assert_eq!(target.to_string(), tree);
+        assert!(pin_checkpoint_tree(&root, "../HEAD", Mark::End, &tree).is_err());
+
+        unpin_checkpoint_trees(&root, "turn-1").unwrap();
+        assert!(repo.find_reference(name).is_err());
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn diff_checkpoints_covers_changes_between_snapshots() {
+        let root = std::env::temp_dir().join(format!("turn-checkpoints-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        Repository::init(&root).unwrap();
+        std::fs::write(root.join("kept.txt"), "same\n").unwrap();
+        std::fs::write(root.join("edited.txt"), "before\n").unwrap();
+        let start = snapshot_tree(&root).unwrap();
+        std::fs::write(root.join("edited.txt"), "after\n").unwrap();
+        std::fs::write(root.join("added.txt"), "new\n").unwrap();
+        let end = snapshot_tree(&root).unwrap();
+
+        let files = diff_checkpoints(&root, &start, Some(&end)).unwrap();
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["added.txt", "edited.txt"]);
+        assert!(files[1].diff.contains("-before\n+after"));
+        // The user's index is left alone.
+        assert!(Repository::open(&root).unwrap().index().unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn map_line_back_follows_shifts_and_detects_additions() {
+        let old = "a\nb\nc\nd\n";
+        let new = "new\na\nc\nd\nadded\n";
+        assert_eq!(map_line_back(old, new, 1).unwrap(), None);
+        assert_eq!(map_line_back(old, new, 2).unwrap(), Some(1));
+        assert_eq!(map_line_back(old, new, 3).unwrap(), Some(3));
+        assert_eq!(map_line_back(old, new, 5).unwrap(), None);
+    }
+
+    #[test]
+    fn find_line_turn_skips_lines_from_later_turns_and_user_edits() {
+        let root = std::env::temp_dir().join(format!("line-turn-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        Repository::init(&root).unwrap();
+        let file = root.join("notes.txt");
+        let checkpoint = |turn_id: &str, start: String, end: String, at: i64| {
+            let entry = TurnCheckpoint {
+                workspace_id: "ws-1".to_string(),
+                turn_id: turn_id.to_string(),
+                start_tree: start,
+                end_tree: Some(end),
+                started_at: at,
+                completed_at: Some(at),
+            };
+            ("thread-1".to_string(), entry)
+        };
+        std::fs::write(&file, "a\nb\nc\n").unwrap();
+        let first_start = snapshot_tree(&root).unwrap();
+        std::fs::write(&file, "a\nB\nc\n").unwrap();
+        let first_end = snapshot_tree(&root).unwrap();
+        std::fs::write(&file, "top\na\nB\nc\n").unwrap();
+        let second_start = snapshot_tree(&root).unwrap();
+        std::fs::write(&file, "top\na\nB\nc\nd\n").unwrap();
+        let second_end = snapshot_tree(&root).unwrap();
+        let checkpoints = [
+            checkpoint("turn-2", second_start, second_end, 2),
+            checkpoint("turn-1", first_start, first_end, 1),
+        ];
+        let current = std::fs::read_to_string(&file).unwrap();
+        let path = Path::new("notes.txt");
+
+        let found = |line| find_line_turn(&root, path, current.clone(), line, &checkpoints);
+        let changed_b = found(3).unwrap().unwrap();
+        assert_eq!((changed_b.turn_id.as_str(), changed_b.line), ("turn-1", 2));
+        assert_eq!(found(5).unwrap().unwrap().turn_id, "turn-2");
+        assert_eq!(found(1).unwrap(), None);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn find_line_turn_skips_checkpoints_with_pruned_trees() {
+        let root = std::env::temp_dir().join(format!("line-turn-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        Repository::init(&root).unwrap();
+        let file = root.join("notes.txt");
+        std::fs::write(&file, "a\n").unwrap();
+        let start = snapshot_tree(&root).unwrap();
+        std::fs::write(&file, "a\nb\n").unwrap();
+        let end = snapshot_tree(&root).unwrap();
+        let checkpoint = |turn_id: &str, start: String, end: String, at: i64| {
+            let entry = TurnCheckpoint {
+                workspace_id: "ws-1".to_string(),
+                turn_id: turn_id.to_string(),
+                start_tree: start,
+                end_tree: Some(end),
+                started_at: at,
+                completed_at: Some(at),
+            };
+            ("thread-1".to_string(), entry)
+        };
+        let pruned = "1".repeat(40);
+        let checkpoints = [
+            checkpoint("turn-pruned", pruned.clone(), pruned, 2),
+            checkpoint("turn-1", start, end, 1),
+        ];
+        let current = std::fs::read_to_string(&file).unwrap();
+
+        let found = find_line_turn(&root, Path::new("notes.txt"), current, 2, &checkpoints)
+            .unwrap()
+            .unwrap();
+        assert_eq!((found.turn_id.as_str(), found.line), ("turn-1", 2));
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,364 @@
+//! With `turnRecordingEnabled` set, every app-server event from `turn/started`
+//! through `turn/completed` is appended to
+//! `turn_recordings/<workspace>/<turn>.jsonl` along with its offset from the
+//! start of the turn. `replay_turn` plays a recording back as `turn-replay`
+//! events with the original spacing (scaled by `speed`), so a run can be shown
+//! again without touching live thread state. Remote events pass through the
+//! local event sink, so recordings always live on this machine.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::backend::events::AppServerEvent;
+
+pub(crate) const RECORDINGS_DIR_NAME: &str = "turn_recordings";
+
+static RECORDINGS_DIR: OnceLock<PathBuf> = OnceLock::new();
+static RECORDING_ENABLED: AtomicBool = AtomicBool::new(false);
+static ACTIVE_TURNS: OnceLock<Mutex<HashMap<(String, String), ActiveTurn>>> = OnceLock::new();
+static RUNNING_REPLAYS: OnceLock<Mutex<HashMap<String, oneshot::Sender<()>>>> = OnceLock::new();
+
+fn active_turns() -> &'static Mutex<HashMap<(String, String), ActiveTurn>> {
+    ACTIVE_TURNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn running_replays() -> &'static Mutex<HashMap<String, oneshot::Sender<()>>> {
+    RUNNING_REPLAYS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+struct ActiveTurn {
+    started: Instant,
+    file: File,
+}
+
+/// One line of a recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedEvent {
+    pub(crate) offset_ms: u64,
+    pub(crate) message: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnRecordingInfo {
+    pub(crate) turn_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) recorded_at: i64,
+    pub(crate) event_count: usize,
+    pub(crate) duration_ms: u64,
+}
+
+/// A `turn-replay` event. `message` is absent on the final event, which has
+/// `done` set.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TurnReplayEvent {
+    replay_id: String,
+    workspace_id: String,
+    turn_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Value>,
+    done: bool,
+}
+
+/// Remember where recordings live and whether to record. Called once at startup.
+pub(crate) fn init_turn_recordings(data_dir: &Path, enabled: bool) {
+    let _ = RECORDINGS_DIR.set(data_dir.join(RECORDINGS_DIR_NAME));
+    set_turn_recording_enabled(enabled);
+}
+
+/// Turning recording off stops turns that are being recorded.
+pub(crate) fn set_turn_recording_enabled(enabled: bool) {
+    RECORDING_ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        active_turns()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+}
+
+/// Ids become file names, so anything that could leave the directory is refused.
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn recording_path(dir: &Path, workspace_id: &str, turn_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_id(workspace_id) || !is_safe_id(turn_id) {
+        return Err("Invalid recording id.".to_string());
+    }
+    Ok(dir.join(workspace_id).join(format!("{turn_id}.jsonl")))
+}
+
+fn append_line(file: &mut File, offset_ms: u64, message: &Value) {
+    let event = RecordedEvent {
+        offset_ms,
+        message: message.clone(),
+    };
+    if let Ok(line) = serde_json::to_string(&event) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+fn start_recording(dir: &Path, workspace_id: &str, turn_id: &str) -> Option<ActiveTurn> {
+    let path = recording_path(dir, workspace_id, turn_id).ok()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(&path)
+        .map_err(|err| tracing::warn!("[turn-recordings] {}: {err}", path.display()))
+        .ok()?;
+    Some(ActiveTurn {
+        started: Instant::now(),
+        file,
+    })
+}
+
+/// Append an outgoing event to its thread's recording, starting or finishing
+/// the recording on turn boundaries.
+pub(crate) fn observe_app_server_event(event: &AppServerEvent) {
+    if !RECORDING_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Some(dir) = RECORDINGS_DIR.get() else {
+        return;
+    };
+    let method = event.message.get("method").and_then(Value::as_str);
+    let params = event.message.get("params");
+    let Some(thread_id) = params
+        .and_then(|params| params.get("threadId"))
+        .and_then(Value::as_str)
+    else {
+        return;
+    };
+    let key = (event.workspace_id.clone(), thread_id.to_string());
+    let mut turns = active_turns().lock().unwrap_or_else(|err| err.into_inner());
+    if method == Some("turn/started") {
+        let turn_id = params
+            .and_then(|params| params.get("turn"))
+            .and_then(|turn| turn.get("id"))
+            .and_then(Value::as_str);
+        match turn_id.and_then(|turn_id| start_recording(dir, &event.workspace_id, turn_id)) {
+            Some(turn) => {
+                turns.insert(key.clone(), turn);
+            }
+            None => {
+                turns.remove(&key);
+                return;
+            }
+        }
+    }
+    let Some(turn) = turns.get_mut(&key) else {
+        return;
+    };
+    let offset_ms = turn.started.elapsed().as_millis() as u64;
+    append_line(&mut turn.file, offset_ms, &event.message);
+    if method == Some("turn/completed") {
+        turns.remove(&key);
+    }
+}
+
+fn read_recording(path: &Path) -> Result<Vec<RecordedEvent>, String> {
+    let file = File::open(path).map_err(|_| "Recording not found.".to_string())?;
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+fn list_recordings(dir: &Path, workspace_id: &str) -> Result<Vec<TurnRecordingInfo>, String> {
+    if !is_safe_id(workspace_id) {
+        return Err("Invalid recording id.".to_string());
+    }
+    let entries = match fs::read_dir(dir.join(workspace_id)) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let mut recordings: Vec<TurnRecordingInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+                return None;
+            }
+            let turn_id = path.file_stem()?.to_str()?.to_string();
+            let events = read_recording(&path).ok()?;
+            let recorded_at = entry
+                .metadata()
+                .ok()
+                .and_then(|meta| meta.modified().ok())
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or(0);
+            Some(TurnRecordingInfo {
+                turn_id,
+                thread_id: events
+                    .first()
+                    .and_then(|event| event.message["params"]["threadId"].as_str())
+                    .map(str::to_string),
+                recorded_at,
+                event_count: events.len(),
+                duration_ms: events.last().map_or(0, |event| event.offset_ms),
+            })
+        })
+        .collect();
+    recordings.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
+    Ok(recordings)
+}
+
+/// How long to wait before each event, given the recorded offsets.
+fn replay_delays(events: &[RecordedEvent], speed: f64) -> Vec<Duration> {
+    let mut previous = 0;
+    events
+        .iter()
+        .map(|event| {
+            let gap = Duration::from_millis(event.offset_ms.saturating_sub(previous));
+            previous = event.offset_ms;
+            gap.div_f64(speed)
+        })
+        .collect()
+}
+
+fn recordings_dir() -> Result<&'static PathBuf, String> {
+    RECORDINGS_DIR
+        .get()
+        .ok_or_else(|| "Turn recordings are unavailable.".to_string())
+}
+
+/// Recordings for a workspace, newest first.
+#[tauri::command]
+pub(crate) async fn list_turn_recordings(
+    workspace_id: String,
+) -> Result<Vec<TurnRecordingInfo>, String> {
+    let dir = recordings_dir()?;
+    tokio::task::spawn_blocking(move || list_recordings(dir, &workspace_id))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+/// Start replaying a recorded turn and return the replay id carried by its
+/// `turn-replay` events. `speed` defaults to 1; 2 plays twice as fast.
+#[tauri::command]
+pub(crate) async fn replay_turn(
+    workspace_id: String,
+    turn_id: String,
+    speed: Option<f64>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let speed = speed.unwrap_or(1.0);
+    if !(speed.is_finite() && speed > 0.0) {
+        return Err("Replay speed must be greater than zero.".to_string());
+    }
+    let path = recording_path(recordings_dir()?, &workspace_id, &turn_id)?;
+    let events = tokio::task::spawn_blocking(move || read_recording(&path))
+        .await
+        .map_err(|err| err.to_string())??;
+    let delays = replay_delays(&events, speed);
+
+    let replay_id = Uuid::new_v4().to_string();
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+    running_replays()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(replay_id.clone(), cancel_tx);
+
+    let id = replay_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let replay_event = |message: Option<Value>| TurnReplayEvent {
+            replay_id: id.clone(),
+            workspace_id: workspace_id.clone(),
+            turn_id: turn_id.clone(),
+            done: message.is_none(),
+            message,
+        };
+        for (event, delay) in events.into_iter().zip(delays) {
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = &mut cancel_rx => break,
+            }
+            let _ = app.emit("turn-replay", replay_event(Some(event.message)));
+        }
+        running_replays()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&id);
+        let _ = app.emit("turn-replay", replay_event(None));
+    });
+    Ok(replay_id)
+}
+
+/// Stop a replay started by `replay_turn`.
+#[tauri::command]
+pub(crate) async fn cancel_turn_replay(replay_id: String) -> Result<(), String> {
+    let sender = running_replays()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(&replay_id)
+        .ok_or("Replay not running.")?;
+    let _ = sender.send(());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn recordings_round_trip_and_replay_with_scaled_gaps() {
+        let dir = std::env::temp_dir().join(format!("turn-recordings-{}", Uuid::new_v4()));
+        let path = recording_path(&dir, "ws-1", "turn-1").unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = File::create(&path).unwrap();
+        for (offset_ms, method) in [
+            (0, "turn/started"),
+            (400, "item/agentMessage/delta"),
+            (60_400, "turn/completed"),
+        ] {
+            let message = json!({ "method": method, "params": { "threadId": "thread-1" } });
+            append_line(&mut file, offset_ms, &message);
+        }
+        drop(file);
+
+        let events = read_recording(&path).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].message["method"], "item/agentMessage/delta");
+        assert_eq!(
+            replay_delays(&events, 2.0),
+            vec![
+                Duration::ZERO,
+                Duration::from_millis(200),
+                Duration::from_secs(30)
+            ]
+        );
+
+        let listed = list_recordings(&dir, "ws-1").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].turn_id, "turn-1");
+        assert_eq!(listed[0].thread_id.as_deref(), Some("thread-1"));
+        assert_eq!(listed[0].event_count, 3);
+        assert_eq!(listed[0].duration_ms, 60_400);
+        assert!(list_recordings(&dir, "ws-2").unwrap().is_empty());
+        assert!(recording_path(&dir, "ws-1", "../secrets").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::background_runs::EDIT_TOOLS;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::extract_file_paths;
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+/// Oldest reports are dropped once a thread has more than this many.
+const MAX_REPORTS_PER_THREAD: usize = 200;
+
+/// Serializes read-modify-write cycles on turn_reports.json.
+static TURN_REPORTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Audit summary of a single completed turn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnReport {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// "completed" or "failed"
+    pub(crate) status: String,
+    pub(crate) files_changed: Vec<String>,
+    pub(crate) commands_run: Vec<String>,
+    #[serde(default)]
+    pub(crate) usage: Option<Value>,
+    #[serde(default)]
+    pub(crate) model_usage: Option<Value>,
+    #[serde(default)]
+    pub(crate) cost_usd: Option<f64>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<u64>,
+    pub(crate) created_at: i64,
+}
+
+/// thread_id -> reports, oldest first
+pub(crate) type TurnReports = HashMap<String, Vec<TurnReport>>;
+
+pub(crate) fn turn_reports_path(settings_path: &Path) -> Result<PathBuf, String> {
+    settings_path
+        .parent()
+        .map(|path| path.join("turn_reports.json"))
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+pub(crate) fn read_turn_reports(path: &Path) -> Result<TurnReports, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&contents).map_err(|err| err.to_string())
+}
+
+fn write_turn_reports(path: &Path, data: &TurnReports) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(data).map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn store_turn_report(path: &Path, report: &TurnReport) -> Result<(), String> {
+    let _guard = TURN_REPORTS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut reports = read_turn_reports(path)?;
+    let entries = reports.entry(report.thread_id.clone()).or_default();
+    entries.retain(|entry| entry.turn_id != report.turn_id);
+    entries.push(report.clone());
+    if entries.len() > MAX_REPORTS_PER_THREAD {
+        let excess = entries.len() - MAX_REPORTS_PER_THREAD;
+        entries.drain(..excess);
+    }
+    write_turn_reports(path, &reports)
+}
+
+/// Drop every report matching `remove`. Returns how many were dropped.
+pub(crate) fn remove_turn_reports(
+    path: &Path,
+    remove: impl Fn(&TurnReport) -> bool,
+) -> Result<usize, String> {
+    let _guard = TURN_REPORTS_LOCK
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut reports = read_turn_reports(path)?;
+    let mut removed = 0;
+    for entries in reports.values_mut() {
+        let before = entries.len();
+        entries.retain(|entry| !remove(entry));
+        removed += before - entries.len();
+    }
+    if removed > 0 {
+        reports.retain(|_, entries| !entries.is_empty());
+        write_turn_reports(path, &reports)?;
+    }
+    Ok(removed)
+}
+
+/// Build a report from the turn's tool calls (in call order) and its CLI result event.
+pub(crate) fn build_turn_report(
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    status: &str,
+    tool_calls: &[(String, Value)],
+    result: &Value,
+) -> TurnReport {
+    let mut files_changed: Vec<String> = Vec::new();
+    let mut seen_files: HashSet<String> = HashSet::new();
+    let mut commands_run: Vec<String> = Vec::new();
+    for (name, input) in tool_calls {
+        if EDIT_TOOLS.contains(&name.as_str()) {
+            for path in extract_file_paths(input) {
+                if seen_files.insert(path.clone()) {
+                    files_changed.push(path);
+                }
+            }
+        } else if name == "Bash" {
+            let command = input
+                .get("command")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+            if !command.is_empty() {
+                commands_run.push(command.to_string());
+            }
+        }
+    }
+    TurnReport {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        turn_id: turn_id.to_string(),
+        status: status.to_string(),
+        files_changed,
+        commands_run,
+        usage: result.get("usage").cloned(),
+        model_usage: result.get("modelUsage").cloned(),
+        cost_usd: result
+            .get("total_cost_usd")
+            .or_else(|| result.get("totalCostUsd"))
+            .and_then(|v| v.as_f64()),
+        duration_ms: result
+            .get("duration_ms")
+            .or_else(|| result.get("durationMs"))
+            .and_then(|v| v.as_u64()),
+        created_at: now_millis(),
+    }
+}
+
+/// Persist and announce a turn report when turn reports are enabled.
+pub(crate) fn record_turn_report(event_sink: &TauriEventSink, report: TurnReport) {
+    let event_sink = event_sink.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = event_sink.app_handle().state::<AppState>();
+        if !state.app_settings.lock().await.turn_reports_enabled {
+            return;
+        }
+        let stored = turn_reports_path(&state.settings_path)
+            .and_then(|path| store_turn_report(&path, &report));
+        if let Err(err) = stored {
+            tracing::warn!("Failed to store turn report: {err}");
+            return;
+        }
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: report.workspace_id.clone(),
+            message: json!({
+                "method": "turn/report",
+                "params": {
+                    "threadId": report.thread_id,
+                    "turnId": report.turn_id,
+                    "report": report,
+                }
+            }),
+        });
+    });
+}
+
+/// Fetch the report for a turn, or the thread's latest report when no turn is given.
+#[tauri::command]
+pub(crate) async fn get_turn_report(
+    workspace_id: String,
+    thread_id: String,
+    turn_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<TurnReport>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_turn_report",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "turnId": turn_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let path = turn_reports_path(&state.settings_path)?;
+    let reports = read_turn_reports(&path)?
+        .remove(&thread_id)
+        .unwrap_or_default();
+    Ok(reports
+        .into_iter()
+        .rev()
+        .filter(|report| report.workspace_id == workspace_id)
+        .find(|report| turn_id.is_none() || turn_id.as_deref() == Some(report.turn_id.as_str())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report(turn_id: &str) -> TurnReport {
+        build_turn_report(
+            "ws-1",
+            "thread-1",
+            turn_id,
+            "completed",
+            &[],
+            &Value::Null,
+        )
+    }
+
+    #[test]
+    fn build_turn_report_collects_files_commands_and_cost() {
+        let tool_calls = vec![
+            ("Edit".to_string(), json!({ "file_path": "src/main.rs" })),
+            ("Bash".to_string(), json!({ "command": " cargo test " })),
+            ("Write".to_string(), json!({ "file_path": "src/main.rs" })),
+            ("Read".to_string(), json!({ "file_path": "README.md" })),
+            ("Bash".to_string(), json!({ "command": "  " })),
+        ];
+        let report = build_turn_report(
+            "ws-1",
+            "thread-1",
+            "turn-1",
+            "completed",
+            &tool_calls,
+            &json!({
+                "total_cost_usd": 0.0125,
+                "duration_ms": 5400,
+                "usage": { "input_tokens": 10, "output_tokens": 4 },
+            }),
+        );
+        assert_eq!(report.files_changed, vec!["src/main.rs"]);
+        assert_eq!(report.commands_run, vec!["cargo test"]);
+        assert_eq!(report.cost_usd, Some(0.0125));
+        assert_eq!(report.duration_ms, Some(5400));
+        assert!(report.usage.is_some());
+    }
+
+    #[test]
+    fn store_turn_report_replaces_same_turn_and_caps_history() {
+        let dir = std::env::temp_dir().join(format!("turn-reports-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("turn_reports.json");
+        store_turn_report(&path, &sample_report("turn-1")).unwrap();
+        store_turn_report(&path, &sample_report("turn-1")).unwrap();
+        assert_eq!(read_turn_reports(&path).unwrap()["thread-1"].len(), 1);
+
+        for index in 0..MAX_REPORTS_PER_THREAD + 5 {
+            store_turn_report(&path, &sample_report(&format!("turn-{index}"))).unwrap();
+        }
+        let reports = read_turn_reports(&path).unwrap();
+        let thread = &reports["thread-1"];
+        assert_eq!(thread.len(), MAX_REPORTS_PER_THREAD);
+        assert_eq!(
+            thread.last().map(|report| report.turn_id.as_str()),
+            Some(format!("turn-{}", MAX_REPORTS_PER_THREAD + 4).as_str())
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn remove_turn_reports_drops_matching_reports_and_empty_threads() {
+        let dir = std::env::temp_dir().join(format!("turn-reports-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("turn_reports.json");
+        store_turn_report(&path, &sample_report("turn-1")).unwrap();
+        store_turn_report(&path, &sample_report("turn-2")).unwrap();
+
+        let removed = remove_turn_reports(&path, |report| report.turn_id == "turn-1").unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(read_turn_reports(&path).unwrap()["thread-1"].len(), 1);
+        assert_eq!(remove_turn_reports(&path, |_| false).unwrap(), 0);
+        assert_eq!(remove_turn_reports(&path, |_| true).unwrap(), 1);
+        assert!(read_turn_reports(&path).unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
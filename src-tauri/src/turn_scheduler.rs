@@ -0,0 +1,618 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::State;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::{oneshot, Mutex, MutexGuard};
+
+use crate::backend::claude_cli::TurnCompletion;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude::{ensure_persistent_session, WorkspaceSession};
+use crate::event_sink::TauriEventSink;
+use crate::redaction::redact_outgoing_prompt;
+use crate::state::AppState;
+use crate::utils::now_millis;
+
+/// A turn waiting for a free slot under `maxParallelTurns`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueuedTurn {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// 1-based position in the global queue
+    pub(crate) position: usize,
+    pub(crate) queued_at: i64,
+}
+
+struct Waiter {
+    workspace_id: String,
+    thread_id: String,
+    turn_id: String,
+    queued_at: i64,
+    ready: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct SchedulerQueue {
+    /// `None` means unlimited.
+    limit: Option<usize>,
+    active: HashSet<String>,
+    waiting: VecDeque<Waiter>,
+}
+
+impl SchedulerQueue {
+    fn has_capacity(&self) -> bool {
+        match self.limit {
+            Some(limit) => self.active.len() < limit,
+            None => true,
+        }
+    }
+
+    /// Move waiters into free slots, oldest first.
+    fn promote(&mut self) -> Vec<Waiter> {
+        let mut promoted = Vec::new();
+        while self.has_capacity() {
+            let Some(waiter) = self.waiting.pop_front() else {
+                break;
+            };
+            if waiter.ready.is_closed() {
+                continue;
+            }
+            self.active.insert(waiter.turn_id.clone());
+            promoted.push(waiter);
+        }
+        promoted
+    }
+
+    fn snapshot(&self) -> Vec<QueuedTurn> {
+        self.waiting
+            .iter()
+            .enumerate()
+            .map(|(index, waiter)| QueuedTurn {
+                workspace_id: waiter.workspace_id.clone(),
+                thread_id: waiter.thread_id.clone(),
+                turn_id: waiter.turn_id.clone(),
+                position: index + 1,
+                queued_at: waiter.queued_at,
+            })
+            .collect()
+    }
+}
+
+fn normalize_limit(limit: Option<u32>) -> Option<usize> {
+    limit.filter(|limit| *limit > 0).map(|limit| limit as usize)
+}
+
+fn emit_queued(event_sink: &impl EventSink, turn: &QueuedTurn) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: turn.workspace_id.clone(),
+        message: json!({
+            "method": "turn/queued",
+            "params": turn,
+        }),
+    });
+}
+
+fn emit_dequeued(event_sink: &impl EventSink, waiter: &Waiter, reason: &str) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: waiter.workspace_id.clone(),
+        message: json!({
+            "method": "turn/dequeued",
+            "params": {
+                "threadId": waiter.thread_id,
+                "turnId": waiter.turn_id,
+                "reason": reason,
+            },
+        }),
+    });
+}
+
+fn promote_and_notify(mut queue: MutexGuard<'_, SchedulerQueue>, event_sink: &impl EventSink) {
+    let promoted = queue.promote();
+    if promoted.is_empty() {
+        return;
+    }
+    let remaining = queue.snapshot();
+    drop(queue);
+    for waiter in promoted {
+        emit_dequeued(event_sink, &waiter, "started");
+        let _ = waiter.ready.send(());
+    }
+    for turn in &remaining {
+        emit_queued(event_sink, turn);
+    }
+}
+
+/// Global limiter for turns running across every workspace.
+pub(crate) struct TurnScheduler {
+    queue: Mutex<SchedulerQueue>,
+}
+
+impl TurnScheduler {
+    pub(crate) fn new(limit: Option<u32>) -> Self {
+        Self {
+            queue: Mutex::new(SchedulerQueue {
+                limit: normalize_limit(limit),
+                ..SchedulerQueue::default()
+            }),
+        }
+    }
+
+    /// Take a slot now, or join the queue and get a receiver that fires once promoted.
+    async fn acquire(
+        &self,
+        workspace_id: &str,
+        thread_id: &str,
+        turn_id: &str,
+    ) -> Option<(oneshot::Receiver<()>, QueuedTurn)> {
+        let mut queue = self.queue.lock().await;
+        if queue.waiting.is_empty() && queue.has_capacity() {
+            queue.active.insert(turn_id.to_string());
+            return None;
+        }
+        let (ready, receiver) = oneshot::channel();
+        let queued_at = now_millis();
+        queue.waiting.push_back(Waiter {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            queued_at,
+            ready,
+        });
+        let turn = QueuedTurn {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            position: queue.waiting.len(),
+            queued_at,
+        };
+        Some((receiver, turn))
+    }
+
+    pub(crate) async fn release(&self, turn_id: &str, event_sink: &impl EventSink) {
+        let mut queue = self.queue.lock().await;
+        queue.active.remove(turn_id);
+        promote_and_notify(queue, event_sink);
+    }
+
+    /// Apply a new `maxParallelTurns` value, starting queued turns if it was raised.
+    pub(crate) async fn set_limit(&self, limit: Option<u32>, event_sink: &TauriEventSink) {
+        let mut queue = self.queue.lock().await;
+        queue.limit = normalize_limit(limit);
+        promote_and_notify(queue, event_sink);
+    }
+
+    /// Drop a turn that has not started yet. Returns false if it was not queued.
+    pub(crate) async fn cancel(&self, turn_id: &str, event_sink: &impl EventSink) -> bool {
+        let mut queue = self.queue.lock().await;
+        let Some(index) = queue
+            .waiting
+            .iter()
+            .position(|waiter| waiter.turn_id == turn_id)
+        else {
+            return false;
+        };
+        let waiter = queue.waiting.remove(index).expect("waiter index in range");
+        let remaining = queue.snapshot();
+        drop(queue);
+        emit_dequeued(event_sink, &waiter, "cancelled");
+        for turn in remaining.iter().skip(index) {
+            emit_queued(event_sink, turn);
+        }
+        true
+    }
+
+    pub(crate) async fn queued(&self) -> Vec<QueuedTurn> {
+        self.queue.lock().await.snapshot()
+    }
+}
+
+/// Free the turn's slot once the reader reports it finished. The reader reports
+/// every turn it was handed, including ones that end before any assistant output.
+fn release_on_completion(
+    scheduler: Arc<TurnScheduler>,
+    event_sink: impl EventSink,
+    turn_id: String,
+    mut turn_events: broadcast::Receiver<TurnCompletion>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match turn_events.recv().await {
+                Ok(completion) if completion.turn_id == turn_id => break,
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+        scheduler.release(&turn_id, &event_sink).await;
+    });
+}
+
+/// Wait for a queued turn to be promoted. A turn cancelled before it starts is
+/// settled as "interrupted" so anything tracking it stops waiting.
+async fn wait_for_slot(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    turn_id: &str,
+    ready: oneshot::Receiver<()>,
+) -> bool {
+    if ready.await.is_ok() {
+        return true;
+    }
+    session.notify_turn_completed(thread_id, turn_id, "interrupted", None);
+    false
+}
+
+async fn start_turn(
+    scheduler: &Arc<TurnScheduler>,
+    event_sink: &TauriEventSink,
+    session: &WorkspaceSession,
+    thread_id: &str,
+    turn_id: &str,
+    content: &Value,
+) -> Result<(), String> {
+    let turn_events = session.subscribe_turn_events();
+    session.set_pending_turn_id(thread_id, turn_id.to_string()).await;
+    if let Err(error) = session.send_message_content(thread_id, content.clone()).await {
+        scheduler.release(turn_id, event_sink).await;
+        return Err(error);
+    }
+    release_on_completion(
+        Arc::clone(scheduler),
+        event_sink.clone(),
+        turn_id.to_string(),
+        turn_events,
+    );
+    Ok(())
+}
+
+/// Send a turn's prompt now if a slot is free, otherwise queue it behind the
+/// global limit and send it once promoted. Returns the queue position if it waits.
+///
+/// The prompt is masked with the workspace's redaction rules first, so every
+/// app-built prompt (background runs, comparisons, benches, tasks) is covered.
+pub(crate) async fn dispatch_turn(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    prompt: &str,
+) -> Result<Option<usize>, String> {
+    let prompt = redact_outgoing_prompt(state, workspace_id, prompt).await?;
+    let content = Value::String(prompt);
+    dispatch_turn_content(state, event_sink, session, workspace_id, thread_id, turn_id, content)
+        .await
+}
+
+/// [`dispatch_turn`] for message content that is more than text, such as a
+/// prompt with image blocks. Callers apply redaction themselves, since the
+/// composer can skip it for a single message.
+pub(crate) async fn dispatch_turn_content(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    content: Value,
+) -> Result<Option<usize>, String> {
+    let scheduler = Arc::clone(&state.turn_scheduler);
+    let Some((ready, queued)) = scheduler.acquire(workspace_id, thread_id, turn_id).await else {
+        start_turn(&scheduler, event_sink, session, thread_id, turn_id, &content).await?;
+        return Ok(None);
+    };
+    emit_queued(event_sink, &queued);
+
+    let position = queued.position;
+    let event_sink = event_sink.clone();
+    let session = Arc::clone(session);
+    let workspace_id = workspace_id.to_string();
+    let thread_id = thread_id.to_string();
+    let turn_id = turn_id.to_string();
+    tokio::spawn(async move {
+        if !wait_for_slot(&session, &thread_id, &turn_id, ready).await {
+            return;
+        }
+        if let Err(error) =
+            start_turn(&scheduler, &event_sink, &session, &thread_id, &turn_id, &content).await
+        {
+            tracing::warn!("[turn_scheduler] Failed to start queued turn {turn_id}: {error}");
+            session.notify_turn_completed(&thread_id, &turn_id, "failed", None);
+            event_sink.emit_app_server_event(AppServerEvent {
+                workspace_id,
+                message: json!({
+                    "method": "turn/completed",
+                    "params": {
+                        "threadId": thread_id,
+                        "turn": { "id": turn_id, "threadId": thread_id },
+                    },
+                }),
+            });
+        }
+    });
+    Ok(Some(position))
+}
+
+/// A turn the app started itself, watched until it ends.
+pub(crate) struct TrackedTurn {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    /// Position in the global queue when the turn had to wait for a slot
+    pub(crate) queue_position: Option<usize>,
+    turn_events: broadcast::Receiver<TurnCompletion>,
+}
+
+impl TrackedTurn {
+    /// Wait for the turn to end. A session that goes away first reports the
+    /// turn as "interrupted".
+    pub(crate) async fn completion(mut self) -> TurnCompletion {
+        loop {
+            match self.turn_events.recv().await {
+                Ok(completion)
+                    if completion.thread_id == self.thread_id
+                        && completion.turn_id == self.turn_id =>
+                {
+                    return completion;
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => {
+                    return TurnCompletion {
+                        thread_id: self.thread_id,
+                        turn_id: self.turn_id,
+                        status: "interrupted".to_string(),
+                        cost_usd: None,
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Make sure the thread has a CLI process with `model` and `access_mode`, then
+/// dispatch `prompt` as a new turn and start watching for it to end.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn start_tracked_turn(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    thread_id: &str,
+    model: Option<&str>,
+    access_mode: Option<&str>,
+    prompt: &str,
+) -> Result<TrackedTurn, String> {
+    // Subscribe before sending so a fast turn cannot finish unobserved.
+    let turn_events = session.subscribe_turn_events();
+    let turn_id = ensure_persistent_session(
+        workspace_id,
+        session,
+        thread_id,
+        model,
+        access_mode,
+        None,
+        event_sink.clone(),
+    )
+    .await?;
+    let queue_position = dispatch_turn(
+        state,
+        event_sink,
+        session,
+        workspace_id,
+        thread_id,
+        &turn_id,
+        prompt,
+    )
+    .await?;
+    Ok(TrackedTurn {
+        thread_id: thread_id.to_string(),
+        turn_id,
+        queue_position,
+        turn_events,
+    })
+}
+
+/// [`start_tracked_turn`] and wait for the turn to end.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_turn_until_complete(
+    state: &AppState,
+    event_sink: &TauriEventSink,
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    thread_id: &str,
+    model: Option<&str>,
+    access_mode: Option<&str>,
+    prompt: &str,
+) -> Result<TurnCompletion, String> {
+    let turn = start_tracked_turn(
+        state,
+        event_sink,
+        session,
+        workspace_id,
+        thread_id,
+        model,
+        access_mode,
+        prompt,
+    )
+    .await?;
+    Ok(turn.completion().await)
+}
+
+/// Turns currently waiting for a slot, in the order they will start.
+#[tauri::command]
+pub(crate) async fn get_turn_queue(state: State<'_, AppState>) -> Result<Vec<QueuedTurn>, String> {
+    Ok(state.turn_scheduler.queued().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::events::TerminalOutput;
+    use std::time::Duration;
+
+    #[derive(Clone)]
+    struct NullSink;
+
+    impl EventSink for NullSink {
+        fn emit_app_server_event(&self, _event: AppServerEvent) {}
+        fn emit_terminal_output(&self, _event: TerminalOutput) {}
+    }
+
+    fn waiter(turn_id: &str) -> (Waiter, oneshot::Receiver<()>) {
+        let (ready, receiver) = oneshot::channel();
+        (
+            Waiter {
+                workspace_id: "ws".to_string(),
+                thread_id: format!("thread-{turn_id}"),
+                turn_id: turn_id.to_string(),
+                queued_at: 0,
+                ready,
+            },
+            receiver,
+        )
+    }
+
+    #[test]
+    fn normalize_limit_treats_zero_as_unlimited() {
+        assert_eq!(normalize_limit(None), None);
+        assert_eq!(normalize_limit(Some(0)), None);
+        assert_eq!(normalize_limit(Some(3)), Some(3));
+    }
+
+    #[test]
+    fn promote_fills_free_slots_in_order_and_skips_cancelled() {
+        let mut queue = SchedulerQueue {
+            limit: Some(2),
+            ..SchedulerQueue::default()
+        };
+        queue.active.insert("running".to_string());
+        let (first, _first_rx) = waiter("a");
+        let (dropped, dropped_rx) = waiter("b");
+        let (second, _second_rx) = waiter("c");
+        let (third, _third_rx) = waiter("d");
+        drop(dropped_rx);
+        queue.waiting.extend([first, dropped, second, third]);
+
+        let promoted: Vec<String> = queue.promote().into_iter().map(|w| w.turn_id).collect();
+        assert_eq!(promoted, vec!["a"]);
+
+        queue.active.remove("running");
+        let promoted: Vec<String> = queue.promote().into_iter().map(|w| w.turn_id).collect();
+        assert_eq!(promoted, vec!["c"]);
+
+        let snapshot = queue.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].turn_id, "d");
+        assert_eq!(snapshot[0].position, 1);
+    }
+
+    #[tokio::test]
+    async fn acquire_queues_beyond_the_limit() {
+        let scheduler = TurnScheduler::new(Some(1));
+        assert!(scheduler.acquire("ws", "t1", "turn-1").await.is_none());
+        let (_ready, queued) = scheduler.acquire("ws", "t2", "turn-2").await.unwrap();
+        assert_eq!(queued.position, 1);
+        let (_ready, queued) = scheduler.acquire("ws", "t3", "turn-3").await.unwrap();
+        assert_eq!(queued.position, 2);
+        assert_eq!(scheduler.queued().await.len(), 2);
+
+        let unlimited = TurnScheduler::new(None);
+        for index in 0..5 {
+            assert!(unlimited
+                .acquire("ws", "t", &format!("turn-{index}"))
+                .await
+                .is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn result_only_turn_releases_its_slot() {
+        let scheduler = Arc::new(TurnScheduler::new(Some(1)));
+        assert!(scheduler.acquire("ws", "t1", "turn-1").await.is_none());
+        let (ready, _queued) = scheduler.acquire("ws", "t2", "turn-2").await.unwrap();
+
+        let (sender, receiver) = broadcast::channel(8);
+        release_on_completion(Arc::clone(&scheduler), NullSink, "turn-1".to_string(), receiver);
+        // What the reader reports for a turn whose result arrives before any assistant message.
+        sender
+            .send(TurnCompletion {
+                thread_id: "t1".to_string(),
+                turn_id: "turn-1".to_string(),
+                status: "failed".to_string(),
+                cost_usd: None,
+            })
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), ready)
+            .await
+            .expect("queued turn was never promoted")
+            .unwrap();
+        assert!(scheduler.queued().await.is_empty());
+    }
+
+    fn completion(thread_id: &str, turn_id: &str, status: &str) -> TurnCompletion {
+        TurnCompletion {
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            status: status.to_string(),
+            cost_usd: Some(0.5),
+        }
+    }
+
+    fn tracked(receiver: broadcast::Receiver<TurnCompletion>) -> TrackedTurn {
+        TrackedTurn {
+            thread_id: "t1".to_string(),
+            turn_id: "turn-1".to_string(),
+            queue_position: None,
+            turn_events: receiver,
+        }
+    }
+
+    #[tokio::test]
+    async fn tracked_turn_waits_for_its_own_completion() {
+        let (sender, receiver) = broadcast::channel(8);
+        let turn = tracked(receiver);
+        sender.send(completion("t2", "turn-1", "failed")).unwrap();
+        sender.send(completion("t1", "turn-0", "failed")).unwrap();
+        sender.send(completion("t1", "turn-1", "completed")).unwrap();
+        let finished = turn.completion().await;
+        assert_eq!(finished.status, "completed");
+        assert_eq!(finished.cost_usd, Some(0.5));
+
+        let (sender, receiver) = broadcast::channel(8);
+        let turn = tracked(receiver);
+        drop(sender);
+        let finished = turn.completion().await;
+        assert_eq!((finished.turn_id.as_str(), finished.status.as_str()), ("turn-1", "interrupted"));
+        assert_eq!(finished.cost_usd, None);
+    }
+
+    // The test session helper lives in the unix-only claude_cli tests.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cancelled_queued_turn_is_reported_interrupted() {
+        use crate::backend::claude_cli::tests::create_test_workspace_session;
+
+        let scheduler = TurnScheduler::new(Some(1));
+        assert!(scheduler.acquire("ws", "t0", "turn-0").await.is_none());
+        let (ready, _queued) = scheduler.acquire("ws", "t1", "turn-1").await.unwrap();
+        let session = Arc::new(create_test_workspace_session());
+        let turn = tracked(session.subscribe_turn_events());
+
+        let waiting = {
+            let session = Arc::clone(&session);
+            tokio::spawn(async move { wait_for_slot(&session, "t1", "turn-1", ready).await })
+        };
+        assert!(scheduler.cancel("turn-1", &NullSink).await);
+        assert!(!waiting.await.unwrap());
+
+        let finished = tokio::time::timeout(Duration::from_secs(1), turn.completion())
+            .await
+            .expect("cancelled turn was never settled");
+        assert_eq!(finished.status, "interrupted");
+        assert!(scheduler.queued().await.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -53,6 +55,45 @@ pub(crate) struct GitLogEntry {
     pub(crate) timestamp: i64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitTag {
+    pub(crate) name: String,
+    /// The commit the tag points at.
+    pub(crate) sha: String,
+    /// Annotation message; `None` for lightweight tags.
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    /// Tagger time for annotated tags, else the commit time.
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlameCommit {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) email: String,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlameLine {
+    pub(crate) line: usize,
+    /// None for lines that are not committed yet.
+    pub(crate) sha: Option<String>,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlame {
+    pub(crate) path: String,
+    /// None when blaming the working tree copy.
+    pub(crate) rev: Option<String>,
+    pub(crate) lines: Vec<GitBlameLine>,
+    /// Every commit referenced by `lines`, newest first.
+    pub(crate) commits: Vec<GitBlameCommit>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitLogResponse {
     pub(crate) total: usize,
@@ -69,6 +110,50 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorktreePullRequest {
+    pub(crate) number: u64,
+    pub(crate) url: String,
+    pub(crate) state: String,
+    /// "success", "failure" or "pending"; `None` when the PR has no checks.
+    #[serde(default, rename = "ciStatus")]
+    pub(crate) ci_status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorktreeSummary {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) branch: String,
+    #[serde(rename = "baseBranch")]
+    pub(crate) base_branch: String,
+    pub(crate) files: Vec<GitFileStatus>,
+    pub(crate) insertions: usize,
+    pub(crate) deletions: usize,
+    pub(crate) ahead: usize,
+    pub(crate) behind: usize,
+    /// Paths that would conflict if the branch were merged into the base now.
+    #[serde(default, rename = "conflictPaths")]
+    pub(crate) conflict_paths: Vec<String>,
+    #[serde(default, rename = "pullRequest")]
+    pub(crate) pull_request: Option<WorktreePullRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorktreeRebaseResult {
+    /// "rebased", "upToDate" or "conflicts".
+    pub(crate) status: String,
+    /// The ref the branch was rebased onto: the parent's base branch, or its
+    /// upstream (e.g. "origin/main") when the base branch was only behind it.
+    pub(crate) onto: String,
+    /// Paths with unresolved conflicts while the rebase is stopped.
+    #[serde(default)]
+    pub(crate) conflicts: Vec<String>,
+    /// The commit that failed to apply.
+    #[serde(default, rename = "stoppedAt")]
+    pub(crate) stopped_at: Option<GitLogEntry>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
@@ -136,6 +221,30 @@ pub(crate) struct GitHubPullRequestComment {
     pub(crate) author: Option<GitHubPullRequestAuthor>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct GitHubReviewThreadComment {
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) body: String,
+}
+
+/// An unresolved inline review thread on a pull request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct GitHubReviewThread {
+    pub(crate) id: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) line: Option<u64>,
+    #[serde(default, rename = "diffHunk")]
+    pub(crate) diff_hunk: String,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    #[serde(default, rename = "isOutdated")]
+    pub(crate) is_outdated: bool,
+    pub(crate) comments: Vec<GitHubReviewThreadComment>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct LocalUsageDay {
@@ -264,6 +373,90 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) group_id: Option<String>,
     #[serde(default, rename = "gitRoot")]
     pub(crate) git_root: Option<String>,
+    #[serde(default, rename = "redactionRules")]
+    pub(crate) redaction_rules: Vec<RedactionRule>,
+    /// Start this workspace's session when the app launches.
+    #[serde(default, rename = "autoConnect")]
+    pub(crate) auto_connect: bool,
+    /// Directories outside the workspace its sessions may use, passed to the
+    /// CLI as `--add-dir`. Relative paths resolve against the workspace root.
+    #[serde(default, rename = "additionalDirs")]
+    pub(crate) additional_dirs: Vec<String>,
+    /// Shell commands, such as a dev server, run in managed terminals.
+    #[serde(default, rename = "startupCommands")]
+    pub(crate) startup_commands: Vec<String>,
+    /// Run `startup_commands` whenever the workspace connects.
+    #[serde(default, rename = "runStartupCommands")]
+    pub(crate) run_startup_commands: bool,
+    /// Run agent sessions inside a container instead of on the host.
+    #[serde(default)]
+    pub(crate) container: Option<ContainerSettings>,
+    /// Domains the agent's web tools may reach; `None` leaves them unrestricted.
+    #[serde(default, rename = "webAllowedDomains")]
+    pub(crate) web_allowed_domains: Option<Vec<String>>,
+    /// Saved ways to start a thread, used by `start_thread_from_preset`.
+    #[serde(default, rename = "threadPresets")]
+    pub(crate) thread_presets: Vec<ThreadPreset>,
+}
+
+/// Where a workspace's agent sessions run when they are isolated from the host.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct ContainerSettings {
+    pub(crate) mode: ContainerMode,
+    /// Container name or id for `exec`, image for `run`.
+    pub(crate) target: String,
+    /// Workspace path inside the container; defaults to the host path. The
+    /// CLI files transcripts under the project dir of this path, so workspaces
+    /// sharing a workdir also share their session history.
+    #[serde(default)]
+    pub(crate) workdir: Option<String>,
+    /// Container CLI, `docker` unless set (e.g. `podman`).
+    #[serde(default)]
+    pub(crate) runtime: Option<String>,
+    /// Claude CLI inside the container, `claude` unless set.
+    #[serde(default, rename = "claudeBin")]
+    pub(crate) claude_bin: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ContainerMode {
+    /// `docker exec` into a running container, such as a devcontainer.
+    /// Threads resume only if it mounts the host Claude home at the same path
+    /// and sets `CLAUDE_CONFIG_DIR` to it.
+    Exec,
+    /// `docker run` a fresh container per session with the workspace mounted.
+    Run,
+}
+
+/// A pre-configured new thread for a recurring flow.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct ThreadPreset {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default, rename = "accessMode")]
+    pub(crate) access_mode: Option<String>,
+    /// First message of the thread; `$NAME` placeholders are filled in when it starts.
+    #[serde(rename = "promptTemplate")]
+    pub(crate) prompt_template: String,
+    /// Skills mentioned at the top of the first message.
+    #[serde(default)]
+    pub(crate) skills: Vec<String>,
+}
+
+/// A pattern masked out of outgoing prompts for a workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct RedactionRule {
+    pub(crate) label: String,
+    /// Regular expression matched against the prompt text.
+    pub(crate) pattern: String,
+    #[serde(default = "default_redaction_rule_enabled")]
+    pub(crate) enabled: bool,
+    /// Replacement text; defaults to `[REDACTED:<label>]`.
+    #[serde(default)]
+    pub(crate) replacement: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -276,6 +469,41 @@ pub(crate) struct AppSettings {
     pub(crate) remote_backend_host: String,
     #[serde(default, rename = "remoteBackendToken")]
     pub(crate) remote_backend_token: Option<String>,
+    /// Reach the remote daemon through a supervised `ssh -L` forward.
+    #[serde(default, rename = "remoteSshTunnel")]
+    pub(crate) remote_ssh_tunnel: Option<SshTunnelSettings>,
+    /// OTLP collector the app exports metrics to. Falls back to
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` when unset.
+    #[serde(default, rename = "otlpEndpoint")]
+    pub(crate) otlp_endpoint: Option<String>,
+    /// Tell other clients of the remote daemon which thread this one is on.
+    /// Needs a daemon that handles `presence_update`.
+    #[serde(default, rename = "presenceSharingEnabled")]
+    pub(crate) presence_sharing_enabled: bool,
+    /// Folders new workspaces must live under; empty allows any path.
+    #[serde(default, rename = "allowedWorkspaceRoots")]
+    pub(crate) allowed_workspace_roots: Vec<String>,
+    /// Answer permission prompts nobody responded to after this many seconds; 0 waits forever.
+    #[serde(default, rename = "approvalTimeoutSecs")]
+    pub(crate) approval_timeout_secs: u64,
+    /// "accept" or "decline", applied when an approval times out.
+    #[serde(
+        default = "default_approval_timeout_decision",
+        rename = "approvalTimeoutDecision"
+    )]
+    pub(crate) approval_timeout_decision: String,
+    /// Tool output in live item events is cut to this many bytes; 0 sends it whole.
+    #[serde(
+        default = "default_event_output_limit_bytes",
+        rename = "eventOutputLimitBytes"
+    )]
+    pub(crate) event_output_limit_bytes: usize,
+    /// Gitignore-style patterns left out of diffs sent in commit and review prompts.
+    #[serde(
+        default = "default_prompt_diff_excludes",
+        rename = "promptDiffExcludes"
+    )]
+    pub(crate) prompt_diff_excludes: Vec<String>,
     #[serde(default = "default_access_mode", rename = "defaultAccessMode")]
     pub(crate) default_access_mode: String,
     #[serde(
@@ -395,6 +623,9 @@ pub(crate) struct AppSettings {
         rename = "dictationHoldKey"
     )]
     pub(crate) dictation_hold_key: String,
+    /// Treat a trailing "send", a lone "cancel" and "new line" as composer actions.
+    #[serde(default, rename = "dictationVoiceCommands")]
+    pub(crate) dictation_voice_commands: bool,
     #[serde(default = "default_composer_editor_preset", rename = "composerEditorPreset")]
     pub(crate) composer_editor_preset: String,
     #[serde(default = "default_composer_fence_expand_on_space", rename = "composerFenceExpandOnSpace")]
@@ -418,6 +649,108 @@ pub(crate) struct AppSettings {
     pub(crate) composer_code_block_copy_use_modifier: bool,
     #[serde(default = "default_workspace_groups", rename = "workspaceGroups")]
     pub(crate) workspace_groups: Vec<WorkspaceGroup>,
+    /// Purge app-side records older than this many days. `None` keeps everything.
+    #[serde(default, rename = "dataRetentionDays")]
+    pub(crate) data_retention_days: Option<u32>,
+    /// Store a files/commands/usage/cost report after every completed turn.
+    #[serde(default, rename = "turnReportsEnabled")]
+    pub(crate) turn_reports_enabled: bool,
+    /// Record every turn's event stream to disk so it can be replayed later.
+    #[serde(default, rename = "turnRecordingEnabled")]
+    pub(crate) turn_recording_enabled: bool,
+    /// Snapshot the worktree around every turn so `get_turn_diff` can show
+    /// what a single turn changed.
+    #[serde(default, rename = "turnCheckpointsEnabled")]
+    pub(crate) turn_checkpoints_enabled: bool,
+    /// Title threads with a one-line haiku summary after their first turn.
+    #[serde(default, rename = "threadSummariesEnabled")]
+    pub(crate) thread_summaries_enabled: bool,
+    /// Poll GitHub checks for every workspace's branch in the background.
+    #[serde(default, rename = "ciStatusPollingEnabled")]
+    pub(crate) ci_status_polling_enabled: bool,
+    /// Run at most this many turns at once across all workspaces; extra turns queue.
+    /// `None` means no limit.
+    #[serde(default, rename = "maxParallelTurns")]
+    pub(crate) max_parallel_turns: Option<u32>,
+    /// Menu accelerators registered at startup, keyed by menu item id.
+    /// Owned by `menu_set_accelerators`; `None` means the item has no shortcut.
+    #[serde(default)]
+    pub(crate) accelerators: BTreeMap<String, Option<String>>,
+    /// Release channel the updater checks: "stable" or "beta".
+    #[serde(default = "default_update_channel", rename = "updateChannel")]
+    pub(crate) update_channel: String,
+    /// Minimum level written to the app log; `None` means info.
+    /// `CLAUDE_MONITOR_LOG` overrides it.
+    #[serde(default, rename = "logLevel")]
+    pub(crate) log_level: Option<String>,
+    /// Per-module level overrides, keyed by module path such as `claude` or
+    /// `backend::claude_cli`.
+    #[serde(default, rename = "logLevels")]
+    pub(crate) log_levels: BTreeMap<String, String>,
+    /// OS sandbox wrapped around sessions running in `bypassPermissions` mode.
+    #[serde(default, rename = "fullAccessSandbox")]
+    pub(crate) full_access_sandbox: Option<SandboxSettings>,
+    /// Hold dangerous Bash commands for approval in full-access sessions.
+    /// Off by default, leaving full access unguarded.
+    #[serde(default, rename = "dangerousCommandGuardEnabled")]
+    pub(crate) dangerous_command_guard_enabled: bool,
+    /// Regexes for Bash commands a guarded full-access session must ask
+    /// before running; `None` uses the built-in list.
+    #[serde(default, rename = "dangerousCommandPatterns")]
+    pub(crate) dangerous_command_patterns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SandboxSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) tool: SandboxTool,
+    /// Writable besides the workspace, its additional dirs, the Claude home
+    /// and temp dirs.
+    #[serde(default, rename = "writablePaths")]
+    pub(crate) writable_paths: Vec<String>,
+    /// Skip the network restriction, which otherwise allows only loopback
+    /// connections and HTTPS to `api_hosts` through a local proxy. Only
+    /// sandbox-exec can filter the network, so other tools refuse to start
+    /// without this.
+    #[serde(default, rename = "allowNetwork")]
+    pub(crate) allow_network: bool,
+    /// Hosts (`host` or `host:port`) a network-restricted session may reach.
+    /// Empty means the Anthropic API and sign-in hosts; the host of
+    /// `ANTHROPIC_BASE_URL` is always added.
+    #[serde(default, rename = "apiHosts")]
+    pub(crate) api_hosts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SandboxTool {
+    /// sandbox-exec on macOS; bubblewrap, else firejail, on Linux.
+    #[default]
+    Auto,
+    SandboxExec,
+    Bubblewrap,
+    Firejail,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SshTunnelSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    pub(crate) host: String,
+    #[serde(default)]
+    pub(crate) user: Option<String>,
+    #[serde(default, rename = "keyPath")]
+    pub(crate) key_path: Option<String>,
+    #[serde(default, rename = "sshPort")]
+    pub(crate) ssh_port: Option<u16>,
+    /// Local end of the forward; the remote backend connects here while the tunnel is up.
+    #[serde(default = "default_ssh_tunnel_local_port", rename = "localPort")]
+    pub(crate) local_port: u16,
+    /// Port the daemon listens on, on the SSH host's loopback interface.
+    #[serde(default = "default_ssh_tunnel_remote_port", rename = "remotePort")]
+    pub(crate) remote_port: u16,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -433,10 +766,47 @@ impl Default for BackendMode {
     }
 }
 
+fn default_ssh_tunnel_local_port() -> u16 {
+    4733
+}
+
+fn default_ssh_tunnel_remote_port() -> u16 {
+    4732
+}
+
 fn default_access_mode() -> String {
     "current".to_string()
 }
 
+fn default_approval_timeout_decision() -> String {
+    "decline".to_string()
+}
+
+fn default_event_output_limit_bytes() -> usize {
+    16 * 1024
+}
+
+fn default_prompt_diff_excludes() -> Vec<String> {
+    [
+        "package-lock.json",
+        "yarn.lock",
+        "pnpm-lock.yaml",
+        "bun.lockb",
+        "Cargo.lock",
+        "Gemfile.lock",
+        "poetry.lock",
+        "go.sum",
+        "*.min.js",
+        "*.min.css",
+        "*.map",
+        "vendor/",
+        "node_modules/",
+    ]
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect()
+}
+
 fn default_remote_backend_host() -> String {
     "127.0.0.1:4732".to_string()
 }
@@ -526,6 +896,10 @@ fn default_notification_sounds_enabled() -> bool {
     true
 }
 
+fn default_redaction_rule_enabled() -> bool {
+    true
+}
+
 fn default_experimental_collab_enabled() -> bool {
     false
 }
@@ -554,6 +928,10 @@ fn default_composer_editor_preset() -> String {
     "default".to_string()
 }
 
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
 fn default_composer_fence_expand_on_space() -> bool {
     false
 }
@@ -597,6 +975,14 @@ impl Default for AppSettings {
             backend_mode: BackendMode::Local,
             remote_backend_host: default_remote_backend_host(),
             remote_backend_token: None,
+            remote_ssh_tunnel: None,
+            otlp_endpoint: None,
+            presence_sharing_enabled: false,
+            allowed_workspace_roots: Vec::new(),
+            approval_timeout_secs: 0,
+            approval_timeout_decision: default_approval_timeout_decision(),
+            event_output_limit_bytes: default_event_output_limit_bytes(),
+            prompt_diff_excludes: default_prompt_diff_excludes(),
             default_access_mode: "current".to_string(),
             composer_model_shortcut: default_composer_model_shortcut(),
             composer_access_shortcut: default_composer_access_shortcut(),
@@ -628,6 +1014,7 @@ impl Default for AppSettings {
             dictation_model_id: default_dictation_model_id(),
             dictation_preferred_language: None,
             dictation_hold_key: default_dictation_hold_key(),
+            dictation_voice_commands: false,
             composer_editor_preset: default_composer_editor_preset(),
             composer_fence_expand_on_space: default_composer_fence_expand_on_space(),
             composer_fence_expand_on_enter: default_composer_fence_expand_on_enter(),
@@ -638,6 +1025,20 @@ impl Default for AppSettings {
             composer_list_continuation: default_composer_list_continuation(),
             composer_code_block_copy_use_modifier: default_composer_code_block_copy_use_modifier(),
             workspace_groups: default_workspace_groups(),
+            data_retention_days: None,
+            turn_reports_enabled: false,
+            turn_recording_enabled: false,
+            turn_checkpoints_enabled: false,
+            thread_summaries_enabled: false,
+            ci_status_polling_enabled: false,
+            max_parallel_turns: None,
+            accelerators: BTreeMap::new(),
+            update_channel: default_update_channel(),
+            log_level: None,
+            log_levels: BTreeMap::new(),
+            full_access_sandbox: None,
+            dangerous_command_guard_enabled: false,
+            dangerous_command_patterns: None,
         }
     }
 }
@@ -655,6 +1056,17 @@ mod tests {
         assert!(matches!(settings.backend_mode, BackendMode::Local));
         assert_eq!(settings.remote_backend_host, "127.0.0.1:4732");
         assert!(settings.remote_backend_token.is_none());
+        assert!(settings.remote_ssh_tunnel.is_none());
+        assert!(settings.otlp_endpoint.is_none());
+        assert!(!settings.presence_sharing_enabled);
+        assert!(settings.allowed_workspace_roots.is_empty());
+        assert_eq!(settings.approval_timeout_secs, 0);
+        assert_eq!(settings.approval_timeout_decision, "decline");
+        assert_eq!(settings.event_output_limit_bytes, 16 * 1024);
+        assert!(settings
+            .prompt_diff_excludes
+            .iter()
+            .any(|pattern| pattern == "package-lock.json"));
         assert_eq!(settings.default_access_mode, "current");
         assert_eq!(
             settings.composer_model_shortcut.as_deref(),
@@ -706,6 +1118,7 @@ mod tests {
         assert_eq!(settings.dictation_model_id, "base");
         assert!(settings.dictation_preferred_language.is_none());
         assert_eq!(settings.dictation_hold_key, "alt");
+        assert!(!settings.dictation_voice_commands);
         assert_eq!(settings.composer_editor_preset, "default");
         assert!(!settings.composer_fence_expand_on_space);
         assert!(!settings.composer_fence_expand_on_enter);
@@ -716,6 +1129,20 @@ mod tests {
         assert!(!settings.composer_list_continuation);
         assert!(!settings.composer_code_block_copy_use_modifier);
         assert!(settings.workspace_groups.is_empty());
+        assert!(settings.data_retention_days.is_none());
+        assert!(!settings.turn_reports_enabled);
+        assert!(!settings.turn_recording_enabled);
+        assert!(!settings.turn_checkpoints_enabled);
+        assert!(!settings.thread_summaries_enabled);
+        assert!(!settings.ci_status_polling_enabled);
+        assert!(settings.max_parallel_turns.is_none());
+        assert!(settings.accelerators.is_empty());
+        assert_eq!(settings.update_channel, "stable");
+        assert!(settings.log_level.is_none());
+        assert!(settings.log_levels.is_empty());
+        assert!(settings.full_access_sandbox.is_none());
+        assert!(!settings.dangerous_command_guard_enabled);
+        assert!(settings.dangerous_command_patterns.is_none());
     }
 
     #[test]
@@ -765,5 +1192,6 @@ mod tests {
         assert!(settings.sort_order.is_none());
         assert!(settings.group_id.is_none());
         assert!(settings.git_root.is_none());
+        assert!(settings.redaction_rules.is_empty());
     }
 }
@@ -0,0 +1,221 @@
+use serde::Serialize;
+#[cfg(desktop)]
+use serde_json::json;
+use tauri::{AppHandle, State};
+#[cfg(desktop)]
+use tauri::Emitter;
+
+use crate::state::AppState;
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/siddartha-10/ClaudeCodeMonitor/releases/latest/download/latest.json";
+/// Pre-releases are published under a rolling `beta` tag, since GitHub's
+/// `latest` release never points at one.
+const BETA_ENDPOINT: &str =
+    "https://github.com/siddartha-10/ClaudeCodeMonitor/releases/download/beta/latest.json";
+
+/// A heading in the release notes and the entries under it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReleaseNoteSection {
+    /// `None` for entries before the first heading.
+    pub(crate) title: Option<String>,
+    pub(crate) items: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpdateCheck {
+    pub(crate) channel: String,
+    pub(crate) current_version: String,
+    pub(crate) available: bool,
+    pub(crate) version: Option<String>,
+    pub(crate) date: Option<String>,
+    /// Release notes as published, in markdown.
+    pub(crate) notes: Option<String>,
+    pub(crate) sections: Vec<ReleaseNoteSection>,
+}
+
+/// Unknown channels fall back to stable rather than failing the check.
+fn channel_endpoint(channel: &str) -> (&'static str, &'static str) {
+    match channel {
+        "beta" => ("beta", BETA_ENDPOINT),
+        _ => ("stable", STABLE_ENDPOINT),
+    }
+}
+
+/// Group markdown release notes into sections by heading, one item per bullet
+/// or paragraph line.
+fn parse_release_notes(notes: &str) -> Vec<ReleaseNoteSection> {
+    let mut sections: Vec<ReleaseNoteSection> = Vec::new();
+    for line in notes.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('#') {
+            let title = line.trim_start_matches('#').trim();
+            if !title.is_empty() {
+                sections.push(ReleaseNoteSection {
+                    title: Some(title.to_string()),
+                    items: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("+ "))
+            .unwrap_or(line)
+            .trim();
+        if item.is_empty() {
+            continue;
+        }
+        if sections.is_empty() {
+            sections.push(ReleaseNoteSection {
+                title: None,
+                items: Vec::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.items.push(item.to_string());
+        }
+    }
+    sections.retain(|section| !section.items.is_empty());
+    sections
+}
+
+#[cfg(desktop)]
+async fn channel_update(
+    app: &AppHandle,
+    channel: &str,
+) -> Result<Option<tauri_plugin_updater::Update>, String> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let (_, endpoint) = channel_endpoint(channel);
+    let url = tauri::Url::parse(endpoint).map_err(|err| err.to_string())?;
+    app.updater_builder()
+        .endpoints(vec![url])
+        .map_err(|err| err.to_string())?
+        .build()
+        .map_err(|err| err.to_string())?
+        .check()
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Check the configured update channel for a newer release.
+#[tauri::command]
+pub(crate) async fn check_for_updates(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<UpdateCheck, String> {
+    let channel = state.app_settings.lock().await.update_channel.clone();
+    let (channel, _) = channel_endpoint(&channel);
+    let current_version = app.package_info().version.to_string();
+    #[cfg(desktop)]
+    {
+        let update = channel_update(&app, channel).await?;
+        let notes = update.as_ref().and_then(|update| update.body.clone());
+        Ok(UpdateCheck {
+            channel: channel.to_string(),
+            current_version,
+            available: update.is_some(),
+            version: update.as_ref().map(|update| update.version.clone()),
+            date: update
+                .as_ref()
+                .and_then(|update| update.date)
+                .map(|date| date.to_string()),
+            sections: notes.as_deref().map(parse_release_notes).unwrap_or_default(),
+            notes,
+        })
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = current_version;
+        Err("Updates are not supported on this platform.".to_string())
+    }
+}
+
+/// Download and install the latest release on the configured channel, emitting
+/// `updater-progress` while downloading. The app must be restarted afterwards.
+#[tauri::command]
+pub(crate) async fn install_update(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let channel = state.app_settings.lock().await.update_channel.clone();
+    #[cfg(desktop)]
+    {
+        let Some(update) = channel_update(&app, &channel).await? else {
+            return Err("No update is available.".to_string());
+        };
+        let version = update.version.clone();
+        let mut downloaded: u64 = 0;
+        let result = update
+            .download_and_install(
+                |chunk, total| {
+                    downloaded += chunk as u64;
+                    let _ = app.emit(
+                        "updater-progress",
+                        json!({
+                            "version": version,
+                            "downloaded": downloaded,
+                            "total": total,
+                        }),
+                    );
+                },
+                || {
+                    let _ = app.emit("updater-downloaded", json!({ "version": version }));
+                },
+            )
+            .await
+            .map_err(|err| err.to_string());
+        crate::audit::record(
+            "install_update",
+            None,
+            json!({ "channel": channel, "version": update.version }),
+            &result,
+        );
+        result
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = (channel, app);
+        Err("Updates are not supported on this platform.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_release_notes_groups_items_by_heading() {
+        let notes = "Small release.\n\n## Features\n- Usage heatmap\n* Model compare\n\n\
+### Fixes\n+ Thread titles no longer flicker\n## Empty\n";
+        assert_eq!(
+            parse_release_notes(notes),
+            vec![
+                ReleaseNoteSection {
+                    title: None,
+                    items: vec!["Small release.".to_string()],
+                },
+                ReleaseNoteSection {
+                    title: Some("Features".to_string()),
+                    items: vec!["Usage heatmap".to_string(), "Model compare".to_string()],
+                },
+                ReleaseNoteSection {
+                    title: Some("Fixes".to_string()),
+                    items: vec!["Thread titles no longer flicker".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn channel_endpoint_defaults_to_stable() {
+        assert_eq!(channel_endpoint("beta").1, BETA_ENDPOINT);
+        assert_eq!(channel_endpoint("nightly"), ("stable", STABLE_ENDPOINT));
+    }
+}
@@ -1,8 +1,27 @@
-use tauri::{Theme, Window};
-
-#[cfg(test)]
+use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Theme, WebviewUrl, WebviewWindowBuilder, Window, WindowEvent};
+
+use crate::backend::events::AppServerEvent;
+
+const THREAD_WINDOW_PREFIX: &str = "thread-";
+
+/// Which workspace/thread a secondary thread window shows.
+#[derive(Debug, Clone, PartialEq)]
+struct ThreadWindowScope {
+    workspace_id: String,
+    thread_id: String,
+}
+
+/// Open thread windows keyed by window label.
+static THREAD_WINDOWS: OnceLock<Mutex<HashMap<String, ThreadWindowScope>>> = OnceLock::new();
+
+fn thread_windows() -> &'static Mutex<HashMap<String, ThreadWindowScope>> {
+    THREAD_WINDOWS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 #[cfg(test)]
 type WindowAppearanceOverride =
     Box<dyn Fn(&Window, &str) -> Result<(), String> + Send + Sync + 'static>;
@@ -48,6 +67,38 @@ fn apply_macos_window_appearance(window: &Window, theme: &str) -> Result<(), Str
     Ok(())
 }
 
+/// Mirror the number of running turns on the dock badge (macOS) or as an
+/// indeterminate taskbar progress bar (Windows); cleared at zero.
+pub(crate) fn set_running_turn_indicator(app: &AppHandle, count: usize) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    #[cfg(target_os = "macos")]
+    {
+        let badge = (count > 0).then_some(count as i64);
+        if let Err(error) = window.set_badge_count(badge) {
+            tracing::debug!("[window] Failed to set dock badge: {error}");
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use tauri::window::{ProgressBarState, ProgressBarStatus};
+        let status = if count > 0 {
+            ProgressBarStatus::Indeterminate
+        } else {
+            ProgressBarStatus::None
+        };
+        if let Err(error) = window.set_progress_bar(ProgressBarState {
+            status: Some(status),
+            progress: None,
+        }) {
+            tracing::debug!("[window] Failed to set taskbar progress: {error}");
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let _ = (window, count);
+}
+
 pub(crate) fn apply_window_appearance(window: &Window, theme: &str) -> Result<(), String> {
     #[cfg(test)]
     if let Some(handler) = WINDOW_APPEARANCE_OVERRIDE
@@ -79,3 +130,165 @@ pub(crate) fn apply_window_appearance(window: &Window, theme: &str) -> Result<()
 
     Ok(())
 }
+
+/// Window labels only allow alphanumerics and `-/:_`.
+fn thread_window_label(workspace_id: &str, thread_id: &str) -> String {
+    format!("{THREAD_WINDOW_PREFIX}{workspace_id}-{thread_id}")
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '/' | ':' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+                (byte as char).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// Event name a thread window listens on for its filtered app-server events.
+pub(crate) fn thread_window_event_name(label: &str) -> String {
+    format!("app-server-event:{label}")
+}
+
+fn event_matches_scope(scope: &ThreadWindowScope, event: &AppServerEvent) -> bool {
+    // Global events (rate limits, tunnel status, ...) go to every window.
+    if event.workspace_id.is_empty() {
+        return true;
+    }
+    if event.workspace_id != scope.workspace_id {
+        return false;
+    }
+    let params = event.message.get("params");
+    let thread_id = params
+        .and_then(|params| params.get("threadId"))
+        .or_else(|| {
+            params
+                .and_then(|params| params.get("thread"))
+                .and_then(|thread| thread.get("id"))
+        })
+        .and_then(Value::as_str);
+    match thread_id {
+        Some(thread_id) => thread_id == scope.thread_id,
+        None => true,
+    }
+}
+
+/// Labels of the thread windows that should receive an app-server event.
+pub(crate) fn thread_window_targets(event: &AppServerEvent) -> Vec<String> {
+    let windows = thread_windows().lock().unwrap_or_else(|err| err.into_inner());
+    windows
+        .iter()
+        .filter(|(_, scope)| event_matches_scope(scope, event))
+        .map(|(label, _)| label.clone())
+        .collect()
+}
+
+/// Open (or focus) a secondary window showing a single thread.
+///
+/// The window receives only the events for its thread on
+/// `app-server-event:<label>`, so it can sit beside the main window.
+#[tauri::command]
+pub(crate) async fn open_thread_window(
+    workspace_id: String,
+    thread_id: String,
+    title: Option<String>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let label = thread_window_label(&workspace_id, &thread_id);
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(label);
+    }
+
+    let url = format!(
+        "index.html?workspaceId={}&threadId={}",
+        encode_query_value(&workspace_id),
+        encode_query_value(&thread_id)
+    );
+    let title = title
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "Claude Code Monitor".to_string());
+    let window = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title(title)
+        .inner_size(720.0, 700.0)
+        .min_inner_size(360.0, 400.0)
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    thread_windows()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(
+            label.clone(),
+            ThreadWindowScope {
+                workspace_id,
+                thread_id,
+            },
+        );
+    let closed_label = label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            thread_windows()
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .remove(&closed_label);
+        }
+    });
+    Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope() -> ThreadWindowScope {
+        ThreadWindowScope {
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+        }
+    }
+
+    fn event(workspace_id: &str, params: Value) -> AppServerEvent {
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({ "method": "item/started", "params": params }),
+        }
+    }
+
+    #[test]
+    fn thread_window_label_replaces_disallowed_characters() {
+        assert_eq!(
+            thread_window_label("ws 1", "abc.def"),
+            "thread-ws_1-abc_def"
+        );
+        assert_eq!(encode_query_value("a b/c"), "a%20b%2Fc");
+    }
+
+    #[test]
+    fn event_matches_scope_filters_by_workspace_and_thread() {
+        let scope = scope();
+        assert!(event_matches_scope(&scope, &event("", json!({}))));
+        assert!(event_matches_scope(&scope, &event("ws-1", json!({ "threadId": "thread-1" }))));
+        assert!(event_matches_scope(&scope, &event("ws-1", json!({ "thread": { "id": "thread-1" } }))));
+        assert!(event_matches_scope(&scope, &event("ws-1", json!({}))));
+        assert!(!event_matches_scope(&scope, &event("ws-1", json!({ "threadId": "thread-2" }))));
+        assert!(!event_matches_scope(&scope, &event("ws-2", json!({ "threadId": "thread-1" }))));
+    }
+}
@@ -1,32 +1,40 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 
 use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use uuid::Uuid;
 
 use crate::claude::{
-    ensure_workspace_thread_watcher, spawn_workspace_session, stop_workspace_thread_watcher,
+    ensure_workspace_thread_watcher, migrate_project_sessions, spawn_workspace_session,
+    stop_workspace_thread_watcher, workspace_thread_meta,
 };
 use crate::backend::events::{AppServerEvent, EventSink};
+use crate::claude_home::resolve_home_dir;
+use crate::container::validate_container_settings;
 use crate::event_sink::TauriEventSink;
+use crate::file_io::{read_file_range, LineRange, TextFileResponse};
+use crate::file_tree::forget_file_index;
+use crate::git_clone::{clone_args, run_clone, CloneOptions};
+use crate::redaction::validate_redaction_rules;
 use crate::remote_backend;
+use crate::startup_commands::{start_startup_commands, stop_startup_commands};
 use crate::state::AppState;
+use crate::thread_presets::validate_thread_presets;
 use crate::git_utils::resolve_git_root;
-use crate::storage::write_workspaces;
+use crate::storage::{write_settings, write_workspaces};
 use crate::types::{
     WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
-fn should_skip_dir(name: &str) -> bool {
+pub(crate) fn should_skip_dir(name: &str) -> bool {
     matches!(
         name,
         ".git" | "node_modules" | "dist" | "target" | "release-artifacts"
@@ -67,7 +75,7 @@ fn sanitize_clone_dir_name(name: &str) -> String {
     }
 }
 
-fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
+pub(crate) fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
     let mut results = Vec::new();
     let walker = WalkBuilder::new(root)
         // Allow hidden entries.
@@ -111,18 +119,13 @@ fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
     results
 }
 
-const MAX_WORKSPACE_FILE_BYTES: u64 = 400_000;
-
-#[derive(Serialize, Deserialize, Clone)]
-pub(crate) struct WorkspaceFileResponse {
-    content: String,
-    truncated: bool,
-}
+const MAX_WORKSPACE_FILE_BYTES: usize = 400_000;
 
 fn read_workspace_file_inner(
     root: &PathBuf,
     relative_path: &str,
-) -> Result<WorkspaceFileResponse, String> {
+    range: LineRange,
+) -> Result<TextFileResponse, String> {
     let canonical_root = root
         .canonicalize()
         .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
@@ -139,36 +142,29 @@ fn read_workspace_file_inner(
         return Err("Path is not a file".to_string());
     }
 
-    let file =
-        File::open(&canonical_path).map_err(|err| format!("Failed to open file: {err}"))?;
-    let mut buffer = Vec::new();
-    file.take(MAX_WORKSPACE_FILE_BYTES + 1)
-        .read_to_end(&mut buffer)
-        .map_err(|err| format!("Failed to read file: {err}"))?;
-
-    let truncated = buffer.len() > MAX_WORKSPACE_FILE_BYTES as usize;
-    if truncated {
-        buffer.truncate(MAX_WORKSPACE_FILE_BYTES as usize);
-    }
-
-    let content =
-        String::from_utf8(buffer).map_err(|_| "File is not valid UTF-8".to_string())?;
-    Ok(WorkspaceFileResponse { content, truncated })
+    read_file_range(&canonical_path, range, MAX_WORKSPACE_FILE_BYTES, "file")
 }
 
 #[tauri::command]
 pub(crate) async fn read_workspace_file(
     workspace_id: String,
     path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceFileResponse, String> {
+) -> Result<TextFileResponse, String> {
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
             app,
             "read_workspace_file",
-            json!({ "workspaceId": workspace_id, "path": path }),
+            json!({
+                "workspaceId": workspace_id,
+                "path": path,
+                "offset": offset,
+                "limit": limit,
+            }),
         )
         .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
@@ -179,7 +175,7 @@ pub(crate) async fn read_workspace_file(
         .get(&workspace_id)
         .ok_or("workspace not found")?;
     let root = PathBuf::from(&entry.path);
-    read_workspace_file_inner(&root, &path)
+    read_workspace_file_inner(&root, &path, LineRange { offset, limit })
 }
 
 fn sort_workspaces(list: &mut Vec<WorkspaceInfo>) {
@@ -193,6 +189,29 @@ fn sort_workspaces(list: &mut Vec<WorkspaceInfo>) {
     });
 }
 
+/// Resolve an `additionalDirs` entry against the workspace root.
+pub(crate) fn resolve_additional_dir(workspace_path: &str, dir: &str) -> PathBuf {
+    Path::new(workspace_path).join(dir.trim())
+}
+
+fn validate_additional_dirs(
+    workspace_path: &str,
+    dirs: &[String],
+    allowed_roots: &[String],
+) -> Result<(), String> {
+    for dir in dirs {
+        if dir.trim().is_empty() {
+            return Err("Additional directory cannot be empty.".to_string());
+        }
+        let path = resolve_additional_dir(workspace_path, dir);
+        if !path.is_dir() {
+            return Err(format!("Additional directory not found: {}", dir.trim()));
+        }
+        ensure_allowed_workspace_path(&path, allowed_roots)?;
+    }
+    Ok(())
+}
+
 fn apply_workspace_settings_update(
     workspaces: &mut HashMap<String, WorkspaceEntry>,
     id: &str,
@@ -207,7 +226,7 @@ fn apply_workspace_settings_update(
     }
 }
 
-async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
+pub(crate) async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
     let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
     let output = Command::new(git_bin)
         .args(args)
@@ -466,6 +485,137 @@ fn build_clone_destination_path(copies_folder: &PathBuf, copy_name: &str) -> Pat
     unique_worktree_path(copies_folder, &safe_name)
 }
 
+pub(crate) fn expand_root(root: &str) -> Option<PathBuf> {
+    let root = root.trim();
+    if root.is_empty() {
+        return None;
+    }
+    if root == "~" {
+        return resolve_home_dir();
+    }
+    if let Some(rest) = root.strip_prefix("~/") {
+        return resolve_home_dir().map(|home| home.join(rest));
+    }
+    Some(PathBuf::from(root))
+}
+
+/// Reject `path` unless it sits inside one of `allowed_roots`. Both sides are
+/// canonicalized so `..` segments and symlinks can't escape a root. An empty
+/// list allows any path.
+pub(crate) fn ensure_allowed_workspace_path(
+    path: &Path,
+    allowed_roots: &[String],
+) -> Result<(), String> {
+    if allowed_roots.iter().all(|root| root.trim().is_empty()) {
+        return Ok(());
+    }
+    let roots: Vec<PathBuf> = allowed_roots
+        .iter()
+        .filter_map(|root| expand_root(root))
+        .filter_map(|root| root.canonicalize().ok())
+        .collect();
+    let resolved = path
+        .canonicalize()
+        .map_err(|err| format!("Unable to resolve {}: {err}", path.display()))?;
+    if roots.iter().any(|root| resolved.starts_with(root)) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} is outside the allowed workspace roots.",
+        resolved.display()
+    ))
+}
+
+pub(crate) async fn check_allowed_workspace_path(state: &AppState, path: &Path) -> Result<(), String> {
+    let allowed_roots = state.app_settings.lock().await.allowed_workspace_roots.clone();
+    ensure_allowed_workspace_path(path, &allowed_roots)
+}
+
+/// [`ensure_allowed_workspace_path`] for a path on the remote host, which
+/// can't be resolved here: it must be absolute, free of `..`, and lexically
+/// inside a root. Roots are compared as written, with `~` expanded locally.
+fn ensure_allowed_remote_workspace_path(
+    path: &Path,
+    allowed_roots: &[String],
+) -> Result<(), String> {
+    if allowed_roots.iter().all(|root| root.trim().is_empty()) {
+        return Ok(());
+    }
+    let lexically_safe = path.is_absolute()
+        && path
+            .components()
+            .all(|component| !matches!(component, Component::ParentDir));
+    if lexically_safe
+        && allowed_roots
+            .iter()
+            .filter_map(|root| expand_root(root))
+            .any(|root| path.starts_with(root))
+    {
+        return Ok(());
+    }
+    Err(format!(
+        "{} is outside the allowed workspace roots.",
+        path.display()
+    ))
+}
+
+/// Checked before a path is forwarded in remote mode, since the daemon does
+/// not see this app's allow-list.
+async fn check_allowed_remote_workspace_path(state: &AppState, path: &str) -> Result<(), String> {
+    let allowed_roots = state
+        .app_settings
+        .lock()
+        .await
+        .allowed_workspace_roots
+        .clone();
+    ensure_allowed_remote_workspace_path(Path::new(path.trim()), &allowed_roots)
+}
+
+/// Replace the allowed workspace roots after the user confirms in a native
+/// dialog. `update_app_settings` leaves the list alone, so a frontend call
+/// can't widen it unnoticed.
+#[tauri::command]
+pub(crate) async fn set_allowed_workspace_roots(
+    roots: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<String>, String> {
+    let roots: Vec<String> = roots
+        .iter()
+        .map(|root| root.trim().to_string())
+        .filter(|root| !root.is_empty())
+        .collect();
+    if state.app_settings.lock().await.allowed_workspace_roots == roots {
+        return Ok(roots);
+    }
+    let message = if roots.is_empty() {
+        "Allow workspaces in any folder?".to_string()
+    } else {
+        format!("Only allow workspaces inside:\n\n{}", roots.join("\n"))
+    };
+    let dialog_app = app.clone();
+    let confirmed = tokio::task::spawn_blocking(move || {
+        dialog_app
+            .dialog()
+            .message(message)
+            .title("Change allowed workspace roots")
+            .kind(MessageDialogKind::Warning)
+            .buttons(MessageDialogButtons::OkCancel)
+            .blocking_show()
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+    if !confirmed {
+        return Err("Change to allowed workspace roots was cancelled.".to_string());
+    }
+    let mut settings = state.app_settings.lock().await;
+    let mut next = settings.clone();
+    next.allowed_workspace_roots = roots.clone();
+    write_settings(&state.settings_path, &next)?;
+    *settings = next;
+    Ok(roots)
+}
+
 fn null_device_path() -> &'static str {
     if cfg!(windows) {
         "NUL"
@@ -531,6 +681,7 @@ pub(crate) async fn add_workspace(
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
     if remote_backend::is_remote_mode(&*state).await {
+        check_allowed_remote_workspace_path(&state, &path).await?;
         let response = remote_backend::call_remote(
             &*state,
             app,
@@ -544,6 +695,7 @@ pub(crate) async fn add_workspace(
     if !PathBuf::from(&path).is_dir() {
         return Err("Workspace path must be a folder.".to_string());
     }
+    check_allowed_workspace_path(&state, Path::new(&path)).await?;
 
     let name = PathBuf::from(&path)
         .file_name()
@@ -601,14 +753,24 @@ pub(crate) async fn add_workspace(
     })
 }
 
+/// Clone a workspace into `copies_folder`, emitting `clone-progress` events
+/// tagged with `clone_id`, which `cancel_clone` also takes. `depth` and
+/// `filter` make a shallow or partial clone of large repositories.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn add_clone(
     source_workspace_id: String,
     copy_name: String,
     copies_folder: String,
+    depth: Option<u32>,
+    filter: Option<String>,
+    clone_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
+    let clone_options = CloneOptions { depth, filter };
+    clone_options.validate()?;
+    let clone_id = clone_id.unwrap_or_else(|| Uuid::new_v4().to_string());
     let copy_name = copy_name.trim().to_string();
     if copy_name.is_empty() {
         return Err("Copy name is required.".to_string());
@@ -619,11 +781,16 @@ pub(crate) async fn add_clone(
         return Err("Copies folder is required.".to_string());
     }
     let copies_folder_path = PathBuf::from(&copies_folder);
+    // Check the nearest existing ancestor before creating anything.
+    if let Some(existing) = copies_folder_path.ancestors().find(|path| path.exists()) {
+        check_allowed_workspace_path(&state, existing).await?;
+    }
     std::fs::create_dir_all(&copies_folder_path)
         .map_err(|e| format!("Failed to create copies folder: {e}"))?;
     if !copies_folder_path.is_dir() {
         return Err("Copies folder must be a directory.".to_string());
     }
+    check_allowed_workspace_path(&state, &copies_folder_path).await?;
 
     let (source_entry, inherited_group_id) = {
         let workspaces = state.workspaces.lock().await;
@@ -646,12 +813,8 @@ pub(crate) async fn add_clone(
     let destination_path = build_clone_destination_path(&copies_folder_path, &copy_name);
     let destination_path_string = destination_path.to_string_lossy().to_string();
 
-    if let Err(error) = run_git_command(
-        &copies_folder_path,
-        &["clone", &source_entry.path, &destination_path_string],
-    )
-    .await
-    {
+    let clone_args = clone_args(&source_entry.path, &destination_path_string, &clone_options);
+    if let Err(error) = run_clone(&app, &clone_id, &copies_folder_path, &clone_args).await {
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
@@ -748,6 +911,7 @@ pub(crate) async fn add_worktree(
     if parent_entry.kind.is_worktree() {
         return Err("Cannot create a worktree from another worktree.".to_string());
     }
+    check_allowed_workspace_path(&state, Path::new(&parent_entry.path)).await?;
 
     let worktree_root = app
         .path()
@@ -822,6 +986,204 @@ pub(crate) async fn add_worktree(
     })
 }
 
+/// A git worktree of a workspace's repository that is not registered as a
+/// workspace yet, e.g. one created with `git worktree add` from a terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExternalWorktree {
+    pub(crate) path: String,
+    /// Checked-out branch; `None` for a detached HEAD.
+    pub(crate) branch: Option<String>,
+    pub(crate) head: Option<String>,
+    pub(crate) locked: bool,
+    /// Git reports the worktree's folder as missing.
+    pub(crate) prunable: bool,
+}
+
+/// Parse `git worktree list --porcelain`, skipping bare entries.
+fn parse_worktree_list(output: &str) -> Vec<ExternalWorktree> {
+    let mut worktrees = Vec::new();
+    for record in output.split("\n\n") {
+        let mut worktree: Option<ExternalWorktree> = None;
+        let mut bare = false;
+        for line in record.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match (key, worktree.as_mut()) {
+                ("worktree", _) => {
+                    worktree = Some(ExternalWorktree {
+                        path: value.to_string(),
+                        branch: None,
+                        head: None,
+                        locked: false,
+                        prunable: false,
+                    });
+                }
+                ("HEAD", Some(worktree)) => worktree.head = Some(value.to_string()),
+                ("branch", Some(worktree)) => {
+                    let branch = value.strip_prefix("refs/heads/").unwrap_or(value);
+                    worktree.branch = Some(branch.to_string());
+                }
+                ("locked", Some(worktree)) => worktree.locked = true,
+                ("prunable", Some(worktree)) => worktree.prunable = true,
+                ("bare", _) => bare = true,
+                _ => {}
+            }
+        }
+        if let Some(worktree) = worktree.filter(|_| !bare) {
+            worktrees.push(worktree);
+        }
+    }
+    worktrees
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => normalize_git_path(&a.to_string_lossy()) == normalize_git_path(&b.to_string_lossy()),
+    }
+}
+
+/// Worktrees of `parent`'s repository other than the main one that no
+/// workspace points at.
+async fn external_worktrees(
+    parent: &WorkspaceEntry,
+    state: &AppState,
+) -> Result<Vec<ExternalWorktree>, String> {
+    let parent_root = resolve_git_root(parent)?;
+    let output = run_git_command(&parent_root, &["worktree", "list", "--porcelain"]).await?;
+    let registered: Vec<PathBuf> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .map(|workspace| PathBuf::from(&workspace.path))
+        .collect();
+    Ok(parse_worktree_list(&output)
+        .into_iter()
+        .filter(|worktree| {
+            let path = Path::new(&worktree.path);
+            !same_path(path, &parent_root) && !registered.iter().any(|known| same_path(path, known))
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub(crate) async fn list_external_worktrees(
+    parent_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ExternalWorktree>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_external_worktrees",
+            json!({ "parentId": parent_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let parent = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&parent_id)
+            .cloned()
+            .ok_or("parent workspace not found")?
+    };
+    if parent.kind.is_worktree() {
+        return Err("Worktrees are listed from their main workspace.".to_string());
+    }
+    external_worktrees(&parent, &state).await
+}
+
+/// Register an existing worktree of `parent_id`'s repository as a worktree
+/// workspace, leaving it where it is on disk.
+#[tauri::command]
+pub(crate) async fn adopt_worktree(
+    parent_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        check_allowed_remote_workspace_path(&state, &path).await?;
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "adopt_worktree",
+            json!({ "parentId": parent_id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let parent = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&parent_id)
+            .cloned()
+            .ok_or("parent workspace not found")?
+    };
+    if parent.kind.is_worktree() {
+        return Err("Cannot adopt a worktree into another worktree.".to_string());
+    }
+    let worktree = external_worktrees(&parent, &state)
+        .await?
+        .into_iter()
+        .find(|worktree| same_path(Path::new(&worktree.path), Path::new(path.trim())))
+        .ok_or("Not an unregistered worktree of this workspace.")?;
+    if worktree.prunable || !Path::new(&worktree.path).is_dir() {
+        return Err("Worktree folder is missing.".to_string());
+    }
+    let branch = worktree
+        .branch
+        .ok_or("Worktree has a detached HEAD; check out a branch first.")?;
+    check_allowed_workspace_path(&state, Path::new(&worktree.path)).await?;
+
+    let entry = WorkspaceEntry {
+        id: Uuid::new_v4().to_string(),
+        name: branch.clone(),
+        path: worktree.path,
+        claude_bin: parent.claude_bin.clone(),
+        kind: WorkspaceKind::Worktree,
+        parent_id: Some(parent.id.clone()),
+        worktree: Some(WorktreeInfo { branch }),
+        settings: WorkspaceSettings::default(),
+    };
+
+    let default_bin = {
+        let settings = state.app_settings.lock().await;
+        settings.claude_bin.clone()
+    };
+    let session = spawn_workspace_session(entry.clone(), default_bin).await?;
+    {
+        let mut workspaces = state.workspaces.lock().await;
+        workspaces.insert(entry.id.clone(), entry.clone());
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(&state.storage_path, &list)?;
+    }
+    state
+        .sessions
+        .lock()
+        .await
+        .insert(entry.id.clone(), session);
+
+    ensure_workspace_thread_watcher(&entry.id, entry.clone(), &state, app).await;
+
+    Ok(WorkspaceInfo {
+        id: entry.id,
+        name: entry.name,
+        path: entry.path,
+        claude_bin: entry.claude_bin,
+        connected: true,
+        kind: entry.kind,
+        parent_id: entry.parent_id,
+        worktree: entry.worktree,
+        settings: entry.settings,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn remove_workspace(
     id: String,
@@ -845,8 +1207,18 @@ pub(crate) async fn remove_workspace(
     };
 
     stop_workspace_thread_watcher(&id, &state).await;
+    stop_startup_commands(&id, &state).await;
+    forget_file_index(&id);
+    state.file_watches.stop_workspace(&id).await;
+    state.git_status_cache.stop_workspace(&id);
+    state.thread_drafts.forget_workspace(&id).await;
     for child in &child_worktrees {
         stop_workspace_thread_watcher(&child.id, &state).await;
+        stop_startup_commands(&child.id, &state).await;
+        forget_file_index(&child.id);
+        state.file_watches.stop_workspace(&child.id).await;
+        state.git_status_cache.stop_workspace(&child.id);
+        state.thread_drafts.forget_workspace(&child.id).await;
     }
 
     let parent_path = PathBuf::from(&entry.path);
@@ -919,6 +1291,11 @@ pub(crate) async fn remove_worktree(
     };
 
     stop_workspace_thread_watcher(&entry.id, &state).await;
+    stop_startup_commands(&entry.id, &state).await;
+    forget_file_index(&entry.id);
+    state.file_watches.stop_workspace(&entry.id).await;
+    state.git_status_cache.stop_workspace(&entry.id);
+    state.thread_drafts.forget_workspace(&entry.id).await;
 
     if let Some(session) = state.sessions.lock().await.remove(&entry.id) {
         let _ = session.kill_all_persistent_sessions().await;
@@ -956,6 +1333,49 @@ pub(crate) async fn remove_worktree(
     Ok(())
 }
 
+/// Replace a connected workspace's session, killing its turns, so it runs from
+/// the entry's current path. Disconnected workspaces are left alone.
+async fn respawn_workspace_session(
+    entry: &WorkspaceEntry,
+    state: &AppState,
+    app: &AppHandle,
+    context: &str,
+) {
+    let Some(session) = state.sessions.lock().await.remove(&entry.id) else {
+        return;
+    };
+    // Kill the persistent session first
+    let _ = session.kill_all_persistent_sessions().await;
+    // Then kill any active turns
+    let mut active_turns = session.active_turns.lock().await;
+    let children = active_turns
+        .drain()
+        .map(|(_, active_turn)| active_turn.child)
+        .collect::<Vec<_>>();
+    drop(active_turns);
+    for child in children {
+        let mut guard = child.lock().await;
+        let _ = guard.kill().await;
+    }
+    let default_bin = {
+        let settings = state.app_settings.lock().await;
+        settings.claude_bin.clone()
+    };
+    match spawn_workspace_session(entry.clone(), default_bin).await {
+        Ok(session) => {
+            state
+                .sessions
+                .lock()
+                .await
+                .insert(entry.id.clone(), session);
+            ensure_workspace_thread_watcher(&entry.id, entry.clone(), state, app.clone()).await;
+        }
+        Err(error) => {
+            tracing::warn!("{context}: respawn failed for {}: {error}", entry.id);
+        }
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn rename_worktree(
     id: String,
@@ -1075,50 +1495,7 @@ pub(crate) async fn rename_worktree(
     };
     write_workspaces(&state.storage_path, &list)?;
 
-    let was_connected = state.sessions.lock().await.contains_key(&entry_snapshot.id);
-    if was_connected {
-        if let Some(session) = state.sessions.lock().await.remove(&entry_snapshot.id) {
-            // Kill the persistent session first
-            let _ = session.kill_all_persistent_sessions().await;
-            // Then kill any active turns
-            let mut active_turns = session.active_turns.lock().await;
-            let children = active_turns
-                .drain()
-                .map(|(_, active_turn)| active_turn.child)
-                .collect::<Vec<_>>();
-            drop(active_turns);
-            for child in children {
-                let mut guard = child.lock().await;
-                let _ = guard.kill().await;
-            }
-        }
-        let default_bin = {
-            let settings = state.app_settings.lock().await;
-            settings.claude_bin.clone()
-        };
-        match spawn_workspace_session(entry_snapshot.clone(), default_bin).await {
-            Ok(session) => {
-                state
-                    .sessions
-                    .lock()
-                    .await
-                    .insert(entry_snapshot.id.clone(), session);
-                ensure_workspace_thread_watcher(
-                    &entry_snapshot.id,
-                    entry_snapshot.clone(),
-                    &state,
-                    app.clone(),
-                )
-                .await;
-            }
-            Err(error) => {
-                eprintln!(
-                    "rename_worktree: respawn failed for {} after rename: {error}",
-                    entry_snapshot.id
-                );
-            }
-        }
-    }
+    respawn_workspace_session(&entry_snapshot, &state, &app, "rename_worktree").await;
 
     let connected = state.sessions.lock().await.contains_key(&entry_snapshot.id);
     Ok(WorkspaceInfo {
@@ -1365,14 +1742,171 @@ pub(crate) async fn apply_worktree_changes(
     Err(detail.to_string())
 }
 
+#[tauri::command]
+pub(crate) async fn rename_workspace(
+    id: String,
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_workspace",
+            json!({ "id": id, "name": name }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Workspace name is required.".to_string());
+    }
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get_mut(&id).ok_or("workspace not found")?;
+        if entry.kind.is_worktree() {
+            return Err("Use rename_worktree for worktree agents.".to_string());
+        }
+        entry.name = trimmed.to_string();
+        let snapshot = entry.clone();
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let connected = state.sessions.lock().await.contains_key(&id);
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        claude_bin: entry_snapshot.claude_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
+/// Point a workspace at the folder it was moved to on disk. Its Claude project
+/// dirs are moved along so existing threads stay listed and resumable, and a
+/// connected session is respawned in the new location. If the sessions or
+/// worktrees can't be carried over, the error is returned and the workspace
+/// keeps its old path.
+#[tauri::command]
+pub(crate) async fn move_workspace_path(
+    id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        check_allowed_remote_workspace_path(&state, &path).await?;
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "move_workspace_path",
+            json!({ "id": id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let next_path = path.trim().trim_end_matches(['/', '\\']).to_string();
+    if !PathBuf::from(&next_path).is_dir() {
+        return Err("Workspace path must be a folder.".to_string());
+    }
+    check_allowed_workspace_path(&state, Path::new(&next_path)).await?;
+
+    let (old_path, has_worktrees) = {
+        let workspaces = state.workspaces.lock().await;
+        if workspaces
+            .values()
+            .any(|workspace| workspace.id != id && workspace.path == next_path)
+        {
+            return Err("Another workspace already uses this folder.".to_string());
+        }
+        let has_worktrees = workspaces
+            .values()
+            .any(|workspace| workspace.parent_id.as_deref() == Some(id.as_str()));
+        let entry = workspaces.get(&id).ok_or("workspace not found")?;
+        if entry.kind.is_worktree() {
+            return Err("Worktrees are moved with rename_worktree.".to_string());
+        }
+        if entry.path == next_path {
+            return Err("Workspace path is unchanged.".to_string());
+        }
+        (entry.path.clone(), has_worktrees)
+    };
+
+    // Fix up everything that refers to the old location before recording the
+    // new one, so a failure leaves the workspace where it was. Both steps are
+    // safe to repeat when the move is retried.
+    if has_worktrees {
+        // Worktrees record their main repository's path; let git fix them up.
+        run_git_command(&PathBuf::from(&next_path), &["worktree", "repair"])
+            .await
+            .map_err(|error| format!("Failed to repair worktrees: {error}"))?;
+    }
+    let scoped_cwds: Vec<String> = workspace_thread_meta(&state, &id)
+        .into_values()
+        .filter_map(|meta| meta.cwd)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let moved = migrate_project_sessions(&old_path, &next_path, &scoped_cwds)
+        .map_err(|error| format!("Failed to move sessions: {error}"))?;
+    tracing::info!("move_workspace_path: moved {moved} session entries for {id} to {next_path}");
+
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get_mut(&id).ok_or("workspace not found")?;
+        entry.path = next_path.clone();
+        let snapshot = entry.clone();
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    forget_file_index(&id);
+    state.file_watches.stop_workspace(&id).await;
+    state.git_status_cache.stop_workspace(&id);
+
+    respawn_workspace_session(&entry_snapshot, &state, &app, "move_workspace_path").await;
+
+    let connected = state.sessions.lock().await.contains_key(&id);
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        claude_bin: entry_snapshot.claude_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn update_workspace_settings(
     id: String,
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
 ) -> Result<WorkspaceInfo, String> {
+    validate_redaction_rules(&settings.redaction_rules)?;
+    validate_thread_presets(&settings.thread_presets)?;
+    if let Some(container) = &settings.container {
+        validate_container_settings(container)?;
+    }
+    let allowed_roots = state.app_settings.lock().await.allowed_workspace_roots.clone();
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
+        let workspace_path = &workspaces.get(&id).ok_or("workspace not found")?.path;
+        validate_additional_dirs(workspace_path, &settings.additional_dirs, &allowed_roots)?;
         let entry_snapshot = apply_workspace_settings_update(&mut workspaces, &id, settings)?;
         let list: Vec<_> = workspaces.values().cloned().collect();
         (entry_snapshot, list)
@@ -1456,12 +1990,107 @@ pub(crate) async fn connect_workspace(
     ensure_workspace_thread_watcher(&entry.id, entry.clone(), &state, app.clone()).await;
     let event_sink = TauriEventSink::new(app.clone());
     event_sink.emit_app_server_event(AppServerEvent {
-        workspace_id: entry.id,
+        workspace_id: entry.id.clone(),
         message: json!({ "method": "claude/connected", "params": {} }),
     });
+    start_startup_commands(&entry, &state, &app).await;
     Ok(())
 }
 
+/// Workspaces flagged `autoConnect` that have no session yet, in sidebar order.
+fn auto_connect_targets(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    connected: &HashSet<String>,
+) -> Vec<WorkspaceEntry> {
+    let mut targets: Vec<WorkspaceEntry> = workspaces
+        .values()
+        .filter(|entry| entry.settings.auto_connect && !connected.contains(&entry.id))
+        .cloned()
+        .collect();
+    targets.sort_by(|a, b| {
+        a.settings
+            .sort_order
+            .unwrap_or(u32::MAX)
+            .cmp(&b.settings.sort_order.unwrap_or(u32::MAX))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    targets
+}
+
+/// Start sessions for `autoConnect` workspaces in parallel once the app is up.
+/// Each finished workspace emits `workspace/autoConnectProgress`; successful
+/// ones also emit `claude/connected` like `connect_workspace`.
+pub(crate) fn spawn_auto_connect(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if remote_backend::is_remote_mode(&*state).await {
+            return;
+        }
+        let targets = {
+            let workspaces = state.workspaces.lock().await;
+            let sessions = state.sessions.lock().await;
+            let connected: HashSet<String> = sessions.keys().cloned().collect();
+            auto_connect_targets(&workspaces, &connected)
+        };
+        if targets.is_empty() {
+            return;
+        }
+        let default_bin = state.app_settings.lock().await.claude_bin.clone();
+        let total = targets.len();
+        let mut tasks = tokio::task::JoinSet::new();
+        for entry in targets {
+            let default_bin = default_bin.clone();
+            tasks.spawn(async move {
+                let result = spawn_workspace_session(entry.clone(), default_bin).await;
+                (entry, result)
+            });
+        }
+        let event_sink = TauriEventSink::new(app.clone());
+        let mut completed = 0;
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((entry, result)) = joined else {
+                continue;
+            };
+            completed += 1;
+            let error = match result {
+                Ok(session) => {
+                    // A manual connect that finished first keeps its session.
+                    state
+                        .sessions
+                        .lock()
+                        .await
+                        .entry(entry.id.clone())
+                        .or_insert(session);
+                    ensure_workspace_thread_watcher(&entry.id, entry.clone(), &state, app.clone())
+                        .await;
+                    event_sink.emit_app_server_event(AppServerEvent {
+                        workspace_id: entry.id.clone(),
+                        message: json!({ "method": "claude/connected", "params": {} }),
+                    });
+                    start_startup_commands(&entry, &state, &app).await;
+                    None
+                }
+                Err(error) => {
+                    tracing::warn!("[workspaces] Auto-connect failed for {}: {error}", entry.name);
+                    Some(error)
+                }
+            };
+            event_sink.emit_app_server_event(AppServerEvent {
+                workspace_id: entry.id.clone(),
+                message: json!({
+                    "method": "workspace/autoConnectProgress",
+                    "params": {
+                        "completed": completed,
+                        "total": total,
+                        "ok": error.is_none(),
+                        "error": error,
+                    },
+                }),
+            });
+        }
+    });
+}
+
 #[tauri::command]
 pub(crate) async fn list_workspace_files(
     workspace_id: String,
@@ -1511,7 +2140,8 @@ mod tests {
     use std::path::PathBuf;
 
     use super::{
-        apply_workspace_settings_update, build_clone_destination_path, sanitize_clone_dir_name,
+        apply_workspace_settings_update, build_clone_destination_path,
+        ensure_allowed_workspace_path, parse_worktree_list, sanitize_clone_dir_name,
         sanitize_worktree_name, sort_workspaces,
     };
     use crate::storage::{read_workspaces, write_workspaces};
@@ -1552,6 +2182,14 @@ mod tests {
                 sort_order,
                 group_id: None,
                 git_root: None,
+                redaction_rules: Vec::new(),
+                auto_connect: false,
+                additional_dirs: Vec::new(),
+                startup_commands: Vec::new(),
+                run_startup_commands: false,
+                container: None,
+                web_allowed_domains: None,
+                thread_presets: Vec::new(),
             },
         }
     }
@@ -1682,6 +2320,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn validate_additional_dirs_requires_existing_directories() {
+        let root = std::env::temp_dir().join(format!("additional-dirs-{}", Uuid::new_v4()));
+        let workspace = root.join("app");
+        std::fs::create_dir_all(root.join("shared")).unwrap();
+        std::fs::create_dir_all(&workspace).unwrap();
+        let workspace_path = workspace.to_string_lossy().to_string();
+        let shared = root.join("shared").to_string_lossy().to_string();
+
+        let dirs = vec!["../shared".to_string(), shared];
+        assert!(validate_additional_dirs(&workspace_path, &dirs, &[]).is_ok());
+        assert!(validate_additional_dirs(&workspace_path, &["../missing".to_string()], &[]).is_err());
+        assert!(validate_additional_dirs(&workspace_path, &[" ".to_string()], &[]).is_err());
+
+        let roots = vec![root.to_string_lossy().to_string()];
+        assert!(validate_additional_dirs(&workspace_path, &dirs, &roots).is_ok());
+        let workspace_only = vec![workspace_path.clone()];
+        let error = validate_additional_dirs(&workspace_path, &dirs, &workspace_only).unwrap_err();
+        assert!(error.contains("outside the allowed workspace roots"));
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn auto_connect_targets_skip_unflagged_and_connected() {
+        let entry = |id: &str, sort_order: Option<u32>, auto_connect: bool| WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: "/tmp".to_string(),
+            claude_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                sort_order,
+                auto_connect,
+                ..WorkspaceSettings::default()
+            },
+        };
+        let workspaces: HashMap<String, WorkspaceEntry> = [
+            entry("late", None, true),
+            entry("first", Some(1), true),
+            entry("manual", Some(0), false),
+            entry("live", Some(2), true),
+        ]
+        .into_iter()
+        .map(|entry| (entry.id.clone(), entry))
+        .collect();
+        let connected = HashSet::from(["live".to_string()]);
+        let ids: Vec<String> = auto_connect_targets(&workspaces, &connected)
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(ids, vec!["first", "late"]);
+    }
+
     #[test]
     fn update_workspace_settings_persists_sort_and_group() {
         let id = "workspace-1".to_string();
@@ -1724,4 +2417,49 @@ mod tests {
         assert!(stored.settings.sidebar_collapsed);
         assert_eq!(stored.settings.git_root.as_deref(), Some("/tmp"));
     }
+
+    #[test]
+    fn allowed_roots_restrict_workspace_paths() {
+        let base = std::env::temp_dir().join(format!("allowed-roots-{}", Uuid::new_v4()));
+        let allowed = base.join("projects");
+        let inside = allowed.join("app");
+        let outside = base.join("secrets");
+        std::fs::create_dir_all(&inside).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        let roots = vec![allowed.to_string_lossy().to_string()];
+
+        assert!(ensure_allowed_workspace_path(&outside, &[]).is_ok());
+        assert!(ensure_allowed_workspace_path(&inside, &roots).is_ok());
+        assert!(ensure_allowed_workspace_path(&outside, &roots).is_err());
+        assert!(ensure_allowed_workspace_path(&inside.join("../../secrets"), &roots).is_err());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn remote_paths_are_checked_lexically_against_allowed_roots() {
+        let roots = vec!["/srv/projects".to_string()];
+        let check = |path: &str| ensure_allowed_remote_workspace_path(Path::new(path), &roots);
+        assert!(ensure_allowed_remote_workspace_path(Path::new("/etc"), &[]).is_ok());
+        assert!(check("/srv/projects/app").is_ok());
+        assert!(check("/srv/projects-old/app").is_err());
+        assert!(check("/srv/projects/../secrets").is_err());
+        assert!(check("projects/app").is_err());
+    }
+
+    #[test]
+    fn parse_worktree_list_reads_porcelain_records() {
+        let output = "worktree /repo\nHEAD aaa\nbranch refs/heads/main\n\n\
+worktree /repo.git\nbare\n\n\
+worktree /tmp/feature\nHEAD bbb\nbranch refs/heads/feature/login\nlocked in use\n\n\
+worktree /tmp/gone\nHEAD ccc\ndetached\nprunable gitdir file points to non-existent location\n";
+        let worktrees = parse_worktree_list(output);
+        assert_eq!(worktrees.len(), 3);
+        assert_eq!(worktrees[0].path, "/repo");
+        assert_eq!(worktrees[1].branch.as_deref(), Some("feature/login"));
+        assert!(worktrees[1].locked);
+        assert_eq!(worktrees[2].branch, None);
+        assert_eq!(worktrees[2].head.as_deref(), Some("ccc"));
+        assert!(worktrees[2].prunable);
+    }
 }
@@ -73,6 +73,8 @@ import { useClonePrompt } from "./features/workspaces/hooks/useClonePrompt";
 import { useWorkspaceController } from "./features/app/hooks/useWorkspaceController";
 import { useWorkspaceSelection } from "./features/workspaces/hooks/useWorkspaceSelection";
 import { useLocalUsage } from "./features/home/hooks/useLocalUsage";
+import { useRecentThreads } from "./features/home/hooks/useRecentThreads";
+import { usePresence } from "./features/collaboration/hooks/usePresence";
 import { useClaudeTasks } from "./features/plan/hooks/useClaudeTasks";
 import { useWorkspaceHome } from "./features/workspaces/hooks/useWorkspaceHome";
 import { useWorkspaceClaudeMd } from "./features/workspaces/hooks/useWorkspaceClaudeMd";
@@ -560,7 +562,9 @@ function MainApp() {
     activeThreadId,
     activeItems,
     permissionDenials,
+    approvalRequests,
     userInputRequests,
+    planProposals,
     threadsByWorkspace,
     threadParentById,
     threadStatusById,
@@ -593,7 +597,11 @@ function MainApp() {
     handlePermissionRemember,
     handlePermissionRetry,
     handlePermissionDismiss,
+    handleApprovalDecision,
+    handleApprovalRemember,
     handleUserInputSubmit,
+    handlePlanApprove,
+    handlePlanReject,
   } = useThreads({
     activeWorkspace,
     onWorkspaceConnected: markWorkspaceConnected,
@@ -884,6 +892,20 @@ function MainApp() {
     error: localUsageError,
     refresh: refreshLocalUsage,
   } = useLocalUsage(showHome);
+  const connectedWorkspaceKey = useMemo(
+    () =>
+      workspaces
+        .filter((workspace) => workspace.connected)
+        .map((workspace) => workspace.id)
+        .join(","),
+    [workspaces],
+  );
+  const {
+    threads: recentThreads,
+    isLoading: isLoadingRecentThreads,
+    hasMore: hasMoreRecentThreads,
+    loadMore: loadMoreRecentThreads,
+  } = useRecentThreads(showHome, connectedWorkspaceKey);
   const canInterrupt = activeThreadId
     ? threadStatusById[activeThreadId]?.isProcessing ?? false
     : false;
@@ -945,6 +967,16 @@ function MainApp() {
     textareaRef: composerInputRef,
   });
 
+  const { label: presenceViewersLabel, error: presenceError } = usePresence({
+    enabled: appSettings.backendMode === "remote" && appSettings.presenceSharingEnabled,
+    workspaceId: activeWorkspaceId,
+    threadId: activeThreadId,
+    draftText: activeDraft,
+  });
+  const presenceLabel = presenceError
+    ? `Presence sharing stopped: ${presenceError}`
+    : presenceViewersLabel;
+
   const {
     commitMessage,
     commitMessageLoading,
@@ -962,6 +994,8 @@ function MainApp() {
     onCommitAndSync: handleCommitAndSync,
     onPush: handlePush,
     onSync: handleSync,
+    onAmendCommit: handleAmendCommit,
+    onUndoLastCommit: handleUndoLastCommit,
   } = useGitCommitController({
     activeWorkspace,
     activeWorkspaceId,
@@ -1132,6 +1166,7 @@ function MainApp() {
   useWorkspaceRestore({
     workspaces,
     hasLoaded,
+    activeWorkspaceId,
     connectWorkspace,
     listThreadsForWorkspace
   });
@@ -1329,6 +1364,7 @@ function MainApp() {
     onOpenSettings: () => openSettings(),
     onCycleAgent: handleCycleAgent,
     onCycleWorkspace: handleCycleWorkspace,
+    onOpenWorkspace: selectWorkspace,
     onToggleDebug: handleDebugClick,
     onToggleTerminal: handleToggleTerminal,
     sidebarCollapsed,
@@ -1399,7 +1435,13 @@ function MainApp() {
     handlePermissionRemember,
     handlePermissionRetry,
     handlePermissionDismiss,
+    approvalRequests,
+    handleApprovalDecision,
+    handleApprovalRemember,
     handleUserInputSubmit,
+    planProposals,
+    handlePlanApprove,
+    handlePlanReject,
     onForkThreadFromMessage: forkThreadFromMessage,
     onRewindThreadToMessage: rewindThreadToMessage,
     onForkAndRewindThread: forkAndRewindThread,
@@ -1444,7 +1486,7 @@ function MainApp() {
     },
     onDeleteThread: (workspaceId, threadId) => {
       removeThread(workspaceId, threadId);
-      clearDraftForThread(threadId);
+      clearDraftForThread(workspaceId, threadId);
       removeImagesForThread(threadId);
     },
     onSyncThread: (workspaceId, threadId) => {
@@ -1482,6 +1524,10 @@ function MainApp() {
     onDismissUpdate: dismissUpdate,
     latestAgentRuns,
     isLoadingLatestAgents,
+    recentThreads,
+    isLoadingRecentThreads,
+    hasMoreRecentThreads,
+    onLoadMoreRecentThreads: loadMoreRecentThreads,
     localUsageSnapshot,
     isLoadingLocalUsage,
     localUsageError,
@@ -1614,6 +1660,8 @@ function MainApp() {
     onCommitAndSync: handleCommitAndSync,
     onPush: handlePush,
     onSync: handleSync,
+    onAmendCommit: handleAmendCommit,
+    onUndoLastCommit: handleUndoLastCommit,
     commitLoading,
     pushLoading,
     syncLoading,
@@ -1689,6 +1737,7 @@ function MainApp() {
     onDismissDictationError: clearDictationError,
     dictationHint,
     onDismissDictationHint: clearDictationHint,
+    presenceLabel,
     composerSendLabel,
     showComposer,
     plan: activePlan,
@@ -1862,6 +1911,16 @@ function MainApp() {
           onMoveWorkspaceGroup: moveWorkspaceGroup,
           onDeleteWorkspaceGroup: deleteWorkspaceGroup,
           onAssignWorkspaceGroup: assignWorkspaceGroup,
+          onToggleWorkspaceAutoConnect: (workspaceId, enabled) => {
+            const target = workspacesById.get(workspaceId);
+            if (!target) {
+              return;
+            }
+            void updateWorkspaceSettings(workspaceId, {
+              ...target.settings,
+              autoConnect: enabled,
+            });
+          },
           reduceTransparency,
           onToggleTransparency: setReduceTransparency,
           appSettings,
@@ -1879,6 +1938,12 @@ function MainApp() {
           onDownloadDictationModel: dictationModel.download,
           onCancelDictationDownload: dictationModel.cancel,
           onRemoveDictationModel: dictationModel.remove,
+          onSettingsImported: (summary) => {
+            setAppSettings((prev) => ({ ...prev, ...summary.settings }));
+            if (summary.workspacesImported > 0) {
+              void refreshWorkspaces();
+            }
+          },
         }}
       />
     </div>
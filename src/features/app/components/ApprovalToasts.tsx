@@ -1,5 +1,10 @@
 import { useMemo } from "react";
-import type { PermissionDenial, WorkspaceInfo } from "../../../types";
+import type {
+  PermissionDenial,
+  ToolApprovalDecision,
+  ToolApprovalRequest,
+  WorkspaceInfo,
+} from "../../../types";
 import type { ApprovalRuleInfo } from "../../../utils/approvalRules";
 import { getApprovalRuleInfo } from "../../../utils/approvalRules";
 
@@ -9,6 +14,12 @@ type ApprovalToastsProps = {
   onPermissionRemember?: (denial: PermissionDenial, ruleInfo: ApprovalRuleInfo) => void;
   onPermissionRetry?: (denial: PermissionDenial, ruleInfo: ApprovalRuleInfo) => void;
   onPermissionDismiss?: (denial: PermissionDenial) => void;
+  approvalRequests?: ToolApprovalRequest[];
+  onApprovalDecision?: (
+    request: ToolApprovalRequest,
+    decision: ToolApprovalDecision,
+  ) => void;
+  onApprovalRemember?: (request: ToolApprovalRequest, ruleInfo: ApprovalRuleInfo) => void;
 };
 
 export function ApprovalToasts({
@@ -17,6 +28,9 @@ export function ApprovalToasts({
   onPermissionRemember,
   onPermissionRetry,
   onPermissionDismiss,
+  approvalRequests,
+  onApprovalDecision,
+  onApprovalRemember,
 }: ApprovalToastsProps) {
   const workspaceLabels = useMemo(
     () => new Map(workspaces.map((workspace) => [workspace.id, workspace.name])),
@@ -24,8 +38,9 @@ export function ApprovalToasts({
   );
 
   const denials = permissionDenials ?? [];
+  const requests = approvalRequests ?? [];
 
-  if (!denials.length) {
+  if (!denials.length && !requests.length) {
     return null;
   }
 
@@ -51,8 +66,68 @@ export function ApprovalToasts({
     return { text: JSON.stringify(value, null, 2), isCode: true };
   };
 
+  const renderInputEntries = (inputEntries: [string, unknown][]) =>
+    inputEntries.map(([key, value]) => {
+      const rendered = renderParamValue(value);
+      return (
+        <div key={key} className="approval-toast-detail">
+          <div className="approval-toast-detail-label">{formatLabel(key)}</div>
+          {rendered.isCode ? (
+            <pre className="approval-toast-detail-code">{rendered.text}</pre>
+          ) : (
+            <div className="approval-toast-detail-value">{rendered.text}</div>
+          )}
+        </div>
+      );
+    });
+
   return (
     <div className="approval-toasts" role="region" aria-live="assertive">
+      {requests.map((request) => {
+        const workspaceName = workspaceLabels.get(request.workspace_id);
+        const toolInput = request.tool_input ?? {};
+        const ruleInfo = getApprovalRuleInfo({
+          ...toolInput,
+          tool_name: request.tool_name,
+        });
+        return (
+          <div key={request.id} className="approval-toast" role="alert">
+            <div className="approval-toast-header">
+              <div className="approval-toast-title">Approval requested</div>
+              {workspaceName ? (
+                <div className="approval-toast-workspace">{workspaceName}</div>
+              ) : null}
+            </div>
+            <div className="approval-toast-method">{request.tool_name}</div>
+            <div className="approval-toast-details">
+              {renderInputEntries(Object.entries(toolInput))}
+            </div>
+            <div className="approval-toast-actions">
+              <button
+                className="secondary"
+                onClick={() => onApprovalDecision?.(request, "decline")}
+              >
+                Deny
+              </button>
+              {ruleInfo && onApprovalRemember ? (
+                <button
+                  className="ghost approval-toast-remember"
+                  onClick={() => onApprovalRemember(request, ruleInfo)}
+                  title={ruleInfo.label}
+                >
+                  Always allow
+                </button>
+              ) : null}
+              <button
+                className="primary"
+                onClick={() => onApprovalDecision?.(request, "accept")}
+              >
+                Allow
+              </button>
+            </div>
+          </div>
+        );
+      })}
       {denials.map((denial) => {
         const workspaceName = workspaceLabels.get(denial.workspace_id);
         const toolInput = denial.tool_input ?? {};
@@ -82,27 +157,7 @@ export function ApprovalToasts({
                   Add to settings.local.json to allow.
                 </div>
               </div>
-              {inputEntries.length
-                ? inputEntries.map(([key, value]) => {
-                    const rendered = renderParamValue(value);
-                    return (
-                      <div key={key} className="approval-toast-detail">
-                        <div className="approval-toast-detail-label">
-                          {formatLabel(key)}
-                        </div>
-                        {rendered.isCode ? (
-                          <pre className="approval-toast-detail-code">
-                            {rendered.text}
-                          </pre>
-                        ) : (
-                          <div className="approval-toast-detail-value">
-                            {rendered.text}
-                          </div>
-                        )}
-                      </div>
-                    );
-                  })
-                : null}
+              {inputEntries.length ? renderInputEntries(inputEntries) : null}
             </div>
             <div className="approval-toast-actions">
               <button
@@ -0,0 +1,68 @@
+import { useEffect, useMemo, useState } from "react";
+import type { PlanProposal } from "../../../types";
+import { Markdown } from "../../messages/components/Markdown";
+
+type PlanApprovalMessageProps = {
+  proposals: PlanProposal[];
+  activeThreadId: string | null;
+  activeWorkspaceId?: string | null;
+  onApprove: (proposal: PlanProposal) => void;
+  onReject: (proposal: PlanProposal, feedback: string) => void;
+};
+
+export function PlanApprovalMessage({
+  proposals,
+  activeThreadId,
+  activeWorkspaceId,
+  onApprove,
+  onReject,
+}: PlanApprovalMessageProps) {
+  const activeProposal = useMemo(
+    () =>
+      proposals.find(
+        (proposal) =>
+          proposal.threadId === activeThreadId &&
+          (!activeWorkspaceId || proposal.workspaceId === activeWorkspaceId),
+      ) ?? null,
+    [proposals, activeThreadId, activeWorkspaceId],
+  );
+  const [feedback, setFeedback] = useState("");
+
+  useEffect(() => {
+    setFeedback("");
+  }, [activeProposal?.toolUseId]);
+
+  if (!activeProposal) {
+    return null;
+  }
+
+  return (
+    <div className="message request-user-input-message">
+      <div
+        className="bubble request-user-input-card plan-approval-card"
+        role="group"
+        aria-label="Plan approval requested"
+      >
+        <div className="request-user-input-header">
+          <div className="request-user-input-title">Plan ready for review</div>
+        </div>
+        <div className="request-user-input-body">
+          <Markdown value={activeProposal.plan} className="markdown plan-approval-plan" />
+          <textarea
+            className="request-user-input-notes"
+            placeholder="Feedback for the agent if you reject (optional)"
+            value={feedback}
+            onChange={(event) => setFeedback(event.target.value)}
+            rows={2}
+          />
+        </div>
+        <div className="request-user-input-actions">
+          <button onClick={() => onReject(activeProposal, feedback)}>Keep planning</button>
+          <button className="primary" onClick={() => onApprove(activeProposal)}>
+            Approve plan
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -166,6 +166,12 @@ export function Sidebar({
     COLLAPSED_GROUPS_STORAGE_KEY,
   );
   const { getThreadRows } = useThreadRows(threadParentById);
+  const getThreadTags = useCallback(
+    (workspaceId: string, threadId: string) =>
+      threadsByWorkspace[workspaceId]?.find((thread) => thread.id === threadId)
+        ?.tags ?? [],
+    [threadsByWorkspace],
+  );
   const { showThreadMenu, showWorkspaceMenu, showWorktreeMenu } =
     useSidebarMenus({
       onDeleteThread,
@@ -173,6 +179,7 @@ export function Sidebar({
       onPinThread: pinThread,
       onUnpinThread: unpinThread,
       isThreadPinned,
+      getThreadTags,
       onRenameThread,
       onReloadWorkspaceThreads,
       onDeleteWorkspace,
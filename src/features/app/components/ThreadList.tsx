@@ -208,6 +208,15 @@ export function ThreadList({
         <span className={`thread-status ${statusClass}`} aria-hidden />
         {isPinned && <span className="thread-pin-icon" aria-label="Pinned">📌</span>}
         <span className="thread-name">{thread.name}</span>
+        {thread.tags && thread.tags.length > 0 && (
+          <span className="thread-tags">
+            {thread.tags.map((tag) => (
+              <span key={tag} className="thread-tag">
+                {tag}
+              </span>
+            ))}
+          </span>
+        )}
         <div className="thread-meta">
           {relativeTime && <span className="thread-time">{relativeTime}</span>}
           <div className="thread-menu">
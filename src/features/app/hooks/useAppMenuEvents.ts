@@ -10,6 +10,7 @@ import {
   subscribeMenuNextAgent,
   subscribeMenuPrevWorkspace,
   subscribeMenuNextWorkspace,
+  subscribeMenuOpenWorkspace,
   subscribeMenuToggleDebugPanel,
   subscribeMenuToggleGitSidebar,
   subscribeMenuToggleProjectsSidebar,
@@ -27,6 +28,7 @@ type Params = {
   onOpenSettings: () => void;
   onCycleAgent: (direction: "next" | "prev") => void;
   onCycleWorkspace: (direction: "next" | "prev") => void;
+  onOpenWorkspace: (workspaceId: string) => void;
   onToggleDebug: () => void;
   onToggleTerminal: () => void;
   sidebarCollapsed: boolean;
@@ -47,6 +49,7 @@ export function useAppMenuEvents({
   onOpenSettings,
   onCycleAgent,
   onCycleWorkspace,
+  onOpenWorkspace,
   onToggleDebug,
   onToggleTerminal,
   sidebarCollapsed,
@@ -101,6 +104,10 @@ export function useAppMenuEvents({
     onCycleWorkspace("prev");
   });
 
+  useTauriEvent(subscribeMenuOpenWorkspace, (workspaceId) => {
+    onOpenWorkspace(workspaceId);
+  });
+
   useTauriEvent(subscribeMenuToggleDebugPanel, () => {
     onToggleDebug();
   });
@@ -187,6 +187,37 @@ describe("useAppServerEvents", () => {
     expect(unlisten).toHaveBeenCalledTimes(1);
   });
 
+  it("routes subagent links for tool items", async () => {
+    const handlers: Handlers = {
+      onItemSubagentLinked: vi.fn(),
+    };
+    const { root } = await mount(handlers);
+
+    act(() => {
+      listener?.({
+        workspace_id: "ws-1",
+        message: {
+          method: "item/updated",
+          params: {
+            threadId: "thread-1",
+            itemId: "toolu_1",
+            subagentThreadId: "thread-1::subagent::agent-a",
+          },
+        },
+      });
+    });
+    expect(handlers.onItemSubagentLinked).toHaveBeenCalledWith(
+      "ws-1",
+      "thread-1",
+      "toolu_1",
+      "thread-1::subagent::agent-a",
+    );
+
+    await act(async () => {
+      root.unmount();
+    });
+  });
+
   it("normalizes request user input questions and options", async () => {
     const handlers: Handlers = {
       onRequestUserInput: vi.fn(),
@@ -257,6 +288,45 @@ describe("useAppServerEvents", () => {
     });
   });
 
+  it("routes turn queue events with their position", async () => {
+    const handlers: Handlers = {
+      onTurnQueued: vi.fn(),
+      onTurnDequeued: vi.fn(),
+    };
+    const { root } = await mount(handlers);
+
+    act(() => {
+      listener?.({
+        workspace_id: "ws-1",
+        message: {
+          method: "turn/queued",
+          params: { threadId: "thread-1", turnId: "turn-1", position: 2 },
+        },
+      });
+    });
+    expect(handlers.onTurnQueued).toHaveBeenCalledWith("ws-1", "thread-1", "turn-1", 2);
+
+    act(() => {
+      listener?.({
+        workspace_id: "ws-1",
+        message: {
+          method: "turn/dequeued",
+          params: { threadId: "thread-1", turnId: "turn-1", reason: "cancelled" },
+        },
+      });
+    });
+    expect(handlers.onTurnDequeued).toHaveBeenCalledWith(
+      "ws-1",
+      "thread-1",
+      "turn-1",
+      "cancelled",
+    );
+
+    await act(async () => {
+      root.unmount();
+    });
+  });
+
   it("ignores delta events missing required fields", async () => {
     const handlers: Handlers = {
       onAgentMessageDelta: vi.fn(),
@@ -1,5 +1,11 @@
 import { useEffect } from "react";
-import type { AppServerEvent, PermissionDenial, RequestUserInputRequest } from "../../../types";
+import type {
+  AppServerEvent,
+  PermissionDenial,
+  PlanProposal,
+  RequestUserInputRequest,
+  ToolApprovalRequest,
+} from "../../../types";
 import { subscribeAppServerEvents } from "../../../services/events";
 
 type AgentDelta = {
@@ -26,6 +32,10 @@ type AppServerEventHandlers = {
     denials: PermissionDenial[];
   }) => void;
   onRequestUserInput?: (request: RequestUserInputRequest) => void;
+  onPlanProposed?: (proposal: PlanProposal) => void;
+  onApprovalRequest?: (request: ToolApprovalRequest) => void;
+  onApprovalCancelled?: (workspaceId: string, threadId: string, toolUseId: string) => void;
+  onPlanResolved?: (workspaceId: string, threadId: string, toolUseId: string) => void;
   onAgentMessageDelta?: (event: AgentDelta) => void;
   onAgentMessageStarted?: (event: {
     workspaceId: string;
@@ -37,6 +47,18 @@ type AppServerEventHandlers = {
   onAppServerEvent?: (event: AppServerEvent) => void;
   onTurnStarted?: (workspaceId: string, threadId: string, turnId: string) => void;
   onTurnCompleted?: (workspaceId: string, threadId: string, turnId: string) => void;
+  onTurnQueued?: (
+    workspaceId: string,
+    threadId: string,
+    turnId: string,
+    position: number,
+  ) => void;
+  onTurnDequeued?: (
+    workspaceId: string,
+    threadId: string,
+    turnId: string,
+    reason: "started" | "cancelled",
+  ) => void;
   onContextCompacted?: (workspaceId: string, threadId: string, turnId: string) => void;
   onTurnError?: (
     workspaceId: string,
@@ -53,6 +75,12 @@ type AppServerEventHandlers = {
   onThreadCreated?: (workspaceId: string, thread: Record<string, unknown>) => void;
   onItemStarted?: (workspaceId: string, threadId: string, item: Record<string, unknown>) => void;
   onItemCompleted?: (workspaceId: string, threadId: string, item: Record<string, unknown>) => void;
+  onItemSubagentLinked?: (
+    workspaceId: string,
+    threadId: string,
+    itemId: string,
+    subagentThreadId: string,
+  ) => void;
   onReasoningSummaryDelta?: (workspaceId: string, threadId: string, itemId: string, delta: string) => void;
   onReasoningSummaryBoundary?: (workspaceId: string, threadId: string, itemId: string) => void;
   onReasoningTextDelta?: (workspaceId: string, threadId: string, itemId: string, delta: string) => void;
@@ -190,6 +218,28 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "turn/queued") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const turnId = String(params.turnId ?? params.turn_id ?? "");
+        const position = Number(params.position ?? 0);
+        if (threadId && turnId && position > 0) {
+          handlers.onTurnQueued?.(workspace_id, threadId, turnId, position);
+        }
+        return;
+      }
+
+      if (method === "turn/dequeued") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const turnId = String(params.turnId ?? params.turn_id ?? "");
+        const reason = params.reason === "cancelled" ? "cancelled" : "started";
+        if (threadId && turnId) {
+          handlers.onTurnDequeued?.(workspace_id, threadId, turnId, reason);
+        }
+        return;
+      }
+
       if (method === "thread/compacted") {
         const params = message.params as Record<string, unknown>;
         const threadId = String(params.threadId ?? params.thread_id ?? "");
@@ -258,6 +308,67 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "item/tool/requestApproval") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const toolUseId = String(params.toolUseId ?? params.tool_use_id ?? "");
+        const toolName = String(params.toolName ?? params.tool_name ?? "").trim();
+        const toolInputValue = params.toolInput ?? params.tool_input ?? null;
+        if (threadId && toolUseId && toolName) {
+          handlers.onApprovalRequest?.({
+            id: toolUseId,
+            workspace_id,
+            thread_id: threadId,
+            turn_id: String(params.turnId ?? params.turn_id ?? ""),
+            tool_name: toolName,
+            tool_use_id: toolUseId,
+            tool_input:
+              toolInputValue && typeof toolInputValue === "object"
+                ? (toolInputValue as Record<string, unknown>)
+                : null,
+          });
+        }
+        return;
+      }
+
+      if (method === "item/tool/approvalCancelled") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const toolUseId = String(params.toolUseId ?? params.tool_use_id ?? "");
+        if (toolUseId) {
+          handlers.onApprovalCancelled?.(workspace_id, threadId, toolUseId);
+        }
+        return;
+      }
+
+      if (method === "plan/proposed") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const toolUseId = String(params.toolUseId ?? params.tool_use_id ?? "");
+        const plan = String(params.plan ?? "");
+        if (threadId && toolUseId && plan) {
+          handlers.onPlanProposed?.({
+            workspaceId: workspace_id,
+            threadId,
+            turnId: String(params.turnId ?? params.turn_id ?? ""),
+            itemId: String(params.itemId ?? params.item_id ?? toolUseId),
+            toolUseId,
+            plan,
+          });
+        }
+        return;
+      }
+
+      if (method === "plan/resolved") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const toolUseId = String(params.toolUseId ?? params.tool_use_id ?? "");
+        if (threadId && toolUseId) {
+          handlers.onPlanResolved?.(workspace_id, threadId, toolUseId);
+        }
+        return;
+      }
+
       if (method === "turn/plan/updated") {
         const params = message.params as Record<string, unknown>;
         const threadId = String(params.threadId ?? params.thread_id ?? "");
@@ -329,6 +440,19 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "item/updated") {
+        const params = message.params as Record<string, unknown>;
+        const threadId = String(params.threadId ?? params.thread_id ?? "");
+        const itemId = String(params.itemId ?? params.item_id ?? "");
+        const subagentThreadId = String(
+          params.subagentThreadId ?? params.subagent_thread_id ?? "",
+        );
+        if (threadId && itemId && subagentThreadId) {
+          handlers.onItemSubagentLinked?.(workspace_id, threadId, itemId, subagentThreadId);
+        }
+        return;
+      }
+
       if (method === "item/started") {
         const params = message.params as Record<string, unknown>;
         const threadId = String(params.threadId ?? params.thread_id ?? "");
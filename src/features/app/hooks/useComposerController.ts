@@ -1,6 +1,8 @@
 import { useCallback, useMemo, useState } from "react";
-import type { QueuedMessage, WorkspaceInfo } from "../../../types";
+import type { QueuedMessage, ThreadDraft, WorkspaceInfo } from "../../../types";
+import { saveThreadDraft } from "../../../services/tauri";
 import { useComposerImages } from "../../composer/hooks/useComposerImages";
+import { usePersistedThreadDrafts } from "../../composer/hooks/usePersistedThreadDrafts";
 import { useQueuedSend } from "../../threads/hooks/useQueuedSend";
 
 export function useComposerController({
@@ -98,6 +100,27 @@ export function useComposerController({
     [activeThreadId],
   );
 
+  const restoreDraft = useCallback(
+    (threadId: string, draft: ThreadDraft) => {
+      // Anything typed while the stored draft was loading wins.
+      setComposerDraftsByThread((prev) =>
+        prev[threadId] ? prev : { ...prev, [threadId]: draft.text },
+      );
+      if (draft.attachments.length > 0) {
+        setImagesForThread(threadId, draft.attachments);
+      }
+    },
+    [setImagesForThread],
+  );
+
+  usePersistedThreadDrafts({
+    activeWorkspaceId,
+    activeThreadId,
+    draftText: activeDraft,
+    attachments: activeImages,
+    onRestore: restoreDraft,
+  });
+
   const handleSendPrompt = useCallback(
     (text: string) => {
       if (!text.trim()) {
@@ -130,7 +153,8 @@ export function useComposerController({
     [activeThreadId, removeQueuedMessage],
   );
 
-  const clearDraftForThread = useCallback((threadId: string) => {
+  const clearDraftForThread = useCallback((workspaceId: string, threadId: string) => {
+    void saveThreadDraft(workspaceId, threadId, "", []).catch(() => {});
     setComposerDraftsByThread((prev) => {
       if (!(threadId in prev)) {
         return prev;
@@ -1,11 +1,13 @@
 import { useCallback, useEffect, useMemo, useState, type RefObject } from "react";
 import type { WorkspaceInfo } from "../../../types";
 import {
+  amendGitCommit,
   commitGit,
   generateCommitMessage,
   pushGit,
   stageGitAll,
   syncGit,
+  undoLastCommit,
 } from "../../../services/tauri";
 import { shouldApplyCommitMessage } from "../../../utils/commitMessage";
 import { useGitStatus } from "../../git/hooks/useGitStatus";
@@ -39,6 +41,8 @@ type GitCommitController = {
   onCommitAndSync: () => Promise<void>;
   onPush: () => Promise<void>;
   onSync: () => Promise<void>;
+  onAmendCommit: (force?: boolean) => Promise<void>;
+  onUndoLastCommit: (force?: boolean) => Promise<void>;
 };
 
 export function useGitCommitController({
@@ -237,6 +241,51 @@ export function useGitCommitController({
     refreshGitStatus,
   ]);
 
+  const handleAmendCommit = useCallback(
+    async (force = false) => {
+      if (!activeWorkspace || commitLoading) {
+        return;
+      }
+      setCommitLoading(true);
+      setCommitError(null);
+      try {
+        // A message in the commit box rewords the commit; otherwise it keeps its message.
+        await amendGitCommit(activeWorkspace.id, {
+          message: commitMessage.trim() || null,
+          force,
+        });
+        setCommitMessage("");
+        refreshGitStatus();
+        refreshGitLog?.();
+      } catch (error) {
+        setCommitError(error instanceof Error ? error.message : String(error));
+      } finally {
+        setCommitLoading(false);
+      }
+    },
+    [activeWorkspace, commitLoading, commitMessage, refreshGitLog, refreshGitStatus],
+  );
+
+  const handleUndoLastCommit = useCallback(
+    async (force = false) => {
+      if (!activeWorkspace || commitLoading) {
+        return;
+      }
+      setCommitLoading(true);
+      setCommitError(null);
+      try {
+        await undoLastCommit(activeWorkspace.id, force);
+        refreshGitStatus();
+        refreshGitLog?.();
+      } catch (error) {
+        setCommitError(error instanceof Error ? error.message : String(error));
+      } finally {
+        setCommitLoading(false);
+      }
+    },
+    [activeWorkspace, commitLoading, refreshGitLog, refreshGitStatus],
+  );
+
   const handlePush = useCallback(async () => {
     if (!activeWorkspace || pushLoading) {
       return;
@@ -289,5 +338,7 @@ export function useGitCommitController({
     onCommitAndSync: handleCommitAndSync,
     onPush: handlePush,
     onSync: handleSync,
+    onAmendCommit: handleAmendCommit,
+    onUndoLastCommit: handleUndoLastCommit,
   };
 }
@@ -23,12 +23,22 @@ export function useMenuAccelerators({
         if (!active) {
           return;
         }
-        await setMenuAccelerators(
+        const report = await setMenuAccelerators(
           accelerators.map(({ id, shortcut }) => ({
             id,
             accelerator: toMenuAccelerator(shortcut),
           })),
         );
+        if (active && report.failed.length > 0) {
+          const details = report.failed
+            .map((failure) =>
+              failure.conflictsWith
+                ? `${failure.accelerator} (${failure.id}: ${failure.reason} with ${failure.conflictsWith})`
+                : `${failure.accelerator} (${failure.id}: ${failure.reason})`,
+            )
+            .join(", ");
+          onError?.(new Error(`Shortcuts not registered: ${details}`));
+        }
       } catch (error) {
         onError?.(error);
       }
@@ -1,7 +1,27 @@
 import { useCallback, type MouseEvent } from "react";
-import { Menu, MenuItem } from "@tauri-apps/api/menu";
+import { CheckMenuItem, Menu, MenuItem, Submenu } from "@tauri-apps/api/menu";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { ask, message } from "@tauri-apps/plugin-dialog";
+import {
+  listTags,
+  pruneThreadOutputs,
+  tagThread,
+  untagThread,
+  verifySession,
+} from "../../../services/tauri";
+
+const SUGGESTED_THREAD_TAGS = ["bug", "experiment", "prod-incident"];
+
+function formatBytes(value: number) {
+  if (value < 1024) {
+    return `${value} B`;
+  }
+  if (value < 1024 * 1024) {
+    return `${(value / 1024).toFixed(1)} KB`;
+  }
+  return `${(value / (1024 * 1024)).toFixed(1)} MB`;
+}
 
 type SidebarMenuHandlers = {
   onDeleteThread: (workspaceId: string, threadId: string) => void;
@@ -9,6 +29,7 @@ type SidebarMenuHandlers = {
   onPinThread: (workspaceId: string, threadId: string) => void;
   onUnpinThread: (workspaceId: string, threadId: string) => void;
   isThreadPinned: (workspaceId: string, threadId: string) => boolean;
+  getThreadTags: (workspaceId: string, threadId: string) => string[];
   onRenameThread: (workspaceId: string, threadId: string) => void;
   onReloadWorkspaceThreads: (workspaceId: string) => void;
   onDeleteWorkspace: (workspaceId: string) => void;
@@ -21,11 +42,130 @@ export function useSidebarMenus({
   onPinThread,
   onUnpinThread,
   isThreadPinned,
+  getThreadTags,
   onRenameThread,
   onReloadWorkspaceThreads,
   onDeleteWorkspace,
   onDeleteWorktree,
 }: SidebarMenuHandlers) {
+  const buildTagsMenu = useCallback(
+    async (workspaceId: string, threadId: string) => {
+      let knownTags: string[] = [];
+      try {
+        const response = await listTags(workspaceId);
+        knownTags = (response?.data ?? []).map((entry) => entry.tag);
+      } catch {
+        // Fall back to the suggestions when the workspace is unavailable.
+      }
+      const current = getThreadTags(workspaceId, threadId);
+      const tags = Array.from(
+        new Set([...current, ...knownTags, ...SUGGESTED_THREAD_TAGS]),
+      );
+      const items = await Promise.all(
+        tags.map((tag) => {
+          const checked = current.includes(tag);
+          return CheckMenuItem.new({
+            text: tag,
+            checked,
+            action: async () => {
+              try {
+                if (checked) {
+                  await untagThread(workspaceId, threadId, tag);
+                } else {
+                  await tagThread(workspaceId, threadId, tag);
+                }
+              } finally {
+                onReloadWorkspaceThreads(workspaceId);
+              }
+            },
+          });
+        }),
+      );
+      return Submenu.new({ text: "Tags", items });
+    },
+    [getThreadTags, onReloadWorkspaceThreads],
+  );
+
+  const checkTranscript = useCallback(
+    async (workspaceId: string, threadId: string) => {
+      try {
+        const check = await verifySession(workspaceId, threadId);
+        if (check.issues.length === 0) {
+          await message(`All ${check.totalLines} lines of the transcript are readable.`, {
+            title: "Transcript intact",
+            kind: "info",
+          });
+          return;
+        }
+        const lines = check.issues
+          .slice(0, 5)
+          .map((issue) => `Line ${issue.line}: ${issue.error}`)
+          .join("\n");
+        const more =
+          check.issues.length > 5 ? `\n…and ${check.issues.length - 5} more` : "";
+        const confirmed = await ask(
+          `${check.issues.length} of ${check.totalLines} lines in ${check.path} are damaged:\n\n${lines}${more}\n\nRepair drops them from the transcript and keeps a copy in ${check.path}.bak.`,
+          {
+            title: "Damaged transcript",
+            kind: "warning",
+            okLabel: "Repair",
+            cancelLabel: "Cancel",
+          },
+        );
+        if (!confirmed) {
+          return;
+        }
+        await verifySession(workspaceId, threadId, true);
+        onSyncThread(workspaceId, threadId);
+      } catch (error) {
+        await message(error instanceof Error ? error.message : String(error), {
+          title: "Transcript check failed",
+          kind: "error",
+        });
+      }
+    },
+    [onSyncThread],
+  );
+
+  const pruneOutputs = useCallback(
+    async (workspaceId: string, threadId: string) => {
+      const confirmed = await ask(
+        "Replace large tool outputs in this thread's transcript with short previews? The full outputs are saved next to the transcript.",
+        {
+          title: "Prune large tool outputs",
+          kind: "warning",
+          okLabel: "Prune",
+          cancelLabel: "Cancel",
+        },
+      );
+      if (!confirmed) {
+        return;
+      }
+      try {
+        const report = await pruneThreadOutputs(workspaceId, threadId);
+        if (report.prunedOutputs === 0) {
+          await message("No tool outputs were large enough to prune.", {
+            title: "Nothing to prune",
+            kind: "info",
+          });
+          return;
+        }
+        const saved = Math.max(0, report.bytesBefore - report.bytesAfter);
+        await message(
+          `Pruned ${report.prunedOutputs} outputs and saved ${formatBytes(saved)}. Full outputs are in ${report.outputsDir}.`,
+          { title: "Transcript pruned", kind: "info" },
+        );
+        onSyncThread(workspaceId, threadId);
+      } catch (error) {
+        await message(error instanceof Error ? error.message : String(error), {
+          title: "Prune failed",
+          kind: "error",
+        });
+      }
+    },
+    [onSyncThread],
+  );
+
   const showThreadMenu = useCallback(
     async (
       event: MouseEvent,
@@ -47,6 +187,14 @@ export function useSidebarMenus({
         text: "Archive",
         action: () => onDeleteThread(workspaceId, threadId),
       });
+      const checkItem = await MenuItem.new({
+        text: "Check transcript",
+        action: () => void checkTranscript(workspaceId, threadId),
+      });
+      const pruneItem = await MenuItem.new({
+        text: "Prune large tool outputs",
+        action: () => void pruneOutputs(workspaceId, threadId),
+      });
       const copyItem = await MenuItem.new({
         text: "Copy ID",
         action: async () => {
@@ -73,18 +221,23 @@ export function useSidebarMenus({
           }),
         );
       }
-      items.push(copyItem, archiveItem);
-      const menu = await Menu.new({ items });
+      const tagsMenu = await buildTagsMenu(workspaceId, threadId);
+      const menu = await Menu.new({
+        items: [...items, tagsMenu, checkItem, pruneItem, copyItem, archiveItem],
+      });
       const window = getCurrentWindow();
       const position = new LogicalPosition(event.clientX, event.clientY);
       await menu.popup(position, window);
     },
     [
+      buildTagsMenu,
+      checkTranscript,
       isThreadPinned,
       onDeleteThread,
       onPinThread,
       onRenameThread,
       onSyncThread,
+      pruneOutputs,
       onUnpinThread,
     ],
   );
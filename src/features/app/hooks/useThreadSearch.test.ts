@@ -0,0 +1,18 @@
+import { describe, expect, it } from "vitest";
+import { parseThreadSearchQuery } from "./useThreadSearch";
+
+describe("parseThreadSearchQuery", () => {
+  it("separates tag filters from free text", () => {
+    expect(parseThreadSearchQuery("tag:Bug login  tag:prod-incident")).toEqual({
+      text: "login",
+      tags: ["bug", "prod-incident"],
+    });
+  });
+
+  it("treats a bare prefix as text", () => {
+    expect(parseThreadSearchQuery("tag: abc123")).toEqual({
+      text: "tag: abc123",
+      tags: [],
+    });
+  });
+});
@@ -1,6 +1,12 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { ThreadSummary, WorkspaceInfo } from "../../../types";
-import { searchThread as searchThreadService } from "../../../services/tauri";
+import {
+  listThreads as listThreadsService,
+  searchThread as searchThreadService,
+} from "../../../services/tauri";
+
+const TAG_PREFIX = "tag:";
+const TAG_ONLY_LIMIT = 50;
 
 type SearchResult = {
   workspaceId: string;
@@ -12,6 +18,29 @@ type UseThreadSearchOptions = {
   debounceMs?: number;
 };
 
+function threadTagsOf(thread: Record<string, unknown>) {
+  return Array.isArray(thread?.tags)
+    ? (thread.tags as unknown[]).map((tag) => String(tag))
+    : [];
+}
+
+/** Split `tag:bug tag:prod-incident login` into tag filters and free text. */
+export function parseThreadSearchQuery(query: string) {
+  const tags: string[] = [];
+  const words: string[] = [];
+  query
+    .split(/\s+/)
+    .filter(Boolean)
+    .forEach((word) => {
+      if (word.toLowerCase().startsWith(TAG_PREFIX) && word.length > TAG_PREFIX.length) {
+        tags.push(word.slice(TAG_PREFIX.length).toLowerCase());
+      } else {
+        words.push(word);
+      }
+    });
+  return { text: words.join(" "), tags };
+}
+
 export function useThreadSearch({
   workspaces,
   debounceMs = 300,
@@ -36,15 +65,17 @@ export function useThreadSearch({
       return;
     }
     setIsSearching(true);
+    const { text, tags } = parseThreadSearchQuery(trimmed);
     searchTimerRef.current = setTimeout(async () => {
       try {
         // Search all workspaces in parallel for better performance
         const searchPromises = workspaces.map(async (workspace) => {
           try {
-            const response = (await searchThreadService(
-              workspace.id,
-              trimmed,
-            )) as Record<string, unknown>;
+            const response = (
+              text
+                ? await searchThreadService(workspace.id, text)
+                : await listThreadsService(workspace.id, null, TAG_ONLY_LIMIT, tags)
+            ) as Record<string, unknown>;
             const result = (response.result ?? response) as Record<
               string,
               unknown
@@ -53,9 +84,15 @@ export function useThreadSearch({
               ? (result.data as Record<string, unknown>[])
               : [];
             return data
+              .filter((thread) => {
+                const threadTags = threadTagsOf(thread);
+                return tags.every((tag) => threadTags.includes(tag));
+              })
               .map((thread) => {
                 const id = String(thread?.id ?? "");
-                const preview = String(thread?.preview ?? "").trim();
+                const title = String(thread?.title ?? "").trim();
+                const preview = title || String(thread?.preview ?? "").trim();
+                const threadTags = threadTagsOf(thread);
                 const updatedAt = Number(
                   thread?.updatedAt ?? thread?.createdAt ?? 0,
                 );
@@ -68,6 +105,7 @@ export function useThreadSearch({
                         ? `${preview.slice(0, 38)}...`
                         : preview || id.slice(0, 12),
                     updatedAt,
+                    ...(threadTags.length > 0 ? { tags: threadTags } : {}),
                   },
                 };
               })
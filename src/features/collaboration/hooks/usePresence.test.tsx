@@ -0,0 +1,123 @@
+// @vitest-environment jsdom
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { AppServerEvent, Presence } from "../../../types";
+import { subscribeAppServerEvents } from "../../../services/events";
+import { updatePresence } from "../../../services/tauri";
+import { formatPresenceLabel, usePresence } from "./usePresence";
+
+vi.mock("../../../services/events", () => ({
+  subscribeAppServerEvents: vi.fn(),
+}));
+
+vi.mock("../../../services/tauri", () => ({
+  updatePresence: vi.fn(),
+}));
+
+let listener: ((event: AppServerEvent) => void) | null = null;
+
+function presence(overrides: Partial<Presence>): Presence {
+  return {
+    clientId: "peer-1",
+    name: "ana",
+    workspaceId: "ws-1",
+    threadId: "thread-1",
+    typing: false,
+    at: Date.now(),
+    ...overrides,
+  };
+}
+
+function emitPresence(value: Presence) {
+  act(() => {
+    listener?.({
+      workspace_id: value.workspaceId,
+      message: { method: "presence/updated", params: value },
+    });
+  });
+}
+
+describe("usePresence", () => {
+  beforeEach(() => {
+    listener = null;
+    vi.mocked(subscribeAppServerEvents).mockImplementation((cb) => {
+      listener = cb;
+      return () => {};
+    });
+    vi.mocked(updatePresence).mockImplementation(async (workspaceId, threadId, typing) =>
+      presence({ clientId: "self", name: "me", workspaceId, threadId, typing }),
+    );
+  });
+
+  it("tracks peers on the active thread and ignores its own echo", async () => {
+    const { result } = renderHook(() =>
+      usePresence({
+        enabled: true,
+        workspaceId: "ws-1",
+        threadId: "thread-1",
+        draftText: "",
+      }),
+    );
+    await waitFor(() =>
+      expect(updatePresence).toHaveBeenCalledWith("ws-1", "thread-1", false),
+    );
+
+    emitPresence(presence({ clientId: "self" }));
+    emitPresence(presence({ clientId: "peer-2", threadId: "thread-9" }));
+    expect(result.current.viewers).toEqual([]);
+
+    emitPresence(presence({ typing: true }));
+    expect(result.current.label).toBe("ana is typing...");
+  });
+
+  it("reports typing while the draft changes", async () => {
+    const { rerender } = renderHook((props: { draftText: string }) =>
+      usePresence({
+        enabled: true,
+        workspaceId: "ws-1",
+        threadId: "thread-1",
+        draftText: props.draftText,
+      }),
+    { initialProps: { draftText: "" } },
+    );
+    rerender({ draftText: "fix the" });
+    await waitFor(() =>
+      expect(updatePresence).toHaveBeenLastCalledWith("ws-1", "thread-1", true),
+    );
+  });
+
+  it("stops sending and reports the error when the daemon rejects presence", async () => {
+    vi.mocked(updatePresence).mockReset();
+    vi.mocked(updatePresence).mockRejectedValue(new Error("unknown method: presence_update"));
+    const { result, rerender } = renderHook((props: { draftText: string }) =>
+      usePresence({
+        enabled: true,
+        workspaceId: "ws-1",
+        threadId: "thread-1",
+        draftText: props.draftText,
+      }),
+    { initialProps: { draftText: "" } },
+    );
+    await waitFor(() =>
+      expect(result.current.error).toBe("unknown method: presence_update"),
+    );
+    rerender({ draftText: "still typing" });
+    expect(updatePresence).toHaveBeenCalledTimes(1);
+  });
+});
+
+describe("formatPresenceLabel", () => {
+  it("prefers typing over viewing", () => {
+    expect(formatPresenceLabel([])).toBeNull();
+    expect(formatPresenceLabel([presence({})])).toBe("ana is viewing this thread");
+    expect(
+      formatPresenceLabel([presence({}), presence({ clientId: "peer-2", name: "bo" })]),
+    ).toBe("2 others are viewing this thread");
+    expect(
+      formatPresenceLabel([
+        presence({ typing: true }),
+        presence({ clientId: "peer-2", typing: true }),
+      ]),
+    ).toBe("2 people are typing...");
+  });
+});
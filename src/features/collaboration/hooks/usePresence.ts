@@ -0,0 +1,157 @@
+import { useEffect, useMemo, useRef, useState } from "react";
+import type { Presence } from "../../../types";
+import { subscribeAppServerEvents } from "../../../services/events";
+import { updatePresence } from "../../../services/tauri";
+
+const HEARTBEAT_MS = 15_000;
+const TYPING_IDLE_MS = 4_000;
+const PRESENCE_TTL_MS = 45_000;
+
+type UsePresenceArgs = {
+  enabled: boolean;
+  workspaceId: string | null;
+  threadId: string | null;
+  draftText: string;
+};
+
+function parsePresence(params: unknown): Presence | null {
+  if (!params || typeof params !== "object") {
+    return null;
+  }
+  const value = params as Record<string, unknown>;
+  const clientId = typeof value.clientId === "string" ? value.clientId : "";
+  const workspaceId = typeof value.workspaceId === "string" ? value.workspaceId : "";
+  if (!clientId || !workspaceId) {
+    return null;
+  }
+  return {
+    clientId,
+    name: typeof value.name === "string" && value.name ? value.name : "Someone",
+    workspaceId,
+    threadId: typeof value.threadId === "string" ? value.threadId : null,
+    typing: value.typing === true,
+    at: typeof value.at === "number" ? value.at : Date.now(),
+  };
+}
+
+export function formatPresenceLabel(viewers: Presence[]) {
+  if (viewers.length === 0) {
+    return null;
+  }
+  const typing = viewers.filter((viewer) => viewer.typing);
+  if (typing.length === 1) {
+    return `${typing[0].name} is typing...`;
+  }
+  if (typing.length > 1) {
+    return `${typing.length} people are typing...`;
+  }
+  if (viewers.length === 1) {
+    return `${viewers[0].name} is viewing this thread`;
+  }
+  return `${viewers.length} others are viewing this thread`;
+}
+
+/**
+ * Share which thread this client is viewing (and whether it is typing) with
+ * other clients attached to the same daemon, and track theirs.
+ */
+export function usePresence({
+  enabled,
+  workspaceId,
+  threadId,
+  draftText,
+}: UsePresenceArgs) {
+  const [peers, setPeers] = useState<Record<string, Presence>>({});
+  const [now, setNow] = useState(() => Date.now());
+  const [typing, setTyping] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const ownClientIdRef = useRef<string | null>(null);
+  const lastDraftRef = useRef(draftText);
+
+  useEffect(() => {
+    if (!enabled) {
+      setPeers({});
+      return;
+    }
+    const unsubscribe = subscribeAppServerEvents(({ message }) => {
+      if (message.method !== "presence/updated") {
+        return;
+      }
+      const presence = parsePresence(message.params);
+      if (!presence || presence.clientId === ownClientIdRef.current) {
+        return;
+      }
+      setPeers((prev) => ({ ...prev, [presence.clientId]: presence }));
+    });
+    const timer = window.setInterval(() => setNow(Date.now()), HEARTBEAT_MS);
+    return () => {
+      unsubscribe();
+      window.clearInterval(timer);
+    };
+  }, [enabled]);
+
+  useEffect(() => {
+    if (!enabled || draftText === lastDraftRef.current) {
+      lastDraftRef.current = draftText;
+      return;
+    }
+    lastDraftRef.current = draftText;
+    if (!draftText.trim()) {
+      setTyping(false);
+      return;
+    }
+    setTyping(true);
+    const timeoutId = window.setTimeout(() => setTyping(false), TYPING_IDLE_MS);
+    return () => {
+      window.clearTimeout(timeoutId);
+    };
+  }, [draftText, enabled]);
+
+  useEffect(() => {
+    setTyping(false);
+  }, [threadId]);
+
+  useEffect(() => {
+    setError(null);
+  }, [enabled]);
+
+  useEffect(() => {
+    if (!enabled || !workspaceId || error) {
+      return;
+    }
+    let cancelled = false;
+    const send = () => {
+      updatePresence(workspaceId, threadId, typing)
+        .then((presence) => {
+          ownClientIdRef.current = presence.clientId;
+        })
+        .catch((err) => {
+          // A daemon without presence support fails every call; stop until
+          // presence is turned off and on again.
+          if (!cancelled) {
+            setError(err instanceof Error ? err.message : String(err));
+          }
+        });
+    };
+    send();
+    const timer = window.setInterval(send, HEARTBEAT_MS);
+    return () => {
+      cancelled = true;
+      window.clearInterval(timer);
+    };
+  }, [enabled, error, threadId, typing, workspaceId]);
+
+  const viewers = useMemo(
+    () =>
+      Object.values(peers).filter(
+        (peer) =>
+          peer.workspaceId === workspaceId &&
+          peer.threadId === threadId &&
+          threadId !== null &&
+          now - peer.at < PRESENCE_TTL_MS,
+      ),
+    [now, peers, threadId, workspaceId],
+  );
+
+  return { viewers, label: formatPresenceLabel(viewers), error };
+}
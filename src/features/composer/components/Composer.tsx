@@ -6,7 +6,7 @@ import type {
   QueuedMessage,
   ThreadTokenUsage,
 } from "../../../types";
-import { computeDictationInsertion } from "../../../utils/dictation";
+import { applyDictationTranscript } from "../../../utils/dictation";
 import { isComposingEvent } from "../../../utils/keys";
 import {
   getFenceTriggerLine,
@@ -73,6 +73,7 @@ type ComposerProps = {
   onDismissDictationError?: () => void;
   dictationHint?: string | null;
   onDismissDictationHint?: () => void;
+  presenceLabel?: string | null;
 };
 
 const DEFAULT_EDITOR_SETTINGS: ComposerEditorSettings = {
@@ -138,6 +139,7 @@ export function Composer({
   onDismissDictationError,
   dictationHint = null,
   onDismissDictationHint,
+  presenceLabel = null,
 }: ComposerProps) {
   const [text, setText] = useState(draftText);
   const [selectionStart, setSelectionStart] = useState<number | null>(null);
@@ -233,20 +235,28 @@ export function Composer({
     if (!dictationTranscript) {
       return;
     }
-    const textToInsert = dictationTranscript.text.trim();
-    if (!textToInsert) {
+    if (!dictationTranscript.text.trim() && !dictationTranscript.command) {
       onDictationTranscriptHandled?.(dictationTranscript.id);
       return;
     }
     const textarea = textareaRef.current;
     const start = textarea?.selectionStart ?? selectionStart ?? text.length;
     const end = textarea?.selectionEnd ?? start;
-    const { nextText, nextCursor } = computeDictationInsertion(
+    const { nextText, nextCursor } = applyDictationTranscript(
       text,
-      textToInsert,
+      dictationTranscript,
       start,
       end,
     );
+    if (dictationTranscript.command === "send") {
+      onDictationTranscriptHandled?.(dictationTranscript.id);
+      const trimmed = nextText.trim();
+      if (!disabled && (trimmed || attachedImages.length > 0)) {
+        onSend(trimmed, attachedImages);
+        setComposerText("");
+        return;
+      }
+    }
     setComposerText(nextText);
     requestAnimationFrame(() => {
       if (!textareaRef.current) {
@@ -258,9 +268,12 @@ export function Composer({
     });
     onDictationTranscriptHandled?.(dictationTranscript.id);
   }, [
+    attachedImages,
     dictationTranscript,
+    disabled,
     handleSelectionChange,
     onDictationTranscriptHandled,
+    onSend,
     selectionStart,
     setComposerText,
     text,
@@ -383,6 +396,11 @@ export function Composer({
 
   return (
     <footer className={`composer${disabled ? " is-disabled" : ""}`}>
+      {presenceLabel && (
+        <div className="composer-presence" role="status">
+          {presenceLabel}
+        </div>
+      )}
       <ComposerQueue
         queuedMessages={queuedMessages}
         onEditQueued={onEditQueued}
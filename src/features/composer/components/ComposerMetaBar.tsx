@@ -45,6 +45,16 @@ export function ComposerMetaBar({
         )
       : null;
 
+  const contextLabel =
+    contextFreePercent === null
+      ? "Context free --"
+      : `Context free ${Math.round(contextFreePercent)}%`;
+  // Thread totals per model, so subagent models show up next to the main one.
+  const modelUsageLabel = (contextUsage?.perModel ?? [])
+    .filter((entry) => entry.total.totalTokens > 0)
+    .map((entry) => `${entry.model} ${entry.total.totalTokens.toLocaleString()} tokens`)
+    .join(" · ");
+
   return (
     <div className="composer-bar">
       <div className="composer-meta">
@@ -202,9 +212,9 @@ export function ComposerMetaBar({
         <div
           className="composer-context-ring"
           data-tooltip={
-            contextFreePercent === null
-              ? "Context free --"
-              : `Context free ${Math.round(contextFreePercent)}%`
+            modelUsageLabel
+              ? `${contextLabel} · ${modelUsageLabel}`
+              : contextLabel
           }
           aria-label={
             contextFreePercent === null
@@ -0,0 +1,119 @@
+/** @vitest-environment jsdom */
+import React, { act } from "react";
+import { createRoot } from "react-dom/client";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import type { ThreadDraft } from "../../../types";
+import { getThreadDraft, saveThreadDraft } from "../../../services/tauri";
+import { usePersistedThreadDrafts } from "./usePersistedThreadDrafts";
+
+vi.mock("../../../services/tauri", () => ({
+  getThreadDraft: vi.fn(),
+  saveThreadDraft: vi.fn().mockResolvedValue(undefined),
+}));
+
+type Props = Parameters<typeof usePersistedThreadDrafts>[0];
+
+function renderDrafts(initial: Props) {
+  let props = initial;
+
+  function Test() {
+    usePersistedThreadDrafts(props);
+    return null;
+  }
+
+  const container = document.createElement("div");
+  document.body.appendChild(container);
+  const root = createRoot(container);
+
+  act(() => {
+    root.render(React.createElement(Test));
+  });
+
+  return {
+    rerender: (next: Partial<Props>) => {
+      props = { ...props, ...next };
+      act(() => {
+        root.render(React.createElement(Test));
+      });
+    },
+    unmount: () => {
+      act(() => {
+        root.unmount();
+      });
+      container.remove();
+    },
+  };
+}
+
+async function flushPromises() {
+  await act(async () => {
+    await Promise.resolve();
+  });
+}
+
+describe("usePersistedThreadDrafts", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.mocked(saveThreadDraft).mockClear();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("restores the stored draft before saving edits", async () => {
+    const stored: ThreadDraft = {
+      workspaceId: "ws-1",
+      text: "half written",
+      attachments: ["/tmp/a.png"],
+      updatedAt: 1,
+    };
+    vi.mocked(getThreadDraft).mockResolvedValueOnce(stored);
+    const onRestore = vi.fn();
+    const hook = renderDrafts({
+      activeWorkspaceId: "ws-1",
+      activeThreadId: "thread-1",
+      draftText: "",
+      attachments: [],
+      onRestore,
+    });
+
+    await flushPromises();
+    expect(onRestore).toHaveBeenCalledWith("thread-1", stored);
+
+    hook.rerender({ draftText: "half written more" });
+    act(() => {
+      vi.advanceTimersByTime(500);
+    });
+    expect(saveThreadDraft).toHaveBeenLastCalledWith(
+      "ws-1",
+      "thread-1",
+      "half written more",
+      [],
+    );
+    hook.unmount();
+  });
+
+  it("flushes a pending save when switching threads", async () => {
+    vi.mocked(getThreadDraft).mockResolvedValue(null);
+    const hook = renderDrafts({
+      activeWorkspaceId: "ws-1",
+      activeThreadId: "thread-1",
+      draftText: "",
+      attachments: [],
+      onRestore: vi.fn(),
+    });
+    await flushPromises();
+
+    hook.rerender({ draftText: "typed quickly" });
+    vi.mocked(saveThreadDraft).mockClear();
+    hook.rerender({ activeThreadId: "thread-2", draftText: "" });
+    expect(saveThreadDraft).toHaveBeenCalledWith(
+      "ws-1",
+      "thread-1",
+      "typed quickly",
+      [],
+    );
+    hook.unmount();
+  });
+});
@@ -0,0 +1,110 @@
+import { useEffect, useRef, useState } from "react";
+import type { ThreadDraft } from "../../../types";
+import { getThreadDraft, saveThreadDraft } from "../../../services/tauri";
+
+const SAVE_DELAY_MS = 400;
+
+type PendingDraft = {
+  workspaceId: string;
+  threadId: string;
+  text: string;
+  attachments: string[];
+};
+
+type UsePersistedThreadDraftsArgs = {
+  activeWorkspaceId: string | null;
+  activeThreadId: string | null;
+  draftText: string;
+  attachments: string[];
+  onRestore: (threadId: string, draft: ThreadDraft) => void;
+};
+
+function flushDraft(pending: PendingDraft | null) {
+  if (!pending) {
+    return;
+  }
+  void saveThreadDraft(
+    pending.workspaceId,
+    pending.threadId,
+    pending.text,
+    pending.attachments,
+  ).catch(() => {});
+}
+
+export function usePersistedThreadDrafts({
+  activeWorkspaceId,
+  activeThreadId,
+  draftText,
+  attachments,
+  onRestore,
+}: UsePersistedThreadDraftsArgs) {
+  const loadedThreadsRef = useRef<Set<string>>(new Set());
+  const pendingRef = useRef<PendingDraft | null>(null);
+  const onRestoreRef = useRef(onRestore);
+  const [loadedThreadId, setLoadedThreadId] = useState<string | null>(null);
+  // Compare attachments by value; callers rebuild the array on every render.
+  const attachmentsKey = attachments.join("\n");
+
+  useEffect(() => {
+    onRestoreRef.current = onRestore;
+  }, [onRestore]);
+
+  useEffect(() => {
+    if (pendingRef.current && pendingRef.current.threadId !== activeThreadId) {
+      flushDraft(pendingRef.current);
+      pendingRef.current = null;
+    }
+    if (!activeWorkspaceId || !activeThreadId) {
+      return;
+    }
+    if (loadedThreadsRef.current.has(activeThreadId)) {
+      setLoadedThreadId(activeThreadId);
+      return;
+    }
+    let cancelled = false;
+    const threadId = activeThreadId;
+    getThreadDraft(activeWorkspaceId, threadId)
+      .then((draft) => {
+        if (!cancelled && draft) {
+          onRestoreRef.current(threadId, draft);
+        }
+      })
+      .catch(() => {})
+      .finally(() => {
+        if (!cancelled) {
+          loadedThreadsRef.current.add(threadId);
+          setLoadedThreadId(threadId);
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [activeThreadId, activeWorkspaceId]);
+
+  useEffect(() => {
+    // Don't write until the stored draft has been read, or it would be wiped.
+    if (!activeWorkspaceId || !activeThreadId || loadedThreadId !== activeThreadId) {
+      return;
+    }
+    pendingRef.current = {
+      workspaceId: activeWorkspaceId,
+      threadId: activeThreadId,
+      text: draftText,
+      attachments: attachmentsKey ? attachmentsKey.split("\n") : [],
+    };
+    const timeoutId = window.setTimeout(() => {
+      flushDraft(pendingRef.current);
+      pendingRef.current = null;
+    }, SAVE_DELAY_MS);
+    return () => {
+      window.clearTimeout(timeoutId);
+    };
+  }, [activeThreadId, activeWorkspaceId, attachmentsKey, draftText, loadedThreadId]);
+
+  useEffect(() => {
+    return () => {
+      flushDraft(pendingRef.current);
+      pendingRef.current = null;
+    };
+  }, []);
+}
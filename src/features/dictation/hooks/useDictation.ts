@@ -53,6 +53,14 @@ export function useDictation(): UseDictationResult {
         });
         return;
       }
+      if (event.type === "command") {
+        setTranscript({
+          id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
+          text: event.text,
+          command: event.command,
+        });
+        return;
+      }
       if (event.type === "error") {
         setError(event.message);
         return;
@@ -27,7 +27,13 @@ import FileVideo from "lucide-react/dist/esm/icons/file-video";
 import Folder from "lucide-react/dist/esm/icons/folder";
 import Search from "lucide-react/dist/esm/icons/search";
 import { PanelTabs, type PanelTabId } from "../../layout/components/PanelTabs";
-import { readWorkspaceFile } from "../../../services/tauri";
+import { subscribeAppServerEvents } from "../../../services/events";
+import {
+  readWorkspaceFile,
+  unwatchFile,
+  watchFile,
+} from "../../../services/tauri";
+import { formatDownloadSize } from "../../../utils/formatting";
 import { languageFromPath } from "../../../utils/syntax";
 import { FilePreviewPopover } from "./FilePreviewPopover";
 
@@ -234,6 +240,7 @@ export function FileTreePanel({
   const [previewTruncated, setPreviewTruncated] = useState(false);
   const [previewLoading, setPreviewLoading] = useState(false);
   const [previewError, setPreviewError] = useState<string | null>(null);
+  const [previewRevision, setPreviewRevision] = useState(0);
   const [previewSelection, setPreviewSelection] = useState<{
     start: number;
     end: number;
@@ -425,6 +432,14 @@ export function FileTreePanel({
         if (cancelled) {
           return;
         }
+        if (response.kind === "binary") {
+          setPreviewContent("");
+          setPreviewTruncated(false);
+          setPreviewError(
+            `Binary file (${response.mime ?? "unknown type"}, ${formatDownloadSize(response.size)}) can't be previewed.`,
+          );
+          return;
+        }
         setPreviewContent(response.content ?? "");
         setPreviewTruncated(Boolean(response.truncated));
       })
@@ -442,6 +457,27 @@ export function FileTreePanel({
     return () => {
       cancelled = true;
     };
+  }, [previewKind, previewPath, previewRevision, workspaceId]);
+
+  useEffect(() => {
+    if (!previewPath || previewKind === "image") {
+      return;
+    }
+    // Reload the preview when the agent or another tool edits the file.
+    watchFile(workspaceId, previewPath).catch(() => {});
+    const unsubscribe = subscribeAppServerEvents(({ workspace_id, message }) => {
+      if (workspace_id !== workspaceId || message.method !== "file/changed") {
+        return;
+      }
+      const params = (message.params as Record<string, unknown>) ?? {};
+      if (params.path === previewPath) {
+        setPreviewRevision((value) => value + 1);
+      }
+    });
+    return () => {
+      unsubscribe();
+      unwatchFile(workspaceId, previewPath).catch(() => {});
+    };
   }, [previewKind, previewPath, workspaceId]);
 
   useEffect(() => {
@@ -98,6 +98,8 @@ type GitDiffPanelProps = {
   onCommitAndSync?: () => void | Promise<void>;
   onPush?: () => void | Promise<void>;
   onSync?: () => void | Promise<void>;
+  onAmendCommit?: (force?: boolean) => void | Promise<void>;
+  onUndoLastCommit?: (force?: boolean) => void | Promise<void>;
   commitLoading?: boolean;
   pushLoading?: boolean;
   syncLoading?: boolean;
@@ -621,6 +623,8 @@ export function GitDiffPanel({
   onCommitAndSync: _onCommitAndSync,
   onPush,
   onSync: _onSync,
+  onAmendCommit,
+  onUndoLastCommit,
   commitLoading = false,
   pushLoading = false,
   syncLoading: _syncLoading = false,
@@ -771,12 +775,65 @@ export function GitDiffPanel({
         });
         items.push(openItem);
       }
+      if (entry.sha === logEntries[0]?.sha) {
+        // Without an upstream nothing is pushed; with one, only ahead commits are local.
+        const isPushed =
+          Boolean(logUpstream) &&
+          !logAheadEntries.some((ahead) => ahead.sha === entry.sha);
+        const pushedWarning = isPushed
+          ? "\n\nThis commit is already pushed. You will need to force push afterwards."
+          : "";
+        if (onAmendCommit) {
+          items.push(
+            await MenuItem.new({
+              text: commitMessage.trim()
+                ? "Amend last commit with new message"
+                : "Amend last commit",
+              action: async () => {
+                const confirmed = await ask(
+                  `Add the staged changes to "${entry.summary}"${
+                    commitMessage.trim() ? " and replace its message" : ""
+                  }?${pushedWarning}`,
+                  { title: "Amend commit", kind: "warning" },
+                );
+                if (confirmed) {
+                  await onAmendCommit(isPushed);
+                }
+              },
+            }),
+          );
+        }
+        if (onUndoLastCommit) {
+          items.push(
+            await MenuItem.new({
+              text: "Undo last commit",
+              action: async () => {
+                const confirmed = await ask(
+                  `Undo "${entry.summary}"? Its changes stay staged.${pushedWarning}`,
+                  { title: "Undo commit", kind: "warning" },
+                );
+                if (confirmed) {
+                  await onUndoLastCommit(isPushed);
+                }
+              },
+            }),
+          );
+        }
+      }
       const menu = await Menu.new({ items });
       const window = getCurrentWindow();
       const position = new LogicalPosition(event.clientX, event.clientY);
       await menu.popup(position, window);
     },
-    [githubBaseUrl],
+    [
+      commitMessage,
+      githubBaseUrl,
+      logAheadEntries,
+      logEntries,
+      logUpstream,
+      onAmendCommit,
+      onUndoLastCommit,
+    ],
   );
 
   const showPullRequestMenu = useCallback(
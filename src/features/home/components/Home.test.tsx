@@ -8,6 +8,10 @@ const baseProps = {
   onAddWorkspace: vi.fn(),
   latestAgentRuns: [],
   isLoadingLatestAgents: false,
+  recentThreads: [],
+  isLoadingRecentThreads: false,
+  hasMoreRecentThreads: false,
+  onLoadMoreRecentThreads: vi.fn(),
   localUsageSnapshot: null,
   isLoadingLocalUsage: false,
   localUsageError: null,
@@ -96,4 +100,34 @@ describe("Home", () => {
     expect(screen.getByText("Runs")).toBeTruthy();
     expect(screen.getByText("Peak day")).toBeTruthy();
   });
+
+  it("lists recent threads across workspaces and pages through them", () => {
+    const onSelectThread = vi.fn();
+    const onLoadMoreRecentThreads = vi.fn();
+    render(
+      <Home
+        {...baseProps}
+        recentThreads={[
+          {
+            id: "thread-9",
+            workspaceId: "workspace-2",
+            workspaceName: "api-server",
+            workspaceBranch: "fix/timeouts",
+            preview: "Investigate gateway timeouts",
+            updatedAt: Date.now(),
+          },
+        ]}
+        hasMoreRecentThreads
+        onLoadMoreRecentThreads={onLoadMoreRecentThreads}
+        onSelectThread={onSelectThread}
+      />,
+    );
+
+    expect(screen.getByText("Recent activity")).toBeTruthy();
+    expect(screen.getByText("fix/timeouts")).toBeTruthy();
+    fireEvent.click(screen.getByText("Investigate gateway timeouts"));
+    expect(onSelectThread).toHaveBeenCalledWith("workspace-2", "thread-9");
+    fireEvent.click(screen.getByText("Show more"));
+    expect(onLoadMoreRecentThreads).toHaveBeenCalled();
+  });
 });
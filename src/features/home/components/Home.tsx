@@ -1,5 +1,5 @@
 import RefreshCw from "lucide-react/dist/esm/icons/refresh-cw";
-import type { LocalUsageSnapshot } from "../../../types";
+import type { LocalUsageSnapshot, RecentThread } from "../../../types";
 import { formatRelativeTime } from "../../../utils/time";
 
 type LatestAgentRun = {
@@ -19,6 +19,10 @@ type HomeProps = {
   onAddWorkspace: () => void;
   latestAgentRuns: LatestAgentRun[];
   isLoadingLatestAgents: boolean;
+  recentThreads: RecentThread[];
+  isLoadingRecentThreads: boolean;
+  hasMoreRecentThreads: boolean;
+  onLoadMoreRecentThreads: () => void;
   localUsageSnapshot: LocalUsageSnapshot | null;
   isLoadingLocalUsage: boolean;
   localUsageError: string | null;
@@ -33,6 +37,10 @@ export function Home({
   onAddWorkspace,
   latestAgentRuns,
   isLoadingLatestAgents,
+  recentThreads,
+  isLoadingRecentThreads,
+  hasMoreRecentThreads,
+  onLoadMoreRecentThreads,
   localUsageSnapshot,
   isLoadingLocalUsage,
   localUsageError,
@@ -231,6 +239,49 @@ export function Home({
           </div>
         )}
       </div>
+      {recentThreads.length > 0 && (
+        <div className="home-recent">
+          <div className="home-section-header">
+            <div className="home-section-title">Recent activity</div>
+          </div>
+          <div className="home-recent-list">
+            {recentThreads.map((thread) => {
+              const branch = thread.workspaceBranch ?? thread.gitBranch;
+              return (
+                <button
+                  className="home-recent-row"
+                  key={`${thread.workspaceId}:${thread.id}`}
+                  onClick={() => onSelectThread(thread.workspaceId, thread.id)}
+                  type="button"
+                >
+                  <span className="home-recent-workspace">
+                    {thread.workspaceName}
+                    {branch && (
+                      <span className="home-recent-branch">{branch}</span>
+                    )}
+                  </span>
+                  <span className="home-recent-title">
+                    {thread.title || thread.preview || thread.id}
+                  </span>
+                  <span className="home-recent-time">
+                    {formatRelativeTime(thread.updatedAt)}
+                  </span>
+                </button>
+              );
+            })}
+          </div>
+          {hasMoreRecentThreads && (
+            <button
+              type="button"
+              className="home-recent-more"
+              onClick={onLoadMoreRecentThreads}
+              disabled={isLoadingRecentThreads}
+            >
+              {isLoadingRecentThreads ? "Loading…" : "Show more"}
+            </button>
+          )}
+        </div>
+      )}
       <div className="home-actions">
         <button
           className="home-button primary"
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { LocalUsageSnapshot } from "../../../types";
 import { localUsageSnapshot } from "../../../services/tauri";
+import { subscribeLocalUsageUpdated } from "../../../services/events";
 
 type LocalUsageState = {
   snapshot: LocalUsageSnapshot | null;
@@ -64,5 +65,18 @@ export function useLocalUsage(enabled: boolean) {
     };
   }, [enabled, refresh]);
 
+  useEffect(() => {
+    if (!enabled) {
+      return;
+    }
+    // The backend tails session files and pushes fresh snapshots as they change.
+    return subscribeLocalUsageUpdated((snapshot) => {
+      if (!enabledRef.current) {
+        return;
+      }
+      setState({ snapshot, isLoading: false, error: null });
+    });
+  }, [enabled]);
+
   return { ...state, refresh };
 }
@@ -0,0 +1,81 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { RecentThread } from "../../../types";
+import { listAllThreads } from "../../../services/tauri";
+
+const PAGE_SIZE = 12;
+
+type RecentThreadsState = {
+  threads: RecentThread[];
+  nextCursor: string | null;
+  isLoading: boolean;
+  error: string | null;
+};
+
+const emptyState: RecentThreadsState = {
+  threads: [],
+  nextCursor: null,
+  isLoading: false,
+  error: null,
+};
+
+/**
+ * Recent threads across all connected workspaces. `connectedKey` should change
+ * whenever the set of connected workspaces does so the list is refetched.
+ */
+export function useRecentThreads(enabled: boolean, connectedKey: string) {
+  const [state, setState] = useState<RecentThreadsState>(emptyState);
+  const requestIdRef = useRef(0);
+
+  const load = useCallback((cursor: string | null) => {
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    setState((prev) => ({ ...prev, isLoading: true, error: null }));
+    return listAllThreads(cursor, PAGE_SIZE)
+      .then((response) => {
+        if (requestIdRef.current !== requestId) {
+          return;
+        }
+        const page = response?.data ?? [];
+        setState((prev) => ({
+          threads: cursor ? [...prev.threads, ...page] : page,
+          nextCursor: response?.nextCursor ?? null,
+          isLoading: false,
+          error: null,
+        }));
+      })
+      .catch((err) => {
+        if (requestIdRef.current !== requestId) {
+          return;
+        }
+        const message = err instanceof Error ? err.message : String(err);
+        setState((prev) => ({ ...prev, isLoading: false, error: message }));
+      });
+  }, []);
+
+  useEffect(() => {
+    if (!enabled) {
+      return;
+    }
+    void load(null);
+  }, [connectedKey, enabled, load]);
+
+  const loadMore = useCallback(() => {
+    if (!state.nextCursor || state.isLoading) {
+      return;
+    }
+    void load(state.nextCursor);
+  }, [load, state.isLoading, state.nextCursor]);
+
+  const refresh = useCallback(() => {
+    void load(null);
+  }, [load]);
+
+  return {
+    threads: state.threads,
+    hasMore: Boolean(state.nextCursor),
+    isLoading: state.isLoading,
+    error: state.error,
+    loadMore,
+    refresh,
+  };
+}
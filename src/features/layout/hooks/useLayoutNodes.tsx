@@ -39,13 +39,17 @@ import type {
   LocalUsageSnapshot,
   ModelOption,
   PermissionDenial,
+  PlanProposal,
   QueuedMessage,
   RateLimitSnapshot,
+  RecentThread,
   RequestUserInputRequest,
   RequestUserInputResponse,
   SkillOption,
   ThreadSummary,
   ThreadTokenUsage,
+  ToolApprovalDecision,
+  ToolApprovalRequest,
   TurnPlan,
   WorkspaceInfo,
 } from "../../../types";
@@ -129,10 +133,22 @@ type LayoutNodesOptions = {
     ruleInfo: ApprovalRuleInfo,
   ) => void;
   handlePermissionDismiss: (denial: PermissionDenial) => void;
+  approvalRequests: ToolApprovalRequest[];
+  handleApprovalDecision: (
+    request: ToolApprovalRequest,
+    decision: ToolApprovalDecision,
+  ) => void;
+  handleApprovalRemember: (
+    request: ToolApprovalRequest,
+    ruleInfo: ApprovalRuleInfo,
+  ) => void;
   handleUserInputSubmit: (
     request: RequestUserInputRequest,
     response: RequestUserInputResponse,
   ) => void;
+  planProposals: PlanProposal[];
+  handlePlanApprove: (proposal: PlanProposal) => void;
+  handlePlanReject: (proposal: PlanProposal, feedback: string) => void;
   onForkThreadFromMessage: (
     workspaceId: string,
     threadId: string,
@@ -192,6 +208,10 @@ type LayoutNodesOptions = {
     isProcessing: boolean;
   }>;
   isLoadingLatestAgents: boolean;
+  recentThreads: RecentThread[];
+  isLoadingRecentThreads: boolean;
+  hasMoreRecentThreads: boolean;
+  onLoadMoreRecentThreads: () => void;
   localUsageSnapshot: LocalUsageSnapshot | null;
   isLoadingLocalUsage: boolean;
   localUsageError: string | null;
@@ -298,6 +318,8 @@ type LayoutNodesOptions = {
   onCommitAndSync?: () => void | Promise<void>;
   onPush?: () => void | Promise<void>;
   onSync?: () => void | Promise<void>;
+  onAmendCommit?: (force?: boolean) => void | Promise<void>;
+  onUndoLastCommit?: (force?: boolean) => void | Promise<void>;
   commitLoading?: boolean;
   pushLoading?: boolean;
   syncLoading?: boolean;
@@ -375,6 +397,7 @@ type LayoutNodesOptions = {
   onDismissDictationError: () => void;
   dictationHint: string | null;
   onDismissDictationHint: () => void;
+  presenceLabel?: string | null;
   showComposer: boolean;
   composerSendLabel?: string;
   plan: TurnPlan | null;
@@ -514,9 +537,13 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       codeBlockCopyUseModifier={options.codeBlockCopyUseModifier}
       userInputRequests={options.userInputRequests}
       onUserInputSubmit={options.handleUserInputSubmit}
+      planProposals={options.planProposals}
+      onPlanApprove={options.handlePlanApprove}
+      onPlanReject={options.handlePlanReject}
       onForkThreadFromMessage={options.onForkThreadFromMessage}
       onRewindThreadToMessage={options.onRewindThreadToMessage}
       onForkAndRewindThread={options.onForkAndRewindThread}
+      onOpenThread={options.onSelectThread}
       isThinking={
         options.activeThreadId
           ? options.threadStatusById[options.activeThreadId]?.isProcessing ?? false
@@ -583,6 +610,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onDismissDictationError={options.onDismissDictationError}
       dictationHint={options.dictationHint}
       onDismissDictationHint={options.onDismissDictationHint}
+      presenceLabel={options.presenceLabel}
     />
   ) : null;
 
@@ -593,6 +621,9 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onPermissionRemember={options.handlePermissionRemember}
       onPermissionRetry={options.handlePermissionRetry}
       onPermissionDismiss={options.handlePermissionDismiss}
+      approvalRequests={options.approvalRequests}
+      onApprovalDecision={options.handleApprovalDecision}
+      onApprovalRemember={options.handleApprovalRemember}
     />
   );
 
@@ -610,6 +641,10 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onAddWorkspace={options.onAddWorkspace}
       latestAgentRuns={options.latestAgentRuns}
       isLoadingLatestAgents={options.isLoadingLatestAgents}
+      recentThreads={options.recentThreads}
+      isLoadingRecentThreads={options.isLoadingRecentThreads}
+      hasMoreRecentThreads={options.hasMoreRecentThreads}
+      onLoadMoreRecentThreads={options.onLoadMoreRecentThreads}
       localUsageSnapshot={options.localUsageSnapshot}
       isLoadingLocalUsage={options.isLoadingLocalUsage}
       localUsageError={options.localUsageError}
@@ -819,6 +854,8 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
         onCommitAndSync={options.onCommitAndSync}
         onPush={options.onPush}
         onSync={options.onSync}
+        onAmendCommit={options.onAmendCommit}
+        onUndoLastCommit={options.onUndoLastCommit}
         commitLoading={options.commitLoading}
         pushLoading={options.pushLoading}
         syncLoading={options.syncLoading}
@@ -17,14 +17,18 @@ import Wrench from "lucide-react/dist/esm/icons/wrench";
 import X from "lucide-react/dist/esm/icons/x";
 import type {
   ConversationItem,
+  PlanProposal,
   RequestUserInputRequest,
   RequestUserInputResponse,
 } from "../../../types";
 import { Markdown } from "./Markdown";
+import { getItemFullOutput } from "../../../services/tauri";
+import { pushErrorToast } from "../../../services/toasts";
 import { DiffBlock } from "../../git/components/DiffBlock";
 import { languageFromPath } from "../../../utils/syntax";
 import { useFileLinkOpener } from "../hooks/useFileLinkOpener";
 import { useMessageMenu } from "../hooks/useMessageMenu";
+import { PlanApprovalMessage } from "../../app/components/PlanApprovalMessage";
 import { RequestUserInputMessage } from "../../app/components/RequestUserInputMessage";
 
 type MessagesProps = {
@@ -41,6 +45,9 @@ type MessagesProps = {
     request: RequestUserInputRequest,
     response: RequestUserInputResponse,
   ) => void;
+  planProposals?: PlanProposal[];
+  onPlanApprove?: (proposal: PlanProposal) => void;
+  onPlanReject?: (proposal: PlanProposal, feedback: string) => void;
   onForkThreadFromMessage?: (
     workspaceId: string,
     threadId: string,
@@ -56,6 +63,7 @@ type MessagesProps = {
     threadId: string,
     messageId: string,
   ) => void;
+  onOpenThread?: (workspaceId: string, threadId: string) => void;
 };
 
 type TodoItem = {
@@ -121,6 +129,8 @@ type ToolRowProps = {
   onOpenFileLink?: (path: string) => void;
   onOpenFileLinkMenu?: (event: React.MouseEvent, path: string) => void;
   onRequestAutoScroll?: () => void;
+  onOpenSubagentThread?: (threadId: string) => void;
+  onLoadFullOutput?: (itemId: string) => Promise<string>;
 };
 
 type ExploreRowProps = {
@@ -700,6 +710,70 @@ function buildToolSummary(
   };
 }
 
+function toolDetailBadges(
+  details: Extract<ConversationItem, { kind: "tool" }>["details"],
+): { label: string; title?: string }[] {
+  if (!details) {
+    return [];
+  }
+  switch (details.kind) {
+    case "bash":
+      return details.exitCode !== null && details.exitCode !== 0
+        ? [
+            {
+              label: `exit ${details.exitCode}`,
+              title: `Exited with code ${details.exitCode}`,
+            },
+          ]
+        : [];
+    case "grep":
+      if (details.matchCount !== null) {
+        const noun = details.matchCount === 1 ? "match" : "matches";
+        return [{ label: `${details.matchCount} ${noun}` }];
+      }
+      if (details.fileCount !== null) {
+        const noun = details.fileCount === 1 ? "file" : "files";
+        return [{ label: `${details.fileCount} ${noun}` }];
+      }
+      return [];
+    case "read":
+      if (details.lineStart === null || details.lineEnd === null) {
+        return [];
+      }
+      return [
+        {
+          label: `L${details.lineStart}–${details.lineEnd}`,
+          title: details.totalLines
+            ? `Lines ${details.lineStart}–${details.lineEnd} of ${details.totalLines}`
+            : undefined,
+        },
+      ];
+    case "webFetch":
+      return details.status !== null
+        ? [{ label: [details.status, details.statusText].filter(Boolean).join(" ") }]
+        : [];
+  }
+}
+
+function changeStatsBadges(
+  changes: Extract<ConversationItem, { kind: "tool" }>["changes"],
+): { label: string; title?: string }[] {
+  const counted = (changes ?? []).filter(
+    (change) => change.additions !== undefined && change.deletions !== undefined,
+  );
+  if (counted.length === 0) {
+    return [];
+  }
+  const added = counted.reduce((sum, change) => sum + (change.additions ?? 0), 0);
+  const removed = counted.reduce((sum, change) => sum + (change.deletions ?? 0), 0);
+  return [
+    {
+      label: `+${added} −${removed}`,
+      title: `${added} lines added, ${removed} removed`,
+    },
+  ];
+}
+
 function toolIconForSummary(
   item: Extract<ConversationItem, { kind: "tool" }>,
   summary: ToolSummary,
@@ -781,6 +855,16 @@ function toolStatusTone(
   item: Extract<ConversationItem, { kind: "tool" }>,
   hasChanges: boolean,
 ): StatusTone {
+  if (
+    item.details?.kind === "bash" &&
+    item.details.exitCode !== null &&
+    item.details.exitCode !== 0
+  ) {
+    return "failed";
+  }
+  if (item.details?.kind === "webFetch" && (item.details.status ?? 0) >= 400) {
+    return "failed";
+  }
   const fromStatus = statusToneFromText(item.status);
   if (fromStatus !== "unknown") {
     return fromStatus;
@@ -1041,13 +1125,36 @@ const DiffRow = memo(function DiffRow({ item }: DiffRowProps) {
 });
 
 const ToolRow = memo(function ToolRow({
-  item,
+  item: toolItem,
   isExpanded,
   onToggle,
   onOpenFileLink,
   onOpenFileLinkMenu,
   onRequestAutoScroll,
+  onOpenSubagentThread,
+  onLoadFullOutput,
 }: ToolRowProps) {
+  const [fullOutput, setFullOutput] = useState<string | null>(null);
+  const [isLoadingOutput, setIsLoadingOutput] = useState(false);
+  const item = fullOutput === null ? toolItem : { ...toolItem, output: fullOutput };
+  const canLoadFullOutput =
+    Boolean(toolItem.outputTruncated) && fullOutput === null && Boolean(onLoadFullOutput);
+  const loadFullOutput = async () => {
+    if (!onLoadFullOutput) {
+      return;
+    }
+    setIsLoadingOutput(true);
+    try {
+      setFullOutput(await onLoadFullOutput(toolItem.id));
+    } catch (error) {
+      pushErrorToast({
+        title: "Couldn't load full output",
+        message: error instanceof Error ? error.message : String(error),
+      });
+    } finally {
+      setIsLoadingOutput(false);
+    }
+  };
   const isFileChange = item.toolType === "fileChange";
   const isCommand = item.toolType === "commandExecution";
   const commandText = isCommand
@@ -1060,7 +1167,11 @@ const ToolRow = memo(function ToolRow({
   const hasChanges = changeNames.length > 0;
   const tone = toolStatusTone(item, hasChanges);
   const ToolIcon = toolIconForSummary(item, summary);
-  const summaryBadges = summary.badges ?? [];
+  const summaryBadges = [
+    ...(summary.badges ?? []),
+    ...toolDetailBadges(item.details),
+    ...changeStatsBadges(item.changes),
+  ];
   const summaryLabel = isFileChange
     ? changeNames.length > 1
       ? "files edited"
@@ -1084,6 +1195,21 @@ const ToolRow = memo(function ToolRow({
     typeof item.durationMs === "number" ? item.durationMs : null;
   const isLongRunning = commandDurationMs !== null && commandDurationMs >= 1200;
   const [showLiveOutput, setShowLiveOutput] = useState(false);
+  const [lightboxIndex, setLightboxIndex] = useState<number | null>(null);
+  const toolImages = useMemo(() => {
+    if (!item.images || item.images.length === 0) {
+      return [];
+    }
+    return item.images
+      .map((image, index) => {
+        const src = normalizeMessageImageSrc(image.path);
+        if (!src) {
+          return null;
+        }
+        return { src, label: `Tool image ${index + 1}` };
+      })
+      .filter(Boolean) as MessageImage[];
+  }, [item.images]);
 
   useEffect(() => {
     if (!isCommandRunning) {
@@ -1161,6 +1287,29 @@ const ToolRow = memo(function ToolRow({
             </span>
           )}
         </button>
+        {item.subagentThreadId && onOpenSubagentThread && (
+          <button
+            type="button"
+            className="tool-inline-link"
+            onClick={() => onOpenSubagentThread(item.subagentThreadId ?? "")}
+          >
+            Open subagent thread
+          </button>
+        )}
+        {isExpanded && canLoadFullOutput && (
+          <button
+            type="button"
+            className="tool-inline-link"
+            disabled={isLoadingOutput}
+            onClick={() => void loadFullOutput()}
+          >
+            {isLoadingOutput
+              ? "Loading full output…"
+              : toolItem.outputBytes
+                ? `Output truncated · load all ${Math.ceil(toolItem.outputBytes / 1024)} KB`
+                : "Output truncated · load all"}
+          </button>
+        )}
         {isExpanded && summary.detail && !isFileChange && (
           <div className="tool-inline-detail">{summary.detail}</div>
         )}
@@ -1185,6 +1334,12 @@ const ToolRow = memo(function ToolRow({
                   <span className="tool-inline-change-path">
                     {basename(change.path)}
                   </span>
+                  {change.additions !== undefined && change.deletions !== undefined && (
+                    <span className="tool-inline-change-stats">
+                      <span className="tool-inline-change-added">+{change.additions}</span>
+                      <span className="tool-inline-change-removed">−{change.deletions}</span>
+                    </span>
+                  )}
                 </div>
                 {change.diff && (
                   <div className="diff-viewer-output">
@@ -1223,6 +1378,20 @@ const ToolRow = memo(function ToolRow({
             ))}
           </div>
         )}
+        {toolImages.length > 0 && (
+          <MessageImageGrid
+            images={toolImages}
+            onOpen={setLightboxIndex}
+            hasText={false}
+          />
+        )}
+        {lightboxIndex !== null && toolImages.length > 0 && (
+          <ImageLightbox
+            images={toolImages}
+            activeIndex={lightboxIndex}
+            onClose={() => setLightboxIndex(null)}
+          />
+        )}
         {showCommandOutput && <CommandOutput output={summary.output ?? ""} />}
         {showToolOutput && summary.output && !isCommand && (
           <Markdown
@@ -1345,9 +1514,13 @@ export const Messages = memo(function Messages({
   codeBlockCopyUseModifier = false,
   userInputRequests = [],
   onUserInputSubmit,
+  planProposals = [],
+  onPlanApprove,
+  onPlanReject,
   onForkThreadFromMessage,
   onRewindThreadToMessage,
   onForkAndRewindThread,
+  onOpenThread,
 }: MessagesProps) {
   const SCROLL_THRESHOLD_PX = 120;
   const bottomRef = useRef<HTMLDivElement | null>(null);
@@ -1367,7 +1540,17 @@ export const Messages = memo(function Messages({
             (!workspaceId || request.workspace_id === workspaceId),
         )?.request_id ?? null)
       : null;
-  const scrollKey = `${scrollKeyForItems(items)}-${activeUserInputRequestId ?? "no-input"}`;
+  const activePlanProposalId =
+    threadId && planProposals.length
+      ? (planProposals.find(
+          (proposal) =>
+            proposal.threadId === threadId &&
+            (!workspaceId || proposal.workspaceId === workspaceId),
+        )?.toolUseId ?? null)
+      : null;
+  const scrollKey = `${scrollKeyForItems(items)}-${activeUserInputRequestId ?? "no-input"}-${
+    activePlanProposalId ?? "no-plan"
+  }`;
   const { openFileLink, showFileLinkMenu } = useFileLinkOpener(workspacePath);
   const showMessageMenu = useMessageMenu();
 
@@ -1400,6 +1583,21 @@ export const Messages = memo(function Messages({
   useEffect(() => {
     autoScrollRef.current = true;
   }, [threadId]);
+  const openSubagentThread = useMemo(
+    () =>
+      workspaceId && onOpenThread
+        ? (subagentThreadId: string) => onOpenThread(workspaceId, subagentThreadId)
+        : undefined,
+    [onOpenThread, workspaceId],
+  );
+  const loadFullOutput = useMemo(
+    () =>
+      workspaceId && threadId
+        ? (itemId: string) => getItemFullOutput(workspaceId, threadId, itemId)
+        : undefined,
+    [threadId, workspaceId],
+  );
+
   const toggleExpanded = useCallback((id: string) => {
     setExpandedItems((prev) => {
       const next = new Set(prev);
@@ -1546,6 +1744,16 @@ export const Messages = memo(function Messages({
         onSubmit={onUserInputSubmit}
       />
     ) : null;
+  const planApprovalNode =
+    activePlanProposalId !== null && onPlanApprove && onPlanReject ? (
+      <PlanApprovalMessage
+        proposals={planProposals}
+        activeThreadId={threadId}
+        activeWorkspaceId={workspaceId}
+        onApprove={onPlanApprove}
+        onReject={onPlanReject}
+      />
+    ) : null;
 
   const renderItem = (item: ConversationItem) => {
     if (item.kind === "message") {
@@ -1600,6 +1808,8 @@ export const Messages = memo(function Messages({
           onOpenFileLink={openFileLink}
           onOpenFileLinkMenu={showFileLinkMenu}
           onRequestAutoScroll={requestAutoScroll}
+          onOpenSubagentThread={openSubagentThread}
+          onLoadFullOutput={loadFullOutput}
         />
       );
     }
@@ -1659,6 +1869,7 @@ export const Messages = memo(function Messages({
         return renderItem(entry.item);
       })}
       {userInputNode}
+      {planApprovalNode}
       <WorkingIndicator
         isThinking={isThinking}
         processingStartedAt={processingStartedAt}
@@ -1666,7 +1877,7 @@ export const Messages = memo(function Messages({
         hasItems={items.length > 0}
         reasoningLabel={latestReasoningLabel}
       />
-      {!items.length && !userInputNode && (
+      {!items.length && !userInputNode && !planApprovalNode && (
         <div className="empty messages-empty">
           Start a thread and send a prompt to the agent.
         </div>
@@ -15,6 +15,7 @@ import { SettingsView } from "./SettingsView";
 vi.mock("@tauri-apps/plugin-dialog", () => ({
   ask: vi.fn(),
   open: vi.fn(),
+  save: vi.fn(),
 }));
 
 const baseSettings: AppSettings = {
@@ -22,6 +23,14 @@ const baseSettings: AppSettings = {
   backendMode: "local",
   remoteBackendHost: "127.0.0.1:4732",
   remoteBackendToken: null,
+  remoteSshTunnel: null,
+  otlpEndpoint: null,
+  presenceSharingEnabled: false,
+  allowedWorkspaceRoots: [],
+  approvalTimeoutSecs: 0,
+  approvalTimeoutDecision: "decline",
+  eventOutputLimitBytes: 16384,
+  promptDiffExcludes: [],
   defaultAccessMode: "current",
   composerModelShortcut: null,
   composerAccessShortcut: null,
@@ -55,6 +64,7 @@ const baseSettings: AppSettings = {
   dictationModelId: "base",
   dictationPreferredLanguage: null,
   dictationHoldKey: null,
+  dictationVoiceCommands: false,
   composerEditorPreset: "default",
   composerFenceExpandOnSpace: false,
   composerFenceExpandOnEnter: false,
@@ -65,6 +75,20 @@ const baseSettings: AppSettings = {
   composerListContinuation: false,
   composerCodeBlockCopyUseModifier: false,
   workspaceGroups: [],
+  dataRetentionDays: null,
+  turnReportsEnabled: false,
+  turnRecordingEnabled: false,
+  turnCheckpointsEnabled: false,
+  threadSummariesEnabled: false,
+  ciStatusPollingEnabled: false,
+  maxParallelTurns: null,
+  accelerators: {},
+  updateChannel: "stable",
+  logLevel: null,
+  logLevels: {},
+  fullAccessSandbox: null,
+  dangerousCommandGuardEnabled: false,
+  dangerousCommandPatterns: null,
 };
 
 const createDoctorResult = () => ({
@@ -1,5 +1,5 @@
 import { useEffect, useMemo, useState } from "react";
-import { ask, open } from "@tauri-apps/plugin-dialog";
+import { ask, open, save } from "@tauri-apps/plugin-dialog";
 import ChevronDown from "lucide-react/dist/esm/icons/chevron-down";
 import ChevronUp from "lucide-react/dist/esm/icons/chevron-up";
 import LayoutGrid from "lucide-react/dist/esm/icons/layout-grid";
@@ -15,16 +15,19 @@ import FlaskConical from "lucide-react/dist/esm/icons/flask-conical";
 import { useGlobalClaudeSettings } from "../hooks/useGlobalClaudeSettings";
 import { FileEditorCard } from "../../shared/components/FileEditorCard";
 import { useGlobalClaudeMd } from "../hooks/useGlobalClaudeMd";
+import { useSettingsTransfer } from "../hooks/useSettingsTransfer";
 import type {
   AppSettings,
   ClaudeDoctorResult,
   DictationModelStatus,
+  SettingsImportSummary,
   WorkspaceGroup,
   WorkspaceInfo,
 } from "../../../types";
 import { formatDownloadSize } from "../../../utils/formatting";
 import { buildShortcutValue, formatShortcut } from "../../../utils/shortcuts";
 import { clampUiScale } from "../../../utils/uiScale";
+import { setAllowedWorkspaceRoots } from "../../../services/tauri";
 import {
   DEFAULT_CODE_FONT_FAMILY,
   DEFAULT_UI_FONT_FAMILY,
@@ -115,6 +118,7 @@ export type SettingsViewProps = {
     workspaceId: string,
     groupId: string | null,
   ) => Promise<boolean | null>;
+  onToggleWorkspaceAutoConnect?: (workspaceId: string, enabled: boolean) => void;
   reduceTransparency: boolean;
   onToggleTransparency: (value: boolean) => void;
   appSettings: AppSettings;
@@ -128,6 +132,7 @@ export type SettingsViewProps = {
   onDownloadDictationModel?: () => void;
   onCancelDictationDownload?: () => void;
   onRemoveDictationModel?: () => void;
+  onSettingsImported?: (summary: SettingsImportSummary) => void;
   initialSection?: ClaudeSection;
 };
 
@@ -193,6 +198,7 @@ export function SettingsView({
   onMoveWorkspaceGroup,
   onDeleteWorkspaceGroup,
   onAssignWorkspaceGroup,
+  onToggleWorkspaceAutoConnect,
   reduceTransparency,
   onToggleTransparency,
   appSettings,
@@ -206,12 +212,22 @@ export function SettingsView({
   onDownloadDictationModel,
   onCancelDictationDownload,
   onRemoveDictationModel,
+  onSettingsImported,
   initialSection,
 }: SettingsViewProps) {
   const [activeSection, setActiveSection] = useState<ClaudeSection>("projects");
   const [claudePathDraft, setClaudePathDraft] = useState(appSettings.claudeBin ?? "");
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
   const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
+  const [otlpEndpointDraft, setOtlpEndpointDraft] = useState(
+    appSettings.otlpEndpoint ?? "",
+  );
+  const [allowedRootsDraft, setAllowedRootsDraft] = useState(
+    (appSettings.allowedWorkspaceRoots ?? []).join(", "),
+  );
+  const [diffExcludesDraft, setDiffExcludesDraft] = useState(
+    (appSettings.promptDiffExcludes ?? []).join(", "),
+  );
   const [scaleDraft, setScaleDraft] = useState(
     `${Math.round(clampUiScale(appSettings.uiScale) * 100)}%`,
   );
@@ -224,6 +240,7 @@ export function SettingsView({
   const [groupDrafts, setGroupDrafts] = useState<Record<string, string>>({});
   const [newGroupName, setNewGroupName] = useState("");
   const [groupError, setGroupError] = useState<string | null>(null);
+  const [profileName, setProfileName] = useState("");
   const [doctorState, setDoctorState] = useState<{
     status: "idle" | "running" | "done";
     result: ClaudeDoctorResult | null;
@@ -249,6 +266,9 @@ export function SettingsView({
   const dictationProgress = dictationModelStatus?.progress ?? null;
   const globalClaudeSettings = useGlobalClaudeSettings();
   const globalClaudeMd = useGlobalClaudeMd();
+  const settingsTransfer = useSettingsTransfer((summary) => {
+    onSettingsImported?.(summary);
+  });
   const selectedDictationModel = useMemo(() => {
     return (
       DICTATION_MODELS.find(
@@ -301,6 +321,18 @@ export function SettingsView({
     setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
   }, [appSettings.remoteBackendToken]);
 
+  useEffect(() => {
+    setOtlpEndpointDraft(appSettings.otlpEndpoint ?? "");
+  }, [appSettings.otlpEndpoint]);
+
+  useEffect(() => {
+    setAllowedRootsDraft((appSettings.allowedWorkspaceRoots ?? []).join(", "));
+  }, [appSettings.allowedWorkspaceRoots]);
+
+  useEffect(() => {
+    setDiffExcludesDraft((appSettings.promptDiffExcludes ?? []).join(", "));
+  }, [appSettings.promptDiffExcludes]);
+
   useEffect(() => {
     setScaleDraft(`${Math.round(clampUiScale(appSettings.uiScale) * 100)}%`);
   }, [appSettings.uiScale]);
@@ -423,6 +455,58 @@ export function SettingsView({
     });
   };
 
+  const handleCommitOtlpEndpoint = async () => {
+    const nextEndpoint = otlpEndpointDraft.trim() ? otlpEndpointDraft.trim() : null;
+    setOtlpEndpointDraft(nextEndpoint ?? "");
+    if (nextEndpoint === appSettings.otlpEndpoint) {
+      return;
+    }
+    await onUpdateAppSettings({
+      ...appSettings,
+      otlpEndpoint: nextEndpoint,
+    });
+  };
+
+  const handleCommitAllowedRoots = async () => {
+    const nextRoots = allowedRootsDraft
+      .split(",")
+      .map((root) => root.trim())
+      .filter(Boolean);
+    setAllowedRootsDraft(nextRoots.join(", "));
+    const currentRoots = appSettings.allowedWorkspaceRoots ?? [];
+    if (nextRoots.join("\n") === currentRoots.join("\n")) {
+      return;
+    }
+    // The backend asks for confirmation and keeps the old list if declined.
+    let savedRoots: string[];
+    try {
+      savedRoots = await setAllowedWorkspaceRoots(nextRoots);
+    } catch {
+      setAllowedRootsDraft(currentRoots.join(", "));
+      return;
+    }
+    await onUpdateAppSettings({
+      ...appSettings,
+      allowedWorkspaceRoots: savedRoots,
+    });
+  };
+
+  const handleCommitDiffExcludes = async () => {
+    const nextPatterns = diffExcludesDraft
+      .split(",")
+      .map((pattern) => pattern.trim())
+      .filter(Boolean);
+    setDiffExcludesDraft(nextPatterns.join(", "));
+    const currentPatterns = appSettings.promptDiffExcludes ?? [];
+    if (nextPatterns.join("\n") === currentPatterns.join("\n")) {
+      return;
+    }
+    await onUpdateAppSettings({
+      ...appSettings,
+      promptDiffExcludes: nextPatterns,
+    });
+  };
+
   const handleCommitScale = async () => {
     if (parsedScale === null) {
       setScaleDraft(`${Math.round(clampUiScale(appSettings.uiScale) * 100)}%`);
@@ -502,6 +586,38 @@ export function SettingsView({
     });
   };
 
+  const handleExportSettings = async () => {
+    const path = await save({
+      defaultPath: "claude-code-monitor-settings.json",
+      filters: [{ name: "Settings", extensions: ["json"] }],
+    });
+    if (!path) {
+      return;
+    }
+    await settingsTransfer.exportTo(path);
+  };
+
+  const handleImportSettings = async () => {
+    const selection = await open({
+      multiple: false,
+      directory: false,
+      filters: [{ name: "Settings", extensions: ["json"] }],
+    });
+    if (!selection || Array.isArray(selection)) {
+      return;
+    }
+    await settingsTransfer.importFrom(selection);
+  };
+
+  const handleSaveProfile = async () => {
+    const name = profileName.trim();
+    if (!name) {
+      return;
+    }
+    await settingsTransfer.saveProfile(name);
+    setProfileName("");
+  };
+
   const handleBrowseClaude = async () => {
     const selection = await open({ multiple: false, directory: false });
     if (!selection || Array.isArray(selection)) {
@@ -908,6 +1024,25 @@ export function SettingsView({
                                   </option>
                                 ))}
                               </select>
+                              {onToggleWorkspaceAutoConnect && (
+                                <button
+                                  type="button"
+                                  className={`settings-toggle ${
+                                    workspace.settings.autoConnect ? "on" : ""
+                                  }`}
+                                  onClick={() =>
+                                    onToggleWorkspaceAutoConnect(
+                                      workspace.id,
+                                      !workspace.settings.autoConnect,
+                                    )
+                                  }
+                                  aria-pressed={Boolean(workspace.settings.autoConnect)}
+                                  aria-label="Connect on launch"
+                                  title="Connect on launch"
+                                >
+                                  <span className="settings-toggle-knob" />
+                                </button>
+                              )}
                               <button
                                 type="button"
                                 className="ghost icon-button"
@@ -944,6 +1079,106 @@ export function SettingsView({
                     <div className="settings-empty">No projects yet.</div>
                   )}
                 </div>
+                <div className="settings-subsection-title">Backup &amp; profiles</div>
+                <div className="settings-subsection-subtitle">
+                  Move settings, global prompts, and projects to another machine, or
+                  switch between saved setups. The remote backend token is never
+                  exported.
+                </div>
+                <div className="settings-groups">
+                  <div className="settings-group-create">
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={() => {
+                        void handleExportSettings();
+                      }}
+                      disabled={settingsTransfer.isBusy}
+                    >
+                      Export…
+                    </button>
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={() => {
+                        void handleImportSettings();
+                      }}
+                      disabled={settingsTransfer.isBusy}
+                    >
+                      Import…
+                    </button>
+                  </div>
+                  <div className="settings-group-create">
+                    <input
+                      className="settings-input settings-input--compact"
+                      value={profileName}
+                      placeholder="Profile name"
+                      aria-label="Profile name"
+                      onChange={(event) => setProfileName(event.target.value)}
+                      onKeyDown={(event) => {
+                        if (event.key === "Enter") {
+                          event.preventDefault();
+                          void handleSaveProfile();
+                        }
+                      }}
+                    />
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={() => {
+                        void handleSaveProfile();
+                      }}
+                      disabled={!profileName.trim() || settingsTransfer.isBusy}
+                    >
+                      Save profile
+                    </button>
+                  </div>
+                  {settingsTransfer.error && (
+                    <div className="settings-group-error">{settingsTransfer.error}</div>
+                  )}
+                  {settingsTransfer.status && (
+                    <div className="settings-help">{settingsTransfer.status}</div>
+                  )}
+                  {settingsTransfer.profiles.length > 0 ? (
+                    <div className="settings-group-list">
+                      {settingsTransfer.profiles.map((profile) => (
+                        <div key={profile.name} className="settings-group-row">
+                          <div className="settings-group-fields">
+                            <div className="settings-project-name">{profile.name}</div>
+                            <div className="settings-project-path">
+                              Saved {new Date(profile.savedAt).toLocaleString()}
+                            </div>
+                          </div>
+                          <div className="settings-group-actions">
+                            <button
+                              type="button"
+                              className="ghost settings-button-compact"
+                              onClick={() => {
+                                void settingsTransfer.applyProfile(profile.name);
+                              }}
+                              disabled={settingsTransfer.isBusy}
+                            >
+                              Apply
+                            </button>
+                            <button
+                              type="button"
+                              className="ghost icon-button"
+                              onClick={() => {
+                                void settingsTransfer.deleteProfile(profile.name);
+                              }}
+                              disabled={settingsTransfer.isBusy}
+                              aria-label={`Delete profile ${profile.name}`}
+                            >
+                              <Trash2 aria-hidden />
+                            </button>
+                          </div>
+                        </div>
+                      ))}
+                    </div>
+                  ) : (
+                    <div className="settings-empty">No saved profiles.</div>
+                  )}
+                </div>
               </section>
             )}
             {activeSection === "display" && (
@@ -1551,6 +1786,28 @@ export function SettingsView({
                     Hold the key to start dictation, release to stop and process.
                   </div>
                 </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Voice commands</div>
+                    <div className="settings-toggle-subtitle">
+                      End with "send" to submit, say "new line" for a line break, or say just
+                      "cancel" to clear the composer.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.dictationVoiceCommands ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        dictationVoiceCommands: !appSettings.dictationVoiceCommands,
+                      })
+                    }
+                    aria-pressed={appSettings.dictationVoiceCommands}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
                 {dictationModelStatus && (
                   <div className="settings-field">
                     <div className="settings-field-label">
@@ -2152,6 +2409,184 @@ export function SettingsView({
                   </div>
                 )}
 
+                {appSettings.backendMode === "remote" && (
+                  <div className="settings-toggle-row">
+                    <div>
+                      <div className="settings-toggle-title">Share presence</div>
+                      <div className="settings-toggle-subtitle">
+                        Show other clients of the daemon which thread you are on and when you
+                        are typing. The daemon must support presence.
+                      </div>
+                    </div>
+                    <button
+                      type="button"
+                      className={`settings-toggle ${
+                        appSettings.presenceSharingEnabled ? "on" : ""
+                      }`}
+                      onClick={() =>
+                        void onUpdateAppSettings({
+                          ...appSettings,
+                          presenceSharingEnabled: !appSettings.presenceSharingEnabled,
+                        })
+                      }
+                      aria-pressed={appSettings.presenceSharingEnabled}
+                    >
+                      <span className="settings-toggle-knob" />
+                    </button>
+                  </div>
+                )}
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="otlp-endpoint">
+                    Metrics export (OTLP)
+                  </label>
+                  <input
+                    id="otlp-endpoint"
+                    className="settings-input"
+                    value={otlpEndpointDraft}
+                    placeholder="http://otel-collector:4318"
+                    onChange={(event) => setOtlpEndpointDraft(event.target.value)}
+                    onBlur={() => {
+                      void handleCommitOtlpEndpoint();
+                    }}
+                    onKeyDown={(event) => {
+                      if (event.key === "Enter") {
+                        event.preventDefault();
+                        void handleCommitOtlpEndpoint();
+                      }
+                    }}
+                  />
+                  <div className="settings-help">
+                    When set, the app exports turn, error, token and session metrics to this
+                    collector. Leave empty to use <code>OTEL_EXPORTER_OTLP_ENDPOINT</code>.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="allowed-workspace-roots">
+                    Allowed workspace roots
+                  </label>
+                  <input
+                    id="allowed-workspace-roots"
+                    className="settings-input"
+                    value={allowedRootsDraft}
+                    placeholder="Any folder"
+                    onChange={(event) => setAllowedRootsDraft(event.target.value)}
+                    onBlur={() => {
+                      void handleCommitAllowedRoots();
+                    }}
+                    onKeyDown={(event) => {
+                      if (event.key === "Enter") {
+                        event.preventDefault();
+                        void handleCommitAllowedRoots();
+                      }
+                    }}
+                  />
+                  <div className="settings-help">
+                    Comma-separated folders. When set, new workspaces, clones and worktrees must
+                    live under one of them. Set this on the daemon host before exposing it on a
+                    shared machine.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="approval-timeout">
+                    Unanswered approvals
+                  </label>
+                  <div className="settings-field-row">
+                    <select
+                      id="approval-timeout"
+                      className="settings-select settings-select--compact"
+                      value={appSettings.approvalTimeoutSecs}
+                      onChange={(event) =>
+                        void onUpdateAppSettings({
+                          ...appSettings,
+                          approvalTimeoutSecs: Number(event.target.value),
+                        })
+                      }
+                    >
+                      <option value={0}>Wait forever</option>
+                      <option value={60}>After 1 minute</option>
+                      <option value={300}>After 5 minutes</option>
+                      <option value={900}>After 15 minutes</option>
+                    </select>
+                    <select
+                      className="settings-select settings-select--compact"
+                      value={appSettings.approvalTimeoutDecision}
+                      disabled={appSettings.approvalTimeoutSecs === 0}
+                      onChange={(event) =>
+                        void onUpdateAppSettings({
+                          ...appSettings,
+                          approvalTimeoutDecision: event.target
+                            .value as AppSettings["approvalTimeoutDecision"],
+                        })
+                      }
+                      aria-label="Decision when an approval times out"
+                    >
+                      <option value="decline">Decline</option>
+                      <option value="accept">Accept</option>
+                    </select>
+                  </div>
+                  <div className="settings-help">
+                    Permission prompts go to every connected client and the first answer wins.
+                    If nobody answers in time, the chosen decision is sent for you.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="event-output-limit">
+                    Live tool output
+                  </label>
+                  <select
+                    id="event-output-limit"
+                    className="settings-select settings-select--compact"
+                    value={appSettings.eventOutputLimitBytes}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        eventOutputLimitBytes: Number(event.target.value),
+                      })
+                    }
+                  >
+                    <option value={4096}>4 KB</option>
+                    <option value={16384}>16 KB</option>
+                    <option value={65536}>64 KB</option>
+                    <option value={262144}>256 KB</option>
+                    <option value={0}>No limit</option>
+                  </select>
+                  <div className="settings-help">
+                    Longer tool output is cut in live updates. Expand the tool and load the full
+                    output from the transcript when you need it.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="prompt-diff-excludes">
+                    Leave out of commit and review diffs
+                  </label>
+                  <input
+                    id="prompt-diff-excludes"
+                    className="settings-input"
+                    value={diffExcludesDraft}
+                    placeholder="Include every file"
+                    onChange={(event) => setDiffExcludesDraft(event.target.value)}
+                    onBlur={() => {
+                      void handleCommitDiffExcludes();
+                    }}
+                    onKeyDown={(event) => {
+                      if (event.key === "Enter") {
+                        event.preventDefault();
+                        void handleCommitDiffExcludes();
+                      }
+                    }}
+                  />
+                  <div className="settings-help">
+                    Comma-separated .gitignore-style patterns for lockfiles, generated code and
+                    vendored folders. Matching files are named in the prompt but their diffs are
+                    not sent.
+                  </div>
+                </div>
+
                 <div className="settings-field">
                   <div className="settings-field-label">Workspace overrides</div>
                   <div className="settings-overrides">
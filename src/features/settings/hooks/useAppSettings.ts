@@ -17,6 +17,28 @@ const defaultSettings: AppSettings = {
   backendMode: "local",
   remoteBackendHost: "127.0.0.1:4732",
   remoteBackendToken: null,
+  remoteSshTunnel: null,
+  otlpEndpoint: null,
+  presenceSharingEnabled: false,
+  allowedWorkspaceRoots: [],
+  approvalTimeoutSecs: 0,
+  approvalTimeoutDecision: "decline",
+  eventOutputLimitBytes: 16384,
+  promptDiffExcludes: [
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "Cargo.lock",
+    "Gemfile.lock",
+    "poetry.lock",
+    "go.sum",
+    "*.min.js",
+    "*.min.css",
+    "*.map",
+    "vendor/",
+    "node_modules/",
+  ],
   defaultAccessMode: "current",
   composerModelShortcut: "cmd+shift+m",
   composerAccessShortcut: "cmd+shift+a",
@@ -48,6 +70,7 @@ const defaultSettings: AppSettings = {
   dictationModelId: "base",
   dictationPreferredLanguage: null,
   dictationHoldKey: "alt",
+  dictationVoiceCommands: false,
   composerEditorPreset: "default",
   composerFenceExpandOnSpace: false,
   composerFenceExpandOnEnter: false,
@@ -58,6 +81,20 @@ const defaultSettings: AppSettings = {
   composerListContinuation: false,
   composerCodeBlockCopyUseModifier: false,
   workspaceGroups: [],
+  dataRetentionDays: null,
+  turnReportsEnabled: false,
+  turnRecordingEnabled: false,
+  turnCheckpointsEnabled: false,
+  threadSummariesEnabled: false,
+  ciStatusPollingEnabled: false,
+  maxParallelTurns: null,
+  accelerators: {},
+  updateChannel: "stable",
+  logLevel: null,
+  logLevels: {},
+  fullAccessSandbox: null,
+  dangerousCommandGuardEnabled: false,
+  dangerousCommandPatterns: null,
 };
 
 function normalizeAppSettings(settings: AppSettings): AppSettings {
@@ -1,5 +1,5 @@
 import { useCallback, useMemo } from "react";
-import { fileRead, fileWrite } from "../../../services/tauri";
+import { fileRead, fileWrite, type FileVersion } from "../../../services/tauri";
 import { useFileEditor } from "../../shared/hooks/useFileEditor";
 
 // Use a constant key since global CLAUDE.md is not workspace-specific
@@ -10,9 +10,12 @@ export function useGlobalClaudeMd() {
     return fileRead("global", "claude_md");
   }, []);
 
-  const write = useCallback(async (content: string) => {
-    return fileWrite("global", "claude_md", content);
-  }, []);
+  const write = useCallback(
+    async (content: string, expectedVersion: FileVersion | null) => {
+      return fileWrite("global", "claude_md", content, null, expectedVersion);
+    },
+    [],
+  );
 
   const editor = useFileEditor({
     key: GLOBAL_CLAUDE_MD_KEY,
@@ -1,5 +1,5 @@
 import { useCallback, useMemo } from "react";
-import { fileRead, fileWrite } from "../../../services/tauri";
+import { fileRead, fileWrite, type FileVersion } from "../../../services/tauri";
 import { useFileEditor } from "../../shared/hooks/useFileEditor";
 
 // Use a constant key since global settings is not workspace-specific
@@ -10,9 +10,12 @@ export function useGlobalClaudeSettings() {
     return fileRead("global", "settings");
   }, []);
 
-  const write = useCallback(async (content: string) => {
-    return fileWrite("global", "settings", content);
-  }, []);
+  const write = useCallback(
+    async (content: string, expectedVersion: FileVersion | null) => {
+      return fileWrite("global", "settings", content, null, expectedVersion);
+    },
+    [],
+  );
 
   const editor = useFileEditor({
     key: GLOBAL_SETTINGS_KEY,
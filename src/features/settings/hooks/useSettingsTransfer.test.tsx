@@ -0,0 +1,72 @@
+// @vitest-environment jsdom
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import type { AppSettings, SettingsImportSummary } from "../../../types";
+import {
+  applySettingsProfile,
+  importAppSettings,
+  listSettingsProfiles,
+  saveSettingsProfile,
+} from "../../../services/tauri";
+import { formatImportSummary, useSettingsTransfer } from "./useSettingsTransfer";
+
+vi.mock("../../../services/tauri", () => ({
+  applySettingsProfile: vi.fn(),
+  deleteSettingsProfile: vi.fn(),
+  exportAppSettings: vi.fn(),
+  importAppSettings: vi.fn(),
+  listSettingsProfiles: vi.fn(),
+  saveSettingsProfile: vi.fn(),
+}));
+
+const summary: SettingsImportSummary = {
+  settings: { theme: "dark" } as AppSettings,
+  promptsImported: 2,
+  promptsSkipped: 1,
+  workspacesImported: 1,
+  workspacesSkipped: 0,
+  acceleratorsFailed: 0,
+};
+
+describe("useSettingsTransfer", () => {
+  it("formats import results", () => {
+    expect(formatImportSummary(summary)).toBe(
+      "Settings applied; 2 prompts and 1 project added, 1 already present or unavailable.",
+    );
+  });
+
+  it("lists profiles and refreshes them after saving", async () => {
+    vi.mocked(listSettingsProfiles).mockResolvedValueOnce([]);
+    vi.mocked(listSettingsProfiles).mockResolvedValueOnce([
+      { name: "work", savedAt: 1 },
+    ]);
+    vi.mocked(saveSettingsProfile).mockResolvedValue({ name: "work", savedAt: 1 });
+    const { result } = renderHook(() => useSettingsTransfer(vi.fn()));
+
+    await waitFor(() => expect(listSettingsProfiles).toHaveBeenCalled());
+    await act(async () => {
+      await result.current.saveProfile("work");
+    });
+    expect(result.current.profiles).toEqual([{ name: "work", savedAt: 1 }]);
+    expect(result.current.status).toBe('Saved profile "work".');
+  });
+
+  it("hands imported settings to the caller and surfaces errors", async () => {
+    vi.mocked(listSettingsProfiles).mockResolvedValue([]);
+    vi.mocked(importAppSettings).mockResolvedValue(summary);
+    vi.mocked(applySettingsProfile).mockRejectedValue(new Error("Profile not found."));
+    const onImported = vi.fn();
+    const { result } = renderHook(() => useSettingsTransfer(onImported));
+
+    await act(async () => {
+      await result.current.importFrom("/tmp/settings.json");
+    });
+    expect(onImported).toHaveBeenCalledWith(summary);
+
+    await act(async () => {
+      await result.current.applyProfile("missing");
+    });
+    expect(result.current.error).toBe("Profile not found.");
+    expect(result.current.isBusy).toBe(false);
+  });
+});
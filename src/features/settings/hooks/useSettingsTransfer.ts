@@ -0,0 +1,128 @@
+import { useCallback, useEffect, useState } from "react";
+import type { SettingsImportSummary, SettingsProfile } from "../../../types";
+import {
+  applySettingsProfile,
+  deleteSettingsProfile,
+  exportAppSettings,
+  importAppSettings,
+  listSettingsProfiles,
+  saveSettingsProfile,
+} from "../../../services/tauri";
+
+function plural(count: number, noun: string) {
+  return `${count} ${noun}${count === 1 ? "" : "s"}`;
+}
+
+export function formatImportSummary(summary: SettingsImportSummary) {
+  const parts = [
+    `${plural(summary.promptsImported, "prompt")} and ${plural(
+      summary.workspacesImported,
+      "project",
+    )} added`,
+  ];
+  const skipped = summary.promptsSkipped + summary.workspacesSkipped;
+  if (skipped > 0) {
+    parts.push(`${skipped} already present or unavailable`);
+  }
+  if (summary.acceleratorsFailed > 0) {
+    parts.push(`${plural(summary.acceleratorsFailed, "shortcut")} could not be set`);
+  }
+  return `Settings applied; ${parts.join(", ")}.`;
+}
+
+export function useSettingsTransfer(
+  onImported: (summary: SettingsImportSummary) => void,
+) {
+  const [profiles, setProfiles] = useState<SettingsProfile[]>([]);
+  const [status, setStatus] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+
+  const refreshProfiles = useCallback(async () => {
+    try {
+      setProfiles(await listSettingsProfiles());
+    } catch {
+      // Profiles are optional; an unreadable folder just shows none.
+    }
+  }, []);
+
+  useEffect(() => {
+    void refreshProfiles();
+  }, [refreshProfiles]);
+
+  const run = useCallback(
+    async (action: () => Promise<string>) => {
+      setIsBusy(true);
+      setError(null);
+      setStatus(null);
+      try {
+        setStatus(await action());
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setIsBusy(false);
+      }
+    },
+    [],
+  );
+
+  const applySummary = useCallback(
+    (summary: SettingsImportSummary) => {
+      onImported(summary);
+      return formatImportSummary(summary);
+    },
+    [onImported],
+  );
+
+  const exportTo = useCallback(
+    (path: string) =>
+      run(async () => {
+        await exportAppSettings(path);
+        return `Exported to ${path}.`;
+      }),
+    [run],
+  );
+
+  const importFrom = useCallback(
+    (path: string) => run(async () => applySummary(await importAppSettings(path))),
+    [applySummary, run],
+  );
+
+  const saveProfile = useCallback(
+    (name: string) =>
+      run(async () => {
+        const profile = await saveSettingsProfile(name);
+        await refreshProfiles();
+        return `Saved profile "${profile.name}".`;
+      }),
+    [refreshProfiles, run],
+  );
+
+  const applyProfile = useCallback(
+    (name: string) =>
+      run(async () => applySummary(await applySettingsProfile(name))),
+    [applySummary, run],
+  );
+
+  const deleteProfile = useCallback(
+    (name: string) =>
+      run(async () => {
+        await deleteSettingsProfile(name);
+        await refreshProfiles();
+        return `Deleted profile "${name}".`;
+      }),
+    [refreshProfiles, run],
+  );
+
+  return {
+    profiles,
+    status,
+    error,
+    isBusy,
+    exportTo,
+    importFrom,
+    saveProfile,
+    applyProfile,
+    deleteProfile,
+  };
+}
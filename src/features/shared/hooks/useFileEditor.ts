@@ -1,16 +1,22 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
+import {
+  fileMergeThreeWay,
+  type FileVersion,
+  type FileWriteResult,
+} from "../../../services/tauri";
 import { pushErrorToast } from "../../../services/toasts";
 
 export type FileEditorResponse = {
   exists: boolean;
   content: string;
   truncated: boolean;
+  version?: FileVersion | null;
 };
 
 type UseFileEditorOptions = {
   key: string | null;
   read: () => Promise<FileEditorResponse>;
-  write: (content: string) => Promise<void>;
+  write: (content: string, expectedVersion: FileVersion | null) => Promise<FileWriteResult>;
   readErrorTitle: string;
   writeErrorTitle: string;
 };
@@ -42,6 +48,7 @@ export function useFileEditor({
 }: UseFileEditorOptions) {
   const [state, setState] = useState<FileEditorState>(EMPTY_STATE);
   const lastLoadedContentRef = useRef<string>("");
+  const versionRef = useRef<FileVersion | null>(null);
   const requestIdRef = useRef(0);
   const inFlightRef = useRef(false);
   const latestKeyRef = useRef<string | null>(key);
@@ -68,6 +75,7 @@ export function useFileEditor({
         return;
       }
       lastLoadedContentRef.current = response.content;
+      versionRef.current = response.version ?? null;
       setState({
         content: response.content,
         exists: response.exists,
@@ -101,13 +109,51 @@ export function useFileEditor({
     const content = state.content;
     setState((prev) => ({ ...prev, isSaving: true, error: null }));
     try {
-      await write(content);
+      let saved = content;
+      let result = await write(content, versionRef.current);
+      if (result.status === "conflict") {
+        // The file changed on disk (e.g. Claude edited it): merge instead of clobbering.
+        const current = result.current;
+        const merged = await fileMergeThreeWay(
+          lastLoadedContentRef.current,
+          content,
+          current.content,
+        );
+        if (merged.clean) {
+          saved = merged.content;
+          result = await write(saved, current.version ?? null);
+        }
+        if (!merged.clean || result.status === "conflict") {
+          if (requestId !== requestIdRef.current || keyAtRequest !== latestKeyRef.current) {
+            return false;
+          }
+          const latest = result.status === "conflict" ? result.current : current;
+          lastLoadedContentRef.current = latest.content;
+          versionRef.current = latest.version ?? null;
+          const message =
+            "The file changed on disk. Resolve the conflict markers and save again.";
+          setState((prev) => ({
+            ...prev,
+            content: merged.content,
+            exists: latest.exists,
+            isSaving: false,
+            error: message,
+          }));
+          pushErrorToast({
+            title: writeErrorTitle,
+            message,
+          });
+          return false;
+        }
+      }
       if (requestId !== requestIdRef.current || keyAtRequest !== latestKeyRef.current) {
         return false;
       }
-      lastLoadedContentRef.current = content;
+      lastLoadedContentRef.current = saved;
+      versionRef.current = result.version;
       setState((prev) => ({
         ...prev,
+        content: saved,
         exists: true,
         truncated: false,
         isSaving: false,
@@ -136,6 +182,7 @@ export function useFileEditor({
     requestIdRef.current += 1;
     inFlightRef.current = false;
     lastLoadedContentRef.current = "";
+    versionRef.current = null;
     setState(EMPTY_STATE);
     if (!key) {
       return;
@@ -1,6 +1,6 @@
 import { useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { ClaudeTask, TaskUpdate } from '../types';
+import type { ClaudeTask, TaskExecuteResponse, TaskUpdate } from '../types';
 
 export function useTaskActions(listId: string | null) {
   const createTask = useCallback(async (subject: string, description: string, activeForm?: string) => {
@@ -18,5 +18,15 @@ export function useTaskActions(listId: string | null) {
     return invoke<void>('task_delete', { listId, taskId });
   }, [listId]);
 
-  return { createTask, updateTask, deleteTask };
+  const executeTask = useCallback(async (
+    taskId: string,
+    workspaceId: string,
+    accessMode?: string,
+    model?: string,
+  ) => {
+    if (!listId) throw new Error('No list ID');
+    return invoke<TaskExecuteResponse>('task_execute', { workspaceId, listId, taskId, accessMode, model });
+  }, [listId]);
+
+  return { createTask, updateTask, deleteTask, executeTask };
 }
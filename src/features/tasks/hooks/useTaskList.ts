@@ -1,7 +1,7 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { ClaudeTask, TaskListResponse } from '../types';
+import type { ClaudeTask, TaskChangeEvent, TaskListResponse } from '../types';
 
 export function useTaskList(listId: string | null) {
   const [tasks, setTasks] = useState<ClaudeTask[]>([]);
@@ -34,7 +34,14 @@ export function useTaskList(listId: string | null) {
       // Silently fail - watcher is optional enhancement
     });
 
-    const unlisten = listen(`task-list-changed:${listId}`, () => fetchTasks());
+    // Apply granular changes in place instead of re-reading the whole list
+    const unlisten = listen<TaskChangeEvent>(`task-event:${listId}`, ({ payload }) => {
+      setTasks(prev => {
+        const rest = prev.filter(t => t.id !== payload.taskId);
+        if (payload.kind === 'task/deleted' || !payload.task) return rest;
+        return [...rest, payload.task].sort((a, b) => Number(a.id) - Number(b.id));
+      });
+    });
 
     return () => {
       unlisten.then(fn => fn());
@@ -49,7 +56,9 @@ export function useTaskList(listId: string | null) {
 
   const availableTasks = tasks.filter(t =>
     t.status === 'pending' &&
-    t.blockedBy.every(depId => tasks.find(d => d.id === depId)?.status === 'completed')
+    [...t.blockedBy, ...(t.dependsOn ?? [])].every(
+      depId => tasks.find(d => d.id === depId)?.status === 'completed',
+    )
   );
 
   return { tasks, loading, error, refresh: fetchTasks, pendingTasks, inProgressTasks, completedTasks, availableTasks };
@@ -11,6 +11,8 @@ export interface TaskUpdate {
   owner?: string;
   addBlocks?: string[];
   addBlockedBy?: string[];
+  addDependsOn?: string[];
+  removeDependsOn?: string[];
   metadata?: Record<string, unknown>;
 }
 
@@ -19,6 +21,27 @@ export interface TaskListResponse {
   tasks: ClaudeTask[];
 }
 
+export type TaskChangeKind =
+  | 'task/created'
+  | 'task/updated'
+  | 'task/statusChanged'
+  | 'task/deleted';
+
+export interface TaskChangeEvent {
+  kind: TaskChangeKind;
+  listId: string;
+  taskId: string;
+  task: ClaudeTask | null;
+  changedFields: string[];
+  previousStatus: ClaudeTaskStatus | null;
+}
+
+export interface TaskExecuteResponse {
+  taskId: string;
+  threadId: string;
+  turn: { id: string; threadId: string };
+}
+
 export interface TaskListInfo {
   id: string;
   taskCount: number;
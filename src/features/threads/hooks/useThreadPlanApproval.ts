@@ -0,0 +1,81 @@
+import { useCallback } from "react";
+import type { Dispatch } from "react";
+import type { PlanProposal } from "../../../types";
+import { approvePlan, rejectPlan } from "../../../services/tauri";
+import type { ThreadAction } from "./useThreadsReducer";
+
+type UseThreadPlanApprovalOptions = {
+  dispatch: Dispatch<ThreadAction>;
+  pushThreadErrorMessage: (threadId: string, message: string) => void;
+};
+
+function extractTurnId(response: unknown) {
+  const record = (response ?? {}) as Record<string, unknown>;
+  const result = (record.result ?? record) as Record<string, unknown>;
+  const turn = (result.turn ?? null) as Record<string, unknown> | null;
+  return typeof turn?.id === "string" ? turn.id : "";
+}
+
+export function useThreadPlanApproval({
+  dispatch,
+  pushThreadErrorMessage,
+}: UseThreadPlanApprovalOptions) {
+  const resolvePlan = useCallback(
+    async (proposal: PlanProposal, send: () => Promise<unknown>) => {
+      dispatch({
+        type: "removePlanProposal",
+        workspaceId: proposal.workspaceId,
+        threadId: proposal.threadId,
+        toolUseId: proposal.toolUseId,
+      });
+      dispatch({
+        type: "markProcessing",
+        threadId: proposal.threadId,
+        isProcessing: true,
+        timestamp: Date.now(),
+      });
+      try {
+        const turnId = extractTurnId(await send());
+        if (!turnId) {
+          throw new Error("Turn failed to start.");
+        }
+        dispatch({ type: "setActiveTurnId", threadId: proposal.threadId, turnId });
+      } catch (error) {
+        dispatch({
+          type: "markProcessing",
+          threadId: proposal.threadId,
+          isProcessing: false,
+          timestamp: Date.now(),
+        });
+        dispatch({ type: "setActiveTurnId", threadId: proposal.threadId, turnId: null });
+        pushThreadErrorMessage(
+          proposal.threadId,
+          error instanceof Error ? error.message : String(error),
+        );
+      }
+    },
+    [dispatch, pushThreadErrorMessage],
+  );
+
+  const handlePlanApprove = useCallback(
+    (proposal: PlanProposal, accessMode?: string | null) =>
+      resolvePlan(proposal, () =>
+        approvePlan(proposal.workspaceId, proposal.threadId, proposal.toolUseId, {
+          accessMode,
+        }),
+      ),
+    [resolvePlan],
+  );
+
+  const handlePlanReject = useCallback(
+    (proposal: PlanProposal, feedback?: string | null) =>
+      resolvePlan(proposal, () =>
+        rejectPlan(proposal.workspaceId, proposal.threadId, proposal.toolUseId, {
+          feedback,
+        }),
+      ),
+    [resolvePlan],
+  );
+
+  return { handlePlanApprove, handlePlanReject };
+}
@@ -7,8 +7,10 @@ import { useThreadRows } from "../../app/hooks/useThreadRows";
 import {
   interruptTurn,
   listThreads,
+  pinThread,
   resumeThread,
   sendUserMessage,
+  setThreadTitle,
 } from "../../../services/tauri";
 import { useThreads } from "./useThreads";
 
@@ -31,6 +33,8 @@ vi.mock("../../../services/tauri", () => ({
   listThreads: vi.fn(),
   resumeThread: vi.fn(),
   archiveThread: vi.fn(),
+  setThreadTitle: vi.fn().mockResolvedValue({}),
+  pinThread: vi.fn().mockResolvedValue({}),
   interruptTurn: vi.fn(),
 }));
 
@@ -461,4 +465,58 @@ describe("useThreads UX integration", () => {
     ]);
     expect(unpinnedRows.map((row) => row.thread.id)).toEqual(["thread-b"]);
   });
+
+  it("persists titles and pins and adopts ones stored by the backend", async () => {
+    vi.mocked(listThreads).mockResolvedValue({
+      result: {
+        data: [
+          {
+            id: "thread-a",
+            preview: "Alpha",
+            title: "Release checklist",
+            pinned: true,
+            pinnedAt: 4000,
+            updated_at: 1000,
+            cwd: workspace.path,
+          },
+          {
+            id: "thread-b",
+            preview: "Beta",
+            updated_at: 3000,
+            cwd: workspace.path,
+          },
+        ],
+        nextCursor: null,
+      },
+    });
+
+    const { result } = renderHook(() =>
+      useThreads({
+        activeWorkspace: workspace,
+        onWorkspaceConnected: vi.fn(),
+      }),
+    );
+
+    await act(async () => {
+      await result.current.listThreadsForWorkspace(workspace);
+    });
+
+    const stored = result.current.threadsByWorkspace["ws-1"]?.find(
+      (thread) => thread.id === "thread-a",
+    );
+    expect(stored?.name).toBe("Release checklist");
+    expect(result.current.getPinTimestamp("ws-1", "thread-a")).toBe(4000);
+
+    act(() => {
+      result.current.renameThread("ws-1", "thread-b", "Flaky test hunt");
+      result.current.pinThread("ws-1", "thread-b");
+    });
+    expect(setThreadTitle).toHaveBeenCalledWith("ws-1", "thread-b", "Flaky test hunt");
+    expect(pinThread).toHaveBeenCalledWith("ws-1", "thread-b", true);
+
+    act(() => {
+      result.current.unpinThread("ws-1", "thread-a");
+    });
+    expect(pinThread).toHaveBeenCalledWith("ws-1", "thread-a", false);
+  });
 });
@@ -5,10 +5,15 @@ import type {
   ConversationItem,
   CustomPromptOption,
   DebugEntry,
+  ModelTokenUsage,
   PermissionDenial,
+  PlanProposal,
   RequestUserInputRequest,
   ThreadSummary,
   ThreadTokenUsage,
+  TokenUsageBreakdown,
+  ToolApprovalDecision,
+  ToolApprovalRequest,
   TurnPlan,
   TurnPlanStep,
   TurnPlanStepStatus,
@@ -20,6 +25,7 @@ import {
 } from "../../../utils/approvalRules";
 import {
   rememberApprovalRule,
+  respondToApprovalRequest,
   sendUserMessage as sendUserMessageService,
   startReview as startReviewService,
   startThread as startThreadService,
@@ -28,8 +34,11 @@ import {
   forkThreadFromMessage as forkThreadFromMessageService,
   rewindThreadFiles as rewindThreadFilesService,
   archiveThread as archiveThreadService,
+  setThreadTitle as setThreadTitleService,
+  pinThread as pinThreadService,
   interruptTurn as interruptTurnService,
 } from "../../../services/tauri";
+import { pushErrorToast } from "../../../services/toasts";
 import { useAppServerEvents } from "../../app/hooks/useAppServerEvents";
 import {
   buildConversationItem,
@@ -42,6 +51,7 @@ import {
 } from "../../../utils/threadItems";
 import { expandCustomPromptText } from "../../../utils/customPrompts";
 import { initialState, threadReducer } from "./useThreadsReducer";
+import { useThreadPlanApproval } from "./useThreadPlanApproval";
 import { useThreadUserInput } from "./useThreadUserInput";
 import { useThreadAccountInfo } from "./useThreadAccountInfo";
 
@@ -128,6 +138,13 @@ function saveCustomName(workspaceId: string, threadId: string, name: string): vo
   }
 }
 
+function parseThreadTags(thread: Record<string, unknown>): string[] | undefined {
+  const tags = Array.isArray(thread?.tags)
+    ? thread.tags.map((tag) => asString(tag)).filter(Boolean)
+    : [];
+  return tags.length > 0 ? tags : undefined;
+}
+
 function makePinKey(workspaceId: string, threadId: string): string {
   return `${workspaceId}:${threadId}`;
 }
@@ -251,6 +268,13 @@ function parseReviewTarget(input: string) {
       ...(title ? { title } : {}),
     } as const;
   }
+  if (lower === "comments" || lower.startsWith("comments ")) {
+    const prNumber = Number(rest.slice(8).trim().replace(/^#/, ""));
+    return {
+      type: "pullRequestComments",
+      ...(Number.isInteger(prNumber) && prNumber > 0 ? { prNumber } : {}),
+    } as const;
+  }
   if (lower.startsWith("custom ")) {
     const instructions = rest.slice(7).trim();
     return { type: "custom", instructions } as const;
@@ -271,41 +295,56 @@ function asNumber(value: unknown): number {
   return 0;
 }
 
+function normalizeTokenBreakdown(raw: unknown): TokenUsageBreakdown {
+  const value = (raw as Record<string, unknown> | null | undefined) ?? {};
+  return {
+    totalTokens: asNumber(value.totalTokens ?? value.total_tokens),
+    inputTokens: asNumber(value.inputTokens ?? value.input_tokens),
+    cachedInputTokens: asNumber(value.cachedInputTokens ?? value.cached_input_tokens),
+    outputTokens: asNumber(value.outputTokens ?? value.output_tokens),
+    reasoningOutputTokens: asNumber(
+      value.reasoningOutputTokens ?? value.reasoning_output_tokens,
+    ),
+  };
+}
+
+function asNullableNumber(value: unknown): number | null {
+  if (typeof value === "number") {
+    return value;
+  }
+  if (typeof value === "string") {
+    const parsed = Number(value);
+    return Number.isFinite(parsed) ? parsed : null;
+  }
+  return null;
+}
+
+function normalizeModelTokenUsage(raw: unknown): ModelTokenUsage[] | undefined {
+  if (!Array.isArray(raw)) {
+    return undefined;
+  }
+  return raw
+    .map((entry) => (entry ?? {}) as Record<string, unknown>)
+    .filter((entry) => typeof entry.model === "string" && entry.model)
+    .map((entry) => ({
+      model: String(entry.model),
+      primary: Boolean(entry.primary),
+      last: normalizeTokenBreakdown(entry.last),
+      total: normalizeTokenBreakdown(entry.total),
+      contextWindow: asNullableNumber(entry.contextWindow ?? entry.context_window),
+      costUsd: asNullableNumber(entry.costUsd ?? entry.cost_usd),
+    }));
+}
+
 function normalizeTokenUsage(raw: Record<string, unknown>): ThreadTokenUsage {
-  const total = (raw.total as Record<string, unknown>) ?? {};
-  const last = (raw.last as Record<string, unknown>) ?? {};
+  const perModel = normalizeModelTokenUsage(raw.perModel ?? raw.per_model);
   return {
-    total: {
-      totalTokens: asNumber(total.totalTokens ?? total.total_tokens),
-      inputTokens: asNumber(total.inputTokens ?? total.input_tokens),
-      cachedInputTokens: asNumber(
-        total.cachedInputTokens ?? total.cached_input_tokens,
-      ),
-      outputTokens: asNumber(total.outputTokens ?? total.output_tokens),
-      reasoningOutputTokens: asNumber(
-        total.reasoningOutputTokens ?? total.reasoning_output_tokens,
-      ),
-    },
-    last: {
-      totalTokens: asNumber(last.totalTokens ?? last.total_tokens),
-      inputTokens: asNumber(last.inputTokens ?? last.input_tokens),
-      cachedInputTokens: asNumber(last.cachedInputTokens ?? last.cached_input_tokens),
-      outputTokens: asNumber(last.outputTokens ?? last.output_tokens),
-      reasoningOutputTokens: asNumber(
-        last.reasoningOutputTokens ?? last.reasoning_output_tokens,
-      ),
-    },
-    modelContextWindow: (() => {
-      const value = raw.modelContextWindow ?? raw.model_context_window;
-      if (typeof value === "number") {
-        return value;
-      }
-      if (typeof value === "string") {
-        const parsed = Number(value);
-        return Number.isFinite(parsed) ? parsed : null;
-      }
-      return null;
-    })(),
+    total: normalizeTokenBreakdown(raw.total),
+    last: normalizeTokenBreakdown(raw.last),
+    modelContextWindow: asNullableNumber(
+      raw.modelContextWindow ?? raw.model_context_window,
+    ),
+    ...(perModel ? { perModel } : {}),
   };
 }
 
@@ -454,6 +493,7 @@ export function useThreads({
     pinnedThreadsRef.current = next;
     savePinnedThreads(next);
     setPinnedThreadsVersion((version) => version + 1);
+    void pinThreadService(workspaceId, threadId, true).catch(() => {});
     return true;
   }, []);
 
@@ -466,8 +506,42 @@ export function useThreads({
     pinnedThreadsRef.current = rest;
     savePinnedThreads(rest);
     setPinnedThreadsVersion((version) => version + 1);
+    void pinThreadService(workspaceId, threadId, false).catch(() => {});
   }, []);
 
+  // Titles and pins stored by the backend survive a cleared webview cache and
+  // follow remote workspaces; merge any the local maps don't know about.
+  const adoptStoredThreadMeta = useCallback(
+    (workspaceId: string, threads: Record<string, unknown>[]) => {
+      let pinsChanged = false;
+      const nextPins = { ...pinnedThreadsRef.current };
+      threads.forEach((thread) => {
+        const threadId = String(thread?.id ?? "");
+        if (!threadId) {
+          return;
+        }
+        const title = asString(thread?.title ?? "").trim();
+        const nameKey = makeCustomNameKey(workspaceId, threadId);
+        if (title && !customNamesRef.current[nameKey]) {
+          customNamesRef.current[nameKey] = title;
+          saveCustomName(workspaceId, threadId, title);
+        }
+        const pinnedAt = Number(thread?.pinnedAt ?? 0);
+        const pinKey = makePinKey(workspaceId, threadId);
+        if (thread?.pinned === true && !(pinKey in nextPins)) {
+          nextPins[pinKey] = pinnedAt > 0 ? pinnedAt : Date.now();
+          pinsChanged = true;
+        }
+      });
+      if (pinsChanged) {
+        pinnedThreadsRef.current = nextPins;
+        savePinnedThreads(nextPins);
+        setPinnedThreadsVersion((version) => version + 1);
+      }
+    },
+    [],
+  );
+
   const isThreadPinned = useCallback(
     (workspaceId: string, threadId: string): boolean => {
       const key = makePinKey(workspaceId, threadId);
@@ -519,6 +593,11 @@ export function useThreads({
     });
   }, []);
 
+  const { handlePlanApprove, handlePlanReject } = useThreadPlanApproval({
+    dispatch,
+    pushThreadErrorMessage,
+  });
+
   const safeMessageActivity = useCallback(() => {
     try {
       void onMessageActivity?.();
@@ -696,6 +775,18 @@ export function useThreads({
       onRequestUserInput: (request: RequestUserInputRequest) => {
         dispatch({ type: "addUserInputRequest", request });
       },
+      onApprovalRequest: (request: ToolApprovalRequest) => {
+        dispatch({ type: "addApprovalRequest", request });
+      },
+      onApprovalCancelled: (workspaceId: string, _threadId: string, toolUseId: string) => {
+        dispatch({ type: "removeApprovalRequest", workspaceId, requestId: toolUseId });
+      },
+      onPlanProposed: (proposal: PlanProposal) => {
+        dispatch({ type: "addPlanProposal", proposal });
+      },
+      onPlanResolved: (workspaceId: string, threadId: string, toolUseId: string) => {
+        dispatch({ type: "removePlanProposal", workspaceId, threadId, toolUseId });
+      },
       onPermissionDenied: ({
         workspaceId,
         threadId,
@@ -705,12 +796,16 @@ export function useThreads({
         threadId: string;
         denials: PermissionDenial[];
       }) => {
-        if (denials.length) {
-          dispatch({ type: "addPermissionDenials", denials });
+        // ExitPlanMode is answered through the plan approval card instead.
+        const pendingDenials = denials.filter(
+          (d) => d.tool_name !== "ExitPlanMode",
+        );
+        if (pendingDenials.length) {
+          dispatch({ type: "addPermissionDenials", denials: pendingDenials });
           // If AskUserQuestion was denied, clear any pending user input requests for this thread.
           // This happens when using bypassPermissions or dontAsk mode where the CLI
           // immediately denies the tool without waiting for user input.
-          const hasAskUserQuestionDenial = denials.some(
+          const hasAskUserQuestionDenial = pendingDenials.some(
             (d) => d.tool_name === "AskUserQuestion",
           );
           if (hasAskUserQuestionDenial && threadId && workspaceId) {
@@ -823,6 +918,14 @@ export function useThreads({
       ) => {
         handleItemUpdate(workspaceId, threadId, item, false);
       },
+      onItemSubagentLinked: (
+        _workspaceId: string,
+        threadId: string,
+        itemId: string,
+        subagentThreadId: string,
+      ) => {
+        dispatch({ type: "linkSubagentThread", threadId, itemId, subagentThreadId });
+      },
       onReasoningSummaryDelta: (
         _workspaceId: string,
         threadId: string,
@@ -1111,6 +1214,17 @@ export function useThreads({
           | null;
         if (thread) {
           applyCollabThreadLinksFromThread(threadId, thread);
+          const malformedLines = asNumber(
+            thread.malformedLines ?? thread.malformed_lines,
+          );
+          if (malformedLines > 0 && !loadedThreads.current[threadId]) {
+            pushErrorToast({
+              title: "Damaged session transcript",
+              message: `${malformedLines} line${
+                malformedLines === 1 ? "" : "s"
+              } of this thread's transcript could not be read, which can break resuming it. Use "Check transcript" in the thread menu to repair it.`,
+            });
+          }
           const items = buildItemsFromThread(thread);
           const localItems = state.itemsByThread[threadId] ?? [];
           const shouldReplace =
@@ -1319,6 +1433,7 @@ export function useThreads({
             `[debug:sessions] Deduplication removed ${dupesRemoved} duplicate threads, ${uniqueThreads.length} unique remain`,
           );
         }
+        adoptStoredThreadMeta(workspace.id, uniqueThreads);
         const activityByThread = threadActivityRef.current[workspace.id] ?? {};
         const nextActivityByThread = { ...activityByThread };
         let didChangeActivity = false;
@@ -1389,10 +1504,12 @@ export function useThreads({
                   ? `${preview.slice(0, 38)}…`
                   : preview
                 : fallbackName;
+            const tags = parseThreadTags(thread);
             return {
               id,
               name,
               updatedAt: getThreadTimestamp(thread),
+              ...(tags ? { tags } : {}),
             };
           })
           .filter((entry) => entry.id);
@@ -1441,7 +1558,7 @@ export function useThreads({
         }
       }
     },
-    [applyParentLinksFromThreads, getCustomName, onDebug],
+    [adoptStoredThreadMeta, applyParentLinksFromThreads, getCustomName, onDebug],
   );
 
   const loadOlderThreadsForWorkspace = useCallback(
@@ -1533,6 +1650,7 @@ export function useThreads({
         );
 
         applyParentLinksFromThreads(matchingThreads);
+        adoptStoredThreadMeta(workspace.id, matchingThreads);
         const existingIds = new Set(existing.map((thread) => thread.id));
         const additions: ThreadSummary[] = [];
         matchingThreads.forEach((thread) => {
@@ -1550,7 +1668,13 @@ export function useThreads({
                 ? `${preview.slice(0, 38)}…`
                 : preview
               : fallbackName;
-          additions.push({ id, name, updatedAt: getThreadTimestamp(thread) });
+          const tags = parseThreadTags(thread);
+          additions.push({
+            id,
+            name,
+            updatedAt: getThreadTimestamp(thread),
+            ...(tags ? { tags } : {}),
+          });
           existingIds.add(id);
         });
 
@@ -1600,6 +1724,7 @@ export function useThreads({
       }
     },
     [
+      adoptStoredThreadMeta,
       applyParentLinksFromThreads,
       getCustomName,
       onDebug,
@@ -1630,7 +1755,11 @@ export function useThreads({
       threadId: string,
       text: string,
       images: string[] = [],
-      options?: { skipPromptExpansion?: boolean; skipUserMessage?: boolean },
+      options?: {
+        skipPromptExpansion?: boolean;
+        skipUserMessage?: boolean;
+        redact?: boolean;
+      },
     ) => {
       const messageText = text.trim();
       if (!messageText && images.length === 0) {
@@ -1720,7 +1849,14 @@ export function useThreads({
             workspace.id,
             threadId,
             finalText,
-            { model, effort, collaborationMode, accessMode, images },
+            {
+              model,
+              effort,
+              collaborationMode,
+              accessMode,
+              images,
+              redact: options?.redact,
+            },
           )) as Record<string, unknown>;
         onDebug?.({
           id: `${Date.now()}-server-turn-start`,
@@ -2118,6 +2254,51 @@ export function useThreads({
     dispatch({ type: "removePermissionDenial", denialId: denial.id });
   }, []);
 
+  const handleApprovalDecision = useCallback(
+    async (request: ToolApprovalRequest, decision: ToolApprovalDecision) => {
+      dispatch({
+        type: "removeApprovalRequest",
+        workspaceId: request.workspace_id,
+        requestId: request.id,
+      });
+      try {
+        await respondToApprovalRequest(
+          request.workspace_id,
+          request.thread_id,
+          request.tool_use_id,
+          decision,
+        );
+      } catch (error) {
+        pushThreadErrorMessage(
+          request.thread_id,
+          error instanceof Error ? error.message : String(error),
+        );
+      }
+    },
+    [pushThreadErrorMessage],
+  );
+
+  const handleApprovalRemember = useCallback(
+    async (request: ToolApprovalRequest, ruleInfo: ApprovalRuleInfo) => {
+      try {
+        await rememberApprovalRule(request.workspace_id, ruleInfo.rule);
+      } catch (error) {
+        onDebug?.({
+          id: `${Date.now()}-client-approval-rule-error`,
+          timestamp: Date.now(),
+          source: "error",
+          label: "approval rule error",
+          payload: error instanceof Error ? error.message : String(error),
+        });
+      }
+      if (ruleInfo.commandTokens) {
+        rememberApprovalPrefix(request.workspace_id, ruleInfo.commandTokens);
+      }
+      await handleApprovalDecision(request, "accept");
+    },
+    [handleApprovalDecision, onDebug, rememberApprovalPrefix],
+  );
+
   const setActiveThreadId = useCallback(
     (threadId: string | null, workspaceId?: string) => {
       const targetId = workspaceId ?? activeWorkspaceId;
@@ -2294,6 +2475,7 @@ export function useThreads({
       const key = makeCustomNameKey(workspaceId, threadId);
       customNamesRef.current[key] = newName;
       dispatch({ type: "setThreadName", workspaceId, threadId, name: newName });
+      void setThreadTitleService(workspaceId, threadId, newName).catch(() => {});
     },
     [dispatch],
   );
@@ -2303,7 +2485,9 @@ export function useThreads({
     setActiveThreadId,
     activeItems,
     permissionDenials: state.permissionDenials,
+    approvalRequests: state.approvalRequests,
     userInputRequests: state.userInputRequests,
+    planProposals: state.planProposals,
     threadsByWorkspace: state.threadsByWorkspace,
     threadParentById: state.threadParentById,
     threadStatusById: state.threadStatusById,
@@ -2339,7 +2523,11 @@ export function useThreads({
     handlePermissionRemember,
     handlePermissionRetry,
     handlePermissionDismiss,
+    handleApprovalDecision,
+    handleApprovalRemember,
     handleUserInputSubmit,
+    handlePlanApprove,
+    handlePlanReject,
     refreshAccountInfo,
   };
 }
@@ -333,6 +333,39 @@ describe("threadReducer", () => {
     expect(next).toBe(base);
   });
 
+  it("links a Task tool item to its subagent thread", () => {
+    const task: ConversationItem = {
+      id: "toolu_1",
+      kind: "tool",
+      toolType: "commandExecution",
+      title: "Task",
+      detail: "",
+      status: "running",
+    };
+    const base: ThreadState = {
+      ...initialState,
+      itemsByThread: { "thread-1": [task] },
+    };
+    const linked = threadReducer(base, {
+      type: "linkSubagentThread",
+      threadId: "thread-1",
+      itemId: "toolu_1",
+      subagentThreadId: "thread-1::subagent::agent-a",
+    });
+    const item = linked.itemsByThread["thread-1"]?.[0];
+    expect(item?.kind === "tool" ? item.subagentThreadId : null).toBe(
+      "thread-1::subagent::agent-a",
+    );
+
+    const missing = threadReducer(base, {
+      type: "linkSubagentThread",
+      threadId: "thread-1",
+      itemId: "toolu_2",
+      subagentThreadId: "thread-1::subagent::agent-b",
+    });
+    expect(missing).toBe(base);
+  });
+
   it("adds and removes user input requests by workspace and id", () => {
     const requestA = {
       workspace_id: "ws-1",
@@ -382,4 +415,42 @@ describe("threadReducer", () => {
     });
     expect(removed.userInputRequests).toEqual([requestB]);
   });
+
+  it("keeps one plan proposal per thread and removes it when resolved", () => {
+    const first = {
+      workspaceId: "ws-1",
+      threadId: "thread-1",
+      turnId: "turn-1",
+      itemId: "tool-1",
+      toolUseId: "tool-1",
+      plan: "1. Read the code",
+    };
+    const second = { ...first, toolUseId: "tool-2", itemId: "tool-2", plan: "1. Revised" };
+
+    const added = threadReducer(initialState, {
+      type: "addPlanProposal",
+      proposal: first,
+    });
+    const replaced = threadReducer(added, {
+      type: "addPlanProposal",
+      proposal: second,
+    });
+    expect(replaced.planProposals).toEqual([second]);
+
+    const unchanged = threadReducer(replaced, {
+      type: "removePlanProposal",
+      workspaceId: "ws-1",
+      threadId: "thread-1",
+      toolUseId: "tool-1",
+    });
+    expect(unchanged).toBe(replaced);
+
+    const removed = threadReducer(replaced, {
+      type: "removePlanProposal",
+      workspaceId: "ws-1",
+      threadId: "thread-1",
+      toolUseId: "tool-2",
+    });
+    expect(removed.planProposals).toEqual([]);
+  });
 });
@@ -2,9 +2,11 @@ import type {
   AccountSnapshot,
   ConversationItem,
   PermissionDenial,
+  PlanProposal,
   RequestUserInputRequest,
   ThreadSummary,
   ThreadTokenUsage,
+  ToolApprovalRequest,
   TurnPlan,
 } from "../../../types";
 import { normalizeItem, prepareThreadItems, upsertItem } from "../../../utils/threadItems";
@@ -117,7 +119,9 @@ export type ThreadState = {
   threadListCursorByWorkspace: Record<string, string | null>;
   activeTurnIdByThread: Record<string, string | null>;
   permissionDenials: PermissionDenial[];
+  approvalRequests: ToolApprovalRequest[];
   userInputRequests: RequestUserInputRequest[];
+  planProposals: PlanProposal[];
   tokenUsageByThread: Record<string, ThreadTokenUsage>;
   accountByWorkspace: Record<string, AccountSnapshot | null>;
   planByThread: Record<string, TurnPlan | null>;
@@ -181,6 +185,12 @@ export type ThreadAction =
     }
   | { type: "appendReasoningContent"; threadId: string; itemId: string; delta: string }
   | { type: "appendToolOutput"; threadId: string; itemId: string; delta: string }
+  | {
+      type: "linkSubagentThread";
+      threadId: string;
+      itemId: string;
+      subagentThreadId: string;
+    }
   | { type: "setThreads"; workspaceId: string; threads: ThreadSummary[] }
   | {
       type: "setThreadListLoading";
@@ -199,9 +209,18 @@ export type ThreadAction =
     }
   | { type: "addPermissionDenials"; denials: PermissionDenial[] }
   | { type: "removePermissionDenial"; denialId: string }
+  | { type: "addApprovalRequest"; request: ToolApprovalRequest }
+  | { type: "removeApprovalRequest"; workspaceId: string; requestId: string }
   | { type: "addUserInputRequest"; request: RequestUserInputRequest }
   | { type: "removeUserInputRequest"; requestId: number; workspaceId: string }
   | { type: "clearUserInputRequestsForThread"; threadId: string; workspaceId: string }
+  | { type: "addPlanProposal"; proposal: PlanProposal }
+  | {
+      type: "removePlanProposal";
+      workspaceId: string;
+      threadId: string;
+      toolUseId: string;
+    }
   | { type: "setThreadTokenUsage"; threadId: string; tokenUsage: ThreadTokenUsage }
   | {
       type: "setAccountInfo";
@@ -237,7 +256,9 @@ export const initialState: ThreadState = {
   threadListCursorByWorkspace: {},
   activeTurnIdByThread: {},
   permissionDenials: [],
+  approvalRequests: [],
   userInputRequests: [],
+  planProposals: [],
   tokenUsageByThread: {},
   planByThread: {},
   lastAgentMessageByThread: {},
@@ -868,6 +889,27 @@ export function threadReducer(state: ThreadState, action: ThreadAction): ThreadS
         },
       };
     }
+    case "linkSubagentThread": {
+      const list = state.itemsByThread[action.threadId] ?? [];
+      const index = list.findIndex((entry) => entry.id === action.itemId);
+      const existing = list[index];
+      if (
+        !existing ||
+        existing.kind !== "tool" ||
+        existing.subagentThreadId === action.subagentThreadId
+      ) {
+        return state;
+      }
+      const next = [...list];
+      next[index] = { ...existing, subagentThreadId: action.subagentThreadId };
+      return {
+        ...state,
+        itemsByThread: {
+          ...state.itemsByThread,
+          [action.threadId]: prepareThreadItems(next, action.threadId),
+        },
+      };
+    }
     case "addPermissionDenials": {
       if (!action.denials.length) {
         return state;
@@ -896,6 +938,33 @@ export function threadReducer(state: ThreadState, action: ThreadAction): ThreadS
         permissionDenials: filtered,
       };
     }
+    case "addApprovalRequest": {
+      const exists = state.approvalRequests.some(
+        (item) =>
+          item.id === action.request.id &&
+          item.workspace_id === action.request.workspace_id,
+      );
+      if (exists) {
+        return state;
+      }
+      return {
+        ...state,
+        approvalRequests: [...state.approvalRequests, action.request],
+      };
+    }
+    case "removeApprovalRequest": {
+      const filtered = state.approvalRequests.filter(
+        (item) =>
+          item.id !== action.requestId || item.workspace_id !== action.workspaceId,
+      );
+      if (filtered.length === state.approvalRequests.length) {
+        return state;
+      }
+      return {
+        ...state,
+        approvalRequests: filtered,
+      };
+    }
     case "addUserInputRequest": {
       const exists = state.userInputRequests.some(
         (item) =>
@@ -933,6 +1002,33 @@ export function threadReducer(state: ThreadState, action: ThreadAction): ThreadS
         userInputRequests: filtered,
       };
     }
+    case "addPlanProposal": {
+      // A newer proposal for the same thread supersedes the previous one.
+      const others = state.planProposals.filter(
+        (item) =>
+          item.threadId !== action.proposal.threadId ||
+          item.workspaceId !== action.proposal.workspaceId,
+      );
+      return {
+        ...state,
+        planProposals: [...others, action.proposal],
+      };
+    }
+    case "removePlanProposal": {
+      const filtered = state.planProposals.filter(
+        (item) =>
+          item.toolUseId !== action.toolUseId ||
+          item.threadId !== action.threadId ||
+          item.workspaceId !== action.workspaceId,
+      );
+      if (filtered.length === state.planProposals.length) {
+        return state;
+      }
+      return {
+        ...state,
+        planProposals: filtered,
+      };
+    }
     case "setThreads": {
       return {
         ...state,
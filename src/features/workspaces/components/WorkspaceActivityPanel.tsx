@@ -0,0 +1,143 @@
+import { useMemo, useState } from "react";
+import type { WorkspaceActivityEntry } from "../../../types";
+import { formatRelativeTime } from "../../../utils/time";
+import { useWorkspaceActivity } from "../hooks/useWorkspaceActivity";
+
+type ActivityFilter = "all" | WorkspaceActivityEntry["kind"];
+
+const FILTERS: { value: ActivityFilter; label: string }[] = [
+  { value: "all", label: "All" },
+  { value: "commit", label: "Commits" },
+  { value: "thread", label: "Threads" },
+  { value: "task", label: "Tasks" },
+  { value: "file", label: "Files" },
+];
+
+const MAX_ENTRIES = 30;
+
+const KIND_LABELS: Record<WorkspaceActivityEntry["kind"], string> = {
+  commit: "Commit",
+  thread: "Thread",
+  task: "Task",
+  run: "Run",
+  file: "File",
+};
+
+function describeEntry(entry: WorkspaceActivityEntry) {
+  switch (entry.kind) {
+    case "commit":
+      return `${entry.summary} · ${entry.sha.slice(0, 7)}${
+        entry.author ? ` · ${entry.author}` : ""
+      }`;
+    case "thread":
+      return entry.title || "Untitled thread";
+    case "task":
+      return `${entry.subject} → ${entry.status || "pending"}`;
+    case "run":
+      return `Background run ${entry.status}`;
+    case "file":
+      return `${entry.path} (${entry.status})`;
+  }
+}
+
+function entryThreadId(entry: WorkspaceActivityEntry) {
+  return entry.kind === "thread" || entry.kind === "task" || entry.kind === "run"
+    ? entry.threadId
+    : null;
+}
+
+type WorkspaceActivityPanelProps = {
+  workspaceId: string;
+  onSelectThread: (workspaceId: string, threadId: string) => void;
+};
+
+export function WorkspaceActivityPanel({
+  workspaceId,
+  onSelectThread,
+}: WorkspaceActivityPanelProps) {
+  const { entries, isLoading, error, refresh } = useWorkspaceActivity(workspaceId);
+  const [filter, setFilter] = useState<ActivityFilter>("all");
+  const visible = useMemo(
+    () =>
+      entries
+        .filter(
+          (entry) =>
+            filter === "all" ||
+            entry.kind === filter ||
+            (filter === "task" && entry.kind === "run"),
+        )
+        .slice(0, MAX_ENTRIES),
+    [entries, filter],
+  );
+
+  return (
+    <div className="workspace-home-runs workspace-home-activity">
+      <div className="workspace-home-section-header">
+        <div className="workspace-home-section-title">Activity</div>
+        <div className="workspace-home-stats-ranges" role="group" aria-label="Filter">
+          {FILTERS.map((option) => (
+            <button
+              key={option.value}
+              type="button"
+              className={`workspace-home-stats-range${
+                option.value === filter ? " is-active" : ""
+              }`}
+              onClick={() => setFilter(option.value)}
+            >
+              {option.label}
+            </button>
+          ))}
+          <button
+            type="button"
+            className="workspace-home-stats-range"
+            onClick={refresh}
+            disabled={isLoading}
+          >
+            Refresh
+          </button>
+        </div>
+      </div>
+      {error ? (
+        <div className="workspace-home-run-error">{error}</div>
+      ) : visible.length === 0 ? (
+        <div className="workspace-home-empty">
+          {isLoading ? "Loading activity..." : "No activity in the last week."}
+        </div>
+      ) : (
+        <div className="workspace-home-activity-list">
+          {visible.map((entry, index) => {
+            const threadId = entryThreadId(entry);
+            const content = (
+              <>
+                <span className={`workspace-home-activity-kind is-${entry.kind}`}>
+                  {KIND_LABELS[entry.kind]}
+                </span>
+                <span className="workspace-home-activity-text">
+                  {describeEntry(entry)}
+                </span>
+                <span className="workspace-home-activity-time">
+                  {formatRelativeTime(entry.timestamp)}
+                </span>
+              </>
+            );
+            const key = `${entry.kind}-${entry.timestamp}-${index}`;
+            return threadId ? (
+              <button
+                key={key}
+                type="button"
+                className="workspace-home-activity-row is-link"
+                onClick={() => onSelectThread(workspaceId, threadId)}
+              >
+                {content}
+              </button>
+            ) : (
+              <div key={key} className="workspace-home-activity-row">
+                {content}
+              </div>
+            );
+          })}
+        </div>
+      )}
+    </div>
+  );
+}
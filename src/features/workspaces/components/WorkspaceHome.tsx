@@ -29,8 +29,10 @@ import Laptop from "lucide-react/dist/esm/icons/laptop";
 import GitBranch from "lucide-react/dist/esm/icons/git-branch";
 import ChevronDown from "lucide-react/dist/esm/icons/chevron-down";
 import ChevronRight from "lucide-react/dist/esm/icons/chevron-right";
-import { computeDictationInsertion } from "../../../utils/dictation";
+import { applyDictationTranscript } from "../../../utils/dictation";
 import { FileEditorCard } from "../../shared/components/FileEditorCard";
+import { WorkspaceActivityPanel } from "./WorkspaceActivityPanel";
+import { WorkspaceStatsPanel } from "./WorkspaceStatsPanel";
 import { getCaretPosition } from "../../../utils/caretPosition";
 
 type ThreadStatus = {
@@ -254,17 +256,17 @@ export function WorkspaceHome({
     if (!dictationTranscript) {
       return;
     }
-    const textToInsert = dictationTranscript.text.trim();
-    if (!textToInsert) {
+    if (!dictationTranscript.text.trim() && !dictationTranscript.command) {
       onDictationTranscriptHandled(dictationTranscript.id);
       return;
     }
     const textarea = textareaRef.current;
     const start = textarea?.selectionStart ?? selectionStart ?? prompt.length;
     const end = textarea?.selectionEnd ?? start;
-    const { nextText, nextCursor } = computeDictationInsertion(
+    // Starting runs stays a deliberate click here, so "send" only inserts the text.
+    const { nextText, nextCursor } = applyDictationTranscript(
       prompt,
-      textToInsert,
+      dictationTranscript,
       start,
       end,
     );
@@ -718,6 +720,13 @@ export function WorkspaceHome({
         )}
       </div>
 
+      <WorkspaceStatsPanel workspaceId={workspace.id} />
+
+      <WorkspaceActivityPanel
+        workspaceId={workspace.id}
+        onSelectThread={onSelectInstance}
+      />
+
       <div className="workspace-home-runs">
         {claudeMdTruncated && (
           <div className="workspace-home-warning">
@@ -0,0 +1,124 @@
+import type { UsageAnalyticsRange } from "../../../types";
+import { useUsageAnalytics } from "../hooks/useUsageAnalytics";
+
+const RANGES: { value: UsageAnalyticsRange; label: string }[] = [
+  { value: "day", label: "24h" },
+  { value: "week", label: "7d" },
+  { value: "month", label: "30d" },
+  { value: "all", label: "All" },
+];
+
+const MAX_TOOLS = 8;
+
+function formatTurnDuration(durationMs: number | null) {
+  if (durationMs === null) {
+    return "--";
+  }
+  const seconds = Math.round(durationMs / 1000);
+  if (seconds < 60) {
+    return `${seconds}s`;
+  }
+  const minutes = Math.floor(seconds / 60);
+  return `${minutes}m ${String(seconds % 60).padStart(2, "0")}s`;
+}
+
+function formatPercent(value: number) {
+  return `${(value * 100).toFixed(value > 0 && value < 0.1 ? 1 : 0)}%`;
+}
+
+type WorkspaceStatsPanelProps = {
+  workspaceId: string;
+};
+
+export function WorkspaceStatsPanel({ workspaceId }: WorkspaceStatsPanelProps) {
+  const { analytics, range, setRange, isLoading, error } =
+    useUsageAnalytics(workspaceId);
+  const maxCalls = analytics?.tools[0]?.calls ?? 0;
+
+  return (
+    <div className="workspace-home-runs workspace-home-stats">
+      <div className="workspace-home-section-header">
+        <div className="workspace-home-section-title">Statistics</div>
+        <div className="workspace-home-stats-ranges" role="group" aria-label="Range">
+          {RANGES.map((option) => (
+            <button
+              key={option.value}
+              type="button"
+              className={`workspace-home-stats-range${
+                option.value === range ? " is-active" : ""
+              }`}
+              onClick={() => setRange(option.value)}
+            >
+              {option.label}
+            </button>
+          ))}
+        </div>
+      </div>
+      {error ? (
+        <div className="workspace-home-run-error">{error}</div>
+      ) : !analytics ? (
+        <div className="workspace-home-empty">
+          {isLoading ? "Loading statistics..." : "No statistics yet."}
+        </div>
+      ) : analytics.sessions === 0 ? (
+        <div className="workspace-home-empty">No sessions in this range.</div>
+      ) : (
+        <>
+          <div className="workspace-home-stats-grid">
+            <div className="workspace-home-stats-card">
+              <div className="workspace-home-stats-label">Turns</div>
+              <div className="workspace-home-stats-value">{analytics.turns}</div>
+              <div className="workspace-home-stats-meta">
+                {analytics.sessions} session{analytics.sessions === 1 ? "" : "s"}
+              </div>
+            </div>
+            <div className="workspace-home-stats-card">
+              <div className="workspace-home-stats-label">Avg turn</div>
+              <div className="workspace-home-stats-value">
+                {formatTurnDuration(analytics.averageTurnMs)}
+              </div>
+            </div>
+            <div className="workspace-home-stats-card">
+              <div className="workspace-home-stats-label">Permission denials</div>
+              <div className="workspace-home-stats-value">
+                {formatPercent(analytics.permissionDenialRate)}
+              </div>
+              <div className="workspace-home-stats-meta">
+                {analytics.permissionDenials} of {analytics.toolCalls} tool calls
+              </div>
+            </div>
+            <div className="workspace-home-stats-card">
+              <div className="workspace-home-stats-label">Edits kept</div>
+              <div className="workspace-home-stats-value">
+                {analytics.editsAccepted}/{analytics.edits}
+              </div>
+              <div className="workspace-home-stats-meta">
+                {analytics.editsRewound} rewound
+              </div>
+            </div>
+          </div>
+          {analytics.tools.length > 0 && (
+            <div className="workspace-home-stats-tools">
+              {analytics.tools.slice(0, MAX_TOOLS).map((usage) => (
+                <div className="workspace-home-stats-tool" key={usage.tool}>
+                  <span className="workspace-home-stats-tool-name">{usage.tool}</span>
+                  <span className="workspace-home-stats-tool-bar" aria-hidden>
+                    <span
+                      style={{
+                        width: `${maxCalls ? (usage.calls / maxCalls) * 100 : 0}%`,
+                      }}
+                    />
+                  </span>
+                  <span className="workspace-home-stats-tool-count">
+                    {usage.calls}
+                    {usage.errors > 0 ? ` · ${usage.errors} failed` : ""}
+                  </span>
+                </div>
+              ))}
+            </div>
+          )}
+        </>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,50 @@
+// @vitest-environment jsdom
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import type { UsageAnalytics } from "../../../types";
+import { usageAnalytics } from "../../../services/tauri";
+import { useUsageAnalytics } from "./useUsageAnalytics";
+
+vi.mock("../../../services/tauri", () => ({
+  usageAnalytics: vi.fn(),
+}));
+
+const snapshot: UsageAnalytics = {
+  range: "week",
+  since: 0,
+  sessions: 2,
+  turns: 5,
+  averageTurnMs: 12_000,
+  toolCalls: 10,
+  tools: [{ tool: "Read", calls: 10, errors: 0, denials: 0 }],
+  permissionDenials: 1,
+  permissionDenialRate: 0.1,
+  edits: 3,
+  editsAccepted: 2,
+  editsRewound: 1,
+};
+
+describe("useUsageAnalytics", () => {
+  it("loads the week by default and refetches when the range changes", async () => {
+    vi.mocked(usageAnalytics).mockResolvedValue(snapshot);
+    const { result } = renderHook(() => useUsageAnalytics("ws-1"));
+
+    await waitFor(() => expect(result.current.analytics).toEqual(snapshot));
+    expect(usageAnalytics).toHaveBeenCalledWith("ws-1", "week");
+
+    act(() => {
+      result.current.setRange("all");
+    });
+    await waitFor(() =>
+      expect(usageAnalytics).toHaveBeenLastCalledWith("ws-1", "all"),
+    );
+  });
+
+  it("surfaces backend errors", async () => {
+    vi.mocked(usageAnalytics).mockRejectedValueOnce(new Error("workspace not found"));
+    const { result } = renderHook(() => useUsageAnalytics("ws-missing"));
+
+    await waitFor(() => expect(result.current.error).toBe("workspace not found"));
+    expect(result.current.isLoading).toBe(false);
+  });
+});
@@ -0,0 +1,44 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { UsageAnalytics, UsageAnalyticsRange } from "../../../types";
+import { usageAnalytics } from "../../../services/tauri";
+
+export function useUsageAnalytics(workspaceId: string | null) {
+  const [range, setRange] = useState<UsageAnalyticsRange>("week");
+  const [analytics, setAnalytics] = useState<UsageAnalytics | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const requestIdRef = useRef(0);
+
+  const refresh = useCallback(() => {
+    if (!workspaceId) {
+      setAnalytics(null);
+      return;
+    }
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    setIsLoading(true);
+    setError(null);
+    usageAnalytics(workspaceId, range)
+      .then((response) => {
+        if (requestIdRef.current === requestId) {
+          setAnalytics(response);
+        }
+      })
+      .catch((err) => {
+        if (requestIdRef.current === requestId) {
+          setError(err instanceof Error ? err.message : String(err));
+        }
+      })
+      .finally(() => {
+        if (requestIdRef.current === requestId) {
+          setIsLoading(false);
+        }
+      });
+  }, [range, workspaceId]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  return { analytics, range, setRange, isLoading, error, refresh };
+}
@@ -0,0 +1,37 @@
+// @vitest-environment jsdom
+import { renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import type { WorkspaceActivityEntry } from "../../../types";
+import { getWorkspaceActivity } from "../../../services/tauri";
+import { useWorkspaceActivity } from "./useWorkspaceActivity";
+
+vi.mock("../../../services/tauri", () => ({
+  getWorkspaceActivity: vi.fn(),
+}));
+
+const feed: WorkspaceActivityEntry[] = [
+  { timestamp: 3, kind: "commit", sha: "abc", summary: "Fix build", author: "Ana" },
+  { timestamp: 2, kind: "file", path: "src/main.rs", status: "M" },
+];
+
+describe("useWorkspaceActivity", () => {
+  it("loads the feed for the workspace", async () => {
+    vi.mocked(getWorkspaceActivity).mockResolvedValue(feed);
+    const { result } = renderHook(() => useWorkspaceActivity("ws-1"));
+
+    await waitFor(() => expect(result.current.entries).toEqual(feed));
+    expect(getWorkspaceActivity).toHaveBeenCalledWith("ws-1");
+  });
+
+  it("clears the feed without a workspace and surfaces errors", async () => {
+    const { result, rerender } = renderHook(
+      ({ workspaceId }) => useWorkspaceActivity(workspaceId),
+      { initialProps: { workspaceId: null as string | null } },
+    );
+    expect(result.current.entries).toEqual([]);
+
+    vi.mocked(getWorkspaceActivity).mockRejectedValueOnce(new Error("workspace not found"));
+    rerender({ workspaceId: "ws-missing" });
+    await waitFor(() => expect(result.current.error).toBe("workspace not found"));
+  });
+});
@@ -0,0 +1,43 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { WorkspaceActivityEntry } from "../../../types";
+import { getWorkspaceActivity } from "../../../services/tauri";
+
+export function useWorkspaceActivity(workspaceId: string | null) {
+  const [entries, setEntries] = useState<WorkspaceActivityEntry[]>([]);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const requestIdRef = useRef(0);
+
+  const refresh = useCallback(() => {
+    if (!workspaceId) {
+      setEntries([]);
+      return;
+    }
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    setIsLoading(true);
+    setError(null);
+    getWorkspaceActivity(workspaceId)
+      .then((response) => {
+        if (requestIdRef.current === requestId) {
+          setEntries(response);
+        }
+      })
+      .catch((err) => {
+        if (requestIdRef.current === requestId) {
+          setError(err instanceof Error ? err.message : String(err));
+        }
+      })
+      .finally(() => {
+        if (requestIdRef.current === requestId) {
+          setIsLoading(false);
+        }
+      });
+  }, [workspaceId]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  return { entries, isLoading, error, refresh };
+}
@@ -1,6 +1,6 @@
 import { useCallback, useMemo } from "react";
 import type { WorkspaceInfo } from "../../../types";
-import { fileRead, fileWrite } from "../../../services/tauri";
+import { fileRead, fileWrite, type FileVersion } from "../../../services/tauri";
 import { useFileEditor } from "../../shared/hooks/useFileEditor";
 
 type UseWorkspaceClaudeMdOptions = {
@@ -24,11 +24,11 @@ export function useWorkspaceClaudeMd({
   }, [workspaceId]);
 
   const write = useCallback(
-    async (content: string) => {
+    async (content: string, expectedVersion: FileVersion | null) => {
       if (!workspaceId) {
         throw new Error("No workspace selected");
       }
-      return fileWrite("workspace", "claude_md", content, workspaceId);
+      return fileWrite("workspace", "claude_md", content, workspaceId, expectedVersion);
     },
     [workspaceId],
   );
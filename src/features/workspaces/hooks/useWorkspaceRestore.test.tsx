@@ -0,0 +1,43 @@
+// @vitest-environment jsdom
+import { renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import type { WorkspaceInfo } from "../../../types";
+import { useWorkspaceRestore } from "./useWorkspaceRestore";
+
+const workspace = (id: string, connected = false): WorkspaceInfo => ({
+  id,
+  name: id,
+  path: `/tmp/${id}`,
+  connected,
+  settings: { sidebarCollapsed: false },
+});
+
+describe("useWorkspaceRestore", () => {
+  it("lists every workspace but connects only the active one", async () => {
+    const connectWorkspace = vi.fn().mockResolvedValue(undefined);
+    const listThreadsForWorkspace = vi.fn().mockResolvedValue(undefined);
+    const workspaces = [workspace("a"), workspace("b"), workspace("c", true)];
+    const { rerender } = renderHook(
+      ({ activeWorkspaceId }) =>
+        useWorkspaceRestore({
+          workspaces,
+          hasLoaded: true,
+          activeWorkspaceId,
+          connectWorkspace,
+          listThreadsForWorkspace,
+        }),
+      { initialProps: { activeWorkspaceId: "a" as string | null } },
+    );
+
+    await waitFor(() => expect(listThreadsForWorkspace).toHaveBeenCalledTimes(3));
+    expect(connectWorkspace).toHaveBeenCalledTimes(1);
+    expect(connectWorkspace).toHaveBeenCalledWith(workspaces[0]);
+
+    rerender({ activeWorkspaceId: "c" });
+    rerender({ activeWorkspaceId: "a" });
+    expect(connectWorkspace).toHaveBeenCalledTimes(1);
+
+    rerender({ activeWorkspaceId: "b" });
+    expect(connectWorkspace).toHaveBeenLastCalledWith(workspaces[1]);
+  });
+});
@@ -4,6 +4,7 @@ import type { WorkspaceInfo } from "../../../types";
 type WorkspaceRestoreOptions = {
   workspaces: WorkspaceInfo[];
   hasLoaded: boolean;
+  activeWorkspaceId: string | null;
   connectWorkspace: (workspace: WorkspaceInfo) => Promise<void>;
   listThreadsForWorkspace: (
     workspace: WorkspaceInfo,
@@ -11,13 +12,19 @@ type WorkspaceRestoreOptions = {
   ) => Promise<void>;
 };
 
+/**
+ * Threads are listed for every workspace, but only the workspace in view is
+ * connected; the rest start their session on first send.
+ */
 export function useWorkspaceRestore({
   workspaces,
   hasLoaded,
+  activeWorkspaceId,
   connectWorkspace,
   listThreadsForWorkspace,
 }: WorkspaceRestoreOptions) {
   const restoredWorkspaces = useRef(new Set<string>());
+  const connectedOnView = useRef(new Set<string>());
 
   useEffect(() => {
     if (!hasLoaded) {
@@ -28,16 +35,27 @@ export function useWorkspaceRestore({
         return;
       }
       restoredWorkspaces.current.add(workspace.id);
-      void (async () => {
-        try {
-          if (!workspace.connected) {
-            await connectWorkspace(workspace);
-          }
-          await listThreadsForWorkspace(workspace);
-        } catch {
-          // Silent: connection errors show in debug panel.
-        }
-      })();
+      void listThreadsForWorkspace(workspace).catch(() => {
+        // Silent: listing errors show in debug panel.
+      });
     });
-  }, [connectWorkspace, hasLoaded, listThreadsForWorkspace, workspaces]);
+  }, [hasLoaded, listThreadsForWorkspace, workspaces]);
+
+  useEffect(() => {
+    if (!hasLoaded || !activeWorkspaceId) {
+      return;
+    }
+    const workspace = workspaces.find((entry) => entry.id === activeWorkspaceId);
+    if (
+      !workspace ||
+      workspace.connected ||
+      connectedOnView.current.has(workspace.id)
+    ) {
+      return;
+    }
+    connectedOnView.current.add(workspace.id);
+    void connectWorkspace(workspace).catch(() => {
+      // Silent: connection errors show in debug panel.
+    });
+  }, [activeWorkspaceId, connectWorkspace, hasLoaded, workspaces]);
 }
@@ -3,10 +3,12 @@ import type { Event, EventCallback, UnlistenFn } from "@tauri-apps/api/event";
 import { listen } from "@tauri-apps/api/event";
 import type { AppServerEvent } from "../types";
 import {
+  checkEventSequence,
   subscribeAppServerEvents,
   subscribeMenuCycleModel,
   subscribeMenuNewAgent,
   subscribeTerminalOutput,
+  subscribeThreadWindowEvents,
 } from "./events";
 
 vi.mock("@tauri-apps/api/event", () => ({
@@ -47,6 +49,14 @@ describe("events subscriptions", () => {
     expect(unlisten).toHaveBeenCalledTimes(1);
   });
 
+  it("classifies event sequence numbers", () => {
+    expect(checkEventSequence(null, 5)).toBe("next");
+    expect(checkEventSequence(4, 5)).toBe("next");
+    expect(checkEventSequence(4, 4)).toBe("duplicate");
+    expect(checkEventSequence(4, 7)).toBe("gap");
+    expect(checkEventSequence(40, 1)).toBe("restart");
+  });
+
   it("cleans up listeners that resolve after unsubscribe", async () => {
     let resolveListener: (handler: UnlistenFn) => void = () => {};
     const unlisten = vi.fn();
@@ -89,6 +99,18 @@ describe("events subscriptions", () => {
     cleanup();
   });
 
+  it("listens on the per-window channel for thread windows", () => {
+    vi.mocked(listen).mockResolvedValue(vi.fn());
+
+    const cleanup = subscribeThreadWindowEvents("thread-ws-1-t-1", vi.fn());
+
+    expect(listen).toHaveBeenCalledWith(
+      "app-server-event:thread-ws-1-t-1",
+      expect.any(Function),
+    );
+    cleanup();
+  });
+
   it("reports listen errors through options", async () => {
     const error = new Error("nope");
     vi.mocked(listen).mockRejectedValueOnce(error);
@@ -1,5 +1,13 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppServerEvent,
+  CloneProgress,
+  DictationEvent,
+  DictationModelStatus,
+  LocalUsageSnapshot,
+  TurnReplayEvent,
+  UpdaterProgress,
+} from "../types";
 
 export type Unsubscribe = () => void;
 
@@ -81,6 +89,11 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const updaterCheckHub = createEventHub<void>("updater-check");
+const updaterProgressHub = createEventHub<UpdaterProgress>("updater-progress");
+const cloneProgressHub = createEventHub<CloneProgress>("clone-progress");
+const turnReplayHub = createEventHub<TurnReplayEvent>("turn-replay");
+const updaterDownloadedHub = createEventHub<{ version: string }>("updater-downloaded");
+const localUsageUpdatedHub = createEventHub<LocalUsageSnapshot>("local-usage-updated");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
 const menuNewCloneAgentHub = createEventHub<void>("menu-new-clone-agent");
@@ -94,6 +107,7 @@ const menuNextAgentHub = createEventHub<void>("menu-next-agent");
 const menuPrevAgentHub = createEventHub<void>("menu-prev-agent");
 const menuNextWorkspaceHub = createEventHub<void>("menu-next-workspace");
 const menuPrevWorkspaceHub = createEventHub<void>("menu-prev-workspace");
+const menuOpenWorkspaceHub = createEventHub<string>("menu-open-workspace");
 const menuCycleModelHub = createEventHub<void>("menu-composer-cycle-model");
 const menuCycleAccessHub = createEventHub<void>("menu-composer-cycle-access");
 const menuCycleReasoningHub = createEventHub<void>("menu-composer-cycle-reasoning");
@@ -101,6 +115,27 @@ const menuComposerCycleModelHub = createEventHub<void>("menu-composer-cycle-mode
 const menuComposerCycleAccessHub = createEventHub<void>("menu-composer-cycle-access");
 const menuComposerCycleReasoningHub = createEventHub<void>("menu-composer-cycle-reasoning");
 
+export type EventSequence = "next" | "duplicate" | "gap" | "restart";
+
+/**
+ * Classify an app-server event's `seq` against the last one handled for its
+ * workspace. Drop duplicates; on a gap, call `replayEvents` with `lastSeq` and
+ * reload the thread if the replay is incomplete. A restart means the backend
+ * started numbering again, so `lastSeq` should be reset.
+ */
+export function checkEventSequence(
+  lastSeq: number | null | undefined,
+  seq: number,
+): EventSequence {
+  if (lastSeq == null || seq === lastSeq + 1) {
+    return "next";
+  }
+  if (seq > lastSeq) {
+    return "gap";
+  }
+  return seq === 1 ? "restart" : "duplicate";
+}
+
 export function subscribeAppServerEvents(
   onEvent: (event: AppServerEvent) => void,
   options?: SubscriptionOptions,
@@ -108,6 +143,25 @@ export function subscribeAppServerEvents(
   return appServerHub.subscribe(onEvent, options);
 }
 
+const threadWindowHubs = new Map<
+  string,
+  ReturnType<typeof createEventHub<AppServerEvent>>
+>();
+
+// Thread windows get only their own thread's events on a per-window channel.
+export function subscribeThreadWindowEvents(
+  windowLabel: string,
+  onEvent: (event: AppServerEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  let hub = threadWindowHubs.get(windowLabel);
+  if (!hub) {
+    hub = createEventHub<AppServerEvent>(`app-server-event:${windowLabel}`);
+    threadWindowHubs.set(windowLabel, hub);
+  }
+  return hub.subscribe(onEvent, options);
+}
+
 export function subscribeDictationDownload(
   onEvent: (event: DictationModelStatus) => void,
   options?: SubscriptionOptions,
@@ -129,6 +183,13 @@ export function subscribeTerminalOutput(
   return terminalOutputHub.subscribe(onEvent, options);
 }
 
+export function subscribeLocalUsageUpdated(
+  onEvent: (snapshot: LocalUsageSnapshot) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return localUsageUpdatedHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -138,6 +199,34 @@ export function subscribeUpdaterCheck(
   }, options);
 }
 
+export function subscribeCloneProgress(
+  onEvent: (event: CloneProgress) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return cloneProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeTurnReplay(
+  onEvent: (event: TurnReplayEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return turnReplayHub.subscribe(onEvent, options);
+}
+
+export function subscribeUpdaterProgress(
+  onEvent: (event: UpdaterProgress) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return updaterProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeUpdaterDownloaded(
+  onEvent: (event: { version: string }) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return updaterDownloadedHub.subscribe(onEvent, options);
+}
+
 export function subscribeMenuNewAgent(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -255,6 +344,13 @@ export function subscribeMenuPrevWorkspace(
   }, options);
 }
 
+export function subscribeMenuOpenWorkspace(
+  onEvent: (workspaceId: string) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return menuOpenWorkspaceHub.subscribe(onEvent, options);
+}
+
 export function subscribeMenuCycleModel(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -9,6 +9,7 @@ import {
   respondToUserInputRequest,
   sendUserMessage,
   startReview,
+  taskReadyList,
 } from "./tauri";
 
 vi.mock("@tauri-apps/api/core", () => ({
@@ -110,6 +111,9 @@ describe("tauri invoke wrappers", () => {
       accessMode: "full-access",
       images: ["image.png"],
       collaborationMode: null,
+      redact: null,
+      cwd: null,
+      editScope: null,
     });
   });
 
@@ -166,4 +170,15 @@ describe("tauri invoke wrappers", () => {
       },
     });
   });
+
+  it("invokes task_ready_list with the list id", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ listId: "list-1", tasks: [] });
+
+    await taskReadyList("list-1");
+
+    expect(invokeMock).toHaveBeenCalledWith("task_ready_list", {
+      listId: "list-1",
+    });
+  });
 });
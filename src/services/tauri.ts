@@ -1,26 +1,86 @@
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type {
+  AcceleratorReport,
   AppSettings,
+  AttachedImage,
+  AttachmentStageResult,
+  AuditEntry,
+  BackgroundRun,
+  BackgroundRunApprovalPolicy,
+  BenchReport,
+  BenchSpec,
   ClaudeDoctorResult,
+  CodeBlock,
+  CodeBlockApplication,
+  CleanupReport,
   ClaudeTasksResponse,
+  CloneOptions,
+  DaemonStatus,
+  DiagnosticsReport,
+  EventReplay,
+  ExternalWorktree,
   DictationModelStatus,
   DictationSessionState,
+  FileReference,
+  LineTurn,
+  LiveTurnState,
   LocalUsageSnapshot,
+  LogEntry,
+  LogLevel,
+  ModelComparison,
+  PaletteResult,
+  Presence,
+  PresetThreadStart,
+  PruneReport,
+  PurgeReport,
+  QueuedTurn,
+  RecentThread,
+  RecoveryReport,
+  RedactionPreview,
+  RemoteHealth,
+  SelectionContext,
+  SessionCheck,
+  SessionResourceUsage,
+  SettingsImportSummary,
+  SettingsProfile,
+  StorageReport,
+  ThreadDraft,
+  ThreadTagCount,
+  ToolApprovalDecision,
+  TunnelStatus,
+  TurnDiff,
+  TurnRecordingInfo,
+  TurnReport,
+  UpdateCheck,
+  UsageAnalytics,
+  UsageAnalyticsRange,
+  UsageHeatmap,
+  WorkspaceActivityEntry,
+  WorkspaceDirEntry,
+  WorkspaceFileMatch,
   WorkspaceInfo,
   WorkspaceSettings,
 } from "../types";
 import type {
+  CiStatus,
+  GitBlame,
   GitFileDiff,
   GitFileStatus,
   GitCommitDiff,
   GitHubIssuesResponse,
+  IssueThreadStart,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
   GitHubPullRequestsResponse,
+  GitLogEntry,
   GitLogResponse,
+  GitTag,
   ReviewTarget,
+  WorktreeRebaseResult,
+  WorktreeSummary,
 } from "../types";
+import type { TaskListResponse } from "../features/tasks/types";
 
 /**
  * Detect whether an error is due to a missing Tauri invoke bridge.
@@ -62,6 +122,33 @@ export async function pickImageFiles(): Promise<string[]> {
   return Array.isArray(selection) ? selection : [selection];
 }
 
+export async function attachClipboardImage(
+  workspaceId: string,
+  threadId: string,
+): Promise<AttachedImage> {
+  return invoke<AttachedImage>("attach_clipboard_image", { workspaceId, threadId });
+}
+
+export async function attachmentStage(
+  workspaceId: string,
+  threadId: string,
+  paths: string[],
+): Promise<AttachmentStageResult> {
+  return invoke<AttachmentStageResult>("attachment_stage", {
+    workspaceId,
+    threadId,
+    paths,
+  });
+}
+
+export async function checkForUpdates(): Promise<UpdateCheck> {
+  return invoke<UpdateCheck>("check_for_updates");
+}
+
+export async function installUpdate(): Promise<void> {
+  return invoke("install_update");
+}
+
 export async function listWorkspaces(): Promise<WorkspaceInfo[]> {
   try {
     return await invoke<WorkspaceInfo[]>("list_workspaces");
@@ -88,14 +175,22 @@ export async function addClone(
   sourceWorkspaceId: string,
   copiesFolder: string,
   copyName: string,
+  options?: CloneOptions,
 ): Promise<WorkspaceInfo> {
   return invoke<WorkspaceInfo>("add_clone", {
     sourceWorkspaceId,
     copiesFolder,
     copyName,
+    depth: options?.depth ?? null,
+    filter: options?.filter ?? null,
+    cloneId: options?.cloneId ?? null,
   });
 }
 
+export async function cancelClone(cloneId: string): Promise<void> {
+  return invoke("cancel_clone", { cloneId });
+}
+
 export async function addWorktree(
   parentId: string,
   branch: string,
@@ -103,6 +198,19 @@ export async function addWorktree(
   return invoke<WorkspaceInfo>("add_worktree", { parentId, branch });
 }
 
+export async function listExternalWorktrees(
+  parentId: string,
+): Promise<ExternalWorktree[]> {
+  return invoke<ExternalWorktree[]>("list_external_worktrees", { parentId });
+}
+
+export async function adoptWorktree(
+  parentId: string,
+  path: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("adopt_worktree", { parentId, path });
+}
+
 export async function updateWorkspaceSettings(
   id: string,
   settings: WorkspaceSettings,
@@ -125,6 +233,30 @@ export async function removeWorktree(id: string): Promise<void> {
   return invoke("remove_worktree", { id });
 }
 
+export async function runStartupCommands(workspaceId: string): Promise<void> {
+  return invoke("run_startup_commands", { workspaceId });
+}
+
+export async function stopWorkspaceStartupCommands(
+  workspaceId: string,
+): Promise<void> {
+  return invoke("stop_workspace_startup_commands", { workspaceId });
+}
+
+export async function renameWorkspace(
+  id: string,
+  name: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("rename_workspace", { id, name });
+}
+
+export async function moveWorkspacePath(
+  id: string,
+  path: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("move_workspace_path", { id, path });
+}
+
 export async function renameWorktree(
   id: string,
   branch: string,
@@ -148,12 +280,56 @@ export async function openWorkspaceIn(path: string, app: string): Promise<void>
   return invoke("open_workspace_in", { path, app });
 }
 
+export async function openThreadWindow(
+  workspaceId: string,
+  threadId: string,
+  title?: string | null,
+): Promise<string> {
+  return invoke<string>("open_thread_window", {
+    workspaceId,
+    threadId,
+    title: title ?? null,
+  });
+}
+
+export async function paletteQuery(
+  query: string,
+  options?: { workspaceId?: string | null; limit?: number | null },
+): Promise<PaletteResult[]> {
+  return invoke<PaletteResult[]>("palette_query", {
+    query,
+    workspaceId: options?.workspaceId ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function resolveFileReference(
+  workspaceId: string,
+  text: string,
+): Promise<FileReference[]> {
+  return invoke<FileReference[]>("resolve_file_reference", { workspaceId, text });
+}
+
+export async function buildSelectionContext(
+  workspaceId: string,
+  path: string,
+  startLine: number,
+  endLine: number,
+): Promise<SelectionContext> {
+  return invoke<SelectionContext>("build_selection_context", {
+    workspaceId,
+    path,
+    startLine,
+    endLine,
+  });
+}
+
 export async function connectWorkspace(id: string): Promise<void> {
   return invoke("connect_workspace", { id });
 }
 
-export async function startThread(workspaceId: string) {
-  return invoke<any>("start_thread", { workspaceId });
+export async function startThread(workspaceId: string, cwd?: string | null) {
+  return invoke<any>("start_thread", { workspaceId, cwd: cwd ?? null });
 }
 
 export async function sendUserMessage(
@@ -166,6 +342,9 @@ export async function sendUserMessage(
     accessMode?: "read-only" | "current" | "full-access";
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
+    redact?: boolean;
+    cwd?: string | null;
+    editScope?: string[] | null;
   },
 ) {
   return invoke("send_user_message", {
@@ -177,6 +356,152 @@ export async function sendUserMessage(
     accessMode: options?.accessMode ?? null,
     images: options?.images ?? null,
     collaborationMode: options?.collaborationMode ?? null,
+    redact: options?.redact ?? null,
+    cwd: options?.cwd ?? null,
+    editScope: options?.editScope ?? null,
+  });
+}
+
+export async function previewRedaction(
+  workspaceId: string,
+  text: string,
+): Promise<RedactionPreview> {
+  return invoke<RedactionPreview>("redaction_preview", { workspaceId, text });
+}
+
+export async function startBackgroundRun(
+  workspaceId: string,
+  prompt: string,
+  options?: { model?: string | null; approvalPolicy?: BackgroundRunApprovalPolicy },
+): Promise<BackgroundRun> {
+  return invoke<BackgroundRun>("start_background_run", {
+    workspaceId,
+    prompt,
+    options: options ?? null,
+  });
+}
+
+export async function getBackgroundRunReport(runId: string): Promise<BackgroundRun> {
+  return invoke<BackgroundRun>("run_get_report", { runId });
+}
+
+export async function listBackgroundRuns(
+  workspaceId?: string | null,
+): Promise<BackgroundRun[]> {
+  return invoke<BackgroundRun[]>("background_run_list", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function benchRun(
+  workspaceId: string,
+  spec: BenchSpec,
+): Promise<BenchReport> {
+  return invoke<BenchReport>("bench_run", { workspaceId, spec });
+}
+
+export async function getBenchReport(benchId: string): Promise<BenchReport> {
+  return invoke<BenchReport>("bench_get_report", { benchId });
+}
+
+export async function extractCodeBlocks(
+  workspaceId: string,
+  threadId: string,
+  itemId: string,
+): Promise<CodeBlock[]> {
+  return invoke<CodeBlock[]>("extract_code_blocks", {
+    workspaceId,
+    threadId,
+    itemId,
+  });
+}
+
+export async function applyCodeBlock(
+  workspaceId: string,
+  threadId: string,
+  itemId: string,
+  blockIndex: number,
+  path: string,
+  options?: { dryRun?: boolean; expectedHash?: string | null },
+): Promise<CodeBlockApplication> {
+  return invoke<CodeBlockApplication>("apply_code_block", {
+    workspaceId,
+    threadId,
+    itemId,
+    blockIndex,
+    path,
+    dryRun: options?.dryRun ?? false,
+    expectedHash: options?.expectedHash ?? null,
+  });
+}
+
+export async function compareModels(
+  workspaceId: string,
+  prompt: string,
+  models: string[],
+  threadId?: string | null,
+): Promise<ModelComparison> {
+  return invoke<ModelComparison>("compare_models", {
+    workspaceId,
+    threadId: threadId ?? null,
+    prompt,
+    models,
+  });
+}
+
+export async function getTurnReport(
+  workspaceId: string,
+  threadId: string,
+  turnId?: string | null,
+): Promise<TurnReport | null> {
+  return invoke<TurnReport | null>("get_turn_report", {
+    workspaceId,
+    threadId,
+    turnId: turnId ?? null,
+  });
+}
+
+export async function getTurnDiff(
+  workspaceId: string,
+  threadId: string,
+  turnId: string,
+): Promise<TurnDiff> {
+  return invoke<TurnDiff>("get_turn_diff", { workspaceId, threadId, turnId });
+}
+
+export async function whichTurnChanged(
+  workspaceId: string,
+  path: string,
+  line: number,
+): Promise<LineTurn | null> {
+  return invoke<LineTurn | null>("which_turn_changed", { workspaceId, path, line });
+}
+
+export async function approvePlan(
+  workspaceId: string,
+  threadId: string,
+  toolUseId: string,
+  options?: { accessMode?: string | null },
+) {
+  return invoke("approve_plan", {
+    workspaceId,
+    threadId,
+    toolUseId,
+    accessMode: options?.accessMode ?? null,
+  });
+}
+
+export async function rejectPlan(
+  workspaceId: string,
+  threadId: string,
+  toolUseId: string,
+  options?: { feedback?: string | null },
+) {
+  return invoke("reject_plan", {
+    workspaceId,
+    threadId,
+    toolUseId,
+    feedback: options?.feedback ?? null,
   });
 }
 
@@ -215,6 +540,30 @@ export async function respondToUserInputRequest(
   });
 }
 
+export async function respondToApprovalRequest(
+  workspaceId: string,
+  threadId: string,
+  toolUseId: string,
+  decision: ToolApprovalDecision,
+) {
+  return invoke("respond_to_server_request", {
+    workspaceId,
+    threadId,
+    toolUseId,
+    result: { decision },
+  });
+}
+
+export async function getLiveTurnState(
+  workspaceId: string,
+  threadId: string,
+): Promise<LiveTurnState | null> {
+  return invoke<LiveTurnState | null>("get_live_turn_state", {
+    workspaceId,
+    threadId,
+  });
+}
+
 export async function rememberApprovalRule(
   workspaceId: string,
   rule: string,
@@ -253,6 +602,72 @@ export async function getGitLog(
   return invoke("get_git_log", { workspaceId: workspace_id, limit });
 }
 
+export async function getGitFileLog(
+  workspace_id: string,
+  path: string,
+  limit = 100,
+): Promise<GitLogEntry[]> {
+  return invoke("get_git_file_log", { workspaceId: workspace_id, path, limit });
+}
+
+export async function getGitBlame(
+  workspace_id: string,
+  path: string,
+  rev: string | null = null,
+): Promise<GitBlame> {
+  return invoke("get_git_blame", { workspaceId: workspace_id, path, rev });
+}
+
+export async function getWorktreeSummary(
+  workspace_id: string,
+): Promise<WorktreeSummary> {
+  return invoke("get_worktree_summary", { workspaceId: workspace_id });
+}
+
+export async function rebaseWorktreeOntoParent(
+  workspace_id: string,
+): Promise<WorktreeRebaseResult> {
+  return invoke("rebase_worktree_onto_parent", { workspaceId: workspace_id });
+}
+
+export async function continueWorktreeRebase(
+  workspace_id: string,
+): Promise<WorktreeRebaseResult> {
+  return invoke("continue_worktree_rebase", { workspaceId: workspace_id });
+}
+
+export async function abortWorktreeRebase(workspace_id: string): Promise<void> {
+  return invoke("abort_worktree_rebase", { workspaceId: workspace_id });
+}
+
+export async function listGitTags(workspace_id: string): Promise<GitTag[]> {
+  return invoke("list_git_tags", { workspaceId: workspace_id });
+}
+
+export async function createGitTag(
+  workspace_id: string,
+  name: string,
+  message: string | null = null,
+  target: string | null = null,
+): Promise<GitTag> {
+  return invoke("create_git_tag", { workspaceId: workspace_id, name, message, target });
+}
+
+export async function pushGitTag(
+  workspace_id: string,
+  name: string,
+  remote: string | null = null,
+): Promise<void> {
+  return invoke("push_git_tag", { workspaceId: workspace_id, name, remote });
+}
+
+export async function getCiStatus(
+  workspace_id: string,
+  branch?: string | null,
+): Promise<CiStatus> {
+  return invoke("get_ci_status", { workspaceId: workspace_id, branch: branch ?? null });
+}
+
 export async function getGitCommitDiff(
   workspace_id: string,
   sha: string,
@@ -291,6 +706,25 @@ export async function commitGit(
   return invoke("commit_git", { workspaceId, message });
 }
 
+export async function amendGitCommit(
+  workspaceId: string,
+  options: { message?: string | null; includeStaged?: boolean; force?: boolean } = {},
+): Promise<void> {
+  return invoke("amend_git_commit", {
+    workspaceId,
+    message: options.message ?? null,
+    includeStaged: options.includeStaged ?? true,
+    force: options.force ?? false,
+  });
+}
+
+export async function undoLastCommit(
+  workspaceId: string,
+  force = false,
+): Promise<void> {
+  return invoke("undo_last_commit", { workspaceId, force });
+}
+
 export async function pushGit(workspaceId: string): Promise<void> {
   return invoke("push_git", { workspaceId });
 }
@@ -309,6 +743,40 @@ export async function getGitHubIssues(
   return invoke("get_github_issues", { workspaceId: workspace_id });
 }
 
+export async function startThreadFromIssue(
+  workspaceId: string,
+  issueNumber: number,
+  options?: {
+    createWorktree?: boolean;
+    model?: string | null;
+    accessMode?: "read-only" | "current" | "full-access";
+  },
+): Promise<IssueThreadStart> {
+  return invoke<IssueThreadStart>("start_thread_from_issue", {
+    workspaceId,
+    issueNumber,
+    options: options
+      ? {
+          createWorktree: options.createWorktree ?? false,
+          model: options.model ?? null,
+          accessMode: options.accessMode ?? null,
+        }
+      : null,
+  });
+}
+
+export async function startThreadFromPreset(
+  workspaceId: string,
+  presetId: string,
+  variables?: Record<string, string> | null,
+): Promise<PresetThreadStart> {
+  return invoke<PresetThreadStart>("start_thread_from_preset", {
+    workspaceId,
+    presetId,
+    variables: variables ?? null,
+  });
+}
+
 export async function getGitHubPullRequests(
   workspace_id: string,
 ): Promise<GitHubPullRequestsResponse> {
@@ -346,6 +814,201 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
+export async function usageAnalytics(
+  workspaceId: string,
+  range: UsageAnalyticsRange,
+): Promise<UsageAnalytics> {
+  return invoke<UsageAnalytics>("usage_analytics", { workspaceId, range });
+}
+
+export async function usageHeatmap(range: UsageAnalyticsRange): Promise<UsageHeatmap> {
+  return invoke<UsageHeatmap>("usage_heatmap", { range });
+}
+
+export async function getWorkspaceActivity(
+  workspaceId: string,
+  since?: number | null,
+  limit?: number | null,
+): Promise<WorkspaceActivityEntry[]> {
+  return invoke<WorkspaceActivityEntry[]>("get_workspace_activity", {
+    workspaceId,
+    since: since ?? null,
+    limit: limit ?? null,
+  });
+}
+
+export async function getTurnQueue(): Promise<QueuedTurn[]> {
+  return invoke<QueuedTurn[]>("get_turn_queue");
+}
+
+export async function getSessionResourceSnapshot(): Promise<SessionResourceUsage[]> {
+  return invoke<SessionResourceUsage[]>("session_resource_snapshot");
+}
+
+export async function killSessionProcess(workspaceId: string, threadId: string) {
+  return invoke("kill_session_process", { workspaceId, threadId });
+}
+
+export async function verifySession(
+  workspaceId: string,
+  threadId: string,
+  repair = false,
+): Promise<SessionCheck> {
+  return invoke<SessionCheck>("verify_session", { workspaceId, threadId, repair });
+}
+
+export async function pruneThreadOutputs(
+  workspaceId: string,
+  threadId: string,
+  thresholdBytes?: number,
+): Promise<PruneReport> {
+  return invoke<PruneReport>("prune_thread_outputs", {
+    workspaceId,
+    threadId,
+    thresholdBytes: thresholdBytes ?? null,
+  });
+}
+
+export async function getRecoveryReport(): Promise<RecoveryReport | null> {
+  return invoke<RecoveryReport | null>("get_recovery_report");
+}
+
+export async function getRemoteBackendHealth(): Promise<RemoteHealth> {
+  return invoke<RemoteHealth>("remote_backend_health");
+}
+
+export async function getSshTunnelStatus(): Promise<TunnelStatus> {
+  return invoke<TunnelStatus>("ssh_tunnel_status");
+}
+
+export async function getDaemonLogs(options?: {
+  level?: LogLevel | null;
+  since?: number | null;
+  until?: number | null;
+  limit?: number | null;
+}): Promise<LogEntry[]> {
+  return invoke<LogEntry[]>("get_daemon_logs", {
+    level: options?.level ?? null,
+    since: options?.since ?? null,
+    until: options?.until ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function replayEvents(
+  workspaceId: string,
+  sinceSeq?: number | null,
+): Promise<EventReplay> {
+  return invoke<EventReplay>("replay_events", {
+    workspaceId,
+    sinceSeq: sinceSeq ?? null,
+  });
+}
+
+export async function listTurnRecordings(
+  workspaceId: string,
+): Promise<TurnRecordingInfo[]> {
+  return invoke<TurnRecordingInfo[]>("list_turn_recordings", { workspaceId });
+}
+
+export async function replayTurn(
+  workspaceId: string,
+  turnId: string,
+  speed?: number | null,
+): Promise<string> {
+  return invoke<string>("replay_turn", {
+    workspaceId,
+    turnId,
+    speed: speed ?? null,
+  });
+}
+
+export async function cancelTurnReplay(replayId: string): Promise<void> {
+  return invoke("cancel_turn_replay", { replayId });
+}
+
+export async function getAppLogs(options?: {
+  level?: LogLevel | null;
+  module?: string | null;
+  since?: number | null;
+  until?: number | null;
+  limit?: number | null;
+}): Promise<LogEntry[]> {
+  return invoke<LogEntry[]>("get_app_logs", {
+    level: options?.level ?? null,
+    module: options?.module ?? null,
+    since: options?.since ?? null,
+    until: options?.until ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function getAuditLog(options?: {
+  workspaceId?: string | null;
+  since?: number | null;
+  limit?: number | null;
+}): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>("get_audit_log", {
+    workspaceId: options?.workspaceId ?? null,
+    since: options?.since ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function generateDiagnostics(path?: string | null): Promise<DiagnosticsReport> {
+  return invoke<DiagnosticsReport>("generate_diagnostics", { path: path ?? null });
+}
+
+export async function updatePresence(
+  workspaceId: string,
+  threadId: string | null,
+  typing: boolean,
+): Promise<Presence> {
+  return invoke<Presence>("presence_update", { workspaceId, threadId, typing });
+}
+
+export async function daemonInstall(binaryPath?: string | null): Promise<DaemonStatus> {
+  return invoke<DaemonStatus>("daemon_install", { binaryPath: binaryPath ?? null });
+}
+
+export async function daemonStart(): Promise<DaemonStatus> {
+  return invoke<DaemonStatus>("daemon_start");
+}
+
+export async function daemonStop(): Promise<DaemonStatus> {
+  return invoke<DaemonStatus>("daemon_stop");
+}
+
+export async function daemonStatus(): Promise<DaemonStatus> {
+  return invoke<DaemonStatus>("daemon_status");
+}
+
+export async function purgeExpiredData(): Promise<PurgeReport> {
+  return invoke("data_retention_purge");
+}
+
+export async function purgeWorkspaceData(
+  workspaceId: string,
+  includeClaudeSessions = false,
+): Promise<PurgeReport> {
+  return invoke("purge_workspace_data", { workspaceId, includeClaudeSessions });
+}
+
+export async function getWorkspaceStorageReport(): Promise<StorageReport> {
+  return invoke("workspace_storage_report");
+}
+
+export async function deleteOldSessions(
+  workspaceId: string,
+  olderThanDays: number,
+): Promise<CleanupReport> {
+  return invoke("delete_old_sessions", { workspaceId, olderThanDays });
+}
+
+export async function pruneAppCaches(): Promise<CleanupReport> {
+  return invoke("prune_app_caches");
+}
+
 export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
@@ -451,6 +1114,38 @@ export async function updateAppSettings(settings: AppSettings): Promise<AppSetti
   return invoke<AppSettings>("update_app_settings", { settings });
 }
 
+export async function setAllowedWorkspaceRoots(roots: string[]): Promise<string[]> {
+  return invoke<string[]>("set_allowed_workspace_roots", { roots });
+}
+
+export async function exportAppSettings(path: string): Promise<void> {
+  await invoke("export_app_settings", { path });
+}
+
+export async function importAppSettings(
+  path: string,
+): Promise<SettingsImportSummary> {
+  return invoke<SettingsImportSummary>("import_app_settings", { path });
+}
+
+export async function listSettingsProfiles(): Promise<SettingsProfile[]> {
+  return invoke<SettingsProfile[]>("list_settings_profiles");
+}
+
+export async function saveSettingsProfile(name: string): Promise<SettingsProfile> {
+  return invoke<SettingsProfile>("save_settings_profile", { name });
+}
+
+export async function applySettingsProfile(
+  name: string,
+): Promise<SettingsImportSummary> {
+  return invoke<SettingsImportSummary>("apply_settings_profile", { name });
+}
+
+export async function deleteSettingsProfile(name: string): Promise<void> {
+  await invoke("delete_settings_profile", { name });
+}
+
 type MenuAcceleratorUpdate = {
   id: string;
   accelerator: string | null;
@@ -458,8 +1153,12 @@ type MenuAcceleratorUpdate = {
 
 export async function setMenuAccelerators(
   updates: MenuAcceleratorUpdate[],
-): Promise<void> {
-  return invoke("menu_set_accelerators", { updates });
+): Promise<AcceleratorReport> {
+  return invoke<AcceleratorReport>("menu_set_accelerators", { updates });
+}
+
+export async function resetMenuAccelerators(): Promise<AcceleratorReport> {
+  return invoke<AcceleratorReport>("menu_reset_accelerators");
 }
 
 export async function runClaudeDoctor(
@@ -472,13 +1171,40 @@ export async function getWorkspaceFiles(workspaceId: string) {
   return invoke<string[]>("list_workspace_files", { workspaceId });
 }
 
+export async function listWorkspaceDir(
+  workspaceId: string,
+  options?: { path?: string | null; depth?: number | null },
+): Promise<WorkspaceDirEntry[]> {
+  return invoke<WorkspaceDirEntry[]>("list_workspace_dir", {
+    workspaceId,
+    path: options?.path ?? null,
+    depth: options?.depth ?? null,
+  });
+}
+
+export async function searchWorkspaceFiles(
+  workspaceId: string,
+  query: string,
+  options?: { limit?: number | null; refresh?: boolean },
+): Promise<WorkspaceFileMatch[]> {
+  return invoke<WorkspaceFileMatch[]>("search_workspace_files", {
+    workspaceId,
+    query,
+    limit: options?.limit ?? null,
+    refresh: options?.refresh ?? false,
+  });
+}
+
 export async function readWorkspaceFile(
   workspaceId: string,
   path: string,
-): Promise<{ content: string; truncated: boolean }> {
-  return invoke<{ content: string; truncated: boolean }>("read_workspace_file", {
+  range?: FileLineRange,
+): Promise<FileReadResponse> {
+  return invoke<FileReadResponse>("read_workspace_file", {
     workspaceId,
     path,
+    offset: range?.offset ?? null,
+    limit: range?.limit ?? null,
   });
 }
 
@@ -589,8 +1315,16 @@ export async function listThreads(
   workspaceId: string,
   cursor?: string | null,
   limit?: number | null,
+  tags?: string[] | null,
 ) {
-  return invoke<any>("list_threads", { workspaceId, cursor, limit });
+  return invoke<any>("list_threads", { workspaceId, cursor, limit, tags });
+}
+
+export async function listAllThreads(
+  cursor?: string | null,
+  limit?: number | null,
+): Promise<{ data: RecentThread[]; nextCursor: string | null }> {
+  return invoke("list_all_threads", { cursor, limit });
 }
 
 export async function searchThread(workspaceId: string, query: string) {
@@ -601,15 +1335,31 @@ export async function resumeThread(workspaceId: string, threadId: string) {
   return invoke<any>("resume_thread", { workspaceId, threadId });
 }
 
+export async function getItemFullOutput(
+  workspaceId: string,
+  threadId: string,
+  itemId: string,
+): Promise<string> {
+  return invoke<string>("get_item_full_output", { workspaceId, threadId, itemId });
+}
+
 export async function forkThreadFromMessage(
   workspaceId: string,
   threadId: string,
   messageId: string,
+  replacementText?: string | null,
+  options?: {
+    model?: string | null;
+    accessMode?: "read-only" | "current" | "full-access";
+  },
 ) {
   return invoke<any>("fork_thread_from_message", {
     workspaceId,
     threadId,
     messageId,
+    replacementText: replacementText ?? null,
+    model: options?.model ?? null,
+    accessMode: options?.accessMode ?? null,
   });
 }
 
@@ -629,6 +1379,30 @@ export async function archiveThread(workspaceId: string, threadId: string) {
   return invoke<any>("archive_thread", { workspaceId, threadId });
 }
 
+export async function setThreadTitle(
+  workspaceId: string,
+  threadId: string,
+  title: string | null,
+) {
+  return invoke<any>("set_thread_title", { workspaceId, threadId, title });
+}
+
+export async function pinThread(workspaceId: string, threadId: string, pinned: boolean) {
+  return invoke<any>("pin_thread", { workspaceId, threadId, pinned });
+}
+
+export async function tagThread(workspaceId: string, threadId: string, tag: string) {
+  return invoke<any>("tag_thread", { workspaceId, threadId, tag });
+}
+
+export async function untagThread(workspaceId: string, threadId: string, tag: string) {
+  return invoke<any>("untag_thread", { workspaceId, threadId, tag });
+}
+
+export async function listTags(workspaceId: string): Promise<{ data: ThreadTagCount[] }> {
+  return invoke<{ data: ThreadTagCount[] }>("list_tags", { workspaceId });
+}
+
 export async function getCommitMessagePrompt(
   workspaceId: string,
 ): Promise<string> {
@@ -641,6 +1415,13 @@ export async function generateCommitMessage(
   return invoke("generate_commit_message", { workspaceId });
 }
 
+export async function generateReleaseNotes(
+  workspaceId: string,
+  since: string | null = null,
+): Promise<string> {
+  return invoke("generate_release_notes", { workspaceId, since });
+}
+
 export async function generateRunMetadata(
   workspaceId: string,
   prompt: string,
@@ -654,6 +1435,11 @@ export async function getClaudeTasks(
   return invoke<ClaudeTasksResponse>("get_claude_tasks", { sessionId });
 }
 
+/** Pending tasks in a list whose dependencies are all completed. */
+export async function taskReadyList(listId: string): Promise<TaskListResponse> {
+  return invoke<TaskListResponse>("task_ready_list", { listId });
+}
+
 // File operations types
 export type FileScope = "workspace" | "global";
 export type FileKind = "claude_md" | "settings";
@@ -662,17 +1448,51 @@ export type FileReadResponse = {
   exists: boolean;
   content: string;
   truncated: boolean;
+  kind: "text" | "binary";
+  size: number;
+  mime: string | null;
+  encoding: string | null;
+  offset: number;
+  lineCount: number;
+  version: FileVersion | null;
+};
+
+export type FileVersion = {
+  exists: boolean;
+  modifiedAt: number | null;
+  hash: string;
+  truncated: boolean;
+  offset: number;
+  encoding: string | null;
+};
+
+export type FileWriteResult =
+  | { status: "written"; version: FileVersion }
+  | { status: "conflict"; current: FileReadResponse };
+
+export type ThreeWayMerge = {
+  content: string;
+  conflicts: number;
+  clean: boolean;
+};
+
+export type FileLineRange = {
+  offset?: number | null;
+  limit?: number | null;
 };
 
 export async function fileRead(
   scope: FileScope,
   kind: FileKind,
   workspaceId?: string | null,
+  range?: FileLineRange,
 ): Promise<FileReadResponse> {
   return invoke<FileReadResponse>("file_read", {
     scope,
     kind,
     workspaceId: workspaceId ?? null,
+    offset: range?.offset ?? null,
+    limit: range?.limit ?? null,
   });
 }
 
@@ -681,11 +1501,138 @@ export async function fileWrite(
   kind: FileKind,
   content: string,
   workspaceId?: string | null,
-): Promise<void> {
-  return invoke("file_write", {
+  expectedVersion?: FileVersion | null,
+): Promise<FileWriteResult> {
+  return invoke<FileWriteResult>("file_write", {
     scope,
     kind,
     content,
     workspaceId: workspaceId ?? null,
+    expectedVersion: expectedVersion ?? null,
   });
 }
+
+export type ClaudeMdImport = {
+  reference: string;
+  path: string;
+  source: string;
+  line: number;
+  depth: number;
+  estimatedTokens: number;
+  error: string | null;
+};
+
+export type ClaudeMdDocument = {
+  file: FileReadResponse;
+  imports: ClaudeMdImport[];
+  estimatedTokens: number;
+  contextTokens: number;
+};
+
+export type ClaudeMdWriteResponse = {
+  result: FileWriteResult;
+  imports: ClaudeMdImport[];
+};
+
+export async function claudeMdRead(
+  scope: FileScope,
+  workspaceId?: string | null,
+): Promise<ClaudeMdDocument> {
+  return invoke<ClaudeMdDocument>("claude_md_read", {
+    scope,
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function claudeMdWrite(
+  scope: FileScope,
+  content: string,
+  workspaceId?: string | null,
+  expectedVersion?: FileVersion | null,
+): Promise<ClaudeMdWriteResponse> {
+  return invoke<ClaudeMdWriteResponse>("claude_md_write", {
+    scope,
+    content,
+    workspaceId: workspaceId ?? null,
+    expectedVersion: expectedVersion ?? null,
+  });
+}
+
+export type ContextMemoryFile = {
+  scope: "user" | "project" | "local";
+  path: string;
+  imports: ClaudeMdImport[];
+  estimatedTokens: number;
+};
+
+export type ContextSkill = {
+  name: string;
+  description: string;
+  scope: "user" | "project";
+  path: string;
+  estimatedTokens: number;
+};
+
+export type ContextMcpServer = {
+  name: string;
+  scope: "user" | "project" | "local";
+  tools: string[];
+  estimatedTokens: number;
+};
+
+export type ContextPreview = {
+  workspaceId: string;
+  memory: ContextMemoryFile[];
+  skills: ContextSkill[];
+  mcpServers: ContextMcpServer[];
+  permissionMode: {
+    mode: string;
+    source: "app" | "localSettings" | "projectSettings" | "userSettings" | "cliDefault";
+  };
+  memoryTokens: number;
+  skillsTokens: number;
+  mcpTokens: number;
+  totalTokens: number;
+};
+
+export async function getContextPreview(workspaceId: string): Promise<ContextPreview> {
+  return invoke<ContextPreview>("get_context_preview", { workspaceId });
+}
+
+export type FileChangedEvent = {
+  path: string;
+  version: FileVersion;
+  diff: string | null;
+};
+
+export async function watchFile(workspaceId: string, path: string): Promise<FileVersion> {
+  return invoke<FileVersion>("file_watch", { workspaceId, path });
+}
+
+export async function unwatchFile(workspaceId: string, path: string): Promise<void> {
+  return invoke("file_unwatch", { workspaceId, path });
+}
+
+export async function saveThreadDraft(
+  workspaceId: string,
+  threadId: string,
+  text: string,
+  attachments: string[],
+): Promise<void> {
+  return invoke("thread_draft_save", { workspaceId, threadId, text, attachments });
+}
+
+export async function getThreadDraft(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadDraft | null> {
+  return invoke<ThreadDraft | null>("thread_draft_get", { workspaceId, threadId });
+}
+
+export async function fileMergeThreeWay(
+  base: string,
+  ours: string,
+  theirs: string,
+): Promise<ThreeWayMerge> {
+  return invoke<ThreeWayMerge>("file_merge_three_way", { base, ours, theirs });
+}
@@ -3,6 +3,43 @@ export type WorkspaceSettings = {
   sortOrder?: number | null;
   groupId?: string | null;
   gitRoot?: string | null;
+  redactionRules?: RedactionRule[];
+  autoConnect?: boolean;
+  additionalDirs?: string[];
+  startupCommands?: string[];
+  runStartupCommands?: boolean;
+  container?: ContainerSettings | null;
+  webAllowedDomains?: string[] | null;
+  threadPresets?: ThreadPreset[];
+};
+
+export type ContainerSettings = {
+  mode: "exec" | "run";
+  target: string;
+  workdir?: string | null;
+  runtime?: string | null;
+  claudeBin?: string | null;
+};
+
+export type ThreadPreset = {
+  id: string;
+  name: string;
+  model?: string | null;
+  accessMode?: string | null;
+  promptTemplate: string;
+  skills?: string[];
+};
+
+export type RedactionRule = {
+  label: string;
+  pattern: string;
+  enabled: boolean;
+  replacement?: string | null;
+};
+
+export type RedactionPreview = {
+  text: string;
+  matches: { label: string; count: number; samples: string[] }[];
 };
 
 export type WorkspaceGroup = {
@@ -18,6 +55,31 @@ export type WorktreeInfo = {
   branch: string;
 };
 
+export type CloneOptions = {
+  /** Shallow clone with this many commits. */
+  depth?: number | null;
+  /** Partial clone filter. */
+  filter?: "blob:none" | "tree:0" | null;
+  /** Tags `clone-progress` events and identifies the clone for `cancelClone`. */
+  cloneId?: string | null;
+};
+
+export type CloneProgress = {
+  cloneId: string;
+  phase: string;
+  percent: number | null;
+  current: number | null;
+  total: number | null;
+};
+
+export type ExternalWorktree = {
+  path: string;
+  branch: string | null;
+  head: string | null;
+  locked: boolean;
+  prunable: boolean;
+};
+
 export type WorkspaceInfo = {
   id: string;
   name: string;
@@ -33,6 +95,34 @@ export type WorkspaceInfo = {
 export type AppServerEvent = {
   workspace_id: string;
   message: Record<string, unknown>;
+  /** Increases by one per workspace; see `checkEventSequence`. */
+  seq?: number;
+};
+
+export type SequencedEvent = AppServerEvent & {
+  seq: number;
+};
+
+export type EventReplay = {
+  events: SequencedEvent[];
+  latestSeq: number;
+  complete: boolean;
+};
+
+export type TurnRecordingInfo = {
+  turnId: string;
+  threadId: string | null;
+  recordedAt: number;
+  eventCount: number;
+  durationMs: number;
+};
+
+export type TurnReplayEvent = {
+  replayId: string;
+  workspaceId: string;
+  turnId: string;
+  message?: Record<string, unknown>;
+  done: boolean;
 };
 
 export type Message = {
@@ -69,19 +159,79 @@ export type ConversationItem =
       status?: string;
       output?: string;
       durationMs?: number | null;
-      changes?: { path: string; kind?: string; diff?: string }[];
+      changes?: {
+        path: string;
+        kind?: string;
+        diff?: string;
+        additions?: number;
+        deletions?: number;
+      }[];
+      todos?: { content: string; status: string; activeForm?: string }[];
+      images?: { path: string; mediaType?: string }[];
+      subagentThreadId?: string;
+      outputTruncated?: boolean;
+      outputBytes?: number;
+      details?: ToolDetails;
+    };
+
+export type ToolDetails =
+  | {
+      kind: "bash";
+      command: string;
+      description: string | null;
+      exitCode: number | null;
+    }
+  | {
+      kind: "grep";
+      pattern: string;
+      path: string | null;
+      outputMode: string;
+      matchCount: number | null;
+      fileCount: number | null;
+    }
+  | {
+      kind: "read";
+      path: string;
+      lineStart: number | null;
+      lineEnd: number | null;
+      totalLines: number | null;
+    }
+  | {
+      kind: "webFetch";
+      url: string;
+      status: number | null;
+      statusText: string | null;
     };
 
 export type ThreadSummary = {
   id: string;
   name: string;
   updatedAt: number;
+  tags?: string[];
+};
+
+export type RecentThread = {
+  id: string;
+  workspaceId: string;
+  workspaceName: string;
+  workspaceBranch?: string | null;
+  gitBranch?: string | null;
+  preview: string;
+  title?: string | null;
+  tags?: string[];
+  updatedAt: number;
+};
+
+export type ThreadTagCount = {
+  tag: string;
+  count: number;
 };
 
 export type ReviewTarget =
   | { type: "uncommittedChanges" }
   | { type: "baseBranch"; branch: string }
   | { type: "commit"; sha: string; title?: string }
+  | { type: "pullRequestComments"; prNumber?: number }
   | { type: "custom"; instructions: string };
 
 export type AccessMode = "read-only" | "current" | "full-access";
@@ -102,11 +252,29 @@ export type ComposerEditorSettings = {
   continueListOnShiftEnter: boolean;
 };
 
+export type SshTunnelSettings = {
+  enabled: boolean;
+  host: string;
+  user: string | null;
+  keyPath: string | null;
+  sshPort: number | null;
+  localPort: number;
+  remotePort: number;
+};
+
 export type AppSettings = {
   claudeBin: string | null;
   backendMode: BackendMode;
   remoteBackendHost: string;
   remoteBackendToken: string | null;
+  remoteSshTunnel: SshTunnelSettings | null;
+  otlpEndpoint: string | null;
+  presenceSharingEnabled: boolean;
+  allowedWorkspaceRoots: string[];
+  approvalTimeoutSecs: number;
+  approvalTimeoutDecision: "accept" | "decline";
+  eventOutputLimitBytes: number;
+  promptDiffExcludes: string[];
   defaultAccessMode: AccessMode;
   composerModelShortcut: string | null;
   composerAccessShortcut: string | null;
@@ -138,6 +306,7 @@ export type AppSettings = {
   dictationModelId: string;
   dictationPreferredLanguage: string | null;
   dictationHoldKey: string | null;
+  dictationVoiceCommands: boolean;
   composerEditorPreset: ComposerEditorPreset;
   composerFenceExpandOnSpace: boolean;
   composerFenceExpandOnEnter: boolean;
@@ -148,6 +317,444 @@ export type AppSettings = {
   composerListContinuation: boolean;
   composerCodeBlockCopyUseModifier: boolean;
   workspaceGroups: WorkspaceGroup[];
+  dataRetentionDays: number | null;
+  turnReportsEnabled: boolean;
+  turnRecordingEnabled: boolean;
+  turnCheckpointsEnabled: boolean;
+  threadSummariesEnabled: boolean;
+  ciStatusPollingEnabled: boolean;
+  maxParallelTurns: number | null;
+  accelerators: Record<string, string | null>;
+  updateChannel: UpdateChannel;
+  logLevel: LogLevel | null;
+  logLevels: Record<string, LogLevel>;
+  fullAccessSandbox: SandboxSettings | null;
+  dangerousCommandGuardEnabled: boolean;
+  dangerousCommandPatterns: string[] | null;
+};
+
+export type SandboxTool = "auto" | "sandbox-exec" | "bubblewrap" | "firejail";
+
+export type SandboxSettings = {
+  enabled: boolean;
+  tool: SandboxTool;
+  writablePaths: string[];
+  allowNetwork: boolean;
+  apiHosts: string[];
+};
+
+export type UpdateChannel = "stable" | "beta";
+
+export type ReleaseNoteSection = {
+  title: string | null;
+  items: string[];
+};
+
+export type UpdateCheck = {
+  channel: UpdateChannel;
+  currentVersion: string;
+  available: boolean;
+  version: string | null;
+  date: string | null;
+  notes: string | null;
+  sections: ReleaseNoteSection[];
+};
+
+export type UpdaterProgress = {
+  version: string;
+  downloaded: number;
+  total: number | null;
+};
+
+export type AcceleratorFailureReason =
+  | "invalid"
+  | "reserved"
+  | "duplicate"
+  | "unregistered";
+
+export type AcceleratorFailure = {
+  id: string;
+  accelerator: string;
+  reason: AcceleratorFailureReason;
+  conflictsWith: string | null;
+};
+
+export type AcceleratorReport = {
+  applied: { id: string; accelerator: string | null }[];
+  failed: AcceleratorFailure[];
+};
+
+export type PaletteResultKind = "workspace" | "thread" | "prompt" | "file";
+
+export type PaletteResult = {
+  kind: PaletteResultKind;
+  id: string;
+  title: string;
+  subtitle: string | null;
+  workspaceId: string | null;
+  score: number;
+};
+
+export type AttachedImage = {
+  path: string;
+  mediaType: string;
+  size: number;
+  deduplicated: boolean;
+};
+
+export type StagedAttachment = {
+  name: string;
+  sourcePath: string;
+  path: string;
+  mime: string;
+  size: number;
+  kind: "text" | "binary";
+  preview: string | null;
+  deduplicated: boolean;
+};
+
+export type AttachmentStageResult = {
+  staged: StagedAttachment[];
+  rejected: { path: string; reason: string }[];
+};
+
+export type SelectionContext = {
+  path: string;
+  language: string | null;
+  startLine: number;
+  endLine: number;
+  snippet: string;
+  block: string;
+};
+
+export type FileReference = {
+  text: string;
+  path: string;
+  absolutePath: string;
+  line: number | null;
+  column: number | null;
+  exists: boolean;
+};
+
+export type WorkspaceDirEntry = {
+  name: string;
+  path: string;
+  kind: "dir" | "file";
+  size: number | null;
+  gitStatus: string | null;
+  children: WorkspaceDirEntry[] | null;
+  truncated: boolean;
+};
+
+export type WorkspaceFileMatch = {
+  path: string;
+  name: string;
+  score: number;
+};
+
+export type LiveTurnState = {
+  threadId: string;
+  turnId: string;
+  itemId: string;
+  text: string;
+  runningTools: Record<string, unknown>[];
+  pendingPermissions: {
+    toolUseId: string;
+    toolName: string;
+    toolInput: unknown;
+  }[];
+};
+
+export type QueuedTurn = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  position: number;
+  queuedAt: number;
+};
+
+export type SessionIssue = {
+  line: number;
+  offset: number;
+  length: number;
+  error: string;
+  preview: string;
+};
+
+export type PruneReport = {
+  threadId: string;
+  path: string;
+  prunedOutputs: number;
+  bytesBefore: number;
+  bytesAfter: number;
+  outputsDir: string;
+};
+
+export type SessionCheck = {
+  threadId: string;
+  path: string;
+  totalLines: number;
+  issues: SessionIssue[];
+  repaired: boolean;
+  backupPath: string | null;
+};
+
+export type SessionResourceUsage = {
+  workspaceId: string;
+  threadId: string;
+  pid: number;
+  processCount: number;
+  rssBytes: number;
+  cpuPercent: number;
+  sampledAt: number;
+};
+
+export type SpawnedProcess = {
+  pid: number;
+  workspaceId: string;
+  threadId: string;
+  startedAt: number;
+};
+
+export type RecoveryReport = {
+  killed: SpawnedProcess[];
+  exited: SpawnedProcess[];
+  skipped: SpawnedProcess[];
+  recoveredAt: number;
+};
+
+export type RemoteHealth = {
+  connected: boolean;
+  latencyMs: number | null;
+  lastError: string | null;
+  checkedAt: number | null;
+};
+
+export type TunnelStatus = {
+  state: "idle" | "connecting" | "connected" | "disconnected";
+  localAddress: string | null;
+  lastError: string | null;
+  reconnectAttempts: number;
+};
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+export type LogEntry = {
+  timestamp: number;
+  level: LogLevel;
+  target: string;
+  message: string;
+  fields?: Record<string, unknown>;
+};
+
+export type Presence = {
+  clientId: string;
+  name: string;
+  workspaceId: string;
+  threadId: string | null;
+  typing: boolean;
+  at: number;
+};
+
+export type DiagnosticsReport = {
+  path: string;
+  files: string[];
+  redactions: number;
+};
+
+export type AuditEntry = {
+  timestamp: number;
+  client: string;
+  action: string;
+  workspaceId?: string;
+  ok: boolean;
+  error?: string;
+  detail?: Record<string, unknown>;
+};
+
+export type SettingsProfile = {
+  name: string;
+  savedAt: number;
+};
+
+export type SettingsImportSummary = {
+  settings: AppSettings;
+  promptsImported: number;
+  promptsSkipped: number;
+  workspacesImported: number;
+  workspacesSkipped: number;
+  acceleratorsFailed: number;
+};
+
+export type DaemonStatus = {
+  manager: "systemd" | "launchd";
+  installed: boolean;
+  running: boolean;
+  servicePath: string;
+  binaryPath: string | null;
+};
+
+export type PurgeReport = {
+  taskLinks: number;
+  backgroundRuns: number;
+  turnReports: number;
+  archivedThreads: number;
+  threadTitles: number;
+  threadDrafts: number;
+  rewinds: number;
+  turnCheckpoints: number;
+  turnRecordings: number;
+  attachments: number;
+  toolImages: number;
+  benchReports: number;
+  exportedFiles: number;
+  claudeSessionFiles: number;
+};
+
+export type WorkspaceStorage = {
+  workspaceId: string;
+  name: string;
+  worktreeBytes: number;
+  sessionBytes: number;
+  sessionFiles: number;
+};
+
+export type StorageReport = {
+  workspaces: WorkspaceStorage[];
+  worktreesBytes: number;
+  sessionsBytes: number;
+  dictationModelsBytes: number;
+  cachesBytes: number;
+  totalBytes: number;
+};
+
+export type CleanupReport = {
+  filesRemoved: number;
+  bytesFreed: number;
+};
+
+export type BackgroundRunApprovalPolicy = "allowlist" | "full-access";
+
+export type BackgroundRunReport = {
+  filesChanged: string[];
+  commandsRun: string[];
+  testsStatus: "passed" | "failed" | "notRun";
+  summary?: string | null;
+};
+
+export type BackgroundRun = {
+  id: string;
+  workspaceId: string;
+  threadId: string;
+  turnId?: string | null;
+  prompt: string;
+  approvalPolicy: BackgroundRunApprovalPolicy;
+  status: "running" | "completed" | "failed" | "interrupted";
+  startedAt: number;
+  finishedAt?: number | null;
+  report?: BackgroundRunReport | null;
+};
+
+export type BenchCase = {
+  name: string;
+  prompt: string;
+  model?: string | null;
+};
+
+export type BenchSpec = {
+  name?: string | null;
+  cases: BenchCase[];
+  model?: string | null;
+  approvalPolicy?: BackgroundRunApprovalPolicy | null;
+  testCommand?: string | null;
+  keepWorktrees?: boolean;
+};
+
+export type BenchCaseResult = {
+  name: string;
+  prompt: string;
+  model?: string | null;
+  threadId?: string | null;
+  status: "completed" | "failed" | "interrupted" | "error";
+  testsStatus: "passed" | "failed" | "notRun";
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+  costUsd?: number | null;
+  durationMs: number;
+  patchPath?: string | null;
+  worktreePath?: string | null;
+  error?: string | null;
+};
+
+export type BenchReport = {
+  id: string;
+  workspaceId: string;
+  name?: string | null;
+  status: "running" | "completed";
+  startedAt: number;
+  finishedAt?: number | null;
+  results: BenchCaseResult[];
+};
+
+export type CodeBlock = {
+  index: number;
+  language: string | null;
+  path: string | null;
+  code: string;
+  isPatch: boolean;
+};
+
+export type CodeBlockApplication = {
+  path: string;
+  created: boolean;
+  diff: string;
+  baseHash: string;
+  applied: boolean;
+};
+
+export type ModelCompareRun = {
+  model: string;
+  threadId: string;
+  status: "completed" | "failed" | "interrupted";
+  output: string | null;
+};
+
+export type ModelComparison = {
+  sourceThreadId: string | null;
+  prompt: string;
+  runs: ModelCompareRun[];
+  diffs: { baseModel: string; model: string; diff: string }[];
+};
+
+export type TurnReport = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  status: "completed" | "failed";
+  filesChanged: string[];
+  commandsRun: string[];
+  usage?: Record<string, unknown> | null;
+  modelUsage?: Record<string, unknown> | null;
+  costUsd?: number | null;
+  durationMs?: number | null;
+  createdAt: number;
+};
+
+export type TurnDiff = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  complete: boolean;
+  files: GitFileDiff[];
+};
+
+export type LineTurn = {
+  threadId: string;
+  turnId: string;
+  line: number;
+  startedAt: number;
+  completedAt?: number | null;
 };
 
 export type ClaudeDoctorResult = {
@@ -167,6 +774,18 @@ export type PermissionDenial = {
   tool_input?: Record<string, unknown> | null;
 };
 
+export type ToolApprovalRequest = {
+  id: string;
+  workspace_id: string;
+  thread_id: string;
+  turn_id: string;
+  tool_name: string;
+  tool_use_id: string;
+  tool_input?: Record<string, unknown> | null;
+};
+
+export type ToolApprovalDecision = "accept" | "decline";
+
 export type RequestUserInputOption = {
   label: string;
   description: string;
@@ -194,6 +813,15 @@ export type RequestUserInputRequest = {
   params: RequestUserInputParams;
 };
 
+export type PlanProposal = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  itemId: string;
+  toolUseId: string;
+  plan: string;
+};
+
 export type RequestUserInputAnswer = {
   answers: string[];
 };
@@ -239,6 +867,34 @@ export type GitLogEntry = {
   timestamp: number;
 };
 
+export type GitTag = {
+  name: string;
+  sha: string;
+  message: string | null;
+  timestamp: number;
+};
+
+export type GitBlameCommit = {
+  sha: string;
+  summary: string;
+  author: string;
+  email: string;
+  timestamp: number;
+};
+
+export type GitBlameLine = {
+  line: number;
+  sha: string | null;
+  content: string;
+};
+
+export type GitBlame = {
+  path: string;
+  rev: string | null;
+  lines: GitBlameLine[];
+  commits: GitBlameCommit[];
+};
+
 export type GitLogResponse = {
   total: number;
   entries: GitLogEntry[];
@@ -249,6 +905,50 @@ export type GitLogResponse = {
   upstream: string | null;
 };
 
+export type WorktreePullRequest = {
+  number: number;
+  url: string;
+  state: string;
+  ciStatus: "success" | "failure" | "pending" | null;
+};
+
+export type WorktreeSummary = {
+  workspaceId: string;
+  branch: string;
+  baseBranch: string;
+  files: GitFileStatus[];
+  insertions: number;
+  deletions: number;
+  ahead: number;
+  behind: number;
+  conflictPaths: string[];
+  pullRequest: WorktreePullRequest | null;
+};
+
+export type WorktreeRebaseResult = {
+  status: "rebased" | "upToDate" | "conflicts";
+  onto: string;
+  conflicts: string[];
+  stoppedAt: GitLogEntry | null;
+};
+
+export type CiCheckStatus = "success" | "failure" | "pending" | "skipped";
+
+export type CiCheck = {
+  name: string;
+  status: CiCheckStatus;
+  url: string | null;
+};
+
+export type CiStatus = {
+  workspaceId: string;
+  branch: string;
+  provider: "github";
+  overall: Exclude<CiCheckStatus, "skipped"> | null;
+  checks: CiCheck[];
+  updatedAt: number;
+};
+
 export type GitHubIssue = {
   number: number;
   title: string;
@@ -256,6 +956,22 @@ export type GitHubIssue = {
   updatedAt: string;
 };
 
+export type IssueThreadStart = {
+  workspaceId: string;
+  worktree: WorkspaceInfo | null;
+  issue: { number: number; title: string; url: string };
+  thread: { id: string; createdAt: number; updatedAt: number; cwd: string };
+  turn: { id: string; threadId: string };
+};
+
+export type PresetThreadStart = {
+  workspaceId: string;
+  preset: { id: string; name: string };
+  thread: { id: string; createdAt: number; updatedAt: number; cwd: string };
+  turn: { id: string; threadId: string };
+  queuePosition: number | null;
+};
+
 export type GitHubIssuesResponse = {
   total: number;
   issues: GitHubIssue[];
@@ -305,10 +1021,20 @@ export type TokenUsageBreakdown = {
   reasoningOutputTokens: number;
 };
 
+export type ModelTokenUsage = {
+  model: string;
+  primary: boolean;
+  last: TokenUsageBreakdown;
+  total: TokenUsageBreakdown;
+  contextWindow: number | null;
+  costUsd: number | null;
+};
+
 export type ThreadTokenUsage = {
   total: TokenUsageBreakdown;
   last: TokenUsageBreakdown;
   modelContextWindow: number | null;
+  perModel?: ModelTokenUsage[];
 };
 
 export type LocalUsageDay = {
@@ -343,6 +1069,59 @@ export type LocalUsageSnapshot = {
   topModels: LocalUsageModel[];
 };
 
+export type UsageAnalyticsRange = "day" | "week" | "month" | "all";
+
+export type ToolUsage = {
+  tool: string;
+  calls: number;
+  errors: number;
+  denials: number;
+};
+
+export type UsageAnalytics = {
+  range: UsageAnalyticsRange;
+  since: number | null;
+  sessions: number;
+  turns: number;
+  averageTurnMs: number | null;
+  toolCalls: number;
+  tools: ToolUsage[];
+  permissionDenials: number;
+  permissionDenialRate: number;
+  edits: number;
+  editsAccepted: number;
+  editsRewound: number;
+};
+
+export type UsageHeatmap = {
+  range: UsageAnalyticsRange;
+  since: number | null;
+  /** `turns[day][hour]`, day 0 = Monday, in local time. */
+  turns: number[][];
+  messages: number[][];
+  maxTurns: number;
+  maxMessages: number;
+};
+
+export type WorkspaceActivityEntry = { timestamp: number } & (
+  | { kind: "commit"; sha: string; summary: string; author: string }
+  | {
+      kind: "thread";
+      threadId: string;
+      title: string | null;
+      messageCount: number | null;
+    }
+  | {
+      kind: "task";
+      threadId: string;
+      taskId: string;
+      subject: string;
+      status: string;
+    }
+  | { kind: "run"; runId: string; threadId: string; status: string }
+  | { kind: "file"; path: string; status: string }
+);
+
 export type TurnPlanStepStatus = "pending" | "inProgress" | "completed";
 
 export type TurnPlanStep = {
@@ -367,6 +1146,7 @@ export type ClaudeTask = {
   owner?: string;
   blocks: string[];
   blockedBy: string[];
+  dependsOn?: string[];
   metadata?: Record<string, unknown>;
 };
 
@@ -409,6 +1189,13 @@ export type QueuedMessage = {
   images?: string[];
 };
 
+export type ThreadDraft = {
+  workspaceId: string;
+  text: string;
+  attachments: string[];
+  updatedAt: number;
+};
+
 export type ModelOption = {
   id: string;
   model: string;
@@ -480,10 +1267,14 @@ export type DictationEvent =
   | { type: "state"; state: DictationSessionState }
   | { type: "level"; value: number }
   | { type: "transcript"; text: string }
+  | { type: "command"; command: DictationCommand; text: string }
   | { type: "error"; message: string }
   | { type: "canceled"; message: string };
 
+export type DictationCommand = "send" | "newline" | "cancel";
+
 export type DictationTranscript = {
   id: string;
   text: string;
+  command?: DictationCommand;
 };
@@ -1,3 +1,5 @@
+import type { DictationTranscript } from "../types";
+
 export type DictationInsertionResult = {
   nextText: string;
   nextCursor: number;
@@ -33,3 +35,27 @@ export function computeDictationInsertion(
   const nextCursor = start + insertText.length;
   return { nextText, nextCursor };
 }
+
+/**
+ * Insert a dictation result at the selection, then apply its "newline" or
+ * "cancel" command. "send" is left to the caller.
+ */
+export function applyDictationTranscript(
+  currentText: string,
+  transcript: DictationTranscript,
+  start: number,
+  end: number,
+): DictationInsertionResult {
+  if (transcript.command === "cancel") {
+    return { nextText: "", nextCursor: 0 };
+  }
+  const textToInsert = transcript.text.trim();
+  let { nextText, nextCursor } = textToInsert
+    ? computeDictationInsertion(currentText, textToInsert, start, end)
+    : { nextText: currentText, nextCursor: end };
+  if (transcript.command === "newline") {
+    nextText = `${nextText.slice(0, nextCursor)}\n${nextText.slice(nextCursor)}`;
+    nextCursor += 1;
+  }
+  return { nextText, nextCursor };
+}
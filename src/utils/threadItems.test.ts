@@ -103,6 +103,80 @@ describe("threadItems", () => {
     }
   });
 
+  it("keeps structured details on tool items", () => {
+    const item = buildConversationItem({
+      type: "commandExecution",
+      id: "toolu_2",
+      command: ["Read"],
+      status: "completed",
+      details: { kind: "read", path: "/repo/a.ts", lineStart: 3, lineEnd: 9, totalLines: "x" },
+    });
+    expect(item).toMatchObject({
+      kind: "tool",
+      details: { kind: "read", path: "/repo/a.ts", lineStart: 3, lineEnd: 9, totalLines: null },
+    });
+    const unknown = buildConversationItem({
+      type: "commandExecution",
+      id: "toolu_3",
+      command: ["Glob"],
+      details: { kind: "glob" },
+    });
+    expect(unknown && unknown.kind === "tool" ? unknown.details : null).toBeUndefined();
+  });
+
+  it("marks tool items whose live output was truncated", () => {
+    const item = buildConversationItem({
+      type: "commandExecution",
+      id: "toolu_1",
+      command: ["Bash"],
+      status: "completed",
+      aggregatedOutput: "partial",
+      outputTruncated: true,
+      outputBytes: 40000,
+    });
+    expect(item).toMatchObject({
+      kind: "tool",
+      output: "partial",
+      outputTruncated: true,
+      outputBytes: 40000,
+    });
+  });
+
+  it("shows per-path line stats on file change items", () => {
+    const item = buildConversationItem({
+      type: "fileChange",
+      id: "change-2",
+      status: "completed",
+      changes: [{ path: "src/foo.rs", kind: "modify", additions: 42, deletions: 7 }],
+    });
+    expect(item).toMatchObject({
+      kind: "tool",
+      detail: "M +42 −7 src/foo.rs",
+      changes: [{ path: "src/foo.rs", additions: 42, deletions: 7 }],
+    });
+  });
+
+  it("attaches cached tool result images to tool items", () => {
+    const item = buildConversationItem({
+      type: "mcpToolCall",
+      id: "tool-img",
+      server: "playwright",
+      tool: "browser_take_screenshot",
+      status: "completed",
+      result: "",
+      images: [
+        { path: "/cache/tool-images/abc.png", mediaType: "image/png", bytes: 12 },
+        { mediaType: "image/png" },
+      ],
+    });
+    expect(item).not.toBeNull();
+    if (item && item.kind === "tool") {
+      expect(item.images).toEqual([
+        { path: "/cache/tool-images/abc.png", mediaType: "image/png" },
+      ]);
+    }
+  });
+
   it("merges thread items preferring richer local tool output", () => {
     const remote: ConversationItem = {
       id: "tool-2",
@@ -183,6 +257,45 @@ describe("threadItems", () => {
     }
   });
 
+  it("renders todoList items as a checklist", () => {
+    const item = buildConversationItem({
+      type: "todoList",
+      id: "todo-1",
+      status: "completed",
+      todos: [
+        { content: "Write tests", status: "completed" },
+        { content: "Fix bug", status: "in_progress", activeForm: "Fixing bug" },
+        { content: "Ship it", status: "pending" },
+      ],
+    });
+    expect(item).not.toBeNull();
+    if (item && item.kind === "tool") {
+      expect(item.title).toBe("Todo list");
+      expect(item.detail).toBe("1/3 completed");
+      expect(item.output).toBe("[x] Write tests\n[~] Fix bug\n[ ] Ship it");
+      expect(item.todos?.[1]?.activeForm).toBe("Fixing bug");
+    }
+  });
+
+  it("renders reviewComment items with location and author", () => {
+    const item = buildConversationItem({
+      type: "reviewComment",
+      id: "turn-1-review-T1",
+      path: "src/lib.rs",
+      line: 12,
+      author: "ana",
+      body: "Handle the error here",
+      replies: 2,
+      status: "inProgress",
+    });
+    expect(item).not.toBeNull();
+    if (item && item.kind === "tool") {
+      expect(item.title).toBe("Review comment: src/lib.rs:12");
+      expect(item.detail).toBe("@ana · 2 replies");
+      expect(item.output).toBe("Handle the error here");
+    }
+  });
+
   describe("optimistic user message reconciliation", () => {
     it("drops optimistic user message when server has matching new message", () => {
       // Scenario: user sends "hi", switches threads, comes back
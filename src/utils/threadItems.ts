@@ -1,4 +1,4 @@
-import type { ConversationItem } from "../types";
+import type { ConversationItem, ToolDetails } from "../types";
 
 const MAX_ITEMS_PER_THREAD = 200;
 const MAX_ITEM_TEXT = 20000;
@@ -75,6 +75,56 @@ function normalizeStringList(value: unknown) {
   return single ? [single] : [];
 }
 
+function asNullableNumber(value: unknown) {
+  return typeof value === "number" && Number.isFinite(value) ? value : null;
+}
+
+function asNullableString(value: unknown) {
+  return typeof value === "string" && value ? value : null;
+}
+
+function parseToolDetails(value: unknown): ToolDetails | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+  const details = value as Record<string, unknown>;
+  switch (details.kind) {
+    case "bash":
+      return {
+        kind: "bash",
+        command: asString(details.command ?? ""),
+        description: asNullableString(details.description),
+        exitCode: asNullableNumber(details.exitCode),
+      };
+    case "grep":
+      return {
+        kind: "grep",
+        pattern: asString(details.pattern ?? ""),
+        path: asNullableString(details.path),
+        outputMode: asString(details.outputMode ?? "files_with_matches"),
+        matchCount: asNullableNumber(details.matchCount),
+        fileCount: asNullableNumber(details.fileCount),
+      };
+    case "read":
+      return {
+        kind: "read",
+        path: asString(details.path ?? ""),
+        lineStart: asNullableNumber(details.lineStart),
+        lineEnd: asNullableNumber(details.lineEnd),
+        totalLines: asNullableNumber(details.totalLines),
+      };
+    case "webFetch":
+      return {
+        kind: "webFetch",
+        url: asString(details.url ?? ""),
+        status: asNullableNumber(details.status),
+        statusText: asNullableString(details.statusText),
+      };
+    default:
+      return undefined;
+  }
+}
+
 function asRecord(value: unknown) {
   if (!value || typeof value !== "object" || Array.isArray(value)) {
     return null;
@@ -521,8 +571,44 @@ export function previewThreadName(text: string, fallback: string) {
   return trimmed;
 }
 
+function parseToolImages(value: unknown) {
+  if (!Array.isArray(value)) {
+    return [];
+  }
+  return value
+    .map((entry) => asRecord(entry))
+    .filter((entry): entry is Record<string, unknown> => Boolean(entry))
+    .map((entry) => ({
+      path: asString(entry.path ?? ""),
+      mediaType: asString(entry.mediaType ?? entry.media_type ?? "") || undefined,
+    }))
+    .filter((image) => image.path);
+}
+
 export function buildConversationItem(
   item: Record<string, unknown>,
+): ConversationItem | null {
+  const converted = buildItemWithoutImages(item);
+  if (!converted || converted.kind !== "tool") {
+    return converted;
+  }
+  const images = parseToolImages(item.images);
+  const withImages = images.length > 0 ? { ...converted, images } : converted;
+  const subagentThreadId = asString(item.subagentThreadId ?? "");
+  const linked = subagentThreadId ? { ...withImages, subagentThreadId } : withImages;
+  if (item.outputTruncated !== true) {
+    return linked;
+  }
+  const outputBytes = Number(item.outputBytes);
+  return {
+    ...linked,
+    outputTruncated: true,
+    outputBytes: Number.isFinite(outputBytes) ? outputBytes : undefined,
+  };
+}
+
+function buildItemWithoutImages(
+  item: Record<string, unknown>,
 ): ConversationItem | null {
   const type = asString(item.type);
   const id = asString(item.id);
@@ -564,6 +650,7 @@ export function buildConversationItem(
       toolInput,
       status: asString(item.status ?? ""),
       output: asString(item.aggregatedOutput ?? ""),
+      details: parseToolDetails(item.details),
     };
   }
   if (type === "fileChange") {
@@ -580,7 +667,13 @@ export function buildConversationItem(
               : "";
         const normalizedKind = kindType ? kindType.toLowerCase() : "";
         const diff = asString(change?.diff ?? "");
-        return { path, kind: normalizedKind || undefined, diff: diff || undefined };
+        return {
+          path,
+          kind: normalizedKind || undefined,
+          diff: diff || undefined,
+          additions: asNullableNumber(change?.additions) ?? undefined,
+          deletions: asNullableNumber(change?.deletions) ?? undefined,
+        };
       })
       .filter((change) => change.path);
     const formattedChanges = normalizedChanges
@@ -593,7 +686,11 @@ export function buildConversationItem(
               : change.kind
                 ? "M"
                 : "";
-        return [prefix, change.path].filter(Boolean).join(" ");
+        const stats =
+          change.additions !== undefined && change.deletions !== undefined
+            ? `+${change.additions} −${change.deletions}`
+            : "";
+        return [prefix, stats, change.path].filter(Boolean).join(" ");
       })
       .filter(Boolean);
     const paths = formattedChanges.join(", ");
@@ -657,6 +754,51 @@ export function buildConversationItem(
       output: outputParts.join("\n\n"),
     };
   }
+  if (type === "todoList") {
+    const todos = (Array.isArray(item.todos) ? item.todos : [])
+      .map((todo) => {
+        const record = asRecord(todo) ?? {};
+        const content = asString(record.content ?? "");
+        const status = asString(record.status ?? "pending");
+        const activeForm = asString(record.activeForm ?? "");
+        return { content, status, activeForm: activeForm || undefined };
+      })
+      .filter((todo) => todo.content);
+    const completed = todos.filter((todo) => todo.status === "completed").length;
+    const output = todos
+      .map((todo) => {
+        const marker =
+          todo.status === "completed" ? "[x]" : todo.status === "in_progress" ? "[~]" : "[ ]";
+        return `${marker} ${todo.content}`;
+      })
+      .join("\n");
+    return {
+      id,
+      kind: "tool",
+      toolType: type,
+      title: "Todo list",
+      detail: todos.length > 0 ? `${completed}/${todos.length} completed` : "",
+      status: asString(item.status ?? ""),
+      output,
+      todos,
+    };
+  }
+  if (type === "reviewComment") {
+    const path = asString(item.path ?? "");
+    const line = typeof item.line === "number" ? item.line : null;
+    const author = asString(item.author ?? "");
+    const replies = typeof item.replies === "number" ? item.replies : 0;
+    const detailParts = [author ? `@${author}` : "", replies > 0 ? `${replies} replies` : ""];
+    return {
+      id,
+      kind: "tool",
+      toolType: type,
+      title: `Review comment: ${line !== null ? `${path}:${line}` : path}`,
+      detail: detailParts.filter(Boolean).join(" · "),
+      status: asString(item.status ?? ""),
+      output: asString(item.body ?? ""),
+    };
+  }
   if (type === "webSearch") {
     const output = asString(item.aggregatedOutput ?? item.output ?? "");
     return {